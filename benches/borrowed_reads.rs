@@ -0,0 +1,129 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Compares reading large replies with [`Readable`], which copies their
+//! payloads, to reading them with [`ReadableRef`], which borrows them.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use xrb::{
+	visual::VisualId,
+	x11::{
+		reply::{
+			CaptureImage,
+			CaptureImageRef,
+			GetKeyboardMapping,
+			GetKeyboardMappingRef,
+			GetProperty,
+			GetPropertyRef,
+		},
+		request::{DataFormat, DataList},
+	},
+	Atom,
+	Keysym,
+};
+use xrbk::{Readable, ReadableRef, Writable};
+
+/// The size of the payload of each reply, in bytes.
+const PAYLOAD_SIZE: usize = 1 << 20;
+
+/// Writes the given `reply`, without the first byte that identifies it as a
+/// reply, since that is read before the reply itself.
+fn reply_bytes(reply: &impl Writable) -> Vec<u8> {
+	let mut bytes = vec![];
+	reply.write_to(&mut bytes).unwrap();
+
+	bytes.split_off(1)
+}
+
+fn capture_image(c: &mut Criterion) {
+	let bytes = reply_bytes(&CaptureImage {
+		sequence: 1,
+		depth: 24,
+		visual: Some(VisualId::new(0x21)),
+		data: vec![0xaa; PAYLOAD_SIZE],
+	});
+
+	let mut group = c.benchmark_group("CaptureImage");
+	group.throughput(Throughput::Bytes(bytes.len() as u64));
+
+	group.bench_function("owned", |b| {
+		b.iter_batched(
+			|| &bytes[..],
+			|mut buf| CaptureImage::read_from(black_box(&mut buf)).unwrap(),
+			BatchSize::SmallInput,
+		);
+	});
+	group.bench_function("borrowed", |b| {
+		b.iter_batched(
+			|| &bytes[..],
+			|mut buf| CaptureImageRef::read_ref(black_box(&mut buf)).unwrap(),
+			BatchSize::SmallInput,
+		);
+	});
+
+	group.finish();
+}
+
+fn get_property(c: &mut Criterion) {
+	let bytes = reply_bytes(&GetProperty {
+		sequence: 1,
+		format: Some(DataFormat::I32),
+		r#type: Some(Atom::new(6)),
+		bytes_remaining: 0,
+		value: DataList::I32(vec![-1; PAYLOAD_SIZE / 4]),
+	});
+
+	let mut group = c.benchmark_group("GetProperty");
+	group.throughput(Throughput::Bytes(bytes.len() as u64));
+
+	group.bench_function("owned", |b| {
+		b.iter_batched(
+			|| &bytes[..],
+			|mut buf| GetProperty::read_from(black_box(&mut buf)).unwrap(),
+			BatchSize::SmallInput,
+		);
+	});
+	group.bench_function("borrowed", |b| {
+		b.iter_batched(
+			|| &bytes[..],
+			|mut buf| GetPropertyRef::read_ref(black_box(&mut buf)).unwrap(),
+			BatchSize::SmallInput,
+		);
+	});
+
+	group.finish();
+}
+
+fn get_keyboard_mapping(c: &mut Criterion) {
+	// Every keycode from 8 to 255, each with 7 keysyms.
+	let bytes = reply_bytes(&GetKeyboardMapping {
+		sequence: 1,
+		mappings: (8..=255)
+			.map(|keycode| (0..7).map(|i| Keysym::new(keycode << 8 | i)).collect())
+			.collect(),
+	});
+
+	let mut group = c.benchmark_group("GetKeyboardMapping");
+	group.throughput(Throughput::Bytes(bytes.len() as u64));
+
+	group.bench_function("owned", |b| {
+		b.iter_batched(
+			|| &bytes[..],
+			|mut buf| GetKeyboardMapping::read_from(black_box(&mut buf)).unwrap(),
+			BatchSize::SmallInput,
+		);
+	});
+	group.bench_function("borrowed", |b| {
+		b.iter_batched(
+			|| &bytes[..],
+			|mut buf| GetKeyboardMappingRef::read_ref(black_box(&mut buf)).unwrap(),
+			BatchSize::SmallInput,
+		);
+	});
+
+	group.finish();
+}
+
+criterion_group!(benches, capture_image, get_property, get_keyboard_mapping);
+criterion_main!(benches);
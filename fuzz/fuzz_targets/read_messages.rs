@@ -0,0 +1,126 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Feeds arbitrary bytes into XRB's error, reply, and event readers, including
+//! those of the extensions it supports.
+//!
+//! Reading may fail, but it must never panic.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use xrb::{
+	big_requests,
+	composite,
+	damage,
+	randr,
+	shm,
+	x11::{self, reply},
+	xc_misc,
+	xfixes,
+};
+use xrbk::Readable;
+
+/// Reads the message type given by `$selector` from `$buf`, discarding the
+/// result.
+macro_rules! read_one_of {
+	($selector:expr, $buf:expr; $($value:pat => $Type:ty),+$(,)?) => {
+		match $selector {
+			$($value => drop(<$Type>::read_from($buf)),)+
+			_ => {},
+		}
+	};
+}
+
+fuzz_target!(|data: &[u8]| {
+	let Some((&kind, rest)) = data.split_first() else {
+		return;
+	};
+
+	match kind {
+		// Errors: any core error is read with its first byte of `0`.
+		0 => drop(x11::read_error(&mut &data[..])),
+
+		// Replies can't be identified from their bytes alone, so the first
+		// byte selects which reply to read.
+		1 => {
+			let Some((&selector, buf)) = rest.split_first() else {
+				return;
+			};
+
+			read_one_of!(selector, &mut &buf[..];
+				0 => reply::AllocateColor,
+				1 => reply::AllocateColorCells,
+				2 => reply::AllocateColorPlanes,
+				3 => reply::AllocateNamedColor,
+				4 => reply::CaptureImage,
+				5 => reply::ConvertCoordinates,
+				6 => reply::GetAtom,
+				7 => reply::GetAtomName,
+				8 => reply::GetButtonMapping,
+				9 => reply::GetCursorOptions,
+				10 => reply::GetFocus,
+				11 => reply::GetFontSearchDirectories,
+				12 => reply::GetGeometry,
+				13 => reply::GetKeyboardMapping,
+				14 => reply::GetKeyboardOptions,
+				15 => reply::GetModifierMapping,
+				16 => reply::GetMotionHistory,
+				17 => reply::GetNamedColor,
+				18 => reply::GetProperty,
+				19 => reply::GetScreenSaver,
+				20 => reply::GetSelectionOwner,
+				21 => reply::GetWindowAttributes,
+				22 => reply::GrabCursor,
+				23 => reply::GrabKeyboard,
+				24 => reply::ListExtensions,
+				25 => reply::ListFonts,
+				26 => reply::ListInstalledColormaps,
+				27 => reply::ListProperties,
+				28 => reply::QueryAccessControl,
+				29 => reply::QueryColors,
+				30 => reply::QueryCursorLocation,
+				31 => reply::QueryExtension,
+				32 => reply::QueryFont,
+				33 => reply::QueryIdealDimensions,
+				34 => reply::QueryKeyboard,
+				35 => reply::QueryTextExtents,
+				36 => reply::QueryWindowTree,
+				37 => reply::SetButtonMapping,
+				38 => reply::SetModifierMapping,
+
+				39 => big_requests::reply::EnableBigRequests,
+				40 => composite::reply::QueryVersion,
+				41 => damage::reply::QueryVersion,
+				42 => randr::reply::QueryVersion,
+				43 => randr::reply::GetScreenResources,
+				44 => randr::reply::GetOutputInfo,
+				45 => randr::reply::GetCrtcInfo,
+				46 => shm::reply::QueryVersion,
+				47 => shm::reply::CaptureImage,
+				48 => xc_misc::reply::GetVersion,
+				49 => xc_misc::reply::GetResourceIdRange,
+				50 => xc_misc::reply::GetResourceIdList,
+				51 => xfixes::reply::QueryVersion,
+			);
+		},
+
+		// Events with codes reserved for extensions: the extension's first
+		// event code depends on the X server, so the code selects which
+		// extension event to read, as well as being read as an unknown event.
+		64..=127 | 192..=255 => {
+			read_one_of!(kind & 0x7f, &mut &rest[..];
+				64 => randr::ScreenChangeNotify,
+				65 => damage::DamageNotify,
+				66 => shm::Completion,
+			);
+
+			drop(x11::read_event(&mut &data[..]));
+		},
+
+		// Any core event is read with its code. The most significant bit
+		// indicates that the event was sent with `SendEvent`.
+		_ => drop(x11::read_event(&mut &data[..])),
+	}
+});
@@ -0,0 +1,73 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Messages defined in the [BIG-REQUESTS extension]: [requests] and
+//! [replies].
+//!
+//! The BIG-REQUESTS extension allows a client to send [requests] longer than
+//! the core protocol's maximum of `u16::MAX` 4-byte units.
+//!
+//! [BIG-REQUESTS extension]: https://www.x.org/releases/X11R7.7/doc/bigreqsproto/bigreq.html
+//! [requests]: request
+//! [replies]: reply
+
+use crate::message::Extension;
+
+pub mod reply;
+pub mod request;
+
+/// The BIG-REQUESTS extension.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BigRequests;
+
+impl Extension for BigRequests {
+	const NAME: &'static str = "BIG-REQUESTS";
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::message::{ExtensionCodes, Request};
+	use xrbk::{Readable, Writable};
+
+	#[test]
+	fn test_enable_big_requests_layout() {
+		let codes = ExtensionCodes {
+			major_opcode: 133,
+			first_event: 0,
+			first_error: 0,
+		};
+
+		let mut bytes = vec![];
+		codes
+			.write_request(&request::EnableBigRequests, &mut bytes)
+			.unwrap();
+
+		assert_eq!(request::EnableBigRequests::MINOR_OPCODE, Some(0));
+		// The major opcode, then the minor opcode in the metabyte position.
+		assert_eq!(bytes, [133, 0, 0, 1]);
+	}
+
+	#[test]
+	fn test_enable_big_requests_reply_round_trip() {
+		let reply = reply::EnableBigRequests {
+			sequence: 1,
+			max_request_length: 0x003f_ffff,
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32);
+		assert_eq!(bytes[8..12], [0x00, 0x3f, 0xff, 0xff]);
+		assert_eq!(
+			reply::EnableBigRequests::read_from(&mut &bytes[1..]).unwrap(),
+			reply,
+		);
+	}
+}
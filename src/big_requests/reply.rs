@@ -0,0 +1,55 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [Replies] defined in the [BIG-REQUESTS extension].
+//!
+//! [Replies] are messages sent from the X server to an X client in response to
+//! a [request].
+//!
+//! [Replies]: Reply
+//! [request]: crate::message::Request
+//! [BIG-REQUESTS extension]: super
+
+extern crate self as xrb;
+
+use derivative::Derivative;
+
+use xrbk_macro::derive_xrb;
+
+use crate::{big_requests::request, message::Reply};
+
+derive_xrb! {
+	/// The [reply] to an [`EnableBigRequests` request].
+	///
+	/// [reply]: Reply
+	///
+	/// [`EnableBigRequests` request]: request::EnableBigRequests
+	#[doc(alias("BigReqEnable"))]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[derivative(Hash, PartialEq, Eq)]
+	pub struct EnableBigRequests: Reply for request::EnableBigRequests {
+		/// The sequence number identifying the [request] that generated this
+		/// [reply].
+		///
+		/// See [`Reply::sequence`] for more information.
+		///
+		/// [request]: crate::message::Request
+		/// [reply]: Reply
+		///
+		/// [`Reply::sequence`]: Reply::sequence
+		#[sequence]
+		#[derivative(Hash = "ignore", PartialEq = "ignore")]
+		pub sequence: u16,
+
+		/// The maximum length of a [request], in 4-byte units, that the X
+		/// server accepts with extended-length [requests] enabled.
+		///
+		/// [request]: crate::message::Request
+		/// [requests]: crate::message::Request
+		#[doc(alias("maximum_request_length"))]
+		pub max_request_length: u32,
+		[_; ..],
+	}
+}
@@ -0,0 +1,49 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [Requests] defined in the [BIG-REQUESTS extension].
+//!
+//! [Requests] are messages sent from an X client to the X server.
+//!
+//! The [major opcode] of these [requests] is assigned by the X server: they
+//! must be written with [`ExtensionCodes::write_request`].
+//!
+//! [Requests]: Request
+//! [requests]: Request
+//! [BIG-REQUESTS extension]: super
+//! [major opcode]: Request::MAJOR_OPCODE
+//! [`ExtensionCodes::write_request`]: crate::message::ExtensionCodes::write_request
+
+extern crate self as xrb;
+
+use xrbk_macro::derive_xrb;
+
+use crate::{
+	big_requests::reply,
+	message::{Request, UNASSIGNED_MAJOR_OPCODE},
+};
+
+derive_xrb! {
+	/// A [request] that enables the use of extended-length [requests] on
+	/// this connection.
+	///
+	/// Once enabled, a [request] longer than `u16::MAX` 4-byte units may be
+	/// sent by writing `0` in its length field, followed by its length as a
+	/// `u32`.
+	///
+	/// # Replies
+	/// This [request] generates an [`EnableBigRequests` reply].
+	///
+	/// [request]: Request
+	/// [requests]: Request
+	///
+	/// [`EnableBigRequests` reply]: reply::EnableBigRequests
+	#[doc(alias("BigReqEnable"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct EnableBigRequests: Request(
+		UNASSIGNED_MAJOR_OPCODE,
+		0,
+	) -> reply::EnableBigRequests;
+}
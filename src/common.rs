@@ -4,11 +4,13 @@
 
 extern crate self as xrb;
 
-use array_init::array_init;
+use std::time::Duration;
+
 use derive_more::{From, Into};
 use thiserror::Error;
 
 pub use atom::Atom;
+pub use keysym::Keysym;
 pub use mask::*;
 pub use res_id::*;
 pub use wrapper::*;
@@ -21,6 +23,7 @@ use xrbk::{
 	ReadError,
 	ReadError::FailedConversion,
 	ReadResult,
+	Readable,
 	ReadableWithContext,
 	Wrap,
 	Writable,
@@ -31,7 +34,9 @@ use xrbk_macro::{derive_xrb, new, unwrap, ConstantX11Size, Readable, Wrap, Writa
 
 use crate::unit::Px;
 
+pub mod area;
 pub mod atom;
+pub mod keysym;
 pub mod set;
 pub mod visual;
 
@@ -40,7 +45,8 @@ mod res_id;
 mod wrapper;
 
 /// Whether something is enabled or disabled.
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Toggle {
 	/// The thing is disabled.
 	Disabled,
@@ -49,7 +55,8 @@ pub enum Toggle {
 }
 
 /// Whether something is enabled, disabled, or the default is chosen.
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ToggleOrDefault {
 	/// The thing is disabled.
 	Disabled,
@@ -86,12 +93,70 @@ pub enum ToggleOrDefault {
 	Writable,
 	Wrap,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Timestamp(pub(crate) u32);
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+impl Timestamp {
+	/// The furthest a `Timestamp` may be after another for it to be considered
+	/// later, in milliseconds: half the range of a `u32` (about 24.9 days).
+	///
+	/// Any further, and it is considered to be earlier, with the time having
+	/// wrapped around.
+	const MAX_ELAPSED: u32 = i32::MAX.unsigned_abs();
+
+	/// Returns whether this `Timestamp` is later than the `other` `Timestamp`,
+	/// taking into account that the time wraps around.
+	///
+	/// A `Timestamp` up to about 24.9 days after `other` is considered to be
+	/// later. Equal timestamps are not later than each other.
+	#[must_use]
+	pub const fn is_after(self, other: Self) -> bool {
+		let elapsed = self.0.wrapping_sub(other.0);
+
+		elapsed != 0 && elapsed <= Self::MAX_ELAPSED
+	}
+
+	/// Returns the [`Duration`] elapsed between the `earlier` `Timestamp` and
+	/// this one, taking into account that the time wraps around.
+	///
+	/// Returns [`None`] if `earlier` is actually later than this `Timestamp`
+	/// (see [`is_after`]).
+	///
+	/// [`is_after`]: Timestamp::is_after
+	#[must_use]
+	pub fn duration_since(self, earlier: Self) -> Option<Duration> {
+		let elapsed = self.0.wrapping_sub(earlier.0);
+
+		(elapsed <= Self::MAX_ELAPSED).then(|| Duration::from_millis(elapsed.into()))
+	}
+
+	/// Returns the `Timestamp` the given `duration` after this one, wrapping
+	/// around if necessary.
+	///
+	/// Any part of `duration` shorter than a millisecond is ignored.
+	///
+	/// Returns [`None`] if `duration` is too long for the resulting
+	/// `Timestamp` to be considered later than this one (see [`is_after`]).
+	///
+	/// [`is_after`]: Timestamp::is_after
+	#[must_use]
+	pub fn checked_add(self, duration: Duration) -> Option<Self> {
+		match u32::try_from(duration.as_millis()) {
+			Ok(millis) if millis <= Self::MAX_ELAPSED => Some(Self(self.0.wrapping_add(millis))),
+
+			_ => None,
+		}
+	}
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BitGravity {
 	Forget,
-	Static,
 	NorthWest,
 	North,
 	NorthEast,
@@ -101,12 +166,13 @@ pub enum BitGravity {
 	SouthWest,
 	South,
 	SouthEast,
+	Static,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WindowGravity {
 	Unmap,
-	Static,
 	NorthWest,
 	North,
 	NorthEast,
@@ -116,6 +182,47 @@ pub enum WindowGravity {
 	SouthWest,
 	South,
 	SouthEast,
+	Static,
+}
+
+impl TryFrom<u32> for WindowGravity {
+	type Error = ReadError;
+
+	fn try_from(value: u32) -> ReadResult<Self> {
+		match value {
+			0 => Ok(Self::Unmap),
+			1 => Ok(Self::NorthWest),
+			2 => Ok(Self::North),
+			3 => Ok(Self::NorthEast),
+			4 => Ok(Self::West),
+			5 => Ok(Self::Center),
+			6 => Ok(Self::East),
+			7 => Ok(Self::SouthWest),
+			8 => Ok(Self::South),
+			9 => Ok(Self::SouthEast),
+			10 => Ok(Self::Static),
+
+			other => Err(ReadError::UnrecognizedDiscriminant(other as usize)),
+		}
+	}
+}
+
+impl From<WindowGravity> for u32 {
+	fn from(gravity: WindowGravity) -> Self {
+		match gravity {
+			WindowGravity::Unmap => 0,
+			WindowGravity::NorthWest => 1,
+			WindowGravity::North => 2,
+			WindowGravity::NorthEast => 3,
+			WindowGravity::West => 4,
+			WindowGravity::Center => 5,
+			WindowGravity::East => 6,
+			WindowGravity::SouthWest => 7,
+			WindowGravity::South => 8,
+			WindowGravity::SouthEast => 9,
+			WindowGravity::Static => 10,
+		}
+	}
 }
 
 // The `derive_xrb!` attribute here is used to write the discriminants as `u16`.
@@ -124,6 +231,7 @@ derive_xrb! {
 	///
 	/// [window]: Window
 	#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub enum WindowClass: u16 {
 		/// A [window] that both receives input and has a visual output (i.e. what
 		/// one would normally consider a window to be).
@@ -168,13 +276,15 @@ derive_xrb! {
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MaintainContents {
 	Never,
 	WhenMapped,
 	Always,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GrabMode {
 	Normal,
 	Grab,
@@ -184,34 +294,31 @@ pub enum GrabMode {
 /// Whether a grab causes a freeze in [event] processing.
 ///
 /// [event]: crate::message::Event
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FreezeMode {
-	/// [Event] processing is not frozen.
-	///
-	/// [Event]: crate::message::Event
-	#[doc(alias = "Asynchronous")]
-	Unfrozen,
-
 	/// [Event] processing is frozen.
 	///
 	/// [Event]: crate::message::Event
 	#[doc(alias = "Synchronous")]
 	Frozen,
+
+	/// [Event] processing is not frozen.
+	///
+	/// [Event]: crate::message::Event
+	#[doc(alias = "Asynchronous")]
+	Unfrozen,
 }
 
 /// The status of an attempted grab.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GrabStatus {
 	/// The grab was successful.
 	Success,
 
 	/// Another client already had a grab.
 	AlreadyGrabbed,
-	/// Another client already had an active grab and had frozen [event]
-	/// processing.
-	///
-	/// [event]: crate::message::Event
-	Frozen,
 	/// The given time was either earlier than the previous grab, or later than
 	/// the X server's [current time].
 	///
@@ -223,9 +330,15 @@ pub enum GrabStatus {
 	///
 	/// [window]: Window
 	NotViewable,
+	/// Another client already had an active grab and had frozen [event]
+	/// processing.
+	///
+	/// [event]: crate::message::Event
+	Frozen,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StackMode {
 	Above,
 	Below,
@@ -234,34 +347,61 @@ pub enum StackMode {
 	Opposite,
 }
 
-#[derive(
-	Copy,
-	Clone,
-	Eq,
-	PartialEq,
-	Hash,
-	Debug,
-	From,
-	Into,
-	// `new` const fn
-	new,
-	// XRBK traits
-	X11Size,
-	ConstantX11Size,
-	Readable,
-	Writable,
-	Wrap,
-)]
-pub struct Keysym(pub(crate) u32);
-
-impl Keysym {
-	pub const NO_SYMBOL: Self = Self::new(0x0000_0000);
-	pub const VOID_SYMBOL: Self = Self::new(0x00ff_ffff);
+/// One of the eight modifiers to which [keycodes] can be mapped.
+///
+/// [keycodes]: Keycode
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Modifier {
+	/// The shift modifier.
+	Shift,
+	/// The lock modifier, typically mapped to <kbd>Caps Lock</kbd>.
+	#[doc(alias = "CapsLock")]
+	Lock,
+	/// The control modifier.
+	#[doc(alias = "Ctrl")]
+	Control,
+
+	/// The Mod1 modifier.
+	Mod1,
+	/// The Mod2 modifier.
+	Mod2,
+	/// The Mod3 modifier.
+	Mod3,
+	/// The Mod4 modifier.
+	Mod4,
+	/// The Mod5 modifier.
+	Mod5,
+}
 
-	/// Returns the raw contained keysym value.
+impl Modifier {
+	/// All eight modifiers, in the order in which they are listed in the
+	/// X11 protocol.
+	pub const ALL: [Self; 8] = [
+		Self::Shift,
+		Self::Lock,
+		Self::Control,
+		Self::Mod1,
+		Self::Mod2,
+		Self::Mod3,
+		Self::Mod4,
+		Self::Mod5,
+	];
+
+	/// Returns the [`ModifierMask`] bit for this modifier.
 	#[must_use]
-	pub const fn unwrap(&self) -> u32 {
-		self.0
+	pub const fn mask(self) -> ModifierMask {
+		match self {
+			Self::Shift => ModifierMask::SHIFT,
+			Self::Lock => ModifierMask::LOCK,
+			Self::Control => ModifierMask::CONTROL,
+
+			Self::Mod1 => ModifierMask::MOD_1,
+			Self::Mod2 => ModifierMask::MOD_2,
+			Self::Mod3 => ModifierMask::MOD_3,
+			Self::Mod4 => ModifierMask::MOD_4,
+			Self::Mod5 => ModifierMask::MOD_5,
+		}
 	}
 }
 
@@ -283,6 +423,11 @@ impl Keysym {
 	Writable,
 	Wrap,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Keycode(pub(crate) u8);
 
 impl Keycode {
@@ -312,6 +457,11 @@ impl Keycode {
 	Writable,
 	Wrap,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Button(pub(crate) u8);
 
 impl Button {
@@ -339,9 +489,19 @@ impl Button {
 	Writable,
 	Wrap,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Char8(pub(crate) u8);
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug, From, Into, X11Size, Writable)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct String8(Vec<Char8>);
 
 impl String8 {
@@ -350,6 +510,12 @@ impl String8 {
 		self.0.len()
 	}
 
+	/// Returns a slice of the string's characters.
+	#[must_use]
+	pub fn as_slice(&self) -> &[Char8] {
+		&self.0
+	}
+
 	#[must_use]
 	pub fn is_empty(&self) -> bool {
 		self.0.is_empty()
@@ -381,6 +547,7 @@ derive_xrb! {
 		Readable,
 		Writable,
 	)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct LengthString8 {
 		#[allow(clippy::cast_possible_truncation)]
 		let len: u8 = string => string.len() as u8,
@@ -408,6 +575,7 @@ derive_xrb! {
 	Readable,
 	Writable,
 )]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Char16(pub(crate) u8, pub(crate) u8);
 
 impl From<u16> for Char16 {
@@ -427,6 +595,11 @@ impl From<Char16> for u16 {
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug, From, Into, X11Size, Writable)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct String16(Vec<Char16>);
 
 impl String16 {
@@ -471,6 +644,7 @@ impl ReadableWithContext for String16 {
 	Readable,
 	Writable,
 )]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Coords {
 	/// The x coordinate, measured in pixels.
 	pub x: Px<i16>,
@@ -497,6 +671,7 @@ pub struct Coords {
 	Readable,
 	Writable,
 )]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Dimensions {
 	/// The width, measured in pixels.
 	pub width: Px<u16>,
@@ -508,6 +683,7 @@ pub struct Dimensions {
 #[derive(
 	Copy, Clone, Eq, PartialEq, Hash, Debug, new, X11Size, ConstantX11Size, Readable, Writable,
 )]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rectangle {
 	/// The x-coordinate of the upper left corner of the `Rectangle`.
 	pub x: Px<i16>,
@@ -535,6 +711,7 @@ impl Rectangle {
 
 /// Same as a [`Rectangle`], but with unsigned coordinates.
 #[derive(Clone, Eq, PartialEq, Hash, Debug, new, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Region {
 	/// The x-coordinate of the upper left corner of the `Region`.
 	pub x: Px<u16>,
@@ -549,6 +726,7 @@ pub struct Region {
 
 /// A circular or elliptical arc.
 #[derive(Clone, Eq, PartialEq, Hash, Debug, new, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Arc {
 	/// The [rectangle] which contains the arc.
 	///
@@ -578,6 +756,7 @@ pub struct Arc {
 ///
 /// This is used in [`Host`].
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HostFamily {
 	/// An IPv4 address.
 	///
@@ -610,6 +789,7 @@ pub struct NonAsciiEncoding;
 ///
 /// This is used for [`HostAddress::ServerInterpreted`].
 #[derive(Clone, Debug, Hash, PartialEq, Eq, X11Size, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
 pub struct AsciiString(Vec<u8>);
 
 impl AsciiString {
@@ -645,6 +825,13 @@ impl AsciiString {
 	}
 }
 
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AsciiString {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		Self::new(Vec::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+	}
+}
+
 impl ReadableWithContext for AsciiString {
 	type Context = usize;
 
@@ -657,6 +844,7 @@ impl ReadableWithContext for AsciiString {
 ///
 /// [host]: Host
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HostAddress {
 	/// An IPv4 address.
 	Ipv4([u8; 4]),
@@ -732,23 +920,17 @@ impl ReadableWithContext for HostAddress {
 		let buf = &mut buf.take(*length);
 
 		match family {
-			HostFamily::Ipv4 => Ok(Self::Ipv4([
-				buf.get_u8(),
-				buf.get_u8(),
-				buf.get_u8(),
-				buf.get_u8(),
-			])),
-			HostFamily::DecNet => Ok(Self::DecNet([buf.get_u8(), buf.get_u8()])),
-			HostFamily::Chaos => Ok(Self::Chaos([buf.get_u8(), buf.get_u8()])),
+			HostFamily::Ipv4 => Ok(Self::Ipv4(<_>::read_from(buf)?)),
+			HostFamily::DecNet => Ok(Self::DecNet(<_>::read_from(buf)?)),
+			HostFamily::Chaos => Ok(Self::Chaos(<_>::read_from(buf)?)),
 
 			HostFamily::ServerInterpreted => {
 				let mut address_type = vec![];
 				let mut address_value = vec![];
 
 				while buf.has_remaining() {
-					match buf.get_u8() {
+					match u8::read_from(buf)? {
 						0 => {
-							buf.advance(1);
 							address_value = <Vec<u8>>::read_with(buf, &buf.remaining())?;
 
 							break;
@@ -771,7 +953,7 @@ impl ReadableWithContext for HostAddress {
 				}
 			},
 
-			HostFamily::Ipv6 => Ok(Self::Ipv6(array_init(|_| buf.get_u8()))),
+			HostFamily::Ipv6 => Ok(Self::Ipv6(<_>::read_from(buf)?)),
 		}
 	}
 }
@@ -806,6 +988,7 @@ derive_xrb! {
 	///
 	/// [`ChangeHosts` request]: crate::x11::request::ChangeHosts
 	#[derive(Clone, Eq, PartialEq, Hash, Debug, new, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct Host {
 		// The `address`' family.
 		let family: HostFamily = address => address.family(),
@@ -822,3 +1005,122 @@ derive_xrb! {
 		[_; address => pad(address)],
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_timestamp_is_after() {
+		let time = Timestamp::new(1000);
+
+		assert!(Timestamp::new(1001).is_after(time));
+		assert!(!time.is_after(Timestamp::new(1001)));
+
+		// Equal timestamps are not after each other.
+		assert!(!time.is_after(time));
+		assert!(!time.is_after(Timestamp::new(1000)));
+
+		// More than half the range later is considered to be earlier.
+		assert!(Timestamp::new(1000 + i32::MAX.unsigned_abs()).is_after(time));
+		assert!(!Timestamp::new(1001 + i32::MAX.unsigned_abs()).is_after(time));
+	}
+
+	#[test]
+	fn test_timestamp_is_after_wrap() {
+		let before_wrap = Timestamp::new(u32::MAX - 5);
+		let after_wrap = Timestamp::new(10);
+
+		assert!(after_wrap.is_after(before_wrap));
+		assert!(!before_wrap.is_after(after_wrap));
+
+		assert!(Timestamp::new(0).is_after(Timestamp::new(u32::MAX)));
+		assert!(!Timestamp::new(u32::MAX).is_after(Timestamp::new(0)));
+	}
+
+	#[test]
+	fn test_timestamp_duration_since() {
+		let time = Timestamp::new(1000);
+
+		assert_eq!(
+			Timestamp::new(1250).duration_since(time),
+			Some(Duration::from_millis(250)),
+		);
+		assert_eq!(time.duration_since(Timestamp::new(1250)), None);
+		assert_eq!(time.duration_since(time), Some(Duration::ZERO));
+
+		// Straddling the wrap.
+		assert_eq!(
+			Timestamp::new(10).duration_since(Timestamp::new(u32::MAX - 5)),
+			Some(Duration::from_millis(16)),
+		);
+		assert_eq!(
+			Timestamp::new(u32::MAX - 5).duration_since(Timestamp::new(10)),
+			None
+		);
+	}
+
+	#[test]
+	fn test_timestamp_checked_add() {
+		let time = Timestamp::new(u32::MAX - 5);
+
+		assert_eq!(time.checked_add(Duration::ZERO), Some(time));
+		assert_eq!(
+			time.checked_add(Duration::from_millis(5)),
+			Some(Timestamp::new(u32::MAX)),
+		);
+
+		// Wraps around.
+		let later = time.checked_add(Duration::from_millis(16)).unwrap();
+		assert_eq!(later, Timestamp::new(10));
+		assert!(later.is_after(time));
+
+		// Sub-millisecond parts are ignored.
+		assert_eq!(
+			time.checked_add(Duration::from_micros(5_999)),
+			Some(Timestamp::new(u32::MAX)),
+		);
+
+		// Too long to be considered later.
+		let max = Duration::from_millis(i32::MAX.unsigned_abs().into());
+		assert!(time.checked_add(max).unwrap().is_after(time));
+		assert_eq!(time.checked_add(max + Duration::from_millis(1)), None);
+		assert_eq!(time.checked_add(Duration::MAX), None);
+	}
+
+	#[test]
+	fn test_currentable_time_from_timestamp() {
+		let time = Timestamp::new(1000);
+
+		assert_eq!(CurrentableTime::from(time), CurrentableTime::Other(time));
+		assert_eq!(
+			CurrentableTime::from(Timestamp::new(0)),
+			CurrentableTime::CurrentTime
+		);
+
+		assert_eq!(
+			CurrentableTime::Other(time).unwrap_or_current(Timestamp::new(5)),
+			time
+		);
+		assert_eq!(
+			CurrentableTime::CurrentTime.unwrap_or_current(Timestamp::new(5)),
+			Timestamp::new(5)
+		);
+	}
+
+	#[test]
+	fn test_window_gravity_u32() {
+		for value in 0..=10 {
+			let gravity = WindowGravity::try_from(value).unwrap();
+			assert_eq!(u32::from(gravity), value);
+
+			// The conversion agrees with the gravity's encoding.
+			let mut bytes = vec![];
+			gravity.write_to(&mut bytes).unwrap();
+			assert_eq!(bytes, [u8::try_from(value).unwrap()]);
+		}
+
+		assert!(WindowGravity::try_from(11).is_err());
+		assert!(WindowGravity::try_from(0x100).is_err());
+	}
+}
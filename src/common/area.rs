@@ -0,0 +1,570 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [`Area`], a set of pixels made up of rectangles.
+
+use crate::{unit::Px, Coords, Rectangle, Region};
+
+/// A horizontal band of an [`Area`].
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+struct Band {
+	/// The y-coordinate of the top of the band (inclusive).
+	top: i32,
+	/// The y-coordinate of the bottom of the band (exclusive).
+	bottom: i32,
+
+	/// The `(left, right)` spans of pixels covered by the band, with `left`
+	/// inclusive and `right` exclusive.
+	///
+	/// Spans are sorted and never empty, overlapping, or adjacent.
+	spans: Vec<(i32, i32)>,
+}
+
+/// A set operation used to combine two [`Area`]s.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+enum Operation {
+	Union,
+	Intersect,
+	Subtract,
+}
+
+impl Operation {
+	/// Returns whether a pixel is in the result of this operation, given
+	/// whether it is in the `first` and `second` operands.
+	const fn contains(self, first: bool, second: bool) -> bool {
+		match self {
+			Self::Union => first || second,
+			Self::Intersect => first && second,
+			Self::Subtract => first && !second,
+		}
+	}
+}
+
+/// Returns whether any of the given `spans` contain `x`.
+fn spans_contain(spans: &[(i32, i32)], x: i32) -> bool {
+	spans.iter().any(|&(left, right)| left <= x && x < right)
+}
+
+/// Combines the `first` and `second` sorted lists of spans with the given
+/// `operation`.
+fn combine_spans(
+	first: &[(i32, i32)], second: &[(i32, i32)], operation: Operation,
+) -> Vec<(i32, i32)> {
+	let mut edges: Vec<i32> = first
+		.iter()
+		.chain(second)
+		.flat_map(|&span| <[i32; 2]>::from(span))
+		.collect();
+	edges.sort_unstable();
+	edges.dedup();
+
+	let mut spans: Vec<(i32, i32)> = Vec::new();
+
+	for edge in edges.windows(2) {
+		let (left, right) = (edge[0], edge[1]);
+
+		if operation.contains(spans_contain(first, left), spans_contain(second, left)) {
+			match spans.last_mut() {
+				// Adjacent spans are joined.
+				Some(last) if last.1 == left => last.1 = right,
+				_ => spans.push((left, right)),
+			}
+		}
+	}
+
+	spans
+}
+
+/// Converts `value` to an `i16`, clamping it to the range of an `i16`.
+fn clamp_i16(value: i32) -> i16 {
+	i16::try_from(value).unwrap_or(if value < 0 { i16::MIN } else { i16::MAX })
+}
+
+/// Converts `value` to a `u16`, clamping it to the range of a `u16`.
+fn clamp_u16(value: i32) -> u16 {
+	u16::try_from(value).unwrap_or(if value < 0 { u16::MIN } else { u16::MAX })
+}
+
+/// A set of pixels made up of rectangles, such as the damaged or exposed area
+/// of a [window].
+///
+/// An `Area` is stored as horizontal bands, each covering a sorted list of
+/// spans. Its representation is canonical: two `Area`s are equal if and only
+/// if they cover the same pixels. Rectangles with no area cover no pixels.
+///
+/// `Area`s can be created from the lists of [`Rectangle`]s used by the XFixes
+/// [`SetRegion` request] and the lists of [`Region`]s collected by an
+/// [`ExposureCollector`], and converted back with [`rectangles`] and
+/// [`regions`].
+///
+/// [window]: crate::Window
+/// [`SetRegion` request]: crate::xfixes::request::SetRegion
+/// [`ExposureCollector`]: crate::x11::event::ExposureCollector
+///
+/// [`rectangles`]: Area::rectangles
+/// [`regions`]: Area::regions
+#[doc(alias("Region", "RegionSet"))]
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Area {
+	/// The bands of the `Area`.
+	///
+	/// Bands are sorted from top to bottom and never overlap. Adjacent bands
+	/// never have the same spans: such bands are joined.
+	bands: Vec<Band>,
+}
+
+impl Area {
+	/// Creates a new, empty `Area`.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self { bands: Vec::new() }
+	}
+
+	/// Creates an `Area` covering the rectangle between the given edges.
+	fn from_edges(left: i32, top: i32, right: i32, bottom: i32) -> Self {
+		if left >= right || top >= bottom {
+			return Self::new();
+		}
+
+		Self {
+			bands: vec![Band {
+				top,
+				bottom,
+				spans: vec![(left, right)],
+			}],
+		}
+	}
+
+	/// Returns whether this `Area` covers no pixels.
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.bands.is_empty()
+	}
+
+	/// Returns whether the pixel at the given `point` is within this `Area`.
+	#[must_use]
+	pub fn contains_point(&self, point: Coords) -> bool {
+		let (x, y) = (i32::from(point.x.0), i32::from(point.y.0));
+
+		self.spans_at(y)
+			.is_some_and(|spans| spans_contain(spans, x))
+	}
+
+	/// Returns the smallest [`Rectangle`] containing this `Area`, or [`None`]
+	/// if it is empty.
+	///
+	/// Parts of the `Area` beyond the range of a [`Rectangle`]'s coordinates
+	/// are clipped.
+	#[must_use]
+	pub fn extents(&self) -> Option<Rectangle> {
+		let (first, last) = (self.bands.first()?, self.bands.last()?);
+
+		let left = self
+			.bands
+			.iter()
+			.filter_map(|band| band.spans.first())
+			.map(|span| span.0)
+			.min()?;
+		let right = self
+			.bands
+			.iter()
+			.filter_map(|band| band.spans.last())
+			.map(|span| span.1)
+			.max()?;
+
+		Self::from_edges(left, first.top, right, last.bottom)
+			.rectangles()
+			.pop()
+	}
+
+	/// Returns the union of this `Area` and the `other` `Area`: the pixels
+	/// within either.
+	#[must_use]
+	pub fn union(&self, other: &Self) -> Self {
+		self.combine(other, Operation::Union)
+	}
+
+	/// Returns the intersection of this `Area` and the `other` `Area`: the
+	/// pixels within both.
+	#[must_use]
+	pub fn intersect(&self, other: &Self) -> Self {
+		self.combine(other, Operation::Intersect)
+	}
+
+	/// Returns this `Area` with the pixels within the `other` `Area` removed.
+	#[must_use]
+	pub fn subtract(&self, other: &Self) -> Self {
+		self.combine(other, Operation::Subtract)
+	}
+
+	/// Returns this `Area` as a list of non-overlapping [`Rectangle`]s, from
+	/// top to bottom and left to right.
+	///
+	/// Parts of the `Area` beyond the range of a [`Rectangle`]'s coordinates
+	/// are clipped.
+	#[must_use]
+	pub fn rectangles(&self) -> Vec<Rectangle> {
+		let min = i32::from(i16::MIN);
+		let max = i32::from(i16::MAX) + 1;
+
+		self.intersect(&Self::from_edges(min, min, max, max))
+			.edges()
+			.map(|(left, top, right, bottom)| Rectangle {
+				x: Px(clamp_i16(left)),
+				y: Px(clamp_i16(top)),
+				width: Px(clamp_u16(right - left)),
+				height: Px(clamp_u16(bottom - top)),
+			})
+			.collect()
+	}
+
+	/// Returns this `Area` as a list of non-overlapping [`Region`]s, from top
+	/// to bottom and left to right.
+	///
+	/// Parts of the `Area` beyond the range of a [`Region`]'s coordinates -
+	/// including negative coordinates - are clipped.
+	#[must_use]
+	pub fn regions(&self) -> Vec<Region> {
+		let max = i32::from(u16::MAX) + 1;
+
+		self.intersect(&Self::from_edges(0, 0, max, max))
+			.edges()
+			.map(|(left, top, right, bottom)| Region {
+				x: Px(clamp_u16(left)),
+				y: Px(clamp_u16(top)),
+				width: Px(clamp_u16(right - left)),
+				height: Px(clamp_u16(bottom - top)),
+			})
+			.collect()
+	}
+
+	/// Returns the `(left, top, right, bottom)` edges of each span of each
+	/// band.
+	fn edges(&self) -> impl Iterator<Item = (i32, i32, i32, i32)> + '_ {
+		self.bands.iter().flat_map(|band| {
+			band.spans
+				.iter()
+				.map(|&(left, right)| (left, band.top, right, band.bottom))
+		})
+	}
+
+	/// Returns the spans of the band containing the given `y` coordinate.
+	fn spans_at(&self, y: i32) -> Option<&[(i32, i32)]> {
+		self.bands
+			.iter()
+			.find(|band| band.top <= y && y < band.bottom)
+			.map(|band| &*band.spans)
+	}
+
+	/// Combines this `Area` and the `other` `Area` with the given `operation`.
+	fn combine(&self, other: &Self, operation: Operation) -> Self {
+		let mut edges: Vec<i32> = self
+			.bands
+			.iter()
+			.chain(&other.bands)
+			.flat_map(|band| [band.top, band.bottom])
+			.collect();
+		edges.sort_unstable();
+		edges.dedup();
+
+		let mut bands: Vec<Band> = Vec::new();
+
+		for edge in edges.windows(2) {
+			let (top, bottom) = (edge[0], edge[1]);
+
+			let spans = combine_spans(
+				self.spans_at(top).unwrap_or_default(),
+				other.spans_at(top).unwrap_or_default(),
+				operation,
+			);
+
+			if spans.is_empty() {
+				continue;
+			}
+
+			match bands.last_mut() {
+				// Adjacent bands with the same spans are joined.
+				Some(last) if last.bottom == top && last.spans == spans => last.bottom = bottom,
+				_ => bands.push(Band { top, bottom, spans }),
+			}
+		}
+
+		Self { bands }
+	}
+}
+
+impl From<Rectangle> for Area {
+	fn from(rectangle: Rectangle) -> Self {
+		let (x, y) = (i32::from(rectangle.x.0), i32::from(rectangle.y.0));
+
+		Self::from_edges(
+			x,
+			y,
+			x + i32::from(rectangle.width.0),
+			y + i32::from(rectangle.height.0),
+		)
+	}
+}
+
+impl From<Region> for Area {
+	fn from(region: Region) -> Self {
+		let (x, y) = (i32::from(region.x.0), i32::from(region.y.0));
+
+		Self::from_edges(
+			x,
+			y,
+			x + i32::from(region.width.0),
+			y + i32::from(region.height.0),
+		)
+	}
+}
+
+impl FromIterator<Rectangle> for Area {
+	fn from_iter<I: IntoIterator<Item = Rectangle>>(rectangles: I) -> Self {
+		rectangles
+			.into_iter()
+			.fold(Self::new(), |area, rectangle| area.union(&rectangle.into()))
+	}
+}
+
+impl FromIterator<Region> for Area {
+	fn from_iter<I: IntoIterator<Item = Region>>(regions: I) -> Self {
+		regions
+			.into_iter()
+			.fold(Self::new(), |area, region| area.union(&region.into()))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// The size of the brute-force bitmaps, starting from `(-OFFSET, -OFFSET)`.
+	const SIZE: usize = 32;
+	const OFFSET: i16 = 8;
+
+	type Bitmap = [[bool; SIZE]; SIZE];
+
+	/// A deterministic xorshift random number generator.
+	struct Rng(u32);
+
+	impl Rng {
+		fn below(&mut self, max: u16) -> u16 {
+			self.0 ^= self.0 << 13;
+			self.0 ^= self.0 >> 17;
+			self.0 ^= self.0 << 5;
+
+			u16::try_from(self.0 % u32::from(max)).unwrap()
+		}
+
+		/// Returns a small rectangle, which may have no area.
+		fn rectangle(&mut self) -> Rectangle {
+			let x = i16::try_from(self.below(20)).unwrap() - 4;
+			let y = i16::try_from(self.below(20)).unwrap() - 4;
+
+			Rectangle::new(Px(x), Px(y), Px(self.below(8)), Px(self.below(8)))
+		}
+
+		fn rectangles(&mut self) -> Vec<Rectangle> {
+			let len = self.below(6);
+
+			(0..len).map(|_| self.rectangle()).collect()
+		}
+	}
+
+	/// Returns the pixels covered by the given `rectangles`.
+	fn brute_force(rectangles: &[Rectangle]) -> Bitmap {
+		let mut bitmap = [[false; SIZE]; SIZE];
+
+		for rectangle in rectangles {
+			let x = usize::try_from(rectangle.x.0 + OFFSET).unwrap();
+			let y = usize::try_from(rectangle.y.0 + OFFSET).unwrap();
+
+			for row in &mut bitmap[y..(y + usize::from(rectangle.height.0))] {
+				for pixel in &mut row[x..(x + usize::from(rectangle.width.0))] {
+					*pixel = true;
+				}
+			}
+		}
+
+		bitmap
+	}
+
+	/// Returns the pixels covered by the given `area`.
+	fn bitmap(area: &Area) -> Bitmap {
+		let mut bitmap = [[false; SIZE]; SIZE];
+
+		for (y, row) in (-OFFSET..).zip(&mut bitmap) {
+			for (x, pixel) in (-OFFSET..).zip(row) {
+				*pixel = area.contains_point(Coords::new(Px(x), Px(y)));
+			}
+		}
+
+		bitmap
+	}
+
+	fn combine_bitmaps(first: &Bitmap, second: &Bitmap, operation: Operation) -> Bitmap {
+		let mut bitmap = [[false; SIZE]; SIZE];
+
+		for (y, row) in bitmap.iter_mut().enumerate() {
+			for (x, pixel) in row.iter_mut().enumerate() {
+				*pixel = operation.contains(first[y][x], second[y][x]);
+			}
+		}
+
+		bitmap
+	}
+
+	/// Asserts that the bands and spans of the `area` are well-formed.
+	fn assert_well_formed(area: &Area) {
+		for band in &area.bands {
+			assert!(band.top < band.bottom, "empty band in {area:?}");
+			assert!(!band.spans.is_empty(), "band without spans in {area:?}");
+
+			for &(left, right) in &band.spans {
+				assert!(left < right, "empty span in {area:?}");
+			}
+			for spans in band.spans.windows(2) {
+				assert!(
+					spans[0].1 < spans[1].0,
+					"overlapping or adjacent spans in {area:?}"
+				);
+			}
+		}
+
+		for bands in area.bands.windows(2) {
+			assert!(
+				bands[0].bottom <= bands[1].top,
+				"overlapping bands in {area:?}"
+			);
+			assert!(
+				bands[0].bottom < bands[1].top || bands[0].spans != bands[1].spans,
+				"unjoined bands in {area:?}"
+			);
+		}
+	}
+
+	#[test]
+	fn test_area_matches_brute_force() {
+		let mut rng = Rng(0x2545_f491);
+
+		for _ in 0..500 {
+			let (first, second) = (rng.rectangles(), rng.rectangles());
+			let (first_area, second_area): (Area, Area) = (
+				first.iter().copied().collect(),
+				second.iter().copied().collect(),
+			);
+			let (first_bitmap, second_bitmap) = (brute_force(&first), brute_force(&second));
+
+			assert_well_formed(&first_area);
+			assert_eq!(bitmap(&first_area), first_bitmap, "{first:?}");
+			assert_eq!(first_area.is_empty(), first_bitmap == [[false; SIZE]; SIZE]);
+
+			for (operation, area) in [
+				(Operation::Union, first_area.union(&second_area)),
+				(Operation::Intersect, first_area.intersect(&second_area)),
+				(Operation::Subtract, first_area.subtract(&second_area)),
+			] {
+				assert_well_formed(&area);
+				assert_eq!(
+					bitmap(&area),
+					combine_bitmaps(&first_bitmap, &second_bitmap, operation),
+					"{operation:?} of {first:?} and {second:?}",
+				);
+
+				// Converting to rectangles and back gives the same area.
+				let rectangles = area.rectangles();
+				assert_eq!(bitmap(&area), brute_force(&rectangles));
+				assert_eq!(rectangles.into_iter().collect::<Area>(), area);
+			}
+
+			// The representation is canonical.
+			assert_eq!(
+				first_area.union(&second_area),
+				second_area.union(&first_area)
+			);
+		}
+	}
+
+	#[test]
+	fn test_area_degenerate_rectangles() {
+		let rectangle = |x, y, width, height| Rectangle::new(Px(x), Px(y), Px(width), Px(height));
+
+		// Rectangles with no area.
+		let area: Area = [rectangle(0, 0, 0, 5), rectangle(0, 0, 5, 0)]
+			.into_iter()
+			.collect();
+		assert!(area.is_empty());
+		assert_eq!(area.extents(), None);
+		assert!(area.rectangles().is_empty());
+
+		// Duplicate rectangles.
+		let area: Area = [rectangle(1, 1, 4, 4), rectangle(1, 1, 4, 4)]
+			.into_iter()
+			.collect();
+		assert_eq!(area.rectangles(), [rectangle(1, 1, 4, 4)]);
+
+		// Rectangles which touch but don't overlap are joined.
+		let area: Area = [
+			rectangle(0, 0, 4, 4),
+			rectangle(4, 0, 4, 4),
+			rectangle(0, 4, 8, 2),
+		]
+		.into_iter()
+		.collect();
+		assert_eq!(area.rectangles(), [rectangle(0, 0, 8, 6)]);
+
+		// Rectangles which only touch at a corner.
+		let area: Area = [rectangle(0, 0, 4, 4), rectangle(4, 4, 4, 4)]
+			.into_iter()
+			.collect();
+		assert_eq!(
+			area.rectangles(),
+			[rectangle(0, 0, 4, 4), rectangle(4, 4, 4, 4)]
+		);
+		assert_eq!(area.extents(), Some(rectangle(0, 0, 8, 8)));
+
+		// Subtracting an area from itself.
+		assert!(area.subtract(&area).is_empty());
+	}
+
+	#[test]
+	fn test_area_regions() {
+		let area: Area = [
+			Region::new(Px(0), Px(0), Px(10), Px(2)),
+			Region::new(Px(4), Px(2), Px(2), Px(2)),
+		]
+		.into_iter()
+		.collect();
+
+		assert_eq!(
+			area.rectangles(),
+			[
+				Rectangle::new(Px(0), Px(0), Px(10), Px(2)),
+				Rectangle::new(Px(4), Px(2), Px(2), Px(2)),
+			],
+		);
+
+		// Negative coordinates are clipped from regions.
+		let area = area.union(&Rectangle::new(Px(-4), Px(-4), Px(8), Px(2)).into());
+		assert_eq!(
+			area.regions(),
+			[
+				Region::new(Px(0), Px(0), Px(10), Px(2)),
+				Region::new(Px(4), Px(2), Px(2), Px(2)),
+			],
+		);
+
+		// Coordinates beyond the range of a rectangle are clipped.
+		let area = Area::from(Region::new(Px(32_000), Px(0), Px(2_000), Px(1)));
+		assert_eq!(
+			area.rectangles(),
+			[Rectangle::new(Px(32_000), Px(0), Px(768), Px(1))],
+		);
+		assert_eq!(
+			area.regions(),
+			[Region::new(Px(32_000), Px(0), Px(2_000), Px(1))],
+		);
+	}
+}
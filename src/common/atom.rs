@@ -26,6 +26,11 @@ use xrbk_macro::{ConstantX11Size, Readable, Wrap, Writable, X11Size};
 	Writable,
 	Wrap,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Atom(u32);
 
 impl Atom {
@@ -0,0 +1,1033 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [`Keysym`]s, predefined keysym `const`s, and [keyboard mapping]
+//! resolution.
+//!
+//! [keyboard mapping]: KeyboardMapping
+
+use derive_more::{From, Into};
+use xrbk_macro::{new, ConstantX11Size, Readable, Wrap, Writable, X11Size};
+
+use crate::{x11::reply, Keycode, Modifier, ModifierMask};
+
+/// A symbol representing the meaning of a key on a keyboard.
+///
+/// The mapping of [keycodes] to `Keysym`s is given by a [`KeyboardMapping`].
+///
+/// [keycodes]: Keycode
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	From,
+	Into,
+	// `new` const fn
+	new,
+	// XRBK traits
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	Wrap,
+)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
+pub struct Keysym(pub(crate) u32);
+
+/// The offset added to a Unicode code point to form its `Keysym`.
+const UNICODE_OFFSET: u32 = 0x0100_0000;
+
+/// Returns the only `char` in `chars`, or [`None`] if there is not exactly one.
+fn single_char(mut chars: impl Iterator<Item = char>) -> Option<char> {
+	match (chars.next(), chars.next()) {
+		(Some(char), None) => Some(char),
+		_ => None,
+	}
+}
+
+impl Keysym {
+	/// No `Keysym` is mapped.
+	pub const NO_SYMBOL: Self = Self::new(0x0000_0000);
+	/// A `Keysym` which is explicitly void.
+	pub const VOID_SYMBOL: Self = Self::new(0x00ff_ffff);
+
+	/// Returns the raw contained keysym value.
+	#[must_use]
+	pub const fn unwrap(&self) -> u32 {
+		self.0
+	}
+
+	/// Returns the `Keysym` corresponding to the given `char`, if there is
+	/// one.
+	///
+	/// Latin-1 characters map directly to the `Keysym` with the same value.
+	/// The control characters which have a dedicated TTY function `Keysym`
+	/// (e.g. `'\r'` for [`Keysym::RETURN`]) map to that `Keysym`. All other
+	/// characters map to the Unicode `Keysym` range - that is, `0x01000000`
+	/// plus their code point.
+	///
+	/// Control characters without a dedicated `Keysym` return [`None`].
+	///
+	/// # Examples
+	/// ```
+	/// use xrb::Keysym;
+	///
+	/// assert_eq!(Keysym::from_char('a'), Some(Keysym::LOWER_A));
+	/// assert_eq!(Keysym::from_char('\r'), Some(Keysym::RETURN));
+	/// assert_eq!(Keysym::from_char('€'), Some(Keysym::new(0x0100_20ac)));
+	/// ```
+	#[must_use]
+	pub const fn from_char(char: char) -> Option<Self> {
+		let code = char as u32;
+
+		match code {
+			0x08 => Some(Self::BACKSPACE),
+			0x09 => Some(Self::TAB),
+			0x0a => Some(Self::LINEFEED),
+			0x0b => Some(Self::CLEAR),
+			0x0d => Some(Self::RETURN),
+			0x1b => Some(Self::ESCAPE),
+			0x7f => Some(Self::DELETE),
+
+			0x20..=0x7e | 0xa0..=0xff => Some(Self::new(code)),
+			0x00..=0x1f | 0x80..=0x9f => None,
+
+			code => Some(Self::new(UNICODE_OFFSET + code)),
+		}
+	}
+
+	/// Returns the `char` which this `Keysym` represents, if there is one.
+	///
+	/// This is the inverse of [`Keysym::from_char`], with the addition that
+	/// keypad `Keysym`s which produce characters (e.g. [`Keysym::KP_ADD`])
+	/// are also converted.
+	///
+	/// # Examples
+	/// ```
+	/// use xrb::Keysym;
+	///
+	/// assert_eq!(Keysym::A.to_char(), Some('A'));
+	/// assert_eq!(Keysym::KP_5.to_char(), Some('5'));
+	/// assert_eq!(Keysym::new(0x0100_03bb).to_char(), Some('λ'));
+	/// assert_eq!(Keysym::SHIFT_L.to_char(), None);
+	/// ```
+	#[must_use]
+	pub const fn to_char(self) -> Option<char> {
+		match self.0 {
+			0x20..=0x7e | 0xa0..=0xff => char::from_u32(self.0),
+
+			0xff08 => Some('\x08'),
+			0xff09 | 0xff89 => Some('\t'),
+			0xff0a => Some('\n'),
+			0xff0b => Some('\x0b'),
+			0xff0d | 0xff8d => Some('\r'),
+			0xff1b => Some('\x1b'),
+			0xffff => Some('\x7f'),
+
+			0xff80 => Some(' '),
+			0xffbd => Some('='),
+			0xffaa => Some('*'),
+			0xffab => Some('+'),
+			0xffac => Some(','),
+			0xffad => Some('-'),
+			0xffae => Some('.'),
+			0xffaf => Some('/'),
+			// `KP_0` through `KP_9`.
+			#[allow(clippy::cast_possible_truncation)]
+			code @ 0xffb0..=0xffb9 => Some((b'0' + (code - 0xffb0) as u8) as char),
+
+			code @ 0x0100_0100..=0x0110_ffff => char::from_u32(code - UNICODE_OFFSET),
+
+			_ => None,
+		}
+	}
+
+	/// Whether this is a keypad `Keysym`.
+	///
+	/// This includes both the standard keypad `Keysym`s (`KP_SPACE` through
+	/// `KP_EQUAL`) and the vendor-private keypad range.
+	#[must_use]
+	pub const fn is_keypad(self) -> bool {
+		matches!(self.0, 0xff80..=0xffbd | 0x1100_0000..=0x1100_ffff)
+	}
+
+	/// Whether this is a modifier `Keysym`, such as [`Keysym::SHIFT_L`] or
+	/// [`Keysym::NUM_LOCK`].
+	#[must_use]
+	pub const fn is_modifier(self) -> bool {
+		matches!(self.0, 0xffe1..=0xffee | 0xff7e | 0xff7f | 0xfe01..=0xfe13)
+	}
+
+	/// Returns the lowercase and uppercase forms of this `Keysym`, in that
+	/// order.
+	///
+	/// If this `Keysym` has no distinct lowercase and uppercase forms, both
+	/// elements are this `Keysym`.
+	///
+	/// Case conversion is defined for Latin-1 `Keysym`s and for those Unicode
+	/// `Keysym`s with a single-character lowercase and uppercase mapping.
+	#[must_use]
+	pub fn convert_case(self) -> (Self, Self) {
+		match self.0 {
+			// `A` through `Z`, and Latin-1 uppercase letters excluding
+			// `multiply`.
+			upper @ (0x41..=0x5a | 0xc0..=0xd6 | 0xd8..=0xde) => (Self(upper + 0x20), self),
+			// `a` through `z`, and Latin-1 lowercase letters excluding
+			// `division`.
+			lower @ (0x61..=0x7a | 0xe0..=0xf6 | 0xf8..=0xfe) => (self, Self(lower - 0x20)),
+			// The uppercase form of `ydiaeresis` is not in Latin-1: it is the
+			// Latin-9 `Ydiaeresis`.
+			0xff => (self, Self(0x13be)),
+			0x13be => (Self(0xff), self),
+
+			0x0100_0100..=0x0110_ffff => self.to_char().map_or((self, self), |char| {
+				(
+					single_char(char.to_lowercase()).map_or(self, Self::from_unicode),
+					single_char(char.to_uppercase()).map_or(self, Self::from_unicode),
+				)
+			}),
+
+			_ => (self, self),
+		}
+	}
+
+	/// Whether this `Keysym` is a lowercase letter with a distinct uppercase
+	/// form.
+	#[must_use]
+	pub fn is_lowercase(self) -> bool {
+		let (lower, upper) = self.convert_case();
+
+		self == lower && lower != upper
+	}
+
+	/// Whether this `Keysym` is an uppercase letter with a distinct lowercase
+	/// form.
+	#[must_use]
+	pub fn is_uppercase(self) -> bool {
+		let (lower, upper) = self.convert_case();
+
+		self == upper && lower != upper
+	}
+
+	/// Returns the uppercase form of this `Keysym`.
+	///
+	/// If this `Keysym` has no uppercase form, it is returned unchanged.
+	#[must_use]
+	pub fn to_uppercase(self) -> Self {
+		self.convert_case().1
+	}
+
+	/// Returns the lowercase form of this `Keysym`.
+	///
+	/// If this `Keysym` has no lowercase form, it is returned unchanged.
+	#[must_use]
+	pub fn to_lowercase(self) -> Self {
+		self.convert_case().0
+	}
+
+	/// Converts a `char` to a `Keysym` in the same way as
+	/// [`Keysym::from_char`], falling back to the Unicode range rather than
+	/// returning [`None`].
+	fn from_unicode(char: char) -> Self {
+		Self::from_char(char).unwrap_or(Self(UNICODE_OFFSET + char as u32))
+	}
+}
+
+macro_rules! keysyms {
+	(
+		$(
+			$(#[$attr:meta])*
+			$KEYSYM:ident: $name:literal = $value:expr
+		),*$(,)?
+	) => {
+		impl Keysym {
+			$(
+				#[doc = concat!("The `", $name, "` keysym (`XK_", $name, "`).")]
+				$(#[$attr])*
+				pub const $KEYSYM: Self = Self::new($value);
+			)*
+		}
+	};
+}
+
+// Latin-1 {{{
+keysyms! {
+	SPACE: "space" = 0x0020,
+	EXCLAM: "exclam" = 0x0021,
+	QUOTEDBL: "quotedbl" = 0x0022,
+	NUMBERSIGN: "numbersign" = 0x0023,
+	DOLLAR: "dollar" = 0x0024,
+	PERCENT: "percent" = 0x0025,
+	AMPERSAND: "ampersand" = 0x0026,
+	APOSTROPHE: "apostrophe" = 0x0027,
+	PARENLEFT: "parenleft" = 0x0028,
+	PARENRIGHT: "parenright" = 0x0029,
+	ASTERISK: "asterisk" = 0x002a,
+	PLUS: "plus" = 0x002b,
+	COMMA: "comma" = 0x002c,
+	MINUS: "minus" = 0x002d,
+	PERIOD: "period" = 0x002e,
+	SLASH: "slash" = 0x002f,
+	DIGIT_0: "0" = 0x0030,
+	DIGIT_1: "1" = 0x0031,
+	DIGIT_2: "2" = 0x0032,
+	DIGIT_3: "3" = 0x0033,
+	DIGIT_4: "4" = 0x0034,
+	DIGIT_5: "5" = 0x0035,
+	DIGIT_6: "6" = 0x0036,
+	DIGIT_7: "7" = 0x0037,
+	DIGIT_8: "8" = 0x0038,
+	DIGIT_9: "9" = 0x0039,
+	COLON: "colon" = 0x003a,
+	SEMICOLON: "semicolon" = 0x003b,
+	LESS: "less" = 0x003c,
+	EQUAL: "equal" = 0x003d,
+	GREATER: "greater" = 0x003e,
+	QUESTION: "question" = 0x003f,
+	AT: "at" = 0x0040,
+	A: "A" = 0x0041,
+	B: "B" = 0x0042,
+	C: "C" = 0x0043,
+	D: "D" = 0x0044,
+	E: "E" = 0x0045,
+	F: "F" = 0x0046,
+	G: "G" = 0x0047,
+	H: "H" = 0x0048,
+	I: "I" = 0x0049,
+	J: "J" = 0x004a,
+	K: "K" = 0x004b,
+	L: "L" = 0x004c,
+	M: "M" = 0x004d,
+	N: "N" = 0x004e,
+	O: "O" = 0x004f,
+	P: "P" = 0x0050,
+	Q: "Q" = 0x0051,
+	R: "R" = 0x0052,
+	S: "S" = 0x0053,
+	T: "T" = 0x0054,
+	U: "U" = 0x0055,
+	V: "V" = 0x0056,
+	W: "W" = 0x0057,
+	X: "X" = 0x0058,
+	Y: "Y" = 0x0059,
+	Z: "Z" = 0x005a,
+	BRACKETLEFT: "bracketleft" = 0x005b,
+	BACKSLASH: "backslash" = 0x005c,
+	BRACKETRIGHT: "bracketright" = 0x005d,
+	ASCIICIRCUM: "asciicircum" = 0x005e,
+	UNDERSCORE: "underscore" = 0x005f,
+	GRAVE: "grave" = 0x0060,
+	LOWER_A: "a" = 0x0061,
+	LOWER_B: "b" = 0x0062,
+	LOWER_C: "c" = 0x0063,
+	LOWER_D: "d" = 0x0064,
+	LOWER_E: "e" = 0x0065,
+	LOWER_F: "f" = 0x0066,
+	LOWER_G: "g" = 0x0067,
+	LOWER_H: "h" = 0x0068,
+	LOWER_I: "i" = 0x0069,
+	LOWER_J: "j" = 0x006a,
+	LOWER_K: "k" = 0x006b,
+	LOWER_L: "l" = 0x006c,
+	LOWER_M: "m" = 0x006d,
+	LOWER_N: "n" = 0x006e,
+	LOWER_O: "o" = 0x006f,
+	LOWER_P: "p" = 0x0070,
+	LOWER_Q: "q" = 0x0071,
+	LOWER_R: "r" = 0x0072,
+	LOWER_S: "s" = 0x0073,
+	LOWER_T: "t" = 0x0074,
+	LOWER_U: "u" = 0x0075,
+	LOWER_V: "v" = 0x0076,
+	LOWER_W: "w" = 0x0077,
+	LOWER_X: "x" = 0x0078,
+	LOWER_Y: "y" = 0x0079,
+	LOWER_Z: "z" = 0x007a,
+	BRACELEFT: "braceleft" = 0x007b,
+	BAR: "bar" = 0x007c,
+	BRACERIGHT: "braceright" = 0x007d,
+	ASCIITILDE: "asciitilde" = 0x007e,
+	NOBREAKSPACE: "nobreakspace" = 0x00a0,
+	EXCLAMDOWN: "exclamdown" = 0x00a1,
+	CENT: "cent" = 0x00a2,
+	STERLING: "sterling" = 0x00a3,
+	CURRENCY: "currency" = 0x00a4,
+	YEN: "yen" = 0x00a5,
+	BROKENBAR: "brokenbar" = 0x00a6,
+	SECTION: "section" = 0x00a7,
+	DIAERESIS: "diaeresis" = 0x00a8,
+	COPYRIGHT: "copyright" = 0x00a9,
+	ORDFEMININE: "ordfeminine" = 0x00aa,
+	GUILLEMOTLEFT: "guillemotleft" = 0x00ab,
+	NOTSIGN: "notsign" = 0x00ac,
+	HYPHEN: "hyphen" = 0x00ad,
+	REGISTERED: "registered" = 0x00ae,
+	MACRON: "macron" = 0x00af,
+	DEGREE: "degree" = 0x00b0,
+	PLUSMINUS: "plusminus" = 0x00b1,
+	TWOSUPERIOR: "twosuperior" = 0x00b2,
+	THREESUPERIOR: "threesuperior" = 0x00b3,
+	ACUTE: "acute" = 0x00b4,
+	MU: "mu" = 0x00b5,
+	PARAGRAPH: "paragraph" = 0x00b6,
+	PERIODCENTERED: "periodcentered" = 0x00b7,
+	CEDILLA: "cedilla" = 0x00b8,
+	ONESUPERIOR: "onesuperior" = 0x00b9,
+	MASCULINE: "masculine" = 0x00ba,
+	GUILLEMOTRIGHT: "guillemotright" = 0x00bb,
+	ONEQUARTER: "onequarter" = 0x00bc,
+	ONEHALF: "onehalf" = 0x00bd,
+	THREEQUARTERS: "threequarters" = 0x00be,
+	QUESTIONDOWN: "questiondown" = 0x00bf,
+	A_GRAVE: "Agrave" = 0x00c0,
+	A_ACUTE: "Aacute" = 0x00c1,
+	A_CIRCUMFLEX: "Acircumflex" = 0x00c2,
+	A_TILDE: "Atilde" = 0x00c3,
+	A_DIAERESIS: "Adiaeresis" = 0x00c4,
+	A_RING: "Aring" = 0x00c5,
+	AE: "AE" = 0x00c6,
+	C_CEDILLA: "Ccedilla" = 0x00c7,
+	E_GRAVE: "Egrave" = 0x00c8,
+	E_ACUTE: "Eacute" = 0x00c9,
+	E_CIRCUMFLEX: "Ecircumflex" = 0x00ca,
+	E_DIAERESIS: "Ediaeresis" = 0x00cb,
+	I_GRAVE: "Igrave" = 0x00cc,
+	I_ACUTE: "Iacute" = 0x00cd,
+	I_CIRCUMFLEX: "Icircumflex" = 0x00ce,
+	I_DIAERESIS: "Idiaeresis" = 0x00cf,
+	ETH: "ETH" = 0x00d0,
+	N_TILDE: "Ntilde" = 0x00d1,
+	O_GRAVE: "Ograve" = 0x00d2,
+	O_ACUTE: "Oacute" = 0x00d3,
+	O_CIRCUMFLEX: "Ocircumflex" = 0x00d4,
+	O_TILDE: "Otilde" = 0x00d5,
+	O_DIAERESIS: "Odiaeresis" = 0x00d6,
+	MULTIPLY: "multiply" = 0x00d7,
+	O_SLASH: "Oslash" = 0x00d8,
+	U_GRAVE: "Ugrave" = 0x00d9,
+	U_ACUTE: "Uacute" = 0x00da,
+	U_CIRCUMFLEX: "Ucircumflex" = 0x00db,
+	U_DIAERESIS: "Udiaeresis" = 0x00dc,
+	Y_ACUTE: "Yacute" = 0x00dd,
+	THORN: "THORN" = 0x00de,
+	SSHARP: "ssharp" = 0x00df,
+	LOWER_A_GRAVE: "agrave" = 0x00e0,
+	LOWER_A_ACUTE: "aacute" = 0x00e1,
+	LOWER_A_CIRCUMFLEX: "acircumflex" = 0x00e2,
+	LOWER_A_TILDE: "atilde" = 0x00e3,
+	LOWER_A_DIAERESIS: "adiaeresis" = 0x00e4,
+	LOWER_A_RING: "aring" = 0x00e5,
+	LOWER_AE: "ae" = 0x00e6,
+	LOWER_C_CEDILLA: "ccedilla" = 0x00e7,
+	LOWER_E_GRAVE: "egrave" = 0x00e8,
+	LOWER_E_ACUTE: "eacute" = 0x00e9,
+	LOWER_E_CIRCUMFLEX: "ecircumflex" = 0x00ea,
+	LOWER_E_DIAERESIS: "ediaeresis" = 0x00eb,
+	LOWER_I_GRAVE: "igrave" = 0x00ec,
+	LOWER_I_ACUTE: "iacute" = 0x00ed,
+	LOWER_I_CIRCUMFLEX: "icircumflex" = 0x00ee,
+	LOWER_I_DIAERESIS: "idiaeresis" = 0x00ef,
+	LOWER_ETH: "eth" = 0x00f0,
+	LOWER_N_TILDE: "ntilde" = 0x00f1,
+	LOWER_O_GRAVE: "ograve" = 0x00f2,
+	LOWER_O_ACUTE: "oacute" = 0x00f3,
+	LOWER_O_CIRCUMFLEX: "ocircumflex" = 0x00f4,
+	LOWER_O_TILDE: "otilde" = 0x00f5,
+	LOWER_O_DIAERESIS: "odiaeresis" = 0x00f6,
+	DIVISION: "division" = 0x00f7,
+	LOWER_O_SLASH: "oslash" = 0x00f8,
+	LOWER_U_GRAVE: "ugrave" = 0x00f9,
+	LOWER_U_ACUTE: "uacute" = 0x00fa,
+	LOWER_U_CIRCUMFLEX: "ucircumflex" = 0x00fb,
+	LOWER_U_DIAERESIS: "udiaeresis" = 0x00fc,
+	LOWER_Y_ACUTE: "yacute" = 0x00fd,
+	LOWER_THORN: "thorn" = 0x00fe,
+	LOWER_Y_DIAERESIS: "ydiaeresis" = 0x00ff,
+}
+// }}}
+
+// TTY function keys {{{
+keysyms! {
+	BACKSPACE: "BackSpace" = 0xff08,
+	TAB: "Tab" = 0xff09,
+	LINEFEED: "Linefeed" = 0xff0a,
+	CLEAR: "Clear" = 0xff0b,
+	RETURN: "Return" = 0xff0d,
+	PAUSE: "Pause" = 0xff13,
+	SCROLL_LOCK: "Scroll_Lock" = 0xff14,
+	SYS_REQ: "Sys_Req" = 0xff15,
+	ESCAPE: "Escape" = 0xff1b,
+	DELETE: "Delete" = 0xffff,
+}
+// }}}
+
+// Cursor control & motion {{{
+keysyms! {
+	HOME: "Home" = 0xff50,
+	LEFT: "Left" = 0xff51,
+	UP: "Up" = 0xff52,
+	RIGHT: "Right" = 0xff53,
+	DOWN: "Down" = 0xff54,
+	#[doc(alias = "PRIOR")]
+	PAGE_UP: "Page_Up" = 0xff55,
+	#[doc(alias = "NEXT")]
+	PAGE_DOWN: "Page_Down" = 0xff56,
+	END: "End" = 0xff57,
+	BEGIN: "Begin" = 0xff58,
+}
+// }}}
+
+// Miscellaneous functions {{{
+keysyms! {
+	SELECT: "Select" = 0xff60,
+	PRINT: "Print" = 0xff61,
+	EXECUTE: "Execute" = 0xff62,
+	INSERT: "Insert" = 0xff63,
+	UNDO: "Undo" = 0xff65,
+	REDO: "Redo" = 0xff66,
+	MENU: "Menu" = 0xff67,
+	FIND: "Find" = 0xff68,
+	CANCEL: "Cancel" = 0xff69,
+	HELP: "Help" = 0xff6a,
+	BREAK: "Break" = 0xff6b,
+	MODE_SWITCH: "Mode_switch" = 0xff7e,
+	NUM_LOCK: "Num_Lock" = 0xff7f,
+}
+// }}}
+
+// Keypad {{{
+keysyms! {
+	KP_SPACE: "KP_Space" = 0xff80,
+	KP_TAB: "KP_Tab" = 0xff89,
+	KP_ENTER: "KP_Enter" = 0xff8d,
+	KP_F1: "KP_F1" = 0xff91,
+	KP_F2: "KP_F2" = 0xff92,
+	KP_F3: "KP_F3" = 0xff93,
+	KP_F4: "KP_F4" = 0xff94,
+	KP_HOME: "KP_Home" = 0xff95,
+	KP_LEFT: "KP_Left" = 0xff96,
+	KP_UP: "KP_Up" = 0xff97,
+	KP_RIGHT: "KP_Right" = 0xff98,
+	KP_DOWN: "KP_Down" = 0xff99,
+	KP_PAGE_UP: "KP_Page_Up" = 0xff9a,
+	KP_PAGE_DOWN: "KP_Page_Down" = 0xff9b,
+	KP_END: "KP_End" = 0xff9c,
+	KP_BEGIN: "KP_Begin" = 0xff9d,
+	KP_INSERT: "KP_Insert" = 0xff9e,
+	KP_DELETE: "KP_Delete" = 0xff9f,
+	KP_MULTIPLY: "KP_Multiply" = 0xffaa,
+	KP_ADD: "KP_Add" = 0xffab,
+	KP_SEPARATOR: "KP_Separator" = 0xffac,
+	KP_SUBTRACT: "KP_Subtract" = 0xffad,
+	KP_DECIMAL: "KP_Decimal" = 0xffae,
+	KP_DIVIDE: "KP_Divide" = 0xffaf,
+	KP_0: "KP_0" = 0xffb0,
+	KP_1: "KP_1" = 0xffb1,
+	KP_2: "KP_2" = 0xffb2,
+	KP_3: "KP_3" = 0xffb3,
+	KP_4: "KP_4" = 0xffb4,
+	KP_5: "KP_5" = 0xffb5,
+	KP_6: "KP_6" = 0xffb6,
+	KP_7: "KP_7" = 0xffb7,
+	KP_8: "KP_8" = 0xffb8,
+	KP_9: "KP_9" = 0xffb9,
+	KP_EQUAL: "KP_Equal" = 0xffbd,
+}
+// }}}
+
+// Function keys {{{
+keysyms! {
+	F1: "F1" = 0xffbe,
+	F2: "F2" = 0xffbf,
+	F3: "F3" = 0xffc0,
+	F4: "F4" = 0xffc1,
+	F5: "F5" = 0xffc2,
+	F6: "F6" = 0xffc3,
+	F7: "F7" = 0xffc4,
+	F8: "F8" = 0xffc5,
+	F9: "F9" = 0xffc6,
+	F10: "F10" = 0xffc7,
+	F11: "F11" = 0xffc8,
+	F12: "F12" = 0xffc9,
+}
+// }}}
+
+// Modifiers {{{
+keysyms! {
+	SHIFT_L: "Shift_L" = 0xffe1,
+	SHIFT_R: "Shift_R" = 0xffe2,
+	CONTROL_L: "Control_L" = 0xffe3,
+	CONTROL_R: "Control_R" = 0xffe4,
+	CAPS_LOCK: "Caps_Lock" = 0xffe5,
+	SHIFT_LOCK: "Shift_Lock" = 0xffe6,
+	META_L: "Meta_L" = 0xffe7,
+	META_R: "Meta_R" = 0xffe8,
+	ALT_L: "Alt_L" = 0xffe9,
+	ALT_R: "Alt_R" = 0xffea,
+	SUPER_L: "Super_L" = 0xffeb,
+	SUPER_R: "Super_R" = 0xffec,
+	HYPER_L: "Hyper_L" = 0xffed,
+	HYPER_R: "Hyper_R" = 0xffee,
+	ISO_LEVEL3_SHIFT: "ISO_Level3_Shift" = 0xfe03,
+}
+// }}}
+
+/// How the `Lock` modifier is interpreted when resolving [`Keysym`]s.
+///
+/// The `Lock` modifier is interpreted as [`CapsLock`] if a [keycode] mapped to
+/// [`Keysym::CAPS_LOCK`] is attached to the `Lock` modifier, and as
+/// [`ShiftLock`] if a [keycode] mapped to [`Keysym::SHIFT_LOCK`] is attached to
+/// it. If both apply, it is interpreted as [`CapsLock`].
+///
+/// [keycode]: Keycode
+/// [`CapsLock`]: LockInterpretation::CapsLock
+/// [`ShiftLock`]: LockInterpretation::ShiftLock
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LockInterpretation {
+	/// The `Lock` modifier has no effect on [`Keysym`] resolution.
+	#[default]
+	Ignored,
+
+	/// The `Lock` modifier converts lowercase alphabetic [`Keysym`]s to their
+	/// uppercase forms.
+	CapsLock,
+	/// The `Lock` modifier acts as though the `Shift` modifier were held.
+	ShiftLock,
+}
+
+/// The interpretation of the modifiers relevant to [`Keysym`] resolution.
+///
+/// Which modifiers act as `NumLock` and `Mode_switch` is not fixed by the
+/// protocol: it depends on which modifier the [keycodes] mapped to
+/// [`Keysym::NUM_LOCK`] and [`Keysym::MODE_SWITCH`] are attached to. See
+/// [`KeyboardMapping::modifier_interpretation`].
+///
+/// [keycodes]: Keycode
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModifierInterpretation {
+	/// How the `Lock` modifier is interpreted.
+	pub lock: LockInterpretation,
+
+	/// The modifiers which act as `NumLock`.
+	///
+	/// This is empty if no modifier acts as `NumLock`.
+	pub num_lock: ModifierMask,
+	/// The modifiers which act as `Mode_switch`, selecting the second group of
+	/// [`Keysym`]s.
+	///
+	/// This is empty if no modifier acts as `Mode_switch`.
+	pub mode_switch: ModifierMask,
+}
+
+/// The mapping of [keycodes] to [`Keysym`]s.
+///
+/// This is constructed from the mappings returned in a
+/// [`GetKeyboardMapping` reply] and implements the rules for choosing a
+/// [`Keysym`] given the current modifier state.
+///
+/// [keycodes]: Keycode
+/// [`GetKeyboardMapping` reply]: reply::GetKeyboardMapping
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyboardMapping {
+	first_keycode: Keycode,
+	mappings: Vec<Vec<Keysym>>,
+}
+
+impl KeyboardMapping {
+	/// Creates a new `KeyboardMapping` with the given `mappings`, the first of
+	/// which is the mapping for `first_keycode`.
+	#[must_use]
+	pub const fn new(first_keycode: Keycode, mappings: Vec<Vec<Keysym>>) -> Self {
+		Self {
+			first_keycode,
+			mappings,
+		}
+	}
+
+	/// Creates a new `KeyboardMapping` from a [`GetKeyboardMapping` reply].
+	///
+	/// `first_keycode` is the first [keycode] in the `range` of the
+	/// [`GetKeyboardMapping` request] which generated the `reply`.
+	///
+	/// [keycode]: Keycode
+	/// [`GetKeyboardMapping` reply]: reply::GetKeyboardMapping
+	/// [`GetKeyboardMapping` request]: crate::x11::request::GetKeyboardMapping
+	#[must_use]
+	pub fn from_reply(first_keycode: Keycode, reply: &reply::GetKeyboardMapping) -> Self {
+		Self::new(first_keycode, reply.mappings.clone())
+	}
+
+	/// Returns the raw list of [`Keysym`]s mapped to the given `keycode`.
+	///
+	/// Returns [`None`] if the `keycode` is not contained within this
+	/// `KeyboardMapping`.
+	#[must_use]
+	pub fn keysyms(&self, keycode: Keycode) -> Option<&[Keysym]> {
+		let index = keycode.unwrap().checked_sub(self.first_keycode.unwrap())?;
+
+		self.mappings.get(usize::from(index)).map(Vec::as_slice)
+	}
+
+	/// Returns the [`Keysym`] at the given `shift_level` for the given
+	/// `keycode`, after applying the core protocol's canonicalization rules.
+	///
+	/// `shift_level` indexes the canonicalized list of [`Keysym`]s: levels `0`
+	/// and `1` are the first group, `2` and `3` are the second group, and any
+	/// further levels are returned as-is from the raw list.
+	///
+	/// The first four [`Keysym`]s are canonicalized as follows:
+	/// - `K` is treated as `K NoSymbol K NoSymbol`;
+	/// - `K1 K2` is treated as `K1 K2 K1 K2`;
+	/// - `K1 K2 K3` is treated as `K1 K2 K3 NoSymbol`;
+	/// - within each group, if the second [`Keysym`] is `NoSymbol`, the group
+	///   is treated as `lowercase(K) uppercase(K)` if the first [`Keysym`] `K`
+	///   has both lowercase and uppercase forms, and `K K` otherwise.
+	///
+	/// Returns [`None`] if the `keycode` is not contained within this
+	/// `KeyboardMapping` or the resulting [`Keysym`] is
+	/// [`NO_SYMBOL`](Keysym::NO_SYMBOL).
+	#[must_use]
+	pub fn keysym(&self, keycode: Keycode, shift_level: u8) -> Option<Keysym> {
+		let keysyms = self.keysyms(keycode)?;
+		let level = usize::from(shift_level);
+
+		let keysym = if level < 4 {
+			Self::canonical_group(keysyms, level / 2)[level % 2]
+		} else {
+			keysyms.get(level).copied().unwrap_or(Keysym::NO_SYMBOL)
+		};
+
+		(keysym != Keysym::NO_SYMBOL).then_some(keysym)
+	}
+
+	/// Resolves the [`Keysym`] produced by the given `keycode` when the given
+	/// `modifiers` are held.
+	///
+	/// The group is selected by the `mode_switch` modifiers in the given
+	/// `interpretation`. The [`Keysym`] within the group is then selected by
+	/// the first of the following rules that applies:
+	/// 1. If `NumLock` is on and the second [`Keysym`] is a [keypad]
+	///    [`Keysym`], the first [`Keysym`] is used if `Shift` is on (or `Lock`
+	///    is on and is interpreted as `ShiftLock`); otherwise, the second is
+	///    used.
+	/// 2. If neither `Shift` nor `Lock` is on, the first [`Keysym`] is used.
+	/// 3. If `Shift` is off and `Lock` is on and interpreted as `CapsLock`, the
+	///    first [`Keysym`] is used, converted to uppercase.
+	/// 4. If `Shift` is on and `Lock` is on and interpreted as `CapsLock`, the
+	///    second [`Keysym`] is used, converted to uppercase.
+	/// 5. If `Shift` is on, or `Lock` is on and interpreted as `ShiftLock`, the
+	///    second [`Keysym`] is used.
+	///
+	/// [keypad]: Keysym::is_keypad
+	#[must_use]
+	pub fn resolve(
+		&self, keycode: Keycode, modifiers: ModifierMask, interpretation: &ModifierInterpretation,
+	) -> Option<Keysym> {
+		let keysyms = self.keysyms(keycode)?;
+
+		let group = usize::from(modifiers.intersects(interpretation.mode_switch));
+		let [first, second] = Self::canonical_group(keysyms, group);
+
+		let shift = modifiers.contains(ModifierMask::SHIFT);
+		let lock = modifiers.contains(ModifierMask::LOCK);
+
+		let caps_lock = lock && interpretation.lock == LockInterpretation::CapsLock;
+		let shift_lock = lock && interpretation.lock == LockInterpretation::ShiftLock;
+
+		let keysym = if modifiers.intersects(interpretation.num_lock) && second.is_keypad() {
+			if shift || shift_lock {
+				first
+			} else {
+				second
+			}
+		} else if !shift && !caps_lock && !shift_lock {
+			first
+		} else if !shift && caps_lock {
+			first.to_uppercase()
+		} else if shift && caps_lock {
+			second.to_uppercase()
+		} else {
+			second
+		};
+
+		(keysym != Keysym::NO_SYMBOL).then_some(keysym)
+	}
+
+	/// Determines the [`ModifierInterpretation`] for this `KeyboardMapping`
+	/// given the `modifier_mapping` returned in a
+	/// [`GetModifierMapping` reply].
+	///
+	/// [`GetModifierMapping` reply]: reply::GetModifierMapping
+	#[must_use]
+	pub fn modifier_interpretation(
+		&self, modifier_mapping: &reply::GetModifierMapping,
+	) -> ModifierInterpretation {
+		let maps_to = |keycodes: &[Keycode], target: Keysym| {
+			keycodes
+				.iter()
+				.filter_map(|keycode| self.keysyms(*keycode))
+				.any(|keysyms| keysyms.contains(&target))
+		};
+
+		let lock = if maps_to(&modifier_mapping.capslock_keycodes, Keysym::CAPS_LOCK) {
+			LockInterpretation::CapsLock
+		} else if maps_to(&modifier_mapping.capslock_keycodes, Keysym::SHIFT_LOCK) {
+			LockInterpretation::ShiftLock
+		} else {
+			LockInterpretation::Ignored
+		};
+
+		let mut num_lock = ModifierMask::empty();
+		let mut mode_switch = ModifierMask::empty();
+
+		for modifier in Modifier::ALL {
+			let keycodes = modifier_mapping.keycodes_for(modifier);
+			let mask = modifier.mask();
+
+			if maps_to(keycodes, Keysym::NUM_LOCK) {
+				num_lock |= mask;
+			}
+
+			if maps_to(keycodes, Keysym::MODE_SWITCH) {
+				mode_switch |= mask;
+			}
+		}
+
+		ModifierInterpretation {
+			lock,
+			num_lock,
+			mode_switch,
+		}
+	}
+
+	/// Returns the canonicalized pair of [`Keysym`]s for the given `group`
+	/// (`0` or `1`) of the given list of `keysyms`.
+	fn canonical_group(keysyms: &[Keysym], group: usize) -> [Keysym; 2] {
+		// Ignore trailing `NoSymbol`s.
+		let len = keysyms
+			.iter()
+			.rposition(|keysym| *keysym != Keysym::NO_SYMBOL)
+			.map_or(0, |index| index + 1);
+		let get = |index: usize| keysyms.get(index).copied().unwrap_or(Keysym::NO_SYMBOL);
+
+		let (first, second) = match (len, group) {
+			// `K` => `K NoSymbol K NoSymbol`
+			(1, _) => (get(0), Keysym::NO_SYMBOL),
+			// `K1 K2` => `K1 K2 K1 K2`
+			(2, _) | (_, 0) => (get(0), get(1)),
+			// `K1 K2 K3` => `K1 K2 K3 NoSymbol`
+			(..) => (get(2), get(3)),
+		};
+
+		if second == Keysym::NO_SYMBOL {
+			let (lower, upper) = first.convert_case();
+
+			if lower == upper {
+				[first, first]
+			} else {
+				[lower, upper]
+			}
+		} else {
+			[first, second]
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_char_conversions() {
+		let cases = [
+			('a', Keysym::LOWER_A),
+			('Z', Keysym::Z),
+			('0', Keysym::DIGIT_0),
+			(' ', Keysym::SPACE),
+			('~', Keysym::ASCIITILDE),
+			('é', Keysym::LOWER_E_ACUTE),
+			('ÿ', Keysym::LOWER_Y_DIAERESIS),
+			('\u{a0}', Keysym::NOBREAKSPACE),
+			('\r', Keysym::RETURN),
+			('\t', Keysym::TAB),
+			('\x1b', Keysym::ESCAPE),
+			('\x7f', Keysym::DELETE),
+			('€', Keysym::new(0x0100_20ac)),
+			('λ', Keysym::new(0x0100_03bb)),
+			('\u{1f600}', Keysym::new(0x0101_f600)),
+		];
+
+		for (char, keysym) in cases {
+			assert_eq!(Keysym::from_char(char), Some(keysym), "from_char({char:?})");
+			assert_eq!(keysym.to_char(), Some(char), "{keysym:?}.to_char()");
+		}
+
+		assert_eq!(Keysym::from_char('\0'), None);
+		assert_eq!(Keysym::from_char('\u{85}'), None);
+
+		assert_eq!(Keysym::KP_ENTER.to_char(), Some('\r'));
+		assert_eq!(Keysym::KP_7.to_char(), Some('7'));
+		assert_eq!(Keysym::KP_DECIMAL.to_char(), Some('.'));
+		assert_eq!(Keysym::SHIFT_L.to_char(), None);
+		assert_eq!(Keysym::NO_SYMBOL.to_char(), None);
+		// Surrogates are not valid `char`s.
+		assert_eq!(Keysym::new(0x0100_d800).to_char(), None);
+	}
+
+	#[test]
+	fn test_convert_case() {
+		let cases = [
+			(Keysym::LOWER_A, Keysym::LOWER_A, Keysym::A),
+			(Keysym::A, Keysym::LOWER_A, Keysym::A),
+			(Keysym::A_GRAVE, Keysym::LOWER_A_GRAVE, Keysym::A_GRAVE),
+			(Keysym::MULTIPLY, Keysym::MULTIPLY, Keysym::MULTIPLY),
+			(Keysym::DIVISION, Keysym::DIVISION, Keysym::DIVISION),
+			(Keysym::SSHARP, Keysym::SSHARP, Keysym::SSHARP),
+			(Keysym::DIGIT_1, Keysym::DIGIT_1, Keysym::DIGIT_1),
+			(
+				Keysym::LOWER_Y_DIAERESIS,
+				Keysym::LOWER_Y_DIAERESIS,
+				Keysym::new(0x13be),
+			),
+			(
+				Keysym::new(0x13be),
+				Keysym::LOWER_Y_DIAERESIS,
+				Keysym::new(0x13be),
+			),
+			(
+				Keysym::new(0x0100_03bb),
+				Keysym::new(0x0100_03bb),
+				Keysym::new(0x0100_039b),
+			),
+		];
+
+		for (keysym, lower, upper) in cases {
+			assert_eq!(keysym.convert_case(), (lower, upper), "{keysym:?}");
+		}
+	}
+
+	fn mapping() -> KeyboardMapping {
+		KeyboardMapping::new(
+			Keycode::new(8),
+			vec![
+				// 8: single alphabetic keysym
+				vec![Keysym::LOWER_A, Keysym::NO_SYMBOL],
+				// 9: pair
+				vec![Keysym::DIGIT_1, Keysym::EXCLAM],
+				// 10: keypad key
+				vec![Keysym::KP_HOME, Keysym::KP_7],
+				// 11: two groups
+				vec![
+					Keysym::LOWER_E,
+					Keysym::E,
+					Keysym::LOWER_E_ACUTE,
+					Keysym::NO_SYMBOL,
+				],
+				// 12: triple
+				vec![Keysym::SPACE, Keysym::NO_SYMBOL, Keysym::NOBREAKSPACE],
+				// 13: non-alphabetic single keysym
+				vec![Keysym::RETURN],
+				// 14: Latin-1 letter whose uppercase form is outside Latin-1
+				vec![Keysym::LOWER_Y_DIAERESIS],
+			],
+		)
+	}
+
+	#[test]
+	fn test_keysym_levels() {
+		let mapping = mapping();
+		let keycode = Keycode::new;
+
+		let cases = [
+			(8, 0, Some(Keysym::LOWER_A)),
+			(8, 1, Some(Keysym::A)),
+			(8, 2, Some(Keysym::LOWER_A)),
+			(8, 3, Some(Keysym::A)),
+			(9, 2, Some(Keysym::DIGIT_1)),
+			(9, 3, Some(Keysym::EXCLAM)),
+			(11, 2, Some(Keysym::LOWER_E_ACUTE)),
+			(11, 3, Some(Keysym::E_ACUTE)),
+			(12, 1, Some(Keysym::SPACE)),
+			(12, 2, Some(Keysym::NOBREAKSPACE)),
+			(13, 1, Some(Keysym::RETURN)),
+			(13, 3, Some(Keysym::RETURN)),
+			(13, 4, None),
+			(14, 1, Some(Keysym::new(0x13be))),
+			(7, 0, None),
+			(15, 0, None),
+		];
+
+		for (code, level, expected) in cases {
+			assert_eq!(
+				mapping.keysym(keycode(code), level),
+				expected,
+				"keycode {code}, level {level}"
+			);
+		}
+	}
+
+	#[test]
+	fn test_resolve() {
+		let mapping = mapping();
+		let keycode = Keycode::new;
+
+		let caps = ModifierInterpretation {
+			lock: LockInterpretation::CapsLock,
+			num_lock: ModifierMask::MOD_2,
+			mode_switch: ModifierMask::MOD_5,
+		};
+		let shift_lock = ModifierInterpretation {
+			lock: LockInterpretation::ShiftLock,
+			..caps
+		};
+
+		let none = ModifierMask::empty();
+		let shift = ModifierMask::SHIFT;
+		let lock = ModifierMask::LOCK;
+		let num = ModifierMask::MOD_2;
+		let mode = ModifierMask::MOD_5;
+
+		let cases = [
+			(8, none, &caps, Keysym::LOWER_A),
+			(8, shift, &caps, Keysym::A),
+			(8, lock, &caps, Keysym::A),
+			(8, shift | lock, &caps, Keysym::A),
+			(9, lock, &caps, Keysym::DIGIT_1),
+			(9, shift | lock, &caps, Keysym::EXCLAM),
+			(9, lock, &shift_lock, Keysym::EXCLAM),
+			(8, lock, &shift_lock, Keysym::A),
+			// Keypad vs NumLock.
+			(10, none, &caps, Keysym::KP_HOME),
+			(10, num, &caps, Keysym::KP_7),
+			(10, num | shift, &caps, Keysym::KP_HOME),
+			(10, num | lock, &caps, Keysym::KP_7),
+			(10, num | lock, &shift_lock, Keysym::KP_HOME),
+			// Mode switch.
+			(11, mode, &caps, Keysym::LOWER_E_ACUTE),
+			(11, mode | shift, &caps, Keysym::E_ACUTE),
+			(11, mode | lock, &caps, Keysym::E_ACUTE),
+			(12, mode, &caps, Keysym::NOBREAKSPACE),
+			(14, lock, &caps, Keysym::new(0x13be)),
+		];
+
+		for (code, modifiers, interpretation, expected) in cases {
+			assert_eq!(
+				mapping.resolve(keycode(code), modifiers, interpretation),
+				Some(expected),
+				"keycode {code}, modifiers {modifiers:?}, lock {:?}",
+				interpretation.lock
+			);
+		}
+	}
+}
@@ -5,6 +5,7 @@
 #![allow(missing_docs)]
 
 use bitflags::bitflags;
+use thiserror::Error;
 use xrbk_macro::{ConstantX11Size, Readable, Writable, X11Size};
 
 bitflags! {
@@ -161,56 +162,56 @@ bitflags! {
 	/// - `COLORMAP_CHANGE`
 	/// - `OWNER_GRAB_BUTTON`
 	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable)]
-	pub struct CursorEventMask: u32 {
+	pub struct CursorEventMask: u16 {
 		// removes KEY_PRESS and KEY_RELEASE
 		/// Mouse button press events.
-		const BUTTON_PRESS = 0x0000_0004;
+		const BUTTON_PRESS = 0x0004;
 		/// Mouse button release events.
-		const BUTTON_RELEASE = 0x0000_0008;
+		const BUTTON_RELEASE = 0x0008;
 
 		/// Cursor events generated when the cursor enters a window.
 		///
 		/// `ENTER_WINDOW` events are generated not only when the cursor moves
 		/// to enter another window, but when the window under the cursor's
 		/// current position changes.
-		const ENTER_WINDOW = 0x0000_0010;
+		const ENTER_WINDOW = 0x0010;
 		/// Cursor events generated when the cursor leaves a window.
 		///
 		/// `LEAVE_WINDOW` events are generated not only when the cursor moves
 		/// away from a window, but when the window under the cursor's current
 		/// position moves or changes to a different window.
-		const LEAVE_WINDOW = 0x0000_0020;
+		const LEAVE_WINDOW = 0x0020;
 
 		/// Cursor motion events generated when the cursor's position changes.
-		const ANY_MOTION = 0x0000_0040;
-		const MOTION_HINT = 0x0000_0080;
+		const ANY_MOTION = 0x0040;
+		const MOTION_HINT = 0x0080;
 		/// Cursor 'drag' events when the primary mouse button is held.
 		///
 		/// The primary mouse button is usually the one on the left, but many
 		/// tools offer options to switch the positions of the primary and
 		/// secondary mouse buttons.
-		const BUTTON_1_MOTION = 0x0000_0100;
+		const BUTTON_1_MOTION = 0x0100;
 		/// Cursor 'drag' events when the middle mouse button is held.
-		const BUTTON_2_MOTION = 0x0000_0200;
+		const BUTTON_2_MOTION = 0x0200;
 		/// Cursor 'drag' events when the secondary mouse button is held.
 		///
 		/// The secondary mouse button is usually the one on the right, but many
 		/// tools offer options to switch the positions of the primary and
 		/// secondary mouse buttons.
-		const BUTTON_3_MOTION = 0x0000_0400;
+		const BUTTON_3_MOTION = 0x0400;
 		/// Cursor 'drag' events when 'mouse button 4' is held.
-		const BUTTON_4_MOTION = 0x0000_0800;
+		const BUTTON_4_MOTION = 0x0800;
 		/// Cursor 'drag' events when 'mouse button 5' is held.
-		const BUTTON_5_MOTION = 0x0000_1000;
+		const BUTTON_5_MOTION = 0x1000;
 		/// Cursor 'drag' events when any mouse button is held.
-		const ANY_BUTTON_MOTION = 0x0000_2000;
+		const ANY_BUTTON_MOTION = 0x2000;
 
 		/// Events generated after every [`EnterWindow`] and [`Focus`] event
 		/// reporting the currently held keys.
 		///
 		/// [`EnterWindow`]: crate::x11::event::EnterWindow
 		/// [`Focus`]: crate::x11::event::Focus
-		const KEY_STATE = 0x0000_4000;
+		const KEY_STATE = 0x4000;
 
 		// removes other events irrelevant to the cursor and buttons
 	}
@@ -227,9 +228,11 @@ bitflags! {
 	/// - `ENTER_WINDOW`
 	/// - `LEAVE_WINDOW`
 	/// - `MOTION_HINT`
+	/// - `KEYBOARD_STATE`
 	/// - `EXPOSURE`
 	/// - `VISIBILITY_CHANGE`
 	/// - `STRUCTURE_NOTIFY`
+	/// - `RESIZE_REDIRECT`
 	/// - `SUBSTRUCTURE_NOTIFY`
 	/// - `SUBSTRUCTURE_REDIRECT`
 	/// - `FOCUS_CHANGE`
@@ -375,7 +378,7 @@ bitflags! {
 		const CONTROL = 0x0004;
 
 		/// Whether 'modifier key 1' is held.
-		const MOD_1 = 0x0009;
+		const MOD_1 = 0x0008;
 		/// Whether 'modifier key 2' is held.
 		const MOD_2 = 0x0010;
 		/// Whether 'modifier key 3' is held.
@@ -394,3 +397,104 @@ bitflags! {
 		const ANY_MODIFIER = 0x8000;
 	}
 }
+
+impl_display_for_bitflags!(ModifierMask {
+	CONTROL => "Ctrl",
+	SHIFT => "Shift",
+	LOCK => "Lock",
+	MOD_1 => "Mod1",
+	MOD_2 => "Mod2",
+	MOD_3 => "Mod3",
+	MOD_4 => "Mod4",
+	MOD_5 => "Mod5",
+	BUTTON_1 => "Button1",
+	BUTTON_2 => "Button2",
+	BUTTON_3 => "Button3",
+	BUTTON_4 => "Button4",
+	BUTTON_5 => "Button5",
+});
+
+/// An error generated when converting an [`EventMask`] which selects events
+/// that are not available in a [`DeviceEventMask`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Error)]
+#[error("events not available in a device event mask were selected: {unavailable:?}")]
+pub struct DeviceEventMaskError {
+	/// The selected events which are not available in a [`DeviceEventMask`].
+	pub unavailable: EventMask,
+}
+
+impl From<DeviceEventMask> for EventMask {
+	fn from(mask: DeviceEventMask) -> Self {
+		// Every `DeviceEventMask` flag has the same bit as in `EventMask`.
+		Self::from_bits_truncate(mask.bits())
+	}
+}
+
+impl TryFrom<EventMask> for DeviceEventMask {
+	type Error = DeviceEventMaskError;
+
+	fn try_from(mask: EventMask) -> Result<Self, Self::Error> {
+		Self::from_bits(mask.bits()).ok_or_else(|| DeviceEventMaskError {
+			unavailable: mask - EventMask::from(Self::all()),
+		})
+	}
+}
+
+#[cfg(feature = "serde")]
+impl_serde_for_bitflags!(
+	ColorChannelMask,
+	EventMask,
+	CursorEventMask,
+	DeviceEventMask,
+	ModifierMask,
+	ModifierKeyMask,
+	AnyModifierKeyMask
+);
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_device_event_mask_from_event_mask() {
+		let mask = EventMask::KEY_PRESS | EventMask::BUTTON_1_MOTION | EventMask::ANY_BUTTON_MOTION;
+
+		assert_eq!(
+			DeviceEventMask::try_from(mask),
+			Ok(DeviceEventMask::KEY_PRESS
+				| DeviceEventMask::BUTTON_1_MOTION
+				| DeviceEventMask::ANY_BUTTON_MOTION),
+		);
+		assert_eq!(
+			EventMask::from(DeviceEventMask::all()),
+			EventMask::from_bits_truncate(0x3f4f)
+		);
+	}
+
+	#[test]
+	fn test_device_event_mask_rejects_unavailable_events() {
+		let unavailable = [
+			EventMask::ENTER_WINDOW,
+			EventMask::LEAVE_WINDOW,
+			EventMask::MOTION_HINT,
+			EventMask::KEYBOARD_STATE,
+			EventMask::EXPOSURE,
+			EventMask::VISIBILITY_CHANGE,
+			EventMask::STRUCTURE_NOTIFY,
+			EventMask::RESIZE_REDIRECT,
+			EventMask::SUBSTRUCTURE_NOTIFY,
+			EventMask::SUBSTRUCTURE_REDIRECT,
+			EventMask::FOCUS_CHANGE,
+			EventMask::PROPERTY_CHANGE,
+			EventMask::COLORMAP_CHANGE,
+			EventMask::OWNER_GRAB_BUTTON,
+		];
+
+		for event in unavailable {
+			assert_eq!(
+				DeviceEventMask::try_from(EventMask::KEY_PRESS | event),
+				Err(DeviceEventMaskError { unavailable: event }),
+			);
+		}
+	}
+}
@@ -2,7 +2,9 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use crate::xc_misc::reply::{GetResourceIdList, GetResourceIdRange};
 use derive_more::{From, Into};
+use std::collections::BTreeSet;
 use xrbk_macro::{new, unwrap, ConstantX11Size, Readable, Wrap, Writable, X11Size};
 
 /// A resource ID referring to either a [`Window`] or a [`Pixmap`].
@@ -35,6 +37,11 @@ use xrbk_macro::{new, unwrap, ConstantX11Size, Readable, Wrap, Writable, X11Size
 	Writable,
 	Wrap,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Drawable(u32);
 
 impl From<Window> for Drawable {
@@ -84,6 +91,11 @@ impl From<Pixmap> for Drawable {
 	Writable,
 	Wrap,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Window(u32);
 
 impl From<Drawable> for Window {
@@ -122,6 +134,11 @@ impl From<Drawable> for Window {
 	Writable,
 	Wrap,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Pixmap(u32);
 
 impl From<Drawable> for Pixmap {
@@ -160,6 +177,11 @@ impl From<Drawable> for Pixmap {
 	Writable,
 	Wrap,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct CursorAppearance(u32);
 
 /// A resource ID referring to either a [`Font`] or a [`GraphicsContext`].
@@ -182,6 +204,11 @@ pub struct CursorAppearance(u32);
 	Writable,
 	Wrap,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Fontable(u32);
 
 impl From<Font> for Fontable {
@@ -227,6 +254,11 @@ impl From<GraphicsContext> for Fontable {
 	Writable,
 	Wrap,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Font(u32);
 
 impl From<Fontable> for Font {
@@ -271,6 +303,11 @@ impl From<Fontable> for Font {
 	Writable,
 	Wrap,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct GraphicsContext(u32);
 
 impl From<Fontable> for GraphicsContext {
@@ -309,4 +346,427 @@ impl From<Fontable> for GraphicsContext {
 	Writable,
 	Wrap,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Colormap(u32);
+
+/// Allocates resource IDs for new resources created by this client.
+///
+/// When a client connects, the X server assigns it a `resource_id_base` and a
+/// `resource_id_mask` in the [`ConnectionSuccess`] response. The client may
+/// create resources with any ID made by combining the `resource_id_base` with
+/// a subset of the bits set in the `resource_id_mask`. This allocator hands
+/// out those IDs in order, never producing an ID outside of that range.
+///
+/// Released IDs are reused before any new IDs are allocated, so a long-running
+/// client that destroys its resources will not run out of IDs. Once every ID
+/// in the range has been allocated and none have been released, the allocator
+/// is exhausted; the [XC-MISC extension] can be used to find IDs that the X
+/// server no longer considers in use, which are then given to the allocator
+/// with [`refill_from_range`] or [`refill_from_list`].
+///
+/// [`ConnectionSuccess`]: crate::connection::ConnectionSuccess
+/// [XC-MISC extension]: crate::xc_misc
+///
+/// [`refill_from_range`]: ResourceIdAllocator::refill_from_range
+/// [`refill_from_list`]: ResourceIdAllocator::refill_from_list
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ResourceIdAllocator {
+	base: u32,
+	mask: u32,
+
+	/// The bits of the next ID which has never been allocated, or [`None`] if
+	/// every ID has been allocated at least once.
+	next: Option<u32>,
+	/// IDs which have been [released] and may be allocated again.
+	///
+	/// [released]: ResourceIdAllocator::release
+	released: BTreeSet<u32>,
+	/// Ranges of IDs recovered with a [`GetResourceIdRange` reply], as the
+	/// next ID in each range and the number of IDs remaining in it.
+	///
+	/// [`GetResourceIdRange` reply]: GetResourceIdRange
+	recovered: Vec<(u32, u32)>,
+}
+
+impl ResourceIdAllocator {
+	/// Creates a new allocator for the given `resource_id_base` and
+	/// `resource_id_mask`.
+	///
+	/// `base` must not share any bits with `mask` - this is guaranteed for the
+	/// values sent by the X server in the [`ConnectionSuccess`] response.
+	///
+	/// [`ConnectionSuccess`]: crate::connection::ConnectionSuccess
+	#[must_use]
+	pub const fn new(base: u32, mask: u32) -> Self {
+		Self {
+			base,
+			mask,
+
+			// An ID of `0` means 'none', so it cannot be used for a resource.
+			next: if base == 0 {
+				Self::next_bits(0, mask)
+			} else {
+				Some(0)
+			},
+			released: BTreeSet::new(),
+			recovered: Vec::new(),
+		}
+	}
+
+	/// Returns the bits of `mask` which follow `bits` in ascending order, or
+	/// [`None`] if `bits` is the last.
+	const fn next_bits(bits: u32, mask: u32) -> Option<u32> {
+		// Setting every bit outside of `mask` causes the addition to carry
+		// across them, so only bits in `mask` are incremented.
+		match (bits | !mask).wrapping_add(1) & mask {
+			0 => None,
+			next => Some(next),
+		}
+	}
+
+	/// The `resource_id_base` IDs are allocated from.
+	#[must_use]
+	pub const fn base(&self) -> u32 {
+		self.base
+	}
+
+	/// The `resource_id_mask` IDs are allocated from.
+	#[must_use]
+	pub const fn mask(&self) -> u32 {
+		self.mask
+	}
+
+	/// Allocates a new resource ID.
+	///
+	/// Returns [`None`] if every ID has been allocated and none have been
+	/// [released].
+	///
+	/// [released]: ResourceIdAllocator::release
+	pub fn allocate(&mut self) -> Option<u32> {
+		if let Some(id) = self.released.pop_first() {
+			return Some(id);
+		}
+
+		if let Some(bits) = self.next {
+			self.next = Self::next_bits(bits, self.mask);
+
+			return Some(self.base | bits);
+		}
+
+		self.allocate_recovered()
+	}
+
+	/// Allocates the next ID from the ranges recovered with
+	/// [`refill_from_range`].
+	///
+	/// [`refill_from_range`]: ResourceIdAllocator::refill_from_range
+	fn allocate_recovered(&mut self) -> Option<u32> {
+		let step = self.step();
+
+		while let Some((next, remaining)) = self.recovered.last_mut() {
+			let id = *next;
+
+			*next = next.wrapping_add(step);
+			*remaining -= 1;
+
+			if *remaining == 0 {
+				self.recovered.pop();
+			}
+
+			if self.was_allocated(id) && !self.released.contains(&id) {
+				return Some(id);
+			}
+		}
+
+		None
+	}
+
+	/// The difference between consecutive IDs in a range recovered with
+	/// [`refill_from_range`]: the least significant bit of the `mask`.
+	///
+	/// [`refill_from_range`]: ResourceIdAllocator::refill_from_range
+	const fn step(&self) -> u32 {
+		self.mask & self.mask.wrapping_neg()
+	}
+
+	/// Returns whether `id` is within this allocator's range and has been
+	/// allocated before.
+	const fn was_allocated(&self, id: u32) -> bool {
+		let allocated = match self.next {
+			Some(next) => id & self.mask < next,
+			None => true,
+		};
+
+		id != 0 && id & !self.mask == self.base && allocated
+	}
+
+	/// Releases the given resource ID so that it may be allocated again.
+	///
+	/// This should only be called once the resource using `id` has been
+	/// destroyed. IDs which were not allocated by this allocator are ignored.
+	pub fn release(&mut self, id: u32) {
+		if self.was_allocated(id) {
+			self.released.insert(id);
+		}
+	}
+
+	/// Makes the IDs in the given [`GetResourceIdRange` reply] available to be
+	/// allocated again.
+	///
+	/// The reply's `count` IDs, starting at its `start_id`, are allocated once
+	/// any [released] IDs have been. A reply with a `count` of zero means
+	/// that no IDs are available, and is ignored, as are IDs which were not
+	/// allocated by this allocator.
+	///
+	/// [released]: ResourceIdAllocator::release
+	/// [`GetResourceIdRange` reply]: GetResourceIdRange
+	pub fn refill_from_range(&mut self, reply: &GetResourceIdRange) {
+		if reply.count == 0 || !self.was_allocated(reply.start_id) {
+			return;
+		}
+
+		// There can't be more IDs in the range than there are in the mask.
+		let max_count = self
+			.mask
+			.checked_div(self.step())
+			.map_or(1, |max| max.saturating_add(1));
+
+		self.recovered
+			.push((reply.start_id, reply.count.min(max_count)));
+	}
+
+	/// Makes the IDs in the given [`GetResourceIdList` reply] available to be
+	/// allocated again.
+	///
+	/// This is equivalent to [releasing] each of the reply's `ids`.
+	///
+	/// [releasing]: ResourceIdAllocator::release
+	/// [`GetResourceIdList` reply]: GetResourceIdList
+	pub fn refill_from_list(&mut self, reply: &GetResourceIdList) {
+		for id in &reply.ids {
+			self.release(*id);
+		}
+	}
+
+	/// Allocates a new [`Window`] ID.
+	///
+	/// See [`allocate`] for more information.
+	///
+	/// [`allocate`]: ResourceIdAllocator::allocate
+	pub fn allocate_window(&mut self) -> Option<Window> {
+		self.allocate().map(Window)
+	}
+
+	/// Allocates a new [`Pixmap`] ID.
+	///
+	/// See [`allocate`] for more information.
+	///
+	/// [`allocate`]: ResourceIdAllocator::allocate
+	pub fn allocate_pixmap(&mut self) -> Option<Pixmap> {
+		self.allocate().map(Pixmap)
+	}
+
+	/// Allocates a new [`CursorAppearance`] ID.
+	///
+	/// See [`allocate`] for more information.
+	///
+	/// [`allocate`]: ResourceIdAllocator::allocate
+	pub fn allocate_cursor_appearance(&mut self) -> Option<CursorAppearance> {
+		self.allocate().map(CursorAppearance)
+	}
+
+	/// Allocates a new [`Font`] ID.
+	///
+	/// See [`allocate`] for more information.
+	///
+	/// [`allocate`]: ResourceIdAllocator::allocate
+	pub fn allocate_font(&mut self) -> Option<Font> {
+		self.allocate().map(Font)
+	}
+
+	/// Allocates a new [`GraphicsContext`] ID.
+	///
+	/// See [`allocate`] for more information.
+	///
+	/// [`allocate`]: ResourceIdAllocator::allocate
+	pub fn allocate_graphics_context(&mut self) -> Option<GraphicsContext> {
+		self.allocate().map(GraphicsContext)
+	}
+
+	/// Allocates a new [`Colormap`] ID.
+	///
+	/// See [`allocate`] for more information.
+	///
+	/// [`allocate`]: ResourceIdAllocator::allocate
+	pub fn allocate_colormap(&mut self) -> Option<Colormap> {
+		self.allocate().map(Colormap)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_allocations_within_mask() {
+		let (base, mask) = (0x0020_0000, 0x001f_ffff);
+		let mut allocator = ResourceIdAllocator::new(base, mask);
+
+		for _ in 0..1000 {
+			let id = allocator.allocate().unwrap();
+			assert_eq!(id & !mask, base);
+		}
+	}
+
+	#[test]
+	fn test_exhaustion() {
+		let (base, mask) = (0x0400_0000, 0b1010_0000);
+		let mut allocator = ResourceIdAllocator::new(base, mask);
+
+		let ids: Vec<_> = std::iter::from_fn(|| allocator.allocate()).collect();
+
+		assert_eq!(
+			ids,
+			[
+				base,
+				base | 0b0010_0000,
+				base | 0b1000_0000,
+				base | 0b1010_0000
+			]
+		);
+		for id in ids {
+			assert_eq!(id & !mask, base);
+		}
+
+		assert_eq!(allocator.allocate(), None);
+		assert_eq!(allocator.allocate_window(), None);
+	}
+
+	#[test]
+	fn test_exhaustion_with_full_mask() {
+		// A full mask must not wrap around into IDs that have already been
+		// allocated.
+		let mut allocator = ResourceIdAllocator::new(0, 0b11);
+
+		assert_eq!(allocator.allocate(), Some(1));
+		assert_eq!(allocator.allocate(), Some(2));
+		assert_eq!(allocator.allocate(), Some(3));
+		assert_eq!(allocator.allocate(), None);
+	}
+
+	#[test]
+	fn test_reuse_after_release() {
+		let (base, mask) = (0x0020_0000, 0b11);
+		let mut allocator = ResourceIdAllocator::new(base, mask);
+
+		let window = allocator.allocate_window().unwrap();
+		let pixmap = allocator.allocate_pixmap().unwrap();
+		let colormap = allocator.allocate_colormap().unwrap();
+		let context = allocator.allocate_graphics_context().unwrap();
+		assert_eq!(allocator.allocate(), None);
+
+		allocator.release(pixmap.unwrap());
+		allocator.release(window.unwrap());
+		// Releasing the same ID twice must not allow it to be allocated twice.
+		allocator.release(window.unwrap());
+
+		assert_eq!(allocator.allocate(), Some(window.unwrap()));
+		assert_eq!(allocator.allocate(), Some(pixmap.unwrap()));
+		assert_eq!(allocator.allocate(), None);
+
+		for id in [colormap.unwrap(), context.unwrap()] {
+			assert_eq!(id & !mask, base);
+		}
+	}
+
+	#[test]
+	fn test_release_ignores_foreign_ids() {
+		let (base, mask) = (0x0020_0000, 0x001f_ffff);
+		let mut allocator = ResourceIdAllocator::new(base, mask);
+
+		let id = allocator.allocate().unwrap();
+
+		// Outside of the range, or within it but never allocated.
+		allocator.release(0x0040_0000);
+		allocator.release(base | 0x1000);
+		allocator.release(0);
+
+		assert_eq!(allocator.allocate(), Some(id + 1));
+	}
+
+	#[test]
+	fn test_refill_from_range() {
+		let (base, mask) = (0x0020_0000, 0b111);
+		let mut allocator = ResourceIdAllocator::new(base, mask);
+
+		while allocator.allocate().is_some() {}
+
+		// A range outside of the allocator's own is ignored.
+		allocator.refill_from_range(&GetResourceIdRange {
+			sequence: 1,
+			start_id: 0x0040_0002,
+			count: 3,
+		});
+		// As is an empty range.
+		allocator.refill_from_range(&GetResourceIdRange {
+			sequence: 2,
+			start_id: base | 2,
+			count: 0,
+		});
+		assert_eq!(allocator.allocate(), None);
+
+		allocator.refill_from_range(&GetResourceIdRange {
+			sequence: 3,
+			start_id: base | 2,
+			count: 3,
+		});
+		allocator.release(base | 6);
+
+		// Released IDs are allocated before those in the range.
+		assert_eq!(allocator.allocate(), Some(base | 6));
+		assert_eq!(allocator.allocate(), Some(base | 2));
+		assert_eq!(allocator.allocate(), Some(base | 3));
+		assert_eq!(allocator.allocate(), Some(base | 4));
+		assert_eq!(allocator.allocate(), None);
+	}
+
+	#[test]
+	fn test_refill_from_range_is_capped() {
+		let (base, mask) = (0x0020_0000, 0b11);
+		let mut allocator = ResourceIdAllocator::new(base, mask);
+
+		while allocator.allocate().is_some() {}
+
+		// A `count` larger than the mask allows must not run past the range.
+		allocator.refill_from_range(&GetResourceIdRange {
+			sequence: 1,
+			start_id: base | 2,
+			count: u32::MAX,
+		});
+
+		assert_eq!(allocator.allocate(), Some(base | 2));
+		assert_eq!(allocator.allocate(), Some(base | 3));
+		assert_eq!(allocator.allocate(), None);
+	}
+
+	#[test]
+	fn test_refill_from_list() {
+		let (base, mask) = (0x0020_0000, 0b111);
+		let mut allocator = ResourceIdAllocator::new(base, mask);
+
+		while allocator.allocate().is_some() {}
+
+		allocator.refill_from_list(&GetResourceIdList {
+			sequence: 1,
+			ids: vec![base | 5, 0x0040_0000, base | 1],
+		});
+
+		assert_eq!(allocator.allocate(), Some(base | 1));
+		assert_eq!(allocator.allocate(), Some(base | 5));
+		assert_eq!(allocator.allocate(), None);
+	}
+}
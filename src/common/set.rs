@@ -59,6 +59,7 @@ pub(self) fn read_set_value<T: Readable>(
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(self) struct __Px<Num>(Px<Num>);
 
 impl<Num> ConstantX11Size for __Px<Num> {
@@ -123,6 +124,7 @@ impl_px!(get_i32, i32 => {
 	reason = "The type name is chosen to indicate an internal representation of a u8 value."
 )]
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(self) struct __u8(u8);
 
 impl ConstantX11Size for __u8 {
@@ -140,7 +142,7 @@ impl Readable for __u8 {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match u8::try_from(buf.get_u32()) {
+		Ok(Self(match u8::try_from(u32::read_from(buf)?) {
 			Ok(u8) => u8,
 			Err(error) => return Err(ReadError::FailedConversion(Box::new(error))),
 		}))
@@ -165,6 +167,7 @@ impl Writable for __u8 {
 	reason = "The type name is chosen to indicate an internal representation of a u16 value."
 )]
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(self) struct __u16(u16);
 
 impl ConstantX11Size for __u16 {
@@ -182,7 +185,7 @@ impl Readable for __u16 {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match u16::try_from(buf.get_u32()) {
+		Ok(Self(match u16::try_from(u32::read_from(buf)?) {
 			Ok(u16) => u16,
 			Err(error) => return Err(ReadError::FailedConversion(Box::new(error))),
 		}))
@@ -207,6 +210,7 @@ impl Writable for __u16 {
 	reason = "The type name is chosen to indicate an internal representation of an i16 value."
 )]
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(self) struct __i16(i16);
 
 impl ConstantX11Size for __i16 {
@@ -224,7 +228,7 @@ impl Readable for __i16 {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match i16::try_from(buf.get_i32()) {
+		Ok(Self(match i16::try_from(i32::read_from(buf)?) {
 			Ok(i16) => i16,
 			Err(error) => return Err(ReadError::FailedConversion(Box::new(error))),
 		}))
@@ -250,6 +254,7 @@ impl Writable for __i16 {
 	          indicate that it is internal, and `bool` to indicate its wrapped type."
 )]
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(self) struct __bool(bool);
 
 impl ConstantX11Size for __bool {
@@ -267,7 +272,7 @@ impl Readable for __bool {
 	where
 		Self: Sized,
 	{
-		Ok(Self(buf.get_u32() != 0))
+		Ok(Self(u32::read_from(buf)? != 0))
 	}
 }
 
@@ -117,6 +117,7 @@ pub type ColormapAttribute = CopyableFromParent<Colormap>;
 /// [`InputOutput`]: crate::WindowClass::InputOutput
 /// [`InputOnly`]: crate::WindowClass::InputOnly
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Attributes {
 	/// Total [`X11Size`] of these `Attributes`.
 	///
@@ -137,7 +138,7 @@ pub struct Attributes {
 	bit_gravity: Option<__BitGravity>,
 	window_gravity: Option<__WindowGravity>,
 
-	maintain_contents: Option<MaintainContents>,
+	maintain_contents: Option<__MaintainContents>,
 	maintained_planes: Option<u32>,
 	maintenance_fallback_color: Option<ColorId>,
 
@@ -171,6 +172,7 @@ impl Attributes {
 /// [`build()`]: AttributesBuilder::build
 /// [`Attributes` set]: Attributes
 #[derive(Clone, Default, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AttributesBuilder {
 	x11_size: usize,
 
@@ -260,7 +262,7 @@ impl AttributesBuilder {
 			bit_gravity: self.bit_gravity.map(__BitGravity),
 			window_gravity: self.window_gravity.map(__WindowGravity),
 
-			maintain_contents: self.maintain_contents,
+			maintain_contents: self.maintain_contents.map(__MaintainContents),
 			maintained_planes: self.maintained_planes,
 			maintenance_fallback_color: self.maintenance_fallback_color,
 
@@ -707,7 +709,9 @@ impl Attributes {
 		reason = "const is omitted for API uniformity with the other methods and sets"
 	)]
 	pub fn maintain_contents(&self) -> Option<&MaintainContents> {
-		self.maintain_contents.as_ref()
+		self.maintain_contents
+			.as_ref()
+			.map(|__MaintainContents(maintain_contents)| maintain_contents)
 	}
 	/// Which bit planes of the [window] hold dynamic data which must be
 	/// maintained for [`maintain_contents`] and [`maintain_windows_under`].
@@ -953,6 +957,9 @@ bitflags! {
 	}
 }
 
+#[cfg(feature = "serde")]
+impl_serde_for_bitflags!(AttributesMask);
+
 impl X11Size for Attributes {
 	fn x11_size(&self) -> usize {
 		self.x11_size
@@ -1145,6 +1152,7 @@ impl Writable for Attributes {
 ///
 /// [bit gravities]: BitGravity
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __BitGravity(BitGravity);
 
 impl ConstantX11Size for __BitGravity {
@@ -1159,18 +1167,18 @@ impl X11Size for __BitGravity {
 
 impl Readable for __BitGravity {
 	fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
-		Ok(Self(match buf.get_u32() {
+		Ok(Self(match u32::read_from(buf)? {
 			discrim if discrim == 0 => BitGravity::Forget,
-			discrim if discrim == 1 => BitGravity::Static,
-			discrim if discrim == 2 => BitGravity::NorthWest,
-			discrim if discrim == 3 => BitGravity::North,
-			discrim if discrim == 4 => BitGravity::NorthEast,
-			discrim if discrim == 5 => BitGravity::West,
-			discrim if discrim == 6 => BitGravity::Center,
-			discrim if discrim == 7 => BitGravity::East,
-			discrim if discrim == 8 => BitGravity::SouthWest,
-			discrim if discrim == 9 => BitGravity::South,
-			discrim if discrim == 10 => BitGravity::SouthEast,
+			discrim if discrim == 1 => BitGravity::NorthWest,
+			discrim if discrim == 2 => BitGravity::North,
+			discrim if discrim == 3 => BitGravity::NorthEast,
+			discrim if discrim == 4 => BitGravity::West,
+			discrim if discrim == 5 => BitGravity::Center,
+			discrim if discrim == 6 => BitGravity::East,
+			discrim if discrim == 7 => BitGravity::SouthWest,
+			discrim if discrim == 8 => BitGravity::South,
+			discrim if discrim == 9 => BitGravity::SouthEast,
+			discrim if discrim == 10 => BitGravity::Static,
 
 			other_discrim => {
 				return Err(ReadError::UnrecognizedDiscriminant(other_discrim as usize))
@@ -1185,16 +1193,16 @@ impl Writable for __BitGravity {
 
 		match bit_gravity {
 			BitGravity::Forget => buf.put_u32(0),
-			BitGravity::Static => buf.put_u32(1),
-			BitGravity::NorthWest => buf.put_u32(2),
-			BitGravity::North => buf.put_u32(3),
-			BitGravity::NorthEast => buf.put_u32(4),
-			BitGravity::West => buf.put_u32(5),
-			BitGravity::Center => buf.put_u32(6),
-			BitGravity::East => buf.put_u32(7),
-			BitGravity::SouthWest => buf.put_u32(8),
-			BitGravity::South => buf.put_u32(9),
-			BitGravity::SouthEast => buf.put_u32(10),
+			BitGravity::NorthWest => buf.put_u32(1),
+			BitGravity::North => buf.put_u32(2),
+			BitGravity::NorthEast => buf.put_u32(3),
+			BitGravity::West => buf.put_u32(4),
+			BitGravity::Center => buf.put_u32(5),
+			BitGravity::East => buf.put_u32(6),
+			BitGravity::SouthWest => buf.put_u32(7),
+			BitGravity::South => buf.put_u32(8),
+			BitGravity::SouthEast => buf.put_u32(9),
+			BitGravity::Static => buf.put_u32(10),
 		}
 
 		Ok(())
@@ -1206,6 +1214,7 @@ impl Writable for __BitGravity {
 ///
 /// [window gravities]: WindowGravity
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __WindowGravity(WindowGravity);
 
 impl ConstantX11Size for __WindowGravity {
@@ -1220,18 +1229,18 @@ impl X11Size for __WindowGravity {
 
 impl Readable for __WindowGravity {
 	fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
-		Ok(Self(match buf.get_u32() {
+		Ok(Self(match u32::read_from(buf)? {
 			discrim if discrim == 0 => WindowGravity::Unmap,
-			discrim if discrim == 1 => WindowGravity::Static,
-			discrim if discrim == 2 => WindowGravity::NorthWest,
-			discrim if discrim == 3 => WindowGravity::North,
-			discrim if discrim == 4 => WindowGravity::NorthEast,
-			discrim if discrim == 5 => WindowGravity::West,
-			discrim if discrim == 6 => WindowGravity::Center,
-			discrim if discrim == 7 => WindowGravity::East,
-			discrim if discrim == 8 => WindowGravity::SouthWest,
-			discrim if discrim == 9 => WindowGravity::South,
-			discrim if discrim == 10 => WindowGravity::SouthEast,
+			discrim if discrim == 1 => WindowGravity::NorthWest,
+			discrim if discrim == 2 => WindowGravity::North,
+			discrim if discrim == 3 => WindowGravity::NorthEast,
+			discrim if discrim == 4 => WindowGravity::West,
+			discrim if discrim == 5 => WindowGravity::Center,
+			discrim if discrim == 6 => WindowGravity::East,
+			discrim if discrim == 7 => WindowGravity::SouthWest,
+			discrim if discrim == 8 => WindowGravity::South,
+			discrim if discrim == 9 => WindowGravity::SouthEast,
+			discrim if discrim == 10 => WindowGravity::Static,
 
 			other_discrim => {
 				return Err(ReadError::UnrecognizedDiscriminant(other_discrim as usize))
@@ -1246,16 +1255,60 @@ impl Writable for __WindowGravity {
 
 		match window_gravity {
 			WindowGravity::Unmap => buf.put_u32(0),
-			WindowGravity::Static => buf.put_u32(1),
-			WindowGravity::NorthWest => buf.put_u32(2),
-			WindowGravity::North => buf.put_u32(3),
-			WindowGravity::NorthEast => buf.put_u32(4),
-			WindowGravity::West => buf.put_u32(5),
-			WindowGravity::Center => buf.put_u32(6),
-			WindowGravity::East => buf.put_u32(7),
-			WindowGravity::SouthWest => buf.put_u32(8),
-			WindowGravity::South => buf.put_u32(9),
-			WindowGravity::SouthEast => buf.put_u32(10),
+			WindowGravity::NorthWest => buf.put_u32(1),
+			WindowGravity::North => buf.put_u32(2),
+			WindowGravity::NorthEast => buf.put_u32(3),
+			WindowGravity::West => buf.put_u32(4),
+			WindowGravity::Center => buf.put_u32(5),
+			WindowGravity::East => buf.put_u32(6),
+			WindowGravity::SouthWest => buf.put_u32(7),
+			WindowGravity::South => buf.put_u32(8),
+			WindowGravity::SouthEast => buf.put_u32(9),
+			WindowGravity::Static => buf.put_u32(10),
+		}
+
+		Ok(())
+	}
+}
+
+/// A type wrapping [`MaintainContents`] to represent it in [`Attributes`] as
+/// four bytes.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct __MaintainContents(MaintainContents);
+
+impl ConstantX11Size for __MaintainContents {
+	const X11_SIZE: usize = 4;
+}
+
+impl X11Size for __MaintainContents {
+	fn x11_size(&self) -> usize {
+		Self::X11_SIZE
+	}
+}
+
+impl Readable for __MaintainContents {
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
+		Ok(Self(match u32::read_from(buf)? {
+			0 => MaintainContents::Never,
+			1 => MaintainContents::WhenMapped,
+			2 => MaintainContents::Always,
+
+			other_discrim => {
+				return Err(ReadError::UnrecognizedDiscriminant(other_discrim as usize))
+			},
+		}))
+	}
+}
+
+impl Writable for __MaintainContents {
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		let Self(maintain_contents) = self;
+
+		match maintain_contents {
+			MaintainContents::Never => buf.put_u32(0),
+			MaintainContents::WhenMapped => buf.put_u32(1),
+			MaintainContents::Always => buf.put_u32(2),
 		}
 
 		Ok(())
@@ -1263,3 +1316,86 @@ impl Writable for __WindowGravity {
 }
 
 // }}}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// Writes the given `attributes`, checks their size, and reads them back.
+	fn round_trip(attributes: &Attributes) -> Vec<u8> {
+		let mut bytes = vec![];
+		attributes.write_to(&mut bytes).unwrap();
+
+		assert_eq!(attributes.x11_size(), bytes.len());
+		assert_eq!(&Attributes::read_from(&mut &bytes[..]).unwrap(), attributes);
+
+		bytes
+	}
+
+	#[test]
+	fn test_empty_attributes() {
+		let attributes = Attributes::builder().build();
+
+		assert_eq!(round_trip(&attributes), [0, 0, 0, 0]);
+	}
+
+	#[test]
+	fn test_single_attribute() {
+		let mut attributes = Attributes::builder();
+		attributes.bit_gravity(BitGravity::Static);
+		let attributes = attributes.build();
+
+		assert_eq!(attributes.bit_gravity(), Some(&BitGravity::Static));
+		assert_eq!(
+			round_trip(&attributes),
+			[
+				0x00, 0x00, 0x00, 0x10, // mask: `BIT_GRAVITY`
+				0x00, 0x00, 0x00, 10, // bit gravity: `Static`
+			]
+		);
+	}
+
+	#[test]
+	fn test_all_attributes() {
+		let mut attributes = Attributes::builder();
+		attributes
+			.background_pixmap(ParentRelatable::ParentRelative)
+			.background_color(ColorId::new(0x00ff_0000))
+			.border_pixmap(CopyableFromParent::CopyFromParent)
+			.border_color(ColorId::new(0x0000_ff00))
+			.bit_gravity(BitGravity::NorthWest)
+			.window_gravity(WindowGravity::SouthEast)
+			.maintain_contents(MaintainContents::WhenMapped)
+			.maintained_planes(0xffff_ffff)
+			.maintenance_fallback_color(ColorId::new(0))
+			.override_redirect(true)
+			.maintain_windows_under(false)
+			.event_mask(EventMask::EXPOSURE | EventMask::STRUCTURE_NOTIFY)
+			.do_not_propagate_mask(DeviceEventMask::BUTTON_PRESS)
+			.colormap(CopyableFromParent::Other(Colormap::new(0x0000_0020)))
+			.cursor_appearance(Some(CursorAppearance::new(0x0040_0001)));
+		let attributes = attributes.build();
+
+		assert_eq!(
+			round_trip(&attributes),
+			[
+				0x00, 0x00, 0x7f, 0xff, // mask: every attribute
+				0x00, 0x00, 0x00, 0x01, // background pixmap: `ParentRelative`
+				0x00, 0xff, 0x00, 0x00, // background color
+				0x00, 0x00, 0x00, 0x00, // border pixmap: `CopyFromParent`
+				0x00, 0x00, 0xff, 0x00, // border color
+				0x00, 0x00, 0x00, 0x01, // bit gravity: `NorthWest`
+				0x00, 0x00, 0x00, 0x09, // window gravity: `SouthEast`
+				0x00, 0x00, 0x00, 0x01, // maintain contents: `WhenMapped`
+				0xff, 0xff, 0xff, 0xff, // maintained planes
+				0x00, 0x00, 0x00, 0x00, // maintenance fallback color
+				0x00, 0x00, 0x00, 0x01, // override redirect
+				0x00, 0x00, 0x00, 0x00, // maintain windows under
+				0x00, 0x02, 0x80, 0x00, // event mask
+				0x00, 0x00, 0x00, 0x04, // do not propagate mask
+				0x00, 0x00, 0x00, 0x20, // colormap
+				0x00, 0x40, 0x00, 0x01, // cursor appearance
+			]
+		);
+	}
+}
@@ -28,6 +28,7 @@ use xrbk::{
 /// Given a source and destination pixel, represents a bitwise operation applied
 /// to the source and destination to determine the resultant pixel.
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Function {
 	/// The resultant pixel is bitwise zero; that is, it has a `0` for each bit.
 	Clear,
@@ -82,6 +83,7 @@ pub enum Function {
 /// The line can either be [`Thin`] (if it has the special value of zero), or
 /// [`Wide`] for any width greater than or equal to one.
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LineWidth {
 	/// A thin line, having the special width value of zero.
 	Thin,
@@ -124,6 +126,7 @@ impl LineWidth {
 
 /// Defines which sections of a line are drawn.
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LineStyle {
 	/// The full path of the line is drawn.
 	Solid,
@@ -146,6 +149,7 @@ pub enum LineStyle {
 
 /// Defines how the endpoints of a path are drawn.
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CapStyle {
 	/// Equivalent to [`Butt`], except in the case of [`LineWidth::Thin`], where
 	/// the final endpoint is not drawn.
@@ -178,6 +182,7 @@ pub enum CapStyle {
 ///
 /// [`Thick`]: LineWidth::Thick
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum JoinStyle {
 	/// The outer edges of the two lines extend to meet at an angle, if that
 	/// angle is at least 11 degrees.
@@ -199,6 +204,7 @@ pub enum JoinStyle {
 ///
 /// [requests]: crate::x11::request
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FillStyle {
 	/// This is the [foreground color], except for the odd dashes of line
 	/// requests with [`LineStyle::DoubleDash`], where it is the [background
@@ -229,6 +235,7 @@ pub enum FillStyle {
 // Hell if I know what the X11 protocol is talking about for these variants.
 // Really technical language. I imagine it's simply not worth documenting.
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FillRule {
 	EvenOdd,
 	Winding,
@@ -238,6 +245,7 @@ pub enum FillRule {
 ///
 /// [window]: crate::Window
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ChildMode {
 	/// Both source and destination [windows] are additionally clipped by all
 	/// viewable [`InputOutput`] children.
@@ -255,6 +263,7 @@ pub enum ChildMode {
 ///
 /// [`PolyFillArc` request]: crate::x11::request::PolyFillArc
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ArcMode {
 	/// Fills the shape created by tracing the arc and joining its endpoints in
 	/// a straight line.
@@ -332,6 +341,7 @@ pub type ClipMask = Option<Pixmap>;
 /// [`dash_offset`]: GraphicsOptions::dash_offset
 /// [`dashes`]: GraphicsOptions::dashes
 #[derive(Derivative, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derivative(Hash, PartialEq, Eq)]
 pub struct GraphicsOptions {
 	#[derivative(Hash = "ignore", PartialEq = "ignore")]
@@ -395,6 +405,7 @@ impl GraphicsOptions {
 /// [`build()`]: GraphicsOptionsBuilder::build
 /// [`GraphicsOptions` set]: GraphicsOptions
 #[derive(Clone, Default, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GraphicsOptionsBuilder {
 	x11_size: usize,
 
@@ -1328,6 +1339,9 @@ bitflags! {
 	}
 }
 
+#[cfg(feature = "serde")]
+impl_serde_for_bitflags!(GraphicsOptionsMask);
+
 // impl XRBK traits for GraphicsOptions {{{
 
 impl X11Size for GraphicsOptions {
@@ -1606,6 +1620,7 @@ impl Writable for GraphicsOptions {
 // Internal 4-byte representations of types {{{
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __Function(Function);
 
 impl ConstantX11Size for __Function {
@@ -1623,7 +1638,7 @@ impl Readable for __Function {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_u32() {
+		Ok(Self(match u32::read_from(buf)? {
 			discrim if discrim == 0 => Function::Clear,
 			discrim if discrim == 1 => Function::And,
 			discrim if discrim == 2 => Function::AndReverse,
@@ -1680,6 +1695,7 @@ impl Writable for __Function {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __LineWidth(LineWidth);
 
 impl ConstantX11Size for __LineWidth {
@@ -1701,7 +1717,7 @@ impl Readable for __LineWidth {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_u32() {
+		Ok(Self(match u32::read_from(buf)? {
 			discrim if discrim == 0 => LineWidth::Thin,
 			other_width => LineWidth::Thick(other_width as u16),
 		}))
@@ -1722,6 +1738,7 @@ impl Writable for __LineWidth {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __LineStyle(LineStyle);
 
 impl ConstantX11Size for __LineStyle {
@@ -1739,7 +1756,7 @@ impl Readable for __LineStyle {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_u32() {
+		Ok(Self(match u32::read_from(buf)? {
 			discrim if discrim == 0 => LineStyle::Solid,
 			discrim if discrim == 1 => LineStyle::OnOffDash,
 			discrim if discrim == 2 => LineStyle::DoubleDash,
@@ -1764,6 +1781,7 @@ impl Writable for __LineStyle {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __CapStyle(CapStyle);
 
 impl ConstantX11Size for __CapStyle {
@@ -1781,7 +1799,7 @@ impl Readable for __CapStyle {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_u32() {
+		Ok(Self(match u32::read_from(buf)? {
 			discrim if discrim == 0 => CapStyle::NotLast,
 			discrim if discrim == 1 => CapStyle::Butt,
 			discrim if discrim == 2 => CapStyle::Round,
@@ -1808,6 +1826,7 @@ impl Writable for __CapStyle {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __JoinStyle(JoinStyle);
 
 impl ConstantX11Size for __JoinStyle {
@@ -1825,7 +1844,7 @@ impl Readable for __JoinStyle {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_u32() {
+		Ok(Self(match u32::read_from(buf)? {
 			discrim if discrim == 0 => JoinStyle::Miter,
 			discrim if discrim == 1 => JoinStyle::Round,
 			discrim if discrim == 2 => JoinStyle::Bevel,
@@ -1850,6 +1869,7 @@ impl Writable for __JoinStyle {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __FillStyle(FillStyle);
 
 impl ConstantX11Size for __FillStyle {
@@ -1867,7 +1887,7 @@ impl Readable for __FillStyle {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_u32() {
+		Ok(Self(match u32::read_from(buf)? {
 			discrim if discrim == 0 => FillStyle::Solid,
 			discrim if discrim == 1 => FillStyle::Tiled,
 			discrim if discrim == 2 => FillStyle::Stippled,
@@ -1894,6 +1914,7 @@ impl Writable for __FillStyle {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __FillRule(FillRule);
 
 impl ConstantX11Size for __FillRule {
@@ -1911,7 +1932,7 @@ impl Readable for __FillRule {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_u32() {
+		Ok(Self(match u32::read_from(buf)? {
 			discrim if discrim == 0 => FillRule::EvenOdd,
 			discrim if discrim == 1 => FillRule::Winding,
 
@@ -1934,6 +1955,7 @@ impl Writable for __FillRule {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __ChildMode(ChildMode);
 
 impl ConstantX11Size for __ChildMode {
@@ -1951,7 +1973,7 @@ impl Readable for __ChildMode {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_u32() {
+		Ok(Self(match u32::read_from(buf)? {
 			discrim if discrim == 0 => ChildMode::ClipByChildren,
 			discrim if discrim == 1 => ChildMode::IncludeDescendents,
 
@@ -1974,6 +1996,7 @@ impl Writable for __ChildMode {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __ArcMode(ArcMode);
 
 impl ConstantX11Size for __ArcMode {
@@ -1991,7 +2014,7 @@ impl Readable for __ArcMode {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_u32() {
+		Ok(Self(match u32::read_from(buf)? {
 			discrim if discrim == 0 => ArcMode::Chord,
 			discrim if discrim == 1 => ArcMode::PieSlice,
 
@@ -2014,3 +2037,89 @@ impl Writable for __ArcMode {
 }
 
 // }}}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// Writes the given `options`, checks their size, and reads them back.
+	fn round_trip(options: &GraphicsOptions) -> Vec<u8> {
+		let mut bytes = vec![];
+		options.write_to(&mut bytes).unwrap();
+
+		assert_eq!(options.x11_size(), bytes.len());
+		assert_eq!(
+			&GraphicsOptions::read_from(&mut &bytes[..]).unwrap(),
+			options
+		);
+
+		bytes
+	}
+
+	#[test]
+	fn test_empty_graphics_options() {
+		let options = GraphicsOptions::builder().build();
+
+		assert_eq!(round_trip(&options), [0, 0, 0, 0]);
+	}
+
+	#[test]
+	fn test_all_graphics_options() {
+		let mut options = GraphicsOptions::builder();
+		options
+			.function(Function::Xor)
+			.plane_mask(0x0000_00ff)
+			.foreground_color(ColorId::new(0x00ff_ffff))
+			.background_color(ColorId::ZERO)
+			.line_width(LineWidth::new(3))
+			.line_style(LineStyle::DoubleDash)
+			.cap_style(CapStyle::Projecting)
+			.join_style(JoinStyle::Bevel)
+			.fill_style(FillStyle::OpaqueStippled)
+			.fill_rule(FillRule::Winding)
+			.tile(Pixmap::new(0x0020_0001))
+			.stipple(Pixmap::new(0x0020_0002))
+			.tile_stipple_x(Px(-2))
+			.tile_stipple_y(Px(5))
+			.font(Font::new(0x0020_0003))
+			.child_mode(ChildMode::IncludeDescendents)
+			.graphics_exposure(false)
+			.clip_x(Px(-1))
+			.clip_y(Px(7))
+			.clip_mask(None)
+			.dash_offset(Px(6))
+			.dashes(4)
+			.arc_mode(ArcMode::Chord);
+		let options = options.build();
+
+		assert_eq!(
+			round_trip(&options),
+			[
+				0x00, 0x7f, 0xff, 0xff, // mask: every option
+				0x00, 0x00, 0x00, 0x06, // function: `Xor`
+				0x00, 0x00, 0x00, 0xff, // plane mask
+				0x00, 0xff, 0xff, 0xff, // foreground color
+				0x00, 0x00, 0x00, 0x00, // background color
+				0x00, 0x00, 0x00, 0x03, // line width
+				0x00, 0x00, 0x00, 0x02, // line style: `DoubleDash`
+				0x00, 0x00, 0x00, 0x03, // cap style: `Projecting`
+				0x00, 0x00, 0x00, 0x02, // join style: `Bevel`
+				0x00, 0x00, 0x00, 0x03, // fill style: `OpaqueStippled`
+				0x00, 0x00, 0x00, 0x01, // fill rule: `Winding`
+				0x00, 0x20, 0x00, 0x01, // tile
+				0x00, 0x20, 0x00, 0x02, // stipple
+				0xff, 0xff, 0xff, 0xfe, // tile stipple x
+				0x00, 0x00, 0x00, 0x05, // tile stipple y
+				0x00, 0x20, 0x00, 0x03, // font
+				0x00, 0x00, 0x00, 0x01, // child mode: `IncludeDescendents`
+				0x00, 0x00, 0x00, 0x00, // graphics exposure
+				0xff, 0xff, 0xff, 0xff, // clip x
+				0x00, 0x00, 0x00, 0x07, // clip y
+				0x00, 0x00, 0x00, 0x00, // clip mask: `None`
+				0x00, 0x00, 0x00, 0x06, // dash offset
+				0x00, 0x00, 0x00, 0x04, // dashes
+				0x00, 0x00, 0x00, 0x00, // arc mode: `Chord`
+			]
+		);
+	}
+}
@@ -24,6 +24,12 @@ use thiserror::Error;
 
 /// A value representing a non-negative percentage.
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+// `Percentage` checks its own bounds when it is deserialized.
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	allow(clippy::unsafe_derive_deserialize)
+)]
 pub enum PercentOrDefault {
 	/// Resets the percentage to the default percentage.
 	Default,
@@ -108,6 +114,7 @@ impl Display for PercentOrDefault {
 
 /// A value representing a non-negative pitch measured in hertz.
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PitchOrDefault {
 	/// Resets the pitch to the default pitch.
 	Reset,
@@ -117,7 +124,7 @@ pub enum PitchOrDefault {
 	/// The wrapped pitch value can be accessed with [`unwrap()`].
 	///
 	/// [`unwrap()`]: PitchOrDefault::unwrap
-	Pitch(Hz<u8>),
+	Pitch(Hz<u16>),
 }
 
 impl PitchOrDefault {
@@ -135,10 +142,10 @@ impl PitchOrDefault {
 		match value {
 			reset if reset == -1 => Ok(Self::Reset),
 
-			other => u8::try_from(other).map_or(
+			other => u16::try_from(other).map_or(
 				Err(ValueOutOfBounds {
 					min: -1,
-					max: i16::from(u8::MAX),
+					max: i16::MAX,
 					found: other,
 				}),
 				|pitch| Ok(Self::Pitch(Hz(pitch))),
@@ -155,14 +162,14 @@ impl PitchOrDefault {
 	/// Creates a new [`PitchOrDefault::Pitch`] with the specified pitch,
 	/// measured in hertz.
 	#[must_use]
-	pub const fn new_pitch(pitch: Hz<u8>) -> Self {
+	pub const fn new_pitch(pitch: Hz<u16>) -> Self {
 		Self::Pitch(pitch)
 	}
 
 	/// Returns the pitch wrapped by [`PitchOrDefault::Pitch`], or [`None`] in
 	/// the case of [`PitchOrDefault::Reset`].
 	#[must_use]
-	pub const fn unwrap(self) -> Option<Hz<u8>> {
+	pub const fn unwrap(self) -> Option<Hz<u16>> {
 		match self {
 			Self::Reset => None,
 			Self::Pitch(pitch) => Some(pitch),
@@ -181,6 +188,7 @@ impl Display for PitchOrDefault {
 
 /// A value representing a non-negative duration measured in milliseconds.
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DurationOrDefault {
 	/// Resets the duration to the default duration.
 	Reset,
@@ -190,7 +198,7 @@ pub enum DurationOrDefault {
 	/// The wrapped duration can be accessed with [`unwrap()`].
 	///
 	/// [`unwrap()`]: DurationOrDefault::unwrap
-	Duration(Ms<u8>),
+	Duration(Ms<u16>),
 }
 
 impl DurationOrDefault {
@@ -208,10 +216,10 @@ impl DurationOrDefault {
 		match value {
 			reset if reset == -1 => Ok(Self::Reset),
 
-			other => u8::try_from(other).map_or(
+			other => u16::try_from(other).map_or(
 				Err(ValueOutOfBounds {
 					min: -1,
-					max: i16::from(u8::MAX),
+					max: i16::MAX,
 					found: other,
 				}),
 				|duration| Ok(Self::Duration(Ms(duration))),
@@ -228,14 +236,14 @@ impl DurationOrDefault {
 	/// Creates a new [`DurationOrDefault::Duration`] with the specified
 	/// duration, measured in milliseconds.
 	#[must_use]
-	pub const fn new_duration(duration: Ms<u8>) -> Self {
+	pub const fn new_duration(duration: Ms<u16>) -> Self {
 		Self::Duration(duration)
 	}
 
 	/// Returns the duration wrapped by [`DurationOrDefault::Duration`], or
 	/// [`None`] in the case of [`DurationOrDefault::Reset`].
 	#[must_use]
-	pub const fn unwrap(self) -> Option<Ms<u8>> {
+	pub const fn unwrap(self) -> Option<Ms<u16>> {
 		match self {
 			Self::Reset => None,
 			Self::Duration(duration) => Some(duration),
@@ -256,6 +264,11 @@ impl Display for DurationOrDefault {
 ///
 /// LEDs are numbered starting at one, to a maximum of 32.
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Led(u8);
 
 /// Errors generated if the LED number was not in the range `1..=32`.
@@ -298,10 +311,11 @@ impl Led {
 
 /// Whether LEDs are turned on or off.
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LedMode {
-	/// The LED(s) is/are turned on.
-	Off,
 	/// The LED(s) is/are turned off.
+	Off,
+	/// The LED(s) is/are turned on.
 	On,
 }
 
@@ -319,7 +333,7 @@ pub enum LedMode {
 /// - [`auto_repeated_key`]
 /// - [`auto_repeat_mode`]
 ///
-/// [`ChangeKeyboardControl` request]: crate::x11::request::ChangeKeyboardControl
+/// [`ChangeKeyboardControl` request]: crate::x11::request::ChangeKeyboardOptions
 ///
 /// [`key_click_volume`]: KeyboardOptions::key_click_volume
 /// [`bell_volume`]: KeyboardOptions::bell_volume
@@ -330,6 +344,7 @@ pub enum LedMode {
 /// [`auto_repeated_key`]: KeyboardOptions::auto_repeated_key
 /// [`auto_repeat_mode`]: KeyboardOptions::auto_repeat_mode
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KeyboardOptions {
 	x11_size: usize,
 
@@ -367,6 +382,7 @@ impl KeyboardOptions {
 /// [`build()`]: KeyboardOptionsBuilder::build
 /// [`KeyboardOptions` set]: KeyboardOptions
 #[derive(Clone, Default, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KeyboardOptionsBuilder {
 	x11_size: usize,
 
@@ -519,7 +535,7 @@ impl KeyboardOptionsBuilder {
 	/// [configured LED mode]: KeyboardOptionsBuilder::led_mode
 	/// [LED]: Led
 	/// [`Match` error]: crate::x11::error::Match
-	/// [`ChangeKeyboardControl` request]: crate::x11::request::ChangeKeyboardControl
+	/// [`ChangeKeyboardControl` request]: crate::x11::request::ChangeKeyboardOptions
 	pub fn led(&mut self, led: Led) -> &mut Self {
 		if self.led.is_none() {
 			self.x11_size += 4;
@@ -560,7 +576,7 @@ impl KeyboardOptionsBuilder {
 	/// [`auto_repeat_mode`]: KeyboardOptionsBuilder::auto_repeat_mode
 	///
 	/// [`Match` error]: crate::x11::error::Match
-	/// [`ChangeKeyboardControl` request]: crate::x11::request::ChangeKeyboardControl
+	/// [`ChangeKeyboardControl` request]: crate::x11::request::ChangeKeyboardOptions
 	pub fn auto_repeated_key(&mut self, key: Keycode) -> &mut Self {
 		if self.auto_repeated_key.is_none() {
 			self.x11_size += 4;
@@ -749,6 +765,9 @@ bitflags! {
 	}
 }
 
+#[cfg(feature = "serde")]
+impl_serde_for_bitflags!(KeyboardOptionsMask);
+
 impl X11Size for KeyboardOptions {
 	fn x11_size(&self) -> usize {
 		self.x11_size
@@ -863,6 +882,7 @@ impl Writable for KeyboardOptions {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __PercentOrDefault(PercentOrDefault);
 
 impl ConstantX11Size for __PercentOrDefault {
@@ -884,7 +904,7 @@ impl Readable for __PercentOrDefault {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_i32() {
+		Ok(Self(match i32::read_from(buf)? {
 			reset if reset == -1 => PercentOrDefault::Default,
 
 			value => match u8::try_from(value) {
@@ -919,6 +939,7 @@ impl Writable for __PercentOrDefault {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __PitchOrDefault(PitchOrDefault);
 
 impl ConstantX11Size for __PitchOrDefault {
@@ -940,16 +961,16 @@ impl Readable for __PitchOrDefault {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_i32() {
+		Ok(Self(match i32::read_from(buf)? {
 			reset if reset == -1 => PitchOrDefault::Reset,
 
-			other => match u8::try_from(other) {
+			other => match u16::try_from(other) {
 				Ok(pitch) => PitchOrDefault::Pitch(Hz(pitch)),
 
 				_ => {
 					return Err(ReadError::Other(Box::new(ValueOutOfBounds {
 						min: -1,
-						max: i32::from(u8::MAX),
+						max: i32::from(u16::MAX),
 						found: other,
 					})))
 				},
@@ -972,6 +993,7 @@ impl Writable for __PitchOrDefault {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __DurationOrDefault(DurationOrDefault);
 
 impl ConstantX11Size for __DurationOrDefault {
@@ -993,16 +1015,16 @@ impl Readable for __DurationOrDefault {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_i32() {
+		Ok(Self(match i32::read_from(buf)? {
 			reset if reset == -1 => DurationOrDefault::Reset,
 
-			other => match u8::try_from(other) {
+			other => match u16::try_from(other) {
 				Ok(duration) => DurationOrDefault::Duration(Ms(duration)),
 
 				_ => {
 					return Err(ReadError::Other(Box::new(ValueOutOfBounds {
 						min: -1,
-						max: i32::from(u8::MAX),
+						max: i32::from(u16::MAX),
 						found: other,
 					})))
 				},
@@ -1025,6 +1047,7 @@ impl Writable for __DurationOrDefault {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __Led(Led);
 
 impl ConstantX11Size for __Led {
@@ -1042,7 +1065,7 @@ impl Readable for __Led {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match u8::try_from(buf.get_u32()) {
+		Ok(Self(match u8::try_from(u32::read_from(buf)?) {
 			Ok(zero) if zero == 0 => return Err(ReadError::Other(Box::new(LedError::Zero))),
 			Ok(high) if high > 32 => {
 				return Err(ReadError::Other(Box::new(LedError::TooHigh(high))))
@@ -1066,6 +1089,7 @@ impl Writable for __Led {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __LedMode(LedMode);
 
 impl ConstantX11Size for __LedMode {
@@ -1083,7 +1107,7 @@ impl Readable for __LedMode {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_u32() {
+		Ok(Self(match u32::read_from(buf)? {
 			off if off == 0 => LedMode::Off,
 			on if on == 1 => LedMode::On,
 
@@ -1106,6 +1130,7 @@ impl Writable for __LedMode {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __Keycode(Keycode);
 
 impl ConstantX11Size for __Keycode {
@@ -1124,7 +1149,7 @@ impl Readable for __Keycode {
 		Self: Sized,
 	{
 		Ok(Self(Keycode::new(
-			buf.get_u32()
+			u32::read_from(buf)?
 				.try_into()
 				.expect("must fit into u8; represents u8 value"),
 		)))
@@ -1142,6 +1167,7 @@ impl Writable for __Keycode {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __ToggleOrDefault(ToggleOrDefault);
 
 impl ConstantX11Size for __ToggleOrDefault {
@@ -1159,7 +1185,7 @@ impl Readable for __ToggleOrDefault {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_u32() {
+		Ok(Self(match u32::read_from(buf)? {
 			discrim if discrim == 0 => ToggleOrDefault::Disabled,
 			discrim if discrim == 1 => ToggleOrDefault::Enabled,
 
@@ -2,15 +2,17 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::{StackMode, Window};
+use crate::{x11::event::ConfigureWindowRequest, StackMode, Window};
 
 use crate::{set::__Px, unit::Px};
 use bitflags::bitflags;
 use xrbk::{
+	read_padding,
 	Buf,
 	BufMut,
 	ConstantX11Size,
 	ReadError,
+	ReadMode,
 	ReadResult,
 	Readable,
 	Writable,
@@ -46,6 +48,7 @@ use xrbk_macro::{ConstantX11Size, Readable, Writable, X11Size};
 /// [`sibling`]: WindowConfig::sibling
 /// [`stack_mode`]: WindowConfig::stack_mode
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowConfig {
 	/// Total [`X11Size`] of this `WindowConfig`.
 	///
@@ -76,6 +79,51 @@ impl WindowConfig {
 	pub const fn builder() -> WindowConfigBuilder {
 		WindowConfigBuilder::new()
 	}
+
+	/// Creates a `WindowConfig` with exactly the options configured in the
+	/// given [`ConfigureWindowRequest` event].
+	///
+	/// This is the configuration with which the [`ConfigureWindow` request]
+	/// that generated the [event] was sent, so it can be used to send that
+	/// request on the client's behalf.
+	///
+	/// [event]: crate::message::Event
+	/// [`ConfigureWindowRequest` event]: ConfigureWindowRequest
+	/// [`ConfigureWindow` request]: crate::x11::request::ConfigureWindow
+	#[must_use]
+	pub fn from_request(request: &ConfigureWindowRequest) -> Self {
+		let mut builder = Self::builder();
+		let mask = request.mask;
+
+		if mask.contains(WindowConfigMask::X) {
+			builder.x(request.geometry.x);
+		}
+		if mask.contains(WindowConfigMask::Y) {
+			builder.y(request.geometry.y);
+		}
+		if mask.contains(WindowConfigMask::WIDTH) {
+			builder.width(request.geometry.width);
+		}
+		if mask.contains(WindowConfigMask::HEIGHT) {
+			builder.height(request.geometry.height);
+		}
+
+		if mask.contains(WindowConfigMask::BORDER_WIDTH) {
+			builder.border_width(request.border_width);
+		}
+
+		if mask.contains(WindowConfigMask::SIBLING) {
+			if let Some(sibling) = request.sibling {
+				builder.sibling(sibling);
+			}
+		}
+
+		if mask.contains(WindowConfigMask::STACK_MODE) {
+			builder.stack_mode(request.stack_mode);
+		}
+
+		builder.build()
+	}
 }
 
 /// A builder used to construct a new [`WindowConfig` set].
@@ -87,6 +135,7 @@ impl WindowConfig {
 /// [`build()`]: WindowConfigBuilder::build
 /// [`WindowConfig` set]: WindowConfig
 #[derive(Clone, Default, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowConfigBuilder {
 	x11_size: usize,
 
@@ -115,7 +164,8 @@ impl WindowConfigBuilder {
 	#[must_use]
 	pub const fn new() -> Self {
 		Self {
-			x11_size: WindowConfigMask::X11_SIZE,
+			// 2 unused bytes after the mask.
+			x11_size: WindowConfigMask::X11_SIZE + 2,
 
 			mask: WindowConfigMask::empty(),
 
@@ -357,9 +407,16 @@ impl Readable for WindowConfig {
 	where
 		Self: Sized,
 	{
-		let mask = WindowConfigMask::read_from(buf)?;
+		Self::read_from_with_mode(buf, ReadMode::Lenient)
+	}
+
+	fn read_from_with_mode(buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		let mask = WindowConfigMask::read_from_with_mode(buf, mode)?;
 		// 2 unused bytes after the mask.
-		buf.advance(2);
+		read_padding(buf, 2, mode)?;
 
 		let mut x11_size = mask.x11_size() + 2;
 
@@ -440,6 +497,7 @@ impl Writable for WindowConfig {
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct __StackMode(StackMode);
 
 impl ConstantX11Size for __StackMode {
@@ -457,7 +515,7 @@ impl Readable for __StackMode {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_u32() {
+		Ok(Self(match u32::read_from(buf)? {
 			discrim if discrim == 0 => StackMode::Above,
 			discrim if discrim == 1 => StackMode::Below,
 			discrim if discrim == 2 => StackMode::TopIf,
@@ -552,3 +610,16 @@ bitflags! {
 		const STACK_MODE = 0x0040;
 	}
 }
+
+impl_display_for_bitflags!(WindowConfigMask {
+	X => "X",
+	Y => "Y",
+	WIDTH => "Width",
+	HEIGHT => "Height",
+	BORDER_WIDTH => "BorderWidth",
+	SIBLING => "Sibling",
+	STACK_MODE => "StackMode",
+});
+
+#[cfg(feature = "serde")]
+impl_serde_for_bitflags!(WindowConfigMask);
@@ -8,6 +8,7 @@
 )]
 
 use crate::{
+	connection::ImageEndianness,
 	unit::{Mm, Px},
 	Colormap,
 	EventMask,
@@ -58,6 +59,11 @@ use xrbk_macro::{derive_xrb, new, unwrap, ConstantX11Size, Readable, Wrap, Writa
 	Readable,
 	Writable,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct ColorId(u32);
 
 impl ColorId {
@@ -92,6 +98,7 @@ impl ColorId {
 	Readable,
 	Writable,
 )]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RgbColor(
 	/// Red.
 	pub u16,
@@ -170,6 +177,7 @@ impl RgbColor {
 ///
 /// This is returned from [`RgbColor::from_hex`].
 #[derive(Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RgbColorTooHigh;
 
 impl RgbColor {
@@ -304,6 +312,11 @@ impl From<RgbColor> for (u32, u32, u32) {
 	Writable,
 	Wrap,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct VisualId(u32);
 
 derive_xrb! {
@@ -320,6 +333,7 @@ derive_xrb! {
 		Readable,
 		Writable,
 	)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct Format {
 		pub depth: u8,
 		pub bits_per_pixel: u8,
@@ -330,6 +344,7 @@ derive_xrb! {
 
 derive_xrb! {
 	#[derive(Clone, Eq, PartialEq, Hash, Debug, new, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct Screen {
 		pub root: Window,
 		pub default_colormap: Colormap,
@@ -360,6 +375,7 @@ derive_xrb! {
 	}
 
 	#[derive(Clone, Eq, PartialEq, Hash, Debug, new, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct Depth {
 		pub depth: u8,
 		_,
@@ -373,7 +389,41 @@ derive_xrb! {
 	}
 }
 
+impl Screen {
+	/// Returns an iterator over every [`VisualType`] supported by this
+	/// `Screen`, along with the [`Depth`] it is supported at.
+	pub fn visuals(&self) -> impl Iterator<Item = (&Depth, &VisualType)> {
+		self.allowed_depths
+			.iter()
+			.flat_map(|depth| depth.visuals.iter().map(move |visual| (depth, visual)))
+	}
+
+	/// Returns the [`VisualType`] with the given `visual_id`, if it is
+	/// supported by this `Screen`.
+	#[must_use]
+	pub fn visual_by_id(&self, visual_id: VisualId) -> Option<&VisualType> {
+		self.visuals()
+			.map(|(_, visual)| visual)
+			.find(|visual| visual.visual_id == visual_id)
+	}
+
+	/// Returns the first [`VisualType`] of the given `class` supported by this
+	/// `Screen` at the given `depth`.
+	#[must_use]
+	pub fn find_visual(&self, class: VisualClass, depth: u8) -> Option<(&Depth, &VisualType)> {
+		self.visuals()
+			.find(|(allowed, visual)| allowed.depth == depth && visual.class == class)
+	}
+
+	/// Returns the [`VisualType`] of the `root_visual`.
+	#[must_use]
+	pub fn root_visual_type(&self) -> Option<&VisualType> {
+		self.visual_by_id(self.root_visual)
+	}
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VisualClass {
 	StaticGray,
 	GrayScale,
@@ -385,12 +435,484 @@ pub enum VisualClass {
 
 derive_xrb! {
 	#[derive(Clone, Eq, PartialEq, Hash, Debug, new, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct VisualType {
 		pub visual_id: VisualId,
 		pub class: VisualClass,
 		pub bits_per_rgb_value: u8,
 		pub colormap_entries: u16,
-		pub color_mask: RgbColor,
+		pub red_mask: u32,
+		pub green_mask: u32,
+		pub blue_mask: u32,
 		[_; 4],
 	}
 }
+
+impl VisualType {
+	/// Returns how far the given color channel `mask` is shifted from the
+	/// least significant bit.
+	///
+	/// A `mask` of `0` has a shift of `0`.
+	#[must_use]
+	pub const fn mask_shift(mask: u32) -> u32 {
+		if mask == 0 {
+			0
+		} else {
+			mask.trailing_zeros()
+		}
+	}
+
+	/// Returns the number of bits in the given color channel `mask`.
+	#[must_use]
+	pub const fn mask_width(mask: u32) -> u32 {
+		mask.count_ones()
+	}
+
+	/// Returns how far the `red_mask` is shifted from the least significant
+	/// bit.
+	#[must_use]
+	pub const fn red_shift(&self) -> u32 {
+		Self::mask_shift(self.red_mask)
+	}
+
+	/// Returns the number of bits in the `red_mask`.
+	#[must_use]
+	pub const fn red_width(&self) -> u32 {
+		Self::mask_width(self.red_mask)
+	}
+
+	/// Returns how far the `green_mask` is shifted from the least significant
+	/// bit.
+	#[must_use]
+	pub const fn green_shift(&self) -> u32 {
+		Self::mask_shift(self.green_mask)
+	}
+
+	/// Returns the number of bits in the `green_mask`.
+	#[must_use]
+	pub const fn green_width(&self) -> u32 {
+		Self::mask_width(self.green_mask)
+	}
+
+	/// Returns how far the `blue_mask` is shifted from the least significant
+	/// bit.
+	#[must_use]
+	pub const fn blue_shift(&self) -> u32 {
+		Self::mask_shift(self.blue_mask)
+	}
+
+	/// Returns the number of bits in the `blue_mask`.
+	#[must_use]
+	pub const fn blue_width(&self) -> u32 {
+		Self::mask_width(self.blue_mask)
+	}
+
+	/// Packs the given [`RgbColor`] into a pixel value using this visual's
+	/// color channel masks.
+	///
+	/// Each channel is scaled down to the width of its mask by discarding its
+	/// least significant bits. This is only meaningful for
+	/// [`VisualClass::TrueColor`] and [`VisualClass::DirectColor`] visuals.
+	#[must_use]
+	pub fn pack(&self, RgbColor(red, green, blue): RgbColor) -> ColorId {
+		let channel = |value: u16, mask: u32| {
+			let width = Self::mask_width(mask).min(u16::BITS);
+			let value = u32::from(value) >> (u16::BITS - width);
+
+			(value << Self::mask_shift(mask)) & mask
+		};
+
+		ColorId(
+			channel(red, self.red_mask)
+				| channel(green, self.green_mask)
+				| channel(blue, self.blue_mask),
+		)
+	}
+
+	/// Unpacks the given `pixel` value into an [`RgbColor`] using this
+	/// visual's color channel masks.
+	///
+	/// Each channel is scaled up from the width of its mask to the full range
+	/// of a `u16`, so this is the inverse of [`pack`].
+	///
+	/// [`pack`]: VisualType::pack
+	#[must_use]
+	pub fn unpack(&self, ColorId(pixel): ColorId) -> RgbColor {
+		let channel = |mask: u32| {
+			let width = Self::mask_width(mask).min(u16::BITS);
+			let max = (1_u64 << width) - 1;
+
+			if max == 0 {
+				return 0;
+			}
+
+			let value = u64::from((pixel & mask) >> Self::mask_shift(mask));
+
+			u16::try_from(value * u64::from(u16::MAX) / max).unwrap_or(u16::MAX)
+		};
+
+		RgbColor(
+			channel(self.red_mask),
+			channel(self.green_mask),
+			channel(self.blue_mask),
+		)
+	}
+}
+
+/// The layout of the pixels of a [`Zpixmap`] image of a particular depth and
+/// [`VisualType`], such as the `data` of a [`PlaceImage` request].
+///
+/// Each row of the image is padded to the [`Format`]'s `scanline_pad`, and each
+/// pixel value is stored in the X server's image byte order.
+///
+/// Only [`Format`]s with a whole number of bytes per pixel, up to 32 bits, are
+/// supported.
+///
+/// [`Zpixmap`]: crate::x11::request::PlaceImageFormat::Zpixmap
+/// [`PlaceImage` request]: crate::x11::request::PlaceImage
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ImageLayout {
+	/// The [`Format`] of images of this depth.
+	pub format: Format,
+	/// The byte order of pixel values.
+	pub byte_order: ImageEndianness,
+	/// The [`VisualType`] which pixel values are interpreted with.
+	pub visual: VisualType,
+}
+
+impl ImageLayout {
+	/// Creates the `ImageLayout` of images of the given `depth` and `visual`.
+	///
+	/// The [`Format`] for the `depth` is found in the given `formats`, and
+	/// `byte_order` is the X server's image byte order, both of which are
+	/// provided in the [connection setup].
+	///
+	/// Returns [`None`] if there is no [`Format`] for the `depth`, or if it is
+	/// not supported.
+	///
+	/// [connection setup]: crate::connection::ConnectionSuccess
+	#[must_use]
+	pub fn new(
+		formats: &[Format], byte_order: ImageEndianness, depth: u8, visual: &VisualType,
+	) -> Option<Self> {
+		let format = *formats.iter().find(|format| format.depth == depth)?;
+
+		let supported = matches!(format.bits_per_pixel, 8 | 16 | 24 | 32)
+			&& format.scanline_pad != 0
+			&& format.scanline_pad % 8 == 0;
+
+		supported.then(|| Self {
+			format,
+			byte_order,
+			visual: visual.clone(),
+		})
+	}
+
+	/// Returns the number of bytes used by each pixel.
+	const fn bytes_per_pixel(&self) -> usize {
+		(self.format.bits_per_pixel / 8) as usize
+	}
+
+	/// Returns the number of bytes in each row of an image of the given
+	/// `width`, including the padding at the end of the row.
+	#[must_use]
+	pub fn bytes_per_row(&self, width: u16) -> usize {
+		let pad = usize::from(self.format.scanline_pad);
+		let bits = usize::from(width) * usize::from(self.format.bits_per_pixel);
+
+		bits.div_ceil(pad) * pad / 8
+	}
+
+	/// Returns the number of bytes in an image of the given `width` and
+	/// `height`.
+	#[must_use]
+	pub fn required_len(&self, width: u16, height: u16) -> usize {
+		self.bytes_per_row(width) * usize::from(height)
+	}
+
+	/// Returns the range of `data` containing the pixel at the given `x` and
+	/// `y` coordinates in an image of the given `width`.
+	fn pixel_range(&self, width: u16, x: u16, y: u16) -> std::ops::Range<usize> {
+		let start =
+			usize::from(y) * self.bytes_per_row(width) + usize::from(x) * self.bytes_per_pixel();
+
+		start..(start + self.bytes_per_pixel())
+	}
+
+	/// Writes the `color` of the pixel at the given `x` and `y` coordinates in
+	/// the `data` of an image of the given `width`.
+	///
+	/// # Panics
+	/// Panics if the pixel is not within `data`.
+	pub fn put_pixel(&self, data: &mut [u8], width: u16, x: u16, y: u16, color: RgbColor) {
+		let ColorId(pixel_value) = self.visual.pack(color);
+		let pixel = &mut data[self.pixel_range(width, x, y)];
+
+		match self.byte_order {
+			ImageEndianness::LittleEndian => {
+				pixel.copy_from_slice(&pixel_value.to_le_bytes()[..pixel.len()]);
+			},
+
+			ImageEndianness::BigEndian => {
+				let bytes = pixel_value.to_be_bytes();
+				pixel.copy_from_slice(&bytes[(bytes.len() - pixel.len())..]);
+			},
+		}
+	}
+
+	/// Returns the color of the pixel at the given `x` and `y` coordinates in
+	/// the `data` of an image of the given `width`.
+	///
+	/// # Panics
+	/// Panics if the pixel is not within `data`.
+	#[must_use]
+	pub fn get_pixel(&self, data: &[u8], width: u16, x: u16, y: u16) -> RgbColor {
+		let pixel = &data[self.pixel_range(width, x, y)];
+		let mut bytes = [0; 4];
+
+		let pixel_value = match self.byte_order {
+			ImageEndianness::LittleEndian => {
+				bytes[..pixel.len()].copy_from_slice(pixel);
+
+				u32::from_le_bytes(bytes)
+			},
+
+			ImageEndianness::BigEndian => {
+				let start = bytes.len() - pixel.len();
+				bytes[start..].copy_from_slice(pixel);
+
+				u32::from_be_bytes(bytes)
+			},
+		};
+
+		self.visual.unpack(ColorId(pixel_value))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn visual(id: u32, class: VisualClass, masks: (u32, u32, u32)) -> VisualType {
+		let (red_mask, green_mask, blue_mask) = masks;
+
+		VisualType::new(
+			VisualId::new(id),
+			class,
+			8,
+			256,
+			red_mask,
+			green_mask,
+			blue_mask,
+		)
+	}
+
+	fn screen() -> Screen {
+		let true_color = (0xff_0000, 0xff00, 0xff);
+
+		Screen::new(
+			Window::new(0x0000_0512),
+			Colormap::new(0x20),
+			ColorId::new(0x00ff_ffff),
+			ColorId::ZERO,
+			EventMask::empty(),
+			Px(1280),
+			Px(1024),
+			Mm(338),
+			Mm(270),
+			1,
+			1,
+			VisualId::new(0x21),
+			MaintainContents::Never,
+			false,
+			24,
+			vec![
+				Depth::new(
+					24,
+					vec![
+						visual(0x21, VisualClass::TrueColor, true_color),
+						visual(0x22, VisualClass::DirectColor, true_color),
+					],
+				),
+				Depth::new(1, vec![]),
+				Depth::new(8, vec![visual(0x30, VisualClass::PseudoColor, (0, 0, 0))]),
+				Depth::new(32, vec![visual(0x5b, VisualClass::TrueColor, true_color)]),
+			],
+		)
+	}
+
+	#[test]
+	fn test_screen_visual_lookup() {
+		let screen = screen();
+
+		let ids: Vec<(u8, u32)> = screen
+			.visuals()
+			.map(|(depth, visual)| (depth.depth, visual.visual_id.unwrap()))
+			.collect();
+		assert_eq!(ids, [(24, 0x21), (24, 0x22), (8, 0x30), (32, 0x5b)]);
+
+		assert_eq!(
+			screen
+				.visual_by_id(VisualId::new(0x30))
+				.map(|visual| visual.class),
+			Some(VisualClass::PseudoColor),
+		);
+		assert_eq!(screen.visual_by_id(VisualId::new(0x99)), None);
+
+		assert_eq!(
+			screen.root_visual_type().map(|visual| visual.visual_id),
+			Some(VisualId::new(0x21)),
+		);
+
+		let (depth, visual) = screen.find_visual(VisualClass::TrueColor, 32).unwrap();
+		assert_eq!(depth.depth, 32);
+		assert_eq!(visual.visual_id, VisualId::new(0x5b));
+
+		let (depth, visual) = screen.find_visual(VisualClass::PseudoColor, 8).unwrap();
+		assert_eq!(depth.depth, 8);
+		assert_eq!(visual.visual_id, VisualId::new(0x30));
+
+		assert_eq!(screen.find_visual(VisualClass::PseudoColor, 24), None);
+		assert_eq!(screen.find_visual(VisualClass::TrueColor, 16), None);
+	}
+
+	#[test]
+	fn test_visual_type_masks() {
+		let rgb888 = visual(0x21, VisualClass::TrueColor, (0xff_0000, 0xff00, 0xff));
+
+		assert_eq!((rgb888.red_shift(), rgb888.red_width()), (16, 8));
+		assert_eq!((rgb888.green_shift(), rgb888.green_width()), (8, 8));
+		assert_eq!((rgb888.blue_shift(), rgb888.blue_width()), (0, 8));
+		assert_eq!(rgb888.pack(RgbColor::ORANGE), ColorId::new(0x00ff_8000));
+		assert_eq!(rgb888.pack(RgbColor::WHITE), ColorId::new(0x00ff_ffff));
+
+		let rgb565 = visual(0x40, VisualClass::TrueColor, (0xf800, 0x07e0, 0x001f));
+
+		assert_eq!((rgb565.red_shift(), rgb565.red_width()), (11, 5));
+		assert_eq!((rgb565.green_shift(), rgb565.green_width()), (5, 6));
+		assert_eq!((rgb565.blue_shift(), rgb565.blue_width()), (0, 5));
+		assert_eq!(rgb565.pack(RgbColor::WHITE), ColorId::new(0xffff));
+		assert_eq!(rgb565.pack(RgbColor::GREEN), ColorId::new(0x07e0));
+		assert_eq!(rgb565.pack(RgbColor::GRAY), ColorId::new(0x8410));
+
+		assert_eq!(VisualType::mask_shift(0), 0);
+		assert_eq!(VisualType::mask_width(0), 0);
+	}
+
+	#[test]
+	fn test_visual_type_unpack() {
+		let rgb565 = visual(0x40, VisualClass::TrueColor, (0xf800, 0x07e0, 0x001f));
+
+		assert_eq!(rgb565.unpack(ColorId::new(0xffff)), RgbColor::WHITE);
+		assert_eq!(rgb565.unpack(ColorId::new(0x07e0)), RgbColor::GREEN);
+
+		// Unpacking and then packing again gives the same pixel value.
+		for pixel in [0x0000, 0x8410, 0x1234, 0xfedc] {
+			let pixel = ColorId::new(pixel);
+			assert_eq!(rgb565.pack(rgb565.unpack(pixel)), pixel);
+		}
+	}
+
+	fn image_layout(depth: u8, byte_order: ImageEndianness) -> ImageLayout {
+		let formats = [
+			Format::new(1, 1, 32),
+			Format::new(16, 16, 32),
+			Format::new(24, 32, 32),
+		];
+
+		let visual = match depth {
+			16 => visual(0x40, VisualClass::TrueColor, (0xf800, 0x07e0, 0x001f)),
+			_ => visual(0x21, VisualClass::TrueColor, (0xff_0000, 0xff00, 0xff)),
+		};
+
+		ImageLayout::new(&formats, byte_order, depth, &visual).unwrap()
+	}
+
+	#[test]
+	fn test_image_layout_row_padding() {
+		let formats = [Format::new(1, 1, 32), Format::new(8, 8, 32)];
+		let visual = visual(0x30, VisualClass::PseudoColor, (0, 0, 0));
+
+		// Bitmaps and unknown depths are not supported.
+		assert_eq!(
+			ImageLayout::new(&formats, ImageEndianness::LittleEndian, 1, &visual),
+			None
+		);
+		assert_eq!(
+			ImageLayout::new(&formats, ImageEndianness::LittleEndian, 4, &visual),
+			None
+		);
+
+		let layout = ImageLayout::new(&formats, ImageEndianness::LittleEndian, 8, &visual).unwrap();
+		assert_eq!(layout.bytes_per_row(1), 4);
+		assert_eq!(layout.bytes_per_row(5), 8);
+		assert_eq!(layout.required_len(5, 3), 24);
+
+		let layout = image_layout(16, ImageEndianness::LittleEndian);
+		assert_eq!(layout.bytes_per_row(1), 4);
+		assert_eq!(layout.bytes_per_row(2), 4);
+		assert_eq!(layout.bytes_per_row(3), 8);
+		assert_eq!(layout.required_len(3, 2), 16);
+
+		let layout = image_layout(24, ImageEndianness::LittleEndian);
+		assert_eq!(layout.bytes_per_row(3), 12);
+		assert_eq!(layout.required_len(3, 2), 24);
+		assert_eq!(layout.required_len(0, 2), 0);
+	}
+
+	#[test]
+	fn test_image_layout_depth_24_pixels() {
+		for byte_order in [ImageEndianness::LittleEndian, ImageEndianness::BigEndian] {
+			let layout = image_layout(24, byte_order);
+
+			let mut data = vec![0; layout.required_len(3, 2)];
+			layout.put_pixel(&mut data, 3, 2, 1, RgbColor::ORANGE);
+			layout.put_pixel(&mut data, 3, 0, 0, RgbColor::SKY_BLUE);
+
+			let pixel = match byte_order {
+				ImageEndianness::LittleEndian => [0x00, 0x80, 0xff, 0x00],
+				ImageEndianness::BigEndian => [0x00, 0xff, 0x80, 0x00],
+			};
+			// The second row begins after the 12 bytes of the first row.
+			assert_eq!(data[20..24], pixel);
+
+			assert_eq!(
+				layout.get_pixel(&data, 3, 2, 1),
+				RgbColor(0xffff, 0x8080, 0)
+			);
+			assert_eq!(
+				layout.get_pixel(&data, 3, 0, 0),
+				RgbColor(0, 0x8080, 0xffff)
+			);
+			assert_eq!(layout.get_pixel(&data, 3, 1, 0), RgbColor::BLACK);
+		}
+	}
+
+	#[test]
+	fn test_image_layout_rgb565_pixels() {
+		for byte_order in [ImageEndianness::LittleEndian, ImageEndianness::BigEndian] {
+			let layout = image_layout(16, byte_order);
+
+			// Each row of 3 pixels is padded from 6 to 8 bytes.
+			let mut data = vec![0; layout.required_len(3, 2)];
+			layout.put_pixel(&mut data, 3, 0, 1, RgbColor::GRAY);
+			layout.put_pixel(&mut data, 3, 2, 1, RgbColor::WHITE);
+
+			let gray = match byte_order {
+				ImageEndianness::LittleEndian => [0x10, 0x84],
+				ImageEndianness::BigEndian => [0x84, 0x10],
+			};
+			assert_eq!(data[8..10], gray);
+			assert_eq!(data[12..14], [0xff, 0xff]);
+			assert_eq!(data[14..16], [0, 0]);
+
+			assert_eq!(layout.get_pixel(&data, 3, 2, 1), RgbColor::WHITE);
+
+			// Reading a pixel back and writing it again gives the same bytes.
+			let gray = layout.get_pixel(&data, 3, 0, 1);
+			let mut rewritten = vec![0; data.len()];
+			layout.put_pixel(&mut rewritten, 3, 0, 1, gray);
+			assert_eq!(rewritten[8..10], data[8..10]);
+		}
+	}
+}
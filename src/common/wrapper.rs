@@ -68,6 +68,7 @@ macro_rules! impl_writable {
 
 /// Values which may be copied from the 'parent'.
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CopyableFromParent<T> {
 	/// A value is initialized by copying the matching value of the parent.
 	///
@@ -91,7 +92,7 @@ impl_constant_x11_size!(CopyableFromParent<WindowClass> { // {{{
 });
 
 impl_readable!(CopyableFromParent<WindowClass>: buf {
-	match buf.get_u32() {
+	match u16::read_from(buf)? {
 		discrim if discrim == 0 => Ok(Self::CopyFromParent),
 
 		discrim if discrim == 1 => Ok(Self::Other(WindowClass::InputOutput)),
@@ -103,7 +104,7 @@ impl_readable!(CopyableFromParent<WindowClass>: buf {
 
 impl_writable!(CopyableFromParent<WindowClass>: &self, buf {
 	match self {
-		Self::CopyFromParent => buf.put_u32(0),
+		Self::CopyFromParent => buf.put_u16(0),
 		Self::Other(class) => class.write_to(buf)?,
 	}
 
@@ -115,7 +116,7 @@ impl_constant_x11_size!(CopyableFromParent<Pixmap> {
 });
 
 impl_readable!(CopyableFromParent<Pixmap>: buf {
-	Ok(match buf.get_u32() {
+	Ok(match u32::read_from(buf)? {
 		discrim if discrim == 0 => Self::CopyFromParent,
 		val => Self::Other(Pixmap::new(val)),
 	})
@@ -135,7 +136,7 @@ impl_constant_x11_size!(CopyableFromParent<VisualId> {
 });
 
 impl_readable!(CopyableFromParent<VisualId>: buf {
-	Ok(match buf.get_u32() {
+	Ok(match u32::read_from(buf)? {
 		discrim if discrim == 0 => Self::CopyFromParent,
 		val => Self::Other(VisualId::new(val)),
 	})
@@ -155,7 +156,7 @@ impl_constant_x11_size!(CopyableFromParent<Colormap> {
 });
 
 impl_readable!(CopyableFromParent<Colormap>: buf {
-	Ok(match buf.get_u32() {
+	Ok(match u32::read_from(buf)? {
 		discrim if discrim == 0 => Self::CopyFromParent,
 		val => Self::Other(Colormap::new(val)),
 	})
@@ -175,7 +176,7 @@ impl_constant_x11_size!(CopyableFromParent<u8> {
 });
 
 impl_readable!(CopyableFromParent<u8>: buf {
-	Ok(match buf.get_u8() {
+	Ok(match u8::read_from(buf)? {
 		discrim if discrim == 0 => Self::CopyFromParent,
 		val => Self::Other(val),
 	})
@@ -183,7 +184,7 @@ impl_readable!(CopyableFromParent<u8>: buf {
 
 impl_writable!(CopyableFromParent<u8>: &self, buf {
 	match self {
-		Self::CopyFromParent => buf.put_u32(0),
+		Self::CopyFromParent => buf.put_u8(0),
 		Self::Other(val) => val.write_to(buf)?,
 	}
 
@@ -199,6 +200,7 @@ impl_writable!(CopyableFromParent<u8>: &self, buf {
 /// [pixmaps]: Pixmap
 /// [pixmap]: Pixmap
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ParentRelatable<T> {
 	/// The value of the 'parent' is used, as long as the parent has the same
 	/// `depth`.
@@ -213,7 +215,7 @@ impl_constant_x11_size!(ParentRelatable<Option<Pixmap>> { // {{{
 });
 
 impl_readable!(ParentRelatable<Option<Pixmap>>: buf {
-	Ok(match buf.get_u32() {
+	Ok(match u32::read_from(buf)? {
 		discrim if discrim == 0 => Self::Other(None),
 
 		discrim if discrim == 1 => Self::ParentRelative,
@@ -237,6 +239,7 @@ impl_writable!(ParentRelatable<Option<Pixmap>>: &self, buf {
 ///
 /// [`Any`]: Any::Any
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Any<T> {
 	/// Any value.
 	Any,
@@ -250,7 +253,7 @@ impl_constant_x11_size!(Any<Atom> { // {{{
 });
 
 impl_readable!(Any<Atom>: buf {
-	Ok(match buf.get_u32() {
+	Ok(match u32::read_from(buf)? {
 		discrim if discrim == 0 => Self::Any,
 		val => Self::Other(Atom::new(val)),
 	})
@@ -270,7 +273,7 @@ impl_constant_x11_size!(Any<Button> {
 });
 
 impl_readable!(Any<Button>: buf {
-	Ok(match buf.get_u8() {
+	Ok(match u8::read_from(buf)? {
 		discrim if discrim == 0 => Self::Any,
 		val => Self::Other(Button::new(val)),
 	})
@@ -290,7 +293,7 @@ impl_constant_x11_size!(Any<Keycode> {
 });
 
 impl_readable!(Any<Keycode>: buf {
-	Ok(match buf.get_u8() {
+	Ok(match u8::read_from(buf)? {
 		discrim if discrim == 0 => Self::Any,
 		val => Self::Other(Keycode::new(val)),
 	})
@@ -307,6 +310,7 @@ impl_writable!(Any<Keycode>: &self, buf {
 
 /// A time which may simply fill in for the current server time.
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CurrentableTime {
 	/// The X server should treat this time as its current time.
 	CurrentTime,
@@ -315,12 +319,40 @@ pub enum CurrentableTime {
 	Other(Timestamp),
 }
 
+impl CurrentableTime {
+	/// Returns the [`Timestamp`] of this time, or the given `current` time if
+	/// this is [`CurrentTime`].
+	///
+	/// [`CurrentTime`]: CurrentableTime::CurrentTime
+	#[must_use]
+	pub const fn unwrap_or_current(self, current: Timestamp) -> Timestamp {
+		match self {
+			Self::CurrentTime => current,
+			Self::Other(timestamp) => timestamp,
+		}
+	}
+}
+
+impl From<Timestamp> for CurrentableTime {
+	/// Converts a [`Timestamp`] to [`Other`], or [`CurrentTime`] if it is 0,
+	/// which is how it would be read.
+	///
+	/// [`Other`]: CurrentableTime::Other
+	/// [`CurrentTime`]: CurrentableTime::CurrentTime
+	fn from(timestamp: Timestamp) -> Self {
+		match timestamp.unwrap() {
+			0 => Self::CurrentTime,
+			_ => Self::Other(timestamp),
+		}
+	}
+}
+
 impl_constant_x11_size!(CurrentableTime { // {{{
 	Timestamp::X11_SIZE
 });
 
 impl_readable!(CurrentableTime: buf {
-	Ok(match buf.get_u32() {
+	Ok(match u32::read_from(buf)? {
 		discrim if discrim == 0 => Self::CurrentTime,
 		val => Self::Other(Timestamp::new(val)),
 	})
@@ -339,6 +371,7 @@ impl_writable!(CurrentableTime: &self, buf {
 ///
 /// [`SendEvent` request]: crate::x11::request::SendEvent
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DestinationWindow {
 	/// The [window] that the cursor is currently located within.
 	///
@@ -360,7 +393,7 @@ impl_constant_x11_size!(DestinationWindow { // {{{
 });
 
 impl_readable!(DestinationWindow: buf {
-	Ok(match buf.get_u32() {
+	Ok(match u32::read_from(buf)? {
 		discrim if discrim == 0 => Self::Cursor,
 		discrim if discrim == 1 => Self::Focus,
 
@@ -383,6 +416,7 @@ impl_writable!(DestinationWindow: &self, buf {
 ///
 /// [window]: Window
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FocusWindow {
 	/// No [window] is focused.
 	///
@@ -395,6 +429,7 @@ pub enum FocusWindow {
 	/// cursor.
 	///
 	/// [window]: Window
+	#[doc(alias = "PointerRoot")]
 	CursorRoot,
 
 	/// This specific [window].
@@ -406,7 +441,7 @@ impl_constant_x11_size!(FocusWindow { // {{{
 });
 
 impl_readable!(FocusWindow: buf {
-	Ok(match buf.get_u32() {
+	Ok(match u32::read_from(buf)? {
 		discrim if discrim == 0 => Self::None,
 		discrim if discrim == 1 => Self::CursorRoot,
 
@@ -429,11 +464,13 @@ impl_writable!(FocusWindow: &self, buf {
 ///
 /// [`KillClient` request]: crate::x11::request::KillClient
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum KillClientTarget {
 	/// Destroy all remaining resources retained from connections that ended
 	/// with [`RetainResourcesMode::RetainTemporarily`].
 	///
 	/// [`RetainResourcesMode::RetainTemporarily`]: crate::x11::request::RetainResourcesMode::RetainTemporarily
+	#[doc(alias("AllTemporary"))]
 	DestroyTemporarilyRetainedResources,
 
 	/// Kill the client which created the specified `resource`.
@@ -454,7 +491,7 @@ impl_constant_x11_size!(KillClientTarget { // {{{
 });
 
 impl_readable!(KillClientTarget: buf {
-	Ok(match buf.get_u32() {
+	Ok(match u32::read_from(buf)? {
 		0 => Self::DestroyTemporarilyRetainedResources,
 		resource => Self::KillClient { resource },
 	})
@@ -0,0 +1,148 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Messages defined in the [Composite extension]: [requests] and [replies].
+//!
+//! The Composite extension allows the contents of [windows] to be redirected
+//! to off-screen storage, from which a compositing manager can draw them.
+//!
+//! Only the subset of the extension needed by a compositing window manager is
+//! currently defined.
+//!
+//! [Composite extension]: https://gitlab.freedesktop.org/xorg/proto/xorgproto/-/blob/master/compositeproto.txt
+//! [requests]: request
+//! [replies]: reply
+//! [windows]: crate::Window
+
+extern crate self as xrb;
+
+use xrbk_macro::{ConstantX11Size, Readable, Writable, X11Size};
+
+use crate::message::Extension;
+
+pub mod reply;
+pub mod request;
+
+/// The Composite extension.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Composite;
+
+impl Extension for Composite {
+	const NAME: &'static str = "Composite";
+}
+
+/// Whether the contents of a redirected [window] are automatically drawn to
+/// its parent by the X server.
+///
+/// [window]: crate::Window
+#[doc(alias("Update"))]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RedirectMode {
+	/// The X server draws the contents of the [window] to its parent.
+	///
+	/// [window]: crate::Window
+	Automatic,
+	/// The contents of the [window] are not drawn to its parent: a
+	/// compositing manager is responsible for drawing them.
+	///
+	/// Only one client may redirect a [window] with `Manual` at a time.
+	///
+	/// [window]: crate::Window
+	Manual,
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{message::ExtensionCodes, Pixmap, Window};
+	use xrbk::{Readable, Writable};
+
+	const CODES: ExtensionCodes = ExtensionCodes {
+		major_opcode: 142,
+		first_event: 0,
+		first_error: 0,
+	};
+
+	#[test]
+	fn test_redirect_subwindows_layout() {
+		let request = request::RedirectSubwindows {
+			target: Window::new(0x0000_0123),
+			mode: RedirectMode::Manual,
+		};
+
+		let mut bytes = vec![];
+		CODES.write_request(&request, &mut bytes).unwrap();
+
+		#[rustfmt::skip]
+		assert_eq!(bytes, [
+			142, 2, 0, 3,
+			0x00, 0x00, 0x01, 0x23,
+			1, 0, 0, 0,
+		]);
+
+		// The major and minor opcodes are read before the request itself.
+		assert_eq!(
+			request::RedirectSubwindows::read_from(&mut &bytes[2..]).unwrap(),
+			request,
+		);
+	}
+
+	#[test]
+	fn test_requests_round_trip() {
+		fn round_trip<R>(request: &R)
+		where
+			R: crate::message::Request + Readable + Writable + PartialEq + std::fmt::Debug,
+		{
+			let mut bytes = vec![];
+			CODES.write_request(request, &mut bytes).unwrap();
+
+			assert_eq!(bytes.len() % 4, 0);
+			assert_eq!(&R::read_from(&mut &bytes[2..]).unwrap(), request);
+		}
+
+		round_trip(&request::QueryVersion {
+			major_version: 0,
+			minor_version: 4,
+		});
+		round_trip(&request::RedirectWindow {
+			target: Window::new(1),
+			mode: RedirectMode::Automatic,
+		});
+		round_trip(&request::UnredirectWindow {
+			target: Window::new(1),
+			mode: RedirectMode::Automatic,
+		});
+		round_trip(&request::UnredirectSubwindows {
+			target: Window::new(1),
+			mode: RedirectMode::Manual,
+		});
+		round_trip(&request::NameWindowPixmap {
+			target: Window::new(1),
+			pixmap_id: Pixmap::new(2),
+		});
+	}
+
+	#[test]
+	fn test_query_version_reply_round_trip() {
+		let reply = reply::QueryVersion {
+			sequence: 1,
+			major_version: 0,
+			minor_version: 4,
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32);
+		assert_eq!(
+			reply::QueryVersion::read_from(&mut &bytes[1..]).unwrap(),
+			reply,
+		);
+	}
+}
@@ -0,0 +1,313 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [Requests] defined in the [Composite extension].
+//!
+//! [Requests] are messages sent from an X client to the X server.
+//!
+//! The [major opcode] of these [requests] is assigned by the X server: they
+//! must be written with [`ExtensionCodes::write_request`].
+//!
+//! [Requests]: Request
+//! [requests]: Request
+//! [Composite extension]: super
+//! [major opcode]: Request::MAJOR_OPCODE
+//! [`ExtensionCodes::write_request`]: crate::message::ExtensionCodes::write_request
+
+extern crate self as xrb;
+
+use xrbk_macro::derive_xrb;
+
+use crate::{
+	composite::{reply, RedirectMode},
+	message::{Request, UNASSIGNED_MAJOR_OPCODE},
+	x11::error,
+	Pixmap,
+	Window,
+};
+
+macro_rules! request_error {
+	(
+		$(#[$meta:meta])*
+		$vis:vis enum $Name:ident for $Request:ty {
+			$($($Error:ident),+$(,)?)?
+		}
+	) => {
+		#[doc = concat!(
+			"An [error](crate::message::Error) generated because of a failed [`",
+			stringify!($Request),
+			"` request](",
+			stringify!($Request),
+			")."
+		)]
+		#[doc = ""]
+		$(#[$meta])*
+		$vis enum $Name {
+			$($(
+				#[doc = concat!(
+					"A [`",
+					stringify!($Error),
+					"` error](error::",
+					stringify!($Error),
+					")."
+				)]
+				$Error(error::$Error)
+			),+)?
+		}
+
+		impl TryFrom<crate::x11::AnyError> for $Name {
+			type Error = crate::x11::AnyError;
+
+			fn try_from(error: crate::x11::AnyError) -> Result<Self, Self::Error> {
+				match error {
+					$($(crate::x11::AnyError::$Error(error) => Ok(Self::$Error(error)),)+)?
+
+					other => Err(other),
+				}
+			}
+		}
+	};
+}
+
+request_error! {
+	pub enum RedirectWindowError for RedirectWindow {
+		Access,
+		Match,
+		Window,
+	}
+}
+
+request_error! {
+	pub enum RedirectSubwindowsError for RedirectSubwindows {
+		Access,
+		Window,
+	}
+}
+
+request_error! {
+	pub enum UnredirectWindowError for UnredirectWindow {
+		Value,
+		Window,
+	}
+}
+
+request_error! {
+	pub enum UnredirectSubwindowsError for UnredirectSubwindows {
+		Value,
+		Window,
+	}
+}
+
+request_error! {
+	pub enum NameWindowPixmapError for NameWindowPixmap {
+		Match,
+		ResourceIdChoice,
+		Window,
+	}
+}
+
+derive_xrb! {
+	/// A [request] that returns the version of the Composite extension
+	/// supported by the X server.
+	///
+	/// # Replies
+	/// This [request] generates a [`QueryVersion` reply].
+	///
+	/// [request]: Request
+	///
+	/// [`QueryVersion` reply]: reply::QueryVersion
+	#[doc(alias("CompositeQueryVersion"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct QueryVersion: Request(UNASSIGNED_MAJOR_OPCODE, 0) -> reply::QueryVersion {
+		/// The major version of the Composite extension supported by this
+		/// client.
+		pub major_version: u32,
+		/// The minor version of the Composite extension supported by this
+		/// client.
+		pub minor_version: u32,
+	}
+
+	/// A [request] that redirects the contents of the given [window] and its
+	/// descendents to off-screen storage.
+	///
+	/// # Errors
+	/// A [`Window` error] is generated if `target` does not refer to a defined
+	/// [window].
+	///
+	/// An [`Access` error] is generated if `mode` is [`RedirectMode::Manual`]
+	/// and another client has already redirected the `target` with
+	/// [`RedirectMode::Manual`].
+	///
+	/// A [`Match` error] is generated if `target` is a root [window].
+	///
+	/// [window]: Window
+	/// [request]: Request
+	///
+	/// [`Window` error]: error::Window
+	/// [`Access` error]: error::Access
+	/// [`Match` error]: error::Match
+	#[doc(alias("CompositeRedirectWindow"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct RedirectWindow: Request(UNASSIGNED_MAJOR_OPCODE, 1, RedirectWindowError) {
+		/// The [window] which is to be redirected.
+		///
+		/// [window]: Window
+		#[doc(alias("window"))]
+		pub target: Window,
+
+		/// Whether the X server continues to draw the contents of the
+		/// `target` to its parent.
+		#[doc(alias("update"))]
+		pub mode: RedirectMode,
+		[_; ..],
+	}
+
+	/// A [request] that redirects the contents of every current and future
+	/// child of the given [window] to off-screen storage.
+	///
+	/// # Errors
+	/// A [`Window` error] is generated if `target` does not refer to a defined
+	/// [window].
+	///
+	/// An [`Access` error] is generated if `mode` is [`RedirectMode::Manual`]
+	/// and another client has already redirected the children of the `target`
+	/// with [`RedirectMode::Manual`].
+	///
+	/// [window]: Window
+	/// [request]: Request
+	///
+	/// [`Window` error]: error::Window
+	/// [`Access` error]: error::Access
+	#[doc(alias("CompositeRedirectSubwindows"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct RedirectSubwindows: Request(
+		UNASSIGNED_MAJOR_OPCODE,
+		2,
+		RedirectSubwindowsError,
+	) {
+		/// The [window] whose children are to be redirected.
+		///
+		/// [window]: Window
+		#[doc(alias("window"))]
+		pub target: Window,
+
+		/// Whether the X server continues to draw the contents of the
+		/// children to the `target`.
+		#[doc(alias("update"))]
+		pub mode: RedirectMode,
+		[_; ..],
+	}
+
+	/// A [request] that stops redirecting the contents of the given [window]
+	/// to off-screen storage.
+	///
+	/// # Errors
+	/// A [`Window` error] is generated if `target` does not refer to a defined
+	/// [window].
+	///
+	/// A [`Value` error] is generated if the `target` was not redirected by
+	/// this client with the given `mode`.
+	///
+	/// [window]: Window
+	/// [request]: Request
+	///
+	/// [`Window` error]: error::Window
+	/// [`Value` error]: error::Value
+	#[doc(alias("CompositeUnredirectWindow"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct UnredirectWindow: Request(UNASSIGNED_MAJOR_OPCODE, 3, UnredirectWindowError) {
+		/// The [window] which is to no longer be redirected.
+		///
+		/// [window]: Window
+		#[doc(alias("window"))]
+		pub target: Window,
+
+		/// The `mode` with which the `target` was redirected.
+		#[doc(alias("update"))]
+		pub mode: RedirectMode,
+		[_; ..],
+	}
+
+	/// A [request] that stops redirecting the contents of the children of the
+	/// given [window] to off-screen storage.
+	///
+	/// # Errors
+	/// A [`Window` error] is generated if `target` does not refer to a defined
+	/// [window].
+	///
+	/// A [`Value` error] is generated if the children of the `target` were not
+	/// redirected by this client with the given `mode`.
+	///
+	/// [window]: Window
+	/// [request]: Request
+	///
+	/// [`Window` error]: error::Window
+	/// [`Value` error]: error::Value
+	#[doc(alias("CompositeUnredirectSubwindows"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct UnredirectSubwindows: Request(
+		UNASSIGNED_MAJOR_OPCODE,
+		4,
+		UnredirectSubwindowsError,
+	) {
+		/// The [window] whose children are to no longer be redirected.
+		///
+		/// [window]: Window
+		#[doc(alias("window"))]
+		pub target: Window,
+
+		/// The `mode` with which the children of the `target` were
+		/// redirected.
+		#[doc(alias("update"))]
+		pub mode: RedirectMode,
+		[_; ..],
+	}
+
+	/// A [request] that assigns the given [`Pixmap` ID][pixmap] to the
+	/// off-screen storage of the given redirected [window].
+	///
+	/// The [pixmap] continues to refer to the same storage after the [window]
+	/// is resized or unmapped, at which point a new [pixmap] must be named to
+	/// access its new contents.
+	///
+	/// # Errors
+	/// A [`Window` error] is generated if `target` does not refer to a defined
+	/// [window].
+	///
+	/// A [`Match` error] is generated if the `target` is not redirected or is
+	/// not viewable.
+	///
+	/// A [`ResourceIdChoice` error] is generated if `pixmap_id` specifies an ID
+	/// already used for another resource, or an ID not allocated to your
+	/// client.
+	///
+	/// [window]: Window
+	/// [pixmap]: Pixmap
+	/// [request]: Request
+	///
+	/// [`Window` error]: error::Window
+	/// [`Match` error]: error::Match
+	/// [`ResourceIdChoice` error]: error::ResourceIdChoice
+	#[doc(alias("CompositeNameWindowPixmap"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct NameWindowPixmap: Request(UNASSIGNED_MAJOR_OPCODE, 6, NameWindowPixmapError) {
+		/// The redirected [window] whose off-screen storage is named.
+		///
+		/// [window]: Window
+		#[doc(alias("window"))]
+		pub target: Window,
+		/// The [`Pixmap` ID][pixmap] which is to be assigned to the off-screen
+		/// storage of the `target`.
+		///
+		/// [pixmap]: Pixmap
+		#[doc(alias("pixmap"))]
+		pub pixmap_id: Pixmap,
+	}
+}
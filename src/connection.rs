@@ -8,7 +8,7 @@ use xrbk::X11Size;
 use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
 
 use crate::{
-	visual::{Format, Screen},
+	visual::{Format, ImageLayout, Screen, VisualType},
 	Keycode,
 	String8,
 };
@@ -19,13 +19,16 @@ const fn pad(n: usize) -> usize {
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Endianness {
 	BigEndian = 0x42,
 	LittleEndian = 0x6c,
 }
 
 derive_xrb! {
-	#[derive(Debug, X11Size, Readable, Writable)]
+	#[doc(alias("SetupRequest", "ConnectionSetup"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct InitConnection {
 		// XRBK assumes the endianness is big endian, so we hardcode that in.
 		let byte_order: Endianness = Endianness::BigEndian,
@@ -53,13 +56,15 @@ derive_xrb! {
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImageEndianness {
 	LittleEndian,
 	BigEndian,
 }
 
 derive_xrb! {
-	#[derive(Debug, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub enum ConnectionResponse {
 		/// There was a failure in attempting the connection.
 		Failed(ConnectionFailure),
@@ -89,7 +94,9 @@ impl ConnectionResponse {
 
 derive_xrb! {
 	/// There was a failure in attempting the connection.
-	#[derive(Debug, X11Size, Readable, Writable)]
+	#[doc(alias("ConnectionFailed", "SetupFailed"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ConnectionFailure {
 		#[allow(clippy::cast_possible_truncation)]
 		let reason_len: u8 = reason => reason.len() as u8,
@@ -109,11 +116,16 @@ derive_xrb! {
 		/// The reason for the failure.
 		#[context(reason_len => *reason_len as usize)]
 		pub reason: String8,
-		[_; ..],
+		// This is the response's padding, not just this struct's: inferred
+		// unused bytes would not account for the `ConnectionResponse`
+		// discriminant.
+		[_; reason => pad(reason.len())],
 	}
 
 	/// The connection was successfully established.
-	#[derive(Debug, X11Size, Readable, Writable)]
+	#[doc(alias("Setup", "SetupSuccess"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ConnectionSuccess {
 		_,
 		/// The major version of the X11 protocol used by the X server.
@@ -157,7 +169,7 @@ derive_xrb! {
 
 		#[context(vendor_len => *vendor_len as usize)]
 		pub vendor: String8,
-		[_; ..],
+		[_; vendor => pad(vendor.len())],
 
 		#[context(pixmap_formats_len => *pixmap_formats_len as usize)]
 		pub pixmap_formats: Vec<Format>,
@@ -166,7 +178,9 @@ derive_xrb! {
 	}
 
 	/// The connection was refused because authentication was unsuccessful.
-	#[derive(Debug, X11Size, Readable, Writable)]
+	#[doc(alias("ConnectionAuthenticationRequired", "SetupAuthenticate"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ConnectionAuthenticationError {
 		[_; 5],
 
@@ -182,7 +196,19 @@ derive_xrb! {
 			(*additional_data_len as usize) * 4
 		})]
 		pub reason: String8,
-		[_; ..],
+		[_; reason => pad(reason.len())],
+	}
+}
+
+impl ConnectionSuccess {
+	/// Returns the [`ImageLayout`] of images of the given `depth` and
+	/// `visual` on this X server.
+	///
+	/// Returns [`None`] if there is no supported pixmap [`Format`] for the
+	/// `depth`.
+	#[must_use]
+	pub fn image_layout(&self, depth: u8, visual: &VisualType) -> Option<ImageLayout> {
+		ImageLayout::new(&self.pixmap_formats, self.image_byte_order, depth, visual)
 	}
 }
 
@@ -214,3 +240,225 @@ mod r#try {
 		}
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		unit::{Mm, Px},
+		visual::{ColorId, Depth, VisualClass, VisualId, VisualType},
+		Char8,
+		Colormap,
+		EventMask,
+		MaintainContents,
+		Window,
+	};
+	use xrbk::{Readable, Writable};
+
+	fn string8(string: &str) -> String8 {
+		String8::from(string.bytes().map(Char8::new).collect::<Vec<_>>())
+	}
+
+	#[test]
+	fn test_init_connection_pads_auth() {
+		let init = InitConnection {
+			auth_protocol_name: string8("MIT-MAGIC-COOKIE-1"),
+			auth_protocol_data: String8::from(vec![Char8::new(0xab); 16]),
+		};
+
+		let mut bytes = vec![];
+		init.write_to(&mut bytes).unwrap();
+
+		// 12-byte header, 18 bytes of name padded to 20, and 16 bytes of data.
+		assert_eq!(bytes.len(), 48);
+		assert_eq!(init.x11_size(), bytes.len());
+		assert_eq!(bytes[..12], [0x42, 0, 0, 11, 0, 0, 0, 18, 0, 16, 0, 0]);
+		assert_eq!(&bytes[12..30], b"MIT-MAGIC-COOKIE-1");
+		assert_eq!(bytes[30..32], [0, 0]);
+		assert_eq!(bytes[32..], [0xab; 16]);
+
+		assert_eq!(InitConnection::read_from(&mut &bytes[..]).unwrap(), init);
+	}
+
+	/// The setup reply of an Xvfb server with a single 1280x1024 screen with
+	/// a depth of 24.
+	#[rustfmt::skip]
+	fn xvfb_setup() -> Vec<u8> {
+		let mut bytes = vec![
+			1, 0, // success
+			0, 11, 0, 0, // protocol version 11.0
+			0, 0, // length, filled in below
+			0x00, 0xb8, 0xa5, 0x8c, // release number 12101004
+			0x00, 0x20, 0x00, 0x00, // resource ID base
+			0x00, 0x1f, 0xff, 0xff, // resource ID mask
+			0x00, 0x00, 0x01, 0x00, // motion buffer size
+			0, 20, // vendor length
+			0xff, 0xff, // maximum request length
+			1, 7, // number of screens, number of pixmap formats
+			0, 0, // image byte order, bitmap format bit order
+			32, 32, // bitmap format scanline unit, scanline padding
+			8, 255, // min keycode, max keycode
+			0, 0, 0, 0,
+		];
+		bytes.extend_from_slice(b"The X.Org Foundation");
+
+		// Pixmap formats.
+		for (depth, bits_per_pixel) in [(1, 1), (4, 8), (8, 8), (15, 16), (16, 16), (24, 32), (32, 32)] {
+			bytes.extend_from_slice(&[depth, bits_per_pixel, 32, 0, 0, 0, 0, 0]);
+		}
+
+		// Screen.
+		bytes.extend_from_slice(&[
+			0x00, 0x00, 0x05, 0x12, // root
+			0x00, 0x00, 0x00, 0x20, // default colormap
+			0x00, 0xff, 0xff, 0xff, // white
+			0x00, 0x00, 0x00, 0x00, // black
+			0x00, 0x00, 0x00, 0x00, // current input masks
+			0x05, 0x00, 0x04, 0x00, // width and height in pixels
+			0x01, 0x52, 0x01, 0x0e, // width and height in millimeters
+			0, 1, 0, 1, // min and max installed colormaps
+			0x00, 0x00, 0x00, 0x21, // root visual
+			0, 0, 24, // maintain contents, maintain windows under, root depth
+			7, // number of depths
+		]);
+
+		let true_color = |id: u8| {
+			[
+				0, 0, 0, id, // visual ID
+				4, 8, 1, 0, // true color, bits per RGB value, colormap entries
+				0x00, 0xff, 0x00, 0x00, // red mask
+				0x00, 0x00, 0xff, 0x00, // green mask
+				0x00, 0x00, 0x00, 0xff, // blue mask
+				0, 0, 0, 0,
+			]
+		};
+
+		// Depth 24, with two visuals.
+		bytes.extend_from_slice(&[24, 0, 0, 2, 0, 0, 0, 0]);
+		bytes.extend_from_slice(&true_color(0x21));
+		bytes.extend_from_slice(&true_color(0x22));
+		// Depths with no visuals.
+		for depth in [1, 4, 8, 15, 16] {
+			bytes.extend_from_slice(&[depth, 0, 0, 0, 0, 0, 0, 0]);
+		}
+		// Depth 32, with one visual.
+		bytes.extend_from_slice(&[32, 0, 0, 1, 0, 0, 0, 0]);
+		bytes.extend_from_slice(&true_color(0x5b));
+
+		let length = u16::try_from((bytes.len() - 8) / 4).unwrap();
+		bytes[6..8].copy_from_slice(&length.to_be_bytes());
+
+		bytes
+	}
+
+	#[test]
+	fn test_xvfb_setup() {
+		let bytes = xvfb_setup();
+
+		let response = ConnectionResponse::read_from(&mut &bytes[..]).unwrap();
+		let ConnectionResponse::Success(success) = &response else {
+			panic!("expected a successful connection, found {response:?}");
+		};
+
+		assert_eq!(success.protocol_major_version, 11);
+		assert_eq!(success.release_number, 12_101_004);
+		assert_eq!(success.resource_id_base, 0x0020_0000);
+		assert_eq!(success.resource_id_mask, 0x001f_ffff);
+		assert_eq!(success.motion_buffer_size, 256);
+		assert_eq!(success.maximum_request_length, u16::MAX);
+		assert_eq!(success.image_byte_order, ImageEndianness::LittleEndian);
+		assert_eq!(success.bitmap_format_scanline_unit, 32);
+		assert_eq!(success.min_keycode, Keycode(8));
+		assert_eq!(success.max_keycode, Keycode(255));
+		assert_eq!(success.vendor, string8("The X.Org Foundation"));
+
+		assert_eq!(success.pixmap_formats.len(), 7);
+		assert_eq!(success.pixmap_formats[5], Format::new(24, 32, 32),);
+
+		let [screen] = &success.roots[..] else {
+			panic!("expected one screen, found {:?}", success.roots);
+		};
+
+		assert_eq!(screen.root, Window::new(0x0000_0512));
+		assert_eq!(screen.default_colormap, Colormap::new(0x20));
+		assert_eq!(screen.white, ColorId::new(0x00ff_ffff));
+		assert_eq!(screen.current_input_masks, EventMask::empty());
+		assert_eq!((screen.width_px, screen.height_px), (Px(1280), Px(1024)));
+		assert_eq!((screen.width_mm, screen.height_mm), (Mm(338), Mm(270)));
+		assert_eq!(screen.root_visual, VisualId::new(0x21));
+		assert_eq!(screen.maintain_contents_mode, MaintainContents::Never);
+		assert_eq!(screen.root_depth, 24);
+
+		let depths: Vec<(u8, usize)> = screen
+			.allowed_depths
+			.iter()
+			.map(|depth| (depth.depth, depth.visuals.len()))
+			.collect();
+		assert_eq!(
+			depths,
+			[(24, 2), (1, 0), (4, 0), (8, 0), (15, 0), (16, 0), (32, 1)],
+		);
+
+		let Depth { visuals, .. } = &screen.allowed_depths[6];
+		assert_eq!(
+			visuals[0],
+			VisualType::new(
+				VisualId::new(0x5b),
+				VisualClass::TrueColor,
+				8,
+				256,
+				0xff_0000,
+				0xff00,
+				0xff
+			),
+		);
+
+		let layout = success.image_layout(24, &visuals[0]).unwrap();
+		assert_eq!(layout.format, Format::new(24, 32, 32));
+		assert_eq!(layout.byte_order, ImageEndianness::LittleEndian);
+		assert_eq!(success.image_layout(12, &visuals[0]), None);
+
+		let mut written = vec![];
+		response.write_to(&mut written).unwrap();
+
+		assert_eq!(response.x11_size(), bytes.len());
+		assert_eq!(written, bytes);
+	}
+
+	#[test]
+	fn test_connection_failed() {
+		// 22 bytes of reason, padded to 24 (6 units).
+		let mut bytes = vec![0, 22, 0, 11, 0, 0, 0, 6];
+		bytes.extend_from_slice(b"No protocol specified\n\0\0");
+
+		let response = ConnectionResponse::read_from(&mut &bytes[..]).unwrap();
+
+		let ConnectionResponse::Failed(failure) = &response else {
+			panic!("expected a failed connection, found {response:?}");
+		};
+		assert_eq!(failure.protocol_major_version, 11);
+		assert_eq!(failure.reason, string8("No protocol specified\n"));
+
+		let mut written = vec![];
+		response.write_to(&mut written).unwrap();
+		assert_eq!(written, bytes);
+	}
+
+	#[test]
+	fn test_connection_authenticate() {
+		let mut bytes = vec![2, 0, 0, 0, 0, 0, 0, 3];
+		bytes.extend_from_slice(b"Bad cookie\0\0");
+
+		let response = ConnectionResponse::read_from(&mut &bytes[..]).unwrap();
+
+		let ConnectionResponse::Authenticate(authenticate) = &response else {
+			panic!("expected authentication to be required, found {response:?}");
+		};
+		// The reason's length is not sent separately from its padding.
+		assert_eq!(authenticate.reason, string8("Bad cookie\0\0"));
+
+		let mut written = vec![];
+		response.write_to(&mut written).unwrap();
+		assert_eq!(written, bytes);
+	}
+}
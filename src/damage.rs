@@ -0,0 +1,424 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Messages defined in the [Damage extension]: [requests], [replies], and
+//! [events].
+//!
+//! The Damage extension reports the areas of [drawables] which have been
+//! changed ('damaged'), allowing a compositing manager to redraw only those
+//! areas.
+//!
+//! The Damage extension depends on the [regions] of the [XFixes extension].
+//!
+//! [Damage extension]: https://gitlab.freedesktop.org/xorg/proto/xorgproto/-/blob/master/damageproto.txt
+//! [requests]: request
+//! [replies]: reply
+//! [events]: DamageNotify
+//! [drawables]: crate::Drawable
+//! [regions]: crate::xfixes::Region
+//! [XFixes extension]: crate::xfixes
+
+extern crate self as xrb;
+
+use derivative::Derivative;
+use derive_more::{From, Into};
+
+use xrbk::{
+	Buf,
+	BufMut,
+	ConstantX11Size,
+	ReadError,
+	ReadResult,
+	Readable,
+	Writable,
+	WriteResult,
+	X11Size,
+};
+use xrbk_macro::{derive_xrb, new, unwrap, ConstantX11Size, Readable, Wrap, Writable, X11Size};
+
+use crate::{
+	message::{Event, Extension},
+	Drawable,
+	Rectangle,
+	Timestamp,
+};
+
+pub mod reply;
+pub mod request;
+
+/// The Damage extension.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DamageExtension;
+
+impl Extension for DamageExtension {
+	const NAME: &'static str = "DAMAGE";
+}
+
+/// A resource ID referring to a damage object: the damage to a [drawable]
+/// tracked by the X server.
+///
+/// [drawable]: Drawable
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	From,
+	Into,
+	// `new` and `unwrap` const fns
+	new,
+	unwrap,
+	// XRBK traits
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	Wrap,
+)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
+pub struct Damage(u32);
+
+/// When [`DamageNotify` events] are generated for a [`Damage`] object.
+///
+/// [`DamageNotify` events]: DamageNotify
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReportLevel {
+	/// A [`DamageNotify` event] is generated for every damaged rectangle.
+	///
+	/// [`DamageNotify` event]: DamageNotify
+	RawRectangles,
+	/// A [`DamageNotify` event] is generated for every damaged rectangle
+	/// which is not already contained within the damaged area.
+	///
+	/// [`DamageNotify` event]: DamageNotify
+	DeltaRectangles,
+	/// A [`DamageNotify` event] is generated whenever the bounding box of the
+	/// damaged area grows.
+	///
+	/// [`DamageNotify` event]: DamageNotify
+	BoundingBox,
+	/// A [`DamageNotify` event] is generated whenever the damaged area
+	/// becomes non-empty.
+	///
+	/// [`DamageNotify` event]: DamageNotify
+	NonEmpty,
+}
+
+/// The [`ReportLevel`] of a [`DamageNotify` event], and whether more
+/// [`DamageNotify` events] immediately follow it.
+///
+/// [`DamageNotify` event]: DamageNotify
+/// [`DamageNotify` events]: DamageNotify
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NotifyLevel {
+	/// The [`ReportLevel`] of the [`Damage`] object.
+	pub level: ReportLevel,
+	/// Whether more [`DamageNotify` events] immediately follow this one.
+	///
+	/// [`DamageNotify` events]: DamageNotify
+	pub more: bool,
+}
+
+impl NotifyLevel {
+	/// The bit which indicates that more [`DamageNotify` events] follow.
+	///
+	/// [`DamageNotify` events]: DamageNotify
+	const MORE_BIT: u8 = 0x80;
+}
+
+impl ConstantX11Size for NotifyLevel {
+	const X11_SIZE: usize = 1;
+}
+
+impl X11Size for NotifyLevel {
+	fn x11_size(&self) -> usize {
+		Self::X11_SIZE
+	}
+}
+
+impl Readable for NotifyLevel {
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		let byte = u8::read_from(buf)?;
+
+		let level = match byte & !Self::MORE_BIT {
+			0 => ReportLevel::RawRectangles,
+			1 => ReportLevel::DeltaRectangles,
+			2 => ReportLevel::BoundingBox,
+			3 => ReportLevel::NonEmpty,
+
+			other => return Err(ReadError::UnrecognizedDiscriminant(usize::from(other))),
+		};
+
+		Ok(Self {
+			level,
+			more: byte & Self::MORE_BIT != 0,
+		})
+	}
+}
+
+impl Writable for NotifyLevel {
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		let mut byte = self.level as u8;
+
+		if self.more {
+			byte |= Self::MORE_BIT;
+		}
+
+		buf.put_u8(byte);
+
+		Ok(())
+	}
+}
+
+derive_xrb! {
+	/// An [event] generated when a [drawable] tracked by a [`Damage`] object is
+	/// damaged.
+	///
+	/// The code of this [event] is the Damage extension's first event code:
+	/// see [`ExtensionCodes`] for reading and writing it.
+	///
+	/// # Recipients
+	/// This [event] is reported to the client which created the [`Damage`]
+	/// object.
+	///
+	/// [event]: Event
+	/// [drawable]: Drawable
+	/// [`ExtensionCodes`]: crate::message::ExtensionCodes
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[derivative(Hash, PartialEq, Eq)]
+	pub struct DamageNotify: Event(0) {
+		/// The [sequence number] associated with the last [request] related
+		/// to this [event] that was received before this [event] was generated.
+		///
+		/// [sequence number]: Event::sequence
+		/// [request]: crate::message::Request
+		/// [event]: Event
+		#[sequence]
+		#[derivative(PartialEq = "ignore", Hash = "ignore")]
+		pub sequence: u16,
+
+		/// The [`ReportLevel`] of the `damage` object, and whether more
+		/// [`DamageNotify` events] immediately follow this one.
+		///
+		/// [`DamageNotify` events]: DamageNotify
+		#[metabyte]
+		pub level: NotifyLevel,
+
+		/// The [drawable] which was damaged.
+		///
+		/// [drawable]: Drawable
+		pub drawable: Drawable,
+		/// The [`Damage`] object tracking the `drawable`.
+		pub damage: Damage,
+
+		/// The time at which the `drawable` was damaged.
+		pub timestamp: Timestamp,
+
+		/// The damaged area, relative to the `drawable`'s origin.
+		pub area: Rectangle,
+		/// The geometry of the `drawable`.
+		#[doc(alias("x", "y", "width", "height"))]
+		pub geometry: Rectangle,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		message::{ExtensionCodes, Flagged},
+		unit::Px,
+		xfixes::Region,
+	};
+
+	const CODES: ExtensionCodes = ExtensionCodes {
+		major_opcode: 143,
+		first_event: 91,
+		first_error: 152,
+	};
+
+	#[test]
+	fn test_create_layout() {
+		let request = request::Create {
+			damage: Damage::new(0x0020_0001),
+			drawable: Drawable::new(0x0000_0123),
+			level: ReportLevel::NonEmpty,
+		};
+
+		let mut bytes = vec![];
+		CODES.write_request(&request, &mut bytes).unwrap();
+
+		#[rustfmt::skip]
+		assert_eq!(bytes, [
+			143, 1, 0, 4,
+			0x00, 0x20, 0x00, 0x01,
+			0x00, 0x00, 0x01, 0x23,
+			3, 0, 0, 0,
+		]);
+
+		// The major and minor opcodes are read before the request itself.
+		assert_eq!(
+			request::Create::read_from(&mut &bytes[2..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_subtract_layout() {
+		let request = request::Subtract {
+			damage: Damage::new(0x0020_0001),
+			repair: None,
+			parts: Some(Region::new(0x0020_0002)),
+		};
+
+		let mut bytes = vec![];
+		CODES.write_request(&request, &mut bytes).unwrap();
+
+		// A `repair` of `None` is written as zero.
+		#[rustfmt::skip]
+		assert_eq!(bytes, [
+			143, 3, 0, 4,
+			0x00, 0x20, 0x00, 0x01,
+			0x00, 0x00, 0x00, 0x00,
+			0x00, 0x20, 0x00, 0x02,
+		]);
+		assert_eq!(
+			request::Subtract::read_from(&mut &bytes[2..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_requests_round_trip() {
+		fn round_trip<R>(request: &R)
+		where
+			R: crate::message::Request + Readable + Writable + PartialEq + std::fmt::Debug,
+		{
+			let mut bytes = vec![];
+			CODES.write_request(request, &mut bytes).unwrap();
+
+			assert_eq!(bytes.len() % 4, 0);
+			assert_eq!(&R::read_from(&mut &bytes[2..]).unwrap(), request);
+		}
+
+		round_trip(&request::QueryVersion {
+			major_version: 1,
+			minor_version: 1,
+		});
+		round_trip(&request::Destroy {
+			damage: Damage::new(1),
+		});
+		round_trip(&request::Subtract {
+			damage: Damage::new(1),
+			repair: None,
+			parts: Some(Region::new(2)),
+		});
+	}
+
+	#[test]
+	fn test_query_version_reply_round_trip() {
+		let reply = reply::QueryVersion {
+			sequence: 1,
+			major_version: 1,
+			minor_version: 1,
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32);
+		assert_eq!(
+			reply::QueryVersion::read_from(&mut &bytes[1..]).unwrap(),
+			reply,
+		);
+	}
+
+	#[test]
+	fn test_damage_notify_code_is_offset() {
+		let notify = DamageNotify {
+			sequence: 9,
+			level: NotifyLevel {
+				level: ReportLevel::RawRectangles,
+				more: true,
+			},
+			drawable: Drawable::new(0x0000_0123),
+			damage: Damage::new(0x0020_0001),
+			timestamp: Timestamp::new(1000),
+			area: Rectangle::new(Px(1), Px(2), Px(3), Px(4)),
+			geometry: Rectangle::new(Px(0), Px(0), Px(640), Px(480)),
+		};
+		assert_eq!(CODES.event_code::<DamageNotify>(), 91);
+
+		let flagged = Flagged {
+			event: notify,
+			send_event: false,
+		};
+
+		let mut bytes = vec![];
+		CODES.write_event(&flagged, &mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32);
+		assert_eq!(bytes[0], 91);
+		// `level` is `RawRectangles` with the 'more' bit set.
+		assert_eq!(bytes[1], 0x80);
+		assert_eq!(CODES.event_offset(bytes[0]), Some(DamageNotify::CODE));
+
+		assert_eq!(CODES.read_event(&mut &bytes[..]).unwrap(), flagged);
+
+		bytes[1] = 4;
+		assert!(matches!(
+			CODES.read_event::<DamageNotify>(&mut &bytes[..]),
+			Err(ReadError::UnrecognizedDiscriminant(4)),
+		));
+	}
+
+	#[test]
+	fn test_truncated_damage_notify() {
+		let notify = DamageNotify {
+			sequence: 9,
+			level: NotifyLevel {
+				level: ReportLevel::NonEmpty,
+				more: false,
+			},
+			drawable: Drawable::new(0x0000_0123),
+			damage: Damage::new(0x0020_0001),
+			timestamp: Timestamp::new(1000),
+			area: Rectangle::new(Px(1), Px(2), Px(3), Px(4)),
+			geometry: Rectangle::new(Px(0), Px(0), Px(640), Px(480)),
+		};
+
+		let mut bytes = vec![];
+		notify.write_to(&mut bytes).unwrap();
+
+		// The event code is read before the event itself.
+		let bytes = &bytes[1..];
+
+		for len in 0..bytes.len() {
+			assert!(
+				matches!(
+					DamageNotify::read_from(&mut &bytes[..len]),
+					Err(ReadError::NotEnoughData { .. }),
+				),
+				"reading {len} of {} bytes should fail",
+				bytes.len(),
+			);
+		}
+	}
+}
@@ -0,0 +1,54 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [Replies] defined in the [Damage extension].
+//!
+//! [Replies] are messages sent from the X server to an X client in response to
+//! a [request].
+//!
+//! [Replies]: Reply
+//! [request]: crate::message::Request
+//! [Damage extension]: super
+
+extern crate self as xrb;
+
+use derivative::Derivative;
+
+use xrbk_macro::derive_xrb;
+
+use crate::{damage::request, message::Reply};
+
+derive_xrb! {
+	/// The [reply] to a [`QueryVersion` request].
+	///
+	/// [reply]: Reply
+	///
+	/// [`QueryVersion` request]: request::QueryVersion
+	#[doc(alias("DamageQueryVersion"))]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[derivative(Hash, PartialEq, Eq)]
+	pub struct QueryVersion: Reply for request::QueryVersion {
+		/// The sequence number identifying the [request] that generated this
+		/// [reply].
+		///
+		/// See [`Reply::sequence`] for more information.
+		///
+		/// [request]: crate::message::Request
+		/// [reply]: Reply
+		///
+		/// [`Reply::sequence`]: Reply::sequence
+		#[sequence]
+		#[derivative(Hash = "ignore", PartialEq = "ignore")]
+		pub sequence: u16,
+
+		/// The major version of the Damage extension supported by the X
+		/// server.
+		pub major_version: u32,
+		/// The minor version of the Damage extension supported by the X
+		/// server.
+		pub minor_version: u32,
+		[_; ..],
+	}
+}
@@ -0,0 +1,199 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [Requests] defined in the [Damage extension].
+//!
+//! [Requests] are messages sent from an X client to the X server.
+//!
+//! The [major opcode] of these [requests] is assigned by the X server: they
+//! must be written with [`ExtensionCodes::write_request`].
+//!
+//! [Requests]: Request
+//! [requests]: Request
+//! [Damage extension]: super
+//! [major opcode]: Request::MAJOR_OPCODE
+//! [`ExtensionCodes::write_request`]: crate::message::ExtensionCodes::write_request
+
+extern crate self as xrb;
+
+use xrbk_macro::derive_xrb;
+
+use crate::{
+	damage::{reply, Damage, ReportLevel},
+	message::{Request, UNASSIGNED_MAJOR_OPCODE},
+	x11::error,
+	xfixes::Region,
+	Drawable,
+};
+
+macro_rules! request_error {
+	(
+		$(#[$meta:meta])*
+		$vis:vis enum $Name:ident for $Request:ty {
+			$($($Error:ident),+$(,)?)?
+		}
+	) => {
+		#[doc = concat!(
+			"An [error](crate::message::Error) generated because of a failed [`",
+			stringify!($Request),
+			"` request](",
+			stringify!($Request),
+			")."
+		)]
+		#[doc = ""]
+		$(#[$meta])*
+		$vis enum $Name {
+			$($(
+				#[doc = concat!(
+					"A [`",
+					stringify!($Error),
+					"` error](error::",
+					stringify!($Error),
+					")."
+				)]
+				$Error(error::$Error)
+			),+)?
+		}
+
+		impl TryFrom<crate::x11::AnyError> for $Name {
+			type Error = crate::x11::AnyError;
+
+			fn try_from(error: crate::x11::AnyError) -> Result<Self, Self::Error> {
+				match error {
+					$($(crate::x11::AnyError::$Error(error) => Ok(Self::$Error(error)),)+)?
+
+					other => Err(other),
+				}
+			}
+		}
+	};
+}
+
+request_error! {
+	pub enum CreateError for Create {
+		Drawable,
+		ResourceIdChoice,
+		Value,
+	}
+}
+
+// FIXME: the Damage extension also defines a `Damage` error, generated when a
+//        `Damage` does not refer to a defined damage object, and `Subtract`
+//        requests can generate the XFixes extension's `Region` error, which are
+//        not yet represented in these request errors.
+
+derive_xrb! {
+	/// A [request] that returns the version of the Damage extension supported
+	/// by the X server.
+	///
+	/// # Replies
+	/// This [request] generates a [`QueryVersion` reply].
+	///
+	/// [request]: Request
+	///
+	/// [`QueryVersion` reply]: reply::QueryVersion
+	#[doc(alias("DamageQueryVersion"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct QueryVersion: Request(UNASSIGNED_MAJOR_OPCODE, 0) -> reply::QueryVersion {
+		/// The major version of the Damage extension supported by this client.
+		pub major_version: u32,
+		/// The minor version of the Damage extension supported by this client.
+		pub minor_version: u32,
+	}
+
+	/// A [request] that creates a new [`Damage`] object tracking the damage to
+	/// the given [drawable], and assigns the provided [`Damage` ID] to it.
+	///
+	/// # Events
+	/// This [request] causes [`DamageNotify`] events to be generated when the
+	/// `drawable` is damaged, according to the given `level`.
+	///
+	/// # Errors
+	/// A [`Drawable` error] is generated if `drawable` does not refer to a
+	/// defined [window] nor [pixmap].
+	///
+	/// A [`ResourceIdChoice` error] is generated if `damage` specifies an ID
+	/// already used for another resource, or an ID not allocated to your
+	/// client.
+	///
+	/// A [`Value` error] is generated if `level` is not a valid
+	/// [`ReportLevel`].
+	///
+	/// [drawable]: Drawable
+	/// [window]: crate::Window
+	/// [pixmap]: crate::Pixmap
+	/// [request]: Request
+	///
+	/// [`Damage` ID]: Damage
+	/// [`DamageNotify`]: super::DamageNotify
+	///
+	/// [`Drawable` error]: error::Drawable
+	/// [`ResourceIdChoice` error]: error::ResourceIdChoice
+	/// [`Value` error]: error::Value
+	#[doc(alias("DamageCreate"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct Create: Request(UNASSIGNED_MAJOR_OPCODE, 1, CreateError) {
+		/// The [`Damage` ID] which is to be assigned to the [`Damage`] object.
+		///
+		/// [`Damage` ID]: Damage
+		pub damage: Damage,
+		/// The [drawable] whose damage is tracked.
+		///
+		/// [drawable]: Drawable
+		pub drawable: Drawable,
+
+		/// When [`DamageNotify`] events are generated.
+		///
+		/// [`DamageNotify`]: super::DamageNotify
+		pub level: ReportLevel,
+		[_; 3],
+	}
+
+	/// A [request] that deletes the given [`Damage`] object.
+	///
+	/// [request]: Request
+	#[doc(alias("DamageDestroy"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct Destroy: Request(UNASSIGNED_MAJOR_OPCODE, 2) {
+		/// The [`Damage`] object which is to be deleted.
+		pub damage: Damage,
+	}
+
+	/// A [request] that removes the given `repair` [region] from the damaged
+	/// area of the given [`Damage`] object.
+	///
+	/// If `repair` is [`None`], the whole damaged area is removed.
+	///
+	/// # Events
+	/// If the damaged area is not empty after the `repair` is removed, a
+	/// [`DamageNotify`] event is generated, as if the remaining area were
+	/// damaged again.
+	///
+	/// [region]: Region
+	/// [request]: Request
+	///
+	/// [`DamageNotify`]: super::DamageNotify
+	#[doc(alias("DamageSubtract"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct Subtract: Request(UNASSIGNED_MAJOR_OPCODE, 3) {
+		/// The [`Damage`] object whose damaged area is repaired.
+		pub damage: Damage,
+
+		/// The [region] which is to be removed from the damaged area.
+		///
+		/// If this is [`None`], the whole damaged area is removed.
+		///
+		/// [region]: Region
+		pub repair: Option<Region>,
+		/// The [region] which is set to the area which was removed from the
+		/// damaged area, if any.
+		///
+		/// [region]: Region
+		pub parts: Option<Region>,
+	}
+}
@@ -0,0 +1,311 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Parsing of display names, such as the `DISPLAY` environment variable.
+//!
+//! A display name identifies which X server to connect to and which of its
+//! screens to use by default. It takes the form
+//! `[protocol/][host]:display[.screen]`, for example:
+//! - `:0` - display `0` on this machine;
+//! - `:1.0` - screen `0` of display `1` on this machine;
+//! - `localhost:10.0` - screen `0` of display `10` on `localhost`, over TCP;
+//! - `unix:0` - display `0` on this machine, over a Unix domain socket;
+//! - `tcp/example.org:0` - display `0` on `example.org`, over TCP.
+//!
+//! Parsing a display name does not perform any I/O: the [`DisplayAddress`]
+//! resolved from a [`DisplayName`] is what a client connects to.
+
+use std::{fmt, path::PathBuf, str::FromStr};
+use thiserror::Error;
+
+/// The directory containing the Unix domain sockets of local X servers.
+pub const UNIX_SOCKET_DIRECTORY: &str = "/tmp/.X11-unix";
+
+/// The TCP port used by display `0`.
+///
+/// Display `n` uses the port `TCP_PORT_BASE + n`.
+pub const TCP_PORT_BASE: u16 = 6000;
+
+/// A parsed display name, such as the value of the `DISPLAY` environment
+/// variable.
+///
+/// See the [module-level documentation] for the format of display names.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayName {
+	/// The host running the X server, or [`None`] if the X server is running
+	/// on this machine.
+	///
+	/// A host of `unix` is equivalent to no host.
+	pub host: Option<String>,
+	/// The protocol given before the host, if any.
+	///
+	/// This is only a hint: only [`DisplayName::is_unix`] affects how the
+	/// [`DisplayAddress`] is resolved.
+	pub protocol: Option<String>,
+
+	/// The number of the display.
+	///
+	/// This is at most <code>[u16::MAX] - [TCP_PORT_BASE]</code>, so that its
+	/// TCP port is always valid.
+	pub display: u16,
+	/// The number of the screen to use by default, or `0` if none was given.
+	pub screen: u16,
+}
+
+/// The address of an X server resolved from a [`DisplayName`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DisplayAddress {
+	/// The path of the Unix domain socket of a local X server.
+	UnixSocket(PathBuf),
+	/// The host and port of an X server reached over TCP.
+	Tcp(String, u16),
+}
+
+/// An error returned when a [`DisplayName`] cannot be parsed.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Error)]
+pub enum DisplayNameError {
+	/// There was no display number following a `:`.
+	#[error("missing display number in display name {0:?}")]
+	MissingDisplayNumber(String),
+
+	/// The display number was not a number, or was too large.
+	#[error("invalid display number {0:?}")]
+	InvalidDisplayNumber(String),
+	/// The screen number following the display number's `.` was not a number.
+	#[error("invalid screen number {0:?}")]
+	InvalidScreenNumber(String),
+
+	/// A protocol was given, but the host following it was empty.
+	#[error("protocol {0:?} was given with an empty host")]
+	EmptyHostWithProtocol(String),
+}
+
+impl DisplayName {
+	/// Whether the X server is reached over a Unix domain socket.
+	///
+	/// This is the case if the protocol is `unix` or `local`, or if no protocol
+	/// or host was given.
+	#[must_use]
+	pub fn is_unix(&self) -> bool {
+		matches!(self.protocol.as_deref(), Some("unix" | "local")) || self.host.is_none()
+	}
+
+	/// Resolves the [address] of the X server.
+	///
+	/// Local X servers are reached at the Unix domain socket
+	/// `/tmp/.X11-unix/X{display}`; remote X servers are reached over TCP at
+	/// the port <code>[TCP_PORT_BASE] + display</code>.
+	///
+	/// [address]: DisplayAddress
+	#[must_use]
+	pub fn address(&self) -> DisplayAddress {
+		match &self.host {
+			Some(host) if !self.is_unix() => {
+				DisplayAddress::Tcp(host.clone(), TCP_PORT_BASE + self.display)
+			},
+
+			_ => DisplayAddress::UnixSocket(
+				PathBuf::from(UNIX_SOCKET_DIRECTORY).join(format!("X{}", self.display)),
+			),
+		}
+	}
+}
+
+impl FromStr for DisplayName {
+	type Err = DisplayNameError;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		let (protocol, rest) = match name.split_once('/') {
+			Some((protocol, rest)) => (Some(protocol), rest),
+			None => (None, name),
+		};
+
+		// The display number follows the last `:`, so that IPv6 addresses may
+		// be used as the host.
+		let Some((host, number)) = rest.rsplit_once(':') else {
+			return Err(DisplayNameError::MissingDisplayNumber(name.to_owned()));
+		};
+
+		let (display, screen) = match number.split_once('.') {
+			Some((display, screen)) => (display, Some(screen)),
+			None => (number, None),
+		};
+
+		if display.is_empty() {
+			return Err(DisplayNameError::MissingDisplayNumber(name.to_owned()));
+		}
+
+		let display = display
+			.parse::<u16>()
+			.ok()
+			.filter(|display| *display <= u16::MAX - TCP_PORT_BASE)
+			.ok_or_else(|| DisplayNameError::InvalidDisplayNumber(display.to_owned()))?;
+
+		let screen = match screen {
+			Some(screen) => screen
+				.parse()
+				.map_err(|_| DisplayNameError::InvalidScreenNumber(screen.to_owned()))?,
+			None => 0,
+		};
+
+		if let Some(protocol) = protocol {
+			if host.is_empty() {
+				return Err(DisplayNameError::EmptyHostWithProtocol(protocol.to_owned()));
+			}
+		}
+
+		// IPv6 addresses may be enclosed in brackets.
+		let host = host
+			.strip_prefix('[')
+			.and_then(|host| host.strip_suffix(']'))
+			.unwrap_or(host);
+
+		Ok(Self {
+			host: match host {
+				"" | "unix" => None,
+				host => Some(host.to_owned()),
+			},
+			protocol: protocol.map(str::to_owned),
+
+			display,
+			screen,
+		})
+	}
+}
+
+impl fmt::Display for DisplayAddress {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::UnixSocket(path) => write!(f, "{}", path.display()),
+
+			Self::Tcp(host, port) if host.contains(':') => write!(f, "[{host}]:{port}"),
+			Self::Tcp(host, port) => write!(f, "{host}:{port}"),
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn parse(name: &str) -> DisplayName {
+		name.parse().unwrap()
+	}
+
+	fn unix_socket(display: u16) -> DisplayAddress {
+		DisplayAddress::UnixSocket(PathBuf::from(format!("/tmp/.X11-unix/X{display}")))
+	}
+
+	#[test]
+	fn test_local_display() {
+		let name = parse(":0");
+
+		assert_eq!(name.host, None);
+		assert_eq!(name.protocol, None);
+		assert_eq!(name.display, 0);
+		assert_eq!(name.screen, 0);
+		assert_eq!(name.address(), unix_socket(0));
+	}
+
+	#[test]
+	fn test_local_display_with_screen() {
+		let name = parse(":1.2");
+
+		assert_eq!((name.display, name.screen), (1, 2));
+		assert_eq!(name.address(), unix_socket(1));
+	}
+
+	#[test]
+	fn test_tcp_display() {
+		let name = parse("localhost:10.0");
+
+		assert_eq!(name.host.as_deref(), Some("localhost"));
+		assert_eq!((name.display, name.screen), (10, 0));
+		assert_eq!(
+			name.address(),
+			DisplayAddress::Tcp("localhost".to_owned(), 6010)
+		);
+	}
+
+	#[test]
+	fn test_unix_host() {
+		let name = parse("unix:0");
+
+		assert_eq!(name.host, None);
+		assert_eq!(name.address(), unix_socket(0));
+	}
+
+	#[test]
+	fn test_protocol() {
+		let name = parse("hostname/unix:0");
+
+		assert_eq!(name.protocol.as_deref(), Some("hostname"));
+		assert_eq!(name.host, None);
+		assert_eq!(name.address(), unix_socket(0));
+
+		let name = parse("tcp/example.org:3.1");
+
+		assert_eq!(name.protocol.as_deref(), Some("tcp"));
+		assert_eq!(name.host.as_deref(), Some("example.org"));
+		assert_eq!(
+			name.address(),
+			DisplayAddress::Tcp("example.org".to_owned(), 6003)
+		);
+
+		let name = parse("unix/example.org:0");
+
+		assert!(name.is_unix());
+		assert_eq!(name.address(), unix_socket(0));
+	}
+
+	#[test]
+	fn test_ipv6_host() {
+		for name in ["::1:0", "[::1]:0"] {
+			let name = parse(name);
+
+			assert_eq!(name.host.as_deref(), Some("::1"));
+			assert_eq!(name.address(), DisplayAddress::Tcp("::1".to_owned(), 6000));
+			assert_eq!(name.address().to_string(), "[::1]:6000");
+		}
+	}
+
+	#[test]
+	fn test_errors() {
+		for (name, error) in [
+			("", DisplayNameError::MissingDisplayNumber(String::new())),
+			(
+				"localhost",
+				DisplayNameError::MissingDisplayNumber("localhost".to_owned()),
+			),
+			(
+				"localhost:",
+				DisplayNameError::MissingDisplayNumber("localhost:".to_owned()),
+			),
+			(
+				":.0",
+				DisplayNameError::MissingDisplayNumber(":.0".to_owned()),
+			),
+			(":x", DisplayNameError::InvalidDisplayNumber("x".to_owned())),
+			(
+				":60000",
+				DisplayNameError::InvalidDisplayNumber("60000".to_owned()),
+			),
+			(
+				":0.x",
+				DisplayNameError::InvalidScreenNumber("x".to_owned()),
+			),
+			(":0.", DisplayNameError::InvalidScreenNumber(String::new())),
+			(
+				"tcp/:0",
+				DisplayNameError::EmptyHostWithProtocol("tcp".to_owned()),
+			),
+		] {
+			assert_eq!(name.parse::<DisplayName>(), Err(error), "{name:?}");
+		}
+	}
+}
@@ -0,0 +1,909 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Atoms and [client messages] defined in the [Extended Window Manager Hints]
+//! (EWMH).
+//!
+//! Unlike the predefined [atoms] of the core protocol, EWMH atoms must be
+//! interned at runtime. Their names are known at compile time in
+//! [`EwmhAtoms::NAMES`]: a client can send one [`GetAtom` request] for each of
+//! those names (see [`EwmhAtoms::intern_requests`]) and then collect the
+//! replies, in the same order, into an [`EwmhAtoms`] table with
+//! [`EwmhAtoms::from_interned`].
+//!
+//! That table is then used to encode an [`EwmhMessage`] as a
+//! [`ClientMessage` event] and to decode a received [`ClientMessage` event]
+//! back into an [`EwmhMessage`].
+//!
+//! [Extended Window Manager Hints]: https://specifications.freedesktop.org/wm-spec/latest/
+//! [client messages]: ClientMessage
+//! [atoms]: crate::atom
+//! [`GetAtom` request]: GetAtom
+//! [`ClientMessage` event]: ClientMessage
+
+use xrbk::{ReadError, ReadResult};
+
+use crate::{
+	icccm::WrongDataFormat,
+	x11::{
+		event::{ClientMessage, ClientMessageData},
+		request::{DataFormat, GetAtom},
+	},
+	Atom,
+	Char8,
+	CurrentableTime,
+	String8,
+	Timestamp,
+	Window,
+	WindowGravity,
+};
+
+macro_rules! ewmh_atoms {
+	(
+		$(
+			$(#[$attr:meta])*
+			$field:ident: $name:literal
+		),*$(,)?
+	) => {
+		/// A table of EWMH [atoms], interned at runtime.
+		///
+		/// See the [module-level documentation] for more information.
+		///
+		/// [atoms]: Atom
+		/// [module-level documentation]: self
+		#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+		pub struct EwmhAtoms {
+			$(
+				#[doc = concat!("The `", $name, "` [atom](Atom).")]
+				$(#[$attr])*
+				pub $field: Atom,
+			)*
+		}
+
+		impl EwmhAtoms {
+			/// The names of the [atoms] in this table, in the order of its
+			/// fields.
+			///
+			/// [atoms]: Atom
+			pub const NAMES: &'static [&'static str] = &[$($name),*];
+
+			/// Creates an `EwmhAtoms` table from `atoms` interned for each of
+			/// the [`NAMES`], in the same order.
+			///
+			/// Returns [`None`] if the number of `atoms` does not match the
+			/// number of [`NAMES`].
+			///
+			/// [`NAMES`]: Self::NAMES
+			#[must_use]
+			pub fn from_interned(atoms: &[Atom]) -> Option<Self> {
+				let mut atoms = atoms.iter().copied();
+
+				let table = Self {
+					$($field: atoms.next()?,)*
+				};
+
+				atoms.next().is_none().then_some(table)
+			}
+
+			/// Returns the name of the given `atom`, if it is contained in
+			/// this table.
+			#[must_use]
+			pub fn name_of(&self, atom: Atom) -> Option<&'static str> {
+				$(
+					if atom == self.$field {
+						return Some($name);
+					}
+				)*
+
+				None
+			}
+		}
+	};
+}
+
+ewmh_atoms! {
+	utf8_string: "UTF8_STRING",
+
+	// Root window properties & messages.
+	net_supported: "_NET_SUPPORTED",
+	net_client_list: "_NET_CLIENT_LIST",
+	net_client_list_stacking: "_NET_CLIENT_LIST_STACKING",
+	net_number_of_desktops: "_NET_NUMBER_OF_DESKTOPS",
+	net_desktop_geometry: "_NET_DESKTOP_GEOMETRY",
+	net_desktop_viewport: "_NET_DESKTOP_VIEWPORT",
+	net_current_desktop: "_NET_CURRENT_DESKTOP",
+	net_desktop_names: "_NET_DESKTOP_NAMES",
+	net_active_window: "_NET_ACTIVE_WINDOW",
+	net_workarea: "_NET_WORKAREA",
+	net_supporting_wm_check: "_NET_SUPPORTING_WM_CHECK",
+	net_virtual_roots: "_NET_VIRTUAL_ROOTS",
+	net_desktop_layout: "_NET_DESKTOP_LAYOUT",
+	net_showing_desktop: "_NET_SHOWING_DESKTOP",
+
+	// Other root window messages.
+	net_close_window: "_NET_CLOSE_WINDOW",
+	net_moveresize_window: "_NET_MOVERESIZE_WINDOW",
+	net_wm_moveresize: "_NET_WM_MOVERESIZE",
+	net_restack_window: "_NET_RESTACK_WINDOW",
+	net_request_frame_extents: "_NET_REQUEST_FRAME_EXTENTS",
+
+	// Application window properties.
+	net_wm_name: "_NET_WM_NAME",
+	net_wm_visible_name: "_NET_WM_VISIBLE_NAME",
+	net_wm_icon_name: "_NET_WM_ICON_NAME",
+	net_wm_visible_icon_name: "_NET_WM_VISIBLE_ICON_NAME",
+	net_wm_desktop: "_NET_WM_DESKTOP",
+	net_wm_window_type: "_NET_WM_WINDOW_TYPE",
+	net_wm_state: "_NET_WM_STATE",
+	net_wm_allowed_actions: "_NET_WM_ALLOWED_ACTIONS",
+	net_wm_strut: "_NET_WM_STRUT",
+	net_wm_strut_partial: "_NET_WM_STRUT_PARTIAL",
+	net_wm_icon_geometry: "_NET_WM_ICON_GEOMETRY",
+	net_wm_icon: "_NET_WM_ICON",
+	net_wm_pid: "_NET_WM_PID",
+	net_wm_handled_icons: "_NET_WM_HANDLED_ICONS",
+	net_wm_user_time: "_NET_WM_USER_TIME",
+	net_wm_user_time_window: "_NET_WM_USER_TIME_WINDOW",
+	net_frame_extents: "_NET_FRAME_EXTENTS",
+	net_wm_opaque_region: "_NET_WM_OPAQUE_REGION",
+	net_wm_bypass_compositor: "_NET_WM_BYPASS_COMPOSITOR",
+
+	// Window manager protocols.
+	net_wm_ping: "_NET_WM_PING",
+	net_wm_sync_request: "_NET_WM_SYNC_REQUEST",
+	net_wm_fullscreen_monitors: "_NET_WM_FULLSCREEN_MONITORS",
+
+	// `_NET_WM_WINDOW_TYPE` values.
+	net_wm_window_type_desktop: "_NET_WM_WINDOW_TYPE_DESKTOP",
+	net_wm_window_type_dock: "_NET_WM_WINDOW_TYPE_DOCK",
+	net_wm_window_type_toolbar: "_NET_WM_WINDOW_TYPE_TOOLBAR",
+	net_wm_window_type_menu: "_NET_WM_WINDOW_TYPE_MENU",
+	net_wm_window_type_utility: "_NET_WM_WINDOW_TYPE_UTILITY",
+	net_wm_window_type_splash: "_NET_WM_WINDOW_TYPE_SPLASH",
+	net_wm_window_type_dialog: "_NET_WM_WINDOW_TYPE_DIALOG",
+	net_wm_window_type_dropdown_menu: "_NET_WM_WINDOW_TYPE_DROPDOWN_MENU",
+	net_wm_window_type_popup_menu: "_NET_WM_WINDOW_TYPE_POPUP_MENU",
+	net_wm_window_type_tooltip: "_NET_WM_WINDOW_TYPE_TOOLTIP",
+	net_wm_window_type_notification: "_NET_WM_WINDOW_TYPE_NOTIFICATION",
+	net_wm_window_type_combo: "_NET_WM_WINDOW_TYPE_COMBO",
+	net_wm_window_type_dnd: "_NET_WM_WINDOW_TYPE_DND",
+	net_wm_window_type_normal: "_NET_WM_WINDOW_TYPE_NORMAL",
+
+	// `_NET_WM_STATE` values.
+	net_wm_state_modal: "_NET_WM_STATE_MODAL",
+	net_wm_state_sticky: "_NET_WM_STATE_STICKY",
+	net_wm_state_maximized_vert: "_NET_WM_STATE_MAXIMIZED_VERT",
+	net_wm_state_maximized_horz: "_NET_WM_STATE_MAXIMIZED_HORZ",
+	net_wm_state_shaded: "_NET_WM_STATE_SHADED",
+	net_wm_state_skip_taskbar: "_NET_WM_STATE_SKIP_TASKBAR",
+	net_wm_state_skip_pager: "_NET_WM_STATE_SKIP_PAGER",
+	net_wm_state_hidden: "_NET_WM_STATE_HIDDEN",
+	net_wm_state_fullscreen: "_NET_WM_STATE_FULLSCREEN",
+	net_wm_state_above: "_NET_WM_STATE_ABOVE",
+	net_wm_state_below: "_NET_WM_STATE_BELOW",
+	net_wm_state_demands_attention: "_NET_WM_STATE_DEMANDS_ATTENTION",
+	net_wm_state_focused: "_NET_WM_STATE_FOCUSED",
+
+	// `_NET_WM_ALLOWED_ACTIONS` values.
+	net_wm_action_move: "_NET_WM_ACTION_MOVE",
+	net_wm_action_resize: "_NET_WM_ACTION_RESIZE",
+	net_wm_action_minimize: "_NET_WM_ACTION_MINIMIZE",
+	net_wm_action_shade: "_NET_WM_ACTION_SHADE",
+	net_wm_action_stick: "_NET_WM_ACTION_STICK",
+	net_wm_action_maximize_horz: "_NET_WM_ACTION_MAXIMIZE_HORZ",
+	net_wm_action_maximize_vert: "_NET_WM_ACTION_MAXIMIZE_VERT",
+	net_wm_action_fullscreen: "_NET_WM_ACTION_FULLSCREEN",
+	net_wm_action_change_desktop: "_NET_WM_ACTION_CHANGE_DESKTOP",
+	net_wm_action_close: "_NET_WM_ACTION_CLOSE",
+	net_wm_action_above: "_NET_WM_ACTION_ABOVE",
+	net_wm_action_below: "_NET_WM_ACTION_BELOW",
+}
+
+impl EwmhAtoms {
+	/// Returns a [`GetAtom` request] for each of the [`NAMES`], in order.
+	///
+	/// The [atoms] returned in the replies can be collected into an
+	/// `EwmhAtoms` table with [`EwmhAtoms::from_interned`].
+	///
+	/// [atoms]: Atom
+	/// [`GetAtom` request]: GetAtom
+	/// [`NAMES`]: Self::NAMES
+	pub fn intern_requests(no_creation: bool) -> impl Iterator<Item = GetAtom> {
+		Self::NAMES.iter().map(move |name| GetAtom {
+			no_creation,
+			name: String8::from(name.bytes().map(Char8::new).collect::<Vec<_>>()),
+		})
+	}
+}
+
+macro_rules! atom_enum {
+	(
+		$(#[$meta:meta])*
+		pub enum $Name:ident {
+			$(
+				$(#[$attr:meta])*
+				$Variant:ident => $field:ident
+			),*$(,)?
+		}
+	) => {
+		$(#[$meta])*
+		#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+		pub enum $Name {
+			$(
+				$(#[$attr])*
+				$Variant,
+			)*
+		}
+
+		impl $Name {
+			/// Returns the [atom] representing this value in the given table
+			/// of `atoms`.
+			///
+			/// [atom]: Atom
+			#[must_use]
+			pub const fn atom(self, atoms: &EwmhAtoms) -> Atom {
+				match self {
+					$(Self::$Variant => atoms.$field,)*
+				}
+			}
+
+			/// Returns the value represented by the given `atom` in the given
+			/// table of `atoms`, if there is one.
+			#[must_use]
+			pub fn from_atom(atom: Atom, atoms: &EwmhAtoms) -> Option<Self> {
+				$(
+					if atom == atoms.$field {
+						return Some(Self::$Variant);
+					}
+				)*
+
+				None
+			}
+		}
+	};
+}
+
+atom_enum! {
+	/// A functional type of [window], as listed in `_NET_WM_WINDOW_TYPE`.
+	///
+	/// [window]: Window
+	pub enum WindowType {
+		/// `_NET_WM_WINDOW_TYPE_DESKTOP`
+		Desktop => net_wm_window_type_desktop,
+		/// `_NET_WM_WINDOW_TYPE_DOCK`
+		Dock => net_wm_window_type_dock,
+		/// `_NET_WM_WINDOW_TYPE_TOOLBAR`
+		Toolbar => net_wm_window_type_toolbar,
+		/// `_NET_WM_WINDOW_TYPE_MENU`
+		Menu => net_wm_window_type_menu,
+		/// `_NET_WM_WINDOW_TYPE_UTILITY`
+		Utility => net_wm_window_type_utility,
+		/// `_NET_WM_WINDOW_TYPE_SPLASH`
+		Splash => net_wm_window_type_splash,
+		/// `_NET_WM_WINDOW_TYPE_DIALOG`
+		Dialog => net_wm_window_type_dialog,
+		/// `_NET_WM_WINDOW_TYPE_DROPDOWN_MENU`
+		DropdownMenu => net_wm_window_type_dropdown_menu,
+		/// `_NET_WM_WINDOW_TYPE_POPUP_MENU`
+		PopupMenu => net_wm_window_type_popup_menu,
+		/// `_NET_WM_WINDOW_TYPE_TOOLTIP`
+		Tooltip => net_wm_window_type_tooltip,
+		/// `_NET_WM_WINDOW_TYPE_NOTIFICATION`
+		Notification => net_wm_window_type_notification,
+		/// `_NET_WM_WINDOW_TYPE_COMBO`
+		Combo => net_wm_window_type_combo,
+		/// `_NET_WM_WINDOW_TYPE_DND`
+		Dnd => net_wm_window_type_dnd,
+		/// `_NET_WM_WINDOW_TYPE_NORMAL`
+		Normal => net_wm_window_type_normal,
+	}
+}
+
+atom_enum! {
+	/// A state of a [window], as listed in `_NET_WM_STATE`.
+	///
+	/// [window]: Window
+	pub enum WmState {
+		/// `_NET_WM_STATE_MODAL`
+		Modal => net_wm_state_modal,
+		/// `_NET_WM_STATE_STICKY`
+		Sticky => net_wm_state_sticky,
+		/// `_NET_WM_STATE_MAXIMIZED_VERT`
+		MaximizedVert => net_wm_state_maximized_vert,
+		/// `_NET_WM_STATE_MAXIMIZED_HORZ`
+		MaximizedHorz => net_wm_state_maximized_horz,
+		/// `_NET_WM_STATE_SHADED`
+		Shaded => net_wm_state_shaded,
+		/// `_NET_WM_STATE_SKIP_TASKBAR`
+		SkipTaskbar => net_wm_state_skip_taskbar,
+		/// `_NET_WM_STATE_SKIP_PAGER`
+		SkipPager => net_wm_state_skip_pager,
+		/// `_NET_WM_STATE_HIDDEN`
+		Hidden => net_wm_state_hidden,
+		/// `_NET_WM_STATE_FULLSCREEN`
+		Fullscreen => net_wm_state_fullscreen,
+		/// `_NET_WM_STATE_ABOVE`
+		Above => net_wm_state_above,
+		/// `_NET_WM_STATE_BELOW`
+		Below => net_wm_state_below,
+		/// `_NET_WM_STATE_DEMANDS_ATTENTION`
+		DemandsAttention => net_wm_state_demands_attention,
+		/// `_NET_WM_STATE_FOCUSED`
+		Focused => net_wm_state_focused,
+	}
+}
+
+/// Which kind of client sent an [`EwmhMessage`].
+///
+/// Window managers may treat requests from pagers and other tools acting on
+/// behalf of the user differently to those from applications.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SourceIndication {
+	/// The message was sent by a client which predates source indications.
+	Legacy,
+	/// The message was sent by an application.
+	Application,
+	/// The message was sent by a pager or other tool acting on behalf of the
+	/// user.
+	Pager,
+}
+
+impl TryFrom<u32> for SourceIndication {
+	type Error = ReadError;
+
+	fn try_from(value: u32) -> ReadResult<Self> {
+		match value {
+			0 => Ok(Self::Legacy),
+			1 => Ok(Self::Application),
+			2 => Ok(Self::Pager),
+
+			other => Err(ReadError::UnrecognizedDiscriminant(other as usize)),
+		}
+	}
+}
+
+impl From<SourceIndication> for u32 {
+	fn from(source: SourceIndication) -> Self {
+		match source {
+			SourceIndication::Legacy => 0,
+			SourceIndication::Application => 1,
+			SourceIndication::Pager => 2,
+		}
+	}
+}
+
+/// How the states in a [`EwmhMessage::WmState`] message are changed.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WmStateAction {
+	/// The states are removed.
+	Remove,
+	/// The states are added.
+	Add,
+	/// The states are toggled.
+	Toggle,
+}
+
+impl TryFrom<u32> for WmStateAction {
+	type Error = ReadError;
+
+	fn try_from(value: u32) -> ReadResult<Self> {
+		match value {
+			0 => Ok(Self::Remove),
+			1 => Ok(Self::Add),
+			2 => Ok(Self::Toggle),
+
+			other => Err(ReadError::UnrecognizedDiscriminant(other as usize)),
+		}
+	}
+}
+
+impl From<WmStateAction> for u32 {
+	fn from(action: WmStateAction) -> Self {
+		match action {
+			WmStateAction::Remove => 0,
+			WmStateAction::Add => 1,
+			WmStateAction::Toggle => 2,
+		}
+	}
+}
+
+/// A [client message] defined in EWMH.
+///
+/// Each of these messages is sent to the root [window] with a
+/// [`SendEvent` request] selecting [`SUBSTRUCTURE_NOTIFY`] and
+/// [`SUBSTRUCTURE_REDIRECT`]; the `window` of each message is the [window] it
+/// applies to.
+///
+/// [client message]: ClientMessage
+/// [window]: Window
+/// [`SendEvent` request]: crate::x11::request::SendEvent
+/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
+/// [`SUBSTRUCTURE_REDIRECT`]: crate::EventMask::SUBSTRUCTURE_REDIRECT
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EwmhMessage {
+	/// `_NET_WM_STATE`: changes one or two states of a [window].
+	///
+	/// [window]: Window
+	WmState {
+		/// The [window] whose states are changed.
+		///
+		/// [window]: Window
+		window: Window,
+		/// How the states are changed.
+		action: WmStateAction,
+		/// The first state which is changed.
+		///
+		/// This is an [atom] rather than a [`WmState`] so that states not
+		/// defined in EWMH can be represented.
+		///
+		/// [atom]: Atom
+		first: Atom,
+		/// The second state which is changed, if any.
+		second: Option<Atom>,
+		/// Which kind of client sent this message.
+		source: SourceIndication,
+	},
+
+	/// `_NET_ACTIVE_WINDOW`: requests that a [window] is activated.
+	///
+	/// [window]: Window
+	ActiveWindow {
+		/// The [window] to activate.
+		///
+		/// [window]: Window
+		window: Window,
+		/// Which kind of client sent this message.
+		source: SourceIndication,
+		/// The time of the user action which caused this request.
+		time: CurrentableTime,
+		/// The sender's currently active [window], if any.
+		///
+		/// [window]: Window
+		currently_active: Option<Window>,
+	},
+
+	/// `_NET_CLOSE_WINDOW`: requests that a [window] is closed.
+	///
+	/// [window]: Window
+	CloseWindow {
+		/// The [window] to close.
+		///
+		/// [window]: Window
+		window: Window,
+		/// The time of the user action which caused this request.
+		time: CurrentableTime,
+		/// Which kind of client sent this message.
+		source: SourceIndication,
+	},
+
+	/// `_NET_MOVERESIZE_WINDOW`: requests that a [window] is moved and/or
+	/// resized.
+	///
+	/// [window]: Window
+	MoveResizeWindow {
+		/// The [window] to move and/or resize.
+		///
+		/// [window]: Window
+		window: Window,
+		/// The [window gravity] to use, or [`None`] to use the gravity
+		/// specified in the [window]'s `WM_NORMAL_HINTS`.
+		///
+		/// [`WindowGravity::Unmap`] cannot be sent in this message: its
+		/// value, zero, means [`None`]. It is sent as [`None`] instead.
+		///
+		/// [window]: Window
+		/// [window gravity]: WindowGravity
+		gravity: Option<WindowGravity>,
+		/// Which kind of client sent this message.
+		source: SourceIndication,
+
+		/// The new x-coordinate, if it is changed.
+		x: Option<i32>,
+		/// The new y-coordinate, if it is changed.
+		y: Option<i32>,
+		/// The new width, if it is changed.
+		width: Option<i32>,
+		/// The new height, if it is changed.
+		height: Option<i32>,
+	},
+}
+
+impl EwmhMessage {
+	/// Creates a `_NET_WM_STATE` message changing the `first` state of a
+	/// `window` according to the `action`.
+	///
+	/// If `second` is [`Some`], that state is changed in the same way, in the
+	/// same message: for example, to maximize a `window` both vertically and
+	/// horizontally at once. If it is [`None`], only the `first` state is
+	/// changed.
+	#[must_use]
+	pub fn wm_state(
+		atoms: &EwmhAtoms, window: Window, action: WmStateAction, first: WmState,
+		second: Option<WmState>, source: SourceIndication,
+	) -> Self {
+		Self::WmState {
+			window,
+			action,
+			first: first.atom(atoms),
+			second: second.map(|state| state.atom(atoms)),
+			source,
+		}
+	}
+
+	/// The [window] this message applies to.
+	///
+	/// [window]: Window
+	#[must_use]
+	pub const fn window(&self) -> Window {
+		match self {
+			Self::WmState { window, .. }
+			| Self::ActiveWindow { window, .. }
+			| Self::CloseWindow { window, .. }
+			| Self::MoveResizeWindow { window, .. } => *window,
+		}
+	}
+
+	/// Encodes this message as a [`ClientMessage` event] using the given table
+	/// of `atoms`.
+	///
+	/// [`ClientMessage` event]: ClientMessage
+	#[must_use]
+	#[allow(clippy::cast_sign_loss)]
+	pub fn to_client_message(&self, atoms: &EwmhAtoms) -> ClientMessage {
+		let time = |time: &CurrentableTime| match time {
+			CurrentableTime::CurrentTime => 0,
+			CurrentableTime::Other(timestamp) => timestamp.unwrap(),
+		};
+
+		let (r#type, data): (Atom, [u32; 5]) = match self {
+			Self::WmState {
+				action,
+				first,
+				second,
+				source,
+				..
+			} => (
+				atoms.net_wm_state,
+				[
+					u32::from(*action),
+					first.unwrap(),
+					second.map_or(0, Atom::unwrap),
+					u32::from(*source),
+					0,
+				],
+			),
+
+			Self::ActiveWindow {
+				source,
+				time: timestamp,
+				currently_active,
+				..
+			} => (
+				atoms.net_active_window,
+				[
+					u32::from(*source),
+					time(timestamp),
+					currently_active.map_or(0, Window::unwrap),
+					0,
+					0,
+				],
+			),
+
+			Self::CloseWindow {
+				time: timestamp,
+				source,
+				..
+			} => (
+				atoms.net_close_window,
+				[time(timestamp), u32::from(*source), 0, 0, 0],
+			),
+
+			Self::MoveResizeWindow {
+				gravity,
+				source,
+				x,
+				y,
+				width,
+				height,
+				..
+			} => {
+				let mut flags = match gravity {
+					// A gravity of zero means that the gravity in
+					// `WM_NORMAL_HINTS` is used, so `Unmap` is sent as `None`.
+					None | Some(WindowGravity::Unmap) => 0,
+					Some(gravity) => u32::from(*gravity),
+				};
+
+				for (bit, value) in [x, y, width, height].into_iter().enumerate() {
+					if value.is_some() {
+						flags |= 1 << (8 + bit);
+					}
+				}
+
+				flags |= u32::from(*source) << 12;
+
+				(
+					atoms.net_moveresize_window,
+					[
+						flags,
+						x.unwrap_or(0) as u32,
+						y.unwrap_or(0) as u32,
+						width.unwrap_or(0) as u32,
+						height.unwrap_or(0) as u32,
+					],
+				)
+			},
+		};
+
+		ClientMessage::new(self.window(), r#type, ClientMessageData::Longs(data))
+	}
+
+	/// Decodes an EWMH message from the given [`ClientMessage` event] using
+	/// the given table of `atoms`.
+	///
+	/// Returns `Ok(None)` if the `message` is not one of the EWMH messages
+	/// represented by `EwmhMessage`.
+	///
+	/// # Errors
+	/// Returns a [`ReadError`] if the `message` has the `type` of an EWMH
+	/// message, but its `data` is not in the 32-bit format (a
+	/// [`WrongDataFormat`] error) or contains an invalid value.
+	///
+	/// [`ClientMessage` event]: ClientMessage
+	#[allow(clippy::cast_possible_wrap)]
+	pub fn from_client_message(
+		message: &ClientMessage, atoms: &EwmhAtoms,
+	) -> ReadResult<Option<Self>> {
+		let r#type = message.r#type;
+
+		if ![
+			atoms.net_wm_state,
+			atoms.net_active_window,
+			atoms.net_close_window,
+			atoms.net_moveresize_window,
+		]
+		.contains(&r#type)
+		{
+			return Ok(None);
+		}
+
+		let wrong_format = |found| {
+			ReadError::Other(Box::new(WrongDataFormat {
+				expected: DataFormat::I32,
+				found,
+			}))
+		};
+
+		let data = match &message.data {
+			ClientMessageData::Longs(data) => *data,
+
+			ClientMessageData::Bytes(_) => return Err(wrong_format(DataFormat::I8)),
+			ClientMessageData::Words(_) => return Err(wrong_format(DataFormat::I16)),
+		};
+
+		let window = message.window;
+		let time = |value: u32| match value {
+			0 => CurrentableTime::CurrentTime,
+			timestamp => CurrentableTime::Other(Timestamp::new(timestamp)),
+		};
+
+		Ok(Some(
+			if r#type == atoms.net_wm_state {
+				Self::WmState {
+					window,
+					action: WmStateAction::try_from(data[0])?,
+					first: Atom::new(data[1]),
+					second: (data[2] != 0).then(|| Atom::new(data[2])),
+					source: SourceIndication::try_from(data[3])?,
+				}
+			} else if r#type == atoms.net_active_window {
+				Self::ActiveWindow {
+					window,
+					source: SourceIndication::try_from(data[0])?,
+					time: time(data[1]),
+					currently_active: (data[2] != 0).then(|| Window::new(data[2])),
+				}
+			} else if r#type == atoms.net_close_window {
+				Self::CloseWindow {
+					window,
+					time: time(data[0]),
+					source: SourceIndication::try_from(data[1])?,
+				}
+			} else {
+				let flags = data[0];
+				let value =
+					|bit: usize| (flags & (1 << (8 + bit)) != 0).then_some(data[bit + 1] as i32);
+
+				Self::MoveResizeWindow {
+					window,
+					gravity: match flags & 0xff {
+						0 => None,
+						gravity => Some(WindowGravity::try_from(gravity)?),
+					},
+					source: SourceIndication::try_from((flags >> 12) & 0xf)?,
+
+					x: value(0),
+					y: value(1),
+					width: value(2),
+					height: value(3),
+				}
+			},
+		))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::message::Event;
+	use xrbk::{Readable, Writable};
+
+	fn atoms() -> EwmhAtoms {
+		let interned: Vec<_> = (0..EwmhAtoms::NAMES.len())
+			.map(|index| Atom::new(300 + u32::try_from(index).unwrap()))
+			.collect();
+
+		EwmhAtoms::from_interned(&interned).unwrap()
+	}
+
+	#[test]
+	fn test_atom_table() {
+		let atoms = atoms();
+
+		assert_eq!(atoms.utf8_string, Atom::new(300));
+		assert_eq!(atoms.name_of(atoms.net_wm_state), Some("_NET_WM_STATE"));
+		assert_eq!(atoms.name_of(Atom::new(1)), None);
+		assert!(EwmhAtoms::from_interned(&[Atom::new(1)]).is_none());
+
+		assert_eq!(
+			WmState::from_atom(atoms.net_wm_state_fullscreen, &atoms),
+			Some(WmState::Fullscreen)
+		);
+		assert_eq!(WindowType::Dock.atom(&atoms), atoms.net_wm_window_type_dock);
+		assert_eq!(
+			EwmhAtoms::intern_requests(false).count(),
+			EwmhAtoms::NAMES.len()
+		);
+	}
+
+	#[test]
+	fn test_toggle_fullscreen_round_trip() {
+		let atoms = atoms();
+		let message = EwmhMessage::wm_state(
+			&atoms,
+			Window::new(0x0060_0001),
+			WmStateAction::Toggle,
+			WmState::Fullscreen,
+			None,
+			SourceIndication::Pager,
+		);
+
+		let mut bytes = vec![];
+		message
+			.to_client_message(&atoms)
+			.write_to(&mut bytes)
+			.unwrap();
+		assert_eq!(bytes.len(), 32);
+		// The event code is read separately to determine which event to read.
+		assert_eq!(bytes[..2], [ClientMessage::CODE, 32]);
+
+		let fullscreen = atoms.net_wm_state_fullscreen.unwrap().to_be_bytes();
+		#[rustfmt::skip]
+		assert_eq!(bytes[12..], [
+			// `_NET_WM_STATE_TOGGLE`.
+			0, 0, 0, 2,
+			// The first and second states.
+			fullscreen[0], fullscreen[1], fullscreen[2], fullscreen[3],
+			0, 0, 0, 0,
+			// The source indication: a pager.
+			0, 0, 0, 2,
+			0, 0, 0, 0,
+		]);
+
+		let client_message = ClientMessage::read_from(&mut &bytes[1..]).unwrap();
+
+		assert_eq!(
+			EwmhMessage::from_client_message(&client_message, &atoms).unwrap(),
+			Some(message)
+		);
+	}
+
+	#[test]
+	fn test_messages_round_trip() {
+		let atoms = atoms();
+		let window = Window::new(0x0060_0001);
+
+		let messages = [
+			EwmhMessage::ActiveWindow {
+				window,
+				source: SourceIndication::Application,
+				time: CurrentableTime::Other(Timestamp::new(1234)),
+				currently_active: Some(Window::new(0x0060_0002)),
+			},
+			EwmhMessage::CloseWindow {
+				window,
+				time: CurrentableTime::CurrentTime,
+				source: SourceIndication::Pager,
+			},
+			EwmhMessage::MoveResizeWindow {
+				window,
+				gravity: Some(WindowGravity::Static),
+				source: SourceIndication::Application,
+				x: Some(-10),
+				y: None,
+				width: Some(640),
+				height: None,
+			},
+		];
+
+		for message in messages {
+			let client_message = message.to_client_message(&atoms);
+
+			assert_eq!(
+				EwmhMessage::from_client_message(&client_message, &atoms).unwrap(),
+				Some(message)
+			);
+		}
+
+		let other = ClientMessage {
+			sequence: 0,
+			window,
+			r#type: Atom::new(1),
+			data: ClientMessageData::Longs([0; 5]),
+		};
+		assert_eq!(
+			EwmhMessage::from_client_message(&other, &atoms).unwrap(),
+			None
+		);
+	}
+
+	#[test]
+	fn test_move_resize_window_unmap_gravity() {
+		let atoms = atoms();
+
+		let message = EwmhMessage::MoveResizeWindow {
+			window: Window::new(0x0060_0001),
+			gravity: Some(WindowGravity::Unmap),
+			source: SourceIndication::Pager,
+			x: Some(0),
+			y: Some(0),
+			width: None,
+			height: None,
+		};
+
+		let client_message = message.to_client_message(&atoms);
+
+		match EwmhMessage::from_client_message(&client_message, &atoms).unwrap() {
+			Some(EwmhMessage::MoveResizeWindow { gravity, .. }) => assert_eq!(gravity, None),
+
+			other => panic!("expected a _NET_MOVERESIZE_WINDOW message, found {other:?}"),
+		}
+	}
+
+	#[test]
+	fn test_message_wrong_format() {
+		let atoms = atoms();
+
+		let message = ClientMessage {
+			sequence: 0,
+			window: Window::new(0x0060_0001),
+			r#type: atoms.net_close_window,
+			data: ClientMessageData::Words([0; 10]),
+		};
+
+		match EwmhMessage::from_client_message(&message, &atoms) {
+			Err(ReadError::Other(error)) => assert_eq!(
+				error.to_string(),
+				"expected property data of format I32, found I16",
+			),
+
+			other => panic!("expected a wrong data format error, found {other:?}"),
+		}
+	}
+}
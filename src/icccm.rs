@@ -0,0 +1,1708 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Properties defined in the [Inter-Client Communication Conventions Manual]
+//! (ICCCM).
+//!
+//! Each property type can be converted to and from the raw data of a property
+//! with `from_property_data` and `to_property_data`, or to and from a
+//! [`DataList`] as used in the [`GetProperty` reply] and the
+//! [`ModifyProperty` request].
+//!
+//! Properties containing text or lists of text, such as `WM_NAME` and
+//! `_NET_WM_NAME`, are represented by [`TextProperty`].
+//!
+//! Responding to [`ConvertSelectionRequest` events] as the owner of a
+//! selection is handled by [`SelectionOwnerHandler`].
+//!
+//! [Inter-Client Communication Conventions Manual]: https://x.org/releases/X11R7.7/doc/xorg-docs/icccm/icccm.html
+//! [`GetProperty` reply]: crate::x11::reply::GetProperty
+//! [`ModifyProperty` request]: crate::x11::request::ModifyProperty
+//! [`ConvertSelectionRequest` events]: ConvertSelectionRequest
+
+use bitflags::bitflags;
+use std::str::{self, Utf8Error};
+use thiserror::Error;
+use xrbk::{
+	Buf,
+	BufMut,
+	ConstantX11Size,
+	ReadError,
+	ReadResult,
+	Readable,
+	Writable,
+	WriteResult,
+	X11Size,
+};
+
+use crate::{
+	atom,
+	unit::Px,
+	x11::{
+		event::{ConvertSelectionRequest, Selection},
+		reply,
+		request::{
+			DataFormat,
+			DataList,
+			GetProperty,
+			ModifyProperty,
+			ModifyPropertyMode,
+			SendEvent,
+		},
+	},
+	Any,
+	Atom,
+	CurrentableTime,
+	DestinationWindow,
+	EventMask,
+	Pixmap,
+	Timestamp,
+	Window,
+	WindowGravity,
+};
+
+/// The [`DataList`] given for a property was not of the expected
+/// [`DataFormat`].
+#[derive(Error, Debug)]
+#[error("expected property data of format {expected:?}, found {found:?}")]
+pub struct WrongDataFormat {
+	/// The [`DataFormat`] required by the property type.
+	pub expected: DataFormat,
+	/// The [`DataFormat`] of the [`DataList`] which was given.
+	pub found: DataFormat,
+}
+
+/// The string used in a property was not encoded as Latin-1.
+#[derive(Error, Debug)]
+#[error("the provided string contained characters which cannot be encoded in Latin-1")]
+pub struct NonLatin1Encoding;
+
+/// Returns the values of `data` as `u32`s, requiring that `data` is a
+/// [`DataList::I32`].
+fn u32_values(data: &DataList) -> ReadResult<Vec<u32>> {
+	match data {
+		#[allow(clippy::cast_sign_loss)]
+		DataList::I32(values) => Ok(values.iter().map(|value| *value as u32).collect()),
+
+		other => Err(ReadError::Other(Box::new(WrongDataFormat {
+			expected: DataFormat::I32,
+			found: other.format(),
+		}))),
+	}
+}
+
+/// Converts the given `u32` values to a [`DataList::I32`].
+fn i32_data_list(values: &[u32]) -> DataList {
+	#[allow(clippy::cast_possible_wrap)]
+	DataList::I32(values.iter().map(|value| *value as i32).collect())
+}
+
+/// Copies `data` into an array of `N` values, filling any missing trailing
+/// values with zero.
+///
+/// Older clients may write shorter versions of some properties; the missing
+/// values are treated as though their flags were unset.
+fn padded<const N: usize>(data: &[u32]) -> [u32; N] {
+	let mut values = [0; N];
+
+	for (value, datum) in values.iter_mut().zip(data) {
+		*value = *datum;
+	}
+
+	values
+}
+
+/// The state of a top-level [window].
+///
+/// This is used both as the initial state requested in [`WmHints`] and as the
+/// current state recorded by the window manager in [`WmState`].
+///
+/// [window]: Window
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WindowState {
+	/// The [window] is neither mapped nor iconified.
+	///
+	/// [window]: Window
+	Withdrawn,
+	/// The [window] is visible.
+	///
+	/// [window]: Window
+	Normal,
+	/// The [window] is iconified.
+	///
+	/// [window]: Window
+	Iconic,
+}
+
+impl TryFrom<u32> for WindowState {
+	type Error = ReadError;
+
+	fn try_from(value: u32) -> ReadResult<Self> {
+		match value {
+			0 => Ok(Self::Withdrawn),
+			1 => Ok(Self::Normal),
+			3 => Ok(Self::Iconic),
+
+			other => Err(ReadError::UnrecognizedDiscriminant(other as usize)),
+		}
+	}
+}
+
+impl From<WindowState> for u32 {
+	fn from(state: WindowState) -> Self {
+		match state {
+			WindowState::Withdrawn => 0,
+			WindowState::Normal => 1,
+			WindowState::Iconic => 3,
+		}
+	}
+}
+
+bitflags! {
+	/// A mask of which fields of [`WmHints`] are present.
+	#[derive(Default)]
+	pub struct WmHintsFlags: u32 {
+		/// [`WmHints::input`] is present.
+		const INPUT = 0x0000_0001;
+		/// [`WmHints::initial_state`] is present.
+		const STATE = 0x0000_0002;
+		/// [`WmHints::icon_pixmap`] is present.
+		const ICON_PIXMAP = 0x0000_0004;
+		/// [`WmHints::icon_window`] is present.
+		const ICON_WINDOW = 0x0000_0008;
+		/// [`WmHints::icon_position`] is present.
+		const ICON_POSITION = 0x0000_0010;
+		/// [`WmHints::icon_mask`] is present.
+		const ICON_MASK = 0x0000_0020;
+		/// [`WmHints::window_group`] is present.
+		const WINDOW_GROUP = 0x0000_0040;
+		/// Obsolete.
+		const MESSAGE = 0x0000_0080;
+		/// [`WmHints::urgent`] is set.
+		const URGENCY = 0x0000_0100;
+	}
+}
+
+/// A position, measured in pixels, as used in ICCCM properties.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HintCoords {
+	/// The x-coordinate.
+	pub x: Px<i32>,
+	/// The y-coordinate.
+	pub y: Px<i32>,
+}
+
+/// The `WM_HINTS` property: hints about a top-level [window] for the window
+/// manager.
+///
+/// [window]: Window
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WmHints {
+	/// Whether the client relies on the window manager to set the input focus.
+	///
+	/// [`None`] means the client has not specified, which window managers
+	/// typically treat as `true`.
+	pub input: Option<bool>,
+	/// The state which the [window] should be in when it is first mapped.
+	///
+	/// [window]: Window
+	pub initial_state: Option<WindowState>,
+
+	/// A [pixmap] to be used as the icon.
+	///
+	/// [pixmap]: Pixmap
+	pub icon_pixmap: Option<Pixmap>,
+	/// A [window] to be used as the icon.
+	///
+	/// [window]: Window
+	pub icon_window: Option<Window>,
+	/// The initial position of the icon.
+	pub icon_position: Option<HintCoords>,
+	/// A bitmap [pixmap] specifying which pixels of the `icon_pixmap` are used.
+	///
+	/// [pixmap]: Pixmap
+	pub icon_mask: Option<Pixmap>,
+
+	/// The leader of the group of [windows] this [window] belongs to.
+	///
+	/// [window]: Window
+	/// [windows]: Window
+	pub window_group: Option<Window>,
+
+	/// Whether the contents of the [window] are urgent.
+	///
+	/// [window]: Window
+	pub urgent: bool,
+}
+
+impl WmHints {
+	/// The number of 32-bit values in a `WM_HINTS` property.
+	pub const LEN: usize = 9;
+
+	/// Returns the [`WmHintsFlags`] indicating which fields are present.
+	#[must_use]
+	pub fn flags(&self) -> WmHintsFlags {
+		let mut flags = WmHintsFlags::empty();
+
+		flags.set(WmHintsFlags::INPUT, self.input.is_some());
+		flags.set(WmHintsFlags::STATE, self.initial_state.is_some());
+		flags.set(WmHintsFlags::ICON_PIXMAP, self.icon_pixmap.is_some());
+		flags.set(WmHintsFlags::ICON_WINDOW, self.icon_window.is_some());
+		flags.set(WmHintsFlags::ICON_POSITION, self.icon_position.is_some());
+		flags.set(WmHintsFlags::ICON_MASK, self.icon_mask.is_some());
+		flags.set(WmHintsFlags::WINDOW_GROUP, self.window_group.is_some());
+		flags.set(WmHintsFlags::URGENCY, self.urgent);
+
+		flags
+	}
+
+	/// Decodes `WmHints` from the 32-bit values of a `WM_HINTS` property.
+	///
+	/// Missing trailing values are treated as zero.
+	///
+	/// # Errors
+	/// Returns [`ReadError::UnrecognizedDiscriminant`] if the initial state is
+	/// present but not a valid [`WindowState`].
+	pub fn from_property_data(data: &[u32]) -> ReadResult<Self> {
+		let [flags, input, initial_state, icon_pixmap, icon_window, icon_x, icon_y, icon_mask, window_group] =
+			padded::<{ Self::LEN }>(data);
+		let flags = WmHintsFlags::from_bits_truncate(flags);
+
+		#[allow(clippy::cast_possible_wrap)]
+		Ok(Self {
+			input: flags.contains(WmHintsFlags::INPUT).then_some(input != 0),
+			initial_state: if flags.contains(WmHintsFlags::STATE) {
+				Some(WindowState::try_from(initial_state)?)
+			} else {
+				None
+			},
+
+			icon_pixmap: flags
+				.contains(WmHintsFlags::ICON_PIXMAP)
+				.then(|| Pixmap::new(icon_pixmap)),
+			icon_window: flags
+				.contains(WmHintsFlags::ICON_WINDOW)
+				.then(|| Window::new(icon_window)),
+			icon_position: flags
+				.contains(WmHintsFlags::ICON_POSITION)
+				.then_some(HintCoords {
+					x: Px(icon_x as i32),
+					y: Px(icon_y as i32),
+				}),
+			icon_mask: flags
+				.contains(WmHintsFlags::ICON_MASK)
+				.then(|| Pixmap::new(icon_mask)),
+
+			window_group: flags
+				.contains(WmHintsFlags::WINDOW_GROUP)
+				.then(|| Window::new(window_group)),
+
+			urgent: flags.contains(WmHintsFlags::URGENCY),
+		})
+	}
+
+	/// Encodes these `WmHints` as the 32-bit values of a `WM_HINTS` property.
+	#[must_use]
+	#[allow(clippy::cast_sign_loss)]
+	pub fn to_property_data(&self) -> Vec<u32> {
+		let (icon_x, icon_y) = self
+			.icon_position
+			.map_or((0, 0), |HintCoords { x: Px(x), y: Px(y) }| (x, y));
+
+		vec![
+			self.flags().bits(),
+			u32::from(self.input.unwrap_or(false)),
+			self.initial_state.map_or(0, u32::from),
+			self.icon_pixmap.map_or(0, Pixmap::unwrap),
+			self.icon_window.map_or(0, Window::unwrap),
+			icon_x as u32,
+			icon_y as u32,
+			self.icon_mask.map_or(0, Pixmap::unwrap),
+			self.window_group.map_or(0, Window::unwrap),
+		]
+	}
+}
+
+bitflags! {
+	/// A mask of which fields of [`WmSizeHints`] are present.
+	#[derive(Default)]
+	pub struct WmSizeHintsFlags: u32 {
+		/// The user specified the [window]'s position.
+		///
+		/// [window]: Window
+		const USER_POSITION = 0x0000_0001;
+		/// The user specified the [window]'s size.
+		///
+		/// [window]: Window
+		const USER_SIZE = 0x0000_0002;
+		/// The program specified the [window]'s position.
+		///
+		/// [window]: Window
+		const PROGRAM_POSITION = 0x0000_0004;
+		/// The program specified the [window]'s size.
+		///
+		/// [window]: Window
+		const PROGRAM_SIZE = 0x0000_0008;
+		/// [`WmSizeHints::min_size`] is present.
+		const MIN_SIZE = 0x0000_0010;
+		/// [`WmSizeHints::max_size`] is present.
+		const MAX_SIZE = 0x0000_0020;
+		/// [`WmSizeHints::resize_increments`] is present.
+		const RESIZE_INCREMENTS = 0x0000_0040;
+		/// [`WmSizeHints::aspect_ratios`] is present.
+		const ASPECT_RATIOS = 0x0000_0080;
+		/// [`WmSizeHints::base_size`] is present.
+		const BASE_SIZE = 0x0000_0100;
+		/// [`WmSizeHints::window_gravity`] is present.
+		const WINDOW_GRAVITY = 0x0000_0200;
+	}
+}
+
+/// Dimensions, measured in pixels, as used in ICCCM properties.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HintDimensions {
+	/// The width.
+	pub width: Px<i32>,
+	/// The height.
+	pub height: Px<i32>,
+}
+
+/// An aspect ratio expressed as a fraction: `numerator / denominator`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AspectRatio {
+	/// The numerator (the width).
+	pub numerator: i32,
+	/// The denominator (the height).
+	pub denominator: i32,
+}
+
+/// The `WM_NORMAL_HINTS` property, of type `WM_SIZE_HINTS`: hints about the
+/// size and position of a top-level [window] for the window manager.
+///
+/// [window]: Window
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(clippy::struct_excessive_bools)]
+pub struct WmSizeHints {
+	/// Whether the user specified the [window]'s position.
+	///
+	/// [window]: Window
+	pub user_position: bool,
+	/// Whether the user specified the [window]'s size.
+	///
+	/// [window]: Window
+	pub user_size: bool,
+	/// Whether the program specified the [window]'s position.
+	///
+	/// [window]: Window
+	pub program_position: bool,
+	/// Whether the program specified the [window]'s size.
+	///
+	/// [window]: Window
+	pub program_size: bool,
+
+	/// The minimum size of the [window].
+	///
+	/// [window]: Window
+	pub min_size: Option<HintDimensions>,
+	/// The maximum size of the [window].
+	///
+	/// [window]: Window
+	pub max_size: Option<HintDimensions>,
+	/// The increments by which the [window] should be resized.
+	///
+	/// [window]: Window
+	pub resize_increments: Option<HintDimensions>,
+	/// The minimum and maximum aspect ratios of the [window], in that order.
+	///
+	/// [window]: Window
+	pub aspect_ratios: Option<(AspectRatio, AspectRatio)>,
+	/// The base size of the [window], from which `resize_increments` are
+	/// measured.
+	///
+	/// [window]: Window
+	pub base_size: Option<HintDimensions>,
+	/// The [window gravity] to be used when the window manager repositions the
+	/// [window].
+	///
+	/// [window]: Window
+	/// [window gravity]: WindowGravity
+	pub window_gravity: Option<WindowGravity>,
+}
+
+impl WmSizeHints {
+	/// The number of 32-bit values in a `WM_SIZE_HINTS` property.
+	pub const LEN: usize = 18;
+
+	/// Returns the [`WmSizeHintsFlags`] indicating which fields are present.
+	#[must_use]
+	pub fn flags(&self) -> WmSizeHintsFlags {
+		let mut flags = WmSizeHintsFlags::empty();
+
+		flags.set(WmSizeHintsFlags::USER_POSITION, self.user_position);
+		flags.set(WmSizeHintsFlags::USER_SIZE, self.user_size);
+		flags.set(WmSizeHintsFlags::PROGRAM_POSITION, self.program_position);
+		flags.set(WmSizeHintsFlags::PROGRAM_SIZE, self.program_size);
+		flags.set(WmSizeHintsFlags::MIN_SIZE, self.min_size.is_some());
+		flags.set(WmSizeHintsFlags::MAX_SIZE, self.max_size.is_some());
+		flags.set(
+			WmSizeHintsFlags::RESIZE_INCREMENTS,
+			self.resize_increments.is_some(),
+		);
+		flags.set(
+			WmSizeHintsFlags::ASPECT_RATIOS,
+			self.aspect_ratios.is_some(),
+		);
+		flags.set(WmSizeHintsFlags::BASE_SIZE, self.base_size.is_some());
+		flags.set(
+			WmSizeHintsFlags::WINDOW_GRAVITY,
+			self.window_gravity.is_some(),
+		);
+
+		flags
+	}
+
+	/// Decodes `WmSizeHints` from the 32-bit values of a `WM_SIZE_HINTS`
+	/// property.
+	///
+	/// Missing trailing values are treated as zero: pre-ICCCM clients write
+	/// only the first 15 values, omitting the base size and window gravity.
+	///
+	/// # Errors
+	/// Returns [`ReadError::UnrecognizedDiscriminant`] if the window gravity is
+	/// present but not a valid [`WindowGravity`].
+	pub fn from_property_data(data: &[u32]) -> ReadResult<Self> {
+		let values = padded::<{ Self::LEN }>(data);
+		let flags = WmSizeHintsFlags::from_bits_truncate(values[0]);
+
+		// Values 1 through 4 are the obsolete `x`, `y`, `width`, and `height`.
+		#[allow(clippy::cast_possible_wrap)]
+		let dimensions = |index: usize| HintDimensions {
+			width: Px(values[index] as i32),
+			height: Px(values[index + 1] as i32),
+		};
+		#[allow(clippy::cast_possible_wrap)]
+		let aspect_ratio = |index: usize| AspectRatio {
+			numerator: values[index] as i32,
+			denominator: values[index + 1] as i32,
+		};
+
+		Ok(Self {
+			user_position: flags.contains(WmSizeHintsFlags::USER_POSITION),
+			user_size: flags.contains(WmSizeHintsFlags::USER_SIZE),
+			program_position: flags.contains(WmSizeHintsFlags::PROGRAM_POSITION),
+			program_size: flags.contains(WmSizeHintsFlags::PROGRAM_SIZE),
+
+			min_size: flags
+				.contains(WmSizeHintsFlags::MIN_SIZE)
+				.then(|| dimensions(5)),
+			max_size: flags
+				.contains(WmSizeHintsFlags::MAX_SIZE)
+				.then(|| dimensions(7)),
+			resize_increments: flags
+				.contains(WmSizeHintsFlags::RESIZE_INCREMENTS)
+				.then(|| dimensions(9)),
+			aspect_ratios: flags
+				.contains(WmSizeHintsFlags::ASPECT_RATIOS)
+				.then(|| (aspect_ratio(11), aspect_ratio(13))),
+			base_size: flags
+				.contains(WmSizeHintsFlags::BASE_SIZE)
+				.then(|| dimensions(15)),
+			window_gravity: if flags.contains(WmSizeHintsFlags::WINDOW_GRAVITY) {
+				Some(WindowGravity::try_from(values[17])?)
+			} else {
+				None
+			},
+		})
+	}
+
+	/// Encodes these `WmSizeHints` as the 32-bit values of a `WM_SIZE_HINTS`
+	/// property.
+	#[must_use]
+	pub fn to_property_data(&self) -> Vec<u32> {
+		let mut values = vec![0; Self::LEN];
+		values[0] = self.flags().bits();
+
+		#[allow(clippy::cast_sign_loss)]
+		let mut put_dimensions = |index: usize, dimensions: Option<HintDimensions>| {
+			if let Some(HintDimensions {
+				width: Px(width),
+				height: Px(height),
+			}) = dimensions
+			{
+				values[index] = width as u32;
+				values[index + 1] = height as u32;
+			}
+		};
+
+		put_dimensions(5, self.min_size);
+		put_dimensions(7, self.max_size);
+		put_dimensions(9, self.resize_increments);
+		put_dimensions(15, self.base_size);
+
+		#[allow(clippy::cast_sign_loss)]
+		if let Some((min, max)) = self.aspect_ratios {
+			values[11] = min.numerator as u32;
+			values[12] = min.denominator as u32;
+			values[13] = max.numerator as u32;
+			values[14] = max.denominator as u32;
+		}
+
+		if let Some(gravity) = self.window_gravity {
+			values[17] = u32::from(gravity);
+		}
+
+		values
+	}
+}
+
+/// The `WM_CLASS` property: the instance name and class name of a top-level
+/// [window].
+///
+/// These are used by window managers and resource databases to look up
+/// configuration for the application.
+///
+/// [window]: Window
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WmClass {
+	/// The instance name of the application (e.g. `xterm`).
+	pub instance: String,
+	/// The class name of the application (e.g. `XTerm`).
+	pub class: String,
+}
+
+impl WmClass {
+	/// Creates a new `WmClass` with the given `instance` and `class` names.
+	pub fn new(instance: impl Into<String>, class: impl Into<String>) -> Self {
+		Self {
+			instance: instance.into(),
+			class: class.into(),
+		}
+	}
+
+	/// Decodes a `WmClass` from the 8-bit data of a `WM_CLASS` property.
+	///
+	/// The data consists of the instance name and the class name, each
+	/// followed by a null byte. The strings are decoded as Latin-1. A missing
+	/// final null byte or a missing class name is tolerated.
+	#[must_use]
+	pub fn from_property_data(data: &[u8]) -> Self {
+		let mut strings = data.split(|byte| *byte == 0);
+
+		Self {
+			instance: strings.next().map(latin1).unwrap_or_default(),
+			class: strings.next().map(latin1).unwrap_or_default(),
+		}
+	}
+
+	/// Encodes this `WmClass` as the 8-bit data of a `WM_CLASS` property.
+	///
+	/// # Errors
+	/// Returns [`NonLatin1Encoding`] if either name contains characters which
+	/// cannot be encoded in Latin-1.
+	pub fn to_property_data(&self) -> Result<Vec<u8>, NonLatin1Encoding> {
+		let mut data = Vec::with_capacity(self.instance.len() + self.class.len() + 2);
+
+		for string in [&self.instance, &self.class] {
+			for char in string.chars() {
+				data.push(u8::try_from(char).map_err(|_| NonLatin1Encoding)?);
+			}
+
+			data.push(0);
+		}
+
+		Ok(data)
+	}
+}
+
+/// The `WM_STATE` property: the state of a top-level [window] as recorded by
+/// the window manager.
+///
+/// Unlike the other ICCCM properties, the `WM_STATE` type is not a predefined
+/// [atom], so it must be interned before the property can be read or written.
+///
+/// [window]: Window
+/// [atom]: crate::Atom
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WmState {
+	/// The state of the [window].
+	///
+	/// [window]: Window
+	pub state: WindowState,
+	/// The [window] used as the icon for this [window], if any.
+	///
+	/// [window]: Window
+	pub icon: Option<Window>,
+}
+
+impl WmState {
+	/// The number of 32-bit values in a `WM_STATE` property.
+	pub const LEN: usize = 2;
+
+	/// Decodes a `WmState` from the 32-bit values of a `WM_STATE` property.
+	///
+	/// # Errors
+	/// Returns [`ReadError::UnrecognizedDiscriminant`] if the state is not a
+	/// valid [`WindowState`].
+	pub fn from_property_data(data: &[u32]) -> ReadResult<Self> {
+		let [state, icon] = padded::<{ Self::LEN }>(data);
+
+		Ok(Self {
+			state: WindowState::try_from(state)?,
+			icon: (icon != 0).then(|| Window::new(icon)),
+		})
+	}
+
+	/// Encodes this `WmState` as the 32-bit values of a `WM_STATE` property.
+	#[must_use]
+	pub fn to_property_data(&self) -> Vec<u32> {
+		vec![u32::from(self.state), self.icon.map_or(0, Window::unwrap)]
+	}
+}
+
+macro_rules! impl_u32_property { // {{{
+	($($Type:ty),+$(,)?) => {
+		$(
+			impl ConstantX11Size for $Type {
+				const X11_SIZE: usize = Self::LEN * 4;
+			}
+
+			impl X11Size for $Type {
+				fn x11_size(&self) -> usize {
+					Self::X11_SIZE
+				}
+			}
+
+			impl Readable for $Type {
+				fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
+					let mut values = [0; Self::LEN];
+
+					for value in &mut values {
+						*value = u32::read_from(buf)?;
+					}
+
+					Self::from_property_data(&values)
+				}
+			}
+
+			impl Writable for $Type {
+				fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+					self.to_property_data().write_to(buf)
+				}
+			}
+
+			impl TryFrom<&DataList> for $Type {
+				type Error = ReadError;
+
+				fn try_from(data: &DataList) -> ReadResult<Self> {
+					Self::from_property_data(&u32_values(data)?)
+				}
+			}
+
+			impl From<&$Type> for DataList {
+				fn from(property: &$Type) -> Self {
+					i32_data_list(&property.to_property_data())
+				}
+			}
+		)+
+	};
+} // }}}
+
+impl_u32_property!(WmHints, WmSizeHints, WmState);
+
+impl TryFrom<&DataList> for WmClass {
+	type Error = ReadError;
+
+	fn try_from(data: &DataList) -> ReadResult<Self> {
+		match data {
+			#[allow(clippy::cast_sign_loss)]
+			DataList::I8(values) => Ok(Self::from_property_data(
+				&values.iter().map(|value| *value as u8).collect::<Vec<_>>(),
+			)),
+
+			other => Err(ReadError::Other(Box::new(WrongDataFormat {
+				expected: DataFormat::I8,
+				found: other.format(),
+			}))),
+		}
+	}
+}
+
+impl TryFrom<&WmClass> for DataList {
+	type Error = NonLatin1Encoding;
+
+	fn try_from(class: &WmClass) -> Result<Self, NonLatin1Encoding> {
+		#[allow(clippy::cast_possible_wrap)]
+		Ok(Self::I8(
+			class
+				.to_property_data()?
+				.into_iter()
+				.map(|byte| byte as i8)
+				.collect(),
+		))
+	}
+}
+
+/// The encoding of a [`TextProperty`], given by the `type` of the property.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextEncoding {
+	/// The `STRING` type: text encoded as Latin-1.
+	Latin1,
+	/// The `UTF8_STRING` type: text encoded as UTF-8.
+	///
+	/// Unlike `STRING`, `UTF8_STRING` is not a predefined [atom], so it must be
+	/// interned before the property can be read or written.
+	///
+	/// [atom]: crate::Atom
+	Utf8,
+}
+
+/// An error encountered when encoding or decoding a [`TextProperty`].
+#[derive(Error, Debug)]
+pub enum TextPropertyError {
+	/// A string of a [`TextEncoding::Utf8`] property was not valid UTF-8.
+	#[error("string {index} of the text property is not valid UTF-8: {error}")]
+	InvalidUtf8 {
+		/// The index of the string within the property.
+		index: usize,
+		/// The error encountered when decoding the string.
+		error: Utf8Error,
+	},
+
+	/// A string contained a null byte, which would split it into two strings.
+	#[error("string {index} of the text property contains a null byte")]
+	EmbeddedNul {
+		/// The index of the string within the property.
+		index: usize,
+	},
+
+	/// A string of a [`TextEncoding::Latin1`] property contained characters
+	/// which cannot be encoded in Latin-1.
+	#[error(transparent)]
+	NonLatin1Encoding(#[from] NonLatin1Encoding),
+}
+
+/// A property containing a list of strings, such as `WM_NAME` or
+/// `_NET_WM_NAME`.
+///
+/// Each string in the property data is separated from the next by a null byte.
+/// A single trailing null byte ends the last string rather than beginning an
+/// empty one, so it is written only if the last string is empty: `a\0b` and
+/// `a\0b\0` are both decoded as `["a", "b"]`, while `["a", ""]` is encoded as
+/// `a\0\0`. A property with a single string - as is usual for names - is
+/// therefore written without any null bytes.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextProperty {
+	/// The encoding of the `strings`.
+	pub encoding: TextEncoding,
+	/// The strings contained in the property.
+	pub strings: Vec<String>,
+}
+
+impl TextProperty {
+	/// Creates a new `TextProperty` with the given `encoding` and `strings`.
+	pub fn new(
+		encoding: TextEncoding, strings: impl IntoIterator<Item = impl Into<String>>,
+	) -> Self {
+		Self {
+			encoding,
+			strings: strings.into_iter().map(Into::into).collect(),
+		}
+	}
+
+	/// Splits the given property `data` into the bytes of each string.
+	fn split(data: &[u8]) -> impl Iterator<Item = &[u8]> {
+		let strings = data.strip_suffix(&[0]).unwrap_or(data);
+
+		// Empty data contains no strings, rather than one empty string.
+		strings
+			.split(|byte| *byte == 0)
+			.take(if data.is_empty() { 0 } else { usize::MAX })
+	}
+
+	/// Decodes a `TextProperty` from the 8-bit data of a property with the
+	/// given `encoding`.
+	///
+	/// # Errors
+	/// Returns [`TextPropertyError::InvalidUtf8`] if the `encoding` is
+	/// [`TextEncoding::Utf8`] and a string is not valid UTF-8. See
+	/// [`from_property_data_lossy`] to replace invalid UTF-8 instead.
+	///
+	/// [`from_property_data_lossy`]: TextProperty::from_property_data_lossy
+	pub fn from_property_data(
+		encoding: TextEncoding, data: &[u8],
+	) -> Result<Self, TextPropertyError> {
+		let strings = Self::split(data)
+			.enumerate()
+			.map(|(index, bytes)| match encoding {
+				TextEncoding::Latin1 => Ok(latin1(bytes)),
+
+				TextEncoding::Utf8 => str::from_utf8(bytes)
+					.map(ToOwned::to_owned)
+					.map_err(|error| TextPropertyError::InvalidUtf8 { index, error }),
+			})
+			.collect::<Result<_, _>>()?;
+
+		Ok(Self { encoding, strings })
+	}
+
+	/// Decodes a `TextProperty` from the 8-bit data of a property with the
+	/// given `encoding`, replacing any invalid UTF-8 with
+	/// [`U+FFFD REPLACEMENT CHARACTER`].
+	///
+	/// [`U+FFFD REPLACEMENT CHARACTER`]: char::REPLACEMENT_CHARACTER
+	#[must_use]
+	pub fn from_property_data_lossy(encoding: TextEncoding, data: &[u8]) -> Self {
+		let strings = Self::split(data)
+			.map(|bytes| match encoding {
+				TextEncoding::Latin1 => latin1(bytes),
+				TextEncoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+			})
+			.collect();
+
+		Self { encoding, strings }
+	}
+
+	/// Encodes this `TextProperty` as the 8-bit data of a property.
+	///
+	/// # Errors
+	/// Returns [`TextPropertyError::EmbeddedNul`] if a string contains a null
+	/// byte, and [`TextPropertyError::NonLatin1Encoding`] if the `encoding` is
+	/// [`TextEncoding::Latin1`] and a string contains characters which cannot
+	/// be encoded in Latin-1.
+	pub fn to_property_data(&self) -> Result<Vec<u8>, TextPropertyError> {
+		let mut data = Vec::with_capacity(self.strings.iter().map(|string| string.len() + 1).sum());
+
+		for (index, string) in self.strings.iter().enumerate() {
+			if index != 0 {
+				data.push(0);
+			}
+
+			if string.contains('\0') {
+				return Err(TextPropertyError::EmbeddedNul { index });
+			}
+
+			match self.encoding {
+				TextEncoding::Latin1 => {
+					for char in string.chars() {
+						data.push(u8::try_from(char).map_err(|_| NonLatin1Encoding)?);
+					}
+				},
+
+				TextEncoding::Utf8 => data.extend_from_slice(string.as_bytes()),
+			}
+		}
+
+		// Write a trailing null byte to preserve an empty last string.
+		if self.strings.last().is_some_and(String::is_empty) {
+			data.push(0);
+		}
+
+		Ok(data)
+	}
+
+	/// Decodes a `TextProperty` from the given [`DataList`] of a property with
+	/// the given `encoding`.
+	///
+	/// # Errors
+	/// Returns a [`ReadError`] if the `data` is not a [`DataList::I8`], or if
+	/// it could not be decoded with [`from_property_data`].
+	///
+	/// [`from_property_data`]: TextProperty::from_property_data
+	pub fn from_data_list(encoding: TextEncoding, data: &DataList) -> ReadResult<Self> {
+		match data {
+			#[allow(clippy::cast_sign_loss)]
+			DataList::I8(values) => Self::from_property_data(
+				encoding,
+				&values.iter().map(|value| *value as u8).collect::<Vec<_>>(),
+			)
+			.map_err(|error| ReadError::Other(Box::new(error))),
+
+			other => Err(ReadError::Other(Box::new(WrongDataFormat {
+				expected: DataFormat::I8,
+				found: other.format(),
+			}))),
+		}
+	}
+}
+
+impl TryFrom<&TextProperty> for DataList {
+	type Error = TextPropertyError;
+
+	fn try_from(property: &TextProperty) -> Result<Self, TextPropertyError> {
+		#[allow(clippy::cast_possible_wrap)]
+		Ok(Self::I8(
+			property
+				.to_property_data()?
+				.into_iter()
+				.map(|byte| byte as i8)
+				.collect(),
+		))
+	}
+}
+
+/// Decodes the given `bytes` as Latin-1.
+fn latin1(bytes: &[u8]) -> String {
+	bytes.iter().map(|byte| char::from(*byte)).collect()
+}
+
+/// The data of a selection converted to a particular target type.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SelectionData {
+	/// The type of the converted data.
+	///
+	/// This need not be the target type: for example, the `TARGETS` target
+	/// is converted to data of type [`atom::ATOM`].
+	pub r#type: Atom,
+	/// The converted data.
+	pub data: DataList,
+}
+
+/// An operation to perform in response to a [`ConvertSelectionRequest`
+/// event].
+///
+/// [`ConvertSelectionRequest` event]: ConvertSelectionRequest
+#[derive(Eq, PartialEq, Hash, Debug)]
+pub enum SelectionOperation {
+	/// Stores converted data in a property of the `requester`.
+	ModifyProperty(ModifyProperty),
+	/// Notifies the `requester` that the conversion is complete, or that it
+	/// was refused if the `property` is [`None`].
+	Notify(SendEvent<Selection>),
+}
+
+/// The response to a [`ConvertSelectionRequest` event], as returned by
+/// [`SelectionOwnerHandler::respond`].
+///
+/// [`ConvertSelectionRequest` event]: ConvertSelectionRequest
+#[derive(Eq, PartialEq, Hash, Debug)]
+pub enum SelectionResponse {
+	/// The operations to perform, in order.
+	Operations(Vec<SelectionOperation>),
+
+	/// The `MULTIPLE` target was requested.
+	///
+	/// The pairs of targets and properties to convert are stored in a
+	/// property of the `requester`: this [`GetProperty` request] fetches
+	/// them. Its reply should be given to
+	/// [`SelectionOwnerHandler::respond_multiple`].
+	///
+	/// [`GetProperty` request]: GetProperty
+	FetchMultiple(GetProperty),
+}
+
+/// Responds to [`ConvertSelectionRequest` events] for a selection owned by
+/// this client, following the rules given in the ICCCM.
+///
+/// Requests for a different `selection` or `owner`, or made before the
+/// selection was `acquired`, are refused. The `TIMESTAMP` and `MULTIPLE`
+/// targets are handled here; every other target - including `TARGETS`,
+/// which every owner should support - is converted by the caller. Requests
+/// from obsolete clients which give no `property` are answered using the
+/// target type as the property.
+///
+/// Data too large to be sent in a single [`ModifyProperty` request] must be
+/// sent with the `INCR` mechanism, which is not handled here.
+///
+/// Unlike most ICCCM atoms, `MULTIPLE`, `TIMESTAMP`, and `ATOM_PAIR` are not
+/// predefined [atoms], so they must be interned before a
+/// `SelectionOwnerHandler` can be created.
+///
+/// [`ConvertSelectionRequest` events]: ConvertSelectionRequest
+/// [atoms]: atom
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SelectionOwnerHandler {
+	/// The selection which is owned.
+	pub selection: Atom,
+	/// The [window] which owns the `selection`.
+	///
+	/// [window]: Window
+	pub owner: Window,
+	/// The time given in the [`SetSelectionOwner` request] which acquired the
+	/// `selection`.
+	///
+	/// This must not be [`CurrentTime`].
+	///
+	/// [`SetSelectionOwner` request]: crate::x11::request::SetSelectionOwner
+	/// [`CurrentTime`]: CurrentableTime::CurrentTime
+	pub acquired: Timestamp,
+
+	/// The `MULTIPLE` [atom].
+	///
+	/// [atom]: Atom
+	pub multiple: Atom,
+	/// The `TIMESTAMP` [atom].
+	///
+	/// [atom]: Atom
+	pub timestamp: Atom,
+	/// The `ATOM_PAIR` [atom].
+	///
+	/// [atom]: Atom
+	pub atom_pair: Atom,
+}
+
+impl SelectionOwnerHandler {
+	/// Creates a new `SelectionOwnerHandler` for the `selection` acquired by
+	/// the `owner` at the given time.
+	#[must_use]
+	pub const fn new(
+		selection: Atom, owner: Window, acquired: Timestamp, multiple: Atom, timestamp: Atom,
+		atom_pair: Atom,
+	) -> Self {
+		Self {
+			selection,
+			owner,
+			acquired,
+
+			multiple,
+			timestamp,
+			atom_pair,
+		}
+	}
+
+	/// Whether the `event` requests this `selection` while it was owned.
+	fn accepts(&self, event: &ConvertSelectionRequest) -> bool {
+		let owned = match event.time {
+			CurrentableTime::CurrentTime => true,
+			CurrentableTime::Other(time) => !self.acquired.is_after(time),
+		};
+
+		owned && event.selection == self.selection && event.owner == self.owner
+	}
+
+	/// Returns the [`Selection` event] notifying the `requester` of the
+	/// `event` that the conversion is complete, or that it was refused if the
+	/// `property` is [`None`].
+	///
+	/// [`Selection` event]: Selection
+	const fn notify(event: &ConvertSelectionRequest, property: Option<Atom>) -> SelectionOperation {
+		SelectionOperation::Notify(SendEvent {
+			propagate: false,
+			destination: DestinationWindow::Other(event.requester),
+			event_mask: EventMask::empty(),
+
+			event: Selection {
+				sequence: 0,
+
+				time: event.time,
+				requester: event.requester,
+
+				selection: event.selection,
+				target_type: event.target_type,
+				property,
+			},
+		})
+	}
+
+	/// Converts the `target` into the `requester`'s `property`, returning
+	/// [`None`] if the conversion is refused.
+	fn convert(
+		&self, target: Atom, property: Atom, requester: Window,
+		convert: &mut impl FnMut(Atom) -> Option<SelectionData>,
+	) -> Option<ModifyProperty> {
+		let SelectionData { r#type, data } = if target == self.timestamp {
+			SelectionData {
+				r#type: atom::INTEGER,
+				data: i32_data_list(&[self.acquired.unwrap()]),
+			}
+		} else if target == self.multiple {
+			// `MULTIPLE` may not be nested within `MULTIPLE`.
+			return None;
+		} else {
+			convert(target)?
+		};
+
+		Some(ModifyProperty {
+			modify_mode: ModifyPropertyMode::Replace,
+			target: requester,
+			property,
+			r#type,
+			data,
+		})
+	}
+
+	/// Responds to the given [`ConvertSelectionRequest` event].
+	///
+	/// `convert` is called with the target type to convert the selection to,
+	/// returning [`None`] if the selection cannot be converted to that type.
+	///
+	/// [`ConvertSelectionRequest` event]: ConvertSelectionRequest
+	pub fn respond(
+		&self, event: &ConvertSelectionRequest,
+		mut convert: impl FnMut(Atom) -> Option<SelectionData>,
+	) -> SelectionResponse {
+		if !self.accepts(event) {
+			return SelectionResponse::Operations(vec![Self::notify(event, None)]);
+		}
+
+		if event.target_type == self.multiple {
+			// `MULTIPLE` cannot be requested without a property listing the
+			// targets.
+			let Some(property) = event.property else {
+				return SelectionResponse::Operations(vec![Self::notify(event, None)]);
+			};
+
+			return SelectionResponse::FetchMultiple(GetProperty {
+				delete: false,
+				target: event.requester,
+				property,
+				r#type: Any::Other(self.atom_pair),
+				offset: 0,
+				length: u32::MAX,
+			});
+		}
+
+		// Obsolete clients give no property: the target type is used instead.
+		let property = event.property.unwrap_or(event.target_type);
+
+		let Some(modify) = self.convert(event.target_type, property, event.requester, &mut convert)
+		else {
+			return SelectionResponse::Operations(vec![Self::notify(event, None)]);
+		};
+
+		SelectionResponse::Operations(vec![
+			SelectionOperation::ModifyProperty(modify),
+			Self::notify(event, Some(property)),
+		])
+	}
+
+	/// Responds to the given [`ConvertSelectionRequest` event] for the
+	/// `MULTIPLE` target, given the [`GetProperty` reply] to the
+	/// [`FetchMultiple`] request.
+	///
+	/// Each pair of a target and a property is converted as in [`respond`].
+	/// If any pair cannot be converted, its property is replaced with
+	/// [`None`] in the `MULTIPLE` property.
+	///
+	/// [`ConvertSelectionRequest` event]: ConvertSelectionRequest
+	/// [`GetProperty` reply]: reply::GetProperty
+	/// [`FetchMultiple`]: SelectionResponse::FetchMultiple
+	/// [`respond`]: SelectionOwnerHandler::respond
+	pub fn respond_multiple(
+		&self, event: &ConvertSelectionRequest, reply: &reply::GetProperty,
+		mut convert: impl FnMut(Atom) -> Option<SelectionData>,
+	) -> Vec<SelectionOperation> {
+		let refuse = || vec![Self::notify(event, None)];
+
+		if !self.accepts(event) || reply.r#type != Some(self.atom_pair) {
+			return refuse();
+		}
+
+		let (Some(property), Ok(mut pairs)) = (event.property, u32_values(&reply.value)) else {
+			return refuse();
+		};
+
+		let mut operations = vec![];
+		let mut refused = false;
+
+		for pair in pairs.chunks_exact_mut(2) {
+			let target = Atom::new(pair[0]);
+			let modify = (pair[1] != 0)
+				.then(|| Atom::new(pair[1]))
+				.and_then(|property| self.convert(target, property, event.requester, &mut convert));
+
+			if let Some(modify) = modify {
+				operations.push(SelectionOperation::ModifyProperty(modify));
+			} else {
+				pair[1] = 0;
+				refused = true;
+			}
+		}
+
+		if refused {
+			operations.push(SelectionOperation::ModifyProperty(ModifyProperty {
+				modify_mode: ModifyPropertyMode::Replace,
+				target: event.requester,
+				property,
+				r#type: self.atom_pair,
+				data: i32_data_list(&pairs),
+			}));
+		}
+
+		operations.push(Self::notify(event, Some(property)));
+
+		operations
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_wm_hints_round_trip() {
+		let hints = WmHints {
+			input: Some(true),
+			initial_state: Some(WindowState::Iconic),
+			icon_pixmap: Some(Pixmap::new(0x0040_0001)),
+			icon_window: None,
+			icon_position: Some(HintCoords {
+				x: Px(-20),
+				y: Px(40),
+			}),
+			icon_mask: None,
+			window_group: Some(Window::new(0x0040_0002)),
+			urgent: true,
+		};
+
+		let data = hints.to_property_data();
+		assert_eq!(data.len(), WmHints::LEN);
+		assert_eq!(data[0], 0x0000_0157);
+		assert_eq!(WmHints::from_property_data(&data).unwrap(), hints);
+
+		let list = DataList::from(&hints);
+		assert_eq!(WmHints::try_from(&list).unwrap(), hints);
+
+		let mut bytes = vec![];
+		hints.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes.len(), WmHints::X11_SIZE);
+		assert_eq!(WmHints::read_from(&mut &bytes[..]).unwrap(), hints);
+	}
+
+	#[test]
+	fn test_wm_size_hints_round_trip() {
+		let hints = WmSizeHints {
+			user_size: true,
+			program_position: true,
+			min_size: Some(HintDimensions {
+				width: Px(100),
+				height: Px(50),
+			}),
+			max_size: Some(HintDimensions {
+				width: Px(i32::MAX),
+				height: Px(i32::MAX),
+			}),
+			aspect_ratios: Some((
+				AspectRatio {
+					numerator: 4,
+					denominator: 3,
+				},
+				AspectRatio {
+					numerator: 16,
+					denominator: 9,
+				},
+			)),
+			window_gravity: Some(WindowGravity::Static),
+			..WmSizeHints::default()
+		};
+
+		let data = hints.to_property_data();
+		assert_eq!(data.len(), WmSizeHints::LEN);
+		assert_eq!(data[17], 10);
+		assert_eq!(WmSizeHints::from_property_data(&data).unwrap(), hints);
+
+		let list = DataList::from(&hints);
+		assert_eq!(WmSizeHints::try_from(&list).unwrap(), hints);
+	}
+
+	#[test]
+	fn test_xterm_wm_normal_hints() {
+		// `WM_NORMAL_HINTS` as set by xterm, reported by `xprop` as:
+		// ```
+		// program specified minimum size: 10 by 17
+		// program specified resize increment: 6 by 13
+		// program specified base size: 4 by 4
+		// window gravity: NorthWest
+		// ```
+		let data = [
+			0x0000_0350,
+			0,
+			0,
+			0,
+			0,
+			10,
+			17,
+			0,
+			0,
+			6,
+			13,
+			0,
+			0,
+			0,
+			0,
+			4,
+			4,
+			1,
+		];
+
+		let hints = WmSizeHints::from_property_data(&data).unwrap();
+
+		assert_eq!(
+			hints,
+			WmSizeHints {
+				min_size: Some(HintDimensions {
+					width: Px(10),
+					height: Px(17),
+				}),
+				resize_increments: Some(HintDimensions {
+					width: Px(6),
+					height: Px(13),
+				}),
+				base_size: Some(HintDimensions {
+					width: Px(4),
+					height: Px(4),
+				}),
+				window_gravity: Some(WindowGravity::NorthWest),
+				..WmSizeHints::default()
+			}
+		);
+		assert_eq!(hints.to_property_data(), data);
+
+		// Pre-ICCCM clients omit the base size and window gravity.
+		let old = WmSizeHints::from_property_data(&data[..15]).unwrap();
+		assert_eq!(old.min_size, hints.min_size);
+	}
+
+	#[test]
+	fn test_wm_class_and_state() {
+		let class = WmClass::from_property_data(b"xterm\0XTerm\0");
+		assert_eq!(class, WmClass::new("xterm", "XTerm"));
+		assert_eq!(class.to_property_data().unwrap(), b"xterm\0XTerm\0");
+
+		let list = DataList::try_from(&class).unwrap();
+		assert_eq!(WmClass::try_from(&list).unwrap(), class);
+		assert!(WmClass::new("λ", "Lambda").to_property_data().is_err());
+
+		let state = WmState {
+			state: WindowState::Normal,
+			icon: None,
+		};
+		assert_eq!(state.to_property_data(), [1, 0]);
+		assert_eq!(WmState::from_property_data(&[1, 0]).unwrap(), state);
+		assert!(WmState::from_property_data(&[2, 0]).is_err());
+		assert!(WmState::try_from(&DataList::I8(vec![1])).is_err());
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn test_wm_hints_json_round_trip() {
+		let hints = WmHints {
+			input: Some(true),
+			initial_state: Some(WindowState::Normal),
+			window_group: Some(Window::new(0x0040_0002)),
+			..WmHints::default()
+		};
+
+		let json = serde_json::to_string(&hints).unwrap();
+		assert!(json.contains(r#""window_group":4194306"#));
+
+		assert_eq!(serde_json::from_str::<WmHints>(&json).unwrap(), hints);
+	}
+
+	#[test]
+	fn test_text_property_decoding() {
+		let utf8 = |data: &[u8]| {
+			TextProperty::from_property_data(TextEncoding::Utf8, data)
+				.unwrap()
+				.strings
+		};
+
+		assert!(utf8(b"").is_empty());
+		assert_eq!(utf8(b"\0"), [""]);
+		assert_eq!(utf8(b"xterm"), ["xterm"]);
+		assert_eq!(utf8(b"xterm\0"), ["xterm"]);
+		assert_eq!(utf8(b"xterm\0XTerm"), ["xterm", "XTerm"]);
+		assert_eq!(utf8(b"xterm\0XTerm\0"), ["xterm", "XTerm"]);
+		assert_eq!(utf8(b"a\0\0b"), ["a", "", "b"]);
+		assert_eq!(utf8(b"a\0\0"), ["a", ""]);
+		assert_eq!(utf8("λ-term\0".as_bytes()), ["λ-term"]);
+
+		let latin1 = TextProperty::from_property_data(TextEncoding::Latin1, b"caf\xe9").unwrap();
+		assert_eq!(latin1.strings, ["café"]);
+	}
+
+	#[test]
+	fn test_text_property_invalid_utf8() {
+		let data = b"valid\0in\xffvalid";
+
+		assert!(matches!(
+			TextProperty::from_property_data(TextEncoding::Utf8, data),
+			Err(TextPropertyError::InvalidUtf8 { index: 1, .. }),
+		));
+		assert_eq!(
+			TextProperty::from_property_data_lossy(TextEncoding::Utf8, data).strings,
+			["valid", "in\u{fffd}valid"],
+		);
+
+		// Latin-1 can decode any bytes.
+		assert_eq!(
+			TextProperty::from_property_data(TextEncoding::Latin1, data)
+				.unwrap()
+				.strings,
+			["valid", "in\u{ff}valid"],
+		);
+	}
+
+	#[test]
+	fn test_text_property_encoding() {
+		let encode = |strings: &[&str]| {
+			TextProperty::new(TextEncoding::Utf8, strings.iter().copied())
+				.to_property_data()
+				.unwrap()
+		};
+
+		assert_eq!(encode(&[]), b"");
+		assert_eq!(encode(&[""]), b"\0");
+		assert_eq!(encode(&["Terminal"]), b"Terminal");
+		assert_eq!(encode(&["a", "b"]), b"a\0b");
+		assert_eq!(encode(&["a", ""]), b"a\0\0");
+		assert_eq!(encode(&["", "b"]), b"\0b");
+
+		for strings in [
+			&[][..],
+			&[""],
+			&["Terminal"],
+			&["a", "b"],
+			&["a", ""],
+			&["", ""],
+			&["", "b", ""],
+		] {
+			let property = TextProperty::new(TextEncoding::Utf8, strings.iter().copied());
+			let data = property.to_property_data().unwrap();
+
+			assert_eq!(
+				TextProperty::from_property_data(TextEncoding::Utf8, &data).unwrap(),
+				property,
+				"{strings:?}",
+			);
+		}
+
+		assert!(matches!(
+			TextProperty::new(TextEncoding::Utf8, ["ok", "nul\0byte"]).to_property_data(),
+			Err(TextPropertyError::EmbeddedNul { index: 1 }),
+		));
+		assert!(matches!(
+			TextProperty::new(TextEncoding::Latin1, ["λ"]).to_property_data(),
+			Err(TextPropertyError::NonLatin1Encoding(_)),
+		));
+		assert_eq!(
+			TextProperty::new(TextEncoding::Latin1, ["café"])
+				.to_property_data()
+				.unwrap(),
+			b"caf\xe9",
+		);
+	}
+
+	#[test]
+	fn test_text_property_data_list() {
+		let property = TextProperty::new(TextEncoding::Utf8, ["xterm", "XTerm"]);
+		let list = DataList::try_from(&property).unwrap();
+
+		assert_eq!(
+			TextProperty::from_data_list(TextEncoding::Utf8, &list).unwrap(),
+			property,
+		);
+		assert!(TextProperty::from_data_list(TextEncoding::Utf8, &DataList::I32(vec![1])).is_err());
+	}
+
+	const CLIPBOARD: Atom = Atom::new(0x0100);
+	const MULTIPLE: Atom = Atom::new(0x0101);
+	const TIMESTAMP: Atom = Atom::new(0x0102);
+	const ATOM_PAIR: Atom = Atom::new(0x0103);
+
+	const OWNER: Window = Window::new(0x0040_0001);
+	const REQUESTER: Window = Window::new(0x0060_0001);
+
+	fn selection_owner() -> SelectionOwnerHandler {
+		SelectionOwnerHandler::new(
+			CLIPBOARD,
+			OWNER,
+			Timestamp::new(1_000),
+			MULTIPLE,
+			TIMESTAMP,
+			ATOM_PAIR,
+		)
+	}
+
+	fn convert_selection(
+		time: u32, target_type: Atom, property: Option<Atom>,
+	) -> ConvertSelectionRequest {
+		ConvertSelectionRequest {
+			sequence: 0,
+			time: CurrentableTime::Other(Timestamp::new(time)),
+			owner: OWNER,
+			requester: REQUESTER,
+			selection: CLIPBOARD,
+			target_type,
+			property,
+		}
+	}
+
+	/// Converts only the `STRING` target, to `"text"`.
+	fn convert_string(target: Atom) -> Option<SelectionData> {
+		(target == atom::STRING).then(|| SelectionData {
+			r#type: atom::STRING,
+			data: DataList::I8(b"text".iter().map(|byte| *byte as i8).collect()),
+		})
+	}
+
+	fn modify(property: Atom, r#type: Atom, data: DataList) -> SelectionOperation {
+		SelectionOperation::ModifyProperty(ModifyProperty {
+			modify_mode: ModifyPropertyMode::Replace,
+			target: REQUESTER,
+			property,
+			r#type,
+			data,
+		})
+	}
+
+	fn notify(event: &ConvertSelectionRequest, property: Option<Atom>) -> SelectionOperation {
+		SelectionOperation::Notify(SendEvent {
+			propagate: false,
+			destination: DestinationWindow::Other(REQUESTER),
+			event_mask: EventMask::empty(),
+			event: Selection {
+				sequence: 0,
+				time: event.time,
+				requester: REQUESTER,
+				selection: CLIPBOARD,
+				target_type: event.target_type,
+				property,
+			},
+		})
+	}
+
+	#[test]
+	fn test_selection_owner_timestamp() {
+		let property = Atom::new(0x0200);
+		let event = convert_selection(2_000, TIMESTAMP, Some(property));
+
+		assert_eq!(
+			selection_owner().respond(&event, |_| panic!("TIMESTAMP is converted by the handler")),
+			SelectionResponse::Operations(vec![
+				modify(property, atom::INTEGER, DataList::I32(vec![1_000])),
+				notify(&event, Some(property)),
+			]),
+		);
+	}
+
+	#[test]
+	fn test_selection_owner_string() {
+		let property = Atom::new(0x0200);
+		let event = convert_selection(2_000, atom::STRING, Some(property));
+
+		assert_eq!(
+			selection_owner().respond(&event, convert_string),
+			SelectionResponse::Operations(vec![
+				modify(
+					property,
+					atom::STRING,
+					convert_string(atom::STRING).unwrap().data
+				),
+				notify(&event, Some(property)),
+			]),
+		);
+
+		// A request from before the selection was acquired is refused.
+		let event = convert_selection(500, atom::STRING, Some(property));
+
+		assert_eq!(
+			selection_owner().respond(&event, convert_string),
+			SelectionResponse::Operations(vec![notify(&event, None)]),
+		);
+	}
+
+	#[test]
+	fn test_selection_owner_unsupported_target() {
+		let event = convert_selection(2_000, atom::PIXMAP, Some(Atom::new(0x0200)));
+
+		assert_eq!(
+			selection_owner().respond(&event, convert_string),
+			SelectionResponse::Operations(vec![notify(&event, None)]),
+		);
+	}
+
+	#[test]
+	fn test_selection_owner_obsolete_requester() {
+		let event = convert_selection(2_000, atom::STRING, None);
+
+		// The target type is used as the property.
+		assert_eq!(
+			selection_owner().respond(&event, convert_string),
+			SelectionResponse::Operations(vec![
+				modify(
+					atom::STRING,
+					atom::STRING,
+					convert_string(atom::STRING).unwrap().data
+				),
+				notify(&event, Some(atom::STRING)),
+			]),
+		);
+
+		// `MULTIPLE` requires a property.
+		let event = convert_selection(2_000, MULTIPLE, None);
+
+		assert_eq!(
+			selection_owner().respond(&event, convert_string),
+			SelectionResponse::Operations(vec![notify(&event, None)]),
+		);
+	}
+
+	#[test]
+	fn test_selection_owner_multiple() {
+		let property = Atom::new(0x0200);
+		let event = convert_selection(2_000, MULTIPLE, Some(property));
+
+		let SelectionResponse::FetchMultiple(request) =
+			selection_owner().respond(&event, convert_string)
+		else {
+			panic!("expected MULTIPLE's pairs to be fetched");
+		};
+		assert_eq!(request.target, REQUESTER);
+		assert_eq!(request.property, property);
+		assert_eq!(request.r#type, Any::Other(ATOM_PAIR));
+
+		let pairs = [
+			atom::STRING.unwrap(),
+			0x0201,
+			atom::PIXMAP.unwrap(),
+			0x0202,
+			TIMESTAMP.unwrap(),
+			0x0203,
+		];
+		let reply = reply::GetProperty {
+			sequence: 0,
+			format: Some(DataFormat::I32),
+			r#type: Some(ATOM_PAIR),
+			bytes_remaining: 0,
+			value: i32_data_list(&pairs),
+		};
+
+		assert_eq!(
+			selection_owner().respond_multiple(&event, &reply, convert_string),
+			[
+				modify(
+					Atom::new(0x0201),
+					atom::STRING,
+					convert_string(atom::STRING).unwrap().data,
+				),
+				modify(Atom::new(0x0203), atom::INTEGER, DataList::I32(vec![1_000]),),
+				// The `PIXMAP` target could not be converted.
+				modify(
+					property,
+					ATOM_PAIR,
+					i32_data_list(&[
+						atom::STRING.unwrap(),
+						0x0201,
+						atom::PIXMAP.unwrap(),
+						0,
+						TIMESTAMP.unwrap(),
+						0x0203,
+					]),
+				),
+				notify(&event, Some(property)),
+			],
+		);
+	}
+}
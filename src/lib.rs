@@ -82,8 +82,90 @@ pub const PROTOCOL_MAJOR_VERSION: u16 = 11;
 /// probably safe to assume it won't.
 pub const PROTOCOL_MINOR_VERSION: u16 = 0;
 
+/// Implements [`Display`] for a [`bitflags`] mask with the given flag names.
+///
+/// The names of the set flags are joined with `+`, in the order they are
+/// given; any bits which do not correspond to a named flag are appended in
+/// hexadecimal. An empty mask is displayed as `none`.
+///
+/// [`Display`]: std::fmt::Display
+macro_rules! impl_display_for_bitflags {
+	($Mask:ident { $($FLAG:ident => $name:literal),+$(,)? }) => {
+		impl ::std::fmt::Display for $Mask {
+			fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+				if self.is_empty() {
+					return f.write_str("none");
+				}
+
+				let mut separator = "";
+
+				$(
+					if self.contains(Self::$FLAG) {
+						write!(f, "{separator}{}", $name)?;
+						separator = "+";
+					}
+				)+
+
+				let unknown = self.bits() & !Self::all().bits();
+				if unknown != 0 {
+					write!(f, "{separator}{unknown:#x}")?;
+				}
+
+				Ok(())
+			}
+		}
+	};
+}
+
+/// Implements `Serialize` and `Deserialize` for [`bitflags`] masks.
+///
+/// Masks are serialized as their bits. Bits which do not correspond to any
+/// flag are rejected when deserializing.
+#[cfg(feature = "serde")]
+macro_rules! impl_serde_for_bitflags {
+	($($Mask:ident),+$(,)?) => {
+		$(
+			impl ::serde::Serialize for $Mask {
+				fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+				where
+					S: ::serde::Serializer,
+				{
+					::serde::Serialize::serialize(&self.bits(), serializer)
+				}
+			}
+
+			impl<'de> ::serde::Deserialize<'de> for $Mask {
+				fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+				where
+					D: ::serde::Deserializer<'de>,
+				{
+					let bits = ::serde::Deserialize::deserialize(deserializer)?;
+
+					Self::from_bits(bits).ok_or_else(|| {
+						::serde::de::Error::custom(format_args!(
+							"unrecognized bits for {}: {bits:#x}",
+							stringify!($Mask),
+						))
+					})
+				}
+			}
+		)+
+	};
+}
+
+pub mod big_requests;
 pub(crate) mod common;
+pub mod composite;
 pub mod connection;
+pub mod display;
+pub mod damage;
+pub mod ewmh;
+pub mod icccm;
 pub mod message;
+pub mod randr;
+pub mod shm;
 pub mod unit;
 pub mod x11;
+pub mod xauth;
+pub mod xc_misc;
+pub mod xfixes;
@@ -4,8 +4,197 @@
 
 //! Traits defining the format of messages sent via the X11 protocol.
 
-use crate::x11::error;
-use xrbk::{Readable, Writable, X11Size};
+use std::{collections::HashMap, fmt};
+use thiserror::Error;
+
+use crate::x11::{error, event::GenericEvent, reply};
+use xrbk::{
+	Buf,
+	BufMut,
+	ReadError,
+	ReadMode,
+	ReadResult,
+	Readable,
+	Writable,
+	WriteError,
+	WriteResult,
+	X11Size,
+};
+
+/// An error returned when a [request] is too large for its [length] to be
+/// encoded.
+///
+/// The [length] of a [request] is encoded in 2 bytes, so a [request] can be at
+/// most [`MAX_SIZE`] bytes long. Larger [requests] can only be sent once the
+/// [BIG-REQUESTS extension] is enabled, by writing them with
+/// [`write_request_to`].
+///
+/// [requests]: Request
+/// [BIG-REQUESTS extension]: crate::big_requests
+///
+/// [request]: Request
+/// [length]: Request::length
+/// [`MAX_SIZE`]: RequestTooLarge::MAX_SIZE
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Error)]
+#[error(
+	"the request is {size} bytes long, but a request can be at most {} bytes long",
+	Self::MAX_SIZE
+)]
+pub struct RequestTooLarge {
+	/// The size of the [request] in bytes.
+	///
+	/// [request]: Request
+	pub size: usize,
+}
+
+impl RequestTooLarge {
+	/// The largest size of a [request] in bytes.
+	///
+	/// [request]: Request
+	pub const MAX_SIZE: usize = (u16::MAX as usize) * 4;
+}
+
+impl From<RequestTooLarge> for WriteError {
+	fn from(error: RequestTooLarge) -> Self {
+		Self::Other(Box::new(error))
+	}
+}
+
+/// An error returned when a [request] is longer than the maximum request length
+/// accepted by the X server.
+///
+/// [request]: Request
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Error)]
+#[error(
+	"the request is {length} units long, but the X server accepts requests of at most \
+	 {max_request_length} units"
+)]
+pub struct ExceedsMaxRequestLength {
+	/// The length of the [request] in 4-byte units, including the extended
+	/// length field if there is one.
+	///
+	/// [request]: Request
+	pub length: usize,
+	/// The maximum length of a [request] accepted by the X server, in 4-byte
+	/// units.
+	///
+	/// [request]: Request
+	pub max_request_length: u32,
+}
+
+impl From<ExceedsMaxRequestLength> for WriteError {
+	fn from(error: ExceedsMaxRequestLength) -> Self {
+		Self::Other(Box::new(error))
+	}
+}
+
+/// Controls how [requests] are written by [`write_request_to`].
+///
+/// [requests]: Request
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct WriteControl {
+	/// Whether the [BIG-REQUESTS extension] has been enabled with an
+	/// [`EnableBigRequests` request].
+	///
+	/// [BIG-REQUESTS extension]: crate::big_requests
+	/// [`EnableBigRequests` request]: crate::big_requests::request::EnableBigRequests
+	pub big_requests_enabled: bool,
+
+	/// The maximum length of a [request] accepted by the X server, in 4-byte
+	/// units.
+	///
+	/// This is the maximum request length given when the connection is set up
+	/// or, once `big_requests_enabled`, the `max_request_length` given in the
+	/// [`EnableBigRequests` reply].
+	///
+	/// [request]: Request
+	/// [`EnableBigRequests` reply]: crate::big_requests::reply::EnableBigRequests
+	pub max_request_length: u32,
+}
+
+/// How the [length] of a [request] is encoded in its header.
+///
+/// [request]: Request
+/// [length]: Request::length
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum LengthEncoding {
+	/// The [length] is encoded in the 2-byte length field of the header.
+	///
+	/// [length]: Request::length
+	Standard,
+	/// The 2-byte length field of the header is `0`, and it is followed by the
+	/// [length] as a `u32`.
+	///
+	/// This encoding can only be used once the [BIG-REQUESTS extension] is
+	/// enabled. The extended length field is counted in the [length].
+	///
+	/// [length]: Request::length
+	/// [BIG-REQUESTS extension]: crate::big_requests
+	Extended,
+}
+
+impl LengthEncoding {
+	/// The number of bytes which this encoding adds to the size of a
+	/// [request].
+	///
+	/// [request]: Request
+	#[must_use]
+	pub const fn extra_size(self) -> usize {
+		match self {
+			Self::Standard => 0,
+			// The extended `u32` length field.
+			Self::Extended => 4,
+		}
+	}
+}
+
+/// Writes the given [request] according to the given [`WriteControl`].
+///
+/// If the [request] is larger than [`RequestTooLarge::MAX_SIZE`] and the
+/// [BIG-REQUESTS extension] is enabled, it is written with the extended length
+/// encoding: its 2-byte length field is `0`, and it is followed by the
+/// [request]'s length as a `u32`. Otherwise, the [request] is written as
+/// normal.
+///
+/// [request]: Request
+/// [BIG-REQUESTS extension]: crate::big_requests
+///
+/// # Errors
+/// Returns [`RequestTooLarge`] if the [request] is larger than
+/// [`RequestTooLarge::MAX_SIZE`] and the [BIG-REQUESTS extension] is not
+/// enabled.
+///
+/// Returns [`ExceedsMaxRequestLength`] if the [request] is longer than the
+/// `control`'s `max_request_length`.
+///
+/// Returns any error returned when writing the [request].
+pub fn write_request_to<R: Request>(
+	buf: &mut impl BufMut, request: &R, control: &WriteControl,
+) -> WriteResult {
+	let size = request.x11_size();
+	let extended = size > RequestTooLarge::MAX_SIZE;
+
+	if extended && !control.big_requests_enabled {
+		return Err(RequestTooLarge { size }.into());
+	}
+
+	// The extended length field is counted in the request's length.
+	let length = if extended { size / 4 + 1 } else { size / 4 };
+
+	if length > control.max_request_length as usize {
+		return Err(ExceedsMaxRequestLength {
+			length,
+			max_request_length: control.max_request_length,
+		}
+		.into());
+	}
+
+	if extended {
+		request.write_with_encoding(buf, LengthEncoding::Extended)
+	} else {
+		request.write_to(buf)
+	}
+}
 
 /// A message sent from an X client to the X server.
 #[doc(notable_trait)]
@@ -135,6 +324,78 @@ pub trait Request: X11Size + Writable {
 
 		(size / 4) as u16
 	}
+
+	/// The size of this `Request`, including the header, in 4-byte units, if
+	/// it can be encoded.
+	///
+	/// See [`length()`] for more information.
+	///
+	/// # Errors
+	/// Returns [`RequestTooLarge`] if this `Request` is larger than
+	/// [`RequestTooLarge::MAX_SIZE`].
+	///
+	/// [`length()`]: Request::length
+	fn checked_length(&self) -> Result<u16, RequestTooLarge> {
+		let size = self.x11_size();
+
+		if size > RequestTooLarge::MAX_SIZE {
+			Err(RequestTooLarge { size })
+		} else {
+			Ok(self.length())
+		}
+	}
+
+	/// Writes the length field of this `Request`'s header with the given
+	/// [`LengthEncoding`].
+	///
+	/// # Errors
+	/// Returns [`RequestTooLarge`] if the length of this `Request` cannot be
+	/// encoded with the given `encoding`.
+	fn write_length(&self, buf: &mut impl BufMut, encoding: LengthEncoding) -> WriteResult {
+		match encoding {
+			LengthEncoding::Standard => buf.put_u16(self.checked_length()?),
+
+			LengthEncoding::Extended => {
+				let size = self.x11_size();
+				// The extended length field is counted in the length.
+				let length = u32::try_from(size / 4 + 1).map_err(|_| RequestTooLarge { size })?;
+
+				buf.put_u16(0);
+				buf.put_u32(length);
+			},
+		}
+
+		Ok(())
+	}
+
+	/// Writes this `Request` with its length encoded with the given
+	/// [`LengthEncoding`].
+	///
+	/// [`Writable::write_to`] writes a `Request` with the
+	/// [`LengthEncoding::Standard`] encoding. [`write_request_to`] uses this to
+	/// write `Request`s with the [`LengthEncoding::Extended`] encoding.
+	///
+	/// This is implemented by [`derive_xrb!`] for `Request`s which derive
+	/// [`Writable`]. By default, only the [`LengthEncoding::Standard`] encoding
+	/// is supported.
+	///
+	/// [`derive_xrb!`]: xrbk_macro::derive_xrb!
+	///
+	/// # Errors
+	/// Returns [`RequestTooLarge`] if the length of this `Request` cannot be
+	/// encoded with the given `encoding`.
+	///
+	/// Returns any error returned when writing the `Request`.
+	fn write_with_encoding(&self, buf: &mut impl BufMut, encoding: LengthEncoding) -> WriteResult {
+		match encoding {
+			LengthEncoding::Standard => self.write_to(buf),
+
+			LengthEncoding::Extended => Err(RequestTooLarge {
+				size: self.x11_size(),
+			}
+			.into()),
+		}
+	}
 }
 
 /// The result of sending a [request].
@@ -266,12 +527,12 @@ pub trait Reply: X11Size + Readable {
 	/// # use xrb::message::Request;
 	///
 	/// derive_xrb! {
-	///     # #[derive(Debug, Hash, PartialEq, Eq, Readable, Writable, X11Size)]
+	///     # #[derive(Debug, Hash, PartialEq, Eq, Readable, Writable, X11Size, ConstantX11Size)]
 	///     # pub struct GetGeometry: Request(14) -> GetGeometryReply {
 	///     #     pub drawable: xrb::Drawable,
 	///     # }
 	///     #
-	///     #[derive(Debug, Hash, Readable, Writable, X11Size)]
+	///     #[derive(Debug, Hash, Readable, Writable, X11Size, ConstantX11Size)]
 	///     pub struct GetGeometryReply: Reply for GetGeometry {
 	///         // Header is 8 bytes.
 	///
@@ -335,6 +596,16 @@ pub trait Event: X11Size + Readable + Writable {
 	/// events.
 	const CODE: u8;
 
+	/// Returns this `Event`'s [code].
+	///
+	/// This never includes the [`SEND_EVENT_BIT`]: see [`Flagged`] for reading
+	/// and writing `Event`s with it.
+	///
+	/// [code]: Event::CODE
+	fn code(&self) -> u8 {
+		Self::CODE
+	}
+
 	/// The sequence number associated with the last [request] received that
 	/// was related to this `Event`.
 	///
@@ -342,6 +613,122 @@ pub trait Event: X11Size + Readable + Writable {
 	fn sequence(&self) -> Option<u16>;
 }
 
+/// The bit which is set in an [event]'s code if that [event] was generated by
+/// a [`SendEvent` request].
+///
+/// [event]: Event
+/// [`SendEvent` request]: crate::x11::request::SendEvent
+pub const SEND_EVENT_BIT: u8 = 0x80;
+
+/// Returns whether the given [event] code has the [`SEND_EVENT_BIT`] set.
+///
+/// [event]: Event
+#[must_use]
+pub const fn is_send_event(code: u8) -> bool {
+	code & SEND_EVENT_BIT != 0
+}
+
+/// An [event] together with whether it was generated by a
+/// [`SendEvent` request].
+///
+/// [`Event`] implementations neither read nor write the [`SEND_EVENT_BIT`]:
+/// their [`Readable`] implementations start after the code, and their
+/// [`Writable`] implementations write the plain [`CODE`]. `Flagged` reads the
+/// code too, accepting it with or without the [`SEND_EVENT_BIT`], and writes
+/// the [`SEND_EVENT_BIT`] again if `send_event` is `true`.
+///
+/// [event]: Event
+/// [`SendEvent` request]: crate::x11::request::SendEvent
+/// [`CODE`]: Event::CODE
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Flagged<E: Event> {
+	/// The [event].
+	///
+	/// [event]: Event
+	pub event: E,
+	/// Whether the `event` was generated by a [`SendEvent` request].
+	///
+	/// [`SendEvent` request]: crate::x11::request::SendEvent
+	pub send_event: bool,
+}
+
+impl<E: Event> Flagged<E> {
+	/// Returns the code of the `event`, including the [`SEND_EVENT_BIT`] if
+	/// `send_event` is `true`.
+	pub const fn code(&self) -> u8 {
+		if self.send_event {
+			E::CODE | SEND_EVENT_BIT
+		} else {
+			E::CODE
+		}
+	}
+
+	/// Returns whether the `event` was generated by a [`SendEvent` request].
+	///
+	/// [`SendEvent` request]: crate::x11::request::SendEvent
+	pub const fn is_send_event(&self) -> bool {
+		self.send_event
+	}
+}
+
+/// Formats the `event`, followed by `(sent)` if it was generated by a
+/// [`SendEvent` request].
+///
+/// [`SendEvent` request]: crate::x11::request::SendEvent
+impl<E: Event + fmt::Display> fmt::Display for Flagged<E> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.event.fmt(f)?;
+
+		if self.send_event {
+			f.write_str(" (sent)")?;
+		}
+
+		Ok(())
+	}
+}
+
+impl<E: Event> X11Size for Flagged<E> {
+	fn x11_size(&self) -> usize {
+		self.event.x11_size()
+	}
+}
+
+impl<E: Event> Readable for Flagged<E> {
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
+		Self::read_from_with_mode(buf, ReadMode::Lenient)
+	}
+
+	fn read_from_with_mode(buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Self> {
+		let code = u8::read_from(buf)?;
+
+		if code & !SEND_EVENT_BIT != E::CODE {
+			return Err(ReadError::UnrecognizedDiscriminant(code.into()));
+		}
+
+		Ok(Self {
+			event: E::read_from_with_mode(buf, mode)?,
+			send_event: is_send_event(code),
+		})
+	}
+}
+
+impl<E: Event> Writable for Flagged<E> {
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		let mut bytes = Vec::with_capacity(self.event.x11_size());
+		self.event.write_to(&mut bytes)?;
+
+		// Replace the code written by the event.
+		if let Some(code) = bytes.first_mut() {
+			*code = self.code();
+		}
+
+		buf.put_slice(&bytes);
+
+		Ok(())
+	}
+}
+
 /// An error sent from the X server to an X client in response to a failed
 /// [request].
 ///
@@ -370,3 +757,805 @@ pub trait Error: X11Size + Readable {
 	/// [major opcode]: Request::MAJOR_OPCODE
 	fn major_opcode(&self) -> u8;
 }
+
+/// An extension to the core X11 protocol.
+///
+/// Unlike the core protocol, an extension's [major opcode], [event] codes, and
+/// [error] codes are assigned by the X server: they are returned in the
+/// [`QueryExtension` reply] for the extension's [`NAME`], and are represented
+/// by [`ExtensionCodes`].
+///
+/// [major opcode]: Request::MAJOR_OPCODE
+/// [event]: Event
+/// [error]: Error
+///
+/// [`NAME`]: Extension::NAME
+/// [`QueryExtension` reply]: reply::QueryExtension
+pub trait Extension {
+	/// The name used to query the extension with a [`QueryExtension` request].
+	///
+	/// [`QueryExtension` request]: crate::x11::request::QueryExtension
+	const NAME: &'static str;
+}
+
+/// The [major opcode] written by extension [requests].
+///
+/// An extension's [major opcode] is only known once it has been queried, so
+/// extension [requests] are written with this placeholder in its place:
+/// [`ExtensionCodes::write_request`] replaces it with the extension's actual
+/// [major opcode].
+///
+/// [major opcode]: Request::MAJOR_OPCODE
+/// [requests]: Request
+pub const UNASSIGNED_MAJOR_OPCODE: u8 = 0;
+
+/// The codes assigned to an [extension] by the X server.
+///
+/// The [`CODE`] of an extension [event] is its offset from the extension's
+/// `first_event` code: an [event] with a [`CODE`] of `0` is sent with the code
+/// `first_event`, an [event] with a [`CODE`] of `1` is sent with the code
+/// `first_event + 1`, and so on.
+///
+/// [extension]: Extension
+/// [event]: Event
+/// [`CODE`]: Event::CODE
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ExtensionCodes {
+	/// The [major opcode] of the extension's [requests].
+	///
+	/// [major opcode]: Request::MAJOR_OPCODE
+	/// [requests]: Request
+	pub major_opcode: u8,
+
+	/// The code of the extension's first [event].
+	///
+	/// This is `0` if the extension defines no [events][event].
+	///
+	/// [event]: Event
+	pub first_event: u8,
+	/// The code of the extension's first [error].
+	///
+	/// This is `0` if the extension defines no [errors][error].
+	///
+	/// [error]: Error
+	pub first_error: u8,
+}
+
+impl ExtensionCodes {
+	/// Returns the `ExtensionCodes` given in a [`QueryExtension` reply].
+	///
+	/// Returns [`None`] if the extension is not present.
+	///
+	/// [`QueryExtension` reply]: reply::QueryExtension
+	#[must_use]
+	pub fn from_reply(reply: &reply::QueryExtension) -> Option<Self> {
+		if !reply.present {
+			return None;
+		}
+
+		Some(Self {
+			major_opcode: reply.major_opcode?,
+			first_event: reply.first_event_code.unwrap_or(0),
+			first_error: reply.first_error_code.unwrap_or(0),
+		})
+	}
+
+	/// Returns the code with which the [event] `E` is sent.
+	///
+	/// [event]: Event
+	#[must_use]
+	pub const fn event_code<E: Event>(&self) -> u8 {
+		self.first_event.wrapping_add(E::CODE)
+	}
+
+	/// Returns the [`CODE`] of the extension [event] sent with the given
+	/// `code`, ignoring the [`SEND_EVENT_BIT`].
+	///
+	/// Returns [`None`] if the `code` is less than the extension's
+	/// `first_event` code, and so can't be one of the extension's [events].
+	///
+	/// [event]: Event
+	/// [events]: Event
+	/// [`CODE`]: Event::CODE
+	#[must_use]
+	pub const fn event_offset(&self, code: u8) -> Option<u8> {
+		(code & !SEND_EVENT_BIT).checked_sub(self.first_event)
+	}
+
+	/// Writes the given extension [request] with the extension's
+	/// `major_opcode`.
+	///
+	/// [request]: Request
+	///
+	/// # Errors
+	/// Returns any error returned when writing the `request`.
+	pub fn write_request<R: Request>(&self, request: &R, buf: &mut impl BufMut) -> WriteResult {
+		let mut bytes = Vec::with_capacity(request.x11_size());
+		request.write_to(&mut bytes)?;
+
+		// Replace the placeholder major opcode written by the request.
+		if let Some(major_opcode) = bytes.first_mut() {
+			*major_opcode = self.major_opcode;
+		}
+
+		buf.put_slice(&bytes);
+
+		Ok(())
+	}
+
+	/// Reads the extension [event] `E`, including its code.
+	///
+	/// This is the equivalent of reading a [`Flagged`] core [event].
+	///
+	/// [event]: Event
+	///
+	/// # Errors
+	/// Returns [`ReadError::UnrecognizedDiscriminant`] if the code read is not
+	/// [`event_code::<E>()`], with or without the [`SEND_EVENT_BIT`].
+	///
+	/// Returns any error returned when reading the [event].
+	///
+	/// [`event_code::<E>()`]: ExtensionCodes::event_code
+	pub fn read_event<E: Event>(&self, buf: &mut impl Buf) -> ReadResult<Flagged<E>> {
+		self.read_event_with_mode(buf, ReadMode::Lenient)
+	}
+
+	/// Reads the extension [event] `E`, including its code, treating unused
+	/// bytes according to the given [`ReadMode`].
+	///
+	/// [event]: Event
+	///
+	/// # Errors
+	/// The same errors as [`read_event`], as well as
+	/// [`ReadError::NonZeroPadding`] if `mode` is [`ReadMode::Strict`] and an
+	/// unused byte was not zero.
+	///
+	/// [`read_event`]: ExtensionCodes::read_event
+	pub fn read_event_with_mode<E: Event>(
+		&self, buf: &mut impl Buf, mode: ReadMode,
+	) -> ReadResult<Flagged<E>> {
+		let code = u8::read_from(buf)?;
+
+		if code & !SEND_EVENT_BIT != self.event_code::<E>() {
+			return Err(ReadError::UnrecognizedDiscriminant(code.into()));
+		}
+
+		Ok(Flagged {
+			event: E::read_from_with_mode(buf, mode)?,
+			send_event: is_send_event(code),
+		})
+	}
+
+	/// Writes the given extension [event] with its code offset from the
+	/// extension's `first_event` code.
+	///
+	/// This is the equivalent of writing a [`Flagged`] core [event].
+	///
+	/// [event]: Event
+	///
+	/// # Errors
+	/// Returns any error returned when writing the [event].
+	pub fn write_event<E: Event>(&self, event: &Flagged<E>, buf: &mut impl BufMut) -> WriteResult {
+		let mut bytes = Vec::with_capacity(event.x11_size());
+		event.write_to(&mut bytes)?;
+
+		// Replace the offset code written by the event.
+		if let Some(code) = bytes.first_mut() {
+			*code = (*code & SEND_EVENT_BIT) | self.event_code::<E>();
+		}
+
+		buf.put_slice(&bytes);
+
+		Ok(())
+	}
+
+	/// Decodes the given [`GenericEvent`] as the extension [event] `E`.
+	///
+	/// Returns [`None`] if the [`GenericEvent`] is not an `E` event sent by
+	/// this extension.
+	///
+	/// [event]: Event
+	#[must_use]
+	pub fn decode_generic_event<E: GenericExtensionEvent>(
+		&self, event: &GenericEvent,
+	) -> Option<ReadResult<E>> {
+		(event.extension == self.major_opcode && event.event_type == E::EVENT_TYPE)
+			.then(|| E::from_generic(event))
+	}
+}
+
+/// An extension [event] sent as a [`GenericEvent`].
+///
+/// A [`GenericEvent`] is identified by the [major opcode] of its extension and
+/// its `event_type`: `GenericExtensionEvent`s are decoded from the `data` of a
+/// [`GenericEvent`] with a matching `event_type`, either with
+/// [`ExtensionCodes::decode_generic_event`] or with a
+/// [`GenericEventDecoders`] registry.
+///
+/// [event]: Event
+/// [major opcode]: Request::MAJOR_OPCODE
+pub trait GenericExtensionEvent: Sized {
+	/// The `event_type` identifying this [event] among its extension's
+	/// [`GenericEvent`]s.
+	///
+	/// [event]: Event
+	const EVENT_TYPE: u16;
+
+	/// Decodes this [event] from the given [`GenericEvent`].
+	///
+	/// The `extension` and `event_type` of the [`GenericEvent`] have already
+	/// been checked when this is called.
+	///
+	/// [event]: Event
+	///
+	/// # Errors
+	/// Returns any error encountered when reading the [event] from the
+	/// [`GenericEvent`]'s `data`.
+	fn from_generic(event: &GenericEvent) -> ReadResult<Self>;
+}
+
+/// A function decoding a [`GenericEvent`] as a `T`.
+type GenericEventDecoder<T> = Box<dyn Fn(&GenericEvent) -> ReadResult<T> + Send + Sync>;
+
+/// A registry of decoders for [`GenericEvent`]s, keyed by the [major opcode]
+/// of their extension and their `event_type`.
+///
+/// Extension [events] are registered with [`register`], converting them to a
+/// common type `T` - typically an enum of the [events] a client is
+/// interested in.
+///
+/// [major opcode]: Request::MAJOR_OPCODE
+/// [events]: Event
+/// [`register`]: GenericEventDecoders::register
+pub struct GenericEventDecoders<T> {
+	decoders: HashMap<(u8, u16), GenericEventDecoder<T>>,
+}
+
+impl<T> Default for GenericEventDecoders<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> GenericEventDecoders<T> {
+	/// Creates a new registry with no decoders.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			decoders: HashMap::new(),
+		}
+	}
+
+	/// Registers the extension [event] `E`, sent by the extension with the
+	/// given `codes`, to be decoded and converted to a `T` with `convert`.
+	///
+	/// Any decoder previously registered for the same extension and
+	/// `event_type` is replaced.
+	///
+	/// [event]: Event
+	pub fn register<E: GenericExtensionEvent>(
+		&mut self, codes: &ExtensionCodes, convert: impl Fn(E) -> T + Send + Sync + 'static,
+	) {
+		self.decoders.insert(
+			(codes.major_opcode, E::EVENT_TYPE),
+			Box::new(move |event| E::from_generic(event).map(&convert)),
+		);
+	}
+
+	/// Returns whether a decoder is registered for the given extension
+	/// [major opcode] and `event_type`.
+	///
+	/// [major opcode]: Request::MAJOR_OPCODE
+	#[must_use]
+	pub fn is_registered(&self, extension: u8, event_type: u16) -> bool {
+		self.decoders.contains_key(&(extension, event_type))
+	}
+
+	/// Decodes the given [`GenericEvent`] with the decoder registered for its
+	/// `extension` and `event_type`.
+	///
+	/// Returns [`None`] if no decoder is registered for it.
+	#[must_use]
+	pub fn decode(&self, event: &GenericEvent) -> Option<ReadResult<T>> {
+		self.decoders
+			.get(&(event.extension, event.event_type))
+			.map(|decode| decode(event))
+	}
+}
+
+/// The [`ExtensionCodes`] of the [extensions] present on an X server, keyed by
+/// their [`NAME`].
+///
+/// An `ExtensionRegistry` is filled in from the [`QueryExtension` replies] for
+/// the [extensions] a client uses. It can then route the [event] and [error]
+/// codes sent by the X server back to the [extension][extensions] which defines
+/// them.
+///
+/// [extensions]: Extension
+/// [event]: Event
+/// [error]: Error
+///
+/// [`NAME`]: Extension::NAME
+/// [`QueryExtension` replies]: reply::QueryExtension
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExtensionRegistry {
+	extensions: HashMap<&'static str, ExtensionCodes>,
+}
+
+impl ExtensionRegistry {
+	/// Creates a new registry with no [extensions].
+	///
+	/// [extensions]: Extension
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			extensions: HashMap::new(),
+		}
+	}
+
+	/// Registers the [extension] `X` with the given `codes`.
+	///
+	/// Returns the codes previously registered for `X`, if any.
+	///
+	/// [extension]: Extension
+	pub fn insert<X: Extension>(&mut self, codes: ExtensionCodes) -> Option<ExtensionCodes> {
+		self.extensions.insert(X::NAME, codes)
+	}
+
+	/// Registers the [extension] `X` with the codes given in its
+	/// [`QueryExtension` reply].
+	///
+	/// Returns the codes registered, or [`None`] if the [extension] is not
+	/// present - in which case nothing is registered.
+	///
+	/// [extension]: Extension
+	/// [`QueryExtension` reply]: reply::QueryExtension
+	pub fn insert_reply<X: Extension>(
+		&mut self, reply: &reply::QueryExtension,
+	) -> Option<ExtensionCodes> {
+		let codes = ExtensionCodes::from_reply(reply)?;
+		self.insert::<X>(codes);
+
+		Some(codes)
+	}
+
+	/// Returns the codes registered for the [extension] `X`.
+	///
+	/// [extension]: Extension
+	#[must_use]
+	pub fn get<X: Extension>(&self) -> Option<ExtensionCodes> {
+		self.get_by_name(X::NAME)
+	}
+
+	/// Returns the codes registered for the [extension] with the given
+	/// [`NAME`].
+	///
+	/// [extension]: Extension
+	/// [`NAME`]: Extension::NAME
+	#[must_use]
+	pub fn get_by_name(&self, name: &str) -> Option<ExtensionCodes> {
+		self.extensions.get(name).copied()
+	}
+
+	/// Returns an iterator over the [`NAME`] and codes of every registered
+	/// [extension].
+	///
+	/// [extension]: Extension
+	/// [`NAME`]: Extension::NAME
+	pub fn iter(&self) -> impl Iterator<Item = (&'static str, ExtensionCodes)> + '_ {
+		self.extensions.iter().map(|(name, codes)| (*name, *codes))
+	}
+
+	/// Returns the [`NAME`] of the [extension] whose [requests] are sent with
+	/// the given `major_opcode`.
+	///
+	/// [extension]: Extension
+	/// [requests]: Request
+	/// [`NAME`]: Extension::NAME
+	#[must_use]
+	pub fn request_extension(&self, major_opcode: u8) -> Option<&'static str> {
+		self.iter()
+			.find(|(_, codes)| codes.major_opcode == major_opcode)
+			.map(|(name, _)| name)
+	}
+
+	/// Returns the [`NAME`] of the [extension] which defines the [event] sent
+	/// with the given `code`, along with that [event]'s [`CODE`] relative to
+	/// the extension's `first_event`.
+	///
+	/// The [`SEND_EVENT_BIT`] of the `code` is ignored.
+	///
+	/// Each [extension]'s [events][event] are assumed to extend up to the
+	/// `first_event` of the next [extension], so this returns [`None`] only if
+	/// the `code` is lower than every registered `first_event` - for example,
+	/// if it is the code of a core [event].
+	///
+	/// [extension]: Extension
+	/// [event]: Event
+	/// [`NAME`]: Extension::NAME
+	/// [`CODE`]: Event::CODE
+	#[must_use]
+	pub fn event_extension(&self, code: u8) -> Option<(&'static str, u8)> {
+		let code = code & !SEND_EVENT_BIT;
+
+		self.iter()
+			.filter(|(_, codes)| codes.first_event != 0 && codes.first_event <= code)
+			.max_by_key(|(_, codes)| codes.first_event)
+			.map(|(name, codes)| (name, code - codes.first_event))
+	}
+
+	/// Returns the [`NAME`] of the [extension] which defines the [error] sent
+	/// with the given `code`, along with that [error]'s code relative to the
+	/// extension's `first_error`.
+	///
+	/// See [`event_extension`] for how the `code` is matched.
+	///
+	/// [extension]: Extension
+	/// [error]: Error
+	/// [`NAME`]: Extension::NAME
+	/// [`event_extension`]: ExtensionRegistry::event_extension
+	#[must_use]
+	pub fn error_extension(&self, code: u8) -> Option<(&'static str, u8)> {
+		self.iter()
+			.filter(|(_, codes)| codes.first_error != 0 && codes.first_error <= code)
+			.max_by_key(|(_, codes)| codes.first_error)
+			.map(|(name, codes)| (name, code - codes.first_error))
+	}
+}
+
+/// Widens the 16-bit sequence numbers sent over the wire into full sequence
+/// numbers.
+///
+/// Every [request] sent on a connection is assigned the next sequence number,
+/// starting with `1`, but only the least significant 16 bits of that number
+/// are included in [replies], [events], and [errors]. On a long-running
+/// connection, that 16-bit counter wraps every 65536 [requests][request].
+///
+/// A `SequenceTracker` counts the [requests][request] sent with
+/// [`sent_request`], so that [`resolve`] can map a 16-bit sequence number back
+/// to the full sequence number of the [request] it refers to.
+///
+/// [request]: Request
+/// [replies]: Reply
+/// [events]: Event
+/// [errors]: Error
+///
+/// [`sent_request`]: SequenceTracker::sent_request
+/// [`resolve`]: SequenceTracker::resolve
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SequenceTracker {
+	last_sent: u64,
+}
+
+impl SequenceTracker {
+	/// How far ahead of the last [request] sent a 16-bit sequence number may
+	/// resolve to.
+	///
+	/// [Events] generated by the server carry the sequence number of the last
+	/// [request] it has processed. If a [request] has been written to the
+	/// connection before it was recorded with [`sent_request`], an [event] can
+	/// refer to a sequence number slightly ahead of [`last_sent`]. Without
+	/// this allowance, that sequence number would be resolved to one 65536
+	/// [requests][request] earlier.
+	///
+	/// [request]: Request
+	/// [event]: Event
+	/// [Events]: Event
+	///
+	/// [`sent_request`]: SequenceTracker::sent_request
+	/// [`last_sent`]: SequenceTracker::last_sent
+	pub const AHEAD_TOLERANCE: u64 = 256;
+
+	const WRAP: u64 = 1 << 16;
+
+	/// Creates a new `SequenceTracker` for a connection on which no
+	/// [requests][request] have been sent.
+	///
+	/// [request]: Request
+	#[must_use]
+	pub const fn new() -> Self {
+		Self { last_sent: 0 }
+	}
+
+	/// Records that a [request] has been sent, returning its full sequence
+	/// number.
+	///
+	/// [request]: Request
+	pub const fn sent_request(&mut self) -> u64 {
+		self.last_sent += 1;
+
+		self.last_sent
+	}
+
+	/// Returns the full sequence number of the last [request] sent.
+	///
+	/// This is `0` if no [requests][request] have been sent.
+	///
+	/// [request]: Request
+	#[must_use]
+	pub const fn last_sent(&self) -> u64 {
+		self.last_sent
+	}
+
+	/// Resolves a 16-bit `wire_sequence` from a [reply], [event], or [error]
+	/// to the full sequence number it most plausibly refers to.
+	///
+	/// This is the greatest sequence number, no greater than
+	/// [`last_sent`] + [`AHEAD_TOLERANCE`], whose least significant 16 bits
+	/// are `wire_sequence`.
+	///
+	/// [reply]: Reply
+	/// [event]: Event
+	/// [error]: Error
+	///
+	/// [`last_sent`]: SequenceTracker::last_sent
+	/// [`AHEAD_TOLERANCE`]: SequenceTracker::AHEAD_TOLERANCE
+	#[must_use]
+	pub const fn resolve(&self, wire_sequence: u16) -> u64 {
+		let limit = self.last_sent + Self::AHEAD_TOLERANCE;
+		let candidate = (limit & !(Self::WRAP - 1)) | wire_sequence as u64;
+
+		if candidate <= limit || candidate < Self::WRAP {
+			candidate
+		} else {
+			candidate - Self::WRAP
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		x11::event::{ClientMessage, ClientMessageData},
+		Atom,
+		Window,
+	};
+
+	/// A simple xorshift generator, so that the tests are reproducible.
+	struct Xorshift(u64);
+
+	impl Xorshift {
+		fn next(&mut self) -> u64 {
+			self.0 ^= self.0 << 13;
+			self.0 ^= self.0 >> 7;
+			self.0 ^= self.0 << 17;
+
+			self.0
+		}
+	}
+
+	#[allow(clippy::cast_possible_truncation)]
+	const fn wire(sequence: u64) -> u16 {
+		sequence as u16
+	}
+
+	#[test]
+	fn test_sequence_resolution_across_wraps() {
+		let mut tracker = SequenceTracker::new();
+		let mut random = Xorshift(0x2545_f491_4f6c_dd1d);
+
+		for expected in 1..=200_000 {
+			assert_eq!(tracker.sent_request(), expected);
+
+			if expected % 97 != 0 {
+				continue;
+			}
+
+			// Any request sent within the last wrap (less the ahead tolerance)
+			// resolves to itself.
+			let window = expected.min(SequenceTracker::WRAP - SequenceTracker::AHEAD_TOLERANCE);
+
+			for _ in 0..8 {
+				let sequence = expected - random.next() % window;
+
+				assert_eq!(tracker.resolve(wire(sequence)), sequence);
+			}
+
+			// Sequences slightly ahead of the last request sent resolve ahead.
+			let ahead = expected + random.next() % SequenceTracker::AHEAD_TOLERANCE + 1;
+			assert_eq!(tracker.resolve(wire(ahead)), ahead);
+		}
+
+		assert_eq!(tracker.last_sent(), 200_000);
+	}
+
+	#[test]
+	fn test_sequence_resolution_edge_cases() {
+		let mut tracker = SequenceTracker::new();
+
+		// Events may be received before any request is sent.
+		assert_eq!(tracker.resolve(0), 0);
+		assert_eq!(tracker.resolve(5000), 5000);
+
+		for _ in 0..0xffff {
+			tracker.sent_request();
+		}
+
+		assert_eq!(tracker.resolve(0xffff), 0xffff);
+		assert_eq!(tracker.resolve(0x1000), 0x1000);
+		// Sequences just past the wrap are within the ahead tolerance.
+		assert_eq!(tracker.resolve(0), 0x1_0000);
+		assert_eq!(tracker.resolve(1), 0x1_0001);
+
+		tracker.sent_request();
+		tracker.sent_request();
+
+		assert_eq!(tracker.resolve(1), 0x1_0001);
+		assert_eq!(tracker.resolve(0xfffe), 0xfffe);
+	}
+
+	#[test]
+	fn test_flagged_client_message() {
+		#[rustfmt::skip]
+		let bytes: [u8; 32] = [
+			// Code: ClientMessage (33) with the send event bit set.
+			0xa1,
+			// Format: 32.
+			32,
+			// Sequence.
+			0x00, 0x2a,
+			// Window.
+			0x00, 0x60, 0x00, 0x01,
+			// Type.
+			0x00, 0x00, 0x01, 0x9c,
+			// Data.
+			0x00, 0x00, 0x00, 0x01,
+			0x00, 0x00, 0x01, 0x2d,
+			0x00, 0x00, 0x01, 0x2e,
+			0x00, 0x00, 0x00, 0x02,
+			0x00, 0x00, 0x00, 0x00,
+		];
+
+		let flagged = <Flagged<ClientMessage>>::read_from(&mut &bytes[..]).unwrap();
+
+		assert!(flagged.is_send_event());
+		assert_eq!(flagged.code(), 0xa1);
+		assert_eq!(flagged.event.code(), ClientMessage::CODE);
+		assert_eq!(flagged.event.sequence, 0x2a);
+		assert_eq!(flagged.event.window, Window::new(0x0060_0001));
+		assert_eq!(flagged.event.r#type, Atom::new(0x19c));
+		assert_eq!(
+			flagged.event.data,
+			ClientMessageData::Longs([1, 0x12d, 0x12e, 2, 0]),
+		);
+
+		// Writing it sets the send event bit again.
+		let mut written = vec![];
+		flagged.write_to(&mut written).unwrap();
+		assert_eq!(written, bytes);
+
+		// Writing the event itself does not.
+		let mut written = vec![];
+		flagged.event.write_to(&mut written).unwrap();
+		assert_eq!(written[0], ClientMessage::CODE);
+		assert_eq!(written[1..], bytes[1..]);
+	}
+
+	#[test]
+	fn test_flagged_rejects_other_events() {
+		let mut bytes = [0; 32];
+		bytes[0] = ClientMessage::CODE + 1;
+
+		assert!(matches!(
+			<Flagged<ClientMessage>>::read_from(&mut &bytes[..]),
+			Err(ReadError::UnrecognizedDiscriminant(34)),
+		));
+	}
+
+	/// A test extension event carrying a `u32` counter in its first four bytes
+	/// of data.
+	#[derive(Debug, PartialEq, Eq)]
+	struct Counter(u32);
+
+	impl GenericExtensionEvent for Counter {
+		const EVENT_TYPE: u16 = 3;
+
+		fn from_generic(event: &GenericEvent) -> ReadResult<Self> {
+			Ok(Self(u32::read_from(&mut &event.data[..])?))
+		}
+	}
+
+	#[test]
+	fn test_generic_event_decoders() {
+		let codes = ExtensionCodes {
+			major_opcode: 131,
+			first_event: 0,
+			first_error: 0,
+		};
+
+		let mut data = vec![0; GenericEvent::MIN_DATA_LEN];
+		data[..4].copy_from_slice(&42_u32.to_be_bytes());
+
+		let event = |extension, event_type| GenericEvent {
+			sequence: 1,
+			extension,
+			event_type,
+			data: data.clone(),
+		};
+
+		let mut decoders = GenericEventDecoders::new();
+		decoders.register(&codes, |Counter(count)| u64::from(count));
+
+		assert!(decoders.is_registered(131, Counter::EVENT_TYPE));
+		assert_eq!(decoders.decode(&event(131, 3)).unwrap().unwrap(), 42);
+
+		// Another extension's event with the same `event_type`.
+		assert!(decoders.decode(&event(132, 3)).is_none());
+		// Another event of the same extension.
+		assert!(decoders.decode(&event(131, 4)).is_none());
+
+		assert_eq!(
+			codes
+				.decode_generic_event::<Counter>(&event(131, 3))
+				.unwrap()
+				.unwrap(),
+			Counter(42),
+		);
+		assert!(codes
+			.decode_generic_event::<Counter>(&event(131, 4))
+			.is_none());
+	}
+
+	#[test]
+	fn test_extension_registry() {
+		use crate::{composite::Composite, damage::DamageExtension, randr::Randr, xc_misc::XcMisc};
+
+		let mut registry = ExtensionRegistry::new();
+
+		let randr = registry
+			.insert_reply::<Randr>(&reply::QueryExtension {
+				sequence: 1,
+				present: true,
+				major_opcode: Some(140),
+				first_event_code: Some(89),
+				first_error_code: Some(147),
+			})
+			.unwrap();
+		registry.insert::<DamageExtension>(ExtensionCodes {
+			major_opcode: 143,
+			first_event: 91,
+			first_error: 152,
+		});
+		registry.insert::<XcMisc>(ExtensionCodes {
+			major_opcode: 137,
+			first_event: 0,
+			first_error: 0,
+		});
+
+		// Composite is not present.
+		assert!(registry
+			.insert_reply::<Composite>(&reply::QueryExtension {
+				sequence: 2,
+				present: false,
+				major_opcode: None,
+				first_event_code: None,
+				first_error_code: None,
+			})
+			.is_none());
+		assert!(registry.get::<Composite>().is_none());
+
+		assert_eq!(registry.get::<Randr>(), Some(randr));
+		assert_eq!(registry.get_by_name("RANDR"), Some(randr));
+		assert_eq!(registry.iter().count(), 3);
+
+		assert_eq!(registry.request_extension(137), Some("XC-MISC"));
+		assert_eq!(registry.request_extension(1), None);
+
+		// Core events.
+		assert_eq!(registry.event_extension(12), None);
+		assert_eq!(registry.event_extension(35), None);
+
+		assert_eq!(registry.event_extension(89), Some(("RANDR", 0)));
+		assert_eq!(registry.event_extension(90), Some(("RANDR", 1)));
+		assert_eq!(registry.event_extension(91), Some(("DAMAGE", 0)));
+		assert_eq!(
+			registry.event_extension(91 | SEND_EVENT_BIT),
+			Some(("DAMAGE", 0))
+		);
+
+		assert_eq!(registry.error_extension(17), None);
+		assert_eq!(registry.error_extension(150), Some(("RANDR", 3)));
+		assert_eq!(registry.error_extension(152), Some(("DAMAGE", 0)));
+	}
+}
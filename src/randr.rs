@@ -0,0 +1,715 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Messages defined in the [RandR extension]: [requests], [replies], and
+//! [events].
+//!
+//! The RandR (Resize and Rotate) extension describes the monitors connected
+//! to each [screen]: each [`Output`] is a connector to which a monitor may be
+//! connected, and each [`Crtc`] scans out a [`Mode`] from an area of the
+//! [screen] to the [outputs] it drives.
+//!
+//! Only the subset of the extension needed to enumerate monitors is
+//! currently implemented.
+//!
+//! [RandR extension]: https://www.x.org/releases/X11R7.7/doc/randrproto/randrproto.txt
+//! [requests]: request
+//! [replies]: reply
+//! [events]: ScreenChangeNotify
+//! [screen]: crate::visual::Screen
+//! [outputs]: Output
+
+extern crate self as xrb;
+
+use bitflags::bitflags;
+use derivative::Derivative;
+use derive_more::{From, Into};
+
+use xrbk_macro::{derive_xrb, new, unwrap, ConstantX11Size, Readable, Wrap, Writable, X11Size};
+
+use crate::{
+	message::{Event, Extension},
+	unit::Px,
+	Timestamp,
+	Window,
+};
+
+pub mod reply;
+pub mod request;
+
+/// The RandR extension.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Randr;
+
+impl Extension for Randr {
+	const NAME: &'static str = "RANDR";
+}
+
+/// A resource ID referring to a CRTC: the part of the graphics hardware which
+/// scans out an area of a [screen] to one or more [outputs].
+///
+/// [screen]: crate::visual::Screen
+/// [outputs]: Output
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	From,
+	Into,
+	// `new` and `unwrap` const fns
+	new,
+	unwrap,
+	// XRBK traits
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	Wrap,
+)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
+pub struct Crtc(u32);
+
+/// A resource ID referring to an output: a connector to which a monitor may
+/// be connected.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	From,
+	Into,
+	// `new` and `unwrap` const fns
+	new,
+	unwrap,
+	// XRBK traits
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	Wrap,
+)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
+pub struct Output(u32);
+
+/// A resource ID referring to a display mode: a resolution and its timings.
+///
+/// See [`ModeInfo`] for more information.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	From,
+	Into,
+	// `new` and `unwrap` const fns
+	new,
+	unwrap,
+	// XRBK traits
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	Wrap,
+)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
+pub struct Mode(u32);
+
+bitflags! {
+	/// A mask of the rotations and reflections of a [CRTC].
+	///
+	/// [CRTC]: Crtc
+	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable)]
+	pub struct Rotation: u8 {
+		/// The image is not rotated.
+		const ROTATE_0 = 0x01;
+		/// The image is rotated 90 degrees counterclockwise.
+		const ROTATE_90 = 0x02;
+		/// The image is rotated 180 degrees.
+		const ROTATE_180 = 0x04;
+		/// The image is rotated 270 degrees counterclockwise.
+		const ROTATE_270 = 0x08;
+
+		/// The image is reflected along the x axis.
+		const REFLECT_X = 0x10;
+		/// The image is reflected along the y axis.
+		const REFLECT_Y = 0x20;
+	}
+
+	/// A mask of the sync and clock options of a [mode].
+	///
+	/// [mode]: ModeInfo
+	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable)]
+	pub struct ModeFlags: u32 {
+		/// The horizontal sync pulse is positive.
+		const HSYNC_POSITIVE = 0x0000_0001;
+		/// The horizontal sync pulse is negative.
+		const HSYNC_NEGATIVE = 0x0000_0002;
+		/// The vertical sync pulse is positive.
+		const VSYNC_POSITIVE = 0x0000_0004;
+		/// The vertical sync pulse is negative.
+		const VSYNC_NEGATIVE = 0x0000_0008;
+
+		/// The mode is interlaced.
+		const INTERLACE = 0x0000_0010;
+		/// Each scanline is displayed twice.
+		const DOUBLE_SCAN = 0x0000_0020;
+
+		/// A composite sync signal is used.
+		const CSYNC = 0x0000_0040;
+		/// The composite sync pulse is positive.
+		const CSYNC_POSITIVE = 0x0000_0080;
+		/// The composite sync pulse is negative.
+		const CSYNC_NEGATIVE = 0x0000_0100;
+
+		/// The [`horizontal_skew`](ModeInfo::horizontal_skew) of the mode is used.
+		const HSKEW_PRESENT = 0x0000_0200;
+		/// The mode is a broadcast mode.
+		const BROADCAST = 0x0000_0400;
+		/// The pixels are multiplexed.
+		const PIXEL_MULTIPLEX = 0x0000_0800;
+		/// The clock is doubled.
+		const DOUBLE_CLOCK = 0x0000_1000;
+		/// The clock is halved.
+		const CLOCK_DIVIDE_BY_2 = 0x0000_2000;
+	}
+
+	/// A mask of the RandR [events] selected with a [`SelectInput` request].
+	///
+	/// [events]: Event
+	/// [`SelectInput` request]: request::SelectInput
+	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable)]
+	pub struct NotifyMask: u16 {
+		/// [`ScreenChangeNotify`] events.
+		const SCREEN_CHANGE = 0x0001;
+		/// CRTC change events.
+		const CRTC_CHANGE = 0x0002;
+		/// Output change events.
+		const OUTPUT_CHANGE = 0x0004;
+		/// Output property change events.
+		const OUTPUT_PROPERTY = 0x0008;
+	}
+}
+
+#[cfg(feature = "serde")]
+impl_serde_for_bitflags!(Rotation, ModeFlags, NotifyMask);
+
+/// Whether a RandR configuration [request] succeeded.
+///
+/// [request]: crate::message::Request
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConfigStatus {
+	/// The [request] succeeded.
+	///
+	/// [request]: crate::message::Request
+	Success,
+
+	/// The given configuration timestamp did not match the X server's.
+	InvalidConfigTime,
+	/// The given timestamp was earlier than the last configuration change.
+	InvalidTime,
+
+	/// The [request] failed.
+	///
+	/// [request]: crate::message::Request
+	Failed,
+}
+
+/// Whether a monitor is connected to an [output].
+///
+/// [output]: Output
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutputConnection {
+	/// A monitor is connected.
+	Connected,
+	/// No monitor is connected.
+	Disconnected,
+	/// The X server can't tell whether a monitor is connected.
+	Unknown,
+}
+
+/// The order of the subpixels of each pixel of a monitor.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SubpixelOrder {
+	/// The subpixel order is unknown.
+	Unknown,
+
+	/// Red, green, then blue, from left to right.
+	HorizontalRgb,
+	/// Blue, green, then red, from left to right.
+	HorizontalBgr,
+	/// Red, green, then blue, from top to bottom.
+	VerticalRgb,
+	/// Blue, green, then red, from top to bottom.
+	VerticalBgr,
+
+	/// The monitor has no subpixels.
+	#[doc(alias("None"))]
+	NoSubpixels,
+}
+
+derive_xrb! {
+	/// A display mode: a resolution and its timings.
+	///
+	/// The name of a mode is not included in the `ModeInfo` itself: it is
+	/// stored in the [`GetScreenResources` reply] with the names of every other
+	/// mode, and `name_len` is its length.
+	///
+	/// [`GetScreenResources` reply]: reply::GetScreenResources
+	#[derive(Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct ModeInfo {
+		/// The [`Mode` ID][mode] of this mode.
+		///
+		/// [mode]: Mode
+		pub id: Mode,
+
+		/// The width of the mode.
+		pub width: Px<u16>,
+		/// The height of the mode.
+		pub height: Px<u16>,
+
+		/// The pixel clock of the mode, in hertz.
+		pub dot_clock: u32,
+
+		/// The pixel at which the horizontal sync pulse starts.
+		#[doc(alias("hsync_start"))]
+		pub horizontal_sync_start: u16,
+		/// The pixel at which the horizontal sync pulse ends.
+		#[doc(alias("hsync_end"))]
+		pub horizontal_sync_end: u16,
+		/// The total number of pixels in each scanline, including blanking.
+		#[doc(alias("htotal"))]
+		pub horizontal_total: u16,
+		/// The horizontal skew, if [`ModeFlags::HSKEW_PRESENT`] is set.
+		#[doc(alias("hskew"))]
+		pub horizontal_skew: u16,
+
+		/// The scanline at which the vertical sync pulse starts.
+		#[doc(alias("vsync_start"))]
+		pub vertical_sync_start: u16,
+		/// The scanline at which the vertical sync pulse ends.
+		#[doc(alias("vsync_end"))]
+		pub vertical_sync_end: u16,
+		/// The total number of scanlines, including blanking.
+		#[doc(alias("vtotal"))]
+		pub vertical_total: u16,
+
+		/// The length of this mode's name.
+		pub name_len: u16,
+		/// The sync and clock options of this mode.
+		#[doc(alias("mode_flags"))]
+		pub flags: ModeFlags,
+	}
+
+	/// An [event] generated when the configuration of a [screen] changes.
+	///
+	/// The code of this [event] is the RandR extension's first event code:
+	/// see [`ExtensionCodes`] for reading and writing it.
+	///
+	/// # Recipients
+	/// This [event] is reported to clients selecting
+	/// [`NotifyMask::SCREEN_CHANGE`] on a [window] with a
+	/// [`SelectInput` request].
+	///
+	/// [event]: Event
+	/// [screen]: crate::visual::Screen
+	/// [window]: Window
+	/// [`ExtensionCodes`]: crate::message::ExtensionCodes
+	/// [`SelectInput` request]: request::SelectInput
+	#[doc(alias("RRScreenChangeNotify"))]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[derivative(Hash, PartialEq, Eq)]
+	pub struct ScreenChangeNotify: Event(0) {
+		/// The [sequence number] associated with the last [request] related
+		/// to this [event] that was received before this [event] was generated.
+		///
+		/// [sequence number]: Event::sequence
+		/// [request]: crate::message::Request
+		/// [event]: Event
+		#[sequence]
+		#[derivative(PartialEq = "ignore", Hash = "ignore")]
+		pub sequence: u16,
+
+		/// The new rotation and reflection of the [screen].
+		///
+		/// [screen]: crate::visual::Screen
+		#[metabyte]
+		pub rotation: Rotation,
+
+		/// The time at which the [screen] was last changed.
+		///
+		/// [screen]: crate::visual::Screen
+		pub timestamp: Timestamp,
+		/// The time at which the configuration of the [screen] was last
+		/// changed.
+		///
+		/// [screen]: crate::visual::Screen
+		pub config_timestamp: Timestamp,
+
+		/// The root [window] of the [screen].
+		///
+		/// [window]: Window
+		/// [screen]: crate::visual::Screen
+		pub root: Window,
+		/// The [window] on which [`NotifyMask::SCREEN_CHANGE`] was selected.
+		///
+		/// [window]: Window
+		#[doc(alias("request_window"))]
+		pub window: Window,
+
+		/// The index of the new size of the [screen].
+		///
+		/// [screen]: crate::visual::Screen
+		#[doc(alias("size_id"))]
+		pub size_index: u16,
+		// `subpixel_order` is a `u16`, but its high byte is always zero.
+		[_; 1],
+		/// The subpixel order of the [screen].
+		///
+		/// [screen]: crate::visual::Screen
+		pub subpixel_order: SubpixelOrder,
+
+		/// The new width of the [screen].
+		///
+		/// [screen]: crate::visual::Screen
+		pub width: Px<u16>,
+		/// The new height of the [screen].
+		///
+		/// [screen]: crate::visual::Screen
+		pub height: Px<u16>,
+		/// The new width of the [screen], in millimeters.
+		///
+		/// [screen]: crate::visual::Screen
+		pub width_mm: u16,
+		/// The new height of the [screen], in millimeters.
+		///
+		/// [screen]: crate::visual::Screen
+		pub height_mm: u16,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		message::{ExtensionCodes, Flagged},
+		Char8,
+		Rectangle,
+		String8,
+	};
+	use xrbk::{Readable, Writable, X11Size};
+
+	fn mode_info_bytes(bytes: &mut Vec<u8>, id: u32, fields: [u16; 9], dot_clock: u32, flags: u32) {
+		let [width, height, hsync_start, hsync_end, htotal, vsync_start, vsync_end, vtotal, name_len] =
+			fields;
+
+		bytes.extend(id.to_be_bytes());
+		bytes.extend(width.to_be_bytes());
+		bytes.extend(height.to_be_bytes());
+		bytes.extend(dot_clock.to_be_bytes());
+		bytes.extend(hsync_start.to_be_bytes());
+		bytes.extend(hsync_end.to_be_bytes());
+		bytes.extend(htotal.to_be_bytes());
+		// hskew
+		bytes.extend(0u16.to_be_bytes());
+		bytes.extend(vsync_start.to_be_bytes());
+		bytes.extend(vsync_end.to_be_bytes());
+		bytes.extend(vtotal.to_be_bytes());
+		bytes.extend(name_len.to_be_bytes());
+		bytes.extend(flags.to_be_bytes());
+	}
+
+	/// A `GetScreenResources` reply for a screen with two CRTCs driving two
+	/// outputs, and two modes named `1920x1080` and `1024x768`.
+	fn two_output_screen_resources() -> Vec<u8> {
+		let mut bytes = vec![1, 0];
+
+		// Sequence number.
+		bytes.extend(5u16.to_be_bytes());
+		// Length: (8 + 8 + 64 + 17 + 3) / 4.
+		bytes.extend(25u32.to_be_bytes());
+
+		// Timestamp and config timestamp.
+		bytes.extend(0x0000_1000u32.to_be_bytes());
+		bytes.extend(0x0000_0900u32.to_be_bytes());
+
+		// Numbers of CRTCs, outputs, and modes, and the length of the names.
+		for len in [2u16, 2, 2, 17] {
+			bytes.extend(len.to_be_bytes());
+		}
+		bytes.extend([0; 8]);
+
+		for crtc in [0x3fu32, 0x40] {
+			bytes.extend(crtc.to_be_bytes());
+		}
+		for output in [0x41u32, 0x42] {
+			bytes.extend(output.to_be_bytes());
+		}
+
+		#[rustfmt::skip]
+		mode_info_bytes(
+			&mut bytes, 0x4a,
+			[1920, 1080, 2008, 2052, 2200, 1084, 1089, 1125, 9],
+			148_500_000, 0x05,
+		);
+		#[rustfmt::skip]
+		mode_info_bytes(
+			&mut bytes, 0x4b,
+			[1024, 768, 1048, 1184, 1344, 771, 777, 806, 8],
+			65_000_000, 0x0a,
+		);
+
+		bytes.extend(b"1920x10801024x768");
+		bytes.extend([0; 3]);
+
+		bytes
+	}
+
+	fn string8(string: &[u8]) -> String8 {
+		String8::from(string.iter().copied().map(Char8::new).collect::<Vec<_>>())
+	}
+
+	#[test]
+	fn test_get_screen_resources_fixture() {
+		let bytes = two_output_screen_resources();
+		assert_eq!(bytes.len(), 32 + 25 * 4);
+
+		// The first byte, which identifies the message as a reply, is read
+		// before the reply itself.
+		let reply = reply::GetScreenResources::read_from(&mut &bytes[1..]).unwrap();
+
+		assert_eq!(reply.sequence, 5);
+		assert_eq!(reply.timestamp, Timestamp::new(0x1000));
+		assert_eq!(reply.config_timestamp, Timestamp::new(0x0900));
+		assert_eq!(reply.crtcs, [Crtc::new(0x3f), Crtc::new(0x40)]);
+		assert_eq!(reply.outputs, [Output::new(0x41), Output::new(0x42)]);
+
+		assert_eq!(reply.modes.len(), 2);
+		assert_eq!(reply.modes[0].id, Mode::new(0x4a));
+		assert_eq!(reply.modes[0].width, Px(1920));
+		assert_eq!(reply.modes[0].dot_clock, 148_500_000);
+		assert_eq!(
+			reply.modes[0].flags,
+			ModeFlags::HSYNC_POSITIVE | ModeFlags::VSYNC_POSITIVE,
+		);
+		assert_eq!(reply.modes[1].id, Mode::new(0x4b));
+		assert_eq!(reply.modes[1].vertical_total, 806);
+		assert_eq!(
+			reply.modes[1].flags,
+			ModeFlags::HSYNC_NEGATIVE | ModeFlags::VSYNC_NEGATIVE,
+		);
+
+		let names: Vec<_> = reply
+			.mode_names()
+			.map(|(mode, name)| (mode.id, name.to_vec()))
+			.collect();
+		assert_eq!(
+			names,
+			[
+				(Mode::new(0x4a), string8(b"1920x1080").as_slice().to_vec()),
+				(Mode::new(0x4b), string8(b"1024x768").as_slice().to_vec()),
+			],
+		);
+
+		let mut written = vec![];
+		reply.write_to(&mut written).unwrap();
+
+		assert_eq!(written, bytes);
+		assert_eq!(reply.x11_size(), bytes.len());
+	}
+
+	#[test]
+	fn test_mode_names_stops_at_end_of_names() {
+		let bytes = two_output_screen_resources();
+		let mut reply = reply::GetScreenResources::read_from(&mut &bytes[1..]).unwrap();
+
+		reply.names = string8(b"1920x1080");
+
+		assert_eq!(reply.mode_names().count(), 1);
+	}
+
+	#[test]
+	fn test_get_crtc_info_round_trip() {
+		let reply = reply::GetCrtcInfo {
+			sequence: 6,
+			status: ConfigStatus::Success,
+			timestamp: Timestamp::new(0x1000),
+			geometry: Rectangle::new(Px(1920), Px(0), Px(1024), Px(768)),
+			mode: Some(Mode::new(0x4b)),
+			rotation: Rotation::ROTATE_90,
+			rotations: Rotation::ROTATE_0 | Rotation::ROTATE_90 | Rotation::REFLECT_X,
+			outputs: vec![Output::new(0x42)],
+			possible_outputs: vec![Output::new(0x41), Output::new(0x42)],
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32 + 12);
+		assert_eq!(reply.x11_size(), bytes.len());
+		// `rotation` and `rotations` are written as `u16`s.
+		assert_eq!(bytes[24..28], [0, 0x02, 0, 0x13]);
+
+		assert_eq!(
+			reply::GetCrtcInfo::read_from(&mut &bytes[1..]).unwrap(),
+			reply,
+		);
+	}
+
+	#[test]
+	fn test_get_output_info_round_trip() {
+		let reply = reply::GetOutputInfo {
+			sequence: 7,
+			status: ConfigStatus::Success,
+			timestamp: Timestamp::new(0x1000),
+			crtc: None,
+			width_mm: 0,
+			height_mm: 0,
+			connection: OutputConnection::Disconnected,
+			subpixel_order: SubpixelOrder::Unknown,
+			crtcs: vec![Crtc::new(0x3f), Crtc::new(0x40)],
+			modes: vec![],
+			preferred_modes_count: 0,
+			clones: vec![],
+			name: string8(b"DP-1"),
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 36 + 8 + 4);
+		assert_eq!(reply.x11_size(), bytes.len());
+		assert_eq!(
+			reply::GetOutputInfo::read_from(&mut &bytes[1..]).unwrap(),
+			reply,
+		);
+	}
+
+	#[test]
+	fn test_request_layouts() {
+		let codes = ExtensionCodes {
+			major_opcode: 140,
+			first_event: 89,
+			first_error: 147,
+		};
+
+		let query_version = request::QueryVersion {
+			major_version: 1,
+			minor_version: 2,
+		};
+		let mut bytes = vec![];
+		codes.write_request(&query_version, &mut bytes).unwrap();
+
+		assert_eq!(bytes, [140, 0, 0, 3, 0, 0, 0, 1, 0, 0, 0, 2]);
+		assert_eq!(
+			request::QueryVersion::read_from(&mut &bytes[2..]).unwrap(),
+			query_version,
+		);
+
+		let get_screen_resources = request::GetScreenResources {
+			target: Window::new(0x0000_0500),
+		};
+		let mut bytes = vec![];
+		codes
+			.write_request(&get_screen_resources, &mut bytes)
+			.unwrap();
+
+		assert_eq!(bytes, [140, 8, 0, 2, 0x00, 0x00, 0x05, 0x00]);
+
+		let get_output_info = request::GetOutputInfo {
+			output: Output::new(0x41),
+			config_timestamp: Timestamp::new(0x0900),
+		};
+		let mut bytes = vec![];
+		codes.write_request(&get_output_info, &mut bytes).unwrap();
+
+		#[rustfmt::skip]
+		assert_eq!(bytes, [
+			140, 9, 0, 3,
+			0x00, 0x00, 0x00, 0x41,
+			0x00, 0x00, 0x09, 0x00,
+		]);
+		assert_eq!(
+			request::GetOutputInfo::read_from(&mut &bytes[2..]).unwrap(),
+			get_output_info,
+		);
+
+		let get_crtc_info = request::GetCrtcInfo {
+			crtc: Crtc::new(0x3f),
+			config_timestamp: Timestamp::new(0x0900),
+		};
+		let mut bytes = vec![];
+		codes.write_request(&get_crtc_info, &mut bytes).unwrap();
+
+		assert_eq!(bytes[..4], [140, 20, 0, 3]);
+		assert_eq!(
+			request::GetCrtcInfo::read_from(&mut &bytes[2..]).unwrap(),
+			get_crtc_info,
+		);
+	}
+
+	#[test]
+	fn test_screen_change_notify_code_is_offset() {
+		let codes = ExtensionCodes {
+			major_opcode: 140,
+			first_event: 89,
+			first_error: 147,
+		};
+
+		let flagged = Flagged {
+			event: ScreenChangeNotify {
+				sequence: 8,
+				rotation: Rotation::ROTATE_0,
+				timestamp: Timestamp::new(0x1000),
+				config_timestamp: Timestamp::new(0x0900),
+				root: Window::new(0x0000_0500),
+				window: Window::new(0x0000_0500),
+				size_index: 0,
+				subpixel_order: SubpixelOrder::HorizontalRgb,
+				width: Px(2944),
+				height: Px(1080),
+				width_mm: 778,
+				height_mm: 285,
+			},
+			send_event: false,
+		};
+
+		let mut bytes = vec![];
+		codes.write_event(&flagged, &mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32);
+		assert_eq!(bytes[..2], [89, 0x01]);
+		// `subpixel_order` is written as a `u16`.
+		assert_eq!(bytes[22..24], [0, 1]);
+
+		assert_eq!(codes.read_event(&mut &bytes[..]).unwrap(), flagged);
+	}
+}
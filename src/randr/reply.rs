@@ -0,0 +1,354 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [Replies] defined in the [RandR extension].
+//!
+//! [Replies] are messages sent from the X server to an X client in response to
+//! a [request].
+//!
+//! [Replies]: Reply
+//! [request]: crate::message::Request
+//! [RandR extension]: super
+
+extern crate self as xrb;
+
+use derivative::Derivative;
+
+use xrbk::pad;
+use xrbk_macro::derive_xrb;
+
+use crate::{
+	message::Reply,
+	randr::{
+		request,
+		ConfigStatus,
+		Crtc,
+		Mode,
+		ModeInfo,
+		Output,
+		OutputConnection,
+		Rotation,
+		SubpixelOrder,
+	},
+	Char8,
+	Rectangle,
+	String8,
+	Timestamp,
+};
+
+derive_xrb! {
+	/// The [reply] to a [`QueryVersion` request].
+	///
+	/// [reply]: Reply
+	///
+	/// [`QueryVersion` request]: request::QueryVersion
+	#[doc(alias("RRQueryVersion"))]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[derivative(Hash, PartialEq, Eq)]
+	pub struct QueryVersion: Reply for request::QueryVersion {
+		/// The sequence number identifying the [request] that generated this
+		/// [reply].
+		///
+		/// See [`Reply::sequence`] for more information.
+		///
+		/// [request]: crate::message::Request
+		/// [reply]: Reply
+		///
+		/// [`Reply::sequence`]: Reply::sequence
+		#[sequence]
+		#[derivative(Hash = "ignore", PartialEq = "ignore")]
+		pub sequence: u16,
+
+		/// The major version of the RandR extension supported by the X server.
+		pub major_version: u32,
+		/// The minor version of the RandR extension supported by the X server.
+		pub minor_version: u32,
+		[_; ..],
+	}
+
+	/// The [reply] to a [`GetScreenResources` request].
+	///
+	/// [reply]: Reply
+	///
+	/// [`GetScreenResources` request]: request::GetScreenResources
+	#[doc(alias("RRGetScreenResources"))]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[derivative(Hash, PartialEq, Eq)]
+	pub struct GetScreenResources: Reply for request::GetScreenResources {
+		/// The sequence number identifying the [request] that generated this
+		/// [reply].
+		///
+		/// See [`Reply::sequence`] for more information.
+		///
+		/// [request]: crate::message::Request
+		/// [reply]: Reply
+		///
+		/// [`Reply::sequence`]: Reply::sequence
+		#[sequence]
+		#[derivative(Hash = "ignore", PartialEq = "ignore")]
+		pub sequence: u16,
+
+		/// The time at which the [screen] was last changed.
+		///
+		/// [screen]: crate::visual::Screen
+		pub timestamp: Timestamp,
+		/// The time at which the configuration of the [screen] was last
+		/// changed.
+		///
+		/// This is given in [`GetOutputInfo`] and [`GetCrtcInfo` requests].
+		///
+		/// [screen]: crate::visual::Screen
+		///
+		/// [`GetOutputInfo`]: request::GetOutputInfo
+		/// [`GetCrtcInfo` requests]: request::GetCrtcInfo
+		pub config_timestamp: Timestamp,
+
+		// The length of `crtcs`.
+		#[allow(clippy::cast_possible_truncation)]
+		let crtcs_len: u16 = crtcs => crtcs.len() as u16,
+		// The length of `outputs`.
+		#[allow(clippy::cast_possible_truncation)]
+		let outputs_len: u16 = outputs => outputs.len() as u16,
+		// The length of `modes`.
+		#[allow(clippy::cast_possible_truncation)]
+		let modes_len: u16 = modes => modes.len() as u16,
+		// The length of `names`.
+		#[allow(clippy::cast_possible_truncation)]
+		let names_len: u16 = names => names.len() as u16,
+		[_; 8],
+
+		/// The [CRTCs] of the [screen].
+		///
+		/// [CRTCs]: Crtc
+		/// [screen]: crate::visual::Screen
+		#[context(crtcs_len => usize::from(*crtcs_len))]
+		pub crtcs: Vec<Crtc>,
+		/// The [outputs] of the [screen].
+		///
+		/// [outputs]: Output
+		/// [screen]: crate::visual::Screen
+		#[context(outputs_len => usize::from(*outputs_len))]
+		pub outputs: Vec<Output>,
+		/// The [modes] of the [screen].
+		///
+		/// [modes]: ModeInfo
+		/// [screen]: crate::visual::Screen
+		#[context(modes_len => usize::from(*modes_len))]
+		pub modes: Vec<ModeInfo>,
+
+		/// The names of every one of the `modes`, one after the other.
+		///
+		/// The length of each name is given by the [`name_len`] of its mode.
+		/// See [`mode_names`] for iterating over the `modes` with their
+		/// names.
+		///
+		/// [`name_len`]: ModeInfo::name_len
+		/// [`mode_names`]: GetScreenResources::mode_names
+		#[context(names_len => usize::from(*names_len))]
+		pub names: String8,
+		[_; names => pad(names)],
+	}
+}
+
+impl GetScreenResources {
+	/// Returns an iterator over the `modes` with their names.
+	///
+	/// Each mode's name is taken from `names`, following the names of the
+	/// modes before it. The iterator stops early if a mode's [`name_len`]
+	/// runs past the end of `names`.
+	///
+	/// [`name_len`]: ModeInfo::name_len
+	pub fn mode_names(&self) -> impl Iterator<Item = (&ModeInfo, &[Char8])> {
+		let names = self.names.as_slice();
+
+		self.modes.iter().scan(0, move |start, mode| {
+			let end = *start + usize::from(mode.name_len);
+			let name = names.get(*start..end)?;
+			*start = end;
+
+			Some((mode, name))
+		})
+	}
+}
+
+derive_xrb! {
+	/// The [reply] to a [`GetOutputInfo` request].
+	///
+	/// [reply]: Reply
+	///
+	/// [`GetOutputInfo` request]: request::GetOutputInfo
+	#[doc(alias("RRGetOutputInfo"))]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[derivative(Hash, PartialEq, Eq)]
+	pub struct GetOutputInfo: Reply for request::GetOutputInfo {
+		/// The sequence number identifying the [request] that generated this
+		/// [reply].
+		///
+		/// See [`Reply::sequence`] for more information.
+		///
+		/// [request]: crate::message::Request
+		/// [reply]: Reply
+		///
+		/// [`Reply::sequence`]: Reply::sequence
+		#[sequence]
+		#[derivative(Hash = "ignore", PartialEq = "ignore")]
+		pub sequence: u16,
+
+		/// Whether the [request] succeeded.
+		///
+		/// [request]: crate::message::Request
+		#[metabyte]
+		pub status: ConfigStatus,
+
+		/// The time at which the [output] was last changed.
+		///
+		/// [output]: crate::randr::Output
+		pub timestamp: Timestamp,
+		/// The [CRTC] currently driving the [output], if any.
+		///
+		/// [CRTC]: Crtc
+		/// [output]: crate::randr::Output
+		pub crtc: Option<Crtc>,
+
+		/// The physical width of the connected monitor, in millimeters.
+		pub width_mm: u32,
+		/// The physical height of the connected monitor, in millimeters.
+		pub height_mm: u32,
+
+		/// Whether a monitor is connected to the [output].
+		///
+		/// [output]: crate::randr::Output
+		pub connection: OutputConnection,
+		/// The subpixel order of the connected monitor.
+		pub subpixel_order: SubpixelOrder,
+
+		// The length of `crtcs`.
+		#[allow(clippy::cast_possible_truncation)]
+		let crtcs_len: u16 = crtcs => crtcs.len() as u16,
+		// The length of `modes`.
+		#[allow(clippy::cast_possible_truncation)]
+		let modes_len: u16 = modes => modes.len() as u16,
+		/// The number of `modes`, from the start, which are preferred by the
+		/// connected monitor.
+		#[doc(alias("num_preferred"))]
+		pub preferred_modes_count: u16,
+		// The length of `clones`.
+		#[allow(clippy::cast_possible_truncation)]
+		let clones_len: u16 = clones => clones.len() as u16,
+		// The length of `name`.
+		#[allow(clippy::cast_possible_truncation)]
+		let name_len: u16 = name => name.len() as u16,
+
+		/// The [CRTCs] which can drive the [output].
+		///
+		/// [CRTCs]: Crtc
+		/// [output]: crate::randr::Output
+		#[context(crtcs_len => usize::from(*crtcs_len))]
+		pub crtcs: Vec<Crtc>,
+		/// The [modes] supported by the [output].
+		///
+		/// [modes]: Mode
+		/// [output]: crate::randr::Output
+		#[context(modes_len => usize::from(*modes_len))]
+		pub modes: Vec<Mode>,
+		/// The [outputs] which can share a [CRTC] with this [output].
+		///
+		/// [CRTC]: Crtc
+		/// [output]: crate::randr::Output
+		/// [outputs]: crate::randr::Output
+		#[context(clones_len => usize::from(*clones_len))]
+		pub clones: Vec<Output>,
+
+		/// The name of the [output], such as `HDMI-1`.
+		///
+		/// [output]: crate::randr::Output
+		#[context(name_len => usize::from(*name_len))]
+		pub name: String8,
+		[_; name => pad(name)],
+	}
+
+	/// The [reply] to a [`GetCrtcInfo` request].
+	///
+	/// [reply]: Reply
+	///
+	/// [`GetCrtcInfo` request]: request::GetCrtcInfo
+	#[doc(alias("RRGetCrtcInfo"))]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[derivative(Hash, PartialEq, Eq)]
+	pub struct GetCrtcInfo: Reply for request::GetCrtcInfo {
+		/// The sequence number identifying the [request] that generated this
+		/// [reply].
+		///
+		/// See [`Reply::sequence`] for more information.
+		///
+		/// [request]: crate::message::Request
+		/// [reply]: Reply
+		///
+		/// [`Reply::sequence`]: Reply::sequence
+		#[sequence]
+		#[derivative(Hash = "ignore", PartialEq = "ignore")]
+		pub sequence: u16,
+
+		/// Whether the [request] succeeded.
+		///
+		/// [request]: crate::message::Request
+		#[metabyte]
+		pub status: ConfigStatus,
+
+		/// The time at which the [CRTC] was last changed.
+		///
+		/// [CRTC]: Crtc
+		pub timestamp: Timestamp,
+		/// The area of the [screen] scanned out by the [CRTC].
+		///
+		/// [CRTC]: Crtc
+		/// [screen]: crate::visual::Screen
+		#[doc(alias("x", "y", "width", "height"))]
+		pub geometry: Rectangle,
+
+		/// The [mode] of the [CRTC], if it is enabled.
+		///
+		/// [mode]: Mode
+		/// [CRTC]: Crtc
+		pub mode: Option<Mode>,
+
+		// `rotation` is a `u16`, but its high byte is always zero.
+		[_; 1],
+		/// The current rotation and reflection of the [CRTC].
+		///
+		/// [CRTC]: Crtc
+		pub rotation: Rotation,
+		// `rotations` is a `u16`, but its high byte is always zero.
+		[_; 1],
+		/// The rotations and reflections supported by the [CRTC].
+		///
+		/// [CRTC]: Crtc
+		pub rotations: Rotation,
+
+		// The length of `outputs`.
+		#[allow(clippy::cast_possible_truncation)]
+		let outputs_len: u16 = outputs => outputs.len() as u16,
+		// The length of `possible_outputs`.
+		#[allow(clippy::cast_possible_truncation)]
+		let possible_outputs_len: u16 = possible_outputs => possible_outputs.len() as u16,
+
+		/// The [outputs] currently driven by the [CRTC].
+		///
+		/// [outputs]: Output
+		/// [CRTC]: Crtc
+		#[context(outputs_len => usize::from(*outputs_len))]
+		pub outputs: Vec<Output>,
+		/// The [outputs] which the [CRTC] can drive.
+		///
+		/// [outputs]: Output
+		/// [CRTC]: Crtc
+		#[doc(alias("possible"))]
+		#[context(possible_outputs_len => usize::from(*possible_outputs_len))]
+		pub possible_outputs: Vec<Output>,
+	}
+}
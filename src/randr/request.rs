@@ -0,0 +1,228 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [Requests] defined in the [RandR extension].
+//!
+//! [Requests] are messages sent from an X client to the X server.
+//!
+//! The [major opcode] of these [requests] is assigned by the X server: they
+//! must be written with [`ExtensionCodes::write_request`].
+//!
+//! [Requests]: Request
+//! [requests]: Request
+//! [RandR extension]: super
+//! [major opcode]: Request::MAJOR_OPCODE
+//! [`ExtensionCodes::write_request`]: crate::message::ExtensionCodes::write_request
+
+extern crate self as xrb;
+
+use xrbk_macro::derive_xrb;
+
+use crate::{
+	message::{Request, UNASSIGNED_MAJOR_OPCODE},
+	randr::{reply, Crtc, NotifyMask, Output},
+	x11::error,
+	Timestamp,
+	Window,
+};
+
+macro_rules! request_error {
+	(
+		$(#[$meta:meta])*
+		$vis:vis enum $Name:ident for $Request:ty {
+			$($($Error:ident),+$(,)?)?
+		}
+	) => {
+		#[doc = concat!(
+			"An [error](crate::message::Error) generated because of a failed [`",
+			stringify!($Request),
+			"` request](",
+			stringify!($Request),
+			")."
+		)]
+		#[doc = ""]
+		$(#[$meta])*
+		$vis enum $Name {
+			$($(
+				#[doc = concat!(
+					"A [`",
+					stringify!($Error),
+					"` error](error::",
+					stringify!($Error),
+					")."
+				)]
+				$Error(error::$Error)
+			),+)?
+		}
+
+		impl TryFrom<crate::x11::AnyError> for $Name {
+			type Error = crate::x11::AnyError;
+
+			fn try_from(error: crate::x11::AnyError) -> Result<Self, Self::Error> {
+				match error {
+					$($(crate::x11::AnyError::$Error(error) => Ok(Self::$Error(error)),)+)?
+
+					other => Err(other),
+				}
+			}
+		}
+	};
+}
+
+request_error! {
+	pub enum SelectInputError for SelectInput {
+		Value,
+		Window,
+	}
+}
+
+request_error! {
+	pub enum GetScreenResourcesError for GetScreenResources {
+		Window,
+	}
+}
+
+// FIXME: the RandR extension also defines `Output`, `Crtc`, and `Mode` errors,
+//        generated when an ID does not refer to a defined output, CRTC, or
+//        mode, which are not yet represented in these request errors.
+
+derive_xrb! {
+	/// A [request] that returns the version of the RandR extension supported
+	/// by the X server.
+	///
+	/// # Replies
+	/// This [request] generates a [`QueryVersion` reply].
+	///
+	/// [request]: Request
+	///
+	/// [`QueryVersion` reply]: reply::QueryVersion
+	#[doc(alias("RRQueryVersion"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct QueryVersion: Request(UNASSIGNED_MAJOR_OPCODE, 0) -> reply::QueryVersion {
+		/// The major version of the RandR extension supported by this client.
+		pub major_version: u32,
+		/// The minor version of the RandR extension supported by this client.
+		pub minor_version: u32,
+	}
+
+	/// A [request] that selects which RandR [events] are reported to this
+	/// client for the given [window].
+	///
+	/// # Errors
+	/// A [`Window` error] is generated if `target` does not refer to a defined
+	/// [window].
+	///
+	/// A [`Value` error] is generated if `mask` contains an unrecognized
+	/// flag.
+	///
+	/// [events]: crate::message::Event
+	/// [window]: Window
+	/// [request]: Request
+	///
+	/// [`Window` error]: error::Window
+	/// [`Value` error]: error::Value
+	#[doc(alias("RRSelectInput"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct SelectInput: Request(UNASSIGNED_MAJOR_OPCODE, 4, SelectInputError) {
+		/// The [window] for which the [events] in the `mask` are selected.
+		///
+		/// [window]: Window
+		/// [events]: crate::message::Event
+		#[doc(alias("window"))]
+		pub target: Window,
+
+		/// The [events] which are selected.
+		///
+		/// [events]: crate::message::Event
+		#[doc(alias("enable"))]
+		pub mask: NotifyMask,
+		[_; ..],
+	}
+
+	/// A [request] that returns the [CRTCs], [outputs], and [modes] of the
+	/// [screen] of the given [window].
+	///
+	/// # Replies
+	/// This [request] generates a [`GetScreenResources` reply].
+	///
+	/// # Errors
+	/// A [`Window` error] is generated if `target` does not refer to a defined
+	/// [window].
+	///
+	/// [CRTCs]: Crtc
+	/// [outputs]: Output
+	/// [modes]: crate::randr::ModeInfo
+	/// [screen]: crate::visual::Screen
+	/// [window]: Window
+	/// [request]: Request
+	///
+	/// [`GetScreenResources` reply]: reply::GetScreenResources
+	///
+	/// [`Window` error]: error::Window
+	#[doc(alias("RRGetScreenResources"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct GetScreenResources: Request(
+		UNASSIGNED_MAJOR_OPCODE,
+		8,
+		GetScreenResourcesError,
+	) -> reply::GetScreenResources {
+		/// The [window] whose [screen]'s resources are returned.
+		///
+		/// [window]: Window
+		/// [screen]: crate::visual::Screen
+		#[doc(alias("window"))]
+		pub target: Window,
+	}
+
+	/// A [request] that returns information about the given [output].
+	///
+	/// # Replies
+	/// This [request] generates a [`GetOutputInfo` reply].
+	///
+	/// [output]: Output
+	/// [request]: Request
+	///
+	/// [`GetOutputInfo` reply]: reply::GetOutputInfo
+	#[doc(alias("RRGetOutputInfo"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct GetOutputInfo: Request(UNASSIGNED_MAJOR_OPCODE, 9) -> reply::GetOutputInfo {
+		/// The [output] for which information is returned.
+		///
+		/// [output]: Output
+		pub output: Output,
+		/// The `config_timestamp` returned in the
+		/// [`GetScreenResources` reply].
+		///
+		/// [`GetScreenResources` reply]: reply::GetScreenResources
+		pub config_timestamp: Timestamp,
+	}
+
+	/// A [request] that returns information about the given [CRTC].
+	///
+	/// # Replies
+	/// This [request] generates a [`GetCrtcInfo` reply].
+	///
+	/// [CRTC]: Crtc
+	/// [request]: Request
+	///
+	/// [`GetCrtcInfo` reply]: reply::GetCrtcInfo
+	#[doc(alias("RRGetCrtcInfo"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct GetCrtcInfo: Request(UNASSIGNED_MAJOR_OPCODE, 20) -> reply::GetCrtcInfo {
+		/// The [CRTC] for which information is returned.
+		///
+		/// [CRTC]: Crtc
+		pub crtc: Crtc,
+		/// The `config_timestamp` returned in the
+		/// [`GetScreenResources` reply].
+		///
+		/// [`GetScreenResources` reply]: reply::GetScreenResources
+		pub config_timestamp: Timestamp,
+	}
+}
@@ -0,0 +1,410 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Messages defined in the [MIT-SHM extension]: [requests], [replies], and
+//! [events].
+//!
+//! The MIT-SHM extension allows images to be transferred through memory shared
+//! between an X client and the X server, rather than being sent over the
+//! connection. Creating and attaching the shared memory itself is outside the
+//! scope of XRB.
+//!
+//! [MIT-SHM extension]: https://www.x.org/releases/X11R7.7/doc/xextproto/shm.html
+//! [requests]: request
+//! [replies]: reply
+//! [events]: Completion
+
+extern crate self as xrb;
+
+use derivative::Derivative;
+use derive_more::{From, Into};
+
+use xrbk_macro::{derive_xrb, new, unwrap, ConstantX11Size, Readable, Wrap, Writable, X11Size};
+
+use crate::{
+	message::{Event, Extension},
+	Drawable,
+};
+
+pub mod reply;
+pub mod request;
+
+/// The MIT-SHM extension.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Shm;
+
+impl Extension for Shm {
+	const NAME: &'static str = "MIT-SHM";
+}
+
+/// A resource ID referring to a shared memory segment attached with an
+/// [`Attach` request].
+///
+/// [`Attach` request]: request::Attach
+#[doc(alias("Seg", "ShmSeg"))]
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	From,
+	Into,
+	// `new` and `unwrap` const fns
+	new,
+	unwrap,
+	// XRBK traits
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	Wrap,
+)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
+pub struct ShmSegment(u32);
+
+derive_xrb! {
+	/// An [event] generated when the X server has finished reading from the
+	/// shared memory segment used in a [`PlaceImage` request].
+	///
+	/// The code of this [event] is the MIT-SHM extension's first event code:
+	/// see [`ExtensionCodes`] for reading and writing it.
+	///
+	/// # Recipients
+	/// This [event] is reported to the client which sent a
+	/// [`PlaceImage` request] with `send_event` set to `true`.
+	///
+	/// [event]: Event
+	/// [`PlaceImage` request]: request::PlaceImage
+	/// [`ExtensionCodes`]: crate::message::ExtensionCodes
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[derivative(Hash, PartialEq, Eq)]
+	pub struct Completion: Event(0) {
+		/// The [sequence number] associated with the last [request] related
+		/// to this [event] that was received before this [event] was generated.
+		///
+		/// [sequence number]: Event::sequence
+		/// [request]: crate::message::Request
+		/// [event]: Event
+		#[sequence]
+		#[derivative(PartialEq = "ignore", Hash = "ignore")]
+		pub sequence: u16,
+
+		/// The `target` of the [`PlaceImage` request].
+		///
+		/// [`PlaceImage` request]: request::PlaceImage
+		pub drawable: Drawable,
+
+		/// The [minor opcode] of the [`PlaceImage` request].
+		///
+		/// [minor opcode]: crate::message::Request::MINOR_OPCODE
+		/// [`PlaceImage` request]: request::PlaceImage
+		pub minor_opcode: u16,
+		/// The [major opcode] of the MIT-SHM extension.
+		///
+		/// [major opcode]: crate::message::Request::MAJOR_OPCODE
+		pub major_opcode: u8,
+		[_; 1],
+
+		/// The shared memory segment that was read from.
+		pub segment: ShmSegment,
+		/// The offset into the `segment` at which the image began.
+		pub offset: u32,
+		[_; ..],
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		message::{ExtensionCodes, Flagged},
+		unit::Px,
+		visual::VisualId,
+		x11::request::{CaptureImageFormat, PlaceImageFormat},
+		Coords,
+		Dimensions,
+		GraphicsContext,
+		Pixmap,
+		Rectangle,
+	};
+	use xrbk::{ReadError, ReadMode, Readable, Writable};
+
+	const CODES: ExtensionCodes = ExtensionCodes {
+		major_opcode: 130,
+		first_event: 65,
+		first_error: 128,
+	};
+
+	#[test]
+	fn test_place_image_layout() {
+		let request = request::PlaceImage {
+			target: Drawable::new(0x0040_0001),
+			graphics_context: GraphicsContext::new(0x0040_0002),
+			total_dimensions: Dimensions::new(Px(640), Px(480)),
+			source_x: Px(10),
+			source_y: Px(20),
+			source_dimensions: Dimensions::new(Px(100), Px(50)),
+			coordinates: Coords::new(Px(-5), Px(6)),
+			depth: 24,
+			format: PlaceImageFormat::Zpixmap,
+			send_event: true,
+			segment: ShmSegment::new(0x0040_0003),
+			offset: 4096,
+		};
+
+		let mut bytes = vec![];
+		CODES.write_request(&request, &mut bytes).unwrap();
+
+		#[rustfmt::skip]
+		assert_eq!(bytes, [
+			130, 3, 0, 10,
+			0x00, 0x40, 0x00, 0x01,
+			0x00, 0x40, 0x00, 0x02,
+			0x02, 0x80, 0x01, 0xe0,
+			0x00, 0x0a, 0x00, 0x14,
+			0x00, 0x64, 0x00, 0x32,
+			0xff, 0xfb, 0x00, 0x06,
+			24, 2, 1, 0,
+			0x00, 0x40, 0x00, 0x03,
+			0x00, 0x00, 0x10, 0x00,
+		]);
+
+		// The major and minor opcodes are read before the request itself.
+		assert_eq!(
+			request::PlaceImage::read_from(&mut &bytes[2..]).unwrap(),
+			request,
+		);
+	}
+
+	#[test]
+	fn test_query_version_layout() {
+		let mut bytes = vec![];
+		CODES
+			.write_request(&request::QueryVersion, &mut bytes)
+			.unwrap();
+
+		assert_eq!(bytes, [130, 0, 0, 1]);
+	}
+
+	#[test]
+	fn test_attach_and_detach_layout() {
+		let request = request::Attach {
+			segment: ShmSegment::new(0x0040_0003),
+			shmid: 0x0001_8004,
+			read_only: true,
+		};
+
+		let mut bytes = vec![];
+		CODES.write_request(&request, &mut bytes).unwrap();
+
+		#[rustfmt::skip]
+		assert_eq!(bytes, [
+			130, 1, 0, 4,
+			0x00, 0x40, 0x00, 0x03,
+			0x00, 0x01, 0x80, 0x04,
+			1, 0, 0, 0,
+		]);
+		assert_eq!(
+			request::Attach::read_from(&mut &bytes[2..]).unwrap(),
+			request
+		);
+
+		let request = request::Detach {
+			segment: ShmSegment::new(0x0040_0003),
+		};
+
+		let mut bytes = vec![];
+		CODES.write_request(&request, &mut bytes).unwrap();
+
+		assert_eq!(bytes, [130, 2, 0, 2, 0x00, 0x40, 0x00, 0x03]);
+		assert_eq!(
+			request::Detach::read_from(&mut &bytes[2..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_capture_image_layout() {
+		let request = request::CaptureImage {
+			target: Drawable::new(0x0040_0001),
+			area: Rectangle::new(Px(10), Px(20), Px(640), Px(480)),
+			plane_mask: 0xffff_ffff,
+			format: CaptureImageFormat::Zpixmap,
+			segment: ShmSegment::new(0x0040_0003),
+			offset: 4096,
+		};
+
+		let mut bytes = vec![];
+		CODES.write_request(&request, &mut bytes).unwrap();
+
+		#[rustfmt::skip]
+		assert_eq!(bytes, [
+			130, 4, 0, 8,
+			0x00, 0x40, 0x00, 0x01,
+			0x00, 0x0a, 0x00, 0x14,
+			0x02, 0x80, 0x01, 0xe0,
+			0xff, 0xff, 0xff, 0xff,
+			2, 0, 0, 0,
+			0x00, 0x40, 0x00, 0x03,
+			0x00, 0x00, 0x10, 0x00,
+		]);
+		assert_eq!(
+			request::CaptureImage::read_from(&mut &bytes[2..]).unwrap(),
+			request,
+		);
+
+		let reply = reply::CaptureImage {
+			sequence: 3,
+			depth: 24,
+			visual: Some(VisualId::new(0x21)),
+			size: 640 * 480 * 4,
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32);
+		assert_eq!(bytes[..4], [1, 24, 0, 3]);
+		assert_eq!(
+			bytes[8..16],
+			[0x00, 0x00, 0x00, 0x21, 0x00, 0x12, 0xc0, 0x00]
+		);
+		assert_eq!(
+			reply::CaptureImage::read_from(&mut &bytes[1..]).unwrap(),
+			reply,
+		);
+	}
+
+	#[test]
+	fn test_create_pixmap_layout() {
+		let request = request::CreatePixmap {
+			pixmap_id: Pixmap::new(0x0040_0004),
+			drawable: Drawable::new(0x0000_0512),
+			dimensions: Dimensions::new(Px(640), Px(480)),
+			depth: 24,
+			segment: ShmSegment::new(0x0040_0003),
+			offset: 0,
+		};
+
+		let mut bytes = vec![];
+		CODES.write_request(&request, &mut bytes).unwrap();
+
+		#[rustfmt::skip]
+		assert_eq!(bytes, [
+			130, 5, 0, 7,
+			0x00, 0x40, 0x00, 0x04,
+			0x00, 0x00, 0x05, 0x12,
+			0x02, 0x80, 0x01, 0xe0,
+			24, 0, 0, 0,
+			0x00, 0x40, 0x00, 0x03,
+			0x00, 0x00, 0x00, 0x00,
+		]);
+		assert_eq!(
+			request::CreatePixmap::read_from(&mut &bytes[2..]).unwrap(),
+			request,
+		);
+	}
+
+	#[test]
+	fn test_completion_code_is_offset() {
+		let completion = || Completion {
+			sequence: 7,
+			drawable: Drawable::new(0x0040_0001),
+			minor_opcode: 3,
+			major_opcode: CODES.major_opcode,
+			segment: ShmSegment::new(0x0040_0003),
+			offset: 0,
+		};
+		assert_eq!(CODES.event_code::<Completion>(), 65);
+
+		for send_event in [false, true] {
+			let flagged = Flagged {
+				event: completion(),
+				send_event,
+			};
+
+			let mut bytes = vec![];
+			CODES.write_event(&flagged, &mut bytes).unwrap();
+
+			assert_eq!(bytes.len(), 32);
+			assert_eq!(bytes[0], if send_event { 65 | 0x80 } else { 65 });
+			assert_eq!(CODES.event_offset(bytes[0]), Some(Completion::CODE));
+
+			assert_eq!(CODES.read_event(&mut &bytes[..]).unwrap(), flagged);
+
+			// The unoffset code is not recognized as a `Completion` event.
+			bytes[0] = Completion::CODE;
+			assert!(matches!(
+				CODES.read_event::<Completion>(&mut &bytes[..]),
+				Err(ReadError::UnrecognizedDiscriminant(_)),
+			));
+		}
+
+		assert_eq!(CODES.event_offset(64), None);
+	}
+
+	#[test]
+	fn test_sent_completion_with_non_zero_padding() {
+		let flagged = Flagged {
+			event: Completion {
+				sequence: 7,
+				drawable: Drawable::new(0x0040_0001),
+				minor_opcode: 3,
+				major_opcode: CODES.major_opcode,
+				segment: ShmSegment::new(0x0040_0003),
+				offset: 0,
+			},
+			send_event: true,
+		};
+
+		let mut bytes = vec![];
+		CODES.write_event(&flagged, &mut bytes).unwrap();
+
+		*bytes.last_mut().unwrap() = 0xa5;
+
+		assert_eq!(
+			CODES
+				.read_event_with_mode(&mut &bytes[..], ReadMode::Lenient)
+				.unwrap(),
+			flagged,
+		);
+		assert!(matches!(
+			CODES.read_event_with_mode::<Completion>(&mut &bytes[..], ReadMode::Strict),
+			Err(ReadError::NonZeroPadding(0xa5)),
+		));
+	}
+
+	#[test]
+	fn test_query_version_reply_round_trip() {
+		let reply = reply::QueryVersion {
+			sequence: 1,
+			shared_pixmaps: true,
+			major_version: 1,
+			minor_version: 2,
+			user_id: 0,
+			group_id: 0,
+			pixmap_format: 2,
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32);
+		assert_eq!(
+			reply::QueryVersion::read_from(&mut &bytes[1..]).unwrap(),
+			reply,
+		);
+	}
+}
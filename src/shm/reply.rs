@@ -0,0 +1,121 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [Replies] defined in the [MIT-SHM extension].
+//!
+//! [Replies] are messages sent from the X server to an X client in response to
+//! a [request].
+//!
+//! [Replies]: Reply
+//! [request]: crate::message::Request
+//! [MIT-SHM extension]: super
+
+extern crate self as xrb;
+
+use derivative::Derivative;
+
+use xrbk_macro::derive_xrb;
+
+use crate::{message::Reply, shm::request, visual::VisualId};
+
+derive_xrb! {
+	/// The [reply] to a [`QueryVersion` request].
+	///
+	/// [reply]: Reply
+	///
+	/// [`QueryVersion` request]: request::QueryVersion
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[derivative(Hash, PartialEq, Eq)]
+	pub struct QueryVersion: Reply for request::QueryVersion {
+		/// The sequence number identifying the [request] that generated this
+		/// [reply].
+		///
+		/// See [`Reply::sequence`] for more information.
+		///
+		/// [request]: crate::message::Request
+		/// [reply]: Reply
+		///
+		/// [`Reply::sequence`]: Reply::sequence
+		#[sequence]
+		#[derivative(Hash = "ignore", PartialEq = "ignore")]
+		pub sequence: u16,
+
+		/// Whether the X server supports [`CreatePixmap` requests].
+		///
+		/// [`CreatePixmap` requests]: request::CreatePixmap
+		#[metabyte]
+		pub shared_pixmaps: bool,
+
+		/// The major version of the MIT-SHM extension supported by the X
+		/// server.
+		pub major_version: u16,
+		/// The minor version of the MIT-SHM extension supported by the X
+		/// server.
+		pub minor_version: u16,
+
+		/// The user ID of the X server, used to check its access to shared
+		/// memory segments.
+		#[doc(alias = "uid")]
+		pub user_id: u16,
+		/// The group ID of the X server, used to check its access to shared
+		/// memory segments.
+		#[doc(alias = "gid")]
+		pub group_id: u16,
+
+		/// The image format of shared [pixmaps], if `shared_pixmaps` is `true`.
+		///
+		/// This is `1` for XY format and `2` for Z format.
+		///
+		/// [pixmaps]: crate::Pixmap
+		pub pixmap_format: u8,
+		[_; ..],
+	}
+
+	/// The [reply] to a [`CaptureImage` request].
+	///
+	/// The image itself is written to the shared memory segment given in the
+	/// [request].
+	///
+	/// [reply]: Reply
+	/// [request]: crate::message::Request
+	///
+	/// [`CaptureImage` request]: request::CaptureImage
+	#[doc(alias("GetImage", "ShmGetImage"))]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[derivative(Hash, PartialEq, Eq)]
+	pub struct CaptureImage: Reply for request::CaptureImage {
+		/// The sequence number identifying the [request] that generated this
+		/// [reply].
+		///
+		/// See [`Reply::sequence`] for more information.
+		///
+		/// [request]: crate::message::Request
+		/// [reply]: Reply
+		///
+		/// [`Reply::sequence`]: Reply::sequence
+		#[sequence]
+		#[derivative(Hash = "ignore", PartialEq = "ignore")]
+		pub sequence: u16,
+
+		/// The depth of the `target` [drawable] when it was created.
+		///
+		/// [drawable]: crate::Drawable
+		#[metabyte]
+		pub depth: u8,
+
+		/// The visual type of the `target` if it is a [window].
+		///
+		/// If the `target` is a [pixmap], this is [`None`].
+		///
+		/// [window]: crate::Window
+		/// [pixmap]: crate::Pixmap
+		pub visual: Option<VisualId>,
+		/// The size of the image written to the shared memory segment, in
+		/// bytes.
+		pub size: u32,
+		[_; ..],
+	}
+}
@@ -0,0 +1,420 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [Requests] defined in the [MIT-SHM extension].
+//!
+//! [Requests] are messages sent from an X client to the X server.
+//!
+//! The [major opcode] of these [requests] is assigned by the X server: they
+//! must be written with [`ExtensionCodes::write_request`].
+//!
+//! [Requests]: Request
+//! [requests]: Request
+//! [MIT-SHM extension]: super
+//! [major opcode]: Request::MAJOR_OPCODE
+//! [`ExtensionCodes::write_request`]: crate::message::ExtensionCodes::write_request
+
+extern crate self as xrb;
+
+use xrbk_macro::derive_xrb;
+
+use crate::{
+	message::{Request, UNASSIGNED_MAJOR_OPCODE},
+	shm::{reply, ShmSegment},
+	unit::Px,
+	x11::{
+		error,
+		request::{CaptureImageFormat, PlaceImageFormat},
+	},
+	Coords,
+	Dimensions,
+	Drawable,
+	GraphicsContext,
+	Pixmap,
+	Rectangle,
+};
+
+macro_rules! request_error {
+	(
+		$(#[$meta:meta])*
+		$vis:vis enum $Name:ident for $Request:ty {
+			$($($Error:ident),+$(,)?)?
+		}
+	) => {
+		#[doc = concat!(
+			"An [error](crate::message::Error) generated because of a failed [`",
+			stringify!($Request),
+			"` request](",
+			stringify!($Request),
+			")."
+		)]
+		#[doc = ""]
+		$(#[$meta])*
+		$vis enum $Name {
+			$($(
+				#[doc = concat!(
+					"A [`",
+					stringify!($Error),
+					"` error](error::",
+					stringify!($Error),
+					")."
+				)]
+				$Error(error::$Error)
+			),+)?
+		}
+
+		impl TryFrom<crate::x11::AnyError> for $Name {
+			type Error = crate::x11::AnyError;
+
+			fn try_from(error: crate::x11::AnyError) -> Result<Self, Self::Error> {
+				match error {
+					$($(crate::x11::AnyError::$Error(error) => Ok(Self::$Error(error)),)+)?
+
+					other => Err(other),
+				}
+			}
+		}
+	};
+}
+
+request_error! {
+	pub enum AttachError for Attach {
+		Access,
+		Value,
+	}
+}
+
+request_error! {
+	#[doc(alias("PutImageError"))]
+	pub enum PlaceImageError for PlaceImage {
+		Drawable,
+		GraphicsContext,
+		Match,
+		Value,
+	}
+}
+
+request_error! {
+	#[doc(alias("GetImageError"))]
+	pub enum CaptureImageError for CaptureImage {
+		Drawable,
+		Match,
+		Value,
+	}
+}
+
+request_error! {
+	pub enum CreatePixmapError for CreatePixmap {
+		Drawable,
+		Match,
+		ResourceIdChoice,
+		Value,
+	}
+}
+
+// FIXME: the MIT-SHM extension also defines a `Seg` error, generated when a
+//        `ShmSegment` does not refer to an attached segment, which is not yet
+//        represented in these request errors.
+
+derive_xrb! {
+	/// A [request] that returns the version of the MIT-SHM extension
+	/// supported by the X server, and whether it supports shared [pixmaps].
+	///
+	/// # Replies
+	/// This [request] generates a [`QueryVersion` reply].
+	///
+	/// [pixmaps]: Pixmap
+	/// [request]: Request
+	///
+	/// [`QueryVersion` reply]: reply::QueryVersion
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct QueryVersion: Request(UNASSIGNED_MAJOR_OPCODE, 0) -> reply::QueryVersion;
+
+	/// A [request] that attaches the shared memory segment with the given
+	/// `shmid` to the X server, assigning it the given [`ShmSegment` ID].
+	///
+	/// # Errors
+	/// An [`Access` error] is generated if the X server can't access the
+	/// shared memory segment.
+	///
+	/// A [`Value` error] is generated if `shmid` does not refer to a shared
+	/// memory segment.
+	///
+	/// [request]: Request
+	/// [`ShmSegment` ID]: ShmSegment
+	///
+	/// [`Access` error]: error::Access
+	/// [`Value` error]: error::Value
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct Attach: Request(UNASSIGNED_MAJOR_OPCODE, 1, AttachError) {
+		/// The [`ShmSegment` ID] which is to be assigned to the shared memory
+		/// segment.
+		///
+		/// [`ShmSegment` ID]: ShmSegment
+		#[doc(alias = "shmseg")]
+		pub segment: ShmSegment,
+		/// The system ID of the shared memory segment.
+		pub shmid: u32,
+
+		/// Whether the X server may only read from the shared memory segment.
+		pub read_only: bool,
+		[_; ..],
+	}
+
+	/// A [request] that detaches the given shared memory segment from the X
+	/// server.
+	///
+	/// The shared memory segment is not detached until it is no longer used
+	/// by any [pixmaps] created with [`CreatePixmap`].
+	///
+	/// [pixmaps]: Pixmap
+	/// [request]: Request
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct Detach: Request(UNASSIGNED_MAJOR_OPCODE, 2) {
+		/// The shared memory segment which is to be detached.
+		#[doc(alias = "shmseg")]
+		pub segment: ShmSegment,
+	}
+
+	/// A [request] that places an image stored in a shared memory segment on
+	/// the given [drawable].
+	///
+	/// This is the equivalent of the core [`PlaceImage` request], but the
+	/// image is read from the `segment` at the given `offset`, and only the
+	/// `source_dimensions` area at `source_x` and `source_y` of the image is
+	/// placed.
+	///
+	/// # Events
+	/// If `send_event` is `true`, this [request] generates a [`Completion`]
+	/// event once the X server has finished reading from the `segment`.
+	///
+	/// # Errors
+	/// A [`Drawable` error] is generated if `target` does not refer to a
+	/// defined [window] nor [pixmap].
+	///
+	/// A [`GraphicsContext` error] is generated if `graphics_context` does not
+	/// refer to a defined [`GraphicsContext`].
+	///
+	/// A [`Match` error] is generated if `depth` does not match the depth of
+	/// the `target` [drawable], or if `format` is [`PlaceImageFormat::Bitmap`]
+	/// and `depth` is not `1`.
+	///
+	/// A [`Value` error] is generated if the source area does not fit within
+	/// the `total_dimensions` of the image.
+	///
+	/// [drawable]: Drawable
+	/// [window]: crate::Window
+	/// [pixmap]: Pixmap
+	/// [request]: Request
+	///
+	/// [`PlaceImage` request]: crate::x11::request::PlaceImage
+	/// [`Completion`]: super::Completion
+	///
+	/// [`Drawable` error]: error::Drawable
+	/// [`GraphicsContext` error]: error::GraphicsContext
+	/// [`Match` error]: error::Match
+	/// [`Value` error]: error::Value
+	#[doc(alias("PutImage", "ShmPutImage"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct PlaceImage: Request(UNASSIGNED_MAJOR_OPCODE, 3, PlaceImageError) {
+		/// The [drawable] on which the image is placed.
+		///
+		/// [drawable]: Drawable
+		#[doc(alias("drawable"))]
+		pub target: Drawable,
+		/// The [`GraphicsContext`] used in this graphics operation.
+		#[doc(alias("gc", "context", "gcontext"))]
+		pub graphics_context: GraphicsContext,
+
+		/// The width and height of the whole image stored in the `segment`.
+		#[doc(alias("total_width", "total_height"))]
+		pub total_dimensions: Dimensions,
+
+		/// The x coordinate of the area of the image which is placed.
+		#[doc(alias("src_x"))]
+		pub source_x: Px<u16>,
+		/// The y coordinate of the area of the image which is placed.
+		#[doc(alias("src_y"))]
+		pub source_y: Px<u16>,
+		/// The width and height of the area of the image which is placed.
+		#[doc(alias("src_width", "src_height"))]
+		pub source_dimensions: Dimensions,
+
+		/// The [coordinates] at which the image will be placed on the `target`
+		/// [drawable].
+		///
+		/// [drawable]: Drawable
+		/// [coordinates]: Coords
+		#[doc(alias("dst_x", "dst_y"))]
+		pub coordinates: Coords,
+
+		/// The depth of the image.
+		pub depth: u8,
+		/// The [image format] used.
+		///
+		/// [image format]: PlaceImageFormat
+		pub format: PlaceImageFormat,
+
+		/// Whether a [`Completion`] event is generated once the X server has
+		/// finished reading from the `segment`.
+		///
+		/// [`Completion`]: super::Completion
+		pub send_event: bool,
+		[_; 1],
+
+		/// The shared memory segment in which the image is stored.
+		#[doc(alias = "shmseg")]
+		pub segment: ShmSegment,
+		/// The offset into the `segment` at which the image begins.
+		pub offset: u32,
+	}
+
+	/// A [request] that writes the contents of the given `area` of the given
+	/// [drawable] as an image to a shared memory segment.
+	///
+	/// This is the equivalent of the core [`CaptureImage` request], but the
+	/// image is written to the `segment` at the given `offset` rather than
+	/// being returned in the [reply].
+	///
+	/// # Replies
+	/// This [request] generates a [`CaptureImage` reply].
+	///
+	/// # Errors
+	/// A [`Drawable` error] is generated if `target` does not refer to a
+	/// defined [window] nor [pixmap].
+	///
+	/// A [`Match` error] is generated if the given area is not fully contained
+	/// within the `target` [drawable].
+	///
+	/// A [`Value` error] is generated if the image does not fit within the
+	/// `segment`.
+	///
+	/// [drawable]: Drawable
+	/// [window]: crate::Window
+	/// [pixmap]: Pixmap
+	/// [request]: Request
+	/// [reply]: crate::message::Reply
+	///
+	/// [`CaptureImage` request]: crate::x11::request::CaptureImage
+	/// [`CaptureImage` reply]: reply::CaptureImage
+	///
+	/// [`Drawable` error]: error::Drawable
+	/// [`Match` error]: error::Match
+	/// [`Value` error]: error::Value
+	#[doc(alias("GetImage", "ShmGetImage"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct CaptureImage: Request(
+		UNASSIGNED_MAJOR_OPCODE,
+		4,
+		CaptureImageError,
+	) -> reply::CaptureImage {
+		/// The [drawable] for which this [request] captures an image from the
+		/// given `area`.
+		///
+		/// [drawable]: Drawable
+		/// [request]: Request
+		#[doc(alias("drawable"))]
+		pub target: Drawable,
+
+		/// The area of the `target` [drawable] which this [request] captures
+		/// an image of.
+		///
+		/// [drawable]: Drawable
+		/// [request]: Request
+		#[doc(alias("x", "y", "width", "height"))]
+		pub area: Rectangle,
+
+		/// A mask of the bit planes which are included in the image.
+		pub plane_mask: u32,
+		/// The [image format] of the captured image.
+		///
+		/// [image format]: CaptureImageFormat
+		pub format: CaptureImageFormat,
+		[_; 3],
+
+		/// The shared memory segment to which the image is written.
+		#[doc(alias = "shmseg")]
+		pub segment: ShmSegment,
+		/// The offset into the `segment` at which the image is written.
+		pub offset: u32,
+	}
+
+	/// A [request] that creates a new [pixmap] whose contents are stored in a
+	/// shared memory segment, and assigns the provided [`Pixmap` ID][pixmap]
+	/// to it.
+	///
+	/// This is only supported if `shared_pixmaps` is `true` in the
+	/// [`QueryVersion` reply].
+	///
+	/// # Errors
+	/// A [`Drawable` error] is generated if `drawable` does not refer to a
+	/// defined [window] nor [pixmap].
+	///
+	/// A [`Match` error] is generated if the X server does not support shared
+	/// [pixmaps][pixmap].
+	///
+	/// A [`ResourceIdChoice` error] is generated if `pixmap_id` specifies an ID
+	/// already used for another resource, or an ID not allocated to your
+	/// client.
+	///
+	/// A [`Value` error] is generated if `depth` is not a depth supported by
+	/// the `drawable`'s root [window], or if the [pixmap] does not fit within
+	/// the `segment`.
+	///
+	/// [window]: crate::Window
+	/// [pixmap]: Pixmap
+	/// [request]: Request
+	///
+	/// [`QueryVersion` reply]: reply::QueryVersion
+	///
+	/// [`Drawable` error]: error::Drawable
+	/// [`Match` error]: error::Match
+	/// [`ResourceIdChoice` error]: error::ResourceIdChoice
+	/// [`Value` error]: error::Value
+	#[doc(alias("ShmCreatePixmap"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct CreatePixmap: Request(UNASSIGNED_MAJOR_OPCODE, 5, CreatePixmapError) {
+		/// The [`Pixmap` ID][pixmap] which is to be assigned to the [pixmap].
+		///
+		/// [pixmap]: Pixmap
+		#[doc(alias = "pid")]
+		pub pixmap_id: Pixmap,
+		/// The [drawable] used to determine the [screen] on which the [pixmap]
+		/// is created.
+		///
+		/// [drawable]: Drawable
+		/// [pixmap]: Pixmap
+		/// [screen]: crate::visual::Screen
+		pub drawable: Drawable,
+
+		/// The width and height of the [pixmap].
+		///
+		/// [pixmap]: Pixmap
+		#[doc(alias("width", "height"))]
+		pub dimensions: Dimensions,
+		/// The depth of the [pixmap].
+		///
+		/// [pixmap]: Pixmap
+		pub depth: u8,
+		[_; 3],
+
+		/// The shared memory segment in which the [pixmap]'s contents are
+		/// stored.
+		///
+		/// [pixmap]: Pixmap
+		#[doc(alias = "shmseg")]
+		pub segment: ShmSegment,
+		/// The offset into the `segment` at which the [pixmap]'s contents
+		/// begin.
+		///
+		/// [pixmap]: Pixmap
+		pub offset: u32,
+	}
+}
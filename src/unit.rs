@@ -111,6 +111,11 @@ macro_rules! impl_xrbk_traits {
 	RemAssign,
 	Sum,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Px<Num>(pub Num);
 
 impl<Num> Px<Num> {
@@ -159,6 +164,11 @@ impl_xrbk_traits!(Px<Num>(Num));
 	RemAssign,
 	Sum,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Mm<Num>(pub Num);
 
 impl<Num> Mm<Num> {
@@ -207,6 +217,11 @@ impl_xrbk_traits!(Mm<Num>(Num));
 	RemAssign,
 	Sum,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Ms<Num>(pub Num);
 
 impl<Num> Ms<Num> {
@@ -255,6 +270,11 @@ impl_xrbk_traits!(Ms<Num>(Num));
 	RemAssign,
 	Sum,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Sec<Num>(pub Num);
 
 impl<Num> Sec<Num> {
@@ -324,6 +344,11 @@ impl_xrbk_traits!(Sec<Num>(Num));
 	RemAssign,
 	Sum,
 )]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
 pub struct Hz<Num>(pub Num);
 
 impl<Num> Hz<Num> {
@@ -352,6 +377,7 @@ impl_xrbk_traits!(Hz<Num>(Num));
 
 /// A value measured as a percentage from 0% to 100%.
 #[derive(Debug, Hash, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
 pub struct Percentage(u8);
 
 impl Percentage {
@@ -367,6 +393,13 @@ impl Display for Percentage {
 	}
 }
 
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Percentage {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		Self::new(u8::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+	}
+}
+
 impl Percentage {
 	/// Creates a new percentage.
 	///
@@ -430,6 +463,7 @@ impl_xrbk_traits!(Percentage(u8));
 
 /// A value measured as a percentage from -100% to 100%.
 #[derive(Debug, Hash, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
 pub struct SignedPercentage(i8);
 
 impl SignedPercentage {
@@ -445,6 +479,13 @@ impl Display for SignedPercentage {
 	}
 }
 
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SignedPercentage {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		Self::new(i8::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+	}
+}
+
 impl SignedPercentage {
 	/// Creates a new signed percentage.
 	///
@@ -5,12 +5,21 @@
 //! Messages defined in the core X11 protocol: [requests], [replies], [events],
 //! and [errors].
 //!
+//! Any core [event] can be read with [`read_event`], and any core [error] with
+//! [`read_error`].
+//!
 //! [requests]: request
 //! [replies]: reply
 //! [events]: event
+//! [event]: Event
 //! [errors]: error
+//! [error]: AnyError
 
 pub mod error;
 pub mod event;
 pub mod reply;
 pub mod request;
+
+mod decode;
+
+pub use decode::{read_error, read_event, AnyError, Event};
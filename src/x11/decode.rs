@@ -0,0 +1,813 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Reading any [event] or [error] defined in the core X11 protocol,
+//! dispatching on its code.
+//!
+//! [event]: crate::message::Event
+//! [error]: crate::message::Error
+
+use std::fmt;
+use xrbk::{
+	Buf,
+	BufMut,
+	ReadError,
+	ReadMode,
+	ReadResult,
+	Readable,
+	Writable,
+	WriteResult,
+	X11Size,
+};
+
+use super::{error, event};
+use crate::message::{self, is_send_event, Flagged, SEND_EVENT_BIT};
+
+/// The size of an event with an unknown code.
+const UNKNOWN_EVENT_SIZE: usize = 32;
+/// The size of an error with an unknown code.
+const UNKNOWN_ERROR_SIZE: usize = 32;
+
+macro_rules! events {
+	(
+		$(#[$meta:meta])*
+		$vis:vis enum $Name:ident {
+			$($Event:ident),+$(,)?
+		}
+	) => {
+		$(#[$meta])*
+		$vis enum $Name {
+			$(
+				#[doc = concat!(
+					"A [`",
+					stringify!($Event),
+					"` event](event::",
+					stringify!($Event),
+					")."
+				)]
+				$Event(Flagged<event::$Event>),
+			)+
+
+			/// An event with a code reserved for extensions (64 to 127).
+			///
+			/// Extension events are not decoded, but their bytes are kept so
+			/// that they can be decoded by the extension or written again.
+			Unknown {
+				/// The code of the event, as it was received.
+				///
+				/// This includes the [`SEND_EVENT_BIT`] if the event was
+				/// generated by a [`SendEvent` request].
+				///
+				/// [`SendEvent` request]: super::request::SendEvent
+				code: u8,
+				/// The remaining 31 bytes of the event.
+				data: [u8; 31],
+			},
+		}
+
+		impl $Name {
+			/// Returns the code of the event, without the [`SEND_EVENT_BIT`].
+			#[must_use]
+			pub const fn code(&self) -> u8 {
+				match self {
+					$(Self::$Event(_) => <event::$Event as message::Event>::CODE,)+
+
+					Self::Unknown { code, .. } => *code & !SEND_EVENT_BIT,
+				}
+			}
+
+			/// Returns whether the event was generated by a
+			/// [`SendEvent` request].
+			///
+			/// [`SendEvent` request]: super::request::SendEvent
+			#[must_use]
+			pub const fn is_send_event(&self) -> bool {
+				match self {
+					$(Self::$Event(flagged) => flagged.send_event,)+
+
+					Self::Unknown { code, .. } => is_send_event(*code),
+				}
+			}
+
+			/// Returns the sequence number of the last [request] processed by
+			/// the X server before the event was generated, if the event has
+			/// one.
+			///
+			/// Only [`KeyboardState` events] have no sequence number.
+			///
+			/// [request]: message::Request
+			/// [`KeyboardState` events]: event::KeyboardState
+			#[must_use]
+			pub fn sequence(&self) -> Option<u16> {
+				match self {
+					$(Self::$Event(flagged) => message::Event::sequence(&flagged.event),)+
+
+					Self::Unknown { data, .. } => Some(u16::from_be_bytes([data[1], data[2]])),
+				}
+			}
+		}
+
+		impl fmt::Display for $Name {
+			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+				match self {
+					$(Self::$Event(flagged) => flagged.fmt(f),)+
+
+					Self::Unknown { code, .. } => {
+						write!(f, "Unknown code={}", code & !SEND_EVENT_BIT)?;
+
+						if is_send_event(*code) {
+							f.write_str(" (sent)")?;
+						}
+
+						Ok(())
+					},
+				}
+			}
+		}
+
+		impl X11Size for $Name {
+			fn x11_size(&self) -> usize {
+				match self {
+					$(Self::$Event(flagged) => flagged.x11_size(),)+
+
+					Self::Unknown { .. } => UNKNOWN_EVENT_SIZE,
+				}
+			}
+		}
+
+		impl Readable for $Name {
+			fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
+				Self::read_from_with_mode(buf, ReadMode::Lenient)
+			}
+
+			fn read_from_with_mode(buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Self> {
+				let code = u8::read_from(buf)?;
+				let send_event = is_send_event(code);
+
+				Ok(match code & !SEND_EVENT_BIT {
+					$(
+						<event::$Event as message::Event>::CODE => Self::$Event(Flagged {
+							event: event::$Event::read_from_with_mode(buf, mode)?,
+							send_event,
+						}),
+					)+
+
+					64..=127 => Self::Unknown {
+						code,
+						data: <_>::read_from(buf)?,
+					},
+
+					_ => return Err(ReadError::UnrecognizedDiscriminant(code.into())),
+				})
+			}
+		}
+
+		impl Writable for $Name {
+			fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+				match self {
+					$(Self::$Event(flagged) => flagged.write_to(buf)?,)+
+
+					Self::Unknown { code, data } => {
+						buf.put_u8(*code);
+						buf.put_slice(data);
+					},
+				}
+
+				Ok(())
+			}
+		}
+	};
+}
+
+events! {
+	/// Any [event] defined in the core X11 protocol.
+	///
+	/// Each variant records whether its event was generated by a
+	/// [`SendEvent` request]. Events with codes reserved for extensions are
+	/// read as [`Unknown`] events.
+	///
+	/// `Event`s are read with [`read_event`].
+	///
+	/// [event]: message::Event
+	/// [`SendEvent` request]: super::request::SendEvent
+	/// [`Unknown`]: Event::Unknown
+	#[derive(Debug, PartialEq, Eq, Hash)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub enum Event {
+		KeyPress,
+		KeyRelease,
+		ButtonPress,
+		ButtonRelease,
+		Motion,
+		EnterWindow,
+		LeaveWindow,
+		Focus,
+		Unfocus,
+		KeyboardState,
+		Expose,
+		GraphicsExposure,
+		NoExposure,
+		Visibility,
+		Create,
+		Destroy,
+		Unmap,
+		Map,
+		MapWindowRequest,
+		Reparent,
+		Configure,
+		ConfigureWindowRequest,
+		Gravity,
+		ResizeRequest,
+		Circulate,
+		CirculateWindowRequest,
+		Property,
+		SelectionClear,
+		ConvertSelectionRequest,
+		Selection,
+		Colormap,
+		ClientMessage,
+		MappingChange,
+		GenericEvent,
+	}
+}
+
+/// Reads any [event] defined in the core X11 protocol, including its code.
+///
+/// The [`SEND_EVENT_BIT`] is masked off the code to find which [event] it is,
+/// and is recorded in the returned [`Event`]. Codes reserved for extensions
+/// (64 to 127) are read as [`Event::Unknown`].
+///
+/// # Errors
+/// Returns [`ReadError::UnrecognizedDiscriminant`] if the code is neither that
+/// of a core [event] nor reserved for extensions, or any error returned while
+/// reading the [event] itself.
+///
+/// [event]: message::Event
+pub fn read_event(buf: &mut impl Buf) -> ReadResult<Event> {
+	Event::read_from(buf)
+}
+
+macro_rules! errors {
+	(
+		$(#[$meta:meta])*
+		$vis:vis enum $Name:ident {
+			$($Error:ident),+$(,)?
+		}
+	) => {
+		$(#[$meta])*
+		$vis enum $Name {
+			$(
+				#[doc = concat!(
+					"A [`",
+					stringify!($Error),
+					"` error](error::",
+					stringify!($Error),
+					")."
+				)]
+				$Error(error::$Error),
+			)+
+
+			/// An error with a code reserved for extensions (128 to 255).
+			///
+			/// Extension errors are not decoded, but their bytes are kept so
+			/// that they can be decoded by the extension or written again.
+			Unknown {
+				/// The code of the error.
+				code: u8,
+				/// The remaining 30 bytes of the error, starting with its
+				/// sequence number.
+				data: [u8; 30],
+			},
+		}
+
+		impl $Name {
+			/// Returns the code of the error.
+			#[must_use]
+			pub const fn code(&self) -> u8 {
+				match self {
+					$(Self::$Error(_) => <error::$Error as message::Error>::CODE,)+
+
+					Self::Unknown { code, .. } => *code,
+				}
+			}
+
+			/// Returns the sequence number of the [request] which generated
+			/// the error.
+			///
+			/// [request]: message::Request
+			#[must_use]
+			pub fn sequence(&self) -> u16 {
+				match self {
+					$(Self::$Error(error) => message::Error::sequence(error),)+
+
+					Self::Unknown { data, .. } => u16::from_be_bytes([data[0], data[1]]),
+				}
+			}
+
+			/// Returns the [minor opcode] of the [request] which generated the
+			/// error.
+			///
+			/// [request]: message::Request
+			/// [minor opcode]: message::Request::MINOR_OPCODE
+			#[must_use]
+			pub fn minor_opcode(&self) -> u16 {
+				match self {
+					$(Self::$Error(error) => message::Error::minor_opcode(error),)+
+
+					Self::Unknown { data, .. } => u16::from_be_bytes([data[6], data[7]]),
+				}
+			}
+
+			/// Returns the [major opcode] of the [request] which generated the
+			/// error.
+			///
+			/// [request]: message::Request
+			/// [major opcode]: message::Request::MAJOR_OPCODE
+			#[must_use]
+			pub fn major_opcode(&self) -> u8 {
+				match self {
+					$(Self::$Error(error) => message::Error::major_opcode(error),)+
+
+					Self::Unknown { data, .. } => data[8],
+				}
+			}
+		}
+
+		$(
+			impl TryFrom<$Name> for error::$Error {
+				type Error = $Name;
+
+				fn try_from(error: $Name) -> Result<Self, Self::Error> {
+					match error {
+						$Name::$Error(error) => Ok(error),
+						other => Err(other),
+					}
+				}
+			}
+		)+
+
+		impl X11Size for $Name {
+			fn x11_size(&self) -> usize {
+				match self {
+					$(Self::$Error(error) => error.x11_size(),)+
+
+					Self::Unknown { .. } => UNKNOWN_ERROR_SIZE,
+				}
+			}
+		}
+
+		impl Readable for $Name {
+			fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
+				Self::read_from_with_mode(buf, ReadMode::Lenient)
+			}
+
+			fn read_from_with_mode(buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Self> {
+				// A first byte of `0` means that this is an error.
+				let discrim = u8::read_from(buf)?;
+				if discrim != 0 {
+					return Err(ReadError::UnrecognizedDiscriminant(discrim.into()));
+				}
+
+				let code = u8::read_from(buf)?;
+
+				Ok(match code {
+					$(
+						<error::$Error as message::Error>::CODE => {
+							Self::$Error(error::$Error::read_from_with_mode(buf, mode)?)
+						},
+					)+
+
+					128..=255 => Self::Unknown {
+						code,
+						data: <_>::read_from(buf)?,
+					},
+
+					_ => return Err(ReadError::UnrecognizedDiscriminant(code.into())),
+				})
+			}
+		}
+
+		impl Writable for $Name {
+			fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+				match self {
+					$(Self::$Error(error) => error.write_to(buf)?,)+
+
+					Self::Unknown { code, data } => {
+						buf.put_u8(0);
+						buf.put_u8(*code);
+						buf.put_slice(data);
+					},
+				}
+
+				Ok(())
+			}
+		}
+	};
+}
+
+errors! {
+	/// Any [error] defined in the core X11 protocol.
+	///
+	/// Errors with codes reserved for extensions are read as [`Unknown`]
+	/// errors.
+	///
+	/// `AnyError`s are read with [`read_error`], and can be narrowed to the
+	/// errors generated by a particular [request] with `TryFrom`.
+	///
+	/// [error]: message::Error
+	/// [request]: message::Request
+	/// [`Unknown`]: AnyError::Unknown
+	#[derive(Debug, PartialEq, Eq, Hash)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub enum AnyError {
+		Request,
+		Value,
+		Window,
+		Pixmap,
+		Atom,
+		CursorAppearance,
+		Font,
+		Match,
+		Drawable,
+		Access,
+		Alloc,
+		Colormap,
+		GraphicsContext,
+		ResourceIdChoice,
+		Name,
+		Length,
+		Implementation,
+	}
+}
+
+/// Reads any [error] defined in the core X11 protocol, including its first
+/// byte of `0` and its code.
+///
+/// Codes reserved for extensions (128 to 255) are read as
+/// [`AnyError::Unknown`].
+///
+/// # Errors
+/// Returns [`ReadError::UnrecognizedDiscriminant`] if the first byte is not
+/// `0`, or if the code is neither that of a core [error] nor reserved for
+/// extensions. Returns any error returned while reading the [error] itself.
+///
+/// [error]: message::Error
+pub fn read_error(buf: &mut impl Buf) -> ReadResult<AnyError> {
+	AnyError::read_from(buf)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{atom::Atom, message::SequenceTracker, x11::event::ClientMessageData, Window};
+
+	/// Returns the bytes of a core event with the given `code` and otherwise
+	/// zeroed fields.
+	fn core_event_bytes(code: u8) -> [u8; 32] {
+		let mut bytes = [0; 32];
+		bytes[0] = code;
+
+		// A `ClientMessage` event's format must be 8, 16, or 32.
+		if code == <event::ClientMessage as message::Event>::CODE {
+			bytes[1] = 32;
+		}
+
+		bytes
+	}
+
+	#[test]
+	fn test_read_every_core_event() {
+		for code in 2..=35 {
+			let bytes = core_event_bytes(code);
+			let event = read_event(&mut &bytes[..])
+				.unwrap_or_else(|error| panic!("failed to read event {code}: {error:?}"));
+
+			assert_eq!(event.code(), code);
+			assert!(!event.is_send_event());
+			assert_eq!(event.x11_size(), 32);
+
+			let mut written = vec![];
+			event.write_to(&mut written).unwrap();
+			assert_eq!(written, bytes, "event {code} was not written again as read");
+		}
+	}
+
+	#[test]
+	fn test_read_generic_event_longer_than_32_bytes() {
+		let generic = event::GenericEvent {
+			sequence: 3,
+			extension: 131,
+			event_type: 2,
+			data: (0..30).collect(),
+		};
+
+		let mut bytes = vec![];
+		generic.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes.len(), 40);
+
+		// The event following the `GenericEvent` must be read from where the
+		// `GenericEvent` ends, not 32 bytes after it starts.
+		bytes.extend_from_slice(&core_event_bytes(12));
+		let buf = &mut &bytes[..];
+
+		match read_event(buf).unwrap() {
+			Event::GenericEvent(flagged) => assert_eq!(flagged.event, generic),
+			other => panic!("expected a `GenericEvent`, found {other:?}"),
+		}
+		assert_eq!(read_event(buf).unwrap().code(), 12);
+		assert!(buf.is_empty());
+	}
+
+	#[test]
+	fn test_read_synthetic_client_message() {
+		let mut bytes = [0; 32];
+		// `ClientMessage` with the `SEND_EVENT_BIT` set.
+		bytes[0] = 0xa1;
+		bytes[1] = 32;
+		bytes[2..4].copy_from_slice(&5u16.to_be_bytes());
+		bytes[4..8].copy_from_slice(&0x0060_0001u32.to_be_bytes());
+		bytes[8..12].copy_from_slice(&0x0000_0145u32.to_be_bytes());
+		bytes[12..16].copy_from_slice(&1u32.to_be_bytes());
+
+		let event = read_event(&mut &bytes[..]).unwrap();
+
+		assert_eq!(event.code(), 33);
+		assert!(event.is_send_event());
+
+		match &event {
+			Event::ClientMessage(flagged) => {
+				assert!(flagged.send_event);
+				assert_eq!(flagged.event.sequence, 5);
+				assert_eq!(flagged.event.window, Window::new(0x0060_0001));
+				assert_eq!(flagged.event.r#type, Atom::new(0x0000_0145));
+				assert_eq!(
+					flagged.event.data,
+					ClientMessageData::Longs([1, 0, 0, 0, 0])
+				);
+			},
+
+			other => panic!("expected a `ClientMessage` event, found {other:?}"),
+		}
+
+		let mut written = vec![];
+		event.write_to(&mut written).unwrap();
+		assert_eq!(written, bytes);
+	}
+
+	#[test]
+	fn test_event_display() {
+		let mut bytes = [0; 32];
+		// `ClientMessage` with the `SEND_EVENT_BIT` set.
+		bytes[0] = 0xa1;
+		bytes[1] = 32;
+		bytes[4..8].copy_from_slice(&0x0060_0001u32.to_be_bytes());
+		bytes[8..12].copy_from_slice(&0x0000_0145u32.to_be_bytes());
+
+		assert_eq!(
+			read_event(&mut &bytes[..]).unwrap().to_string(),
+			"ClientMessage win=0x600001 type=325 format=32 data=[0, 0, 0, 0, 0] (sent)"
+		);
+
+		assert_eq!(
+			read_event(&mut &core_event_bytes(0xd0)[..])
+				.unwrap()
+				.to_string(),
+			"Unknown code=80 (sent)"
+		);
+	}
+
+	#[test]
+	fn test_event_sequence() {
+		let mut tracker = SequenceTracker::new();
+		for _ in 0..0x1_0005 {
+			tracker.sent_request();
+		}
+
+		let mut bytes = core_event_bytes(<event::Configure as message::Event>::CODE);
+		bytes[2..4].copy_from_slice(&0xfff0u16.to_be_bytes());
+
+		let event = read_event(&mut &bytes[..]).unwrap();
+		assert_eq!(event.sequence(), Some(0xfff0));
+		assert_eq!(tracker.resolve(event.sequence().unwrap()), 0xfff0);
+
+		let mut bytes = core_event_bytes(0x50);
+		bytes[2..4].copy_from_slice(&3u16.to_be_bytes());
+
+		let event = read_event(&mut &bytes[..]).unwrap();
+		assert_eq!(event.sequence(), Some(3));
+		assert_eq!(tracker.resolve(event.sequence().unwrap()), 0x1_0003);
+
+		let bytes = core_event_bytes(<event::KeyboardState as message::Event>::CODE);
+		assert_eq!(read_event(&mut &bytes[..]).unwrap().sequence(), None);
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn test_event_json_round_trip() {
+		let mut bytes = [0; 32];
+		// `ClientMessage` with the `SEND_EVENT_BIT` set.
+		bytes[0] = 0xa1;
+		bytes[1] = 32;
+		bytes[4..8].copy_from_slice(&0x0060_0001u32.to_be_bytes());
+
+		for bytes in [bytes, core_event_bytes(70)] {
+			let event = read_event(&mut &bytes[..]).unwrap();
+
+			let json = serde_json::to_string(&event).unwrap();
+			let deserialized: Event = serde_json::from_str(&json).unwrap();
+
+			assert_eq!(deserialized, event);
+			assert_eq!(deserialized.is_send_event(), event.is_send_event());
+		}
+
+		let json = serde_json::to_string(&read_event(&mut &bytes[..]).unwrap()).unwrap();
+		assert!(json.contains(r#""send_event":true"#));
+		assert!(json.contains(r#""window":6291457"#));
+	}
+
+	#[test]
+	fn test_read_unknown_extension_event() {
+		let mut bytes = [0; 32];
+		bytes[0] = 0x50;
+		for (i, byte) in bytes[1..].iter_mut().enumerate() {
+			*byte = i as u8;
+		}
+
+		let event = read_event(&mut &bytes[..]).unwrap();
+
+		assert_eq!(event.code(), 0x50);
+		assert!(!event.is_send_event());
+		assert_eq!(
+			event,
+			Event::Unknown {
+				code: 0x50,
+				data: bytes[1..].try_into().unwrap(),
+			},
+		);
+
+		let mut written = vec![];
+		event.write_to(&mut written).unwrap();
+		assert_eq!(written, bytes);
+
+		// The same extension event, generated by a `SendEvent` request.
+		bytes[0] = 0xd0;
+		let event = read_event(&mut &bytes[..]).unwrap();
+
+		assert_eq!(event.code(), 0x50);
+		assert!(event.is_send_event());
+	}
+
+	#[test]
+	fn test_read_unrecognized_event_code() {
+		for code in [0, 1, 36, 63, 0x81] {
+			let bytes = core_event_bytes(code);
+
+			assert!(
+				matches!(
+					read_event(&mut &bytes[..]),
+					Err(ReadError::UnrecognizedDiscriminant(discrim)) if discrim == usize::from(code),
+				),
+				"code {code} should not be recognized",
+			);
+		}
+	}
+
+	#[test]
+	fn test_read_window_error() {
+		#[rustfmt::skip]
+		let bytes = [
+			0, 3, 0x00, 0x2a, // error, code, sequence
+			0x00, 0x60, 0x00, 0x01, // invalid window ID
+			0x00, 0x00, 8, // minor opcode, major opcode (`MapWindow`)
+			0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+		];
+
+		let error = read_error(&mut &bytes[..]).unwrap();
+
+		assert_eq!(error.code(), 3);
+		assert_eq!(error.sequence(), 42);
+		assert_eq!(error.minor_opcode(), 0);
+		assert_eq!(error.major_opcode(), 8);
+
+		match &error {
+			AnyError::Window(window) => assert_eq!(window.invalid_window_id, 0x0060_0001),
+			other => panic!("expected a `Window` error, found {other:?}"),
+		}
+
+		let mut written = vec![];
+		error.write_to(&mut written).unwrap();
+		assert_eq!(written, bytes);
+		assert_eq!(error.x11_size(), 32);
+	}
+
+	#[test]
+	fn test_read_value_error() {
+		let mut bytes = [0; 32];
+		bytes[1] = 2;
+		bytes[2..4].copy_from_slice(&7u16.to_be_bytes());
+		bytes[4..8].copy_from_slice(&[0, 0, 0, 9]);
+		// `ConfigureWindow`.
+		bytes[10] = 12;
+
+		let error = read_error(&mut &bytes[..]).unwrap();
+
+		assert_eq!(error.code(), 2);
+		assert_eq!(error.sequence(), 7);
+		assert_eq!(error.major_opcode(), 12);
+
+		match &error {
+			AnyError::Value(value) => assert_eq!(value.invalid_value, [0, 0, 0, 9]),
+			other => panic!("expected a `Value` error, found {other:?}"),
+		}
+	}
+
+	#[test]
+	fn test_read_length_error() {
+		let mut bytes = [0; 32];
+		bytes[1] = 16;
+		bytes[2..4].copy_from_slice(&300u16.to_be_bytes());
+		// `PolyFillRectangle`.
+		bytes[10] = 70;
+
+		let error = read_error(&mut &bytes[..]).unwrap();
+
+		assert!(matches!(error, AnyError::Length(_)));
+		assert_eq!(error.sequence(), 300);
+		assert_eq!(error.major_opcode(), 70);
+	}
+
+	#[test]
+	fn test_narrow_errors() {
+		use crate::x11::request::CreateWindowError;
+
+		let read = |code: u8| {
+			let mut bytes = [0; 32];
+			bytes[1] = code;
+
+			read_error(&mut &bytes[..]).unwrap()
+		};
+
+		assert!(matches!(
+			CreateWindowError::try_from(read(3)),
+			Ok(CreateWindowError::Window(_)),
+		));
+		assert!(matches!(
+			CreateWindowError::try_from(read(2)),
+			Ok(CreateWindowError::Value(_)),
+		));
+		// A `CreateWindow` request cannot generate a `Font` error.
+		assert!(matches!(
+			CreateWindowError::try_from(read(7)),
+			Err(AnyError::Font(_)),
+		));
+
+		assert!(error::Window::try_from(read(3)).is_ok());
+		assert!(matches!(
+			error::Window::try_from(read(16)),
+			Err(AnyError::Length(_)),
+		));
+	}
+
+	#[test]
+	fn test_read_unknown_extension_error() {
+		let mut bytes = [0; 32];
+		bytes[1] = 0x90;
+		for (i, byte) in bytes[2..].iter_mut().enumerate() {
+			*byte = i as u8;
+		}
+
+		let error = read_error(&mut &bytes[..]).unwrap();
+
+		assert_eq!(error.code(), 0x90);
+		assert_eq!(error.sequence(), 0x0001);
+		assert_eq!(error.minor_opcode(), 0x0607);
+		assert_eq!(error.major_opcode(), 8);
+
+		let mut written = vec![];
+		error.write_to(&mut written).unwrap();
+		assert_eq!(written, bytes);
+	}
+
+	#[test]
+	fn test_read_unrecognized_error_code() {
+		for code in [0, 18, 127] {
+			let mut bytes = [0; 32];
+			bytes[1] = code;
+
+			assert!(
+				matches!(
+					read_error(&mut &bytes[..]),
+					Err(ReadError::UnrecognizedDiscriminant(discrim)) if discrim == usize::from(code),
+				),
+				"code {code} should not be recognized",
+			);
+		}
+
+		// A reply is not an error.
+		let mut bytes = [0; 32];
+		bytes[0] = 1;
+		bytes[1] = 3;
+		assert!(read_error(&mut &bytes[..]).is_err());
+	}
+}
@@ -25,7 +25,8 @@ derive_xrb! {
 	/// [request]: crate::message::Request
 	/// [major opcode]: crate::message::Request::MAJOR_OPCODE
 	/// [minor opcode]: crate::message::Request::MINOR_OPCODE
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Request: Error(1) {
 		/// The sequence number identifying the [request] that was
@@ -72,7 +73,8 @@ derive_xrb! {
 	///
 	/// [request]: crate::message::Request
 	/// [error]: Error
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Value: Error(2) {
 		/// The sequence number identifying the [request] that was
@@ -120,7 +122,8 @@ derive_xrb! {
 	/// [`Window`]: crate::Window
 	/// [window]: crate::Window
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Window: Error(3) {
 		/// The sequence number identifying the [request] that was
@@ -168,7 +171,8 @@ derive_xrb! {
 	/// [`Pixmap`]: crate::Pixmap
 	/// [pixmap]: crate::Pixmap
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Pixmap: Error(4) {
 		/// The sequence number identifying the [request] that was
@@ -216,7 +220,8 @@ derive_xrb! {
 	/// [`Atom`]: crate::Atom
 	/// [atom]: crate::Atom
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Atom: Error(5) {
 		/// The sequence number identifying the [request] that was
@@ -264,7 +269,8 @@ derive_xrb! {
 	/// [`CursorAppearance`]: crate::CursorAppearance
 	/// [cursor appearance]: crate::CursorAppearance
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct CursorAppearance: Error(6) {
 		/// The sequence number identifying the [request] that was
@@ -313,7 +319,8 @@ derive_xrb! {
 	/// [`Font`]: crate::Font
 	/// [font]: crate::Font
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Font: Error(7) {
 		/// The sequence number identifying the [request] that was
@@ -370,7 +377,8 @@ derive_xrb! {
 	/// [drawable]: crate::Drawable
 	/// [request]: crate::message::Request
 	/// [graphics context]: crate::GraphicsContext
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Match: Error(8) {
 		/// The sequence number identifying the [request] that was
@@ -409,7 +417,8 @@ derive_xrb! {
 	/// [window]: crate::Window
 	/// [pixmap]: crate::Pixmap
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Drawable: Error(9) {
 		/// The sequence number identifying the [request] that was
@@ -475,7 +484,8 @@ derive_xrb! {
 	/// [select to receive]: crate::mask::EventMask
 	/// [button]: crate::Button
 	/// [colormap]: crate::Colormap
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Access: Error(10) {
 		/// The sequence number identifying the [request] that was
@@ -510,7 +520,8 @@ derive_xrb! {
 	/// resource.
 	///
 	/// [error]: Error
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Alloc: Error(11) {
 		/// The sequence number identifying the [request] that was
@@ -548,7 +559,8 @@ derive_xrb! {
 	/// [`Colormap`]: crate::Colormap
 	/// [colormap]: crate::Colormap
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Colormap: Error(12) {
 		/// The sequence number identifying the [request] that was
@@ -596,7 +608,8 @@ derive_xrb! {
 	/// [`GraphicsContext`]: crate::GraphicsContext
 	/// [graphics context]: crate::GraphicsContext
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GraphicsContext: Error(13) {
 		/// The sequence number identifying the [request] that was
@@ -641,7 +654,8 @@ derive_xrb! {
 	/// resource IDs assigned to the client, or the ID is already in use.
 	///
 	/// [error]: Error
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ResourceIdChoice: Error(14) {
 		/// The sequence number identifying the [request] that was
@@ -683,7 +697,8 @@ derive_xrb! {
 	/// [error]: Error
 	/// [request]: crate::message::Request
 	/// [font]: crate::Font
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Name: Error(15) {
 		/// The sequence number identifying the [request] that was
@@ -722,7 +737,8 @@ derive_xrb! {
 	///
 	/// [error]: Error
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Length: Error(16) {
 		/// The sequence number identifying the [request] that was
@@ -758,7 +774,8 @@ derive_xrb! {
 	///
 	/// [error]: Error
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, Readable, X11Size, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Implementation: Error(17) {
 		/// The sequence number identifying the [request] that was
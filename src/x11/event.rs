@@ -13,8 +13,9 @@ extern crate self as xrb;
 
 use bitflags::bitflags;
 use derivative::Derivative;
+use thiserror::Error;
 
-use xrbk::{Buf, ConstantX11Size, ReadResult, Readable, ReadableWithContext, X11Size};
+use xrbk::{Buf, ConstantX11Size, ReadError, ReadResult, Readable, ReadableWithContext, X11Size};
 use xrbk_macro::{derive_xrb, ConstantX11Size, Readable, Writable, X11Size};
 
 use crate::{
@@ -22,6 +23,7 @@ use crate::{
 	message::Event,
 	set::WindowConfigMask,
 	unit::Px,
+	x11::{reply, request},
 	Button,
 	Coords,
 	CurrentableTime,
@@ -36,6 +38,19 @@ use crate::{
 	Window,
 };
 
+mod click;
+mod display;
+mod exposure;
+mod key_state;
+mod motion_history;
+mod property_cache;
+
+pub use click::{ClickDetector, ClickType};
+pub use exposure::ExposureCollector;
+pub use key_state::KeyState;
+pub use motion_history::MotionHistory;
+pub use property_cache::{CacheState, PropertyCache};
+
 derive_xrb! {
 	/// An [event] generated when a key is pressed.
 	///
@@ -48,7 +63,8 @@ derive_xrb! {
 	/// [event]: Event
 	/// [window]: Window
 	/// [`KEY_PRESS`]: crate::EventMask::KEY_PRESS
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct KeyPress: Event(2) {
 		/// The [sequence number] associated with the last [request] related
@@ -129,7 +145,8 @@ derive_xrb! {
 	///
 	/// [event]: Event
 	/// [`KEY_RELEASE`]: crate::EventMask::KEY_RELEASE
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct KeyRelease: Event(3) {
 		/// The [sequence number] associated with the last [request] related
@@ -213,7 +230,8 @@ derive_xrb! {
 	/// [event]: Event
 	/// [mouse button]: Button
 	/// [`BUTTON_PRESS`]: crate::EventMask::BUTTON_PRESS
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ButtonPress: Event(4) {
 		/// The [sequence number] associated with the last [request] related
@@ -295,7 +313,8 @@ derive_xrb! {
 	/// [event]: Event
 	/// [mouse button]: Button
 	/// [`BUTTON_RELEASE`]: crate::EventMask::BUTTON_RELEASE
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ButtonRelease: Event(5) {
 		/// The [sequence number] associated with the last [request] related
@@ -374,6 +393,7 @@ derive_xrb! {
 ///
 /// [`Motion` event]: Motion
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MotionNotificationType {
 	/// The [`Motion` event] was not one generated for a client selecting
 	/// [`MOTION_HINT`].
@@ -396,7 +416,7 @@ pub enum MotionNotificationType {
 	/// [mouse button]: Button
 	///
 	/// [`QueryCursor`]: super::request::QueryCursorLocation
-	/// [`GetMotionEvents`]: super::request::GetMotionEvents
+	/// [`GetMotionEvents`]: super::request::GetMotionHistory
 	Hint,
 }
 
@@ -436,11 +456,12 @@ derive_xrb! {
 	///
 	/// [`Hint`]: MotionNotificationType::Hint
 	/// [`QueryCursor`]: super::request::QueryCursorLocation
-	/// [`GetMotionEvents`]: super::request::GetMotionEvents
+	/// [`GetMotionEvents`]: super::request::GetMotionHistory
 	///
 	/// [event]: Event
 	/// [window]: Window
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Motion: Event(6) {
 		/// The [sequence number] associated with the last [request] related
@@ -561,6 +582,7 @@ derive_xrb! {
 /// [event]: Event
 /// [window]: Window
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EnterLeaveDetail {
 	/// Used for [`LeaveWindow` events] when the cursor leaves a [window] and
 	/// enters an ancestor of that [window], and for [`EnterWindow` events]
@@ -629,6 +651,14 @@ bitflags! {
 	}
 }
 
+impl_display_for_bitflags!(EnterLeaveMask {
+	FOCUS => "Focus",
+	SAME_SCREEN => "SameScreen",
+});
+
+#[cfg(feature = "serde")]
+impl_serde_for_bitflags!(EnterLeaveMask);
+
 derive_xrb! {
 	/// An [event] generated when the cursor enters a [window].
 	///
@@ -645,7 +675,8 @@ derive_xrb! {
 	/// [event]: Event
 	/// [window]: Window
 	/// [`ENTER_WINDOW`]: crate::EventMask::ENTER_WINDOW
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct EnterWindow: Event(7) {
 		/// The [sequence number] associated with the last [request] related
@@ -736,7 +767,8 @@ derive_xrb! {
 	/// [event]: Event
 	/// [window]: Window
 	/// [`LEAVE_WINDOW`]: crate::EventMask::LEAVE_WINDOW
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct LeaveWindow: Event(8) {
 		/// The [sequence number] associated with the last [request] related
@@ -942,6 +974,7 @@ derive_xrb! {
 /// [event]: Event
 /// [window]: Window
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FocusDetail {
 	/// Used for [`Unfocus` events] for the [window] which has been unfocused if
 	/// the newly focused [window] is an ancestor of that [window], and for
@@ -990,7 +1023,8 @@ pub enum FocusDetail {
 
 /// Detail about how an [`Unfocus`] or [`Focus`] event was generated in relation
 /// to grabs.
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FocusGrabMode {
 	/// Used for [`Unfocus`] and [`Focus`] events generated when the keyboard is
 	/// not grabbed.
@@ -1025,7 +1059,8 @@ derive_xrb! {
 	/// [event]: Event
 	/// [window]: Window
 	/// [`FOCUS_CHANGE`]: crate::EventMask::FOCUS_CHANGE
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Focus: Event(9) {
 		/// The [sequence number] associated with the last [request] related
@@ -1084,7 +1119,8 @@ derive_xrb! {
 	/// [event]: Event
 	/// [window]: Window
 	/// [`FOCUS_CHANGE`]: crate::EventMask::FOCUS_CHANGE
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Unfocus: Event(10) {
 		/// The [sequence number] associated with the last [request] related
@@ -1136,7 +1172,8 @@ derive_xrb! {
 	/// [event]: Event
 	/// [window]: Window
 	/// [`KEYBOARD_STATE`]: crate::EventMask::KEYBOARD_STATE
-	#[derive(Debug, Hash, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct KeyboardState: Event(11) {
 		/// A bit vector representing the current keyboard state.
 		///
@@ -1178,7 +1215,8 @@ derive_xrb! {
 	/// [`WindowClass::InputOnly`]: crate::WindowClass::InputOnly
 	///
 	/// [`EXPOSURE`]: crate::EventMask::EXPOSURE
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Expose: Event(12) {
 		/// The [sequence number] associated with the last [request] related
@@ -1217,7 +1255,8 @@ derive_xrb! {
 	/// [event]: Event
 	/// [`GraphicsContext`]: crate::GraphicsContext
 	/// [`graphics_exposure`]: crate::set::GraphicsOptions::graphics_exposure
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GraphicsExposure: Event(13) {
 		/// The [sequence number] associated with the last [request] related
@@ -1272,7 +1311,8 @@ derive_xrb! {
 	/// [`GraphicsExposure` events]: GraphicsExposure
 	/// [`GraphicsContext`]: crate::GraphicsContext
 	/// [`graphics_exposure`]: crate::set::GraphicsOptions::graphics_exposure
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct NoExposure: Event(14) {
 		/// The [sequence number] associated with the last [request] related
@@ -1315,7 +1355,8 @@ derive_xrb! {
 ///
 /// [window]: Window
 /// [`Visibility` event]: Visibility
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VisibilityState {
 	/// There is nothing obscuring the `window`.
 	///
@@ -1376,7 +1417,8 @@ derive_xrb! {
 	/// [`FullyObscured`]: VisibilityState::FullyObscured
 	///
 	/// [`VISIBILITY_CHANGE`]: crate::EventMask::VISIBILITY_CHANGE
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Visibility: Event(15) {
 		/// The [sequence number] associated with the last [request] related
@@ -1405,7 +1447,8 @@ derive_xrb! {
 	/// [event]: Event
 	/// [window]: Window
 	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Create: Event(16) {
 		/// The [sequence number] associated with the last [request] related
@@ -1419,6 +1462,8 @@ derive_xrb! {
 		pub sequence: u16,
 
 		/// The parent of the `window` that was created.
+		///
+		/// This is the window that this `Create` event was generated on.
 		pub parent: Window,
 		/// The window that was created.
 		pub window: Window,
@@ -1462,7 +1507,8 @@ derive_xrb! {
 	/// [window]: Window
 	/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
 	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Destroy: Event(17) {
 		/// The [sequence number] associated with the last [request] related
@@ -1502,7 +1548,8 @@ derive_xrb! {
 	/// [window]: Window
 	/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
 	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Unmap: Event(18) {
 		/// The [sequence number] associated with the last [request] related
@@ -1550,7 +1597,8 @@ derive_xrb! {
 	/// [window]: Window
 	/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
 	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Map: Event(19) {
 		/// The [sequence number] associated with the last [request] related
@@ -1605,7 +1653,8 @@ derive_xrb! {
 	/// [`MapWindow` request]: super::request::MapWindow
 	///
 	/// [`SUBSTRUCTURE_REDIRECT`]: crate::EventMask::SUBSTRUCTURE_REDIRECT
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct MapWindowRequest: Event(20) {
 		/// The [sequence number] associated with the last [request] related
@@ -1641,7 +1690,8 @@ derive_xrb! {
 	/// [window]: Window
 	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
 	/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Reparent: Event(21) {
 		/// The [sequence number] associated with the last [request] related
@@ -1699,7 +1749,8 @@ derive_xrb! {
 	/// [window]: Window
 	/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
 	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Configure: Event(22) {
 		/// The [sequence number] associated with the last [request] related
@@ -1781,7 +1832,8 @@ derive_xrb! {
 	/// [`ConfigureWindow` request]: super::request::ConfigureWindow
 	///
 	/// [`SUBSTRUCTURE_REDIRECT`]: crate::EventMask::SUBSTRUCTURE_REDIRECT
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ConfigureWindowRequest: Event(23) {
 		/// The [sequence number] associated with the last [request] related
@@ -1826,6 +1878,8 @@ derive_xrb! {
 		///
 		/// The `window`'s dimensions exclude its border.
 		pub geometry: Rectangle,
+		/// The width of the `window`'s border.
+		pub border_width: Px<u16>,
 
 		/// A bitmask representing which attributes were configured in the
 		/// [`ConfigureWindow` request].
@@ -1846,7 +1900,8 @@ derive_xrb! {
 	/// [window]: Window
 	/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
 	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Gravity: Event(24) {
 		/// The [sequence number] associated with the last [request] related
@@ -1868,7 +1923,11 @@ derive_xrb! {
 		/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
 		/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
 		pub event_window: Window,
-		/// The window which was moved.
+		/// The window which was moved because its parent was resized.
+		///
+		/// The `window` is moved according to its [`WindowGravity`].
+		///
+		/// [`WindowGravity`]: crate::WindowGravity
 		pub window: Window,
 
 		/// The new coordinates of the `window`, relative to its parent's
@@ -1889,7 +1948,8 @@ derive_xrb! {
 	/// [window]: Window
 	/// [`RESIZE_REDIRECT`]: crate::EventMask::RESIZE_REDIRECT
 	/// [`ConfigureWindow` request]: super::request::ConfigureWindow
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ResizeRequest: Event(25) {
 		/// The [sequence number] associated with the last [request] related
@@ -1922,6 +1982,41 @@ derive_xrb! {
 	}
 }
 
+impl Configure {
+	/// Creates a `Configure` event to be sent to the client owning `window`
+	/// in a [`SendEvent` request].
+	///
+	/// Window managers send this synthetic event when they handle a
+	/// [`ConfigureWindowRequest`] without actually changing the `window`'s
+	/// geometry, or when they move a `window` without resizing it, as the
+	/// `window`'s coordinates reported by the X server are relative to its
+	/// (window manager-created) parent.
+	///
+	/// The `event_window` is the `window` itself, and `geometry` should be
+	/// relative to the root window. `override_redirect` is `false`, as the
+	/// window manager is managing the `window`. The `sequence` is `0`: it is
+	/// replaced by the X server when the event is sent.
+	///
+	/// [`SendEvent` request]: super::request::SendEvent
+	#[must_use]
+	pub const fn synthetic(
+		window: Window, geometry: Rectangle, border_width: Px<u16>, sibling_below: Option<Window>,
+	) -> Self {
+		Self {
+			sequence: 0,
+
+			event_window: window,
+			window,
+			sibling_below,
+
+			geometry,
+			border_width,
+
+			override_redirect: false,
+		}
+	}
+}
+
 /// The new placement of a [window] restacked in a [`CirculateWindow` request].
 ///
 /// This is used in [`Circulate` events].
@@ -1929,7 +2024,8 @@ derive_xrb! {
 /// [window]: Window
 /// [`CirculateWindow` request]: super::request::CirculateWindow
 /// [`Circulate` events]: Circulate
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Placement {
 	/// The `window` is now above all its siblings in the stack.
 	Top,
@@ -1951,7 +2047,8 @@ derive_xrb! {
 	///
 	/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
 	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Circulate: Event(26) {
 		/// The [sequence number] associated with the last [request] related
@@ -1975,6 +2072,8 @@ derive_xrb! {
 		pub event_window: Window,
 		/// The window which was restacked.
 		pub window: Window,
+		// The core protocol's encoding of `CirculateNotify` (Appendix B,
+		// Events) lists an unused `WINDOW` field here.
 		[_; 4],
 
 		/// The new placement in the window stack of the `window` in relation to
@@ -1994,7 +2093,8 @@ derive_xrb! {
 	/// [window]: Window
 	/// [`SUBSTRUCTURE_REDIRECT`]: crate::EventMask::SUBSTRUCTURE_REDIRECT
 	/// [`CirculateWindow` request]: super::request::CirculateWindow
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct CirculateWindowRequest: Event(27) {
 		/// The [sequence number] associated with the last [request] related
@@ -2020,6 +2120,8 @@ derive_xrb! {
 		///
 		/// [`CirculateWindow` request]: super::request::CirculateWindow
 		pub window: Window,
+		// The core protocol's encoding of `CirculateRequest` (Appendix B,
+		// Events) lists an unused 4-byte field here.
 		[_; 4],
 
 		/// The requested placement in the window stack of the `window` in
@@ -2029,15 +2131,95 @@ derive_xrb! {
 	}
 }
 
+/// An [event] reporting a change to the window hierarchy.
+///
+/// The window hierarchy [events] are reported both to clients selecting
+/// [`STRUCTURE_NOTIFY`] on the affected [window] itself and to clients
+/// selecting [`SUBSTRUCTURE_NOTIFY`] or [`SUBSTRUCTURE_REDIRECT`] on its
+/// parent. This trait distinguishes the [window] that the [event] was selected
+/// on from the [window] that the change happened to, so that window managers
+/// can handle these [events] generically.
+///
+/// [event]: Event
+/// [events]: Event
+/// [window]: Window
+///
+/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
+/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
+/// [`SUBSTRUCTURE_REDIRECT`]: crate::EventMask::SUBSTRUCTURE_REDIRECT
+pub trait HierarchyEvent {
+	/// The [window] on which this [event] was selected.
+	///
+	/// For [events] reported because of [`STRUCTURE_NOTIFY`], this is the
+	/// [`affected_window`] itself. Otherwise, this is the parent on which
+	/// [`SUBSTRUCTURE_NOTIFY`] or [`SUBSTRUCTURE_REDIRECT`] was selected.
+	///
+	/// [window]: Window
+	/// [event]: Event
+	/// [events]: Event
+	/// [`affected_window`]: HierarchyEvent::affected_window
+	///
+	/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
+	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
+	/// [`SUBSTRUCTURE_REDIRECT`]: crate::EventMask::SUBSTRUCTURE_REDIRECT
+	fn was_selected_on(&self) -> Window;
+
+	/// The [window] which was created, destroyed, mapped, reparented,
+	/// configured, etc.
+	///
+	/// [window]: Window
+	fn affected_window(&self) -> Window;
+}
+
+macro_rules! impl_hierarchy_event {
+	($($Event:ty => $selected_on:ident),+$(,)?) => {
+		$(
+			impl HierarchyEvent for $Event {
+				fn was_selected_on(&self) -> Window {
+					self.$selected_on
+				}
+
+				fn affected_window(&self) -> Window {
+					self.window
+				}
+			}
+		)+
+	};
+}
+
+impl_hierarchy_event! {
+	Create => parent,
+	Destroy => event_window,
+	Unmap => event_window,
+	Map => event_window,
+	MapWindowRequest => parent,
+	Reparent => event_window,
+	Configure => event_window,
+	ConfigureWindowRequest => parent,
+	Gravity => event_window,
+	ResizeRequest => window,
+	Circulate => event_window,
+	CirculateWindowRequest => parent,
+}
+
 /// Whether a `property` was [`Modified`] or [`Deleted`] in a [`Property`
 /// event].
 ///
 /// [`Property` event]: Property
 /// [`Modified`]: PropertyChange::Modified
 /// [`Deleted`]: PropertyChange::Deleted
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PropertyChange {
 	/// The `property` was added or its value was changed.
+	///
+	/// This is `NewValue` in the core protocol, which covers both newly added
+	/// properties and changes to existing ones. It is also reported for a
+	/// [`ModifyProperty` request] which appends or prepends no data, even
+	/// though the `property`'s value is unchanged.
+	///
+	/// [`ModifyProperty` request]: request::ModifyProperty
+	#[doc(alias = "NewValue")]
 	Modified,
 	/// The `property` was removed.
 	Deleted,
@@ -2054,7 +2236,8 @@ derive_xrb! {
 	/// [event]: Event
 	/// [window]: Window
 	/// [`PROPERTY_CHANGE`]: crate::EventMask::PROPERTY_CHANGE
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Property: Event(28) {
 		/// The [sequence number] associated with the last [request] related
@@ -2081,7 +2264,9 @@ derive_xrb! {
 		pub change: PropertyChange,
 		[_; ..],
 	}
+}
 
+derive_xrb! {
 	/// An [event] generated when a new selection owner is defined for a
 	/// selection.
 	///
@@ -2093,7 +2278,8 @@ derive_xrb! {
 	///
 	/// [event]: Event
 	/// [`SetSelectionOwner` request]: super::request::SetSelectionOwner
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct SelectionClear: Event(29) {
 		/// The [sequence number] associated with the last [request] related
@@ -2131,7 +2317,8 @@ derive_xrb! {
 	/// [`ConvertSelection` request]: super::request::ConvertSelection
 	/// [`Selection` event]: Selection
 	/// [`SendEvent` request]: super::request::SendEvent
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ConvertSelectionRequest: Event(30) {
 		/// The [sequence number] associated with the last [request] related
@@ -2160,7 +2347,17 @@ derive_xrb! {
 		pub selection: Atom,
 		/// The type that the `selection` should be converted into.
 		pub target_type: Atom,
-		// TODO: undocumented in core protocol, check ICCCM
+		/// The property of the `requester` in which the converted `selection`
+		/// should be stored.
+		///
+		/// If this is [`None`], the request was sent by an obsolete client:
+		/// the ICCCM specifies that the `target_type` should be used as the
+		/// property instead.
+		///
+		/// See [`SelectionOwnerHandler`] for a helper that responds to this
+		/// event following the ICCCM.
+		///
+		/// [`SelectionOwnerHandler`]: crate::icccm::SelectionOwnerHandler
 		pub property: Option<Atom>,
 		[_; ..],
 	}
@@ -2178,7 +2375,8 @@ derive_xrb! {
 	/// [event]: Event
 	/// [`ConvertSelection` request]: super::request::ConvertSelection
 	/// [`SendEvent` request]: super::request::SendEvent
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Selection: Event(31) {
 		/// The [sequence number] associated with the last [request] related
@@ -2207,7 +2405,11 @@ derive_xrb! {
 		///
 		/// The `selection` may or may not have been converted.
 		pub target_type: Atom,
-		// TODO: undocumented in core protocol, check ICCCM.
+		/// The property of the `requester` in which the converted `selection`
+		/// was stored.
+		///
+		/// If this is [`None`], the `selection` could not be converted to the
+		/// `target_type`.
 		pub property: Option<Atom>,
 		[_; ..],
 	}
@@ -2215,7 +2417,8 @@ derive_xrb! {
 	/// The reason why a [`Colormap` event] was generated.
 	///
 	/// [`Colormap` event]: Colormap
-	#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+	#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub enum ColormapDetail {
 		/// The `window`'s [`colormap` attribute] was changed.
 		///
@@ -2231,7 +2434,8 @@ derive_xrb! {
 	///
 	/// [window]: Window
 	/// [colormap]: crate::Colormap
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub enum ColormapState {
 		/// The [window]'s [colormap] is not currently installed.
 		///
@@ -2258,7 +2462,8 @@ derive_xrb! {
 	/// [`colormap` attribute]: crate::Attributes::colormap
 	///
 	/// [`COLORMAP_CHANGE`]: crate::EventMask::COLORMAP_CHANGE
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Colormap: Event(32) {
 		/// The [sequence number] associated with the last [request] related
@@ -2294,31 +2499,78 @@ derive_xrb! {
 }
 
 /// Used in the [`ClientMessage` event] to represent whether its `data` is 20
-/// `i8` values, 10 `i16` values, or 5 `i32` values.
+/// 8-bit values, 10 16-bit values, or 5 32-bit values.
 ///
 /// [`ClientMessage` event]: ClientMessage
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ClientMessageFormat {
-	/// 20 `i8` values: [`ClientMessageData::I8`].
-	I8 = 8,
-	/// 10 `i16` values: [`ClientMessageData::I16`].
-	I16 = 16,
-	/// 5 `i32` values: [`ClientMessageData::I32`].
-	I32 = 32,
+	/// 20 8-bit values: [`ClientMessageData::Bytes`].
+	Bytes = 8,
+	/// 10 16-bit values: [`ClientMessageData::Words`].
+	Words = 16,
+	/// 5 32-bit values: [`ClientMessageData::Longs`].
+	Longs = 32,
+}
+
+/// An error generated when the format of a [`ClientMessage` event] is not 8,
+/// 16, or 32.
+///
+/// [`ClientMessage` event]: ClientMessage
+#[derive(Error, Debug)]
+#[error("unrecognized client message format {0}: expected 8, 16, or 32")]
+pub struct UnrecognizedClientMessageFormat(pub u8);
+
+impl Readable for ClientMessageFormat {
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		match u8::read_from(buf)? {
+			8 => Ok(Self::Bytes),
+			16 => Ok(Self::Words),
+			32 => Ok(Self::Longs),
+
+			other => Err(ReadError::Other(Box::new(UnrecognizedClientMessageFormat(
+				other,
+			)))),
+		}
+	}
 }
 
 /// The `data` contained in a [`ClientMessage` event].
 ///
 /// [`ClientMessage` event]: ClientMessage
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[no_discrim]
 pub enum ClientMessageData {
-	/// Data comprised of 20 `i8` values.
-	I8([i8; 20]),
-	/// Data comprised of 10 `i16` values.
-	I16([i16; 10]),
-	/// Data comprised of 5 `i32` values.
-	I32([i32; 5]),
+	/// Data comprised of 20 8-bit values.
+	#[doc(alias("b"))]
+	Bytes([u8; 20]),
+	/// Data comprised of 10 16-bit values.
+	#[doc(alias("s"))]
+	Words([u16; 10]),
+	/// Data comprised of 5 32-bit values.
+	#[doc(alias("l"))]
+	Longs([u32; 5]),
+}
+
+impl ClientMessageData {
+	/// Returns the [format] of this data.
+	///
+	/// This is written as the `format` of a [`ClientMessage` event].
+	///
+	/// [format]: ClientMessageFormat
+	/// [`ClientMessage` event]: ClientMessage
+	#[must_use]
+	pub const fn format(&self) -> ClientMessageFormat {
+		match self {
+			Self::Bytes(_) => ClientMessageFormat::Bytes,
+			Self::Words(_) => ClientMessageFormat::Words,
+			Self::Longs(_) => ClientMessageFormat::Longs,
+		}
+	}
 }
 
 impl ConstantX11Size for ClientMessageData {
@@ -2339,9 +2591,9 @@ impl ReadableWithContext for ClientMessageData {
 		Self: Sized,
 	{
 		Ok(match format {
-			ClientMessageFormat::I8 => Self::I8(<_>::read_from(buf)?),
-			ClientMessageFormat::I16 => Self::I16(<_>::read_from(buf)?),
-			ClientMessageFormat::I32 => Self::I32(<_>::read_from(buf)?),
+			ClientMessageFormat::Bytes => Self::Bytes(<_>::read_from(buf)?),
+			ClientMessageFormat::Words => Self::Words(<_>::read_from(buf)?),
+			ClientMessageFormat::Longs => Self::Longs(<_>::read_from(buf)?),
 		})
 	}
 }
@@ -2356,7 +2608,8 @@ derive_xrb! {
 	/// [event]: Event
 	/// [`SendEvent` request]: super::request::SendEvent
 	/// [window]: Window
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ClientMessage: Event(33) {
 		/// The [sequence number] associated with the last [request] related
@@ -2369,13 +2622,9 @@ derive_xrb! {
 		#[derivative(PartialEq = "ignore", Hash = "ignore")]
 		pub sequence: u16,
 
-		/// Whether `data` is `[i8; 20]`, `[i16; 10]`, or `[i32; 5]`.
+		/// Whether `data` is `[u8; 20]`, `[u16; 10]`, or `[u32; 5]`.
 		#[metabyte]
-		let format: ClientMessageFormat = data => match data {
-			ClientMessageData::I8(_) => ClientMessageFormat::I8,
-			ClientMessageData::I16(_) => ClientMessageFormat::I16,
-			ClientMessageData::I32(_) => ClientMessageFormat::I32,
-		},
+		let format: ClientMessageFormat = data => data.format(),
 
 		/// The recipient of this `ClientMessage` event.
 		pub window: Window,
@@ -2390,11 +2639,33 @@ derive_xrb! {
 	}
 }
 
+impl ClientMessage {
+	/// Creates a `ClientMessage` event to be sent to `window` in a
+	/// [`SendEvent` request].
+	///
+	/// The `format` written for the event is derived from the variant of
+	/// `data`. The `sequence` is `0`: it is replaced by the X server when the
+	/// event is sent.
+	///
+	/// [`SendEvent` request]: super::request::SendEvent
+	#[must_use]
+	pub const fn new(window: Window, r#type: Atom, data: ClientMessageData) -> Self {
+		Self {
+			sequence: 0,
+
+			window,
+			r#type,
+			data,
+		}
+	}
+}
+
 /// Detail about which [request] generated a [`MappingChange` event].
 ///
 /// [request]: crate::message::Request
 /// [`MappingChange` event]: MappingChange
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MappingRequest {
 	/// The [`MappingChange` event] was generated by a
 	/// [`SetModifierMapping` request].
@@ -2429,7 +2700,8 @@ derive_xrb! {
 	/// [`SetModifierMapping`]: super::request::SetModifierMapping
 	/// [`ChangeKeyboardMapping`]: super::request::ChangeKeyboardMapping
 	/// [`SetCursorMapping`]: super::request::SetButtonMapping
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, ConstantX11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct MappingChange: Event(34) {
 		/// The [sequence number] associated with the last [request] related
@@ -2464,3 +2736,752 @@ derive_xrb! {
 		[_; ..],
 	}
 }
+
+derive_xrb! {
+	/// An [event] defined by an extension, which may be longer than 32 bytes.
+	///
+	/// Extensions send their [events] as `GenericEvent`s, identified by the
+	/// `extension`'s [major opcode] and an `event_type` defined by that
+	/// extension. See [`GenericExtensionEvent`] for decoding the `data` of a
+	/// `GenericEvent` as a particular extension [event].
+	///
+	/// # Recipients
+	/// This [event] is reported to clients which have selected the
+	/// corresponding extension [event].
+	///
+	/// [event]: Event
+	/// [events]: Event
+	/// [major opcode]: crate::message::Request::MAJOR_OPCODE
+	/// [`GenericExtensionEvent`]: crate::message::GenericExtensionEvent
+	#[doc(alias("GeGeneric", "XGE"))]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[derivative(Hash, PartialEq, Eq)]
+	pub struct GenericEvent: Event(35) {
+		/// The [sequence number] associated with the last [request] related
+		/// to this [event] that was received before this [event] was generated.
+		///
+		/// [sequence number]: Event::sequence
+		/// [request]: crate::message::Request
+		/// [event]: Event
+		#[sequence]
+		#[derivative(PartialEq = "ignore", Hash = "ignore")]
+		pub sequence: u16,
+
+		/// The [major opcode] of the extension which defines this [event].
+		///
+		/// [major opcode]: crate::message::Request::MAJOR_OPCODE
+		/// [event]: Event
+		#[metabyte]
+		pub extension: u8,
+
+		// The number of 4-byte units of `data` beyond the first 32 bytes of
+		// this event.
+		#[allow(clippy::cast_possible_truncation)]
+		let length: u32 = data => GenericEvent::length_for(data.len()) as u32,
+
+		/// The type of this [event], as defined by the `extension`.
+		///
+		/// [event]: Event
+		#[doc(alias("evtype"))]
+		pub event_type: u16,
+
+		/// The data of this [event], following the `event_type`.
+		///
+		/// This is always at least [`MIN_DATA_LEN`] bytes long when read. If
+		/// it is not a whole number of 4-byte units beyond that when written,
+		/// it is followed by zeroed padding.
+		///
+		/// [event]: Event
+		/// [`MIN_DATA_LEN`]: GenericEvent::MIN_DATA_LEN
+		#[context(length => GenericEvent::data_len(*length))]
+		pub data: Vec<u8>,
+		[_; data => GenericEvent::padding_for(data.len())],
+	}
+}
+
+impl GenericEvent {
+	/// The number of bytes of `data` contained within the first 32 bytes of a
+	/// `GenericEvent`.
+	pub const MIN_DATA_LEN: usize = 22;
+
+	/// Returns the number of 4-byte units needed for `data_len` bytes of
+	/// `data` beyond the first 32 bytes of a `GenericEvent`.
+	const fn length_for(data_len: usize) -> usize {
+		data_len.saturating_sub(Self::MIN_DATA_LEN).div_ceil(4)
+	}
+
+	/// Returns the number of bytes of `data` in a `GenericEvent` with the
+	/// given `length`.
+	const fn data_len(length: u32) -> usize {
+		Self::MIN_DATA_LEN + (length as usize) * 4
+	}
+
+	/// Returns the number of bytes of padding written after `data_len` bytes
+	/// of `data`.
+	const fn padding_for(data_len: usize) -> usize {
+		Self::MIN_DATA_LEN + Self::length_for(data_len) * 4 - data_len
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use xrbk::{ReadError, ReadMode, Verbatim, Writable};
+
+	fn key_press() -> KeyPress {
+		KeyPress {
+			sequence: 7,
+			keycode: Keycode::new(38),
+			time: Timestamp::new(1_000),
+			root: Window::new(0x0000_0512),
+			event_window: Window::new(0x0060_0001),
+			child_window: None,
+			root_coords: Coords::new(Px(640), Px(480)),
+			event_coords: Coords::new(Px(20), Px(-4)),
+			modifiers: ModifierMask::SHIFT | ModifierMask::CONTROL,
+			same_screen: true,
+		}
+	}
+
+	#[test]
+	fn test_truncated_key_press() {
+		let mut bytes = vec![];
+		key_press().write_to(&mut bytes).unwrap();
+
+		// The event code is read before the event itself.
+		let bytes = &bytes[1..];
+
+		assert_eq!(KeyPress::read_from(&mut &bytes[..]).unwrap(), key_press());
+
+		for len in 0..bytes.len() {
+			assert!(
+				matches!(
+					KeyPress::read_from(&mut &bytes[..len]),
+					Err(ReadError::NotEnoughData { .. }),
+				),
+				"reading {len} of {} bytes should fail",
+				bytes.len(),
+			);
+		}
+	}
+
+	/// Returns `key_press()` as bytes, excluding its code, with its final
+	/// unused byte set to `0xa5`.
+	fn key_press_with_non_zero_padding() -> Vec<u8> {
+		let mut bytes = vec![];
+		key_press().write_to(&mut bytes).unwrap();
+
+		*bytes.last_mut().unwrap() = 0xa5;
+
+		bytes.split_off(1)
+	}
+
+	#[test]
+	fn test_strict_rejects_non_zero_padding() {
+		let bytes = key_press_with_non_zero_padding();
+
+		assert_eq!(
+			KeyPress::read_from_with_mode(&mut &bytes[..], ReadMode::Lenient).unwrap(),
+			key_press(),
+		);
+		assert!(matches!(
+			KeyPress::read_from_with_mode(&mut &bytes[..], ReadMode::Strict),
+			Err(ReadError::NonZeroPadding(0xa5)),
+		));
+
+		// Zeroed padding is accepted in strict mode.
+		let mut zeroed = vec![];
+		key_press().write_to(&mut zeroed).unwrap();
+
+		assert_eq!(
+			KeyPress::read_from_with_mode(&mut &zeroed[1..], ReadMode::Strict).unwrap(),
+			key_press(),
+		);
+	}
+
+	#[test]
+	fn test_verbatim_preserves_non_zero_padding() {
+		let bytes = key_press_with_non_zero_padding();
+
+		let verbatim = <Verbatim<KeyPress>>::read_with_prefix(
+			&[KeyPress::CODE],
+			&mut &bytes[..],
+			ReadMode::Lenient,
+		)
+		.unwrap();
+		assert_eq!(*verbatim, key_press());
+		assert_eq!(verbatim.bytes(), bytes);
+
+		// The whole event is written, including its code.
+		let mut message = vec![KeyPress::CODE];
+		message.extend_from_slice(&bytes);
+
+		let mut written = vec![];
+		verbatim.write_to(&mut written).unwrap();
+		assert_eq!(written, message);
+		assert_eq!(verbatim.x11_size(), KeyPress::X11_SIZE);
+
+		// Writing the value itself zeroes the padding.
+		let mut written = vec![];
+		verbatim.into_inner().write_to(&mut written).unwrap();
+		assert_eq!(written.len(), message.len());
+		assert_eq!(written[..written.len() - 1], message[..message.len() - 1]);
+		assert_eq!(written.last(), Some(&0));
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn test_key_press_json_round_trip() {
+		let key_press = key_press();
+
+		let json = serde_json::to_string(&key_press).unwrap();
+		// Newtypes and masks are serialized as plain integers.
+		assert!(json.contains(r#""keycode":38"#));
+		assert!(json.contains(r#""root":1298"#));
+		assert!(json.contains(r#""modifiers":5"#));
+
+		let deserialized: KeyPress = serde_json::from_str(&json).unwrap();
+
+		assert_eq!(deserialized, key_press);
+		assert_eq!(deserialized.sequence, key_press.sequence);
+
+		// Bits which do not correspond to any modifier are rejected.
+		assert!(serde_json::from_str::<ModifierMask>("16384").is_err());
+	}
+
+	#[cfg(feature = "trace")]
+	#[test]
+	fn test_key_press_trace_covers_every_byte() {
+		let trace = xrbk::Trace::write(&key_press()).unwrap();
+
+		assert_eq!(trace.len(), 32);
+
+		let mut end = 0;
+		for field in trace.fields() {
+			assert_eq!(field.range().start, end, "gap before `{}`", field.name());
+			end = field.range().end;
+		}
+		assert_eq!(end, 32);
+
+		let names: Vec<_> = trace.fields().iter().map(xrbk::TraceField::name).collect();
+		assert_eq!(
+			names,
+			[
+				"code",
+				"keycode",
+				"sequence",
+				"time",
+				"root",
+				"event_window",
+				"child_window",
+				"root_coords",
+				"event_coords",
+				"modifiers",
+				"same_screen",
+				"unused",
+			],
+		);
+
+		let unused = trace.fields().last().unwrap();
+		assert_eq!(unused.range(), 31..32);
+		assert!(unused.is_unused());
+
+		let dump = trace.to_string();
+		assert_eq!(dump.lines().count(), 12);
+		assert!(dump.contains("0x0002..0x0004  00 07"));
+		assert!(dump.lines().last().unwrap().ends_with("(unused)"));
+	}
+
+	#[cfg(feature = "trace")]
+	#[test]
+	fn test_key_press_trace_shows_received_bytes() {
+		let mut bytes = vec![KeyPress::CODE];
+		bytes.extend(key_press_with_non_zero_padding());
+
+		let (key_press, trace) = xrbk::Trace::read::<KeyPress>(&bytes).unwrap();
+
+		assert_eq!(key_press, self::key_press());
+		assert_eq!(trace.bytes(), bytes);
+		assert!(trace.to_string().lines().last().unwrap().contains("a5"));
+	}
+
+	#[test]
+	fn test_configure_window_request() {
+		let event = ConfigureWindowRequest {
+			sequence: 3,
+			stack_mode: StackMode::Opposite,
+			parent: Window::new(0x0000_0512),
+			window: Window::new(0x0060_0001),
+			sibling: None,
+			geometry: Rectangle {
+				x: Px(-10),
+				y: Px(20),
+				width: Px(640),
+				height: Px(480),
+			},
+			border_width: Px(2),
+			mask: WindowConfigMask::WIDTH | WindowConfigMask::BORDER_WIDTH,
+		};
+
+		let mut bytes = vec![];
+		event.write_to(&mut bytes).unwrap();
+
+		#[rustfmt::skip]
+		assert_eq!(bytes, [
+			23, 4, 0, 3,
+			// `parent`.
+			0x00, 0x00, 0x05, 0x12,
+			// `window`.
+			0x00, 0x60, 0x00, 0x01,
+			// `sibling`.
+			0x00, 0x00, 0x00, 0x00,
+			// `geometry`.
+			0xff, 0xf6, 0x00, 0x14, 0x02, 0x80, 0x01, 0xe0,
+			// `border_width` and `mask`.
+			0x00, 0x02, 0x00, 0x14,
+			0, 0, 0, 0,
+		]);
+
+		assert_eq!(
+			ConfigureWindowRequest::read_from(&mut &bytes[1..]).unwrap(),
+			event
+		);
+	}
+
+	#[test]
+	fn test_synthetic_configure() {
+		let event = Configure::synthetic(
+			Window::new(0x0060_0001),
+			Rectangle {
+				x: Px(100),
+				y: Px(-20),
+				width: Px(800),
+				height: Px(600),
+			},
+			Px(1),
+			Some(Window::new(0x0040_0007)),
+		);
+
+		let mut bytes = vec![];
+		event.write_to(&mut bytes).unwrap();
+
+		// The `xConfigureNotify` written by Xlib's `XSendEvent` for an
+		// `XConfigureEvent` with the same fields and a serial of `0`.
+		#[rustfmt::skip]
+		assert_eq!(bytes, [
+			22, 0, 0, 0,
+			// `event_window`.
+			0x00, 0x60, 0x00, 0x01,
+			// `window`.
+			0x00, 0x60, 0x00, 0x01,
+			// `sibling_below`.
+			0x00, 0x40, 0x00, 0x07,
+			// `geometry`.
+			0x00, 0x64, 0xff, 0xec, 0x03, 0x20, 0x02, 0x58,
+			// `border_width` and `override_redirect`.
+			0x00, 0x01, 0,
+			0, 0, 0, 0, 0,
+		]);
+
+		assert_eq!(Configure::read_from(&mut &bytes[1..]).unwrap(), event);
+	}
+
+	#[test]
+	fn test_client_message_format() {
+		let window = Window::new(0x0060_0001);
+		let r#type = Atom::new(0x0000_0123);
+
+		for (data, format) in [
+			(ClientMessageData::Bytes([1; 20]), 8),
+			(ClientMessageData::Words([1; 10]), 16),
+			(ClientMessageData::Longs([1; 5]), 32),
+		] {
+			let event = ClientMessage::new(window, r#type, data);
+
+			let mut bytes = vec![];
+			event.write_to(&mut bytes).unwrap();
+
+			assert_eq!(bytes[..4], [33, format, 0, 0]);
+			assert_eq!(ClientMessage::read_from(&mut &bytes[1..]).unwrap(), event);
+		}
+	}
+
+	#[test]
+	fn test_wm_delete_window_client_message() {
+		// The `WM_PROTOCOLS` and `WM_DELETE_WINDOW` atoms and a timestamp.
+		let (protocols, delete_window, time) = (0x0000_01a4, 0x0000_01a5, 0x0012_d687);
+
+		let event = ClientMessage::new(
+			Window::new(0x0060_0001),
+			Atom::new(protocols),
+			ClientMessageData::Longs([delete_window, time, 0, 0, 0]),
+		);
+
+		let mut bytes = vec![];
+		event.write_to(&mut bytes).unwrap();
+
+		#[rustfmt::skip]
+		assert_eq!(bytes, [
+			33, 32, 0, 0,
+			// `window`.
+			0x00, 0x60, 0x00, 0x01,
+			// `type`.
+			0x00, 0x00, 0x01, 0xa4,
+			// `data`.
+			0x00, 0x00, 0x01, 0xa5,
+			0x00, 0x12, 0xd6, 0x87,
+			0, 0, 0, 0,
+			0, 0, 0, 0,
+			0, 0, 0, 0,
+		]);
+	}
+
+	#[test]
+	fn test_generic_event_of_32_bytes() {
+		let event = GenericEvent {
+			sequence: 3,
+			extension: 131,
+			event_type: 2,
+			data: (0..22).collect(),
+		};
+
+		let mut bytes = vec![];
+		event.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32);
+		#[rustfmt::skip]
+		assert_eq!(bytes[..10], [
+			35, 131, 0, 3,
+			// `length`.
+			0, 0, 0, 0,
+			// `event_type`.
+			0, 2,
+		]);
+
+		// The event code is read before the event itself.
+		assert_eq!(GenericEvent::read_from(&mut &bytes[1..]).unwrap(), event);
+	}
+
+	#[test]
+	fn test_generic_event_longer_than_32_bytes() {
+		let event = GenericEvent {
+			sequence: 3,
+			extension: 131,
+			event_type: 2,
+			data: (0..30).collect(),
+		};
+
+		let mut bytes = vec![];
+		event.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 40);
+		assert_eq!(bytes[4..8], [0, 0, 0, 2]);
+
+		// Only the event's own bytes are consumed, not the following event.
+		bytes.extend_from_slice(&[0xff; 32]);
+		let buf = &mut &bytes[1..];
+
+		assert_eq!(GenericEvent::read_from(buf).unwrap(), event);
+		assert_eq!(buf.len(), 32);
+	}
+
+	#[test]
+	fn test_generic_event_pads_data() {
+		let event = GenericEvent {
+			sequence: 3,
+			extension: 131,
+			event_type: 2,
+			data: vec![1; 25],
+		};
+
+		let mut bytes = vec![];
+		event.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 36);
+		assert_eq!(event.x11_size(), 36);
+		assert_eq!(bytes[4..8], [0, 0, 0, 1]);
+
+		let read = GenericEvent::read_from(&mut &bytes[1..]).unwrap();
+		assert_eq!(read.data[..25], event.data);
+		assert_eq!(read.data[25..], [0; 1]);
+	}
+
+	fn client_message(data: ClientMessageData) -> ClientMessage {
+		ClientMessage {
+			sequence: 3,
+			window: Window::new(0x0060_0001),
+			r#type: Atom::new(0x0000_0145),
+			data,
+		}
+	}
+
+	/// Writes the given `data` in a `ClientMessage` event, checks its format
+	/// byte, and reads it back.
+	fn round_trip_client_message(data: ClientMessageData, format: u8) {
+		let message = client_message(data);
+
+		let mut bytes = vec![];
+		message.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32);
+		assert_eq!(bytes[..2], [33, format]);
+
+		// The event code is read before the event itself.
+		assert_eq!(ClientMessage::read_from(&mut &bytes[1..]).unwrap(), message);
+	}
+
+	#[test]
+	fn test_client_message_bytes() {
+		round_trip_client_message(
+			ClientMessageData::Bytes([
+				0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0x7f, 0x80, 0x81, 0xa5, 0xc0, 0xd0, 0xe0, 0xf0, 0xfe,
+				0xff,
+			]),
+			8,
+		);
+	}
+
+	#[test]
+	fn test_client_message_words() {
+		round_trip_client_message(
+			ClientMessageData::Words([1, 2, 3, 0x8000, 0xffff, 6, 7, 8, 9, 10]),
+			16,
+		);
+	}
+
+	#[test]
+	fn test_client_message_longs() {
+		round_trip_client_message(
+			ClientMessageData::Longs([1, 0x0000_012d, 0x8000_0000, 0xffff_ffff, 0]),
+			32,
+		);
+	}
+
+	#[test]
+	fn test_client_message_unrecognized_format() {
+		let mut bytes = vec![];
+		client_message(ClientMessageData::Longs([0; 5]))
+			.write_to(&mut bytes)
+			.unwrap();
+		bytes[1] = 24;
+
+		match ClientMessage::read_from(&mut &bytes[1..]) {
+			Err(ReadError::Other(error)) => assert_eq!(
+				error.to_string(),
+				"unrecognized client message format 24: expected 8, 16, or 32",
+			),
+
+			other => panic!("expected an unrecognized format error, found {other:?}"),
+		}
+	}
+
+	#[test]
+	fn test_hierarchy_event() {
+		fn windows(event: &impl HierarchyEvent) -> (Window, Window) {
+			(event.was_selected_on(), event.affected_window())
+		}
+
+		let root = Window::new(0x0000_0512);
+		let window = Window::new(0x0060_0001);
+
+		let create = Create {
+			sequence: 1,
+			parent: root,
+			window,
+			geometry: Rectangle::new(Px(0), Px(0), Px(100), Px(100)),
+			border_width: Px(0),
+			override_redirect: false,
+		};
+		assert_eq!(windows(&create), (root, window));
+
+		let reparent = Reparent {
+			sequence: 2,
+			event_window: window,
+			window,
+			new_parent: root,
+			coords: Coords::new(Px(10), Px(10)),
+			override_redirect: false,
+		};
+		assert_eq!(windows(&reparent), (window, window));
+
+		let circulate = Circulate {
+			sequence: 3,
+			event_window: root,
+			window,
+			placement: Placement::Top,
+		};
+		assert_eq!(windows(&circulate), (root, window));
+
+		let resize = ResizeRequest {
+			sequence: 4,
+			window,
+			width: Px(200),
+			height: Px(100),
+		};
+		assert_eq!(windows(&resize), (window, window));
+	}
+
+	/// Reads an event of type `E` from a `fixture` of its bytes in
+	/// [`ReadMode::Strict`], asserting that writing it produces the same bytes.
+	fn read_fixture<E>(fixture: &[u8]) -> E
+	where
+		E: Readable + Writable,
+	{
+		// The event code is read before the event itself.
+		let event = E::read_from_with_mode(&mut &fixture[1..], ReadMode::Strict).unwrap();
+
+		let mut written = vec![];
+		event.write_to(&mut written).unwrap();
+		assert_eq!(written, fixture);
+
+		event
+	}
+
+	#[test]
+	fn test_destroy_fixture() {
+		#[rustfmt::skip]
+		let bytes = [
+			17, 0, 0x00, 0x2a, // header
+			0x00, 0x00, 0x05, 0x12, // event window
+			0x00, 0x60, 0x00, 0x01, // window
+			0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+		];
+
+		let event: Destroy = read_fixture(&bytes);
+
+		assert_eq!(event.sequence, 42);
+		assert_eq!(event.event_window, Window::new(0x0000_0512));
+		assert_eq!(event.window, Window::new(0x0060_0001));
+	}
+
+	#[test]
+	fn test_unmap_fixture() {
+		#[rustfmt::skip]
+		let bytes = [
+			18, 0, 0x00, 0x2b, // header
+			0x00, 0x00, 0x05, 0x12, // event window
+			0x00, 0x60, 0x00, 0x01, // window
+			1, // from configure
+			0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+		];
+
+		let event: Unmap = read_fixture(&bytes);
+
+		assert_eq!(event.sequence, 43);
+		assert_eq!(event.event_window, Window::new(0x0000_0512));
+		assert_eq!(event.window, Window::new(0x0060_0001));
+		assert!(event.from_configure);
+	}
+
+	#[test]
+	fn test_map_fixture() {
+		#[rustfmt::skip]
+		let bytes = [
+			19, 0, 0x00, 0x2c, // header
+			0x00, 0x60, 0x00, 0x01, // event window
+			0x00, 0x60, 0x00, 0x01, // window
+			1, // override redirect
+			0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+		];
+
+		let event: Map = read_fixture(&bytes);
+
+		assert_eq!(event.sequence, 44);
+		assert_eq!(event.event_window, Window::new(0x0060_0001));
+		assert_eq!(event.window, Window::new(0x0060_0001));
+		assert!(event.override_redirect);
+	}
+
+	#[test]
+	fn test_reparent_fixture() {
+		#[rustfmt::skip]
+		let bytes = [
+			21, 0, 0x00, 0x2d, // header
+			0x00, 0x00, 0x05, 0x12, // event window
+			0x00, 0x60, 0x00, 0x01, // window
+			0x00, 0x40, 0x00, 0x07, // new parent
+			0xff, 0xfe, 0x00, 0x16, // x, y
+			0, // override redirect
+			0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+		];
+
+		let event: Reparent = read_fixture(&bytes);
+
+		assert_eq!(event.sequence, 45);
+		assert_eq!(event.event_window, Window::new(0x0000_0512));
+		assert_eq!(event.window, Window::new(0x0060_0001));
+		assert_eq!(event.new_parent, Window::new(0x0040_0007));
+		assert_eq!(event.coords, Coords::new(Px(-2), Px(22)));
+		assert!(!event.override_redirect);
+	}
+
+	#[test]
+	fn test_gravity_fixture() {
+		#[rustfmt::skip]
+		let bytes = [
+			24, 0, 0x00, 0x2e, // header
+			0x00, 0x40, 0x00, 0x07, // event window
+			0x00, 0x60, 0x00, 0x01, // window
+			0x01, 0x2c, 0x00, 0xc8, // x, y
+			0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+		];
+
+		let event: Gravity = read_fixture(&bytes);
+
+		assert_eq!(event.sequence, 46);
+		assert_eq!(event.event_window, Window::new(0x0040_0007));
+		assert_eq!(event.window, Window::new(0x0060_0001));
+		assert_eq!(event.coords, Coords::new(Px(300), Px(200)));
+	}
+
+	#[test]
+	fn test_circulate_fixture() {
+		#[rustfmt::skip]
+		let bytes = [
+			26, 0, 0x00, 0x2f, // header
+			0x00, 0x00, 0x05, 0x12, // event window
+			0x00, 0x60, 0x00, 0x01, // window
+			0, 0, 0, 0, // unused window
+			1, // placement
+			0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+		];
+
+		let event: Circulate = read_fixture(&bytes);
+
+		assert_eq!(event.sequence, 47);
+		assert_eq!(event.event_window, Window::new(0x0000_0512));
+		assert_eq!(event.window, Window::new(0x0060_0001));
+		assert_eq!(event.placement, Placement::Bottom);
+
+		// Anything but zero in the unused window field is rejected when
+		// reading strictly, so a shifted `placement` would not go unnoticed.
+		let mut bytes = bytes;
+		bytes[12] = 1;
+
+		assert!(matches!(
+			Circulate::read_from_with_mode(&mut &bytes[1..], ReadMode::Strict),
+			Err(ReadError::NonZeroPadding(1)),
+		));
+	}
+
+	#[test]
+	fn test_circulate_window_request_fixture() {
+		#[rustfmt::skip]
+		let bytes = [
+			27, 0, 0x00, 0x30, // header
+			0x00, 0x00, 0x05, 0x12, // parent
+			0x00, 0x60, 0x00, 0x01, // window
+			0, 0, 0, 0, // unused
+			0, // placement
+			0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+		];
+
+		let event: CirculateWindowRequest = read_fixture(&bytes);
+
+		assert_eq!(event.sequence, 48);
+		assert_eq!(event.parent, Window::new(0x0000_0512));
+		assert_eq!(event.window, Window::new(0x0060_0001));
+		assert_eq!(event.placement, Placement::Top);
+	}
+}
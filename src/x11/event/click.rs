@@ -0,0 +1,335 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Classification of [`ButtonPress`] and [`ButtonRelease`] events as clicks,
+//! double-clicks, triple-clicks, and drags.
+
+use std::time::Duration;
+
+use super::*;
+
+/// The classification of a [mouse button] being pressed and released, as
+/// returned by a [`ClickDetector`].
+///
+/// [mouse button]: Button
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ClickType {
+	/// The [mouse button] was clicked once.
+	///
+	/// [mouse button]: Button
+	Click,
+	/// The [mouse button] was clicked a second time in quick succession.
+	///
+	/// [mouse button]: Button
+	DoubleClick,
+	/// The [mouse button] was clicked a third time in quick succession.
+	///
+	/// A following click begins a new series of clicks.
+	///
+	/// [mouse button]: Button
+	TripleClick,
+
+	/// The cursor moved further than the [`ClickDetector`]'s `slop` while the
+	/// [mouse button] was held.
+	///
+	/// [mouse button]: Button
+	Drag,
+}
+
+/// A series of clicks of the same [mouse button] in the same [window].
+///
+/// [mouse button]: Button
+/// [window]: Window
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+struct ClickChain {
+	button: Button,
+	event_window: Window,
+
+	/// The coordinates of the first press in the series.
+	origin: Coords,
+	/// The time of the last press in the series.
+	time: Timestamp,
+	/// The number of presses in the series.
+	count: u8,
+
+	/// The coordinates of the press if the [mouse button] is still held.
+	///
+	/// [mouse button]: Button
+	pressed: Option<Coords>,
+}
+
+/// Classifies [`ButtonPress`] and [`ButtonRelease`] events as clicks,
+/// double-clicks, triple-clicks, and drags.
+///
+/// Successive clicks form a series if each press is of the same `button` and
+/// in the same `event_window` as the last, happens within the `threshold` of
+/// the last press, and is within the `slop` of the first press. Timestamps
+/// are compared with [`Timestamp::duration_since`], so a series may span the
+/// time wrapping around.
+///
+/// A release further than the `slop` from its press is a [`Drag`], which ends
+/// the series.
+///
+/// [`Drag`]: ClickType::Drag
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClickDetector {
+	/// The longest time between two presses for them to be part of the same
+	/// series of clicks.
+	pub threshold: Duration,
+	/// The furthest the cursor may move from the first press of a series of
+	/// clicks, measured as a radius in pixels.
+	pub slop: Px<u16>,
+
+	chain: Option<ClickChain>,
+}
+
+impl ClickDetector {
+	/// Creates a new `ClickDetector` with the given `threshold` and `slop`.
+	#[must_use]
+	pub const fn new(threshold: Duration, slop: Px<u16>) -> Self {
+		Self {
+			threshold,
+			slop,
+
+			chain: None,
+		}
+	}
+
+	/// Returns whether the distance between `from` and `to` is within the
+	/// `slop`.
+	fn within_slop(&self, from: Coords, to: Coords) -> bool {
+		let x = i64::from(to.x.0) - i64::from(from.x.0);
+		let y = i64::from(to.y.0) - i64::from(from.y.0);
+		let slop = i64::from(self.slop.0);
+
+		x * x + y * y <= slop * slop
+	}
+
+	/// Records the given [`ButtonPress` event].
+	///
+	/// The press continues the current series of clicks if it can; otherwise,
+	/// it begins a new one.
+	///
+	/// [`ButtonPress` event]: ButtonPress
+	pub fn press(&mut self, event: &ButtonPress) {
+		let continues = |chain: &ClickChain| {
+			chain.count < 3
+				&& chain.button == event.button
+				&& chain.event_window == event.event_window
+				&& self.within_slop(chain.origin, event.event_coords)
+				&& event
+					.time
+					.duration_since(chain.time)
+					.is_some_and(|elapsed| elapsed <= self.threshold)
+		};
+
+		self.chain = Some(match self.chain {
+			Some(chain) if continues(&chain) => ClickChain {
+				time: event.time,
+				count: chain.count + 1,
+				pressed: Some(event.event_coords),
+
+				..chain
+			},
+
+			_ => ClickChain {
+				button: event.button,
+				event_window: event.event_window,
+
+				origin: event.event_coords,
+				time: event.time,
+				count: 1,
+
+				pressed: Some(event.event_coords),
+			},
+		});
+	}
+
+	/// Classifies the given [`ButtonRelease` event].
+	///
+	/// Returns [`None`] if the released `button` was not recorded as pressed
+	/// in the `event_window`.
+	///
+	/// [`ButtonRelease` event]: ButtonRelease
+	pub fn release(&mut self, event: &ButtonRelease) -> Option<ClickType> {
+		let chain = self.chain.as_mut().filter(|chain| {
+			chain.button == event.button && chain.event_window == event.event_window
+		})?;
+		let pressed = chain.pressed.take()?;
+		let count = chain.count;
+
+		if !self.within_slop(pressed, event.event_coords) {
+			self.chain = None;
+
+			return Some(ClickType::Drag);
+		}
+
+		Some(match count {
+			1 => ClickType::Click,
+			2 => ClickType::DoubleClick,
+			_ => ClickType::TripleClick,
+		})
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn button_press(button: u8, window: u32, time: u32, x: i16, y: i16) -> ButtonPress {
+		ButtonPress {
+			sequence: 1,
+			button: Button::new(button),
+			time: Timestamp::new(time),
+			root: Window::new(0x0000_0500),
+			event_window: Window::new(window),
+			child_window: None,
+			root_coords: Coords::new(Px(x), Px(y)),
+			event_coords: Coords::new(Px(x), Px(y)),
+			modifiers: ModifierMask::empty(),
+			same_screen: true,
+		}
+	}
+
+	fn button_release(button: u8, window: u32, time: u32, x: i16, y: i16) -> ButtonRelease {
+		ButtonRelease {
+			sequence: 1,
+			button: Button::new(button),
+			time: Timestamp::new(time),
+			root: Window::new(0x0000_0500),
+			event_window: Window::new(window),
+			child_window: None,
+			root_coords: Coords::new(Px(x), Px(y)),
+			event_coords: Coords::new(Px(x), Px(y)),
+			modifiers: ModifierMask::empty(),
+			same_screen: true,
+		}
+	}
+
+	fn click_detector() -> ClickDetector {
+		ClickDetector::new(Duration::from_millis(400), Px(4))
+	}
+
+	/// Presses and releases the `button` at the given `time` and coordinates.
+	fn click(
+		detector: &mut ClickDetector, button: u8, window: u32, time: u32, x: i16, y: i16,
+	) -> Option<ClickType> {
+		detector.press(&button_press(button, window, time, x, y));
+		detector.release(&button_release(button, window, time + 50, x, y))
+	}
+
+	#[test]
+	fn test_click_detector_chain() {
+		let mut detector = click_detector();
+
+		assert_eq!(
+			click(&mut detector, 1, 1, 1_000, 10, 10),
+			Some(ClickType::Click)
+		);
+		assert_eq!(
+			click(&mut detector, 1, 1, 1_300, 12, 11),
+			Some(ClickType::DoubleClick)
+		);
+		assert_eq!(
+			click(&mut detector, 1, 1, 1_600, 9, 13),
+			Some(ClickType::TripleClick)
+		);
+		// A fourth click begins a new series.
+		assert_eq!(
+			click(&mut detector, 1, 1, 1_900, 10, 10),
+			Some(ClickType::Click)
+		);
+
+		// Too long after the last press.
+		assert_eq!(
+			click(&mut detector, 1, 1, 2_301, 10, 10),
+			Some(ClickType::Click)
+		);
+	}
+
+	#[test]
+	fn test_click_detector_resets_chain() {
+		let mut detector = click_detector();
+
+		// A different button.
+		assert_eq!(
+			click(&mut detector, 1, 1, 1_000, 10, 10),
+			Some(ClickType::Click)
+		);
+		assert_eq!(
+			click(&mut detector, 3, 1, 1_100, 10, 10),
+			Some(ClickType::Click)
+		);
+
+		// A different window.
+		assert_eq!(
+			click(&mut detector, 3, 2, 1_200, 10, 10),
+			Some(ClickType::Click)
+		);
+
+		// Further than the slop from the first press.
+		assert_eq!(
+			click(&mut detector, 3, 2, 1_300, 13, 13),
+			Some(ClickType::Click)
+		);
+		assert_eq!(
+			click(&mut detector, 3, 2, 1_400, 13, 17),
+			Some(ClickType::DoubleClick)
+		);
+		assert_eq!(
+			click(&mut detector, 3, 2, 1_500, 13, 18),
+			Some(ClickType::Click)
+		);
+
+		// A release without a press is not classified.
+		assert_eq!(detector.release(&button_release(3, 2, 1_600, 13, 18)), None);
+		assert_eq!(detector.release(&button_release(2, 2, 1_600, 13, 18)), None);
+	}
+
+	#[test]
+	fn test_click_detector_drag() {
+		let mut detector = click_detector();
+
+		detector.press(&button_press(1, 1, 1_000, 10, 10));
+		assert_eq!(
+			detector.release(&button_release(1, 1, 1_050, 30, 10)),
+			Some(ClickType::Drag)
+		);
+
+		// A drag ends the series.
+		assert_eq!(
+			click(&mut detector, 1, 1, 1_100, 30, 10),
+			Some(ClickType::Click)
+		);
+		detector.press(&button_press(1, 1, 1_200, 30, 10));
+		assert_eq!(
+			detector.release(&button_release(1, 1, 1_250, 25, 10)),
+			Some(ClickType::Drag)
+		);
+		assert_eq!(
+			click(&mut detector, 1, 1, 1_300, 30, 10),
+			Some(ClickType::Click)
+		);
+	}
+
+	#[test]
+	fn test_click_detector_time_wraps_around() {
+		let mut detector = click_detector();
+
+		assert_eq!(
+			click(&mut detector, 1, 1, u32::MAX - 200, 10, 10),
+			Some(ClickType::Click)
+		);
+		assert_eq!(
+			click(&mut detector, 1, 1, 100, 10, 10),
+			Some(ClickType::DoubleClick)
+		);
+
+		// A press which is earlier than the last begins a new series.
+		assert_eq!(
+			click(&mut detector, 1, 1, u32::MAX - 100, 10, 10),
+			Some(ClickType::Click)
+		);
+	}
+}
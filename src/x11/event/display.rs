@@ -0,0 +1,672 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! One-line [`Display`] summaries of [events], intended for logging.
+//!
+//! Each summary starts with the name of the [event], followed by its most
+//! relevant fields as `name=value` pairs. [Windows] and other resource IDs are
+//! displayed in hexadecimal, positions as `@(x,y)`, and rectangles as
+//! `WxH@(x,y)`.
+//!
+//! [events]: crate::message::Event
+//! [event]: crate::message::Event
+//! [Windows]: Window
+
+use std::fmt::{Debug, Display, Formatter, Result};
+
+use super::*;
+
+/// Displays a resource ID in hexadecimal.
+struct Id(u32);
+
+impl Display for Id {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(f, "{:#x}", self.0)
+	}
+}
+
+/// Displays an optional resource ID in hexadecimal, or `none`.
+struct OptionId(Option<u32>);
+
+impl Display for OptionId {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		match self.0 {
+			Some(id) => Id(id).fmt(f),
+			None => f.write_str("none"),
+		}
+	}
+}
+
+/// Displays [`Coords`] as `@(x,y)`.
+struct At<'a>(&'a Coords);
+
+impl Display for At<'_> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(f, "@({},{})", self.0.x.0, self.0.y.0)
+	}
+}
+
+/// Displays a [`Rectangle`] or [`Region`] as `WxH@(x,y)`.
+struct Geometry<X, Y>(Px<X>, Px<Y>, Px<u16>, Px<u16>);
+
+impl<X: Display, Y: Display> Display for Geometry<X, Y> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		let Self(Px(x), Px(y), Px(width), Px(height)) = self;
+
+		write!(f, "{width}x{height}@({x},{y})")
+	}
+}
+
+impl From<&Rectangle> for Geometry<i16, i16> {
+	fn from(rectangle: &Rectangle) -> Self {
+		Self(rectangle.x, rectangle.y, rectangle.width, rectangle.height)
+	}
+}
+
+impl From<&Region> for Geometry<u16, u16> {
+	fn from(region: &Region) -> Self {
+		Self(region.x, region.y, region.width, region.height)
+	}
+}
+
+/// Displays a [`CurrentableTime`] as its timestamp, or `current`.
+struct Time<'a>(&'a CurrentableTime);
+
+impl Display for Time<'_> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		match self.0 {
+			CurrentableTime::CurrentTime => f.write_str("current"),
+			CurrentableTime::Other(time) => write!(f, "{}", time.unwrap()),
+		}
+	}
+}
+
+/// Displays an optional [atom], or `none`.
+///
+/// [atom]: Atom
+struct OptionAtom(Option<Atom>);
+
+impl Display for OptionAtom {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		match self.0 {
+			Some(atom) => write!(f, "{}", atom.unwrap()),
+			None => f.write_str("none"),
+		}
+	}
+}
+
+/// Appends ` flag` if `condition` is true.
+fn flag(f: &mut Formatter<'_>, condition: bool, name: &str) -> Result {
+	if condition {
+		write!(f, " {name}")?;
+	}
+
+	Ok(())
+}
+
+// Enums used in events are displayed as the names of their variants.
+macro_rules! impl_display_as_variant_name {
+	($($Enum:ty),+$(,)?) => {
+		$(
+			impl Display for $Enum {
+				fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+					Debug::fmt(self, f)
+				}
+			}
+		)+
+	};
+}
+
+impl_display_as_variant_name!(
+	MotionNotificationType,
+	EnterLeaveDetail,
+	FocusDetail,
+	FocusGrabMode,
+	VisibilityState,
+	Placement,
+	PropertyChange,
+	ColormapDetail,
+	ColormapState,
+	MappingRequest,
+	GrabMode,
+	StackMode,
+);
+
+macro_rules! impl_display_for_input_event {
+	($($Event:ident { $field:ident: $label:literal }),+$(,)?) => {
+		$(
+			impl Display for $Event {
+				fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+					write!(
+						f,
+						concat!(stringify!($Event), " ", $label, "={} mods={} win={} {}"),
+						self.$field.unwrap(),
+						self.modifiers,
+						Id(self.event_window.unwrap()),
+						At(&self.event_coords),
+					)
+				}
+			}
+		)+
+	};
+}
+
+impl_display_for_input_event!(
+	KeyPress { keycode: "key" },
+	KeyRelease { keycode: "key" },
+	ButtonPress { button: "button" },
+	ButtonRelease { button: "button" },
+);
+
+impl Display for Motion {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"Motion mods={} win={} {}",
+			self.modifiers,
+			Id(self.event_window.unwrap()),
+			At(&self.event_coords),
+		)?;
+
+		flag(
+			f,
+			self.notification_type == MotionNotificationType::Hint,
+			"hint",
+		)
+	}
+}
+
+macro_rules! impl_display_for_enter_leave {
+	($($Event:ident),+$(,)?) => {
+		$(
+			impl Display for $Event {
+				fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+					write!(
+						f,
+						concat!(stringify!($Event), " detail={} mode={} win={} {} mask={}"),
+						self.detail,
+						self.grab_mode,
+						Id(self.event_window.unwrap()),
+						At(&self.event_coords),
+						self.mask,
+					)
+				}
+			}
+		)+
+	};
+}
+
+impl_display_for_enter_leave!(EnterWindow, LeaveWindow);
+
+macro_rules! impl_display_for_focus {
+	($($Event:ident),+$(,)?) => {
+		$(
+			impl Display for $Event {
+				fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+					write!(
+						f,
+						concat!(stringify!($Event), " detail={} mode={} win={}"),
+						self.detail,
+						self.grab_mode,
+						Id(self.window.unwrap()),
+					)
+				}
+			}
+		)+
+	};
+}
+
+impl_display_for_focus!(Focus, Unfocus);
+
+impl Display for KeyboardState {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		let pressed: u32 = self.keys.iter().map(|byte| byte.count_ones()).sum();
+
+		write!(f, "KeyboardState pressed={pressed}")
+	}
+}
+
+impl Display for Expose {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"Expose win={} {} count={}",
+			Id(self.window.unwrap()),
+			Geometry::from(&self.region),
+			self.count,
+		)
+	}
+}
+
+impl Display for GraphicsExposure {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"GraphicsExposure drawable={} {} count={} opcode={}.{}",
+			Id(self.drawable.unwrap()),
+			Geometry::from(&self.region),
+			self.count,
+			self.major_opcode,
+			self.minor_opcode,
+		)
+	}
+}
+
+impl Display for NoExposure {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"NoExposure drawable={} opcode={}.{}",
+			Id(self.drawable.unwrap()),
+			self.major_opcode,
+			self.minor_opcode,
+		)
+	}
+}
+
+impl Display for Visibility {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"Visibility win={} state={}",
+			Id(self.window.unwrap()),
+			self.visibility,
+		)
+	}
+}
+
+impl Display for Create {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"Create win={} parent={} {} border={}",
+			Id(self.window.unwrap()),
+			Id(self.parent.unwrap()),
+			Geometry::from(&self.geometry),
+			self.border_width.0,
+		)?;
+
+		flag(f, self.override_redirect, "override-redirect")
+	}
+}
+
+impl Display for Destroy {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"Destroy win={} event={}",
+			Id(self.window.unwrap()),
+			Id(self.event_window.unwrap()),
+		)
+	}
+}
+
+impl Display for Unmap {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"Unmap win={} event={}",
+			Id(self.window.unwrap()),
+			Id(self.event_window.unwrap()),
+		)?;
+
+		flag(f, self.from_configure, "from-configure")
+	}
+}
+
+impl Display for Map {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"Map win={} event={}",
+			Id(self.window.unwrap()),
+			Id(self.event_window.unwrap()),
+		)?;
+
+		flag(f, self.override_redirect, "override-redirect")
+	}
+}
+
+impl Display for MapWindowRequest {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"MapWindowRequest win={} parent={}",
+			Id(self.window.unwrap()),
+			Id(self.parent.unwrap()),
+		)
+	}
+}
+
+impl Display for Reparent {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"Reparent win={} event={} parent={} {}",
+			Id(self.window.unwrap()),
+			Id(self.event_window.unwrap()),
+			Id(self.new_parent.unwrap()),
+			At(&self.coords),
+		)?;
+
+		flag(f, self.override_redirect, "override-redirect")
+	}
+}
+
+impl Display for Configure {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"Configure win={} event={} {} border={} above={}",
+			Id(self.window.unwrap()),
+			Id(self.event_window.unwrap()),
+			Geometry::from(&self.geometry),
+			self.border_width.0,
+			OptionId(self.sibling_below.map(Window::unwrap)),
+		)?;
+
+		flag(f, self.override_redirect, "override-redirect")
+	}
+}
+
+impl Display for ConfigureWindowRequest {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"ConfigureWindowRequest win={} parent={} {} border={} mask={} stack={} sibling={}",
+			Id(self.window.unwrap()),
+			Id(self.parent.unwrap()),
+			Geometry::from(&self.geometry),
+			self.border_width.0,
+			self.mask,
+			self.stack_mode,
+			OptionId(self.sibling.map(Window::unwrap)),
+		)
+	}
+}
+
+impl Display for Gravity {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"Gravity win={} event={} {}",
+			Id(self.window.unwrap()),
+			Id(self.event_window.unwrap()),
+			At(&self.coords),
+		)
+	}
+}
+
+impl Display for ResizeRequest {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"ResizeRequest win={} {}x{}",
+			Id(self.window.unwrap()),
+			self.width.0,
+			self.height.0,
+		)
+	}
+}
+
+impl Display for Circulate {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"Circulate win={} event={} placement={}",
+			Id(self.window.unwrap()),
+			Id(self.event_window.unwrap()),
+			self.placement,
+		)
+	}
+}
+
+impl Display for CirculateWindowRequest {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"CirculateWindowRequest win={} parent={} placement={}",
+			Id(self.window.unwrap()),
+			Id(self.parent.unwrap()),
+			self.placement,
+		)
+	}
+}
+
+impl Display for Property {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"Property win={} atom={} change={} time={}",
+			Id(self.window.unwrap()),
+			self.property.unwrap(),
+			self.change,
+			self.time.unwrap(),
+		)
+	}
+}
+
+impl Display for SelectionClear {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"SelectionClear owner={} selection={} time={}",
+			Id(self.owner.unwrap()),
+			self.selection.unwrap(),
+			self.time.unwrap(),
+		)
+	}
+}
+
+impl Display for ConvertSelectionRequest {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"ConvertSelectionRequest owner={} requester={} selection={} target={} property={} \
+			 time={}",
+			Id(self.owner.unwrap()),
+			Id(self.requester.unwrap()),
+			self.selection.unwrap(),
+			self.target_type.unwrap(),
+			OptionAtom(self.property),
+			Time(&self.time),
+		)
+	}
+}
+
+impl Display for Selection {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"Selection requester={} selection={} target={} property={} time={}",
+			Id(self.requester.unwrap()),
+			self.selection.unwrap(),
+			self.target_type.unwrap(),
+			OptionAtom(self.property),
+			Time(&self.time),
+		)
+	}
+}
+
+impl Display for Colormap {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"Colormap win={} colormap={} detail={} state={}",
+			Id(self.window.unwrap()),
+			OptionId(self.colormap.map(crate::Colormap::unwrap)),
+			self.detail,
+			self.state,
+		)
+	}
+}
+
+impl Display for ClientMessage {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"ClientMessage win={} type={} ",
+			Id(self.window.unwrap()),
+			self.r#type.unwrap(),
+		)?;
+
+		match &self.data {
+			ClientMessageData::Bytes(data) => write!(f, "format=8 data={data:?}"),
+			ClientMessageData::Words(data) => write!(f, "format=16 data={data:?}"),
+			ClientMessageData::Longs(data) => write!(f, "format=32 data={data:?}"),
+		}
+	}
+}
+
+impl Display for MappingChange {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"MappingChange request={} first={} count={}",
+			self.request,
+			self.first_keycode.unwrap(),
+			self.count,
+		)
+	}
+}
+
+impl Display for GenericEvent {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		write!(
+			f,
+			"GenericEvent ext={} type={} len={}",
+			self.extension,
+			self.event_type,
+			self.data.len(),
+		)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn window(id: u32) -> Window {
+		Window::new(id)
+	}
+
+	#[test]
+	fn test_key_press() {
+		let key_press = KeyPress {
+			sequence: 0,
+			keycode: Keycode::new(38),
+			time: Timestamp::new(0),
+			root: window(0x0000_0512),
+			event_window: window(0x02a0_000f),
+			child_window: None,
+			root_coords: Coords::new(Px(1040), Px(120)),
+			event_coords: Coords::new(Px(1032), Px(88)),
+			modifiers: ModifierMask::SHIFT | ModifierMask::CONTROL,
+			same_screen: true,
+		};
+
+		assert_eq!(
+			key_press.to_string(),
+			"KeyPress key=38 mods=Ctrl+Shift win=0x2a0000f @(1032,88)"
+		);
+	}
+
+	#[test]
+	fn test_enter_window() {
+		let enter_window = EnterWindow {
+			sequence: 0,
+			detail: EnterLeaveDetail::Descendant,
+			time: Timestamp::new(0),
+			root: window(0x0000_0512),
+			event_window: window(0x0060_0001),
+			child_window: None,
+			root_coords: Coords::new(Px(10), Px(20)),
+			event_coords: Coords::new(Px(-1), Px(0)),
+			modifiers: ModifierMask::empty(),
+			grab_mode: GrabMode::Normal,
+			mask: EnterLeaveMask::FOCUS | EnterLeaveMask::SAME_SCREEN,
+		};
+
+		assert_eq!(
+			enter_window.to_string(),
+			"EnterWindow detail=Descendant mode=Normal win=0x600001 @(-1,0) mask=Focus+SameScreen"
+		);
+	}
+
+	#[test]
+	fn test_configure_window_request() {
+		let request = ConfigureWindowRequest {
+			sequence: 0,
+			stack_mode: StackMode::Above,
+			parent: window(0x0000_0512),
+			window: window(0x0060_0001),
+			sibling: None,
+			geometry: Rectangle {
+				x: Px(0),
+				y: Px(-20),
+				width: Px(640),
+				height: Px(480),
+			},
+			border_width: Px(0),
+			mask: WindowConfigMask::Y | WindowConfigMask::WIDTH | WindowConfigMask::HEIGHT,
+		};
+
+		assert_eq!(
+			request.to_string(),
+			"ConfigureWindowRequest win=0x600001 parent=0x512 640x480@(0,-20) border=0 \
+			 mask=Y+Width+Height stack=Above sibling=none"
+		);
+	}
+
+	#[test]
+	fn test_map_and_property() {
+		let map = Map {
+			sequence: 0,
+			event_window: window(0x0000_0512),
+			window: window(0x0060_0001),
+			override_redirect: true,
+		};
+		let property = Property {
+			sequence: 0,
+			window: window(0x0060_0001),
+			property: Atom::new(39),
+			time: Timestamp::new(1234),
+			change: PropertyChange::Modified,
+		};
+
+		assert_eq!(
+			map.to_string(),
+			"Map win=0x600001 event=0x512 override-redirect"
+		);
+		assert_eq!(
+			property.to_string(),
+			"Property win=0x600001 atom=39 change=Modified time=1234"
+		);
+	}
+
+	#[test]
+	fn test_generic_event() {
+		let event = GenericEvent {
+			sequence: 0,
+			extension: 131,
+			event_type: 2,
+			data: vec![0; 30],
+		};
+
+		assert_eq!(event.to_string(), "GenericEvent ext=131 type=2 len=30");
+	}
+
+	#[test]
+	fn test_masks() {
+		assert_eq!(ModifierMask::empty().to_string(), "none");
+		assert_eq!(
+			(ModifierMask::MOD_4 | ModifierMask::BUTTON_1).to_string(),
+			"Mod4+Button1"
+		);
+		assert_eq!(
+			WindowConfigMask::all().to_string(),
+			"X+Y+Width+Height+BorderWidth+Sibling+StackMode"
+		);
+	}
+}
@@ -0,0 +1,338 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Collection of the regions of [`Expose`] and [`GraphicsExposure`] events
+//! into complete batches.
+
+use std::collections::HashMap;
+
+use super::*;
+use crate::common::area::Area;
+
+/// Identifies a series of [`Expose`] or [`GraphicsExposure`] events.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+enum ExposureSeries {
+	/// [`Expose`] events for a [window].
+	///
+	/// [window]: Window
+	Window(Window),
+	/// [`GraphicsExposure`] events for a [`Drawable`].
+	Drawable(Drawable),
+}
+
+/// Collects the regions of successive [`Expose`] and [`GraphicsExposure`]
+/// events into complete batches.
+///
+/// Each of these [events] has a `count` of the [events] which follow it for the
+/// same [window] or [`Drawable`]: a `count` of `0` ends the series. The regions
+/// of each series are collected separately, so series for different
+/// [windows][window] and [`Drawable`]s may be interleaved.
+///
+/// If [`merge`] is enabled, the regions of a complete series are merged into
+/// an [`Area`], and returned as the non-overlapping regions which make it up.
+///
+/// [events]: Event
+/// [window]: Window
+///
+/// [`merge`]: ExposureCollector::merge
+#[doc(alias = "ExposeAccumulator")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExposureCollector {
+	/// Whether the regions of a series are merged into an [`Area`] when the
+	/// series is complete.
+	///
+	/// Merged regions never overlap, so no part of the exposed [`Area`] needs
+	/// to be repainted twice.
+	pub merge: bool,
+
+	pending: HashMap<ExposureSeries, Vec<Region>>,
+}
+
+impl ExposureCollector {
+	/// Creates a new `ExposureCollector` with no pending regions.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			merge: false,
+
+			pending: HashMap::new(),
+		}
+	}
+
+	/// Creates a new `ExposureCollector` with no pending regions which
+	/// [merges] the regions of each complete series.
+	///
+	/// [merges]: ExposureCollector::merge
+	#[must_use]
+	pub fn new_merging() -> Self {
+		Self {
+			merge: true,
+
+			pending: HashMap::new(),
+		}
+	}
+
+	/// Returns the given complete series of `regions`, merged if [`merge`] is
+	/// enabled.
+	///
+	/// [`merge`]: ExposureCollector::merge
+	fn complete(&self, regions: Vec<Region>) -> Vec<Region> {
+		if self.merge {
+			regions.into_iter().collect::<Area>().regions()
+		} else {
+			regions
+		}
+	}
+
+	/// Adds the `region` to the given `series`, returning the complete series
+	/// if `count` is `0`.
+	fn push(&mut self, series: ExposureSeries, region: &Region, count: u16) -> Option<Vec<Region>> {
+		let regions = self.pending.entry(series).or_default();
+		regions.push(region.clone());
+
+		if count == 0 {
+			let regions = self.pending.remove(&series)?;
+
+			Some(self.complete(regions))
+		} else {
+			None
+		}
+	}
+
+	/// Adds the region of the given [`Expose` event].
+	///
+	/// Returns every region exposed in the `window` of the series if this is
+	/// its last [event].
+	///
+	/// [event]: Event
+	/// [`Expose` event]: Expose
+	pub fn expose(&mut self, event: &Expose) -> Option<Vec<Region>> {
+		self.push(
+			ExposureSeries::Window(event.window),
+			&event.region,
+			event.count,
+		)
+	}
+
+	/// Adds the region of the given [`GraphicsExposure` event].
+	///
+	/// Returns every region exposed in the `drawable` of the series if this is
+	/// its last [event].
+	///
+	/// [event]: Event
+	/// [`GraphicsExposure` event]: GraphicsExposure
+	pub fn graphics_exposure(&mut self, event: &GraphicsExposure) -> Option<Vec<Region>> {
+		self.push(
+			ExposureSeries::Drawable(event.drawable),
+			&event.region,
+			event.count,
+		)
+	}
+
+	/// Ends any series of [`GraphicsExposure` events] for the `drawable` of the
+	/// given [`NoExposure` event].
+	///
+	/// A [`NoExposure` event] is generated in place of
+	/// [`GraphicsExposure` events], so this always returns a batch: normally
+	/// an empty one, since no series is expected to be pending for the
+	/// `drawable`.
+	///
+	/// [`GraphicsExposure` events]: GraphicsExposure
+	/// [`NoExposure` event]: NoExposure
+	pub fn no_exposure(&mut self, event: &NoExposure) -> Vec<Region> {
+		let regions = self
+			.pending
+			.remove(&ExposureSeries::Drawable(event.drawable))
+			.unwrap_or_default();
+
+		self.complete(regions)
+	}
+
+	/// Discards any incomplete series of [`Expose` events] for the given
+	/// `window`.
+	///
+	/// This should be used when the `window` is destroyed, since the rest of
+	/// its series will never arrive.
+	///
+	/// [`Expose` events]: Expose
+	pub fn forget(&mut self, window: Window) {
+		self.pending.remove(&ExposureSeries::Window(window));
+	}
+
+	/// Returns whether there are no incomplete series of [events].
+	///
+	/// [events]: Event
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.pending.is_empty()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn region(x: u16) -> Region {
+		Region::new(Px(x), Px(0), Px(8), Px(8))
+	}
+
+	fn graphics_exposure(drawable: u32, x: u16, count: u16) -> GraphicsExposure {
+		GraphicsExposure {
+			sequence: 1,
+			drawable: Drawable::new(drawable),
+			region: region(x),
+			minor_opcode: 0,
+			count,
+			major_opcode: 62,
+		}
+	}
+
+	fn expose(window: u32, x: u16, count: u16) -> Expose {
+		Expose {
+			sequence: 1,
+			window: Window::new(window),
+			region: region(x),
+			count,
+		}
+	}
+
+	#[test]
+	fn test_exposure_collector_interleaved() {
+		let mut collector = ExposureCollector::new();
+
+		assert_eq!(
+			collector.graphics_exposure(&graphics_exposure(1, 0, 2)),
+			None
+		);
+		assert_eq!(
+			collector.graphics_exposure(&graphics_exposure(2, 10, 1)),
+			None
+		);
+		assert_eq!(
+			collector.graphics_exposure(&graphics_exposure(1, 1, 1)),
+			None
+		);
+		// An `Expose` series for a window with the same ID is kept separate.
+		assert_eq!(collector.expose(&expose(1, 20, 1)), None);
+
+		assert_eq!(
+			collector.graphics_exposure(&graphics_exposure(2, 11, 0)),
+			Some(vec![region(10), region(11)]),
+		);
+		assert_eq!(
+			collector.graphics_exposure(&graphics_exposure(1, 2, 0)),
+			Some(vec![region(0), region(1), region(2)]),
+		);
+		assert!(!collector.is_empty());
+
+		assert_eq!(
+			collector.expose(&expose(1, 21, 0)),
+			Some(vec![region(20), region(21)]),
+		);
+		assert!(collector.is_empty());
+
+		// A series of a single event.
+		assert_eq!(collector.expose(&expose(3, 30, 0)), Some(vec![region(30)]));
+	}
+
+	#[test]
+	fn test_exposure_collector_forget() {
+		let mut collector = ExposureCollector::new();
+
+		// Three-event batches for two windows, interleaved.
+		assert_eq!(collector.expose(&expose(1, 0, 2)), None);
+		assert_eq!(collector.expose(&expose(2, 10, 2)), None);
+		assert_eq!(collector.expose(&expose(1, 1, 1)), None);
+		assert_eq!(collector.expose(&expose(2, 11, 1)), None);
+
+		// Window 2 is destroyed before its batch is complete.
+		collector.forget(Window::new(2));
+
+		assert_eq!(
+			collector.expose(&expose(1, 2, 0)),
+			Some(vec![region(0), region(1), region(2)]),
+		);
+		assert!(collector.is_empty());
+
+		// A new batch for window 2 does not include the forgotten regions.
+		assert_eq!(collector.expose(&expose(2, 20, 0)), Some(vec![region(20)]));
+	}
+
+	#[test]
+	fn test_exposure_collector_merge() {
+		let mut collector = ExposureCollector::new_merging();
+
+		let expose = |x, y, width, height, count| Expose {
+			sequence: 1,
+			window: Window::new(1),
+			region: Region::new(Px(x), Px(y), Px(width), Px(height)),
+			count,
+		};
+
+		// Overlapping.
+		assert_eq!(collector.expose(&expose(0, 0, 10, 10, 1)), None);
+		assert_eq!(
+			collector.expose(&expose(5, 5, 10, 10, 0)),
+			Some(vec![
+				Region::new(Px(0), Px(0), Px(10), Px(5)),
+				Region::new(Px(0), Px(5), Px(15), Px(5)),
+				Region::new(Px(5), Px(10), Px(10), Px(5)),
+			]),
+		);
+
+		// Adjacent, and only touching at a corner.
+		assert_eq!(collector.expose(&expose(0, 0, 10, 10, 2)), None);
+		assert_eq!(collector.expose(&expose(10, 0, 10, 10, 1)), None);
+		assert_eq!(
+			collector.expose(&expose(20, 10, 5, 5, 0)),
+			Some(vec![
+				Region::new(Px(0), Px(0), Px(20), Px(10)),
+				Region::new(Px(20), Px(10), Px(5), Px(5)),
+			]),
+		);
+
+		// Separate regions which are joined by a later region.
+		assert_eq!(collector.expose(&expose(0, 0, 10, 10, 3)), None);
+		assert_eq!(collector.expose(&expose(30, 0, 10, 10, 2)), None);
+		assert_eq!(collector.expose(&expose(100, 100, 1, 1, 1)), None);
+		assert_eq!(
+			collector.expose(&expose(5, 0, 30, 10, 0)),
+			Some(vec![
+				Region::new(Px(0), Px(0), Px(40), Px(10)),
+				Region::new(Px(100), Px(100), Px(1), Px(1)),
+			]),
+		);
+		assert!(collector.is_empty());
+	}
+
+	#[test]
+	fn test_exposure_collector_no_exposure() {
+		let mut collector = ExposureCollector::new();
+
+		let no_exposure = |drawable| NoExposure {
+			sequence: 1,
+			drawable: Drawable::new(drawable),
+			minor_opcode: 0,
+			major_opcode: 62,
+		};
+
+		assert!(collector.no_exposure(&no_exposure(1)).is_empty());
+
+		// A `NoExposure` event ends only the series for its own drawable.
+		assert_eq!(
+			collector.graphics_exposure(&graphics_exposure(1, 0, 1)),
+			None
+		);
+		assert_eq!(
+			collector.graphics_exposure(&graphics_exposure(2, 10, 1)),
+			None
+		);
+		assert_eq!(collector.no_exposure(&no_exposure(1)), [region(0)]);
+		assert_eq!(
+			collector.graphics_exposure(&graphics_exposure(2, 11, 0)),
+			Some(vec![region(10), region(11)]),
+		);
+		assert!(collector.is_empty());
+	}
+}
@@ -0,0 +1,184 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Tracking of which keys are pressed from [`KeyPress`], [`KeyRelease`], and
+//! [`KeyboardState`] events.
+
+use super::*;
+
+/// Tracks which keys are currently pressed.
+///
+/// The state of every key is replaced by each [`KeyboardState` event], and
+/// updated by each [`KeyPress`] and [`KeyRelease`] event in between.
+///
+/// [`KeyboardState` event]: KeyboardState
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct KeyState {
+	/// A bit for every [keycode]: byte `N` contains the bits for [keycodes]
+	/// `8N` to `8N + 7`, with the least significant bit representing `8N`.
+	///
+	/// [keycode]: Keycode
+	/// [keycodes]: Keycode
+	keys: [u8; 32],
+}
+
+impl KeyState {
+	/// Creates a new `KeyState` with no keys pressed.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self { keys: [0; 32] }
+	}
+
+	/// Returns the index of the byte and the bit mask for the given `keycode`.
+	const fn bit(keycode: Keycode) -> (usize, u8) {
+		let keycode = keycode.unwrap();
+
+		((keycode / 8) as usize, 1 << (keycode % 8))
+	}
+
+	/// Replaces the state of every key with the `keys` of the given
+	/// [`KeyboardState` event].
+	///
+	/// [`KeyboardState` event]: KeyboardState
+	#[doc(alias = "apply_keymap")]
+	pub fn apply_keyboard_state(&mut self, event: &KeyboardState) {
+		// Keycodes 0 to 7 are not present in the event: they are never pressed.
+		self.keys[0] = 0;
+		self.keys[1..].copy_from_slice(&event.keys);
+	}
+
+	/// Records that the `keycode` of the given [`KeyPress` event] is pressed.
+	///
+	/// [`KeyPress` event]: KeyPress
+	pub const fn apply_press(&mut self, event: &KeyPress) {
+		let (byte, mask) = Self::bit(event.keycode);
+
+		self.keys[byte] |= mask;
+	}
+
+	/// Records that the `keycode` of the given [`KeyRelease` event] is
+	/// released.
+	///
+	/// [`KeyRelease` event]: KeyRelease
+	pub const fn apply_release(&mut self, event: &KeyRelease) {
+		let (byte, mask) = Self::bit(event.keycode);
+
+		self.keys[byte] &= !mask;
+	}
+
+	/// Returns whether the given `keycode` is pressed.
+	#[must_use]
+	pub const fn is_pressed(&self, keycode: Keycode) -> bool {
+		let (byte, mask) = Self::bit(keycode);
+
+		self.keys[byte] & mask != 0
+	}
+
+	/// Returns an iterator over the [keycodes] which are pressed, in
+	/// ascending order.
+	///
+	/// [keycodes]: Keycode
+	pub fn pressed_keys(&self) -> impl Iterator<Item = Keycode> + '_ {
+		(u8::MIN..=u8::MAX)
+			.map(Keycode::new)
+			.filter(|keycode| self.is_pressed(*keycode))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn pressed(keycode: u8) -> KeyPress {
+		KeyPress {
+			sequence: 7,
+			keycode: Keycode::new(keycode),
+			time: Timestamp::new(1_000),
+			root: Window::new(0x0000_0512),
+			event_window: Window::new(0x0060_0001),
+			child_window: None,
+			root_coords: Coords::new(Px(640), Px(480)),
+			event_coords: Coords::new(Px(20), Px(-4)),
+			modifiers: ModifierMask::empty(),
+			same_screen: true,
+		}
+	}
+
+	fn released(keycode: u8) -> KeyRelease {
+		let press = pressed(keycode);
+
+		KeyRelease {
+			sequence: press.sequence,
+			keycode: Keycode::new(keycode),
+			time: press.time,
+			root: press.root,
+			event_window: press.event_window,
+			child_window: press.child_window,
+			root_coords: press.root_coords,
+			event_coords: press.event_coords,
+			modifiers: press.modifiers,
+			same_screen: press.same_screen,
+		}
+	}
+
+	fn pressed_keys(state: &KeyState) -> Vec<u8> {
+		state
+			.pressed_keys()
+			.map(|keycode| keycode.unwrap())
+			.collect()
+	}
+
+	#[test]
+	fn test_key_state_bit_order() {
+		let mut keys = [0; 31];
+		// Keycodes 8 and 15.
+		keys[0] = 0b1000_0001;
+		// Keycode 18.
+		keys[1] = 0b0000_0100;
+		// Keycode 255.
+		keys[30] = 0b1000_0000;
+
+		let mut state = KeyState::new();
+		state.apply_keyboard_state(&KeyboardState { keys });
+
+		assert_eq!(pressed_keys(&state), [8, 15, 18, 255]);
+		assert!(state.is_pressed(Keycode::new(18)));
+		assert!(!state.is_pressed(Keycode::new(17)));
+		assert!(!state.is_pressed(Keycode::new(0)));
+	}
+
+	#[test]
+	fn test_key_state_press_and_release() {
+		let mut state = KeyState::new();
+		assert!(pressed_keys(&state).is_empty());
+
+		state.apply_press(&pressed(38));
+		state.apply_press(&pressed(9));
+		assert_eq!(pressed_keys(&state), [9, 38]);
+
+		state.apply_release(&released(38));
+		assert_eq!(pressed_keys(&state), [9]);
+
+		// Releasing a key which is not pressed has no effect.
+		state.apply_release(&released(40));
+		assert_eq!(pressed_keys(&state), [9]);
+	}
+
+	#[test]
+	fn test_key_state_keyboard_state_clears_stale_presses() {
+		let mut state = KeyState::new();
+
+		// The release of keycode 38 is missed, e.g. while another client had
+		// the keyboard grabbed.
+		state.apply_press(&pressed(38));
+
+		let mut keys = [0; 31];
+		// Keycode 50.
+		keys[5] = 0b0000_0100;
+		state.apply_keyboard_state(&KeyboardState { keys });
+
+		assert!(!state.is_pressed(Keycode::new(38)));
+		assert_eq!(pressed_keys(&state), [50]);
+	}
+}
@@ -0,0 +1,279 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Backfilling of the cursor motion coalesced into [`Motion`] events with a
+//! [`MotionNotificationType`] of [`Hint`].
+//!
+//! [`Hint`]: MotionNotificationType::Hint
+
+use std::collections::HashMap;
+
+use super::*;
+
+/// Fills in the cursor motion coalesced by the X server when
+/// [`MOTION_HINT`] is selected.
+///
+/// A `MotionHistory` records the [time] of the last cursor motion known for
+/// each [window]. When a [`Motion` event] with a [`MotionNotificationType`] of
+/// [`Hint`] is given to [`motion`], it returns the [`GetMotionHistory`
+/// request] for the motion since then. The [`GetMotionHistory` reply] is then
+/// given to [`merge`], which returns only the motion which is new, in
+/// chronological order.
+///
+/// [`MOTION_HINT`]: crate::EventMask::MOTION_HINT
+/// [time]: Timestamp
+/// [window]: Window
+/// [`Motion` event]: Motion
+/// [`Hint`]: MotionNotificationType::Hint
+///
+/// [`motion`]: MotionHistory::motion
+/// [`merge`]: MotionHistory::merge
+///
+/// [`GetMotionHistory` request]: request::GetMotionHistory
+/// [`GetMotionHistory` reply]: reply::GetMotionHistory
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MotionHistory {
+	/// The time of the last cursor motion known for each window.
+	///
+	/// Motion at or before this time has already been seen.
+	last_seen: HashMap<Window, Timestamp>,
+}
+
+impl MotionHistory {
+	/// Creates a new `MotionHistory` with no motion recorded.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			last_seen: HashMap::new(),
+		}
+	}
+
+	/// Records that the cursor motion up to the given `time` has been seen
+	/// for the `window`.
+	///
+	/// Earlier times are ignored.
+	fn see(&mut self, window: Window, time: Timestamp) {
+		let last_seen = self.last_seen.entry(window).or_insert(time);
+
+		if time.is_after(*last_seen) {
+			*last_seen = time;
+		}
+	}
+
+	/// Records the given [`Motion` event].
+	///
+	/// If the `event` is a [`Hint`], the [`GetMotionHistory` request] for the
+	/// motion in its `event_window` since the last motion recorded is
+	/// returned. Sending that request also lets the X server send another
+	/// [`Hint`].
+	///
+	/// [`Motion` event]: Motion
+	/// [`Hint`]: MotionNotificationType::Hint
+	///
+	/// [`GetMotionHistory` request]: request::GetMotionHistory
+	pub fn motion(&mut self, event: &Motion) -> Option<request::GetMotionHistory> {
+		let window = event.event_window;
+
+		match event.notification_type {
+			MotionNotificationType::Normal => {
+				self.see(window, event.time);
+
+				None
+			},
+
+			MotionNotificationType::Hint => {
+				// If no motion has been seen yet, the motion from the time of
+				// this hint is new.
+				let start = *self
+					.last_seen
+					.entry(window)
+					.or_insert_with(|| Timestamp::new(event.time.unwrap().wrapping_sub(1)));
+				// A `start` of `0` would be sent as `CurrentTime`. Motion at
+				// `start` has already been seen, so starting just after it
+				// instead misses nothing.
+				let start = match start.unwrap() {
+					0 => Timestamp::new(1),
+					_ => start,
+				};
+
+				Some(request::GetMotionHistory {
+					target: window,
+					start: CurrentableTime::Other(start),
+					end: CurrentableTime::CurrentTime,
+				})
+			},
+		}
+	}
+
+	/// Merges the `motion_history` of the given [`GetMotionHistory` reply]
+	/// for the `target` [window], returning the motion which has not already
+	/// been seen.
+	///
+	/// The motion returned is in chronological order, with duplicate times
+	/// removed. Motion at or before the last motion recorded for the `target`
+	/// is discarded, so overlapping replies - such as those to two
+	/// [`Hint`]s received before either reply - are only returned once.
+	///
+	/// [window]: Window
+	/// [`Hint`]: MotionNotificationType::Hint
+	///
+	/// [`GetMotionHistory` reply]: reply::GetMotionHistory
+	pub fn merge(
+		&mut self, target: Window, reply: reply::GetMotionHistory,
+	) -> Vec<reply::TimeCoords> {
+		let mut motion = reply.motion_history;
+
+		match self.last_seen.get(&target) {
+			Some(&last_seen) => {
+				motion.retain(|coords| coords.time.is_after(last_seen));
+				// Order the motion by the time elapsed since `last_seen`, so
+				// that motion after the time wraps around is ordered correctly.
+				motion.sort_by_key(|coords| coords.time.unwrap().wrapping_sub(last_seen.unwrap()));
+			},
+
+			// Without any motion to compare to, the times can only be compared
+			// directly.
+			None => motion.sort_by_key(|coords| coords.time.unwrap()),
+		}
+		motion.dedup_by_key(|coords| coords.time);
+
+		if let Some(last) = motion.last() {
+			self.see(target, last.time);
+		}
+
+		motion
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn motion(window: u32, time: u32, notification_type: MotionNotificationType) -> Motion {
+		Motion {
+			sequence: 1,
+			notification_type,
+			time: Timestamp::new(time),
+			root: Window::new(0x0000_0500),
+			event_window: Window::new(window),
+			child_window: None,
+			root_coords: Coords::new(Px(0), Px(0)),
+			event_coords: Coords::new(Px(0), Px(0)),
+			modifiers: ModifierMask::empty(),
+			same_screen: true,
+		}
+	}
+
+	fn motion_history(times: &[u32]) -> reply::GetMotionHistory {
+		reply::GetMotionHistory {
+			sequence: 1,
+			motion_history: times
+				.iter()
+				.map(|time| reply::TimeCoords {
+					time: Timestamp::new(*time),
+					coords: Coords::new(Px(0), Px(0)),
+				})
+				.collect(),
+		}
+	}
+
+	fn times(motion: &[reply::TimeCoords]) -> Vec<u32> {
+		motion.iter().map(|coords| coords.time.unwrap()).collect()
+	}
+
+	#[test]
+	fn test_motion_history_backfills_hints() {
+		use MotionNotificationType::{Hint, Normal};
+
+		let window = Window::new(1);
+		let mut history = MotionHistory::new();
+
+		assert_eq!(history.motion(&motion(1, 100, Normal)), None);
+
+		let request = history.motion(&motion(1, 150, Hint)).unwrap();
+		assert_eq!(request.target, window);
+		assert_eq!(request.start, CurrentableTime::Other(Timestamp::new(100)));
+		assert_eq!(request.end, CurrentableTime::CurrentTime);
+
+		// Motion already seen and duplicate times are removed.
+		let merged = history.merge(window, motion_history(&[150, 100, 120, 150, 90]));
+		assert_eq!(times(&merged), [120, 150]);
+
+		// Another window's motion is recorded separately.
+		let request = history.motion(&motion(2, 160, Hint)).unwrap();
+		assert_eq!(request.start, CurrentableTime::Other(Timestamp::new(159)));
+		assert_eq!(
+			times(&history.merge(Window::new(2), motion_history(&[160, 170]))),
+			[160, 170],
+		);
+
+		let request = history.motion(&motion(1, 200, Hint)).unwrap();
+		assert_eq!(request.start, CurrentableTime::Other(Timestamp::new(150)));
+	}
+
+	#[test]
+	fn test_motion_history_overlapping_replies() {
+		use MotionNotificationType::{Hint, Normal};
+
+		let window = Window::new(1);
+		let mut history = MotionHistory::new();
+
+		history.motion(&motion(1, 100, Normal));
+
+		// Two hints are received before either reply.
+		let first = history.motion(&motion(1, 110, Hint)).unwrap();
+		let second = history.motion(&motion(1, 140, Hint)).unwrap();
+		assert_eq!(first, second);
+
+		assert_eq!(
+			times(&history.merge(window, motion_history(&[110, 130]))),
+			[110, 130],
+		);
+		assert_eq!(
+			times(&history.merge(window, motion_history(&[110, 130, 140]))),
+			[140],
+		);
+
+		// A `Normal` event older than the motion already seen is ignored.
+		history.motion(&motion(1, 120, Normal));
+		let request = history.motion(&motion(1, 150, Hint)).unwrap();
+		assert_eq!(request.start, CurrentableTime::Other(Timestamp::new(140)));
+	}
+
+	#[test]
+	fn test_motion_history_time_wraps_around() {
+		use MotionNotificationType::{Hint, Normal};
+
+		let window = Window::new(1);
+		let mut history = MotionHistory::new();
+
+		history.motion(&motion(1, u32::MAX - 10, Normal));
+		history.motion(&motion(1, 5, Hint)).unwrap();
+
+		let merged = history.merge(
+			window,
+			motion_history(&[2, u32::MAX - 10, 5, u32::MAX - 5, u32::MAX - 20, 2]),
+		);
+		assert_eq!(times(&merged), [u32::MAX - 5, 2, 5]);
+
+		// The last motion seen is now after the time wrapped around.
+		history.motion(&motion(1, u32::MAX, Normal));
+		let request = history.motion(&motion(1, 10, Hint)).unwrap();
+		assert_eq!(request.start, CurrentableTime::Other(Timestamp::new(5)));
+	}
+
+	#[test]
+	fn test_motion_history_hint_at_time_1() {
+		use MotionNotificationType::Hint;
+
+		let window = Window::new(1);
+		let mut history = MotionHistory::new();
+
+		// The motion since time `0` must not be requested from `CurrentTime`.
+		let request = history.motion(&motion(1, 1, Hint)).unwrap();
+		assert_eq!(request.start, CurrentableTime::Other(Timestamp::new(1)));
+
+		assert_eq!(times(&history.merge(window, motion_history(&[1]))), [1]);
+	}
+}
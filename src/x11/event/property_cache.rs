@@ -0,0 +1,281 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Caching of [window] properties, kept up to date by [`Property`] and
+//! [`Destroy`] events.
+//!
+//! [window]: Window
+
+use std::collections::HashMap;
+
+use super::*;
+
+/// The state of a cached `property` in a [`PropertyCache`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CacheState<'a> {
+	/// The `property`'s value has not changed since it was fetched.
+	Fresh(&'a reply::GetProperty),
+	/// The `property` has been [modified] since this value was fetched.
+	///
+	/// [modified]: PropertyChange::Modified
+	Dirty(&'a reply::GetProperty),
+	/// The `property` is known not to exist.
+	Deleted,
+	/// The `property` has not been fetched.
+	Unknown,
+}
+
+/// A cached `property` in a [`PropertyCache`].
+#[derive(Debug, PartialEq, Eq)]
+struct CachedProperty {
+	/// The fetched value of the `property`, or [`None`] if it is known not to
+	/// exist.
+	reply: Option<reply::GetProperty>,
+	/// Whether the `property` has been modified since the `reply` was fetched.
+	dirty: bool,
+}
+
+/// Caches the values of [window] properties, tracking changes to them with
+/// [`Property` events].
+///
+/// Values fetched with [`GetProperty` requests] are [`store`]d, and
+/// [`Property` events] are given to [`invalidate`], so that a property only
+/// needs to be fetched again when it has actually changed - for example, a
+/// window manager does not need to fetch `WM_NAME` every time it draws a
+/// window's title.
+///
+/// As [events] and [replies] are received in the order that they were
+/// generated, a [`Property` event] received before a [`GetProperty` reply]
+/// describes a change which is already reflected in that reply.
+///
+/// [window]: Window
+/// [`Property` event]: Property
+/// [`Property` events]: Property
+/// [events]: Event
+/// [replies]: crate::message::Reply
+///
+/// [`store`]: PropertyCache::store
+/// [`invalidate`]: PropertyCache::invalidate
+///
+/// [`GetProperty` requests]: request::GetProperty
+/// [`GetProperty` reply]: reply::GetProperty
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PropertyCache {
+	properties: HashMap<(Window, Atom), CachedProperty>,
+}
+
+impl PropertyCache {
+	/// Creates a new, empty `PropertyCache`.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			properties: HashMap::new(),
+		}
+	}
+
+	/// Records the change to a `property` reported in the given [`Property`
+	/// event].
+	///
+	/// A [`Modified`] `property` which has been fetched is marked [`Dirty`],
+	/// and a [`Deleted`] `property` is known not to exist. A [`Modified`]
+	/// `property` which was known not to exist has been added again, so it
+	/// is forgotten until it is fetched.
+	///
+	/// [`Property` event]: Property
+	/// [`Modified`]: PropertyChange::Modified
+	/// [`Deleted`]: PropertyChange::Deleted
+	/// [`Dirty`]: CacheState::Dirty
+	pub fn invalidate(&mut self, event: &Property) {
+		let key = (event.window, event.property);
+
+		match event.change {
+			PropertyChange::Modified => match self.properties.get_mut(&key) {
+				Some(CachedProperty {
+					reply: Some(_),
+					dirty,
+				}) => *dirty = true,
+
+				// A deleted property which has been added again has not been
+				// fetched.
+				Some(CachedProperty { reply: None, .. }) => {
+					self.properties.remove(&key);
+				},
+
+				None => {},
+			},
+
+			PropertyChange::Deleted => {
+				self.properties.insert(
+					key,
+					CachedProperty {
+						reply: None,
+						dirty: false,
+					},
+				);
+			},
+		}
+	}
+
+	/// Caches the value of the `window`'s `property` given in the
+	/// [`GetProperty` reply], marking it [`Fresh`].
+	///
+	/// If the `property` does not exist (its `type` is [`None`]), it is marked
+	/// [`Deleted`].
+	///
+	/// [`GetProperty` reply]: reply::GetProperty
+	/// [`Fresh`]: CacheState::Fresh
+	/// [`Deleted`]: CacheState::Deleted
+	pub fn store(&mut self, window: Window, property: Atom, reply: reply::GetProperty) {
+		self.properties.insert(
+			(window, property),
+			CachedProperty {
+				reply: reply.r#type.is_some().then_some(reply),
+				dirty: false,
+			},
+		);
+	}
+
+	/// Returns the [`CacheState`] of the `window`'s `property`.
+	#[must_use]
+	pub fn get(&self, window: Window, property: Atom) -> CacheState<'_> {
+		match self.properties.get(&(window, property)) {
+			Some(CachedProperty {
+				reply: Some(reply),
+				dirty: false,
+			}) => CacheState::Fresh(reply),
+
+			Some(CachedProperty {
+				reply: Some(reply),
+				dirty: true,
+			}) => CacheState::Dirty(reply),
+
+			Some(CachedProperty { reply: None, .. }) => CacheState::Deleted,
+
+			None => CacheState::Unknown,
+		}
+	}
+
+	/// Forgets every property cached for the `window` of the given [`Destroy`
+	/// event].
+	///
+	/// [`Destroy` event]: Destroy
+	pub fn destroy(&mut self, event: &Destroy) {
+		self.properties
+			.retain(|(window, _), _| *window != event.window);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn get_property(value: &str) -> reply::GetProperty {
+		reply::GetProperty {
+			sequence: 0,
+			format: Some(request::DataFormat::I8),
+			r#type: Some(Atom::new(31)),
+			bytes_remaining: 0,
+			value: request::DataList::I8(value.bytes().map(|byte| byte as i8).collect()),
+		}
+	}
+
+	fn property(window: u32, property: u32, change: PropertyChange) -> Property {
+		Property {
+			sequence: 0,
+			window: Window::new(window),
+			property: Atom::new(property),
+			time: Timestamp::new(1_000),
+			change,
+		}
+	}
+
+	const WM_NAME: Atom = Atom::new(39);
+
+	#[test]
+	fn test_property_cache_modify_then_fetch() {
+		let window = Window::new(0x0060_0001);
+		let mut cache = PropertyCache::new();
+
+		assert_eq!(cache.get(window, WM_NAME), CacheState::Unknown);
+
+		// A property which has not been fetched stays unknown.
+		cache.invalidate(&property(0x0060_0001, 39, PropertyChange::Modified));
+		assert_eq!(cache.get(window, WM_NAME), CacheState::Unknown);
+
+		cache.store(window, WM_NAME, get_property("xterm"));
+		assert_eq!(
+			cache.get(window, WM_NAME),
+			CacheState::Fresh(&get_property("xterm")),
+		);
+
+		cache.invalidate(&property(0x0060_0001, 39, PropertyChange::Modified));
+		assert_eq!(
+			cache.get(window, WM_NAME),
+			CacheState::Dirty(&get_property("xterm")),
+		);
+
+		cache.store(window, WM_NAME, get_property("vim"));
+		assert_eq!(
+			cache.get(window, WM_NAME),
+			CacheState::Fresh(&get_property("vim")),
+		);
+
+		// Other properties and windows are unaffected.
+		assert_eq!(cache.get(window, Atom::new(37)), CacheState::Unknown);
+		assert_eq!(
+			cache.get(Window::new(0x0060_0002), WM_NAME),
+			CacheState::Unknown,
+		);
+	}
+
+	#[test]
+	fn test_property_cache_delete() {
+		let window = Window::new(0x0060_0001);
+		let mut cache = PropertyCache::new();
+
+		cache.store(window, WM_NAME, get_property("xterm"));
+		cache.invalidate(&property(0x0060_0001, 39, PropertyChange::Deleted));
+		assert_eq!(cache.get(window, WM_NAME), CacheState::Deleted);
+
+		// A deleted property which is added again must be fetched.
+		cache.invalidate(&property(0x0060_0001, 39, PropertyChange::Modified));
+		assert_eq!(cache.get(window, WM_NAME), CacheState::Unknown);
+
+		// A `GetProperty` reply for a property which does not exist.
+		let missing = reply::GetProperty {
+			sequence: 0,
+			format: None,
+			r#type: None,
+			bytes_remaining: 0,
+			value: request::DataList::I8(vec![]),
+		};
+
+		cache.store(window, Atom::new(37), missing);
+		assert_eq!(cache.get(window, Atom::new(37)), CacheState::Deleted);
+	}
+
+	#[test]
+	fn test_property_cache_destroy_evicts_window() {
+		let window = Window::new(0x0060_0001);
+		let other = Window::new(0x0060_0002);
+		let mut cache = PropertyCache::new();
+
+		cache.store(window, WM_NAME, get_property("xterm"));
+		cache.store(window, Atom::new(37), get_property("xterm"));
+		cache.store(other, WM_NAME, get_property("vim"));
+
+		cache.destroy(&Destroy {
+			sequence: 0,
+			event_window: window,
+			window,
+		});
+
+		assert_eq!(cache.get(window, WM_NAME), CacheState::Unknown);
+		assert_eq!(cache.get(window, Atom::new(37)), CacheState::Unknown);
+		assert_eq!(
+			cache.get(other, WM_NAME),
+			CacheState::Fresh(&get_property("vim")),
+		);
+	}
+}
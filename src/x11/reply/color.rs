@@ -17,7 +17,18 @@
 extern crate self as xrb;
 
 use derivative::Derivative;
-use xrbk::{Buf, BufMut, ConstantX11Size, ReadResult, Readable, Writable, WriteResult, X11Size};
+use xrbk::{
+	read_padding,
+	Buf,
+	BufMut,
+	ConstantX11Size,
+	ReadMode,
+	ReadResult,
+	Readable,
+	Writable,
+	WriteResult,
+	X11Size,
+};
 
 use xrbk_macro::derive_xrb;
 
@@ -35,6 +46,7 @@ derive_xrb! {
 	///
 	/// [`ListInstalledColormaps` request]: request::ListInstalledColormaps
 	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ListInstalledColormaps: Reply for request::ListInstalledColormaps {
 		/// The sequence number identifying the [request] that generated this
@@ -75,7 +87,8 @@ derive_xrb! {
 	///
 	/// [`AllocateColor` request]: request::AllocateColor
 	#[doc(alias("AllocColor"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct AllocateColor: Reply for request::AllocateColor {
 		/// The sequence number identifying the [request] that generated this
@@ -109,7 +122,8 @@ derive_xrb! {
 	///
 	/// [`AllocateNamedColor` request]: request::AllocateNamedColor
 	#[doc(alias("AllocNamedColor"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct AllocateNamedColor: Reply for request::AllocateNamedColor {
 		/// The sequence number identifying the [request] that generated this
@@ -125,6 +139,9 @@ derive_xrb! {
 		#[derivative(Hash = "ignore", PartialEq = "ignore")]
 		pub sequence: u16,
 
+		/// The [`ColorId`] referring to the `actual_color`.
+		pub color_id: ColorId,
+
 		/// The ideal or 'true' color which the name represents.
 		pub ideal_color: RgbColor,
 		/// The closest color that the display was able to provide.
@@ -139,6 +156,7 @@ derive_xrb! {
 	/// [`AllocateColorCells` request]: request::AllocateColorCells
 	#[doc(alias("AllocColorCells"))]
 	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct AllocateColorCells: Reply for request::AllocateColorCells {
 		/// The sequence number identifying the [request] that generated this
@@ -197,6 +215,7 @@ derive_xrb! {
 	/// [`AllocateColorPlanes` request]: request::AllocateColorPlanes
 	#[doc(alias("AllocColorPlanes"))]
 	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct AllocateColorPlanes: Reply for request::AllocateColorPlanes {
 		/// The sequence number identifying the [request] that generated this
@@ -249,6 +268,7 @@ derive_xrb! {
 ///
 /// [`QueryColors` request]: request::QueryColors
 #[derive(Derivative, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derivative(Hash, PartialEq, Eq)]
 pub struct QueryColors {
 	/// The sequence number identifying the [request] that generated this
@@ -285,27 +305,34 @@ impl X11Size for QueryColors {
 	fn x11_size(&self) -> usize {
 		const HEADER: usize = 8;
 
-		HEADER + u16::X11_SIZE + 22 + self.colors.x11_size()
+		// Each color is followed by 2 unused bytes.
+		HEADER + u16::X11_SIZE + 22 + self.colors.len() * (RgbColor::X11_SIZE + 2)
 	}
 }
 
 impl Readable for QueryColors {
 	fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
-		buf.advance(1);
-		let sequence = buf.get_u16();
+		Self::read_from_with_mode(buf, ReadMode::Lenient)
+	}
+
+	fn read_from_with_mode(buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Self> {
+		read_padding(buf, 1, mode)?;
+		let sequence = u16::read_from(buf)?;
 
-		let length = (buf.get_u32() as usize) * 4;
-		let buf = &mut buf.take(length - 8);
+		// The length does not include the first 32 bytes, of which 8 have
+		// already been read.
+		let length = (u32::read_from(buf)? as usize).saturating_mul(4);
+		let buf = &mut buf.take(length.saturating_add(24));
 
-		let colors_len = buf.get_u16();
-		buf.advance(22);
+		let colors_len = u16::read_from(buf)?;
+		read_padding(buf, 22, mode)?;
 
 		let colors = {
 			let mut colors = vec![];
 
 			for _ in 0..colors_len {
-				colors.push(RgbColor::read_from(buf)?);
-				buf.advance(2);
+				colors.push(RgbColor::read_from_with_mode(buf, mode)?);
+				read_padding(buf, 2, mode)?;
 			}
 
 			colors
@@ -318,7 +345,7 @@ impl Readable for QueryColors {
 impl Writable for QueryColors {
 	#[allow(clippy::cast_possible_truncation)]
 	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let buf = &mut buf.limit((self.length() as usize) * 4);
+		let buf = &mut buf.limit(self.x11_size());
 
 		buf.put_u8(1);
 		buf.put_u8(0);
@@ -344,7 +371,8 @@ derive_xrb! {
 	///
 	/// [`GetNamedColor` request]: request::GetNamedColor
 	#[doc(alias("LookupColor"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetNamedColor: Reply for request::GetNamedColor {
 		/// The sequence number identifying the [request] that generated this
@@ -372,3 +400,145 @@ derive_xrb! {
 		[_; ..],
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// Returns the bytes of a reply with the given `body` padded to 32 bytes,
+	/// followed by the given `lists` of 4-byte values.
+	fn reply_bytes(sequence: u16, length: u32, body: &[u8], lists: &[u32]) -> Vec<u8> {
+		let mut bytes = vec![1, 0];
+		bytes.extend_from_slice(&sequence.to_be_bytes());
+		bytes.extend_from_slice(&length.to_be_bytes());
+		bytes.extend_from_slice(body);
+		bytes.resize(32, 0);
+
+		for value in lists {
+			bytes.extend_from_slice(&value.to_be_bytes());
+		}
+
+		bytes
+	}
+
+	#[test]
+	fn test_allocate_color_cells_two_lists() {
+		// Two colors and three plane masks.
+		let bytes = reply_bytes(5, 5, &[0, 2, 0, 3], &[0x10, 0x20, 0x01, 0x02, 0x04]);
+
+		let reply = AllocateColorCells::read_from(&mut &bytes[1..]).unwrap();
+
+		assert_eq!(reply.sequence, 5);
+		assert_eq!(reply.colors, [ColorId::new(0x10), ColorId::new(0x20)]);
+		assert_eq!(reply.plane_masks, [0x01, 0x02, 0x04]);
+
+		let mut written = vec![];
+		reply.write_to(&mut written).unwrap();
+
+		assert_eq!(reply.x11_size(), bytes.len());
+		assert_eq!(written, bytes);
+	}
+
+	#[test]
+	fn test_allocate_color_cells_empty_colors() {
+		// No colors, but two plane masks: the plane masks must not be read as
+		// colors.
+		let bytes = reply_bytes(1, 2, &[0, 0, 0, 2], &[0x08, 0x80]);
+
+		let reply = AllocateColorCells::read_from(&mut &bytes[1..]).unwrap();
+
+		assert!(reply.colors.is_empty());
+		assert_eq!(reply.plane_masks, [0x08, 0x80]);
+	}
+
+	#[test]
+	fn test_allocate_color_planes() {
+		let mut body = vec![0, 2, 0, 0];
+		for mask in [0x0000_0f00_u32, 0x0000_00f0, 0x0000_000f] {
+			body.extend_from_slice(&mask.to_be_bytes());
+		}
+		let bytes = reply_bytes(9, 2, &body, &[0x1000, 0x2000]);
+
+		let reply = AllocateColorPlanes::read_from(&mut &bytes[1..]).unwrap();
+
+		assert_eq!(reply.sequence, 9);
+		assert_eq!(reply.red_plane_mask, 0x0f00);
+		assert_eq!(reply.green_plane_mask, 0x00f0);
+		assert_eq!(reply.blue_plane_mask, 0x000f);
+		assert_eq!(reply.colors, [ColorId::new(0x1000), ColorId::new(0x2000)]);
+
+		let mut written = vec![];
+		reply.write_to(&mut written).unwrap();
+
+		assert_eq!(written, bytes);
+	}
+
+	#[test]
+	fn test_list_installed_colormaps() {
+		let bytes = reply_bytes(3, 2, &[0, 2], &[0x20, 0x0040_0002]);
+
+		let reply = ListInstalledColormaps::read_from(&mut &bytes[1..]).unwrap();
+
+		assert_eq!(
+			reply.colormaps,
+			[Colormap::new(0x20), Colormap::new(0x0040_0002)]
+		);
+
+		let mut written = vec![];
+		reply.write_to(&mut written).unwrap();
+
+		assert_eq!(written, bytes);
+	}
+
+	#[test]
+	fn test_allocate_named_color() {
+		let reply = AllocateNamedColor {
+			sequence: 6,
+			color_id: ColorId::new(0x0012_3456),
+			ideal_color: RgbColor(0x6464, 0x9595, 0xeded),
+			actual_color: RgbColor(0x6400, 0x9500, 0xed00),
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(AllocateNamedColor::X11_SIZE, 32);
+		assert_eq!(bytes.len(), 32);
+		assert_eq!(bytes[4..8], [0, 0, 0, 0]);
+		assert_eq!(bytes[8..12], [0x00, 0x12, 0x34, 0x56]);
+		assert_eq!(bytes[12..14], [0x64, 0x64]);
+		assert_eq!(bytes[18..20], [0x64, 0x00]);
+		assert_eq!(
+			AllocateNamedColor::read_from(&mut &bytes[1..]).unwrap(),
+			reply
+		);
+	}
+
+	#[test]
+	fn test_query_colors_round_trip() {
+		let reply = QueryColors {
+			sequence: 11,
+			colors: vec![
+				RgbColor::BLACK,
+				RgbColor(0x1234, 0x5678, 0x9abc),
+				RgbColor::GRAY,
+			],
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		// 8 bytes per color: the RGB values and 2 unused bytes.
+		assert_eq!(bytes.len(), 32 + 3 * 8);
+		assert_eq!(reply.x11_size(), bytes.len());
+		assert_eq!(bytes[4..8], [0, 0, 0, 6]);
+		assert_eq!(bytes[8..10], [0, 3]);
+		assert_eq!(bytes[40..46], [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc]);
+
+		// Trailing bytes beyond the reply's length must not be read.
+		let mut buf = bytes.clone();
+		buf.extend_from_slice(&[0xff; 8]);
+
+		assert_eq!(QueryColors::read_from(&mut &buf[1..]).unwrap(), reply);
+	}
+}
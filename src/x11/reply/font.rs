@@ -20,9 +20,12 @@ use derivative::Derivative;
 
 use xrbk::{
 	pad,
+	read_padding,
+	skip,
 	Buf,
 	BufMut,
 	ConstantX11Size,
+	ReadMode,
 	ReadResult,
 	Readable,
 	ReadableWithContext,
@@ -38,6 +41,7 @@ use crate::{message::Reply, x11::request, Atom, LengthString8, String8};
 ///
 /// The value of this property is uninterpreted by XRB.
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FontProperty {
 	/// The name of the font property.
 	pub name: Atom,
@@ -53,6 +57,7 @@ pub struct FontProperty {
 ///
 /// For a nonexistent character, all of these fields are zero.
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CharacterInfo {
 	/// The extent of this character's appearance beyond its left edge.
 	///
@@ -98,6 +103,7 @@ impl ConstantX11Size for CharacterInfo {
 /// [`LeftToRight`]: DrawDirection::LeftToRight
 /// [`RightToLeft`]: DrawDirection::RightToLeft
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DrawDirection {
 	/// Most [`CharacterInfo`]s in the font have a positive width.
 	LeftToRight,
@@ -116,6 +122,7 @@ derive_xrb! {
 	///
 	/// [`QueryFont` request]: request::QueryFont
 	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct QueryFont: Reply for request::QueryFont {
 		/// The sequence number identifying the [request] that generated this
@@ -267,7 +274,8 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`QueryTextExtents` request]: request::QueryTextExtents
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct QueryTextExtents: Reply for request::QueryTextExtents {
 		/// The sequence number identifying the [request] that generated this
@@ -326,6 +334,7 @@ derive_xrb! {
 	///
 	/// [`ListFonts` request]: request::ListFonts
 	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ListFonts: Reply for request::ListFonts {
 		/// The sequence number identifying the [request] that generated this
@@ -362,6 +371,8 @@ derive_xrb! {
 /// [reply]: Reply
 ///
 /// [`ListFontsWithInfo` request]: request::ListFontsWithInfo
+#[derive(Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ListFontsWithInfo {
 	/// Information about one of the available fonts.
 	Font(FontWithInfo),
@@ -397,8 +408,8 @@ impl Readable for ListFontsWithInfo {
 	where
 		Self: Sized,
 	{
-		let name_len = buf.get_u8();
-		let sequence = buf.get_u16();
+		let name_len = u8::read_from(buf)?;
+		let sequence = u16::read_from(buf)?;
 
 		Ok(match name_len {
 			zero if zero == 0 => Self::Terminate(<_>::read_with(buf, &sequence)?),
@@ -431,6 +442,7 @@ impl Writable for ListFontsWithInfo {
 /// [`ListFontsWithInfo` request]: request::ListFontsWithInfo
 /// [`TerminateListFontsWithInfo` reply]: TerminateListFontsWithInfo
 #[derive(Derivative, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derivative(Hash, PartialEq, Eq)]
 pub struct FontWithInfo {
 	/// The sequence number identifying the [request] that generated this
@@ -597,7 +609,13 @@ impl X11Size for FontWithInfo {
 impl ReadableWithContext for FontWithInfo {
 	type Context = (u8, u16);
 
-	fn read_with(buf: &mut impl Buf, (name_len, sequence): &(u8, u16)) -> ReadResult<Self> {
+	fn read_with(buf: &mut impl Buf, context: &(u8, u16)) -> ReadResult<Self> {
+		Self::read_with_mode(buf, context, ReadMode::Lenient)
+	}
+
+	fn read_with_mode(
+		buf: &mut impl Buf, (name_len, sequence): &(u8, u16), mode: ReadMode,
+	) -> ReadResult<Self> {
 		let name_len = usize::from(*name_len);
 
 		// We skip the first 4 bytes because:
@@ -607,15 +625,15 @@ impl ReadableWithContext for FontWithInfo {
 		//   `ListFontsWithInfo` reply
 
 		// Read the length - take away the 8 bytes we've already read.
-		let length = ((buf.get_u32() as usize) * 4) + (32 - 8);
+		let length = ((u32::read_from(buf)? as usize) * 4) + (32 - 8);
 		// Limit `buf` by the read `length`.
 		let buf = &mut buf.take(length);
 
-		let min_bounds = CharacterInfo::read_from(buf)?;
-		buf.advance(4); // 4 unused bytes
+		let min_bounds = CharacterInfo::read_from_with_mode(buf, mode)?;
+		read_padding(buf, 4, mode)?; // 4 unused bytes
 
-		let max_bounds = CharacterInfo::read_from(buf)?;
-		buf.advance(4); // 4 unused bytes
+		let max_bounds = CharacterInfo::read_from_with_mode(buf, mode)?;
+		read_padding(buf, 4, mode)?; // 4 unused bytes
 
 		let first_character_or_min_minor_index = u16::read_from(buf)?;
 		let last_character_or_max_minor_index = u16::read_from(buf)?;
@@ -624,7 +642,7 @@ impl ReadableWithContext for FontWithInfo {
 
 		let properties_len = usize::from(u16::read_from(buf)?);
 
-		let draw_direction = DrawDirection::read_from(buf)?;
+		let draw_direction = DrawDirection::read_from_with_mode(buf, mode)?;
 
 		let min_major_index = u8::read_from(buf)?;
 		let max_major_index = u8::read_from(buf)?;
@@ -636,10 +654,10 @@ impl ReadableWithContext for FontWithInfo {
 
 		let replies_hint = u32::read_from(buf)?;
 
-		let properties = <Vec<FontProperty>>::read_with(buf, &properties_len)?;
+		let properties = <Vec<FontProperty>>::read_with_mode(buf, &properties_len, mode)?;
 
-		let name = String8::read_with(buf, &name_len)?;
-		buf.advance(pad(&name));
+		let name = String8::read_with_mode(buf, &name_len, mode)?;
+		read_padding(buf, pad(&name), mode)?;
 
 		Ok(Self {
 			sequence: *sequence,
@@ -725,6 +743,7 @@ impl Writable for FontWithInfo {
 ///
 /// [`ListFontsWithInfo` request]: request::ListFontsWithInfo
 #[derive(Derivative, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derivative(Hash, PartialEq, Eq)]
 pub struct TerminateListFontsWithInfo {
 	/// The sequence number identifying the [request] that generated this
@@ -774,7 +793,11 @@ impl Writable for TerminateListFontsWithInfo {
 impl ReadableWithContext for TerminateListFontsWithInfo {
 	type Context = u16;
 
-	fn read_with(buf: &mut impl Buf, sequence: &u16) -> ReadResult<Self> {
+	fn read_with(buf: &mut impl Buf, context: &u16) -> ReadResult<Self> {
+		Self::read_with_mode(buf, context, ReadMode::Lenient)
+	}
+
+	fn read_with_mode(buf: &mut impl Buf, sequence: &u16, mode: ReadMode) -> ReadResult<Self> {
 		// We skip the first 4 bytes because:
 		// - the first, `1`, was required to know this is a reply
 		// - the second was required to know this is the last reply for
@@ -784,10 +807,10 @@ impl ReadableWithContext for TerminateListFontsWithInfo {
 
 		// Then we skip the length because we know what it is meant to be... should
 		// probably verify that...
-		buf.advance(4);
+		skip(buf, 4)?;
 
 		// And then skip the 52 remaining unused bytes.
-		buf.advance(52);
+		read_padding(buf, 52, mode)?;
 
 		Ok(Self {
 			sequence: *sequence,
@@ -803,6 +826,7 @@ derive_xrb! {
 	/// [`GetFontSearchDirectories` request]: request::GetFontSearchDirectories
 	#[doc(alias = "GetFontPath")]
 	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetFontSearchDirectories: Reply for request::GetFontSearchDirectories {
 		/// The sequence number identifying the [request] that generated this
@@ -830,3 +854,84 @@ derive_xrb! {
 		[_; directories => pad(directories)],
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::Char8;
+
+	fn length_string8(name: &str) -> LengthString8 {
+		String8::from(name.bytes().map(Char8::new).collect::<Vec<_>>()).into()
+	}
+
+	#[test]
+	fn test_query_font_round_trip() {
+		let bounds = |width| CharacterInfo {
+			left_side_bearing: -1,
+			right_side_bearing: width - 1,
+			width,
+			ascent: 11,
+			descent: 3,
+			attributes: 0,
+		};
+
+		let reply = QueryFont {
+			sequence: 12,
+			min_bounds: bounds(5),
+			max_bounds: bounds(9),
+			first_character_or_min_minor_index: 32,
+			last_character_or_max_minor_index: 34,
+			fallback_character: 32,
+			draw_direction: DrawDirection::LeftToRight,
+			min_major_index: 0,
+			max_major_index: 0,
+			all_characters_exist: true,
+			font_ascent: 11,
+			font_descent: 3,
+			properties: vec![
+				FontProperty {
+					name: Atom::new(18),
+					value: [0, 0, 0, 140],
+				},
+				FontProperty {
+					name: Atom::new(19),
+					value: [0, 0, 1, 44],
+				},
+			],
+			character_infos: vec![bounds(5), bounds(7), bounds(9)],
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		// 60-byte header, 8 bytes per property, 12 per character info.
+		assert_eq!(bytes.len(), 60 + 2 * 8 + 3 * 12);
+		assert_eq!(reply.x11_size(), bytes.len());
+		assert_eq!(&bytes[4..8], &((bytes.len() as u32 - 32) / 4).to_be_bytes());
+		// Number of properties and number of character infos.
+		assert_eq!(&bytes[46..48], &[0, 2]);
+		assert_eq!(&bytes[56..60], &[0, 0, 0, 3]);
+
+		assert_eq!(QueryFont::read_from(&mut &bytes[1..]).unwrap(), reply);
+	}
+
+	#[test]
+	fn test_list_fonts_round_trip() {
+		let reply = ListFonts {
+			sequence: 4,
+			names: vec![length_string8("fixed"), length_string8("cursor")],
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		// 1 + 5 + 1 + 6 = 13 bytes of names, padded to 16.
+		assert_eq!(bytes.len(), 32 + 16);
+		assert_eq!(reply.x11_size(), bytes.len());
+		assert_eq!(&bytes[4..8], &[0, 0, 0, 4]);
+		assert_eq!(&bytes[8..10], &[0, 2]);
+		assert_eq!(&bytes[32..38], b"\x05fixed");
+
+		assert_eq!(ListFonts::read_from(&mut &bytes[1..]).unwrap(), reply);
+	}
+}
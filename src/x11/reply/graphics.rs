@@ -21,7 +21,7 @@ extern crate self as xrb;
 
 use derivative::Derivative;
 
-use xrbk::pad;
+use xrbk::{pad, read_padding, take_bytes, ReadMode, ReadResult, Readable, ReadableRef};
 use xrbk_macro::derive_xrb;
 
 use crate::{message::Reply, visual::VisualId, x11::request};
@@ -33,6 +33,7 @@ derive_xrb! {
 	///
 	/// [`CaptureImage` request]: request::CaptureImage
 	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct CaptureImage: Reply for request::CaptureImage {
 		/// The sequence number identifying the [request] that generated this
@@ -63,10 +64,155 @@ derive_xrb! {
 		pub visual: Option<VisualId>,
 		[_; 20],
 
-		// FIXME: how do we know what is padding and what is data?????
 		/// The image's data.
+		///
+		/// The length of the data is not encoded separately from the
+		/// [length] of the [reply], so any padding at the end of the data is
+		/// read as part of the data.
+		///
+		/// [length]: Reply::length
+		/// [reply]: Reply
 		#[context(self::remaining => remaining)]
 		pub data: Vec<u8>,
 		[_; data => pad(data)],
 	}
 }
+
+/// A [`CaptureImage` reply] which borrows its `data` from the bytes it was
+/// read from.
+///
+/// See [`ReadableRef`] for more information.
+///
+/// [`CaptureImage` reply]: CaptureImage
+#[derive(Derivative, Debug)]
+#[derivative(Hash, PartialEq, Eq)]
+pub struct CaptureImageRef<'a> {
+	/// The sequence number identifying the [request] that generated this
+	/// [reply].
+	///
+	/// See [`Reply::sequence`] for more information.
+	///
+	/// [request]: crate::message::Request
+	/// [reply]: Reply
+	///
+	/// [`Reply::sequence`]: Reply::sequence
+	#[derivative(Hash = "ignore", PartialEq = "ignore")]
+	pub sequence: u16,
+
+	/// The depth of the `target` [drawable] when it was created.
+	///
+	/// [drawable]: crate::Drawable
+	pub depth: u8,
+
+	/// The visual type of the `target` if it is a [window].
+	///
+	/// If the `target` is a [pixmap], this is [`None`].
+	///
+	/// [window]: crate::Window
+	/// [pixmap]: crate::Pixmap
+	pub visual: Option<VisualId>,
+
+	/// The image's data.
+	pub data: &'a [u8],
+}
+
+impl CaptureImageRef<'_> {
+	/// Copies the borrowed `data` to create an owned [`CaptureImage` reply].
+	///
+	/// [`CaptureImage` reply]: CaptureImage
+	#[must_use]
+	pub fn into_owned(self) -> CaptureImage {
+		CaptureImage {
+			sequence: self.sequence,
+			depth: self.depth,
+			visual: self.visual,
+			data: self.data.to_vec(),
+		}
+	}
+}
+
+impl<'a> ReadableRef<'a> for CaptureImageRef<'a> {
+	fn read_ref(buf: &mut &'a [u8]) -> ReadResult<Self> {
+		Self::read_ref_with_mode(buf, ReadMode::Lenient)
+	}
+
+	fn read_ref_with_mode(buf: &mut &'a [u8], mode: ReadMode) -> ReadResult<Self> {
+		let depth = u8::read_from(buf)?;
+		let sequence = u16::read_from(buf)?;
+
+		// The length does not include the first 32 bytes, of which 8 have
+		// already been read.
+		let length = (u32::read_from(buf)? as usize).saturating_mul(4);
+		let mut body = take_bytes(buf, length.saturating_add(24))?;
+
+		let visual = <Option<VisualId>>::read_from(&mut body)?;
+		read_padding(&mut body, 20, mode)?;
+
+		Ok(Self {
+			sequence,
+			depth,
+			visual,
+			data: body,
+		})
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use xrbk::{Writable, X11Size};
+
+	#[test]
+	fn test_capture_image_ref_agrees_with_owned() {
+		let reply = CaptureImage {
+			sequence: 9,
+			depth: 24,
+			visual: Some(VisualId::new(0x21)),
+			data: (0..=255).cycle().take(4096).collect(),
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		let bytes = &bytes[1..];
+
+		let owned = CaptureImage::read_from(&mut &bytes[..]).unwrap();
+		let borrowed = CaptureImageRef::read_ref(&mut &bytes[..]).unwrap();
+
+		// The data is borrowed directly from the bytes of the reply.
+		assert!(bytes.as_ptr_range().contains(&borrowed.data.as_ptr()));
+		assert_eq!(borrowed.data, &bytes[31..]);
+
+		assert_eq!(borrowed.into_owned(), owned);
+		assert_eq!(owned, reply);
+	}
+
+	#[test]
+	fn test_capture_image_3x3_zpixmap() {
+		let reply = CaptureImage {
+			sequence: 2,
+			depth: 8,
+			visual: None,
+			data: vec![
+				0x10, 0x11, 0x12, 0, //
+				0x20, 0x21, 0x22, 0, //
+				0x30, 0x31, 0x32, 0, //
+			],
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 44);
+		assert_eq!(reply.x11_size(), bytes.len());
+		// The reply, the depth, and the sequence number.
+		assert_eq!(bytes[..4], [1, 8, 0, 2]);
+		// The length of the reply, after the first 32 bytes, in 4-byte units.
+		assert_eq!(bytes[4..8], 3u32.to_be_bytes());
+		// The visual.
+		assert_eq!(bytes[8..12], [0, 0, 0, 0]);
+		assert_eq!(bytes[32..], reply.data);
+
+		assert_eq!(CaptureImage::read_from(&mut &bytes[1..]).unwrap(), reply);
+	}
+}
@@ -16,7 +16,8 @@ derive_xrb! {
 	///
 	/// [`QueryIdealDimensions` request]: request::QueryIdealDimensions
 	#[doc(alias("QueryBestSize"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct QueryIdealDimensions: Reply for request::QueryIdealDimensions {
 		/// The sequence number identifying the [request] that generated this
@@ -16,11 +16,25 @@
 
 extern crate self as xrb;
 
-use array_init::array_init;
+use array_init::try_array_init;
 use derivative::Derivative;
-use xrbk::{Buf, BufMut, ConstantX11Size, ReadResult, Readable, Writable, WriteResult, X11Size};
+use xrbk::{
+	pad,
+	read_padding,
+	take_bytes,
+	Buf,
+	BufMut,
+	ConstantX11Size,
+	ReadMode,
+	ReadResult,
+	Readable,
+	ReadableRef,
+	Writable,
+	WriteResult,
+	X11Size,
+};
 
-use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
+use xrbk_macro::{derive_xrb, ConstantX11Size, Readable, Writable, X11Size};
 
 use crate::{
 	message::Reply,
@@ -35,6 +49,7 @@ use crate::{
 	GrabStatus,
 	Keycode,
 	Keysym,
+	Modifier,
 	ModifierMask,
 	Timestamp,
 	Toggle,
@@ -48,7 +63,8 @@ derive_xrb! {
 	///
 	/// [`GrabCursor` request]: request::GrabCursor
 	#[doc(alias = "GrabPointer")]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GrabCursor: Reply for request::GrabCursor {
 		/// The sequence number identifying the [request] that generated this
@@ -79,7 +95,8 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`GrabKeyboard` request]: request::GrabKeyboard
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GrabKeyboard: Reply for request::GrabKeyboard {
 		/// The sequence number identifying the [request] that generated this
@@ -111,8 +128,9 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`QueryCursorLocation` request]: request::QueryCursorLocation
-	#[doc(alias("QueryPointer, QueryCursor, GetCursorPos, GetCursorLocation"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[doc(alias("QueryPointer", "QueryCursor", "GetCursorPos", "GetCursorLocation"))]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct QueryCursorLocation: Reply for request::QueryCursorLocation {
 		/// The sequence number identifying the [request] that generated this
@@ -174,6 +192,7 @@ derive_xrb! {
 ///
 /// [`GetMotionHistory` reply]: GetMotionHistory
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TimeCoords {
 	/// The [time] at which the cursor was at the `coords`.
 	///
@@ -191,6 +210,7 @@ derive_xrb! {
 	/// [`GetMotionHistory` request]: request::GetMotionHistory
 	#[doc(alias = "GetMotionEvents")]
 	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetMotionHistory: Reply for request::GetMotionHistory {
 		/// The sequence number identifying the [request] that generated this
@@ -225,7 +245,8 @@ derive_xrb! {
 	///
 	/// [`ConvertCoordinates` request]: request::ConvertCoordinates
 	#[doc(alias = "TranslateCoordinates")]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ConvertCoordinates: Reply for request::ConvertCoordinates {
 		/// The sequence number identifying the [request] that generated this
@@ -277,7 +298,8 @@ derive_xrb! {
 	///
 	/// [`GetFocus` request]: request::GetFocus
 	#[doc(alias = "GetInputFocus")]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetFocus: Reply for request::GetFocus {
 		/// The sequence number identifying the [request] that generated this
@@ -293,7 +315,7 @@ derive_xrb! {
 		#[derivative(Hash = "ignore", PartialEq = "ignore")]
 		pub sequence: u16,
 
-		/// What the focus will retain to if the focused [window] becomes
+		/// What the focus will revert to if the focused [window] becomes
 		/// unviewable.
 		///
 		/// [window]: Window
@@ -311,7 +333,8 @@ derive_xrb! {
 	///
 	/// [`QueryKeyboard` request]: request::QueryKeyboard
 	#[doc(alias = "QueryKeymap")]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct QueryKeyboard: Reply for request::QueryKeyboard {
 		/// The sequence number identifying the [request] that generated this
@@ -348,6 +371,7 @@ pub type KeyMapping = Vec<Keysym>;
 ///
 /// [`GetKeyboardMapping` request]: request::GetKeyboardMapping
 #[derive(Derivative, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derivative(Hash, PartialEq, Eq)]
 pub struct GetKeyboardMapping {
 	/// The sequence number identifying the [request] that generated this
@@ -391,8 +415,13 @@ impl Readable for GetKeyboardMapping {
 	where
 		Self: Sized,
 	{
-		const HEADER: usize = 8;
+		Self::read_from_with_mode(buf, ReadMode::Lenient)
+	}
 
+	fn read_from_with_mode(buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
 		// Header {{{
 
 		// FIXME: actually, replies need to have their first 4 bytes read before
@@ -400,21 +429,22 @@ impl Readable for GetKeyboardMapping {
 		//        and `sequence` should be context for `ReadableWithContext`.
 		//
 		// FIXME: This is a change that needs to be done for all replies...
-		buf.advance(1);
-		let keysyms_per_keycode = buf.get_u8();
-		let sequence = buf.get_u16();
+		let keysyms_per_keycode = u8::read_from(buf)?;
+		let sequence = u16::read_from(buf)?;
 
-		let length = (buf.get_u32() as usize) * 4;
-		let buf = &mut buf.take(length - HEADER);
+		// The length does not include the first 32 bytes, of which 8 have
+		// already been read.
+		let length = (u32::read_from(buf)? as usize).saturating_mul(4);
+		let buf = &mut buf.take(length.saturating_add(24));
 
 		// }}}
 
 		// 24 unused bytes.
-		buf.advance(24);
+		read_padding(buf, 24, mode)?;
 
 		let mappings = {
 			let mapping_size = usize::from(keysyms_per_keycode) * Keysym::X11_SIZE;
-			let mappings_len = buf.remaining() / mapping_size;
+			let mappings_len = buf.remaining().checked_div(mapping_size).unwrap_or(0);
 
 			let mut mappings = vec![];
 
@@ -422,7 +452,7 @@ impl Readable for GetKeyboardMapping {
 				let mut keysyms = vec![];
 
 				for _ in 0..keysyms_per_keycode {
-					keysyms.push(Keysym::read_from(buf)?);
+					keysyms.push(Keysym::read_from_with_mode(buf, mode)?);
 				}
 
 				mappings.push(keysyms);
@@ -445,7 +475,11 @@ impl Writable for GetKeyboardMapping {
 		// Indicates that this is a reply.
 		buf.put_u8(1);
 		// The number of keysyms in each mapping.
-		let mapping_size = self.mappings.x11_size() / self.mappings.len();
+		let mapping_size = self
+			.mappings
+			.x11_size()
+			.checked_div(self.mappings.len())
+			.unwrap_or(0);
 		let keysyms_per_keycode = (mapping_size / Keysym::X11_SIZE) as u8;
 		keysyms_per_keycode.write_to(buf)?;
 		// The sequence number.
@@ -465,6 +499,98 @@ impl Writable for GetKeyboardMapping {
 	}
 }
 
+/// A [`GetKeyboardMapping` reply] which borrows its [keysyms] from the bytes
+/// it was read from.
+///
+/// See [`ReadableRef`] for more information.
+///
+/// [keysyms]: Keysym
+/// [`GetKeyboardMapping` reply]: GetKeyboardMapping
+#[derive(Derivative, Debug)]
+#[derivative(Hash, PartialEq, Eq)]
+pub struct GetKeyboardMappingRef<'a> {
+	/// The sequence number identifying the [request] that generated this
+	/// [reply].
+	///
+	/// See [`Reply::sequence`] for more information.
+	///
+	/// [request]: crate::message::Request
+	/// [reply]: Reply
+	///
+	/// [`Reply::sequence`]: Reply::sequence
+	#[derivative(Hash = "ignore", PartialEq = "ignore")]
+	pub sequence: u16,
+
+	/// The number of [keysyms] in each [keycode]'s mapping.
+	///
+	/// [keycode]: Keycode
+	/// [keysyms]: Keysym
+	pub keysyms_per_keycode: u8,
+
+	/// The big-endian bytes of the [keysyms] in every mapping.
+	///
+	/// [keysyms]: Keysym
+	pub keysyms: &'a [u8],
+}
+
+impl<'a> GetKeyboardMappingRef<'a> {
+	/// Returns an iterator over the mapping of [keysyms] for each [keycode].
+	///
+	/// [keycode]: Keycode
+	/// [keysyms]: Keysym
+	pub fn mappings(&self) -> impl Iterator<Item = impl Iterator<Item = Keysym> + 'a> + 'a {
+		let mapping_size = usize::from(self.keysyms_per_keycode) * Keysym::X11_SIZE;
+
+		// There are no mappings if there are no keysyms per keycode
+		// (`chunks_exact` would panic with a chunk size of zero).
+		let keysyms: &'a [u8] = if mapping_size == 0 { &[] } else { self.keysyms };
+
+		keysyms.chunks_exact(mapping_size.max(1)).map(|mapping| {
+			mapping.chunks_exact(Keysym::X11_SIZE).map(|bytes| {
+				Keysym::new(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+			})
+		})
+	}
+
+	/// Copies the borrowed [keysyms] to create an owned
+	/// [`GetKeyboardMapping` reply].
+	///
+	/// [keysyms]: Keysym
+	/// [`GetKeyboardMapping` reply]: GetKeyboardMapping
+	#[must_use]
+	pub fn into_owned(self) -> GetKeyboardMapping {
+		GetKeyboardMapping {
+			sequence: self.sequence,
+			mappings: self.mappings().map(Iterator::collect).collect(),
+		}
+	}
+}
+
+impl<'a> ReadableRef<'a> for GetKeyboardMappingRef<'a> {
+	fn read_ref(buf: &mut &'a [u8]) -> ReadResult<Self> {
+		Self::read_ref_with_mode(buf, ReadMode::Lenient)
+	}
+
+	fn read_ref_with_mode(buf: &mut &'a [u8], mode: ReadMode) -> ReadResult<Self> {
+		let keysyms_per_keycode = u8::read_from(buf)?;
+		let sequence = u16::read_from(buf)?;
+
+		// The length does not include the first 32 bytes, of which 8 have
+		// already been read.
+		let length = (u32::read_from(buf)? as usize).saturating_mul(4);
+		let mut body = take_bytes(buf, length.saturating_add(24))?;
+
+		// 24 unused bytes.
+		read_padding(&mut body, 24, mode)?;
+
+		Ok(Self {
+			sequence,
+			keysyms_per_keycode,
+			keysyms: body,
+		})
+	}
+}
+
 derive_xrb! {
 	/// The [reply] to a [`GetKeyboardOptions` request].
 	///
@@ -472,7 +598,8 @@ derive_xrb! {
 	///
 	/// [`GetKeyboardOptions` request]: request::GetKeyboardOptions
 	#[doc(alias("GetKeyboardControl"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetKeyboardOptions: Reply for request::GetKeyboardOptions {
 		/// The sequence number identifying the [request] that generated this
@@ -559,7 +686,8 @@ derive_xrb! {
 	///
 	/// [`GetCursorOptions` request]: request::GetCursorOptions
 	#[doc(alias("GetPointerControl", "GetPointerOptions", "GetCursorControl"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetCursorOptions: Reply for request::GetCursorOptions {
 		/// The sequence number identifying the [request] that generated this
@@ -589,13 +717,32 @@ derive_xrb! {
 	}
 }
 
+impl GetKeyboardOptions {
+	/// Returns whether the given `keycode` has [auto repeat mode] enabled.
+	///
+	/// This reads the bit for `keycode` in [`auto_repeat_modes`]. Keys are
+	/// only repeated if the [`global_auto_repeat_mode`] is also enabled.
+	///
+	/// [auto repeat mode]: crate::set::KeyboardOptions::auto_repeat_mode
+	///
+	/// [`auto_repeat_modes`]: GetKeyboardOptions::auto_repeat_modes
+	/// [`global_auto_repeat_mode`]: GetKeyboardOptions::global_auto_repeat_mode
+	#[must_use]
+	pub const fn is_repeating(&self, keycode: Keycode) -> bool {
+		let code = keycode.unwrap();
+
+		self.auto_repeat_modes[(code / 8) as usize] & (1 << (code % 8)) != 0
+	}
+}
+
 /// Whether a [`SetButtonMapping` request] was successful.
 ///
 /// This is used in the [`SetButtonMapping` reply].
 ///
 /// [`SetButtonMapping` request]: request::SetButtonMapping
 /// [`SetButtonMapping` reply]: SetButtonMapping
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SetButtonMappingStatus {
 	/// The [`SetButtonMapping` request] was successful.
 	///
@@ -618,7 +765,8 @@ derive_xrb! {
 	///
 	/// [`SetButtonMapping` request]: request::SetButtonMapping
 	#[doc(alias("SetPointerMapping", "SetCursorMapping"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct SetButtonMapping: Reply for request::SetButtonMapping {
 		/// The sequence number identifying the [request] that generated this
@@ -639,6 +787,7 @@ derive_xrb! {
 		/// See [`SetButtonMappingStatus`] for more information.
 		///
 		/// [`SetButtonMapping` request]: request::SetButtonMapping
+		#[metabyte]
 		pub status: SetButtonMappingStatus,
 		[_; ..],
 	}
@@ -650,6 +799,7 @@ derive_xrb! {
 	/// [`GetButtonMapping` request]: request::GetButtonMapping
 	#[doc(alias("GetPointerMapping", "GetCursorMapping"))]
 	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetButtonMapping: Reply for request::GetButtonMapping {
 		/// The sequence number identifying the [request] that generated this
@@ -682,6 +832,7 @@ derive_xrb! {
 		/// [button]: Button
 		#[context(mappings_len => usize::from(*mappings_len))]
 		pub mappings: Vec<Option<Button>>,
+		[_; mappings => pad(mappings)],
 	}
 }
 
@@ -694,6 +845,7 @@ derive_xrb! {
 /// [`SetModifierMapping` request]: request::SetModifierMapping
 /// [`SetModifierMapping` reply]: SetModifierMapping
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SetModifierMappingStatus {
 	/// The [`SetModifierMapping` request] was successful.
 	///
@@ -727,7 +879,8 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`SetModifierMapping` request]: request::SetModifierMapping
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct SetModifierMapping: Reply for request::SetModifierMapping {
 		/// The sequence number identifying the [request] that generated this
@@ -763,6 +916,7 @@ derive_xrb! {
 ///
 /// [`GetModifierMapping` request]: request::GetModifierMapping
 #[derive(Derivative, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derivative(Hash, PartialEq, Eq)]
 pub struct GetModifierMapping {
 	/// The sequence number identifying the [request] that generated this
@@ -816,6 +970,24 @@ pub struct GetModifierMapping {
 }
 
 impl GetModifierMapping {
+	/// Returns the [keycodes] mapped to the given `modifier`.
+	///
+	/// [keycodes]: Keycode
+	#[must_use]
+	pub fn keycodes_for(&self, modifier: Modifier) -> &[Keycode] {
+		match modifier {
+			Modifier::Shift => &self.shift_keycodes,
+			Modifier::Lock => &self.capslock_keycodes,
+			Modifier::Control => &self.ctrl_keycodes,
+
+			Modifier::Mod1 => &self.mod1_keycodes,
+			Modifier::Mod2 => &self.mod2_keycodes,
+			Modifier::Mod3 => &self.mod3_keycodes,
+			Modifier::Mod4 => &self.mod4_keycodes,
+			Modifier::Mod5 => &self.mod5_keycodes,
+		}
+	}
+
 	fn max_keycodes_len(&self) -> usize {
 		[
 			&self.shift_keycodes,
@@ -858,33 +1030,40 @@ impl Readable for GetModifierMapping {
 	where
 		Self: Sized,
 	{
-		const HEADER: usize = 8;
-		const ALIGNMENT: usize = 4;
+		Self::read_from_with_mode(buf, ReadMode::Lenient)
+	}
 
+	fn read_from_with_mode(buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
 		// FIXME: the first 4 bytes of the header should be read separately, with the
 		// metabyte        position and sequence being given as context. That applies to
 		// all replies.
-		buf.advance(1);
+		let keycodes_per_modifier = u8::read_from(buf)?;
+		let sequence = u16::read_from(buf)?;
 
-		let keycodes_per_modifier = buf.get_u8();
-		let sequence = buf.get_u16();
+		// The length does not include the first 32 bytes, of which 8 have
+		// already been read.
+		let length = (u32::read_from(buf)? as usize).saturating_mul(4);
+		let buf = &mut buf.take(length.saturating_add(24));
 
-		let total_size = ((buf.get_u32() as usize) * ALIGNMENT) - HEADER;
-		let buf = &mut buf.take(total_size);
+		// 24 unused bytes.
+		read_padding(buf, 24, mode)?;
 
 		let [shift_keycodes, capslock_keycodes, ctrl_keycodes, mod1_keycodes, mod2_keycodes, mod3_keycodes, mod4_keycodes, mod5_keycodes] =
-			array_init(|_| {
+			try_array_init(|_| -> ReadResult<_> {
 				let mut keycodes = vec![];
 
 				for _ in 0..keycodes_per_modifier {
-					match buf.get_u8() {
+					match u8::read_from(buf)? {
 						0 => {},
 						code => keycodes.push(Keycode(code)),
 					}
 				}
 
-				keycodes
-			});
+				Ok(keycodes)
+			})?;
 
 		Ok(Self {
 			sequence,
@@ -901,3 +1080,502 @@ impl Readable for GetModifierMapping {
 		})
 	}
 }
+
+impl Writable for GetModifierMapping {
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		let max_keycodes_len = self.max_keycodes_len();
+		let buf = &mut buf.limit(self.x11_size());
+
+		// Header {{{
+
+		// Indicates that this is a reply.
+		buf.put_u8(1);
+		// The number of keycodes per modifier.
+		#[allow(clippy::cast_possible_truncation)]
+		buf.put_u8(max_keycodes_len as u8);
+		// The sequence number.
+		self.sequence.write_to(buf)?;
+
+		// The message length.
+		self.length().write_to(buf)?;
+
+		// }}}
+
+		// 24 unused bytes.
+		buf.put_bytes(0, 24);
+
+		// Each modifier's keycodes are padded with zeroes to the length of the
+		// longest list.
+		for modifier in Modifier::ALL {
+			let keycodes = self.keycodes_for(modifier);
+
+			for index in 0..max_keycodes_len {
+				match keycodes.get(index) {
+					Some(Keycode(code)) => buf.put_u8(*code),
+					None => buf.put_u8(0),
+				}
+			}
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use xrbk::ReadError;
+
+	#[test]
+	fn test_get_keyboard_mapping_ref_agrees_with_owned() {
+		let reply = GetKeyboardMapping {
+			sequence: 12,
+			mappings: (0..8)
+				.map(|keycode| (0..3).map(|i| Keysym::new(keycode * 0x100 + i)).collect())
+				.collect(),
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		let bytes = &bytes[1..];
+
+		let owned = GetKeyboardMapping::read_from(&mut &bytes[..]).unwrap();
+		let borrowed = GetKeyboardMappingRef::read_ref(&mut &bytes[..]).unwrap();
+
+		// The keysyms are borrowed directly from the bytes of the reply.
+		assert!(bytes.as_ptr_range().contains(&borrowed.keysyms.as_ptr()));
+		assert_eq!(borrowed.keysyms, &bytes[31..]);
+		assert_eq!(borrowed.keysyms_per_keycode, 3);
+
+		assert_eq!(borrowed.into_owned(), owned);
+		assert_eq!(owned, reply);
+	}
+
+	#[test]
+	fn test_get_motion_history_round_trip() {
+		let reply = GetMotionHistory {
+			sequence: 4,
+			motion_history: vec![
+				TimeCoords {
+					time: Timestamp::new(1_000),
+					coords: Coords::new(Px(10), Px(-20)),
+				},
+				TimeCoords {
+					time: Timestamp::new(1_016),
+					coords: Coords::new(Px(12), Px(-18)),
+				},
+			],
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32 + 2 * 8);
+		// The length of the reply, after the first 32 bytes, in 4-byte units.
+		assert_eq!(bytes[4..8], 4u32.to_be_bytes());
+		// The number of `TimeCoords`.
+		assert_eq!(bytes[8..12], 2u32.to_be_bytes());
+		assert_eq!(
+			bytes[32..40],
+			[0x00, 0x00, 0x03, 0xe8, 0x00, 0x0a, 0xff, 0xec]
+		);
+
+		let read = GetMotionHistory::read_from(&mut &bytes[1..]).unwrap();
+
+		assert_eq!(read.sequence, 4);
+		assert_eq!(read, reply);
+	}
+
+	#[test]
+	fn test_get_motion_history_empty() {
+		let mut bytes = vec![0; 32];
+		bytes[0] = 1;
+		bytes[2..4].copy_from_slice(&9u16.to_be_bytes());
+
+		let reply = GetMotionHistory::read_from(&mut &bytes[1..]).unwrap();
+
+		assert_eq!(reply.sequence, 9);
+		assert!(reply.motion_history.is_empty());
+
+		let mut written = vec![];
+		reply.write_to(&mut written).unwrap();
+		assert_eq!(written, bytes);
+	}
+
+	/// Returns the bytes of a `GrabCursor` reply with the given `status`
+	/// metabyte.
+	fn grab_cursor_bytes(status: u8) -> [u8; 32] {
+		let mut bytes = [0; 32];
+		bytes[0] = 1;
+		bytes[1] = status;
+		bytes[2..4].copy_from_slice(&3u16.to_be_bytes());
+
+		bytes
+	}
+
+	#[test]
+	fn test_grab_cursor_status() {
+		for (discrim, status) in [
+			(0, GrabStatus::Success),
+			(1, GrabStatus::AlreadyGrabbed),
+			(2, GrabStatus::InvalidTime),
+			(3, GrabStatus::NotViewable),
+			(4, GrabStatus::Frozen),
+		] {
+			let bytes = grab_cursor_bytes(discrim);
+			let reply = GrabCursor::read_from(&mut &bytes[1..]).unwrap();
+
+			assert_eq!(reply.sequence, 3);
+			assert_eq!(reply.grab_status, status);
+
+			let mut written = vec![];
+			reply.write_to(&mut written).unwrap();
+			assert_eq!(written, bytes);
+		}
+	}
+
+	#[test]
+	fn test_grab_cursor_unrecognized_status() {
+		let bytes = grab_cursor_bytes(5);
+
+		assert!(matches!(
+			GrabCursor::read_from(&mut &bytes[1..]),
+			Err(ReadError::UnrecognizedDiscriminant(5)),
+		));
+	}
+
+	#[test]
+	fn test_grab_keyboard_status() {
+		for (discrim, status) in [
+			(0, GrabStatus::Success),
+			(1, GrabStatus::AlreadyGrabbed),
+			(2, GrabStatus::InvalidTime),
+			(3, GrabStatus::NotViewable),
+			(4, GrabStatus::Frozen),
+		] {
+			// `GrabKeyboard` replies are laid out the same as `GrabCursor`
+			// replies.
+			let bytes = grab_cursor_bytes(discrim);
+			let reply = GrabKeyboard::read_from(&mut &bytes[1..]).unwrap();
+
+			assert_eq!(reply.grab_status, status);
+
+			let mut written = vec![];
+			reply.write_to(&mut written).unwrap();
+			assert_eq!(written, bytes);
+		}
+
+		let bytes = grab_cursor_bytes(5);
+		assert!(matches!(
+			GrabKeyboard::read_from(&mut &bytes[1..]),
+			Err(ReadError::UnrecognizedDiscriminant(5)),
+		));
+	}
+
+	#[test]
+	fn test_get_focus_cursor_root() {
+		let mut bytes = [0; 32];
+		bytes[..4].copy_from_slice(&[1, 1, 0, 9]);
+		// A focus of 1 is `CursorRoot`, not the window with ID 1.
+		bytes[8..12].copy_from_slice(&1u32.to_be_bytes());
+
+		let reply = GetFocus::read_from(&mut &bytes[1..]).unwrap();
+
+		assert_eq!(reply.revert_to, RevertFocus::CursorRoot);
+		assert_eq!(reply.focus, FocusWindow::CursorRoot);
+
+		let mut written = vec![];
+		reply.write_to(&mut written).unwrap();
+		assert_eq!(written, bytes);
+	}
+
+	#[test]
+	fn test_get_focus_round_trip() {
+		for (revert_to, focus) in [
+			(RevertFocus::None, FocusWindow::None),
+			(
+				RevertFocus::Parent,
+				FocusWindow::Other(Window::new(0x0060_0001)),
+			),
+		] {
+			let reply = GetFocus {
+				sequence: 9,
+				revert_to,
+				focus,
+			};
+
+			let mut bytes = vec![];
+			reply.write_to(&mut bytes).unwrap();
+
+			assert_eq!(bytes.len(), 32);
+			assert_eq!(GetFocus::read_from(&mut &bytes[1..]).unwrap(), reply);
+		}
+	}
+
+	#[test]
+	fn test_query_cursor_location() {
+		let reply = QueryCursorLocation {
+			sequence: 4,
+			same_screen: true,
+			root: Window::new(0x0000_0512),
+			child: Some(Window::new(0x0060_0001)),
+			root_coords: Coords::new(Px(640), Px(480)),
+			target_coords: Coords::new(Px(20), Px(-4)),
+			modifiers: ModifierMask::SHIFT | ModifierMask::BUTTON_1,
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(QueryCursorLocation::X11_SIZE, 32);
+		assert_eq!(
+			bytes,
+			[
+				1, 1, 0, 4, // header
+				0, 0, 0, 0, // length
+				0x00, 0x00, 0x05, 0x12, // root
+				0x00, 0x60, 0x00, 0x01, // child
+				0x02, 0x80, 0x01, 0xe0, // root_coords
+				0x00, 0x14, 0xff, 0xfc, // target_coords
+				0x01, 0x01, // modifiers
+				0, 0, 0, 0, 0, 0,
+			],
+		);
+		assert_eq!(
+			QueryCursorLocation::read_from(&mut &bytes[1..]).unwrap(),
+			reply
+		);
+	}
+
+	#[test]
+	fn test_convert_coordinates() {
+		let reply = ConvertCoordinates {
+			sequence: 5,
+			same_screen: false,
+			child: None,
+			output_coords: Coords::new(Px(-600), Px(12)),
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(ConvertCoordinates::X11_SIZE, 32);
+		assert_eq!(
+			bytes[..16],
+			[
+				1, 0, 0, 5, // header
+				0, 0, 0, 0, // length
+				0, 0, 0, 0, // child
+				0xfd, 0xa8, 0x00, 0x0c, // output_coords
+			],
+		);
+		assert_eq!(bytes[16..], [0; 16]);
+		assert_eq!(
+			ConvertCoordinates::read_from(&mut &bytes[1..]).unwrap(),
+			reply
+		);
+	}
+
+	#[test]
+	fn test_get_keyboard_mapping_round_trip() {
+		let reply = GetKeyboardMapping {
+			sequence: 12,
+			mappings: (0..8)
+				.map(|keycode| (0..4).map(|i| Keysym::new(keycode * 0x100 + i)).collect())
+				.collect(),
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32 + 8 * 4 * 4);
+		// The reply, `keysyms_per_keycode`, and the sequence number.
+		assert_eq!(bytes[..4], [1, 4, 0, 12]);
+		// The length of the reply, after the first 32 bytes, in 4-byte units.
+		assert_eq!(bytes[4..8], 32u32.to_be_bytes());
+
+		let read = GetKeyboardMapping::read_from(&mut &bytes[1..]).unwrap();
+
+		assert_eq!(read.mappings.len(), 8);
+		assert!(read.mappings.iter().all(|mapping| mapping.len() == 4));
+		assert_eq!(read, reply);
+	}
+
+	#[test]
+	fn test_get_modifier_mapping_round_trip() {
+		let reply = GetModifierMapping {
+			sequence: 7,
+
+			shift_keycodes: vec![Keycode::new(50), Keycode::new(62)],
+			capslock_keycodes: vec![Keycode::new(66)],
+			ctrl_keycodes: vec![Keycode::new(37), Keycode::new(105)],
+
+			mod1_keycodes: vec![Keycode::new(64), Keycode::new(108)],
+			mod2_keycodes: vec![Keycode::new(77)],
+			mod3_keycodes: vec![],
+			mod4_keycodes: vec![Keycode::new(133), Keycode::new(134)],
+			mod5_keycodes: vec![Keycode::new(92)],
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32 + 8 * 2);
+		// The reply, `keycodes_per_modifier`, and the sequence number.
+		assert_eq!(bytes[..4], [1, 2, 0, 7]);
+		// The length of the reply, after the first 32 bytes, in 4-byte units.
+		assert_eq!(bytes[4..8], 4u32.to_be_bytes());
+		// Shorter lists are padded with zeroes.
+		assert_eq!(
+			bytes[32..],
+			[50, 62, 66, 0, 37, 105, 64, 108, 77, 0, 0, 0, 133, 134, 92, 0],
+		);
+
+		let read = GetModifierMapping::read_from(&mut &bytes[1..]).unwrap();
+
+		assert_eq!(read.sequence, 7);
+		// The zero padding is not read as keycodes.
+		assert_eq!(read.keycodes_for(Modifier::Lock), [Keycode::new(66)]);
+		assert_eq!(read.keycodes_for(Modifier::Mod3), []);
+		assert_eq!(read, reply);
+
+		let mut written = vec![];
+		read.write_to(&mut written).unwrap();
+		assert_eq!(written, bytes);
+	}
+
+	#[test]
+	fn test_set_modifier_mapping_status() {
+		for (discrim, status) in [
+			(0, SetModifierMappingStatus::Success),
+			(1, SetModifierMappingStatus::Busy),
+			(2, SetModifierMappingStatus::Rejected),
+		] {
+			let mut bytes = [0; 32];
+			bytes[..4].copy_from_slice(&[1, discrim, 0, 3]);
+
+			let reply = SetModifierMapping::read_from(&mut &bytes[1..]).unwrap();
+			assert_eq!(reply.status, status);
+
+			let mut written = vec![];
+			reply.write_to(&mut written).unwrap();
+			assert_eq!(written, bytes);
+		}
+	}
+
+	#[test]
+	fn test_get_cursor_options() {
+		let reply = GetCursorOptions {
+			sequence: 8,
+			acceleration: Fraction::<Px<u16>>::new(Px(2), Px(1)).unwrap(),
+			threshold: Px(4),
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32);
+		assert_eq!(bytes[..14], [1, 0, 0, 8, 0, 0, 0, 0, 0, 2, 0, 1, 0, 4],);
+		assert_eq!(
+			GetCursorOptions::read_from(&mut &bytes[1..]).unwrap(),
+			reply
+		);
+	}
+
+	#[test]
+	fn test_set_button_mapping_status() {
+		for (discrim, status) in [
+			(0, SetButtonMappingStatus::Success),
+			(1, SetButtonMappingStatus::Busy),
+		] {
+			let reply = SetButtonMapping {
+				sequence: 3,
+				status,
+			};
+
+			let mut bytes = vec![];
+			reply.write_to(&mut bytes).unwrap();
+
+			let mut expected = [0; 32];
+			// The status is in the metabyte.
+			expected[..4].copy_from_slice(&[1, discrim, 0, 3]);
+
+			assert_eq!(bytes, expected);
+			assert_eq!(
+				SetButtonMapping::read_from(&mut &bytes[1..]).unwrap(),
+				reply
+			);
+		}
+	}
+
+	#[test]
+	fn test_get_button_mapping_padding() {
+		let reply = GetButtonMapping {
+			sequence: 4,
+			mappings: vec![
+				Some(Button::new(1)),
+				None,
+				Some(Button::new(3)),
+				Some(Button::new(4)),
+				Some(Button::new(5)),
+			],
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32 + 8);
+		// The reply, the number of mappings, and the sequence number.
+		assert_eq!(bytes[..4], [1, 5, 0, 4]);
+		// The length of the reply, after the first 32 bytes, in 4-byte units.
+		assert_eq!(bytes[4..8], 2u32.to_be_bytes());
+		assert_eq!(bytes[32..], [1, 0, 3, 4, 5, 0, 0, 0]);
+
+		assert_eq!(
+			GetButtonMapping::read_from(&mut &bytes[1..]).unwrap(),
+			reply
+		);
+	}
+
+	#[test]
+	fn test_get_keyboard_options() {
+		let mut auto_repeat_modes = [0; 32];
+		// Keycodes 9 and 255.
+		auto_repeat_modes[1] = 0b0000_0010;
+		auto_repeat_modes[31] = 0b1000_0000;
+
+		let reply = GetKeyboardOptions {
+			sequence: 6,
+			global_auto_repeat_mode: Toggle::Enabled,
+			led_mask: 0b101,
+			key_click_volume: Percentage::new(50).unwrap(),
+			bell_volume: Percentage::new(100).unwrap(),
+			bell_pitch: Hz(400),
+			bell_duration: Ms(100),
+			auto_repeat_modes,
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 52);
+		// The reply, the global auto repeat mode, and the sequence number.
+		assert_eq!(bytes[..4], [1, 1, 0, 6]);
+		// The length of the reply, after the first 32 bytes, in 4-byte units.
+		assert_eq!(bytes[4..8], 5u32.to_be_bytes());
+		assert_eq!(bytes[8..12], 5u32.to_be_bytes());
+		assert_eq!(bytes[12..20], [50, 100, 0x01, 0x90, 0, 100, 0, 0]);
+		assert_eq!(bytes[20..], auto_repeat_modes);
+
+		assert_eq!(
+			GetKeyboardOptions::read_from(&mut &bytes[1..]).unwrap(),
+			reply
+		);
+
+		assert!(reply.is_repeating(Keycode(9)));
+		assert!(reply.is_repeating(Keycode(255)));
+		assert!(!reply.is_repeating(Keycode(8)));
+		assert!(!reply.is_repeating(Keycode(254)));
+	}
+}
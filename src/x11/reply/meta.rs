@@ -17,6 +17,7 @@
 extern crate self as xrb;
 
 use derivative::Derivative;
+use std::time::Duration;
 use xrbk::pad;
 use xrbk_macro::derive_xrb;
 
@@ -28,7 +29,8 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`QueryExtension` request]: request::QueryExtension
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct QueryExtension: Reply for request::QueryExtension {
 		/// The sequence number identifying the [request] that generated this
@@ -64,6 +66,7 @@ derive_xrb! {
 		/// [errors]: crate::message::Error
 		/// [event code]: crate::message::Event::CODE
 		pub first_error_code: Option<u8>,
+		[_; ..],
 	}
 
 	/// The [reply] to a [`ListExtensions` request].
@@ -72,6 +75,7 @@ derive_xrb! {
 	///
 	/// [`ListExtensions` request]: request::ListExtensions
 	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ListExtensions: Reply for request::ListExtensions {
 		/// The sequence number identifying the [request] that generated this
@@ -105,7 +109,8 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`GetScreenSaver` request]: request::GetScreenSaver
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetScreenSaver: Reply for request::GetScreenSaver {
 		/// The sequence number identifying the [request] that generated this
@@ -167,6 +172,7 @@ derive_xrb! {
 	/// [`QueryAccessControl` request]: request::QueryAccessControl
 	#[doc(alias("ListHosts"))]
 	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct QueryAccessControl: Reply for request::QueryAccessControl {
 		/// The sequence number identifying the [request] that generated this
@@ -177,7 +183,7 @@ derive_xrb! {
 		/// [request]: crate::message::Request
 		/// [reply]: Reply
 		///
-		/// [`Reply::sequence`]: Reply
+		/// [`Reply::sequence`]: Reply::sequence
 		#[sequence]
 		#[derivative(Hash = "ignore", PartialEq = "ignore")]
 		pub sequence: u16,
@@ -202,3 +208,156 @@ derive_xrb! {
 		// added at the end here.
 	}
 }
+
+impl GetScreenSaver {
+	/// Returns the [`timeout`] as a [`Duration`], if the screensaver is
+	/// enabled.
+	///
+	/// [`timeout`]: GetScreenSaver::timeout
+	#[must_use]
+	pub fn timeout_duration(&self) -> Option<Duration> {
+		self.timeout
+			.map(|Sec(seconds)| Duration::from_secs(seconds.into()))
+	}
+
+	/// Returns the [`interval`] as a [`Duration`], if periodic changes are
+	/// hinted.
+	///
+	/// [`interval`]: GetScreenSaver::interval
+	#[must_use]
+	pub fn interval_duration(&self) -> Option<Duration> {
+		self.interval
+			.map(|Sec(seconds)| Duration::from_secs(seconds.into()))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{AsciiString, Char8, HostAddress, String8};
+	use xrbk::{ConstantX11Size, Readable, Writable, X11Size};
+
+	#[test]
+	fn test_query_extension_is_32_bytes() {
+		let reply = QueryExtension {
+			sequence: 3,
+			present: true,
+			major_opcode: Some(140),
+			first_event_code: Some(89),
+			first_error_code: None,
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(QueryExtension::X11_SIZE, 32);
+		assert_eq!(bytes.len(), 32);
+		// `present`, `major_opcode`, `first_event_code`, and
+		// `first_error_code`, which is zero when absent.
+		assert_eq!(bytes[8..12], [1, 140, 89, 0]);
+		assert_eq!(QueryExtension::read_from(&mut &bytes[1..]).unwrap(), reply);
+	}
+
+	#[test]
+	fn test_get_screen_saver_reads_long_timeout() {
+		let mut bytes = vec![1, 0, 0, 7, 0, 0, 0, 0];
+		// `timeout` of 600 seconds and `interval` of 0 seconds.
+		bytes.extend_from_slice(&[0x02, 0x58, 0, 0]);
+		// `prefer_blanking` and `allow_expose_events`.
+		bytes.extend_from_slice(&[1, 0]);
+		bytes.resize(32, 0);
+
+		let reply = GetScreenSaver::read_from(&mut &bytes[1..]).unwrap();
+
+		assert_eq!(reply.timeout, Some(Sec(600)));
+		assert_eq!(reply.timeout_duration(), Some(Duration::from_secs(600)));
+		assert_eq!(reply.interval_duration(), None);
+	}
+
+	#[test]
+	fn test_list_extensions_fixture() {
+		let names = [
+			"BIG-REQUESTS",
+			"Composite",
+			"DAMAGE",
+			"DOUBLE-BUFFER",
+			"GLX",
+			"MIT-SHM",
+			"Present",
+			"RANDR",
+			"RENDER",
+			"SHAPE",
+			"SYNC",
+			"X-Resource",
+			"XC-MISC",
+			"XFIXES",
+		];
+
+		let mut list = vec![];
+		for name in names {
+			list.push(u8::try_from(name.len()).unwrap());
+			list.extend_from_slice(name.as_bytes());
+		}
+		let padding = pad(&list);
+		list.resize(list.len() + padding, 0);
+
+		let mut bytes = vec![1, u8::try_from(names.len()).unwrap(), 0, 5];
+		bytes.extend_from_slice(&u32::try_from(list.len() / 4).unwrap().to_be_bytes());
+		bytes.resize(32, 0);
+		bytes.extend_from_slice(&list);
+
+		let reply = ListExtensions::read_from(&mut &bytes[1..]).unwrap();
+
+		let expected: Vec<LengthString8> = names
+			.iter()
+			.map(|name| String8::from(name.bytes().map(Char8::new).collect::<Vec<_>>()).into())
+			.collect();
+		assert_eq!(reply.names, expected);
+
+		let mut written = vec![];
+		reply.write_to(&mut written).unwrap();
+		assert_eq!(written, bytes);
+		assert_eq!(reply.x11_size(), bytes.len());
+	}
+
+	#[test]
+	#[allow(deprecated)]
+	fn test_query_access_control() {
+		let reply = QueryAccessControl {
+			sequence: 9,
+			access_control: Toggle::Enabled,
+			hosts: vec![
+				Host::new(HostAddress::Ipv4([127, 0, 0, 1])),
+				Host::new(HostAddress::ServerInterpreted {
+					address_type: AsciiString::new(b"localuser".to_vec()).unwrap(),
+					address_value: AsciiString::new(b"root".to_vec()).unwrap(),
+				}),
+				Host::new(HostAddress::Ipv6([
+					0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+				])),
+			],
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		// 4 + 4, 4 + 16 (14 bytes of address, padded), and 4 + 16.
+		assert_eq!(bytes.len(), 32 + 8 + 20 + 20);
+		// The reply, whether access control is enabled, and the sequence number.
+		assert_eq!(bytes[..4], [1, 1, 0, 9]);
+		// The length of the reply, after the first 32 bytes, in 4-byte units.
+		assert_eq!(bytes[4..8], 12u32.to_be_bytes());
+		// The number of hosts.
+		assert_eq!(bytes[8..10], [0, 3]);
+
+		assert_eq!(bytes[32..40], [0, 0, 0, 4, 127, 0, 0, 1]);
+		assert_eq!(bytes[40..44], [5, 0, 0, 14]);
+		assert_eq!(&bytes[44..60], b"localuser\0root\0\0");
+		assert_eq!(bytes[60..64], [6, 0, 0, 16]);
+
+		assert_eq!(
+			QueryAccessControl::read_from(&mut &bytes[1..]).unwrap(),
+			reply
+		);
+	}
+}
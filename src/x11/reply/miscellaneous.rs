@@ -18,7 +18,7 @@ extern crate self as xrb;
 
 use derivative::Derivative;
 
-use xrbk::pad;
+use xrbk::{pad, read_padding, take_bytes, ReadMode, ReadResult, Readable, ReadableRef};
 use xrbk_macro::derive_xrb;
 
 use crate::{
@@ -36,7 +36,8 @@ derive_xrb! {
 	///
 	/// [`GetAtom` request]: request::GetAtom
 	#[doc(alias("InternAtom", "CreateAtom"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetAtom: Reply for request::GetAtom {
 		/// The sequence number identifying the [request] that generated this
@@ -64,10 +65,11 @@ derive_xrb! {
 
 	/// The [reply] to a [`GetAtomName` request].
 	///
-	/// [reply]: crate::message
+	/// [reply]: Reply
 	///
 	/// [`GetAtomName` request]: request::GetAtomName
 	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetAtomName: Reply for request::GetAtomName {
 		/// The sequence number identifying the [request] that generated this
@@ -102,6 +104,7 @@ derive_xrb! {
 	///
 	/// [`GetProperty` request]: request::GetProperty
 	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetProperty: Reply for request::GetProperty {
 		/// The sequence number identifying the [request] that generated this
@@ -154,6 +157,7 @@ derive_xrb! {
 		/// empty list.
 		#[context(format, value_len => (format.unwrap_or(DataFormat::I8), *value_len))]
 		pub value: DataList,
+		[_; value => pad(value)],
 	}
 
 	/// The [reply] for a [`ListProperties` request].
@@ -162,6 +166,7 @@ derive_xrb! {
 	///
 	/// [`ListProperties` request]: request::ListProperties
 	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ListProperties: Reply for request::ListProperties {
 		/// The sequence number identifying the [request] that generated this
@@ -195,7 +200,8 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`GetSelectionOwner` request]: request::GetSelectionOwner
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetSelectionOwner: Reply for request::GetSelectionOwner {
 		/// The sequence number identifying the [request] that generated this
@@ -218,3 +224,366 @@ derive_xrb! {
 		[_; ..],
 	}
 }
+
+/// A [`GetProperty` reply] which borrows its `value` from the bytes it was read
+/// from.
+///
+/// See [`ReadableRef`] for more information.
+///
+/// [`GetProperty` reply]: GetProperty
+#[derive(Derivative, Debug)]
+#[derivative(Hash, PartialEq, Eq)]
+pub struct GetPropertyRef<'a> {
+	/// The sequence number identifying the [request] that generated this
+	/// [reply].
+	///
+	/// See [`Reply::sequence`] for more information.
+	///
+	/// [request]: crate::message::Request
+	/// [reply]: Reply
+	///
+	/// [`Reply::sequence`]: Reply::sequence
+	#[derivative(Hash = "ignore", PartialEq = "ignore")]
+	pub sequence: u16,
+
+	/// Whether the `value` is empty ([`None`]), or made up of `i8` values,
+	/// `i16` values, or `i32` values.
+	pub format: Option<DataFormat>,
+
+	/// The actual type of the property.
+	pub r#type: Option<Atom>,
+	/// The number of bytes remaining in the `property`'s data.
+	///
+	/// See [`GetProperty::bytes_remaining`] for more information.
+	#[doc(alias = "bytes_after")]
+	pub bytes_remaining: u32,
+
+	/// The property's value, as the big-endian bytes of its `format`'s values.
+	pub value: &'a [u8],
+}
+
+impl<'a> GetPropertyRef<'a> {
+	/// Returns an iterator over the borrowed `value`'s `i8` values.
+	///
+	/// Returns [`None`] if the `format` is not [`DataFormat::I8`].
+	#[must_use]
+	pub fn i8_values(&self) -> Option<impl Iterator<Item = i8> + 'a> {
+		let value: &'a [u8] = self.value;
+
+		(self.format == Some(DataFormat::I8))
+			.then(|| value.iter().map(|byte| i8::from_be_bytes([*byte])))
+	}
+
+	/// Returns an iterator over the borrowed `value`'s `i16` values.
+	///
+	/// Returns [`None`] if the `format` is not [`DataFormat::I16`].
+	#[must_use]
+	pub fn i16_values(&self) -> Option<impl Iterator<Item = i16> + 'a> {
+		let value: &'a [u8] = self.value;
+
+		(self.format == Some(DataFormat::I16)).then(|| {
+			value
+				.chunks_exact(2)
+				.map(|bytes| i16::from_be_bytes([bytes[0], bytes[1]]))
+		})
+	}
+
+	/// Returns an iterator over the borrowed `value`'s `i32` values.
+	///
+	/// Returns [`None`] if the `format` is not [`DataFormat::I32`].
+	#[must_use]
+	pub fn i32_values(&self) -> Option<impl Iterator<Item = i32> + 'a> {
+		let value: &'a [u8] = self.value;
+
+		(self.format == Some(DataFormat::I32)).then(|| {
+			value
+				.chunks_exact(4)
+				.map(|bytes| i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+		})
+	}
+
+	/// Copies the borrowed `value` to create an owned [`GetProperty` reply].
+	///
+	/// [`GetProperty` reply]: GetProperty
+	#[must_use]
+	pub fn into_owned(self) -> GetProperty {
+		let value = match self.format.unwrap_or(DataFormat::I8) {
+			DataFormat::I8 => DataList::I8(
+				self.value
+					.iter()
+					.map(|byte| i8::from_be_bytes([*byte]))
+					.collect(),
+			),
+
+			DataFormat::I16 => DataList::I16(
+				self.value
+					.chunks_exact(2)
+					.map(|bytes| i16::from_be_bytes([bytes[0], bytes[1]]))
+					.collect(),
+			),
+
+			DataFormat::I32 => DataList::I32(
+				self.value
+					.chunks_exact(4)
+					.map(|bytes| i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+					.collect(),
+			),
+		};
+
+		GetProperty {
+			sequence: self.sequence,
+			format: self.format,
+			r#type: self.r#type,
+			bytes_remaining: self.bytes_remaining,
+			value,
+		}
+	}
+}
+
+impl<'a> ReadableRef<'a> for GetPropertyRef<'a> {
+	fn read_ref(buf: &mut &'a [u8]) -> ReadResult<Self> {
+		Self::read_ref_with_mode(buf, ReadMode::Lenient)
+	}
+
+	fn read_ref_with_mode(buf: &mut &'a [u8], mode: ReadMode) -> ReadResult<Self> {
+		let format = <Option<DataFormat>>::read_from(buf)?;
+		let sequence = u16::read_from(buf)?;
+
+		// The length does not include the first 32 bytes, of which 8 have
+		// already been read.
+		let length = (u32::read_from(buf)? as usize).saturating_mul(4);
+		let mut body = take_bytes(buf, length.saturating_add(24))?;
+
+		let r#type = <Option<Atom>>::read_from(&mut body)?;
+		let bytes_remaining = u32::read_from(&mut body)?;
+		let value_len = u32::read_from(&mut body)? as usize;
+		read_padding(&mut body, 12, mode)?;
+
+		let value_size = match format.unwrap_or(DataFormat::I8) {
+			DataFormat::I8 => 1,
+			DataFormat::I16 => 2,
+			DataFormat::I32 => 4,
+		};
+		let value = take_bytes(&mut body, value_len.saturating_mul(value_size))?;
+
+		Ok(Self {
+			sequence,
+			format,
+			r#type,
+			bytes_remaining,
+			value,
+		})
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::Char8;
+	use xrbk::{ReadError, ReadMode, Readable, ReadableRef, Verbatim, Writable, X11Size};
+
+	#[test]
+	fn test_get_atom_round_trip() {
+		for (atom, encoded) in [
+			(Some(Atom::new(0x0000_0145)), [0x00, 0x00, 0x01, 0x45]),
+			(None, [0, 0, 0, 0]),
+		] {
+			let reply = GetAtom { sequence: 2, atom };
+
+			let mut bytes = vec![];
+			reply.write_to(&mut bytes).unwrap();
+
+			assert_eq!(bytes.len(), 32);
+			assert_eq!(bytes[..8], [1, 0, 0, 2, 0, 0, 0, 0]);
+			assert_eq!(bytes[8..12], encoded);
+
+			assert_eq!(GetAtom::read_from(&mut &bytes[1..]).unwrap(), reply);
+		}
+	}
+
+	#[test]
+	fn test_get_atom_trailing_bytes() {
+		let reply = GetAtom {
+			sequence: 2,
+			atom: Some(Atom::new(0x0000_0145)),
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		// 4 bytes unknown to this version of the protocol follow the reply,
+		// which is followed by the first byte of the next message.
+		bytes[4..8].copy_from_slice(&1u32.to_be_bytes());
+		bytes.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef, 0x55]);
+
+		let buf = &mut &bytes[1..];
+		assert_eq!(GetAtom::read_from(buf).unwrap(), reply);
+		// The unknown bytes are skipped.
+		assert_eq!(buf, &[0x55]);
+
+		let verbatim =
+			<Verbatim<GetAtom>>::read_with_prefix(&[1], &mut &bytes[1..], ReadMode::Lenient)
+				.unwrap();
+		assert_eq!(*verbatim, reply);
+
+		// The whole reply is written, including the unknown bytes.
+		let mut written = vec![];
+		verbatim.write_to(&mut written).unwrap();
+		assert_eq!(written, bytes[..36]);
+		assert_eq!(verbatim.x11_size(), 36);
+	}
+
+	#[test]
+	fn test_get_atom_name_round_trip() {
+		let reply = GetAtomName {
+			sequence: 3,
+			name: String8::from(b"WM_NAME".map(Char8::new).to_vec()),
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32 + 8);
+		assert_eq!(reply.x11_size(), bytes.len());
+		// The length of the reply, after the first 32 bytes, in 4-byte units.
+		assert_eq!(bytes[4..8], 2u32.to_be_bytes());
+		// The length of the name.
+		assert_eq!(bytes[8..10], 7u16.to_be_bytes());
+		assert_eq!(bytes[32..], *b"WM_NAME\0");
+
+		assert_eq!(GetAtomName::read_from(&mut &bytes[1..]).unwrap(), reply);
+	}
+
+	#[test]
+	fn test_truncated_get_atom_name() {
+		let reply = GetAtomName {
+			sequence: 3,
+			name: String8::from(b"WM_NAME".map(Char8::new).to_vec()),
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		// The first byte, which identifies the message as a reply, is read
+		// before the reply itself.
+		let bytes = &bytes[1..];
+
+		assert_eq!(GetAtomName::read_from(&mut &bytes[..]).unwrap(), reply);
+
+		for len in 0..bytes.len() {
+			assert!(
+				matches!(
+					GetAtomName::read_from(&mut &bytes[..len]),
+					Err(ReadError::NotEnoughData { .. }),
+				),
+				"reading {len} of {} bytes should fail",
+				bytes.len(),
+			);
+		}
+	}
+
+	#[test]
+	fn test_get_property_ref_agrees_with_owned() {
+		let values = [
+			(
+				DataFormat::I8,
+				DataList::I8(vec![1, -2, 3, -4, 5, -6, 7, -8]),
+			),
+			(DataFormat::I16, DataList::I16(vec![-300, 400, 500, -600])),
+			(DataFormat::I32, DataList::I32(vec![-70_000, 80_000])),
+		];
+
+		for (format, value) in values {
+			let reply = GetProperty {
+				sequence: 5,
+				format: Some(format),
+				r#type: Some(Atom::new(31)),
+				bytes_remaining: 0,
+				value,
+			};
+
+			let mut bytes = vec![];
+			reply.write_to(&mut bytes).unwrap();
+
+			let bytes = &bytes[1..];
+
+			let owned = GetProperty::read_from(&mut &bytes[..]).unwrap();
+			let borrowed = GetPropertyRef::read_ref(&mut &bytes[..]).unwrap();
+
+			// The value is borrowed directly from the bytes of the reply.
+			assert!(bytes.as_ptr_range().contains(&borrowed.value.as_ptr()));
+			assert_eq!(borrowed.value, &bytes[31..]);
+
+			match &owned.value {
+				DataList::I8(values) => {
+					assert!(borrowed.i8_values().unwrap().eq(values.iter().copied()));
+					assert!(borrowed.i16_values().is_none());
+				},
+				DataList::I16(values) => {
+					assert!(borrowed.i16_values().unwrap().eq(values.iter().copied()));
+					assert!(borrowed.i32_values().is_none());
+				},
+				DataList::I32(values) => {
+					assert!(borrowed.i32_values().unwrap().eq(values.iter().copied()));
+					assert!(borrowed.i8_values().is_none());
+				},
+			}
+
+			assert_eq!(borrowed.into_owned(), owned);
+		}
+	}
+
+	#[test]
+	fn test_get_property_non_zero_padding() {
+		let reply = GetProperty {
+			sequence: 5,
+			format: Some(DataFormat::I8),
+			r#type: Some(Atom::new(31)),
+			bytes_remaining: 0,
+			value: DataList::I8(vec![1, 2, 3]),
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		// The 3-byte value is followed by 1 byte of padding.
+		assert_eq!(bytes.len(), 36);
+
+		let mut bytes = bytes.split_off(1);
+		// One of the 12 unused bytes following the length of the value.
+		bytes[25] = 0xa5;
+
+		for mode in [ReadMode::Lenient, ReadMode::Strict] {
+			let owned = GetProperty::read_from_with_mode(&mut &bytes[..], mode);
+			let borrowed = GetPropertyRef::read_ref_with_mode(&mut &bytes[..], mode);
+
+			match mode {
+				ReadMode::Lenient => {
+					assert_eq!(owned.unwrap(), reply);
+					assert_eq!(borrowed.unwrap().into_owned(), reply);
+				},
+
+				ReadMode::Strict => {
+					assert!(matches!(owned, Err(ReadError::NonZeroPadding(0xa5))));
+					assert!(matches!(borrowed, Err(ReadError::NonZeroPadding(0xa5))));
+				},
+			}
+		}
+
+		// Reading the reply as `Verbatim` reproduces the unused byte when it
+		// is written again.
+		let verbatim =
+			<Verbatim<GetProperty>>::read_with_prefix(&[1], &mut &bytes[..], ReadMode::Lenient)
+				.unwrap();
+		assert_eq!(*verbatim, reply);
+
+		// The whole reply is written, including its first byte.
+		let mut message = vec![1];
+		message.extend_from_slice(&bytes);
+
+		let mut written = vec![];
+		verbatim.write_to(&mut written).unwrap();
+		assert_eq!(written, message);
+		assert_eq!(verbatim.x11_size(), message.len());
+	}
+}
@@ -18,7 +18,19 @@ extern crate self as xrb;
 
 use derivative::Derivative;
 
-use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
+use xrbk::{
+	read_padding,
+	Buf,
+	BufMut,
+	ConstantX11Size,
+	ReadMode,
+	ReadResult,
+	Readable,
+	Writable,
+	WriteResult,
+	X11Size,
+};
+use xrbk_macro::{derive_xrb, ConstantX11Size, Readable, Writable, X11Size};
 
 use crate::{
 	message::Reply,
@@ -39,7 +51,8 @@ use crate::{
 /// The state of the [window] regarding how it is mapped.
 ///
 /// [window]: Window
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MapState {
 	/// The [window] is not mapped.
 	///
@@ -57,191 +70,323 @@ pub enum MapState {
 	Viewable,
 }
 
-derive_xrb! {
-	/// The [reply] to a [`GetWindowAttributes` request].
+/// The [reply] to a [`GetWindowAttributes` request].
+///
+/// [reply]: Reply
+///
+/// [`GetWindowAttributes` request]: request::GetWindowAttributes
+#[derive(Derivative, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derivative(Hash, PartialEq, Eq)]
+pub struct GetWindowAttributes {
+	/// The sequence number identifying the [request] that generated this
+	/// [reply].
 	///
+	/// See [`Reply::sequence`] for more information.
+	///
+	/// [request]: crate::message::Request
 	/// [reply]: Reply
 	///
-	/// [`GetWindowAttributes` request]: request::GetWindowAttributes
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
-	#[derivative(Hash, PartialEq, Eq)]
-	pub struct GetWindowAttributes: Reply for request::GetWindowAttributes {
-		/// The sequence number identifying the [request] that generated this
-		/// [reply].
-		///
-		/// See [`Reply::sequence`] for more information.
-		///
-		/// [request]: crate::message::Request
-		/// [reply]: Reply
-		///
-		/// [`Reply::sequence`]: Reply::sequence
-		#[sequence]
-		#[derivative(Hash = "ignore", PartialEq = "ignore")]
-		pub sequence: u16,
+	/// [`Reply::sequence`]: Reply::sequence
+	#[derivative(Hash = "ignore", PartialEq = "ignore")]
+	pub sequence: u16,
 
-		/// The conditions under which the X server should maintain the obscured
-		/// [regions] of the [window].
-		///
-		/// See [`Attributes::maintain_contents`] for more information.
-		///
-		/// [regions]: crate::Region
-		/// [window]: Window
-		///
-		/// [`Attributes::maintain_contents`]: crate::set::Attributes::maintain_contents
-		#[doc(alias = "backing_store")]
-		#[metabyte]
-		pub maintain_contents: MaintainContents,
+	/// The conditions under which the X server should maintain the obscured
+	/// [regions] of the [window].
+	///
+	/// See [`Attributes::maintain_contents`] for more information.
+	///
+	/// [regions]: crate::Region
+	/// [window]: Window
+	///
+	/// [`Attributes::maintain_contents`]: crate::set::Attributes::maintain_contents
+	#[doc(alias = "backing_store")]
+	pub maintain_contents: MaintainContents,
 
-		/// The visual used by the [window].
-		///
-		/// See [`VisualType`] for more information.
-		///
-		/// [window]: Window
-		///
-		/// [`VisualType`]: crate::visual::VisualType
-		pub visual: VisualId,
-		/// The [window]'s [class].
-		///
-		/// [window]: Window
-		/// [class]: WindowClass
-		pub class: WindowClass,
+	/// The visual used by the [window].
+	///
+	/// See [`VisualType`] for more information.
+	///
+	/// [window]: Window
+	///
+	/// [`VisualType`]: crate::visual::VisualType
+	pub visual: VisualId,
+	/// The [window]'s [class].
+	///
+	/// [window]: Window
+	/// [class]: WindowClass
+	pub class: WindowClass,
 
-		/// Defines the [region] of the [window] which is retained when the
-		/// [window] is resized.
-		///
-		/// See [`Attributes::bit_gravity`] for more information.
-		///
-		/// [region]: crate::Region
-		/// [window]: Window
-		///
-		/// [`Attributes::bit_gravity`]: crate::set::Attributes::bit_gravity
-		pub bit_gravity: BitGravity,
-		/// Defines how the [window] is repositioned if its parent is resized.
-		///
-		/// See [`Attributes::window_gravity`] for more information.
-		///
-		/// [window]: Window
-		///
-		/// [`Attributes::window_gravity`]: crate::set::Attributes::window_gravity
-		#[doc(alias = "win_gravity")]
-		pub window_graivty: WindowGravity,
+	/// Defines the [region] of the [window] which is retained when the
+	/// [window] is resized.
+	///
+	/// See [`Attributes::bit_gravity`] for more information.
+	///
+	/// [region]: crate::Region
+	/// [window]: Window
+	///
+	/// [`Attributes::bit_gravity`]: crate::set::Attributes::bit_gravity
+	pub bit_gravity: BitGravity,
+	/// Defines how the [window] is repositioned if its parent is resized.
+	///
+	/// See [`Attributes::window_gravity`] for more information.
+	///
+	/// [window]: Window
+	///
+	/// [`Attributes::window_gravity`]: crate::set::Attributes::window_gravity
+	#[doc(alias = "win_gravity")]
+	pub window_gravity: WindowGravity,
 
-		/// Defines which bit planes of the [window] hold dynamic data which is
-		/// maintained for `maintain_contents` and `maintain_windows_under`.
-		///
-		/// See [`Attributes::maintained_planes`] for more information.
-		///
-		/// [window]: Window
-		///
-		/// [`Attributes::maintained_planes`]: crate::set::Attributes::maintained_planes
-		#[doc(alias = "backing_planes")]
-		pub maintained_planes: u32,
-		/// Defines the [color] used for bit planes which are not preserved for
-		/// `maintain_contents` and `maintain_windows_under` (see
-		/// `maintained_planes`).
-		///
-		/// See [`Attributes::maintenance_fallback_color`] for more information.
-		///
-		/// [color]: ColorId
-		///
-		/// [`Attributes::maintenance_fallback_color`]: crate::set::Attributes::maintenance_fallback_color
-		#[doc(alias = "backing_pixel")]
-		pub maintenance_fallback_color: ColorId,
-		/// Whether the X server should maintain the contents of
-		/// [windows][window] under this [window].
-		///
-		/// See [`Attributes::maintain_windows_under`] for more information.
-		///
-		/// [window]: Window
-		///
-		/// [`Attributes::maintain_windows_under`]: crate::set::Attributes::maintain_windows_under
-		#[doc(alias = "save_under")]
-		pub maintain_windows_under: bool,
+	/// Defines which bit planes of the [window] hold dynamic data which is
+	/// maintained for `maintain_contents` and `maintain_windows_under`.
+	///
+	/// See [`Attributes::maintained_planes`] for more information.
+	///
+	/// [window]: Window
+	///
+	/// [`Attributes::maintained_planes`]: crate::set::Attributes::maintained_planes
+	#[doc(alias = "backing_planes")]
+	pub maintained_planes: u32,
+	/// Defines the [color] used for bit planes which are not preserved for
+	/// `maintain_contents` and `maintain_windows_under` (see
+	/// `maintained_planes`).
+	///
+	/// See [`Attributes::maintenance_fallback_color`] for more information.
+	///
+	/// [color]: ColorId
+	///
+	/// [`Attributes::maintenance_fallback_color`]: crate::set::Attributes::maintenance_fallback_color
+	#[doc(alias = "backing_pixel")]
+	pub maintenance_fallback_color: ColorId,
+	/// Whether the X server should maintain the contents of
+	/// [windows][window] under this [window].
+	///
+	/// See [`Attributes::maintain_windows_under`] for more information.
+	///
+	/// [window]: Window
+	///
+	/// [`Attributes::maintain_windows_under`]: crate::set::Attributes::maintain_windows_under
+	#[doc(alias = "save_under")]
+	pub maintain_windows_under: bool,
 
-		/// Whether the [window]'s `colormap` is an installed [colormap] for
-		/// the [screen].
-		///
-		/// [window]: Window
-		/// [colormap]: Colormap
-		/// [screen]: crate::visual::Screen
-		pub map_installed: bool,
-		/// The [window]'s [map state].
-		///
-		/// See [`MapState`] for more information.
-		///
-		/// [window]: Window
-		/// [map state]: MapState
-		pub map_state: MapState,
+	/// Whether the [window]'s `colormap` is an installed [colormap] for
+	/// the [screen].
+	///
+	/// [window]: Window
+	/// [colormap]: Colormap
+	/// [screen]: crate::visual::Screen
+	pub map_installed: bool,
+	/// The [window]'s [map state].
+	///
+	/// See [`MapState`] for more information.
+	///
+	/// [window]: Window
+	/// [map state]: MapState
+	pub map_state: MapState,
 
-		/// Whether [`MapWindow`] and [`ConfigureWindow`] requests on the
-		/// [window] override a [`SUBSTRUCTURE_REDIRECT`] selection on its
-		/// parent.
-		///
-		/// This is typically used to inform a window manager not to tamper with
-		/// the [window].
-		///
-		/// See [`Attributes::override_redirect`] for more information.
-		///
-		/// [window]: Window
-		///
-		/// [`MapWindow`]: request::MapWindow
-		/// [`ConfigureWindow`]: request::ConfigureWindow
-		///
-		/// [`SUBSTRUCTURE_REDIRECT`]: EventMask::SUBSTRUCTURE_REDIRECT
-		///
-		/// [`Attributes::override_redirect`]: crate::set::Attributes::override_redirect
-		pub override_redirect: bool,
+	/// Whether [`MapWindow`] and [`ConfigureWindow`] requests on the
+	/// [window] override a [`SUBSTRUCTURE_REDIRECT`] selection on its
+	/// parent.
+	///
+	/// This is typically used to inform a window manager not to tamper with
+	/// the [window].
+	///
+	/// See [`Attributes::override_redirect`] for more information.
+	///
+	/// [window]: Window
+	///
+	/// [`MapWindow`]: request::MapWindow
+	/// [`ConfigureWindow`]: request::ConfigureWindow
+	///
+	/// [`SUBSTRUCTURE_REDIRECT`]: EventMask::SUBSTRUCTURE_REDIRECT
+	///
+	/// [`Attributes::override_redirect`]: crate::set::Attributes::override_redirect
+	pub override_redirect: bool,
 
-		/// The [colormap] which best reflects the true colors of the [window].
-		///
-		/// See [`Attributes::colormap`] for more information.
-		///
-		/// [window]: Window
-		/// [colormap]: Colormap
-		///
-		/// [`Attributes::colormap`]: crate::set::Attributes::colormap
-		pub colormap: Option<Colormap>,
+	/// The [colormap] which best reflects the true colors of the [window].
+	///
+	/// See [`Attributes::colormap`] for more information.
+	///
+	/// [window]: Window
+	/// [colormap]: Colormap
+	///
+	/// [`Attributes::colormap`]: crate::set::Attributes::colormap
+	pub colormap: Option<Colormap>,
 
-		/// All of the [events] selected by all clients on the [window].
-		///
-		/// This is the bitwise OR of every client's [`event_mask`] on the
-		/// [window].
-		///
-		/// [window]: Window
-		/// [events]: crate::message::Event
-		///
-		/// [`event_mask`]: crate::set::Attributes::event_mask
-		pub all_event_masks: EventMask,
-		/// The [events] selected by you on the [window].
-		///
-		/// This is your [`event_mask`] on the [window].
-		///
-		/// [window]: Window
-		/// [events]: crate::message::Event
-		///
-		/// [`event_mask`]: crate::set::Attributes::event_mask
-		pub your_event_mask: EventMask,
-		/// Defines the [events][event] which should not be propagated to
-		/// ancestors of the [window] if no client has selected the [event] on
-		/// the [window].
-		///
-		/// See [`Attributes::do_not_propagate_mask`] for more information.
-		///
-		/// [event]: crate::message::Event
-		/// [window]: Window
-		///
-		/// [`Attributes::do_not_propagate_mask`]: crate::set::Attributes::do_not_propagate_mask
-		pub do_not_propagate_mask: DeviceEventMask,
-		[_; ..],
+	/// All of the [events] selected by all clients on the [window].
+	///
+	/// This is the bitwise OR of every client's [`event_mask`] on the
+	/// [window].
+	///
+	/// [window]: Window
+	/// [events]: crate::message::Event
+	///
+	/// [`event_mask`]: crate::set::Attributes::event_mask
+	pub all_event_masks: EventMask,
+	/// The [events] selected by you on the [window].
+	///
+	/// This is your [`event_mask`] on the [window].
+	///
+	/// [window]: Window
+	/// [events]: crate::message::Event
+	///
+	/// [`event_mask`]: crate::set::Attributes::event_mask
+	pub your_event_mask: EventMask,
+	/// Defines the [events][event] which should not be propagated to
+	/// ancestors of the [window] if no client has selected the [event] on
+	/// the [window].
+	///
+	/// See [`Attributes::do_not_propagate_mask`] for more information.
+	///
+	/// [event]: crate::message::Event
+	/// [window]: Window
+	///
+	/// [`Attributes::do_not_propagate_mask`]: crate::set::Attributes::do_not_propagate_mask
+	///
+	/// This is encoded in only two bytes in this reply.
+	pub do_not_propagate_mask: DeviceEventMask,
+}
+
+impl Reply for GetWindowAttributes {
+	type Request = request::GetWindowAttributes;
+
+	fn sequence(&self) -> u16 {
+		self.sequence
+	}
+}
+
+impl ConstantX11Size for GetWindowAttributes {
+	const X11_SIZE: usize = 44;
+}
+
+impl X11Size for GetWindowAttributes {
+	fn x11_size(&self) -> usize {
+		Self::X11_SIZE
+	}
+}
+
+impl Readable for GetWindowAttributes {
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
+		Self::read_from_with_mode(buf, ReadMode::Lenient)
+	}
+
+	fn read_from_with_mode(buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Self> {
+		// Header {{{
+
+		let maintain_contents = MaintainContents::read_from(buf)?;
+		let sequence = u16::read_from(buf)?;
+
+		// The length does not include the first 32 bytes, of which 8 have
+		// already been read.
+		let length = (u32::read_from(buf)? as usize).saturating_mul(4);
+		let buf = &mut buf.take(length.saturating_add(24));
+
+		// }}}
+
+		let visual = VisualId::read_from(buf)?;
+		let class = WindowClass::read_from(buf)?;
+
+		let bit_gravity = BitGravity::read_from(buf)?;
+		let window_gravity = WindowGravity::read_from(buf)?;
+
+		let maintained_planes = u32::read_from(buf)?;
+		let maintenance_fallback_color = ColorId::read_from(buf)?;
+		let maintain_windows_under = bool::read_from(buf)?;
+
+		let map_installed = bool::read_from(buf)?;
+		let map_state = MapState::read_from(buf)?;
+
+		let override_redirect = bool::read_from(buf)?;
+
+		let colormap = Option::<Colormap>::read_from(buf)?;
+
+		let all_event_masks = EventMask::read_from(buf)?;
+		let your_event_mask = EventMask::read_from(buf)?;
+		// `do_not_propagate_mask` is only two bytes in this reply.
+		let do_not_propagate_mask =
+			DeviceEventMask::from_bits_truncate(u32::from(u16::read_from(buf)?));
+		read_padding(buf, 2, mode)?;
+
+		Ok(Self {
+			sequence,
+
+			maintain_contents,
+
+			visual,
+			class,
+
+			bit_gravity,
+			window_gravity,
+
+			maintained_planes,
+			maintenance_fallback_color,
+			maintain_windows_under,
+
+			map_installed,
+			map_state,
+
+			override_redirect,
+
+			colormap,
+
+			all_event_masks,
+			your_event_mask,
+			do_not_propagate_mask,
+		})
 	}
+}
+
+impl Writable for GetWindowAttributes {
+	#[allow(clippy::cast_possible_truncation)]
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		let buf = &mut buf.limit(self.x11_size());
+
+		// Header {{{
+
+		// Indicates that this is a reply.
+		buf.put_u8(1);
+		self.maintain_contents.write_to(buf)?;
+		self.sequence.write_to(buf)?;
+		buf.put_u32(self.length());
+
+		// }}}
+
+		self.visual.write_to(buf)?;
+		self.class.write_to(buf)?;
 
+		self.bit_gravity.write_to(buf)?;
+		self.window_gravity.write_to(buf)?;
+
+		self.maintained_planes.write_to(buf)?;
+		self.maintenance_fallback_color.write_to(buf)?;
+		self.maintain_windows_under.write_to(buf)?;
+
+		self.map_installed.write_to(buf)?;
+		self.map_state.write_to(buf)?;
+
+		self.override_redirect.write_to(buf)?;
+
+		self.colormap.write_to(buf)?;
+
+		self.all_event_masks.write_to(buf)?;
+		self.your_event_mask.write_to(buf)?;
+		// Every `DeviceEventMask` flag fits within the lower two bytes.
+		buf.put_u16(self.do_not_propagate_mask.bits() as u16);
+		buf.put_bytes(0, 2);
+
+		Ok(())
+	}
+}
+
+derive_xrb! {
 	/// The [reply] to a [`GetGeometry` request].
 	///
 	/// [reply]: Reply
 	///
 	/// [`GetGeometry` request]: request::GetGeometry
 	#[doc(alias("GetX", "GetY", "GetWidth", "GetHeight", "GetBorderWidth"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetGeometry: Reply for request::GetGeometry {
 		/// The sequence number identifying the [request] that generated this
@@ -300,6 +445,7 @@ derive_xrb! {
 	#[doc(alias("QueryParent", "QueryChildren", "QueryRoot"))]
 	#[doc(alias("GetParent", "GetChildren", "GetRoot"))]
 	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct QueryWindowTree: Reply for request::QueryWindowTree {
 		/// The sequence number identifying the [request] that generated this
@@ -336,3 +482,182 @@ derive_xrb! {
 		pub children: Vec<Window>,
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_query_window_tree_without_children() {
+		let reply = QueryWindowTree {
+			sequence: 6,
+			root: Window::new(0x0000_0512),
+			parent: None,
+			children: vec![],
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(
+			bytes,
+			[
+				1, 0, 0, 6, // header
+				0, 0, 0, 0, // length
+				0x00, 0x00, 0x05, 0x12, // root
+				0x00, 0x00, 0x00, 0x00, // parent
+				0, 0, 0, 0, // children length
+				0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+			],
+		);
+		assert_eq!(QueryWindowTree::read_from(&mut &bytes[1..]).unwrap(), reply);
+	}
+
+	#[test]
+	fn test_query_window_tree_with_odd_number_of_children() {
+		let reply = QueryWindowTree {
+			sequence: 6,
+			root: Window::new(0x0000_0512),
+			parent: Some(Window::new(0x0000_0512)),
+			children: vec![
+				Window::new(0x0060_0001),
+				Window::new(0x0060_0002),
+				Window::new(0x0080_0001),
+			],
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32 + 3 * 4);
+		// The length of the reply, after the first 32 bytes, in 4-byte units.
+		assert_eq!(bytes[4..8], 3u32.to_be_bytes());
+		// The number of children.
+		assert_eq!(bytes[16..18], 3u16.to_be_bytes());
+		assert_eq!(
+			bytes[32..],
+			[
+				0x00, 0x60, 0x00, 0x01, // first child
+				0x00, 0x60, 0x00, 0x02, // second child
+				0x00, 0x80, 0x00, 0x01, // third child
+			],
+		);
+
+		let read = QueryWindowTree::read_from(&mut &bytes[1..]).unwrap();
+
+		assert_eq!(read.sequence, 6);
+		assert_eq!(read, reply);
+	}
+
+	#[test]
+	fn test_get_window_attributes_fixture() {
+		#[rustfmt::skip]
+		let bytes = [
+			1, 2, 0x00, 0x09, // header, maintain contents
+			0, 0, 0, 3, // length
+			0x00, 0x00, 0x00, 0x21, // visual
+			0, 1, // class
+			10, 7, // bit gravity, window gravity
+			0xff, 0xff, 0xff, 0xff, // maintained planes
+			0x00, 0x00, 0x00, 0x00, // maintenance fallback color
+			1, 0, 2, 0, // save under, map installed, map state, override redirect
+			0x00, 0x20, 0x00, 0x01, // colormap
+			0x00, 0x52, 0x80, 0x05, // all event masks
+			0x00, 0x42, 0x00, 0x05, // your event mask
+			0x00, 0x0c, // do not propagate mask
+			0, 0,
+		];
+
+		let reply = GetWindowAttributes::read_from(&mut &bytes[1..]).unwrap();
+
+		assert_eq!(reply.sequence, 9);
+		assert_eq!(reply.maintain_contents, MaintainContents::Always);
+		assert_eq!(reply.visual, VisualId::new(0x21));
+		assert_eq!(reply.class, WindowClass::InputOutput);
+		assert_eq!(reply.bit_gravity, BitGravity::Static);
+		assert_eq!(reply.window_gravity, WindowGravity::SouthWest);
+		assert_eq!(reply.maintained_planes, 0xffff_ffff);
+		assert_eq!(reply.maintenance_fallback_color, ColorId::ZERO);
+		assert!(reply.maintain_windows_under);
+		assert!(!reply.map_installed);
+		assert_eq!(reply.map_state, MapState::Viewable);
+		assert!(!reply.override_redirect);
+		assert_eq!(reply.colormap, Some(Colormap::new(0x0020_0001)));
+		assert_eq!(
+			reply.all_event_masks,
+			EventMask::KEY_PRESS
+				| EventMask::BUTTON_PRESS
+				| EventMask::EXPOSURE
+				| EventMask::STRUCTURE_NOTIFY
+				| EventMask::SUBSTRUCTURE_REDIRECT
+				| EventMask::PROPERTY_CHANGE,
+		);
+		assert_eq!(
+			reply.your_event_mask,
+			EventMask::KEY_PRESS
+				| EventMask::BUTTON_PRESS
+				| EventMask::STRUCTURE_NOTIFY
+				| EventMask::PROPERTY_CHANGE,
+		);
+		assert_eq!(
+			reply.do_not_propagate_mask,
+			DeviceEventMask::BUTTON_PRESS | DeviceEventMask::BUTTON_RELEASE,
+		);
+
+		let mut written = vec![];
+		reply.write_to(&mut written).unwrap();
+
+		assert_eq!(GetWindowAttributes::X11_SIZE, 44);
+		assert_eq!(reply.length(), 3);
+		assert_eq!(written, bytes);
+	}
+
+	#[test]
+	fn test_get_window_attributes_without_colormap() {
+		let mut bytes = vec![1, 0, 0, 1, 0, 0, 0, 3];
+		bytes.resize(44, 0);
+		// `class` of `InputOnly`.
+		bytes[13] = 2;
+
+		let reply = GetWindowAttributes::read_from(&mut &bytes[1..]).unwrap();
+
+		assert_eq!(reply.class, WindowClass::InputOnly);
+		assert_eq!(reply.colormap, None);
+		assert_eq!(reply.do_not_propagate_mask, DeviceEventMask::empty());
+	}
+
+	#[test]
+	fn test_get_geometry_fixture() {
+		#[rustfmt::skip]
+		let bytes = [
+			1, 24, 0x00, 0x04, // header, depth
+			0, 0, 0, 0, // length
+			0x00, 0x00, 0x05, 0x12, // root
+			0xff, 0xf6, 0x00, 0x14, // x, y
+			0x02, 0x80, 0x01, 0xe0, // width, height
+			0x00, 0x02, // border width
+			0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+		];
+
+		let reply = GetGeometry::read_from(&mut &bytes[1..]).unwrap();
+
+		assert_eq!(reply.depth, 24);
+		assert_eq!(reply.root, Window::new(0x0000_0512));
+		assert_eq!(
+			reply.geometry,
+			Rectangle {
+				x: Px(-10),
+				y: Px(20),
+				width: Px(640),
+				height: Px(480),
+			},
+		);
+		assert_eq!(reply.border_width, Px(2));
+
+		let mut written = vec![];
+		reply.write_to(&mut written).unwrap();
+
+		assert_eq!(GetGeometry::X11_SIZE, 32);
+		assert_eq!(written, bytes);
+	}
+}
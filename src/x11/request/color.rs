@@ -52,6 +52,18 @@ macro_rules! request_error {
 				$Error(error::$Error)
 			),+)?
 		}
+
+		impl TryFrom<crate::x11::AnyError> for $Name {
+			type Error = crate::x11::AnyError;
+
+			fn try_from(error: crate::x11::AnyError) -> Result<Self, Self::Error> {
+				match error {
+					$($(crate::x11::AnyError::$Error(error) => Ok(Self::$Error(error)),)+)?
+
+					other => Err(other),
+				}
+			}
+		}
 	};
 }
 
@@ -75,6 +87,7 @@ request_error! {
 ///
 /// [colormap]: Colormap
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InitialColormapAllocation {
 	/// The [colormap] initially has no entries, or those initial entries are
 	/// defined elsewhere.
@@ -102,7 +115,7 @@ pub enum InitialColormapAllocation {
 
 	/// The entire [colormap] is allocated as writable.
 	///
-	/// None of these entries can be removed with [`DeleteColormapEntry`].
+	/// None of these entries can be removed with [`DestroyColormapEntries`].
 	///
 	/// [colormap]: Colormap
 	All,
@@ -139,7 +152,8 @@ derive_xrb! {
 	/// [`ResourceIdChoice` error]: error::ResourceIdChoice
 	/// [`Window` error]: error::Window
 	/// [`Match` error]: error::Match
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct CreateColormap: Request(78, CreateColormapError) {
 		/// Whether this [colormap] begins with [no entries allocated] or
 		/// [all entries allocated].
@@ -229,7 +243,8 @@ derive_xrb! {
 	///
 	/// [`Colormap` error]: error::Colormap
 	#[doc(alias("FreeColormap"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct DestroyColormap: Request(79, error::Colormap) {
 		/// The [colormap] which is to be deleted.
 		///
@@ -271,6 +286,7 @@ derive_xrb! {
 	/// [`Colormap` error]: error::Colormap
 	#[doc(alias("CopyColormapAndFree"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct MoveColormap: Request(80, MoveColormapError) {
 		/// The [`Colormap` ID] that will be associated with the new [colormap].
 		///
@@ -345,7 +361,8 @@ derive_xrb! {
 	/// [`Colormap` event]: crate::x11::event::Colormap
 	///
 	/// [`Colormap` error]: error::Colormap
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct InstallColormap: Request(81, error::Colormap) {
 		/// The [colormap] that is to be installed.
 		///
@@ -402,7 +419,8 @@ derive_xrb! {
 	/// [`Colormap` event]: crate::x11::event::Colormap
 	///
 	/// [`Colormap` error]: error::Colormap
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct UninstallColormap: Request(82, error::Colormap) {
 		/// The [colormap] that is to be uninstalled.
 		///
@@ -434,7 +452,8 @@ derive_xrb! {
 	/// [`ListInstalledColormaps` reply]: reply::ListInstalledColormaps
 	///
 	/// [`Window` error]: error::Window
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ListInstalledColormaps: Request(83, error::Window) -> reply::ListInstalledColormaps {
 		/// The [window] for which this [request] returns its installed
 		/// [colormaps].
@@ -478,7 +497,8 @@ derive_xrb! {
 	///
 	/// [`Colormap` error]: error::Colormap
 	#[doc(alias("AllocColor"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct AllocateColor: Request(84, error::Colormap) -> reply::AllocateColor {
 		/// The [colormap] for which the [colormap] entry is allocated.
 		///
@@ -526,6 +546,7 @@ derive_xrb! {
 	/// [`Name` error]: error::Name
 	#[doc(alias("AllocNamedColor"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct AllocateNamedColor: Request(
 		85,
 		AllocateNamedColorError,
@@ -601,7 +622,8 @@ derive_xrb! {
 	/// [`Colormap` error]: error::Colormap
 	/// [`Value` error]: error::Value
 	#[doc(alias("AllocColorCells"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct AllocateColorCells: Request(
 		86,
 		AllocateColorCellsError,
@@ -699,7 +721,8 @@ derive_xrb! {
 	///
 	/// [`RequestError::Alloc`]: crate::message::RequestError::Alloc
 	#[doc(alias("AllocColorPlanes"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct AllocateColorPlanes: Request(
 		87,
 		AllocateColorPlanesError,
@@ -795,6 +818,7 @@ derive_xrb! {
 	// TODO: rename all Destroy* requests to Delete*
 	#[doc(alias("FreeColors"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct DestroyColormapEntries: Request(88, DestroyColormapEntriesError) {
 		/// The [colormap] for which the [colormap] entries are deleted.
 		///
@@ -838,7 +862,9 @@ derive_xrb! {
 	/// [colormap]: Colormap
 	///
 	/// [`StoreColors` request]: StoreColors
+	#[doc(alias("ColorItem"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ColormapEntryChange {
 		/// The [`ColorId`] of the changed [colormap] entry.
 		///
@@ -887,6 +913,7 @@ derive_xrb! {
 	/// [`Colormap` error]: error::Colormap
 	/// [`Value` error]: error::Value
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct StoreColors: Request(89, StoreColorsError) {
 		/// The [colormap] for which the [colormap] entries are changed.
 		///
@@ -945,7 +972,7 @@ derive_xrb! {
 	/// read-only or it is not allocated.
 	///
 	/// A [`Value` error] is generated if the requested [`ColorId`] is not a
-	/// valid into into the `target` [colormap].
+	/// valid index into the `target` [colormap].
 	///
 	/// [RGB values]: RgbColor
 	/// [colormap]: Colormap
@@ -957,11 +984,13 @@ derive_xrb! {
 	/// [`Value` error]: error::Value
 	/// [`Name` error]: error::Name
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct StoreNamedColor: Request(90, StoreNamedColorError) {
 		/// The mask for which of the [colormap] entry's color channels are
 		/// changed.
 		///
 		/// [colormap]: Colormap
+		#[metabyte]
 		pub mask: ColorChannelMask,
 
 		/// The [colormap] for which the [colormap] entry is changed.
@@ -1032,6 +1061,7 @@ derive_xrb! {
 	/// [`Colormap` error]: error::Colormap
 	/// [`Value` error]: error::Value
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct QueryColors: Request(91, QueryColorsError) -> reply::QueryColors {
 		/// The [colormap] on which the [RGB values] of the given [colormap]
 		/// entries are queried.
@@ -1096,6 +1126,7 @@ derive_xrb! {
 	/// [`Name` error]: error::Name
 	#[doc(alias("LookupColor"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GetNamedColor: Request(92, GetNamedColorError) -> reply::GetNamedColor {
 		/// The [colormap] whose [screen] defines the requested color.
 		///
@@ -1113,7 +1144,7 @@ derive_xrb! {
 		// The length of `name`.
 		#[allow(clippy::cast_possible_truncation)]
 		let name_len: u16 = name => name.len() as u16,
-		[_; 22],
+		[_; 2],
 
 		/// The name of the color which this [request] gets the [RGB values] of.
 		///
@@ -1133,3 +1164,203 @@ derive_xrb! {
 		[_; name => pad(name)],
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::Char8;
+	use xrbk::{Readable, Writable, X11Size};
+
+	fn string8(name: &str) -> String8 {
+		String8::from(name.bytes().map(Char8::new).collect::<Vec<_>>())
+	}
+
+	#[test]
+	fn test_create_colormap_allocation_is_metabyte() {
+		let request = CreateColormap {
+			initial_allocation: InitialColormapAllocation::All,
+			colormap_id: Colormap::new(0x0020_0001),
+			window: Window::new(0x0000_01e0),
+			visual: VisualId::new(0x21),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(CreateColormap::X11_SIZE, 16);
+		assert_eq!(bytes[..4], [78, 1, 0, 4]);
+		assert_eq!(bytes[4..8], [0x00, 0x20, 0x00, 0x01]);
+		assert_eq!(bytes[12..], [0, 0, 0, 0x21]);
+		assert_eq!(
+			CreateColormap::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_allocate_color() {
+		let request = AllocateColor {
+			target: Colormap::new(0x20),
+			color: RgbColor(0xffff, 0x8000, 0x0000),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(
+			bytes,
+			[84, 0, 0, 4, 0, 0, 0, 0x20, 0xff, 0xff, 0x80, 0x00, 0x00, 0x00, 0, 0]
+		);
+		assert_eq!(AllocateColor::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_get_named_color_pads_name() {
+		let request = GetNamedColor {
+			target: Colormap::new(0x20),
+			name: string8("cornflowerblue"),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		// 12-byte header and 14 bytes of name padded to 16.
+		assert_eq!(bytes.len(), 28);
+		assert_eq!(request.x11_size(), bytes.len());
+		assert_eq!(bytes[..4], [92, 0, 0, 7]);
+		assert_eq!(bytes[8..12], [0, 14, 0, 0]);
+		assert_eq!(&bytes[12..26], b"cornflowerblue");
+		assert_eq!(GetNamedColor::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_query_colors_list_from_length() {
+		let request = QueryColors {
+			target: Colormap::new(0x20),
+			colors: vec![ColorId::new(0), ColorId::new(1), ColorId::new(0x00ff_ffff)],
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes[..4], [91, 0, 0, 5]);
+
+		// Trailing bytes beyond the request's length must not be read as
+		// colors.
+		let mut buf = bytes.clone();
+		buf.extend_from_slice(&[0xff; 8]);
+
+		assert_eq!(QueryColors::read_from(&mut &buf[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_destroy_colormap_entries_list_from_length() {
+		let request = DestroyColormapEntries {
+			target: Colormap::new(0x20),
+			plane_mask: 0x0000_00f0,
+			colors: vec![ColorId::new(1), ColorId::new(2)],
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes[..4], [88, 0, 0, 5]);
+		assert_eq!(bytes[8..12], [0, 0, 0, 0xf0]);
+		assert_eq!(request.x11_size(), bytes.len());
+
+		let mut buf = bytes.clone();
+		buf.extend_from_slice(&[0xff; 8]);
+
+		assert_eq!(
+			DestroyColormapEntries::read_from(&mut &buf[1..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_store_colors() {
+		let request = StoreColors {
+			target: Colormap::new(0x20),
+			changes: vec![
+				ColormapEntryChange {
+					id: ColorId::new(5),
+					color: RgbColor(0xffff, 0x0000, 0x8000),
+					mask: ColorChannelMask::RED | ColorChannelMask::BLUE,
+				},
+				ColormapEntryChange {
+					id: ColorId::new(6),
+					color: RgbColor(0, 0, 0),
+					mask: ColorChannelMask::all(),
+				},
+			],
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		// 8-byte header and two 12-byte changes.
+		assert_eq!(bytes.len(), 32);
+		assert_eq!(bytes[..4], [89, 0, 0, 8]);
+		#[rustfmt::skip]
+		assert_eq!(bytes[8..20], [
+			0, 0, 0, 5,
+			0xff, 0xff, 0x00, 0x00, 0x80, 0x00,
+			0x05, 0,
+		]);
+		assert_eq!(StoreColors::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_store_named_color_mask_is_metabyte() {
+		let request = StoreNamedColor {
+			mask: ColorChannelMask::GREEN,
+			target: Colormap::new(0x20),
+			id: ColorId::new(7),
+			name: string8("red"),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		// 16-byte header and 3 bytes of name padded to 4.
+		assert_eq!(bytes.len(), 20);
+		assert_eq!(request.x11_size(), bytes.len());
+		assert_eq!(bytes[..4], [90, 0x02, 0, 5]);
+		assert_eq!(bytes[8..16], [0, 0, 0, 7, 0, 3, 0, 0]);
+		assert_eq!(&bytes[16..19], b"red");
+		assert_eq!(
+			StoreNamedColor::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+
+	#[cfg(feature = "trace")]
+	#[test]
+	fn test_get_named_color_trace_labels_padding() {
+		let request = GetNamedColor {
+			target: Colormap::new(0x20),
+			name: string8("red"),
+		};
+
+		let trace = xrbk::Trace::write(&request).unwrap();
+
+		let fields: Vec<_> = trace
+			.fields()
+			.iter()
+			.map(|field| (field.name(), field.range()))
+			.collect();
+		assert_eq!(
+			fields,
+			[
+				("major_opcode", 0..1),
+				("unused", 1..2),
+				("length", 2..4),
+				("target", 4..8),
+				("name_len", 8..10),
+				("unused", 10..12),
+				("name", 12..15),
+				("unused", 15..16),
+			],
+		);
+		assert!(trace.fields().last().unwrap().is_unused());
+	}
+}
@@ -53,6 +53,18 @@ macro_rules! request_error {
 				$Error(error::$Error)
 			),+)?
 		}
+
+		impl TryFrom<crate::x11::AnyError> for $Name {
+			type Error = crate::x11::AnyError;
+
+			fn try_from(error: crate::x11::AnyError) -> Result<Self, Self::Error> {
+				match error {
+					$($(crate::x11::AnyError::$Error(error) => Ok(Self::$Error(error)),)+)?
+
+					other => Err(other),
+				}
+			}
+		}
 	};
 }
 
@@ -70,6 +82,7 @@ derive_xrb! {
 	/// [request]: Request
 	#[doc(alias("OpenFont", "CreateFont", "LoadFont", "AddFont"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct AssignFont: Request(45, AssignFontError) {
 		/// The [`Font` ID] to associate with the font specified by `name`.
 		///
@@ -100,6 +113,7 @@ derive_xrb! {
 	/// [`Font` ID]: Font
 	#[doc(alias("CloseFont", "DeleteFont", "UnloadFont", "RemoveFont"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct UnassignFont: Request(46) {
 		/// The [`Font` ID] which is having its association with a font removed.
 		///
@@ -125,6 +139,7 @@ derive_xrb! {
 	///
 	/// [`Font` error]: error::Font
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct QueryFont: Request(47, error::Font) -> reply::QueryFont {
 		/// The font which this [request] returns information about.
 		///
@@ -174,6 +189,7 @@ derive_xrb! {
 	///
 	/// [`Font` error]: error::Font
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct QueryTextExtents: Request(48, error::Font) -> reply::QueryTextExtents {
 		// Whether `text` is of odd length. Is it is, it has 2 bytes of padding
 		// following it.
@@ -219,6 +235,7 @@ derive_xrb! {
 	///
 	/// [`ListFonts` reply]: reply::ListFonts
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ListFonts: Request(49) -> reply::ListFonts {
 		/// The maximum number of names that will appear in the returned font
 		/// `names`.
@@ -258,6 +275,7 @@ derive_xrb! {
 	/// [`ListFontsWithInfo` replies]: reply::ListFontsWithInfo
 	/// [`QueryFont` reply]: reply::QueryFont
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ListFontsWithInfo: Request(50) -> reply::ListFontsWithInfo {
 		/// The maximum number of [`FontWithInfo` replies] that will be returned.
 		///
@@ -294,6 +312,7 @@ derive_xrb! {
 	/// [`Value` error]: error::Value
 	#[doc(alias = "SetFontPath")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct SetFontSearchDirectories: Request(51, error::Value) {
 		// The length of `directories`.
 		#[allow(clippy::cast_possible_truncation)]
@@ -317,5 +336,45 @@ derive_xrb! {
 	///
 	/// [request]: Request
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GetFontSearchDirectories: Request(52) -> reply::GetFontSearchDirectories;
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::Char8;
+	use xrbk::{Readable, Writable, X11Size};
+
+	#[test]
+	fn test_assign_font_pads_name() {
+		let request = AssignFont {
+			font_id: Font::new(0x0040_0001),
+			name: String8::from(b"fixed".map(Char8::new).to_vec()),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		let mut expected = vec![45, 0, 0, 5, 0x00, 0x40, 0x00, 0x01, 0, 5, 0, 0];
+		expected.extend_from_slice(b"fixed\0\0\0");
+
+		assert_eq!(bytes, expected);
+		assert_eq!(request.x11_size(), bytes.len());
+		assert_eq!(AssignFont::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_unassign_font() {
+		let request = UnassignFont {
+			target: Font::new(0x0040_0001),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(UnassignFont::X11_SIZE, 8);
+		assert_eq!(bytes, [46, 0, 0, 2, 0x00, 0x40, 0x00, 0x01]);
+		assert_eq!(UnassignFont::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+}
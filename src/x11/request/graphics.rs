@@ -15,24 +15,29 @@ pub use config::*;
 
 extern crate self as xrb;
 
+use std::borrow::Cow;
 use thiserror::Error;
 
 use xrbk::{
 	pad,
+	read_padding,
 	Buf,
 	BufMut,
 	ConstantX11Size,
+	ReadMode,
 	ReadResult,
 	Readable,
 	ReadableWithContext,
 	Writable,
+	WritableVectored,
+	WriteError,
 	WriteResult,
 	X11Size,
 };
 use xrbk_macro::{derive_xrb, ConstantX11Size, Readable, Writable, X11Size};
 
 use crate::{
-	message::Request,
+	message::{LengthEncoding, Request},
 	unit::Px,
 	x11::{error, reply},
 	Arc,
@@ -75,6 +80,18 @@ macro_rules! request_error {
 				$Error(error::$Error)
 			),+)?
 		}
+
+		impl TryFrom<crate::x11::AnyError> for $Name {
+			type Error = crate::x11::AnyError;
+
+			fn try_from(error: crate::x11::AnyError) -> Result<Self, Self::Error> {
+				match error {
+					$($(crate::x11::AnyError::$Error(error) => Ok(Self::$Error(error)),)+)?
+
+					other => Err(other),
+				}
+			}
+		}
 	};
 }
 
@@ -111,6 +128,7 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	/// [`Match` error]: error::Match
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ClearArea: Request(61, ClearAreaError) {
 		/// Whether [`GraphicsExposure` events] should be generated for regions
 		/// of the `area` which are visible or maintained.
@@ -210,6 +228,7 @@ derive_xrb! {
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	/// [`Match` error]: error::Match
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct CopyArea: Request(62, CopyAreaError) {
 		/// The [drawable] from which the area is copied.
 		///
@@ -348,6 +367,7 @@ derive_xrb! {
 	/// [`Value` error]: error::Value
 	#[doc(alias("CopyPlane"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct CopyBitPlane: Request(63, CopyBitPlaneError) {
 		/// The [drawable] used as the source in this graphics operation.
 		///
@@ -459,7 +479,8 @@ request_error! {
 ///
 /// [coordinates]: Coords
 /// [drawable]: Drawable
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CoordinateMode {
 	/// [Coordinates] are relative to the top-left corner of the [drawable].
 	///
@@ -518,6 +539,7 @@ derive_xrb! {
 	/// [`Match` error]: error::Match
 	#[doc(alias("PolyPoint", "DrawPoint"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct DrawPoints: Request(64, DrawPointsError) {
 		/// Whether the `points` are drawn relative to the `target` or the
 		/// previously drawn point.
@@ -569,6 +591,23 @@ derive_xrb! {
 	}
 }
 
+impl WritableVectored for DrawPoints {
+	fn write_header(&self, header: &mut impl BufMut) -> Result<Cow<'_, [u8]>, WriteError> {
+		Self::MAJOR_OPCODE.write_to(header)?;
+		self.coordinate_mode.write_to(header)?;
+		self.checked_length()?.write_to(header)?;
+
+		self.target.write_to(header)?;
+		self.graphics_context.write_to(header)?;
+
+		// The coordinates must be converted to big-endian bytes.
+		let mut points = Vec::with_capacity(self.points.x11_size());
+		self.points.write_to(&mut points)?;
+
+		Ok(Cow::Owned(points))
+	}
+}
+
 request_error! {
 	#[doc(alias("PolyLineError", "DrawLinesError", "DrawLineError"))]
 	pub enum DrawPathError for DrawPath {
@@ -658,6 +697,7 @@ derive_xrb! {
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("PolyLine", "DrawLines", "DrawLine"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct DrawPath: Request(65, DrawPathError) {
 		/// Whether the [coordinates] of each point in `points` are relative to
 		/// the `target` or to the previous point.
@@ -706,6 +746,23 @@ derive_xrb! {
 	}
 }
 
+impl WritableVectored for DrawPath {
+	fn write_header(&self, header: &mut impl BufMut) -> Result<Cow<'_, [u8]>, WriteError> {
+		Self::MAJOR_OPCODE.write_to(header)?;
+		self.coordinate_mode.write_to(header)?;
+		self.checked_length()?.write_to(header)?;
+
+		self.target.write_to(header)?;
+		self.graphics_context.write_to(header)?;
+
+		// The coordinates must be converted to big-endian bytes.
+		let mut points = Vec::with_capacity(self.points.x11_size());
+		self.points.write_to(&mut points)?;
+
+		Ok(Cow::Owned(points))
+	}
+}
+
 request_error! {
 	#[doc(alias("PolySegmentError", "DrawSegmentError"))]
 	pub enum DrawLinesError for DrawLines {
@@ -718,6 +775,7 @@ request_error! {
 /// A line from the given `start` point to the given `end` point.
 #[doc(alias("Segment"))]
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Line {
 	/// The start of the line.
 	pub start: Coords,
@@ -793,6 +851,7 @@ derive_xrb! {
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("PolySegment", "DrawSegment"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct DrawLines: Request(66, DrawLinesError) {
 		/// The [drawable] on which the given `lines` are drawn.
 		///
@@ -909,6 +968,7 @@ derive_xrb! {
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("PolyRectangle"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct DrawRectangles: Request(67, DrawRectanglesError) {
 		/// The [drawable] on which the `rectangles`' outlines are drawn.
 		///
@@ -1024,6 +1084,7 @@ derive_xrb! {
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("PolyArc"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct DrawArcs: Request(68, DrawArcsError) {
 		/// The [drawable] on which the [arcs] are drawn.
 		///
@@ -1080,7 +1141,8 @@ request_error! {
 /// This is used in the [`FillPolygon` request].
 ///
 /// [`FillPolygon` request]: FillPolygon
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShapeMode {
 	/// The shape may intersect itself.
 	Complex,
@@ -1158,6 +1220,7 @@ derive_xrb! {
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("FillPoly"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct FillPolygon: Request(69, FillPolygonError) {
 		/// The [drawable] on which the filled polygon is drawn.
 		///
@@ -1287,6 +1350,7 @@ derive_xrb! {
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("PolyFillRectangle"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct FillRectangles: Request(70, FillRectanglesError) {
 		/// The [drawable] on which the [rectangles] are filled.
 		///
@@ -1400,6 +1464,7 @@ derive_xrb! {
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("PolyFillArc"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct FillArcs: Request(71, FillArcsError) {
 		/// The [drawable] on which the [arcs] are filled.
 		///
@@ -1452,7 +1517,8 @@ request_error! {
 ///
 /// [`PlaceImage` request]: PlaceImage
 #[doc(alias("PutImageFormat"))]
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PlaceImageFormat {
 	/// The image must be in XY format.
 	///
@@ -1533,6 +1599,7 @@ derive_xrb! {
 	/// [`Match` error]: error::Match
 	#[doc(alias("PutImage"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct PlaceImage: Request(72, PlaceImageError) {
 		/// The [image format] used.
 		///
@@ -1617,14 +1684,40 @@ derive_xrb! {
 		pub depth: u8,
 		[_; 2],
 
-		// FIXME: how do we know what is padding and what is data??????
 		/// The image's data.
+		///
+		/// The length of the data is not encoded separately from the
+		/// [length] of the [request], so any padding at the end of the data is
+		/// read as part of the data.
+		///
+		/// [length]: Request::length
+		/// [request]: Request
 		#[context(self::remaining => remaining)]
 		pub data: Vec<u8>,
 		[_; data => pad(data)],
 	}
 }
 
+impl WritableVectored for PlaceImage {
+	fn write_header(&self, header: &mut impl BufMut) -> Result<Cow<'_, [u8]>, WriteError> {
+		Self::MAJOR_OPCODE.write_to(header)?;
+		self.format.write_to(header)?;
+		self.checked_length()?.write_to(header)?;
+
+		self.target.write_to(header)?;
+		self.graphics_context.write_to(header)?;
+
+		self.dimensions.write_to(header)?;
+		self.coordinates.write_to(header)?;
+
+		self.left_padding.write_to(header)?;
+		self.depth.write_to(header)?;
+		header.put_bytes(0, 2);
+
+		Ok(Cow::Borrowed(&self.data))
+	}
+}
+
 request_error! {
 	#[doc(alias("GetImageError"))]
 	pub enum CaptureImageError for CaptureImage {
@@ -1641,13 +1734,14 @@ request_error! {
 /// [`CaptureImage` request]: CaptureImage
 /// [`CaptureImage` reply]: reply::CaptureImage
 #[doc(alias("GetImageFormat"))]
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CaptureImageFormat {
 	/// The image is returned in XY format.
-	XyPixmap,
+	XyPixmap = 1,
 
 	/// The image is returned in Z format.
-	Zpixmap,
+	Zpixmap = 2,
 }
 
 derive_xrb! {
@@ -1697,7 +1791,8 @@ derive_xrb! {
 	/// [`Drawable` error]: error::Drawable
 	/// [`Match` error]: error::Match
 	#[doc(alias("GetImage"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct CaptureImage: Request(73, CaptureImageError) -> reply::CaptureImage {
 		/// The [image format] of the image that is returned in the
 		/// [`CaptureImage` reply].
@@ -1753,10 +1848,40 @@ request_error! {
 	}
 }
 
+/// Reads the [`TextItem8`]s or [`TextItem16`]s at the end of a [`DrawText8`] or
+/// [`DrawText16`] request, followed by up to 3 bytes of padding.
+///
+/// The X server stops reading text items when fewer than 4 bytes remain and
+/// those bytes are all zero: those bytes are padding, or otherwise an empty
+/// text item with no horizontal offset, which has no effect.
+fn read_text_items<Item: Readable>(buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Vec<Item>> {
+	const TEXT_ITEM_HEADER: usize = 2;
+	const ALIGNMENT: usize = 4;
+
+	let body = buf.copy_to_bytes(buf.remaining());
+	let body = &mut &body[..];
+
+	let mut text_items = Vec::new();
+
+	while body.len() >= TEXT_ITEM_HEADER {
+		if body.len() < ALIGNMENT && body.iter().all(|byte| *byte == 0) {
+			break;
+		}
+
+		text_items.push(Item::read_from_with_mode(body, mode)?);
+	}
+
+	// Advance the padding bytes at the end.
+	read_padding(body, body.len(), mode)?;
+
+	Ok(text_items)
+}
+
 /// A 'text item' specified in a [`DrawText8` request].
 ///
 /// [`DrawText8` request]: DrawText8
 #[derive(Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextItem8 {
 	/// Specifies text that is to be drawn with the `graphics_context`'s current
 	/// [font].
@@ -1787,8 +1912,8 @@ impl Readable for TextItem8 {
 	where
 		Self: Sized,
 	{
-		Ok(match buf.get_u8() {
-			font_shift if font_shift == 255 => Self::Font(Font::new(buf.get_u32())),
+		Ok(match u8::read_from(buf)? {
+			font_shift if font_shift == 255 => Self::Font(Font::new(u32::read_from(buf)?)),
 			string_len => Self::Text(Box::new(Text8::read_with(buf, &string_len)?)),
 		})
 	}
@@ -1821,6 +1946,7 @@ impl Writable for TextItem8 {
 ///
 /// [`DrawText8` request]: DrawText8
 #[derive(Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Text8 {
 	horizontal_offset: Px<i8>,
 	string: String8,
@@ -1836,6 +1962,15 @@ pub struct TextTooLong {
 	pub found: usize,
 }
 
+impl TextTooLong {
+	/// The maximum length of the string in a [`Text8`] or [`Text16`].
+	///
+	/// A length of 255 indicates a [font shift] instead.
+	///
+	/// [font shift]: TextItem8::Font
+	pub const MAX: u8 = 254;
+}
+
 impl Text8 {
 	/// Creates a new `Text8` with the given `horizontal_offset` and `string`.
 	///
@@ -1843,11 +1978,11 @@ impl Text8 {
 	/// the `string`.
 	///
 	/// # Errors
-	/// A [`TextTooLong`] error is returned if `string.len() > 255`.
+	/// A [`TextTooLong`] error is returned if `string.len() > 254`.
 	pub fn new(horizontal_offset: Px<i8>, string: String8) -> Result<Self, TextTooLong> {
-		if string.len() > 255 {
+		if string.len() > usize::from(TextTooLong::MAX) {
 			Err(TextTooLong {
-				max: 255,
+				max: TextTooLong::MAX,
 				found: string.len(),
 			})
 		} else {
@@ -1971,6 +2106,7 @@ impl Writable for Text8 {
 /// [`Font` error]: error::Font
 #[doc(alias("PolyText8"))]
 #[derive(Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DrawText8 {
 	/// The [drawable] on which the text is drawn.
 	///
@@ -2032,6 +2168,25 @@ impl Request for DrawText8 {
 
 	const MAJOR_OPCODE: u8 = 74;
 	const MINOR_OPCODE: Option<u16> = None;
+
+	fn write_with_encoding(&self, buf: &mut impl BufMut, encoding: LengthEncoding) -> WriteResult {
+		let buf = &mut buf.limit(self.x11_size() + encoding.extra_size());
+
+		buf.put_u8(Self::MAJOR_OPCODE);
+		// Unused metabyte position.
+		buf.put_u8(0);
+		self.write_length(buf, encoding)?;
+
+		self.target.write_to(buf)?;
+		self.graphics_context.write_to(buf)?;
+		self.coordinates.write_to(buf)?;
+		self.text_items.write_to(buf)?;
+
+		// Unused padding bytes at the end.
+		buf.put_bytes(0, pad(&self.text_items));
+
+		Ok(())
+	}
 }
 
 impl X11Size for DrawText8 {
@@ -2054,37 +2209,28 @@ impl Readable for DrawText8 {
 	where
 		Self: Sized,
 	{
-		/// The maximum number of padding bytes that there could be at the end.
-		///
-		/// As long as there are more than this many bytes remaining, we know
-		/// that there are still text items left to read.
-		const MAX_PADDING: usize = 1;
+		Self::read_from_with_mode(buf, ReadMode::Lenient)
+	}
 
+	fn read_from_with_mode(buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
 		// major opcode is already read
 
 		// Metabyte position is unused.
-		buf.advance(1);
+		read_padding(buf, 1, mode)?;
 
-		// Read the length and bound buf to not read more than it.
-		let length = (usize::from(buf.get_u16()) * 4) - 2;
+		// Read the length and bound buf to not read more than it. The header
+		// has already been read.
+		let length = (usize::from(u16::read_from(buf)?) * 4).saturating_sub(4);
 		let buf = &mut buf.take(length);
 
-		let target = Drawable::read_from(buf)?;
-		let graphics_context = GraphicsContext::read_from(buf)?;
-		let coordinates = Coords::read_from(buf)?;
-
-		let text_items = {
-			let mut text_items = Vec::new();
+		let target = Drawable::read_from_with_mode(buf, mode)?;
+		let graphics_context = GraphicsContext::read_from_with_mode(buf, mode)?;
+		let coordinates = Coords::read_from_with_mode(buf, mode)?;
 
-			while buf.remaining() > MAX_PADDING {
-				text_items.push(TextItem8::read_from(buf)?);
-			}
-
-			text_items
-		};
-
-		// Advance the padding bytes at the end.
-		buf.advance(pad(&text_items));
+		let text_items = read_text_items(buf, mode)?;
 
 		Ok(Self {
 			target,
@@ -2097,22 +2243,7 @@ impl Readable for DrawText8 {
 
 impl Writable for DrawText8 {
 	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let buf = &mut buf.limit(self.x11_size());
-
-		buf.put_u8(Self::MAJOR_OPCODE);
-		// Unused metabyte position.
-		buf.put_u8(0);
-		buf.put_u16(self.length());
-
-		self.target.write_to(buf)?;
-		self.graphics_context.write_to(buf)?;
-		self.coordinates.write_to(buf)?;
-		self.text_items.write_to(buf)?;
-
-		// Unused padding bytes at the end.
-		buf.put_bytes(0, pad(&self.text_items));
-
-		Ok(())
+		self.write_with_encoding(buf, LengthEncoding::Standard)
 	}
 }
 
@@ -2130,6 +2261,7 @@ request_error! {
 ///
 /// [`DrawText16` request]: DrawText16
 #[derive(Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextItem16 {
 	/// Specifies text that is to be drawn with the `graphics_context`'s current
 	/// [font].
@@ -2160,8 +2292,8 @@ impl Readable for TextItem16 {
 	where
 		Self: Sized,
 	{
-		Ok(match buf.get_u8() {
-			font_shift if font_shift == 255 => Self::Font(Font::new(buf.get_u32())),
+		Ok(match u8::read_from(buf)? {
+			font_shift if font_shift == 255 => Self::Font(Font::new(u32::read_from(buf)?)),
 			string_len => Self::Text(Box::new(Text16::read_with(buf, &string_len)?)),
 		})
 	}
@@ -2194,6 +2326,7 @@ impl Writable for TextItem16 {
 ///
 /// [`DrawText16` request]: DrawText16
 #[derive(Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Text16 {
 	horizontal_offset: Px<i8>,
 	string: String16,
@@ -2206,11 +2339,11 @@ impl Text16 {
 	/// the `string`.
 	///
 	/// # Errors
-	/// A [`TextTooLong`] error is returned if `string.len() > 255`.
+	/// A [`TextTooLong`] error is returned if `string.len() > 254`.
 	pub fn new(horizontal_offset: Px<i8>, string: String16) -> Result<Self, TextTooLong> {
-		if string.len() > 255 {
+		if string.len() > usize::from(TextTooLong::MAX) {
 			Err(TextTooLong {
-				max: 255,
+				max: TextTooLong::MAX,
 				found: string.len(),
 			})
 		} else {
@@ -2339,6 +2472,7 @@ impl Writable for Text16 {
 /// [`Font` error]: error::Font
 #[doc(alias("PolyText16"))]
 #[derive(Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DrawText16 {
 	/// The [drawable] on which the text is drawn.
 	///
@@ -2395,11 +2529,30 @@ pub struct DrawText16 {
 }
 
 impl Request for DrawText16 {
-	type OtherErrors = DrawText8Error;
+	type OtherErrors = DrawText16Error;
 	type Reply = ();
 
 	const MAJOR_OPCODE: u8 = 75;
 	const MINOR_OPCODE: Option<u16> = None;
+
+	fn write_with_encoding(&self, buf: &mut impl BufMut, encoding: LengthEncoding) -> WriteResult {
+		let buf = &mut buf.limit(self.x11_size() + encoding.extra_size());
+
+		buf.put_u8(Self::MAJOR_OPCODE);
+		// Unused metabyte position.
+		buf.put_u8(0);
+		self.write_length(buf, encoding)?;
+
+		self.target.write_to(buf)?;
+		self.graphics_context.write_to(buf)?;
+		self.coordinates.write_to(buf)?;
+		self.text_items.write_to(buf)?;
+
+		// Unused padding bytes at the end.
+		buf.put_bytes(0, pad(&self.text_items));
+
+		Ok(())
+	}
 }
 
 impl X11Size for DrawText16 {
@@ -2422,37 +2575,28 @@ impl Readable for DrawText16 {
 	where
 		Self: Sized,
 	{
-		/// The maximum number of padding bytes that there could be at the end.
-		///
-		/// As long as there are more than this many bytes remaining, we know
-		/// that there are still text items left to read.
-		const MAX_PADDING: usize = 1;
+		Self::read_from_with_mode(buf, ReadMode::Lenient)
+	}
 
+	fn read_from_with_mode(buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
 		// major opcode is already read
 
 		// Metabyte position is unused.
-		buf.advance(1);
+		read_padding(buf, 1, mode)?;
 
-		// Read the length and bound buf to not read more than it.
-		let length = (usize::from(buf.get_u16()) * 4) - 2;
+		// Read the length and bound buf to not read more than it. The header
+		// has already been read.
+		let length = (usize::from(u16::read_from(buf)?) * 4).saturating_sub(4);
 		let buf = &mut buf.take(length);
 
-		let target = Drawable::read_from(buf)?;
-		let graphics_context = GraphicsContext::read_from(buf)?;
-		let coordinates = Coords::read_from(buf)?;
+		let target = Drawable::read_from_with_mode(buf, mode)?;
+		let graphics_context = GraphicsContext::read_from_with_mode(buf, mode)?;
+		let coordinates = Coords::read_from_with_mode(buf, mode)?;
 
-		let text_items = {
-			let mut text_items = Vec::new();
-
-			while buf.remaining() > MAX_PADDING {
-				text_items.push(TextItem16::read_from(buf)?);
-			}
-
-			text_items
-		};
-
-		// Advance the padding bytes at the end.
-		buf.advance(pad(&text_items));
+		let text_items = read_text_items(buf, mode)?;
 
 		Ok(Self {
 			target,
@@ -2465,22 +2609,7 @@ impl Readable for DrawText16 {
 
 impl Writable for DrawText16 {
 	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let buf = &mut buf.limit(self.x11_size());
-
-		buf.put_u8(Self::MAJOR_OPCODE);
-		// Unused metabyte position.
-		buf.put_u8(0);
-		buf.put_u16(self.length());
-
-		self.target.write_to(buf)?;
-		self.graphics_context.write_to(buf)?;
-		self.coordinates.write_to(buf)?;
-		self.text_items.write_to(buf)?;
-
-		// Unused padding bytes at the end.
-		buf.put_bytes(0, pad(&self.text_items));
-
-		Ok(())
+		self.write_with_encoding(buf, LengthEncoding::Standard)
 	}
 }
 
@@ -2579,6 +2708,7 @@ derive_xrb! {
 	/// [`Drawable` error]: error::Drawable
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ImageText8: Request(76, ImageText8Error) {
 		// The length of `string`.
 		#[metabyte]
@@ -2716,6 +2846,7 @@ derive_xrb! {
 	/// [`Drawable` error]: error::Drawable
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ImageText16: Request(77, ImageText16Error) {
 		// The length of `string`.
 		#[metabyte]
@@ -2761,3 +2892,580 @@ derive_xrb! {
 		[_; string => pad(string)],
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		message::{write_request_to, RequestTooLarge, WriteControl},
+		Char16,
+		Char8,
+	};
+
+	/// Returns the concatenation of the [`IoSlice`]s of the given `request`.
+	///
+	/// [`IoSlice`]: std::io::IoSlice
+	fn concat_io_slices(request: &impl WritableVectored) -> Vec<u8> {
+		request
+			.to_vectored()
+			.unwrap()
+			.io_slices()
+			.iter()
+			.flat_map(|slice| slice.iter().copied())
+			.collect()
+	}
+
+	#[test]
+	fn test_place_image_vectored() {
+		for len in [0, 1, 4, 4099] {
+			let request = PlaceImage {
+				format: PlaceImageFormat::Zpixmap,
+				target: Drawable::new(0x0040_0001),
+				graphics_context: GraphicsContext::new(0x0040_0002),
+				dimensions: Dimensions::new(Px(64), Px(16)),
+				coordinates: Coords::new(Px(-3), Px(7)),
+				left_padding: 0,
+				depth: 24,
+				data: (0..len).map(|i| (i % 251) as u8).collect(),
+			};
+
+			let mut bytes = vec![];
+			request.write_to(&mut bytes).unwrap();
+
+			let vectored = request.to_vectored().unwrap();
+			// The image data is borrowed rather than copied.
+			assert_eq!(vectored.payload().as_ptr(), request.data.as_ptr());
+			assert_eq!(vectored.len(), bytes.len());
+
+			assert_eq!(concat_io_slices(&request), bytes);
+		}
+	}
+
+	#[test]
+	fn test_draw_points_vectored() {
+		let request = DrawPoints {
+			coordinate_mode: CoordinateMode::Previous,
+			target: Drawable::new(0x0040_0001),
+			graphics_context: GraphicsContext::new(0x0040_0002),
+			points: (0..1000).map(|i| Coords::new(Px(i), Px(-i))).collect(),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(concat_io_slices(&request), bytes);
+	}
+
+	#[test]
+	fn test_draw_path_vectored() {
+		let request = DrawPath {
+			coordinate_mode: CoordinateMode::Drawable,
+			target: Drawable::new(0x0040_0001),
+			graphics_context: GraphicsContext::new(0x0040_0002),
+			points: (0..1000).map(|i| Coords::new(Px(i), Px(i / 2))).collect(),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(concat_io_slices(&request), bytes);
+	}
+
+	/// Writes the given `request`, checks its length field, and reads it back
+	/// with another request's bytes following it.
+	fn round_trip<R>(request: &R, size: usize) -> Vec<u8>
+	where
+		R: Request + Readable + Writable + PartialEq + std::fmt::Debug,
+	{
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), size);
+		assert_eq!(request.x11_size(), size);
+		assert_eq!(bytes[2..4], u16::try_from(size / 4).unwrap().to_be_bytes());
+
+		// The list must only be read until the end of the request.
+		let mut buf = bytes.clone();
+		buf.extend_from_slice(&[0xff; 12]);
+
+		let buf = &mut &buf[1..];
+		assert_eq!(&R::read_from(buf).unwrap(), request);
+		assert_eq!(buf.len(), 12);
+
+		bytes
+	}
+
+	#[test]
+	fn test_copy_area_and_bit_plane() {
+		let source = Drawable::new(0x0040_0001);
+		let destination = Drawable::new(0x0040_0002);
+		let graphics_context = GraphicsContext::new(0x0040_0003);
+
+		let request = CopyArea {
+			source,
+			destination,
+			graphics_context,
+			source_coords: Coords::new(Px(-4), Px(8)),
+			destination_coords: Coords::new(Px(16), Px(32)),
+			dimensions: Dimensions::new(Px(640), Px(480)),
+		};
+		let bytes = round_trip(&request, 28);
+		assert_eq!(bytes[..2], [62, 0]);
+		#[rustfmt::skip]
+		assert_eq!(bytes[16..], [
+			0xff, 0xfc, 0x00, 0x08,
+			0x00, 0x10, 0x00, 0x20,
+			0x02, 0x80, 0x01, 0xe0,
+		]);
+
+		let request = CopyBitPlane {
+			source,
+			destination,
+			graphics_context,
+			source_coords: Coords::new(Px(0), Px(0)),
+			destination_coords: Coords::new(Px(1), Px(1)),
+			dimensions: Dimensions::new(Px(8), Px(8)),
+			bit_plane: 1 << 7,
+		};
+		let bytes = round_trip(&request, 32);
+		assert_eq!(bytes[..2], [63, 0]);
+		assert_eq!(bytes[28..], 0x80_u32.to_be_bytes());
+	}
+
+	#[test]
+	fn test_draw_points_and_path() {
+		let target = Drawable::new(0x0040_0001);
+		let graphics_context = GraphicsContext::new(0x0040_0002);
+
+		let request = DrawPoints {
+			coordinate_mode: CoordinateMode::Drawable,
+			target,
+			graphics_context,
+			points: vec![],
+		};
+		let bytes = round_trip(&request, 12);
+		assert_eq!(bytes[..2], [64, 0]);
+
+		let request = DrawPath {
+			coordinate_mode: CoordinateMode::Previous,
+			target,
+			graphics_context,
+			points: vec![Coords::new(Px(-1), Px(2))],
+		};
+		let bytes = round_trip(&request, 16);
+		assert_eq!(bytes[..2], [65, 1]);
+		assert_eq!(bytes[12..], [0xff, 0xff, 0x00, 0x02]);
+	}
+
+	#[test]
+	fn test_draw_lines_and_rectangles() {
+		let target = Drawable::new(0x0040_0001);
+		let graphics_context = GraphicsContext::new(0x0040_0002);
+
+		let request = DrawLines {
+			target,
+			graphics_context,
+			lines: vec![Line {
+				start: Coords::new(Px(0), Px(1)),
+				end: Coords::new(Px(2), Px(3)),
+			}],
+		};
+		let bytes = round_trip(&request, 20);
+		assert_eq!(bytes[12..], [0, 0, 0, 1, 0, 2, 0, 3]);
+
+		let request = DrawRectangles {
+			target,
+			graphics_context,
+			rectangles: vec![Rectangle::new(Px(4), Px(5), Px(6), Px(7))],
+		};
+		let bytes = round_trip(&request, 20);
+		assert_eq!(bytes[..2], [67, 0]);
+		assert_eq!(bytes[12..], [0, 4, 0, 5, 0, 6, 0, 7]);
+
+		let request = FillRectangles {
+			target,
+			graphics_context,
+			rectangles: vec![],
+		};
+		round_trip(&request, 12);
+	}
+
+	#[test]
+	fn test_draw_and_fill_arcs() {
+		let target = Drawable::new(0x0040_0001);
+		let graphics_context = GraphicsContext::new(0x0040_0002);
+
+		let arc = Arc::new(Rectangle::new(Px(1), Px(2), Px(30), Px(40)), 0, 360 * 64);
+
+		let request = DrawArcs {
+			target,
+			graphics_context,
+			arcs: vec![arc.clone()],
+		};
+		let bytes = round_trip(&request, 24);
+		assert_eq!(bytes[..2], [68, 0]);
+		assert_eq!(bytes[12..], [0, 1, 0, 2, 0, 30, 0, 40, 0, 0, 0x5a, 0x00]);
+
+		// 1000 arcs: (12 + 12000) / 4 = 3003 units.
+		let request = FillArcs {
+			target,
+			graphics_context,
+			arcs: vec![arc; 1000],
+		};
+		let bytes = round_trip(&request, 12_012);
+		assert_eq!(bytes[..4], [71, 0, 0x0b, 0xbb]);
+	}
+
+	#[test]
+	fn test_fill_polygon() {
+		let request = FillPolygon {
+			target: Drawable::new(0x0040_0001),
+			graphics_context: GraphicsContext::new(0x0040_0002),
+			shape: ShapeMode::Convex,
+			coordinate_mode: CoordinateMode::Previous,
+			points: (0..3).map(|i| Coords::new(Px(i), Px(i))).collect(),
+		};
+
+		let bytes = round_trip(&request, 28);
+		assert_eq!(bytes[..2], [69, 0]);
+		// The shape, the coordinate mode, and 2 unused bytes.
+		assert_eq!(bytes[12..16], [2, 1, 0, 0]);
+	}
+
+	#[test]
+	fn test_place_image_3x3_zpixmap() {
+		// A 3x3 image with 8 bits per pixel, with each row padded from 3 to 4
+		// bytes.
+		let data = vec![
+			0x10, 0x11, 0x12, 0, //
+			0x20, 0x21, 0x22, 0, //
+			0x30, 0x31, 0x32, 0, //
+		];
+
+		let request = PlaceImage {
+			format: PlaceImageFormat::Zpixmap,
+			target: Drawable::new(0x0040_0001),
+			graphics_context: GraphicsContext::new(0x0040_0002),
+			dimensions: Dimensions::new(Px(3), Px(3)),
+			coordinates: Coords::new(Px(10), Px(20)),
+			left_padding: 0,
+			depth: 8,
+			data,
+		};
+
+		let bytes = round_trip(&request, 36);
+		assert_eq!(bytes[..4], [72, 2, 0, 9]);
+		assert_eq!(
+			bytes[12..24],
+			[
+				0, 3, 0, 3, // width, height
+				0, 10, 0, 20, // x, y
+				0, 8, 0, 0, // left padding, depth
+			]
+		);
+		assert_eq!(bytes[24..], request.data);
+	}
+
+	#[test]
+	fn test_place_image_pads_data() {
+		// A 3x3 image with 8 bits per pixel and no padding between rows.
+		let request = PlaceImage {
+			format: PlaceImageFormat::Zpixmap,
+			target: Drawable::new(0x0040_0001),
+			graphics_context: GraphicsContext::new(0x0040_0002),
+			dimensions: Dimensions::new(Px(3), Px(3)),
+			coordinates: Coords::new(Px(0), Px(0)),
+			left_padding: 0,
+			depth: 8,
+			data: (1..=9).collect(),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 36);
+		assert_eq!(bytes[2..4], [0, 9]);
+		assert_eq!(bytes[24..], [1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 0, 0]);
+
+		// The padding is read as part of the data.
+		let read = PlaceImage::read_from(&mut &bytes[1..]).unwrap();
+		assert_eq!(read.data, bytes[24..]);
+	}
+
+	#[test]
+	fn test_place_image_too_large() {
+		let request = PlaceImage {
+			format: PlaceImageFormat::Zpixmap,
+			target: Drawable::new(0x0040_0001),
+			graphics_context: GraphicsContext::new(0x0040_0002),
+			dimensions: Dimensions::new(Px(256), Px(256)),
+			coordinates: Coords::new(Px(0), Px(0)),
+			left_padding: 0,
+			depth: 8,
+			// 24 bytes of header bring this over the maximum size.
+			data: vec![0; RequestTooLarge::MAX_SIZE - 20],
+		};
+
+		assert!(request.checked_length().is_err());
+		assert!(request.write_to(&mut vec![]).is_err());
+		assert!(request.to_vectored().is_err());
+
+		let request = PlaceImage {
+			data: vec![0; RequestTooLarge::MAX_SIZE - 24],
+			..request
+		};
+
+		assert_eq!(request.checked_length(), Ok(u16::MAX));
+		assert!(request.write_to(&mut vec![]).is_ok());
+	}
+
+	#[test]
+	fn test_place_image_big_requests() {
+		let disabled = WriteControl {
+			big_requests_enabled: false,
+			max_request_length: u32::from(u16::MAX),
+		};
+		let enabled = WriteControl {
+			big_requests_enabled: true,
+			max_request_length: 0x003f_ffff,
+		};
+
+		// Exactly `RequestTooLarge::MAX_SIZE` bytes: written as normal.
+		let request = PlaceImage {
+			format: PlaceImageFormat::Zpixmap,
+			target: Drawable::new(0x0040_0001),
+			graphics_context: GraphicsContext::new(0x0040_0002),
+			dimensions: Dimensions::new(Px(256), Px(256)),
+			coordinates: Coords::new(Px(0), Px(0)),
+			left_padding: 0,
+			depth: 8,
+			data: vec![0xaa; RequestTooLarge::MAX_SIZE - 24],
+		};
+
+		for control in [disabled, enabled] {
+			let mut bytes = vec![];
+			write_request_to(&mut bytes, &request, &control).unwrap();
+
+			assert_eq!(bytes.len(), 262_140);
+			assert_eq!(bytes[2..4], [0xff, 0xff]);
+			assert_eq!(PlaceImage::read_from(&mut &bytes[1..]).unwrap(), request);
+		}
+
+		// Just over `RequestTooLarge::MAX_SIZE` bytes: needs the extended length.
+		let request = PlaceImage {
+			data: vec![0xaa; RequestTooLarge::MAX_SIZE - 20],
+			..request
+		};
+
+		let error = write_request_to(&mut vec![], &request, &disabled).unwrap_err();
+		assert!(error.to_string().contains("262144 bytes"));
+
+		let mut bytes = vec![];
+		write_request_to(&mut bytes, &request, &enabled).unwrap();
+
+		// The request, plus 4 bytes for the extended length field.
+		assert_eq!(bytes.len(), 262_148);
+		assert_eq!(bytes[..2], [72, 2]);
+		assert_eq!(bytes[2..4], [0, 0]);
+		assert_eq!(bytes[4..8], 65_537_u32.to_be_bytes());
+		assert_eq!(bytes[8..12], [0x00, 0x40, 0x00, 0x01]);
+		assert!(bytes[28..].iter().all(|&byte| byte == 0xaa));
+
+		// Writing the request normally is still an error.
+		assert!(request.write_to(&mut vec![]).is_err());
+
+		// The server's maximum request length is also enforced.
+		let small = WriteControl {
+			max_request_length: 65_536,
+			..enabled
+		};
+		let error = write_request_to(&mut vec![], &request, &small).unwrap_err();
+		assert!(error.to_string().contains("65537 units"));
+	}
+
+	#[test]
+	fn test_derived_request_big_requests() {
+		let control = WriteControl {
+			big_requests_enabled: true,
+			max_request_length: 0x003f_ffff,
+		};
+
+		// 12 bytes of header and 32768 8-byte rectangles.
+		let request = FillRectangles {
+			target: Drawable::new(0x0040_0001),
+			graphics_context: GraphicsContext::new(0x0040_0002),
+			rectangles: vec![Rectangle::new(Px(1), Px(2), Px(3), Px(4)); 32_768],
+		};
+
+		let mut bytes = vec![];
+		write_request_to(&mut bytes, &request, &control).unwrap();
+
+		assert_eq!(bytes.len(), 262_160);
+		assert_eq!(bytes[..4], [70, 0, 0, 0]);
+		assert_eq!(bytes[4..8], 65_540_u32.to_be_bytes());
+		assert_eq!(bytes[8..12], [0x00, 0x40, 0x00, 0x01]);
+		assert_eq!(bytes[16..24], [0, 1, 0, 2, 0, 3, 0, 4]);
+	}
+
+	#[test]
+	fn test_draw_text8_big_requests() {
+		let control = WriteControl {
+			big_requests_enabled: true,
+			max_request_length: 0x003f_ffff,
+		};
+
+		// 16 bytes of header, 52425 5-byte font shifts, and 3 bytes of padding.
+		let request = DrawText8 {
+			target: Drawable::new(0x0040_0001),
+			graphics_context: GraphicsContext::new(0x0040_0002),
+			coordinates: Coords::new(Px(0), Px(0)),
+			text_items: (0..52_425)
+				.map(|_| TextItem8::Font(Font::new(0x0050_0001)))
+				.collect(),
+		};
+
+		let mut bytes = vec![];
+		write_request_to(&mut bytes, &request, &control).unwrap();
+
+		// The request, plus 4 bytes for the extended length field.
+		assert_eq!(bytes.len(), 262_148);
+		assert_eq!(bytes[..4], [74, 0, 0, 0]);
+		assert_eq!(bytes[4..8], 65_537_u32.to_be_bytes());
+		assert_eq!(bytes[8..12], [0x00, 0x40, 0x00, 0x01]);
+		assert_eq!(bytes[20..25], [255, 0x00, 0x50, 0x00, 0x01]);
+		assert_eq!(bytes[262_145..], [0, 0, 0]);
+	}
+
+	fn string8(string: &str) -> String8 {
+		String8::from(string.bytes().map(Char8::new).collect::<Vec<_>>())
+	}
+
+	#[test]
+	fn test_draw_text8_font_shift_between_strings() {
+		let request = DrawText8 {
+			target: Drawable::new(0x0040_0001),
+			graphics_context: GraphicsContext::new(0x0040_0002),
+			coordinates: Coords::new(Px(5), Px(10)),
+			text_items: vec![
+				TextItem8::Text(Box::new(Text8::new(Px(1), string8("ab")).unwrap())),
+				TextItem8::Font(Font::new(0x0050_0001)),
+				TextItem8::Text(Box::new(Text8::new(Px(-2), string8("cde")).unwrap())),
+			],
+		};
+
+		let bytes = round_trip(&request, 32);
+		assert_eq!(bytes[..4], [74, 0, 0, 8]);
+		assert_eq!(
+			bytes[16..],
+			[
+				2, 1, b'a', b'b', // text
+				255, 0x00, 0x50, 0x00, 0x01, // font shift
+				3, 0xfe, b'c', b'd', b'e', // text
+				0, 0, // padding
+			]
+		);
+	}
+
+	#[test]
+	fn test_draw_text8_three_bytes_of_padding() {
+		let request = DrawText8 {
+			target: Drawable::new(0x0040_0001),
+			graphics_context: GraphicsContext::new(0x0040_0002),
+			coordinates: Coords::new(Px(0), Px(0)),
+			text_items: vec![TextItem8::Font(Font::new(0x0050_0001))],
+		};
+
+		let bytes = round_trip(&request, 24);
+		assert_eq!(bytes[21..], [0, 0, 0]);
+
+		// An empty text item with a horizontal offset is not padding.
+		let request = DrawText8 {
+			text_items: vec![
+				TextItem8::Font(Font::new(0x0050_0001)),
+				TextItem8::Text(Box::new(Text8::new(Px(4), string8("")).unwrap())),
+			],
+			..request
+		};
+
+		let bytes = round_trip(&request, 24);
+		assert_eq!(bytes[21..], [0, 4, 0]);
+	}
+
+	#[test]
+	fn test_draw_text16_font_shift_between_strings() {
+		let request = DrawText16 {
+			target: Drawable::new(0x0040_0001),
+			graphics_context: GraphicsContext::new(0x0040_0002),
+			coordinates: Coords::new(Px(5), Px(10)),
+			text_items: vec![
+				TextItem16::Text(Box::new(
+					Text16::new(Px(0), String16::from(vec![Char16::from(0x2603)])).unwrap(),
+				)),
+				TextItem16::Font(Font::new(0x0050_0001)),
+				TextItem16::Text(Box::new(
+					Text16::new(
+						Px(3),
+						String16::from(vec![Char16::from(0x0041), Char16::from(0x0042)]),
+					)
+					.unwrap(),
+				)),
+			],
+		};
+
+		let bytes = round_trip(&request, 32);
+		assert_eq!(bytes[..4], [75, 0, 0, 8]);
+		assert_eq!(
+			bytes[16..],
+			[
+				1, 0, 0x26, 0x03, // text
+				255, 0x00, 0x50, 0x00, 0x01, // font shift
+				2, 3, 0x00, 0x41, 0x00, 0x42, // text
+				0,    // padding
+			]
+		);
+	}
+
+	#[test]
+	fn test_text_too_long() {
+		let string = "a".repeat(254);
+		assert!(Text8::new(Px(0), string8(&string)).is_ok());
+
+		let string = "a".repeat(255);
+		assert_eq!(
+			Text8::new(Px(0), string8(&string)),
+			Err(TextTooLong {
+				max: 254,
+				found: 255
+			})
+		);
+	}
+
+	#[test]
+	fn test_image_text8() {
+		let request = ImageText8 {
+			target: Drawable::new(0x0040_0001),
+			graphics_context: GraphicsContext::new(0x0040_0002),
+			coordinates: Coords::new(Px(5), Px(10)),
+			string: string8("hello"),
+		};
+
+		let bytes = round_trip(&request, 24);
+		// The length of the string is in the metabyte.
+		assert_eq!(bytes[..4], [76, 5, 0, 6]);
+		assert_eq!(bytes[16..], [b'h', b'e', b'l', b'l', b'o', 0, 0, 0]);
+	}
+
+	#[test]
+	fn test_image_text16() {
+		let request = ImageText16 {
+			target: Drawable::new(0x0040_0001),
+			graphics_context: GraphicsContext::new(0x0040_0002),
+			coordinates: Coords::new(Px(5), Px(10)),
+			string: String16::from(vec![Char16::from(0x2603)]),
+		};
+
+		let bytes = round_trip(&request, 20);
+		assert_eq!(bytes[..4], [77, 1, 0, 5]);
+		assert_eq!(bytes[16..], [0x26, 0x03, 0, 0]);
+	}
+}
@@ -58,6 +58,18 @@ macro_rules! request_error {
 				$Error(error::$Error)
 			),+)?
 		}
+
+		impl TryFrom<crate::x11::AnyError> for $Name {
+			type Error = crate::x11::AnyError;
+
+			fn try_from(error: crate::x11::AnyError) -> Result<Self, Self::Error> {
+				match error {
+					$($(crate::x11::AnyError::$Error(error) => Ok(Self::$Error(error)),)+)?
+
+					other => Err(other),
+				}
+			}
+		}
 	};
 }
 
@@ -94,6 +106,7 @@ derive_xrb! {
 	/// [`ResourceIdChoice` error]: error::ResourceIdChoice
 	/// [`Value` error]: error::Value
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct CreatePixmap: Request(53, CreatePixmapError) {
 		/// The depth of the [pixmap].
 		///
@@ -162,6 +175,7 @@ derive_xrb! {
 	///
 	/// [`Pixmap` error]: error::Pixmap
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct FreePixmap: Request(54, error::Pixmap) {
 		/// The [pixmap] which is to have its association with its ID removed.
 		///
@@ -210,6 +224,7 @@ derive_xrb! {
 	/// [`Drawable` error]: error::Drawable
 	#[doc(alias("CreateGc", "CreateGC", "CreateGcontext", "CreateGContext"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct CreateGraphicsContext: Request(55, CreateGraphicsContextError) {
 		/// The [`GraphicsContext` ID] which is to be assigned to the
 		/// [`GraphicsContext`].
@@ -302,6 +317,7 @@ derive_xrb! {
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("ChangeGc", "ChangeGC", "ChangeGraphicsContext"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ChangeGraphicsOptions: Request(56, ChangeGraphicsOptionsError) {
 		/// The [`GraphicsContext`] for which this [request] changes its
 		/// [graphics options].
@@ -356,6 +372,7 @@ derive_xrb! {
 	/// [`Match` error]: error::Match
 	#[doc(alias("CopyGc", "CopyGC", "CopyGraphicsContext", "CopyGcontext"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct CopyGraphicsOptions: Request(57, CopyGraphicsOptionsError) {
 		/// The [`GraphicsContext`] from which the [options] specified in
 		/// `options_mask` are copied.
@@ -431,6 +448,7 @@ derive_xrb! {
 	///
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct SetDashes: Request(58, SetDashesError) {
 		/// The [`GraphicsContext`] on which this [request] configures its
 		/// dashes.
@@ -475,6 +493,7 @@ request_error! {
 ///
 /// [`SetClipRectangles` request]: SetClipRectangles
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ClipRectanglesOrdering {
 	/// No particular order is specified.
 	///
@@ -550,6 +569,7 @@ derive_xrb! {
 	///
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct SetClipRectangles: Request(59, SetClipRectanglesError) {
 		/// Specifies the ordering of [rectangles] within `clip_rectangles`.
 		///
@@ -625,6 +645,7 @@ derive_xrb! {
 	#[doc(alias("FreeGc", "FreeGcontext", "FreeGraphicsContext"))]
 	#[doc(alias("DestroyGc", "DestroyGcontext"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct DestroyGraphicsContext: Request(60, error::GraphicsContext) {
 		/// The [`GraphicsContext`] which is to be deleted.
 		///
@@ -697,7 +718,8 @@ derive_xrb! {
 	/// [`Match` error]: error::Match
 	/// [`Pixmap` error]: error::Pixmap
 	#[doc(alias("CreateCursor"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct CreateCursorAppearance: Request(93, CreateCursorAppearanceError) {
 		/// The [`CursorAppearance` ID] which is to be assigned to the
 		/// [`CursorAppearance`].
@@ -872,7 +894,8 @@ derive_xrb! {
 	/// [`Font` error]: error::Font
 	/// [`Value` error]: error::Value
 	#[doc(alias("CreateGlyphCursor"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct CreateGlyphCursorAppearance: Request(94, CreateGlyphCursorAppearanceError) {
 		/// The [`CursorAppearance` ID] which is to be assigned to the
 		/// [`CursorAppearance`].
@@ -1003,7 +1026,8 @@ derive_xrb! {
 	/// [`CursorAppearance` ID]: CursorAppearance
 	///
 	/// [`CursorAppearance` error]: error::CursorAppearance
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct DestroyCursorAppearance: Request(95, error::CursorAppearance) {
 		/// The [`CursorAppearance`] that is to be deleted.
 		///
@@ -1029,7 +1053,8 @@ derive_xrb! {
 	/// [request]: Request
 	///
 	/// [`CursorAppearance` error]: error::CursorAppearance
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct RecolorCursorAppearance: Request(96, error::CursorAppearance) {
 		/// The [`CursorAppearance`] which is to be recolored.
 		///
@@ -1082,6 +1107,7 @@ request_error! {
 /// [`QueryIdealDimension` request]: QueryIdealDimensions
 #[doc(alias("QueryBestSizeClass", "QueryIdealDimensionsClass"))]
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DimensionClass {
 	/// The largest [`CursorAppearance`] [dimensions] that can be fully
 	/// displayed are returned.
@@ -1137,7 +1163,8 @@ derive_xrb! {
 	/// [`Drawable` error]: error::Drawable
 	/// [`Match` error]: error::Match
 	#[doc(alias("QueryBestSize"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct QueryIdealDimensions: Request(
 		97,
 		QueryIdealDimensionsError,
@@ -1183,3 +1210,55 @@ derive_xrb! {
 		pub dimensions: Dimensions,
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use xrbk::{Readable, Writable};
+
+	#[test]
+	fn test_create_pixmap() {
+		let request = CreatePixmap {
+			depth: 24,
+			pixmap_id: Pixmap::new(0x0020_0001),
+			drawable: Drawable::new(0x0000_0100),
+			width: Px(64),
+			height: Px(32),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(CreatePixmap::X11_SIZE, 16);
+		assert_eq!(
+			bytes,
+			[
+				53, 24, 0, 4, // header
+				0x00, 0x20, 0x00, 0x01, // pixmap
+				0x00, 0x00, 0x01, 0x00, // drawable
+				0x00, 0x40, 0x00, 0x20, // width, height
+			]
+		);
+		assert_eq!(CreatePixmap::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_copy_graphics_options() {
+		let request = CopyGraphicsOptions {
+			source: GraphicsContext::new(0x0020_0002),
+			destination: GraphicsContext::new(0x0020_0003),
+			options_mask: GraphicsOptionsMask::FOREGROUND_COLOR | GraphicsOptionsMask::ARC_MODE,
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(CopyGraphicsOptions::X11_SIZE, 16);
+		assert_eq!(bytes[..4], [57, 0, 0, 4]);
+		assert_eq!(bytes[12..], [0x00, 0x40, 0x00, 0x04]);
+		assert_eq!(
+			CopyGraphicsOptions::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+}
@@ -14,10 +14,12 @@ extern crate self as xrb;
 
 use xrbk::{
 	pad,
+	read_padding,
 	Buf,
 	BufMut,
 	ConstantX11Size,
 	ReadError,
+	ReadMode,
 	ReadResult,
 	Readable,
 	Writable,
@@ -26,14 +28,14 @@ use xrbk::{
 };
 use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
 
-use array_init::array_init;
+use array_init::try_array_init;
 use std::ops::RangeInclusive;
 use thiserror::Error;
 
 use crate::{
-	message::Request,
+	message::{LengthEncoding, Request},
 	set::KeyboardOptions,
-	unit::{Px, SignedPercentage},
+	unit::{Px, SignedPercentage, ValueOutOfBounds},
 	x11::{error, reply},
 	Any,
 	AnyModifierKeyMask,
@@ -77,6 +79,18 @@ macro_rules! request_error {
 				$Error(error::$Error)
 			),+)?
 		}
+
+		impl TryFrom<crate::x11::AnyError> for $Name {
+			type Error = crate::x11::AnyError;
+
+			fn try_from(error: crate::x11::AnyError) -> Result<Self, Self::Error> {
+				match error {
+					$($(crate::x11::AnyError::$Error(error) => Ok(Self::$Error(error)),)+)?
+
+					other => Err(other),
+				}
+			}
+		}
 	};
 }
 
@@ -91,6 +105,8 @@ request_error! {
 derive_xrb! {
 	/// A [request] that actively grabs control of the cursor.
 	///
+	/// A `GrabCursor` request is constructed with [`GrabCursor::builder`].
+	///
 	/// This [request] generates [`EnterWindow`] and [`LeaveWindow`] events.
 	///
 	/// # Replies
@@ -115,6 +131,7 @@ derive_xrb! {
 	/// [`CursorAppearance` error]: error::CursorAppearance
 	#[doc(alias = "GrabPointer")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GrabCursor: Request(26, GrabCursorError) -> reply::GrabCursor {
 		/// Whether cursor [events] which would normally be reported to this
 		/// client are reported normally.
@@ -228,6 +245,7 @@ derive_xrb! {
 	/// [`LeaveWindow`]: crate::x11::event::LeaveWindow
 	#[doc(alias = "UngrabPointer")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct UngrabCursor: Request(27) {
 		/// The [time] at which the grab is recorded as having been released.
 		///
@@ -236,6 +254,190 @@ derive_xrb! {
 	}
 }
 
+impl GrabCursor {
+	/// Returns a new [`GrabCursorBuilder`] with which a `GrabCursor` request
+	/// for the given `grab_window` can be created.
+	///
+	/// # Examples
+	/// ```
+	/// use xrb::{x11::request::GrabCursor, CursorEventMask, FreezeMode, Window};
+	///
+	/// # let grab_window = Window::new(0x0060_0001);
+	/// let request = GrabCursor::builder(grab_window)
+	///     .owner_events(true)
+	///     .event_mask(CursorEventMask::BUTTON_PRESS | CursorEventMask::BUTTON_RELEASE)
+	///     .keyboard_freeze(FreezeMode::Frozen)
+	///     .build();
+	///
+	/// assert_eq!(request.confine_to, None);
+	/// ```
+	#[must_use]
+	pub const fn builder(grab_window: Window) -> GrabCursorBuilder {
+		GrabCursorBuilder::new(grab_window)
+	}
+}
+
+/// A builder used to construct a new [`GrabCursor` request].
+///
+/// All options other than the `grab_window` start as:
+/// - `owner_events`: `false`
+/// - `event_mask`: empty
+/// - `cursor_freeze` and `keyboard_freeze`: [`FreezeMode::Unfrozen`]
+/// - `confine_to` and `cursor_appearance`: [`None`]
+/// - `time`: [`CurrentableTime::CurrentTime`]
+///
+/// When the builder is configured, [`build()`] can be used to construct the
+/// resulting [`GrabCursor` request].
+///
+/// [`build()`]: GrabCursorBuilder::build
+/// [`GrabCursor` request]: GrabCursor
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct GrabCursorBuilder {
+	owner_events: bool,
+	grab_window: Window,
+	event_mask: CursorEventMask,
+
+	cursor_freeze: FreezeMode,
+	keyboard_freeze: FreezeMode,
+
+	confine_to: Option<Window>,
+	cursor_appearance: Option<CursorAppearance>,
+
+	time: CurrentableTime,
+}
+
+impl GrabCursorBuilder {
+	/// Creates a new `GrabCursorBuilder` for the given `grab_window`.
+	///
+	/// See [`GrabCursorBuilder`] for the options' initial values.
+	#[must_use]
+	pub const fn new(grab_window: Window) -> Self {
+		Self {
+			owner_events: false,
+			grab_window,
+			event_mask: CursorEventMask::empty(),
+
+			cursor_freeze: FreezeMode::Unfrozen,
+			keyboard_freeze: FreezeMode::Unfrozen,
+
+			confine_to: None,
+			cursor_appearance: None,
+
+			time: CurrentableTime::CurrentTime,
+		}
+	}
+
+	/// Constructs the resulting [`GrabCursor` request] with the configured
+	/// options.
+	///
+	/// [`GrabCursor` request]: GrabCursor
+	#[must_use]
+	pub const fn build(&self) -> GrabCursor {
+		GrabCursor {
+			owner_events: self.owner_events,
+			grab_window: self.grab_window,
+			event_mask: self.event_mask,
+
+			cursor_freeze: self.cursor_freeze,
+			keyboard_freeze: self.keyboard_freeze,
+
+			confine_to: self.confine_to,
+			cursor_appearance: self.cursor_appearance,
+
+			time: self.time,
+		}
+	}
+}
+
+impl GrabCursorBuilder {
+	/// Configures whether cursor [events] which would normally be reported to
+	/// this client are reported normally.
+	///
+	/// See [`GrabCursor::owner_events`] for more information.
+	///
+	/// [events]: crate::message::Event
+	pub const fn owner_events(&mut self, owner_events: bool) -> &mut Self {
+		self.owner_events = owner_events;
+
+		self
+	}
+
+	/// Configures which cursor [events] are reported to this client.
+	///
+	/// See [`GrabCursor::event_mask`] for more information.
+	///
+	/// [events]: crate::message::Event
+	pub const fn event_mask(&mut self, event_mask: CursorEventMask) -> &mut Self {
+		self.event_mask = event_mask;
+
+		self
+	}
+
+	/// Configures whether cursor [event] processing is frozen.
+	///
+	/// See [`GrabCursor::cursor_freeze`] for more information.
+	///
+	/// [event]: crate::message::Event
+	pub const fn cursor_freeze(&mut self, cursor_freeze: FreezeMode) -> &mut Self {
+		self.cursor_freeze = cursor_freeze;
+
+		self
+	}
+
+	/// Configures whether keyboard [event] processing is frozen.
+	///
+	/// See [`GrabCursor::keyboard_freeze`] for more information.
+	///
+	/// [event]: crate::message::Event
+	pub const fn keyboard_freeze(&mut self, keyboard_freeze: FreezeMode) -> &mut Self {
+		self.keyboard_freeze = keyboard_freeze;
+
+		self
+	}
+
+	/// Configures the [window] which the cursor is confined to.
+	///
+	/// Confining the cursor does not affect which [window] cursor [events] are
+	/// reported relative to: unless [`owner_events`] is `true`, they are still
+	/// reported relative to the `grab_window`.
+	///
+	/// See [`GrabCursor::confine_to`] for more information.
+	///
+	/// [window]: Window
+	/// [events]: crate::message::Event
+	/// [`owner_events`]: GrabCursorBuilder::owner_events
+	pub const fn confine_to(&mut self, confine_to: Option<Window>) -> &mut Self {
+		self.confine_to = confine_to;
+
+		self
+	}
+
+	/// Configures the [cursor appearance] displayed during the grab.
+	///
+	/// See [`GrabCursor::cursor_appearance`] for more information.
+	///
+	/// [cursor appearance]: CursorAppearance
+	pub const fn cursor_appearance(
+		&mut self, cursor_appearance: Option<CursorAppearance>,
+	) -> &mut Self {
+		self.cursor_appearance = cursor_appearance;
+
+		self
+	}
+
+	/// Configures the [time] at which the grab is recorded as having been
+	/// initiated.
+	///
+	/// See [`GrabCursor::time`] for more information.
+	///
+	/// [time]: crate::Timestamp
+	pub const fn time(&mut self, time: CurrentableTime) -> &mut Self {
+		self.time = time;
+
+		self
+	}
+}
+
 request_error! {
 	pub enum GrabButtonError for GrabButton {
 		Access,
@@ -249,6 +451,8 @@ derive_xrb! {
 	/// A [request] that establishes a passive cursor grab for a given `button`
 	/// and `modifiers` combination.
 	///
+	/// A `GrabButton` request is constructed with [`GrabButton::builder`].
+	///
 	/// If the following conditions are true, the grab is converted into an
 	/// active cursor grab (as described in the [`GrabCursor` request]):
 	/// - the cursor is not already actively grabbed; and
@@ -279,6 +483,7 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	/// [`CursorAppearance` error]: error::CursorAppearance
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GrabButton: Request(28, GrabButtonError) {
 		/// Whether cursor [events] which would normally be reported to this
 		/// client are reported normally.
@@ -396,6 +601,196 @@ derive_xrb! {
 	}
 }
 
+impl GrabButton {
+	/// Returns a new [`GrabButtonBuilder`] with which a `GrabButton` request
+	/// for the given `button` on the given `grab_window` can be created.
+	///
+	/// # Examples
+	/// ```
+	/// use xrb::{
+	///     x11::request::GrabButton,
+	///     Any,
+	///     AnyModifierKeyMask,
+	///     Button,
+	///     Window,
+	/// };
+	///
+	/// # let grab_window = Window::new(0x0060_0001);
+	/// let request = GrabButton::builder(grab_window, Any::Other(Button::PRIMARY))
+	///     .modifiers(AnyModifierKeyMask::MOD_4)
+	///     .build();
+	///
+	/// assert!(!request.owner_events);
+	/// ```
+	#[must_use]
+	pub const fn builder(grab_window: Window, button: Any<Button>) -> GrabButtonBuilder {
+		GrabButtonBuilder::new(grab_window, button)
+	}
+}
+
+/// A builder used to construct a new [`GrabButton` request].
+///
+/// All options other than the `grab_window` and `button` start as:
+/// - `owner_events`: `false`
+/// - `event_mask`: empty
+/// - `cursor_freeze` and `keyboard_freeze`: [`FreezeMode::Unfrozen`]
+/// - `confine_to` and `cursor_appearance`: [`None`]
+/// - `modifiers`: empty
+///
+/// When the builder is configured, [`build()`] can be used to construct the
+/// resulting [`GrabButton` request].
+///
+/// [`build()`]: GrabButtonBuilder::build
+/// [`GrabButton` request]: GrabButton
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct GrabButtonBuilder {
+	owner_events: bool,
+	grab_window: Window,
+	event_mask: CursorEventMask,
+
+	cursor_freeze: FreezeMode,
+	keyboard_freeze: FreezeMode,
+
+	confine_to: Option<Window>,
+	cursor_appearance: Option<CursorAppearance>,
+
+	button: Any<Button>,
+	modifiers: AnyModifierKeyMask,
+}
+
+impl GrabButtonBuilder {
+	/// Creates a new `GrabButtonBuilder` for the given `button` on the given
+	/// `grab_window`.
+	///
+	/// See [`GrabButtonBuilder`] for the options' initial values.
+	#[must_use]
+	pub const fn new(grab_window: Window, button: Any<Button>) -> Self {
+		Self {
+			owner_events: false,
+			grab_window,
+			event_mask: CursorEventMask::empty(),
+
+			cursor_freeze: FreezeMode::Unfrozen,
+			keyboard_freeze: FreezeMode::Unfrozen,
+
+			confine_to: None,
+			cursor_appearance: None,
+
+			button,
+			modifiers: AnyModifierKeyMask::empty(),
+		}
+	}
+
+	/// Constructs the resulting [`GrabButton` request] with the configured
+	/// options.
+	///
+	/// [`GrabButton` request]: GrabButton
+	#[must_use]
+	pub const fn build(&self) -> GrabButton {
+		GrabButton {
+			owner_events: self.owner_events,
+			grab_window: self.grab_window,
+			event_mask: self.event_mask,
+
+			cursor_freeze: self.cursor_freeze,
+			keyboard_freeze: self.keyboard_freeze,
+
+			confine_to: self.confine_to,
+			cursor_appearance: self.cursor_appearance,
+
+			button: self.button,
+			modifiers: self.modifiers,
+		}
+	}
+}
+
+impl GrabButtonBuilder {
+	/// Configures whether cursor [events] which would normally be reported to
+	/// this client are reported normally.
+	///
+	/// See [`GrabButton::owner_events`] for more information.
+	///
+	/// [events]: crate::message::Event
+	pub const fn owner_events(&mut self, owner_events: bool) -> &mut Self {
+		self.owner_events = owner_events;
+
+		self
+	}
+
+	/// Configures which cursor [events] are reported to this client.
+	///
+	/// See [`GrabButton::event_mask`] for more information.
+	///
+	/// [events]: crate::message::Event
+	pub const fn event_mask(&mut self, event_mask: CursorEventMask) -> &mut Self {
+		self.event_mask = event_mask;
+
+		self
+	}
+
+	/// Configures whether cursor [event] processing is frozen.
+	///
+	/// See [`GrabButton::cursor_freeze`] for more information.
+	///
+	/// [event]: crate::message::Event
+	pub const fn cursor_freeze(&mut self, cursor_freeze: FreezeMode) -> &mut Self {
+		self.cursor_freeze = cursor_freeze;
+
+		self
+	}
+
+	/// Configures whether keyboard [event] processing is frozen.
+	///
+	/// See [`GrabButton::keyboard_freeze`] for more information.
+	///
+	/// [event]: crate::message::Event
+	pub const fn keyboard_freeze(&mut self, keyboard_freeze: FreezeMode) -> &mut Self {
+		self.keyboard_freeze = keyboard_freeze;
+
+		self
+	}
+
+	/// Configures the [window] which the cursor is confined to.
+	///
+	/// Confining the cursor does not affect which [window] cursor [events] are
+	/// reported relative to: unless [`owner_events`] is `true`, they are still
+	/// reported relative to the `grab_window`.
+	///
+	/// See [`GrabButton::confine_to`] for more information.
+	///
+	/// [window]: Window
+	/// [events]: crate::message::Event
+	/// [`owner_events`]: GrabButtonBuilder::owner_events
+	pub const fn confine_to(&mut self, confine_to: Option<Window>) -> &mut Self {
+		self.confine_to = confine_to;
+
+		self
+	}
+
+	/// Configures the [cursor appearance] displayed during the grab.
+	///
+	/// See [`GrabButton::cursor_appearance`] for more information.
+	///
+	/// [cursor appearance]: CursorAppearance
+	pub const fn cursor_appearance(
+		&mut self, cursor_appearance: Option<CursorAppearance>,
+	) -> &mut Self {
+		self.cursor_appearance = cursor_appearance;
+
+		self
+	}
+
+	/// Configures the combination of modifiers which must be held for the grab
+	/// to be activated.
+	///
+	/// See [`GrabButton::modifiers`] for more information.
+	pub const fn modifiers(&mut self, modifiers: AnyModifierKeyMask) -> &mut Self {
+		self.modifiers = modifiers;
+
+		self
+	}
+}
+
 request_error! {
 	pub enum UngrabButtonError for UngrabButton {
 		Value,
@@ -418,6 +813,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct UngrabButton: Request(29, UngrabButtonError) {
 		/// The [button] which the [passive button grab] was established for.
 		///
@@ -487,6 +883,7 @@ derive_xrb! {
 	/// [`CursorAppearance` error]: error::CursorAppearance
 	#[doc(alias = "ChangeActivePointerGrab")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ChangeActiveCursorGrab: Request(30, ChangeActiveCursorGrabError) {
 		/// Optionally overrides the [appearance of the cursor], no matter which
 		/// [window] it is within, for the duration of the grab.
@@ -554,6 +951,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GrabKeyboard: Request(31, GrabKeyboardError) -> reply::GrabKeyboard {
 		/// Whether key [events] which would normally be reported to this client
 		/// are reported normally.
@@ -633,6 +1031,7 @@ derive_xrb! {
 	/// [`Focus`]: crate::x11::event::Focus
 	/// [`Unfocus`]: crate::x11::event::Unfocus
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct UngrabKeyboard: Request(32) {
 		/// The [time] at which the grab is recorded as having been released.
 		///
@@ -641,6 +1040,131 @@ derive_xrb! {
 	}
 }
 
+impl GrabKeyboard {
+	/// Returns a new [`GrabKeyboardBuilder`] with which a `GrabKeyboard`
+	/// request for the given `grab_window` can be created.
+	///
+	/// # Examples
+	/// ```
+	/// use xrb::{x11::request::GrabKeyboard, CurrentableTime, FreezeMode, Window};
+	///
+	/// # let grab_window = Window::new(0x0060_0001);
+	/// let request = GrabKeyboard::builder(grab_window)
+	///     .owner_events(true)
+	///     .cursor_freeze(FreezeMode::Frozen)
+	///     .build();
+	///
+	/// assert_eq!(request.time, CurrentableTime::CurrentTime);
+	/// ```
+	#[must_use]
+	pub const fn builder(grab_window: Window) -> GrabKeyboardBuilder {
+		GrabKeyboardBuilder::new(grab_window)
+	}
+}
+
+/// A builder used to construct a new [`GrabKeyboard` request].
+///
+/// All options other than the `grab_window` start as:
+/// - `owner_events`: `false`
+/// - `time`: [`CurrentableTime::CurrentTime`]
+/// - `cursor_freeze` and `keyboard_freeze`: [`FreezeMode::Unfrozen`]
+///
+/// When the builder is configured, [`build()`] can be used to construct the
+/// resulting [`GrabKeyboard` request].
+///
+/// [`build()`]: GrabKeyboardBuilder::build
+/// [`GrabKeyboard` request]: GrabKeyboard
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct GrabKeyboardBuilder {
+	owner_events: bool,
+	grab_window: Window,
+	time: CurrentableTime,
+
+	cursor_freeze: FreezeMode,
+	keyboard_freeze: FreezeMode,
+}
+
+impl GrabKeyboardBuilder {
+	/// Creates a new `GrabKeyboardBuilder` for the given `grab_window`.
+	///
+	/// See [`GrabKeyboardBuilder`] for the options' initial values.
+	#[must_use]
+	pub const fn new(grab_window: Window) -> Self {
+		Self {
+			owner_events: false,
+			grab_window,
+			time: CurrentableTime::CurrentTime,
+
+			cursor_freeze: FreezeMode::Unfrozen,
+			keyboard_freeze: FreezeMode::Unfrozen,
+		}
+	}
+
+	/// Constructs the resulting [`GrabKeyboard` request] with the configured
+	/// options.
+	///
+	/// [`GrabKeyboard` request]: GrabKeyboard
+	#[must_use]
+	pub const fn build(&self) -> GrabKeyboard {
+		GrabKeyboard {
+			owner_events: self.owner_events,
+			grab_window: self.grab_window,
+			time: self.time,
+
+			cursor_freeze: self.cursor_freeze,
+			keyboard_freeze: self.keyboard_freeze,
+		}
+	}
+}
+
+impl GrabKeyboardBuilder {
+	/// Configures whether key [events] which would normally be reported to
+	/// this client are reported normally.
+	///
+	/// See [`GrabKeyboard::owner_events`] for more information.
+	///
+	/// [events]: crate::message::Event
+	pub const fn owner_events(&mut self, owner_events: bool) -> &mut Self {
+		self.owner_events = owner_events;
+
+		self
+	}
+
+	/// Configures the [time] at which the grab is recorded as having been
+	/// initiated.
+	///
+	/// See [`GrabKeyboard::time`] for more information.
+	///
+	/// [time]: crate::Timestamp
+	pub const fn time(&mut self, time: CurrentableTime) -> &mut Self {
+		self.time = time;
+
+		self
+	}
+
+	/// Configures whether cursor [event] processing is frozen.
+	///
+	/// See [`GrabKeyboard::cursor_freeze`] for more information.
+	///
+	/// [event]: crate::message::Event
+	pub const fn cursor_freeze(&mut self, cursor_freeze: FreezeMode) -> &mut Self {
+		self.cursor_freeze = cursor_freeze;
+
+		self
+	}
+
+	/// Configures whether keyboard [event] processing is frozen.
+	///
+	/// See [`GrabKeyboard::keyboard_freeze`] for more information.
+	///
+	/// [event]: crate::message::Event
+	pub const fn keyboard_freeze(&mut self, keyboard_freeze: FreezeMode) -> &mut Self {
+		self.keyboard_freeze = keyboard_freeze;
+
+		self
+	}
+}
+
 request_error! {
 	pub enum GrabKeyError for GrabKey {
 		Access,
@@ -680,6 +1204,7 @@ derive_xrb! {
 	/// [`Access` error]: error::Access
 	/// [`Window` error]: error::Window
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GrabKey: Request(33, GrabKeyError) {
 		/// Whether key [events] which would normally be reported to this client
 		/// are reported normally.
@@ -763,6 +1288,137 @@ derive_xrb! {
 	}
 }
 
+impl GrabKey {
+	/// Returns a new [`GrabKeyBuilder`] with which a `GrabKey` request for the
+	/// given `key` on the given `grab_window` can be created.
+	///
+	/// # Examples
+	/// ```
+	/// use xrb::{x11::request::GrabKey, Any, AnyModifierKeyMask, Keycode, Window};
+	///
+	/// # let grab_window = Window::new(0x0060_0001);
+	/// let request = GrabKey::builder(grab_window, Any::Other(Keycode::new(38)))
+	///     .modifiers(AnyModifierKeyMask::MOD_4 | AnyModifierKeyMask::SHIFT)
+	///     .build();
+	///
+	/// assert!(!request.owner_events);
+	/// ```
+	#[must_use]
+	pub const fn builder(grab_window: Window, key: Any<Keycode>) -> GrabKeyBuilder {
+		GrabKeyBuilder::new(grab_window, key)
+	}
+}
+
+/// A builder used to construct a new [`GrabKey` request].
+///
+/// All options other than the `grab_window` and `key` start as:
+/// - `owner_events`: `false`
+/// - `modifiers`: empty
+/// - `cursor_freeze` and `keyboard_freeze`: [`FreezeMode::Unfrozen`]
+///
+/// When the builder is configured, [`build()`] can be used to construct the
+/// resulting [`GrabKey` request].
+///
+/// [`build()`]: GrabKeyBuilder::build
+/// [`GrabKey` request]: GrabKey
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct GrabKeyBuilder {
+	owner_events: bool,
+	grab_window: Window,
+
+	modifiers: AnyModifierKeyMask,
+	key: Any<Keycode>,
+
+	cursor_freeze: FreezeMode,
+	keyboard_freeze: FreezeMode,
+}
+
+impl GrabKeyBuilder {
+	/// Creates a new `GrabKeyBuilder` for the given `key` on the given
+	/// `grab_window`.
+	///
+	/// See [`GrabKeyBuilder`] for the options' initial values.
+	#[must_use]
+	pub const fn new(grab_window: Window, key: Any<Keycode>) -> Self {
+		Self {
+			owner_events: false,
+			grab_window,
+
+			modifiers: AnyModifierKeyMask::empty(),
+			key,
+
+			cursor_freeze: FreezeMode::Unfrozen,
+			keyboard_freeze: FreezeMode::Unfrozen,
+		}
+	}
+
+	/// Constructs the resulting [`GrabKey` request] with the configured
+	/// options.
+	///
+	/// [`GrabKey` request]: GrabKey
+	#[must_use]
+	pub const fn build(&self) -> GrabKey {
+		GrabKey {
+			owner_events: self.owner_events,
+			grab_window: self.grab_window,
+
+			modifiers: self.modifiers,
+			key: self.key,
+
+			cursor_freeze: self.cursor_freeze,
+			keyboard_freeze: self.keyboard_freeze,
+		}
+	}
+}
+
+impl GrabKeyBuilder {
+	/// Configures whether key [events] which would normally be reported to
+	/// this client are reported normally.
+	///
+	/// See [`GrabKey::owner_events`] for more information.
+	///
+	/// [events]: crate::message::Event
+	pub const fn owner_events(&mut self, owner_events: bool) -> &mut Self {
+		self.owner_events = owner_events;
+
+		self
+	}
+
+	/// Configures the combination of modifiers which must be held for the grab
+	/// to be activated.
+	///
+	/// See [`GrabKey::modifiers`] for more information.
+	pub const fn modifiers(&mut self, modifiers: AnyModifierKeyMask) -> &mut Self {
+		self.modifiers = modifiers;
+
+		self
+	}
+
+	/// Configures whether cursor [event] processing is frozen once the grab is
+	/// activated.
+	///
+	/// See [`GrabKey::cursor_freeze`] for more information.
+	///
+	/// [event]: crate::message::Event
+	pub const fn cursor_freeze(&mut self, cursor_freeze: FreezeMode) -> &mut Self {
+		self.cursor_freeze = cursor_freeze;
+
+		self
+	}
+
+	/// Configures whether keyboard [event] processing is frozen once the grab
+	/// is activated.
+	///
+	/// See [`GrabKey::keyboard_freeze`] for more information.
+	///
+	/// [event]: crate::message::Event
+	pub const fn keyboard_freeze(&mut self, keyboard_freeze: FreezeMode) -> &mut Self {
+		self.keyboard_freeze = keyboard_freeze;
+
+		self
+	}
+}
+
 request_error! {
 	pub enum UngrabKeyError for UngrabKey {
 		Value,
@@ -785,6 +1441,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct UngrabKey: Request(34, UngrabKeyError) {
 		/// The key which the [passive key grab] was established for.
 		///
@@ -835,9 +1492,11 @@ derive_xrb! {
 ///
 /// [`AllowEvents` request]: AllowEvents
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AllowEventsMode {
 	/// Unfreezes the cursor if it is frozen and you have active grab on the
 	/// cursor.
+	#[doc(alias = "AsyncPointer")]
 	UnfreezeCursor,
 	/// Unfreezes the cursor, but freezes it again after the next
 	/// [`ButtonPress`] or [`ButtonRelease`].
@@ -850,6 +1509,7 @@ pub enum AllowEventsMode {
 	///
 	/// [`ButtonPress`]: crate::x11::event::ButtonPress
 	/// [`ButtonRelease`]: crate::x11::event::ButtonRelease
+	#[doc(alias = "SyncPointer")]
 	RefreezeCursor,
 	/// If the cursor is frozen as a result of the activation of a passive grab
 	/// or [`RefreezeCursor`] mode from your client, the grab is released and
@@ -858,13 +1518,15 @@ pub enum AllowEventsMode {
 	/// [`RefreezeCursor`]: AllowEventsMode::RefreezeCursor
 	///
 	/// [event]: crate::message::Event
+	#[doc(alias = "ReplayPointer")]
 	ReplayCursor,
 
 	/// Unfreezes the keyboard if it is frozen and you have an active grab on
 	/// the keyboard.
+	#[doc(alias = "AsyncKeyboard")]
 	UnfreezeKeyboard,
 	/// Unfreezes the keyboard, but freezes it again after the next
-	/// [`KeyPress`] or [`KeyPress`].
+	/// [`KeyPress`] or [`KeyRelease`].
 	///
 	/// Your client must have an active grab on the keyboard.
 	///
@@ -874,6 +1536,7 @@ pub enum AllowEventsMode {
 	///
 	/// [`KeyPress`]: crate::x11::event::KeyPress
 	/// [`KeyRelease`]: crate::x11::event::KeyRelease
+	#[doc(alias = "SyncKeyboard")]
 	RefreezeKeyboard,
 	/// If the keyboard is frozen as a result of the activation of a passive
 	/// grab or [`RefreezeKeyboard`] mode from your client, the grab is released
@@ -886,6 +1549,7 @@ pub enum AllowEventsMode {
 
 	/// If both the cursor and the keyboard are frozen by your client, both are
 	/// unfrozen.
+	#[doc(alias = "AsyncBoth")]
 	UnfreezeBoth,
 	/// If both the cursor and the keyboard are frozen by your client, both are
 	/// unfrozen but are both frozen again on the next button or key press or
@@ -900,6 +1564,7 @@ pub enum AllowEventsMode {
 	///
 	/// [`KeyPress`]: crate::x11::event::KeyPress
 	/// [`KeyRelease`]: crate::x11::event::KeyRelease
+	#[doc(alias = "SyncBoth")]
 	RefreezeBoth,
 }
 
@@ -910,6 +1575,7 @@ derive_xrb! {
 	/// [frozen]: FreezeMode::Frozen
 	/// [request]: Request
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct AllowEvents: Request(35, error::Value) {
 		/// The conditions under which the queued [events] are released.
 		///
@@ -935,6 +1601,7 @@ derive_xrb! {
 	///
 	/// [request]: Request
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GrabServer: Request(36);
 
 	/// A [request] that unfreezes processing of [requests][request] and
@@ -942,6 +1609,7 @@ derive_xrb! {
 	///
 	/// [request]: Request
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct UngrabServer: Request(37);
 
 	/// A [request] that gets the current location of the cursor.
@@ -954,8 +1622,9 @@ derive_xrb! {
 	/// [request]: Request
 	///
 	/// [`Window` error]: error::Window
-	#[doc(alias("QueryPointer, QueryCursor, GetCursorPos, GetCursorLocation"))]
+	#[doc(alias("QueryPointer", "QueryCursor", "GetCursorPos", "GetCursorLocation"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct QueryCursorLocation: Request(38, error::Window) -> reply::QueryCursorLocation {
 		/// Specifies a [window] to receive relative coordinates of the cursor
 		/// in relation to, if the cursor is on the same screen.
@@ -986,6 +1655,7 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	#[doc(alias = "GetMotionEvents")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GetMotionHistory: Request(39, error::Window) -> reply::GetMotionHistory {
 		/// The [window] for which the motion history is returned.
 		///
@@ -1023,6 +1693,7 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	#[doc(alias = "TranslateCoordinates")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ConvertCoordinates: Request(40, error::Window) -> reply::ConvertCoordinates {
 		/// The [window] which the `original_coords` are relative to.
 		///
@@ -1068,6 +1739,7 @@ derive_xrb! {
 ///
 /// [`WarpCursor` request]: WarpCursor
 #[derive(Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WarpSourceDimension {
 	/// Set the `source_width` to the width of the `source` [window] minus the x
 	/// coordinate or the `source_height` to the height of the `source` [window]
@@ -1094,7 +1766,7 @@ impl Readable for WarpSourceDimension {
 	where
 		Self: Sized,
 	{
-		Ok(match buf.get_u16() {
+		Ok(match u16::read_from(buf)? {
 			zero if zero == 0 => Self::FillRemaining,
 			other => Self::Other(other),
 		})
@@ -1125,6 +1797,7 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	#[doc(alias = "WarpPointer")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct WarpCursor: Request(41, error::Window) {
 		/// The [window] which the cursor is being warped from.
 		///
@@ -1185,6 +1858,44 @@ derive_xrb! {
 	}
 }
 
+impl WarpCursor {
+	/// Creates a `WarpCursor` request which offsets the cursor by `dx` and
+	/// `dy`, wherever it is.
+	///
+	/// The `source` and `destination` are both [`None`].
+	#[must_use]
+	pub const fn relative(dx: Px<i16>, dy: Px<i16>) -> Self {
+		Self {
+			source: None,
+			destination: None,
+
+			source_coords: Coords { x: Px(0), y: Px(0) },
+			source_width: WarpSourceDimension::FillRemaining,
+			source_height: WarpSourceDimension::FillRemaining,
+
+			coords: Coords { x: dx, y: dy },
+		}
+	}
+
+	/// Creates a `WarpCursor` request which moves the cursor to the given
+	/// `coords` relative to the `destination` [window], wherever it is.
+	///
+	/// [window]: Window
+	#[must_use]
+	pub const fn to(destination: Window, coords: Coords) -> Self {
+		Self {
+			source: None,
+			destination: Some(destination),
+
+			source_coords: Coords { x: Px(0), y: Px(0) },
+			source_width: WarpSourceDimension::FillRemaining,
+			source_height: WarpSourceDimension::FillRemaining,
+
+			coords,
+		}
+	}
+}
+
 request_error! {
 	pub enum SetFocusError for SetFocus {
 		Match,
@@ -1201,6 +1912,7 @@ request_error! {
 ///
 /// [`SetFocus` request]: SetFocus
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RevertFocus {
 	/// Revert the focus to no [window].
 	///
@@ -1216,6 +1928,7 @@ pub enum RevertFocus {
 	/// time.
 	///
 	/// [window]: Window
+	#[doc(alias = "PointerRoot")]
 	CursorRoot,
 	/// Revert the focus to the parent of the [window] which the cursor is in at
 	/// the time.
@@ -1246,6 +1959,7 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	#[doc(alias("SetInputFocus", "Focus", "FocusWindow"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct SetFocus: Request(42, SetFocusError) {
 		/// What the focus should revert to if the focused [window] becomes
 		/// unviewable.
@@ -1282,6 +1996,7 @@ derive_xrb! {
 	/// [`GetFocus` reply]: reply::GetFocus
 	#[doc(alias = "GetInputFocus")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GetFocus: Request(43) -> reply::GetFocus;
 
 	/// A [request] that returns a bit vector of the currently held keys on the
@@ -1295,6 +2010,7 @@ derive_xrb! {
 	/// [`QueryKeyboard` reply]: reply::QueryKeyboard
 	#[doc(alias = "QueryKeymap")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct QueryKeyboard: Request(44) -> reply::QueryKeyboard;
 }
 
@@ -1348,6 +2064,7 @@ derive_xrb! {
 ///
 /// [`Value` error]: error::Value
 #[derive(Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChangeKeyboardMapping<const KEYSYMS_PER_KEYCODE: usize> {
 	/// The first [keycode] in the range of [keycodes] that are to have their
 	/// mappings to [keysyms] changed.
@@ -1405,15 +2122,73 @@ pub struct ChangeKeyboardMapping<const KEYSYMS_PER_KEYCODE: usize> {
 	/// [`max_keycode`]: crate::connection::ConnectionSuccess::max_keycode
 	///
 	/// [`Value` error]: error::Value
+	#[cfg_attr(feature = "serde", serde(with = "serde_mappings"))]
 	pub mappings: Vec<[Keysym; KEYSYMS_PER_KEYCODE]>,
 }
 
+/// serde only implements `Serialize` and `Deserialize` for arrays of specific
+/// lengths, so the `mappings` of a [`ChangeKeyboardMapping` request] are
+/// (de)serialized as lists of [keysyms] instead.
+///
+/// [keysyms]: Keysym
+/// [`ChangeKeyboardMapping` request]: ChangeKeyboardMapping
+#[cfg(feature = "serde")]
+mod serde_mappings {
+	use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+	use crate::Keysym;
+
+	pub fn serialize<S: Serializer, const N: usize>(
+		mappings: &[[Keysym; N]], serializer: S,
+	) -> Result<S::Ok, S::Error> {
+		let mappings: Vec<&[Keysym]> = mappings.iter().map(|mapping| &mapping[..]).collect();
+
+		mappings.serialize(serializer)
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+		deserializer: D,
+	) -> Result<Vec<[Keysym; N]>, D::Error> {
+		Vec::<Vec<Keysym>>::deserialize(deserializer)?
+			.into_iter()
+			.map(|mapping| {
+				let len = mapping.len();
+
+				<[Keysym; N]>::try_from(mapping)
+					.map_err(|_| D::Error::invalid_length(len, &"KEYSYMS_PER_KEYCODE keysyms"))
+			})
+			.collect()
+	}
+}
+
 impl<const KEYSYMS_PER_KEYCODE: usize> Request for ChangeKeyboardMapping<KEYSYMS_PER_KEYCODE> {
 	type OtherErrors = error::Value;
 	type Reply = ();
 
 	const MAJOR_OPCODE: u8 = 100;
 	const MINOR_OPCODE: Option<u16> = None;
+
+	#[allow(clippy::cast_possible_truncation)]
+	fn write_with_encoding(&self, buf: &mut impl BufMut, encoding: LengthEncoding) -> WriteResult {
+		// Limit `buf` by the size of the request.
+		let buf = &mut buf.limit(self.x11_size() + encoding.extra_size());
+
+		// The major opcode.
+		Self::MAJOR_OPCODE.write_to(buf)?;
+		// Length of `mappings`.
+		(self.mappings.len() as u8).write_to(buf)?;
+		// The length of the message.
+		self.write_length(buf, encoding)?;
+
+		self.first_keycode.write_to(buf)?;
+		(KEYSYMS_PER_KEYCODE as u8).write_to(buf)?;
+		// 2 unused bytes.
+		buf.put_bytes(0, 2);
+
+		self.mappings.write_to(buf)?;
+
+		Ok(())
+	}
 }
 
 impl<const KEYSYMS_PER_KEYCODE: usize> X11Size for ChangeKeyboardMapping<KEYSYMS_PER_KEYCODE> {
@@ -1436,26 +2211,8 @@ impl<const KEYSYMS_PER_KEYCODE: usize> X11Size for ChangeKeyboardMapping<KEYSYMS
 // compile time.
 
 impl<const KEYSYMS_PER_KEYCODE: usize> Writable for ChangeKeyboardMapping<KEYSYMS_PER_KEYCODE> {
-	#[allow(clippy::cast_possible_truncation)]
 	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		// Limit `buf` by the length (converted to bytes).
-		let buf = &mut buf.limit(usize::from(self.length()) * 4);
-
-		// The major opcode.
-		Self::MAJOR_OPCODE.write_to(buf)?;
-		// Length of `mappings`.
-		(self.mappings.len() as u8).write_to(buf)?;
-		// The length of the message.
-		self.length().write_to(buf)?;
-
-		self.first_keycode.write_to(buf)?;
-		(KEYSYMS_PER_KEYCODE as u8).write_to(buf)?;
-		// 2 unused bytes.
-		buf.put_bytes(0, 2);
-
-		self.mappings.write_to(buf)?;
-
-		Ok(())
+		self.write_with_encoding(buf, LengthEncoding::Standard)
 	}
 }
 
@@ -1503,6 +2260,7 @@ impl<const KEYSYMS_PER_KEYCODE: usize> Writable for ChangeKeyboardMapping<KEYSYM
 ///
 /// [`Value` error]: error::Value
 #[derive(Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GetKeyboardMapping {
 	/// The range of [keycodes] for which this [request] returns their mapped
 	/// [keysyms].
@@ -1559,26 +2317,38 @@ impl X11Size for GetKeyboardMapping {
 
 impl Readable for GetKeyboardMapping {
 	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		Self::read_from_with_mode(buf, ReadMode::Lenient)
+	}
+
+	fn read_from_with_mode(buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Self>
 	where
 		Self: Sized,
 	{
 		const HEADER: usize = 4;
 
 		// Unused metabyte.
-		buf.advance(1);
+		read_padding(buf, 1, mode)?;
 
 		// The message length.
-		let length = usize::from(buf.get_u16()) * 4;
-		let buf = &mut buf.take(length - HEADER);
+		let length = usize::from(u16::read_from(buf)?) * 4;
+		let buf = &mut buf.take(length.saturating_sub(HEADER));
 
-		let first_keycode = Keycode::read_from(buf)?;
-		let keycode_count = buf.get_u8();
-		buf.advance(2);
+		let first_keycode = Keycode::read_from_with_mode(buf, mode)?;
+		let keycode_count = u8::read_from(buf)?;
+		read_padding(buf, 2, mode)?;
 
 		Ok(Self {
 			range: RangeInclusive::new(
 				first_keycode,
-				Keycode::new(first_keycode.unwrap() + keycode_count - 1),
+				Keycode::new(
+					first_keycode
+						.unwrap()
+						.saturating_add(keycode_count)
+						.saturating_sub(1),
+				),
 			),
 		})
 	}
@@ -1590,7 +2360,7 @@ impl Writable for GetKeyboardMapping {
 		// Unused metabyte.
 		buf.put_u8(0);
 		// Message length.
-		self.length().write_to(buf)?;
+		self.checked_length()?.write_to(buf)?;
 
 		// First keycode.
 		self.range.start().write_to(buf)?;
@@ -1621,6 +2391,7 @@ derive_xrb! {
 	/// [options]: KeyboardOptions
 	#[doc(alias("ChangeKeyboardControl"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ChangeKeyboardOptions: Request(102, ChangeKeyboardOptionsError) {
 		/// The changes that are made to the [keyboard options].
 		///
@@ -1642,7 +2413,8 @@ derive_xrb! {
 	///
 	/// [`GetKeyboardOptions` reply]: reply::GetKeyboardOptions
 	#[doc(alias("GetKeyboardControl"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GetKeyboardOptions: Request(103) -> reply::GetKeyboardOptions;
 
 	/// A [request] that rings the bell on the keyboard at the given volume.
@@ -1679,19 +2451,44 @@ derive_xrb! {
 	///
 	/// [`bell_volume`]: KeyboardOptions::bell_volume
 	#[doc(alias("Bell"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct RingBell: Request(104, error::Value) {
 		/// The volume at which the bell is rung relative to the base
 		/// [`bell_volume`].
 		///
 		/// [`bell_volume`]: KeyboardOptions::bell_volume
 		#[doc(alias("percent"))]
+		#[metabyte]
 		pub volume: SignedPercentage,
 	}
 }
 
+impl RingBell {
+	/// Creates a new `RingBell` request with the given `volume` percentage.
+	///
+	/// # Errors
+	/// Returns a [`ValueOutOfBounds`] error if `volume < -100` or
+	/// `volume > 100`.
+	///
+	/// # Examples
+	/// ```
+	/// use xrb::x11::request::RingBell;
+	///
+	/// assert!(RingBell::new(-50).is_ok());
+	/// assert!(RingBell::new(101).is_err());
+	/// ```
+	pub const fn new(volume: i8) -> Result<Self, ValueOutOfBounds<i8>> {
+		match SignedPercentage::new(volume) {
+			Ok(volume) => Ok(Self { volume }),
+			Err(error) => Err(error),
+		}
+	}
+}
+
 /// Represents a type that may be chosen as its default value.
 #[derive(Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OrDefault<T> {
 	/// The default value is chosen.
 	Default,
@@ -1714,7 +2511,7 @@ impl Readable for OrDefault<Px<u8>> {
 	where
 		Self: Sized,
 	{
-		match buf.get_i16() {
+		match i16::read_from(buf)? {
 			default if default == -1 => Ok(Self::Default),
 
 			other => match u8::try_from(other) {
@@ -1740,8 +2537,13 @@ impl Writable for OrDefault<Px<u8>> {
 ///
 /// The denominator may not be zero.
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Fraction<T: X11Size + Readable + Writable>(T, T);
 
+impl<T: ConstantX11Size + Readable + Writable> ConstantX11Size for Fraction<T> {
+	const X11_SIZE: usize = T::X11_SIZE * 2;
+}
+
 impl<T: X11Size + Readable + Writable> Fraction<T> {
 	/// Returns the fraction's numerator.
 	#[must_use]
@@ -1796,33 +2598,129 @@ impl Fraction<Px<u16>> {
 	}
 }
 
-derive_xrb! {
-	/// A [request] that changes the options configured for the cursor.
+/// A [request] that changes the options configured for the cursor.
+///
+/// Options which are [`None`] are left unchanged.
+///
+/// See also: [`GetCursorOptions`], [`ChangeKeyboardOptions`].
+///
+/// [request]: Request
+#[doc(alias("ChangePointerControl", "ChangePointerOptions", "ChangeCursorControl"))]
+#[derive(Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChangeCursorOptions {
+	/// A multiplier applied to the acceleration of the cursor when the
+	/// [`threshold`] is exceeded.
 	///
-	/// See also: [`GetCursorOptions`], [`ChangeKeyboardOptions`].
+	/// If this is [`None`], the acceleration is not changed.
 	///
-	/// [request]: Request
-	#[doc(alias("ChangePointerControl", "ChangePointerOptions", "ChangeCursorControl"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
-	pub struct ChangeCursorOptions: Request(105, error::Value) {
-		/// A multiplier applied to the acceleration of the cursor when the
-		/// [`threshold`] is exceeded.
-		///
-		/// [`threshold`]: ChangeCursorOptions::threshold
-		#[doc(alias("acceleration_numerator", "acceleration_denominator"))]
-		pub acceleration: Fraction<OrDefault<Px<u8>>>,
-		/// The threshold speed which the cursor must exceed for the
-		/// [`acceleration`] multiplier to be applied.
-		///
-		/// [`acceleration`]: ChangeCursorOptions::acceleration
-		pub threshold: OrDefault<Px<u8>>,
+	/// [`threshold`]: ChangeCursorOptions::threshold
+	#[doc(alias("acceleration_numerator", "acceleration_denominator"))]
+	#[doc(alias = "do_acceleration")]
+	pub acceleration: Option<Fraction<OrDefault<Px<u8>>>>,
+	/// The threshold speed which the cursor must exceed for the
+	/// [`acceleration`] multiplier to be applied.
+	///
+	/// If this is [`None`], the threshold is not changed.
+	///
+	/// [`acceleration`]: ChangeCursorOptions::acceleration
+	#[doc(alias = "do_threshold")]
+	pub threshold: Option<OrDefault<Px<u8>>>,
+}
+
+impl Request for ChangeCursorOptions {
+	type OtherErrors = error::Value;
+	type Reply = ();
+
+	const MAJOR_OPCODE: u8 = 105;
+	const MINOR_OPCODE: Option<u16> = None;
+}
+
+impl ConstantX11Size for ChangeCursorOptions {
+	const X11_SIZE: usize = {
+		const HEADER: usize = 4;
+
+		HEADER
+			+ <Fraction<OrDefault<Px<u8>>>>::X11_SIZE
+			+ <OrDefault<Px<u8>>>::X11_SIZE
+			+ bool::X11_SIZE
+			+ bool::X11_SIZE
+	};
+}
+
+impl X11Size for ChangeCursorOptions {
+	fn x11_size(&self) -> usize {
+		Self::X11_SIZE
+	}
+}
+
+impl Readable for ChangeCursorOptions {
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		Self::read_from_with_mode(buf, ReadMode::Lenient)
+	}
+
+	fn read_from_with_mode(buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		// Unused metabyte.
+		read_padding(buf, 1, mode)?;
+		// The message length.
+		let _length = u16::read_from(buf)?;
+
+		// The options are only read if they are to be changed, as they may be
+		// anything otherwise.
+		let acceleration = <[u8; 4]>::read_from(buf)?;
+		let threshold = <[u8; 2]>::read_from(buf)?;
+
+		let do_acceleration = bool::read_from(buf)?;
+		let do_threshold = bool::read_from(buf)?;
+
+		Ok(Self {
+			acceleration: if do_acceleration {
+				Some(Fraction::read_from(&mut &acceleration[..])?)
+			} else {
+				None
+			},
+			threshold: if do_threshold {
+				Some(OrDefault::read_from(&mut &threshold[..])?)
+			} else {
+				None
+			},
+		})
+	}
+}
+
+impl Writable for ChangeCursorOptions {
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		Self::MAJOR_OPCODE.write_to(buf)?;
+		// Unused metabyte.
+		buf.put_u8(0);
+		// The message length.
+		self.checked_length()?.write_to(buf)?;
+
+		match &self.acceleration {
+			Some(acceleration) => acceleration.write_to(buf)?,
+			None => buf.put_bytes(0, <Fraction<OrDefault<Px<u8>>>>::X11_SIZE),
+		}
+		match &self.threshold {
+			Some(threshold) => threshold.write_to(buf)?,
+			None => buf.put_bytes(0, <OrDefault<Px<u8>>>::X11_SIZE),
+		}
+
+		// Whether the acceleration is changed.
+		self.acceleration.is_some().write_to(buf)?;
+		// Whether the threshold is changed.
+		self.threshold.is_some().write_to(buf)?;
 
-		// TODO: undocumented in the core protocol
-		pub do_acceleration: bool,
-		// TODO: undocumented in the core protocol
-		pub do_threshold: bool,
+		Ok(())
 	}
+}
 
+derive_xrb! {
 	/// A [request] that gets the current [cursor options].
 	///
 	/// See also: [`ChangeCursorOptions`].
@@ -1830,7 +2728,8 @@ derive_xrb! {
 	/// [cursor options]: ChangeCursorOptions
 	/// [request]: Request
 	#[doc(alias("GetPointerControl", "GetPointerOptions", "GetCursorControl"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GetCursorOptions: Request(106) -> reply::GetCursorOptions;
 
 	/// A [request] that changes the mapping of the [mouse buttons].
@@ -1868,6 +2767,7 @@ derive_xrb! {
 	/// [`Value` error]: error::Value
 	#[doc(alias("SetPointerMapping", "SetCursorMapping"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct SetButtonMapping: Request(116, error::Value) -> reply::SetButtonMapping {
 		// The length of `mappings`.
 		#[metabyte]
@@ -1912,7 +2812,8 @@ derive_xrb! {
 	///
 	/// [`GetButtonMapping` reply]: reply::GetButtonMapping
 	#[doc(alias("GetPointerMapping", "GetCursorMapping"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GetButtonMapping: Request(117) -> reply::GetButtonMapping;
 }
 
@@ -1955,6 +2856,7 @@ derive_xrb! {
 ///
 /// [`Value` error]: error::Value
 #[derive(Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SetModifierMapping {
 	/// The [keycodes] mapped to the shift modifier.
 	///
@@ -2038,24 +2940,24 @@ impl Readable for SetModifierMapping {
 	{
 		const ALIGNMENT: usize = 4;
 
-		let keycodes_per_modifier = buf.get_u8();
+		let keycodes_per_modifier = u8::read_from(buf)?;
 
-		let total_size = usize::from(buf.get_u16()) * ALIGNMENT;
+		let total_size = usize::from(u16::read_from(buf)?) * ALIGNMENT;
 		let buf = &mut buf.take(total_size);
 
 		let [shift_keycodes, capslock_keycodes, ctrl_keycodes, mod1_keycodes, mod2_keycodes, mod3_keycodes, mod4_keycodes, mod5_keycodes] =
-			array_init(|_| {
+			try_array_init(|_| -> ReadResult<_> {
 				let mut keycodes = vec![];
 
 				for _ in 0..keycodes_per_modifier {
-					match buf.get_u8() {
+					match u8::read_from(buf)? {
 						0 => {},
 						code => keycodes.push(Keycode(code)),
 					}
 				}
 
-				keycodes
-			});
+				Ok(keycodes)
+			})?;
 
 		Ok(Self {
 			shift_keycodes,
@@ -2080,6 +2982,14 @@ impl Writable for SetModifierMapping {
 
 		let buf = &mut buf.limit(HEADER + (8 * keycodes_size));
 
+		// The major opcode.
+		Self::MAJOR_OPCODE.write_to(buf)?;
+		// The number of keycodes per modifier.
+		#[allow(clippy::cast_possible_truncation)]
+		buf.put_u8(max_keycodes_len as u8);
+		// The length of the message.
+		self.checked_length()?.write_to(buf)?;
+
 		// For each keycodes field, we want to make sure that they are written
 		// as the same length as the longest list. Fortunately, that is easy to
 		// do, because (a) the order of each list does not matter, and (b) a `0`
@@ -2128,6 +3038,742 @@ derive_xrb! {
 	/// [request]: Request
 	///
 	/// [`GetModifierMapping` reply]: reply::GetModifierMapping
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GetModifierMapping: Request(119) -> reply::GetModifierMapping;
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		set::{DurationOrDefault, Led, LedMode, PitchOrDefault},
+		unit::Hz,
+		CursorAppearance,
+		Timestamp,
+		ToggleOrDefault,
+	};
+
+	#[test]
+	fn test_ring_bell_volume_is_metabyte() {
+		let mut bytes = vec![];
+		RingBell::new(-50).unwrap().write_to(&mut bytes).unwrap();
+
+		assert_eq!(RingBell::X11_SIZE, 4);
+		assert_eq!(bytes, [104, (-50_i8).to_be_bytes()[0], 0, 1]);
+	}
+
+	#[test]
+	fn test_grab_and_ungrab_server() {
+		let mut bytes = vec![];
+		GrabServer.write_to(&mut bytes).unwrap();
+
+		assert_eq!(GrabServer::X11_SIZE, 4);
+		assert_eq!(bytes, [36, 0, 0, 1]);
+		assert_eq!(GrabServer::read_from(&mut &bytes[1..]).unwrap(), GrabServer);
+
+		let mut bytes = vec![];
+		UngrabServer.write_to(&mut bytes).unwrap();
+
+		assert_eq!(UngrabServer::X11_SIZE, 4);
+		assert_eq!(bytes, [37, 0, 0, 1]);
+		assert_eq!(
+			UngrabServer::read_from(&mut &bytes[1..]).unwrap(),
+			UngrabServer
+		);
+	}
+
+	#[test]
+	fn test_ring_bell_bounds() {
+		assert!(RingBell::new(-100).is_ok());
+		assert!(RingBell::new(100).is_ok());
+
+		assert!(RingBell::new(-101).is_err());
+		assert!(RingBell::new(101).is_err());
+	}
+
+	#[test]
+	fn test_change_keyboard_options() {
+		let mut options = KeyboardOptions::builder();
+		options
+			.bell_pitch(PitchOrDefault::new_pitch(Hz(400)))
+			.bell_duration(DurationOrDefault::new_reset())
+			.led(Led::new(3).unwrap())
+			.led_mode(LedMode::On)
+			.auto_repeat_mode(ToggleOrDefault::Disabled);
+
+		let request = ChangeKeyboardOptions {
+			changed_options: options.build(),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(request.x11_size(), 28);
+		assert_eq!(
+			bytes,
+			[
+				102, 0, 0, 7, // header
+				0, 0, 0, 0xbc, // mask
+				0, 0, 0x01, 0x90, // bell_pitch
+				0xff, 0xff, 0xff, 0xff, // bell_duration
+				0, 0, 0, 3, // led
+				0, 0, 0, 1, // led_mode
+				0, 0, 0, 0, // auto_repeat_mode
+			],
+		);
+		assert_eq!(
+			ChangeKeyboardOptions::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_get_motion_history_round_trip() {
+		let request = GetMotionHistory {
+			target: Window::new(0x0060_0001),
+			start: CurrentableTime::Other(Timestamp::new(1_000)),
+			end: CurrentableTime::CurrentTime,
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(
+			bytes,
+			[
+				39, 0, 0, 4, // header
+				0x00, 0x60, 0x00, 0x01, // target
+				0x00, 0x00, 0x03, 0xe8, // start
+				0x00, 0x00, 0x00, 0x00, // end
+			],
+		);
+
+		// The major opcode is read before the request itself.
+		assert_eq!(
+			GetMotionHistory::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_grab_cursor() {
+		let request = GrabCursor {
+			owner_events: true,
+			grab_window: Window::new(0x0060_0001),
+			event_mask: CursorEventMask::BUTTON_PRESS | CursorEventMask::BUTTON_RELEASE,
+			cursor_freeze: FreezeMode::Unfrozen,
+			keyboard_freeze: FreezeMode::Frozen,
+			confine_to: Some(Window::new(0x0000_0512)),
+			cursor_appearance: None,
+			time: CurrentableTime::Other(Timestamp::new(1_000)),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(GrabCursor::X11_SIZE, 24);
+		assert_eq!(
+			bytes,
+			[
+				26, 1, 0, 6, // header
+				0x00, 0x60, 0x00, 0x01, // grab_window
+				0x00, 0x0c, // event_mask
+				1,    // cursor_freeze: asynchronous
+				0,    // keyboard_freeze: synchronous
+				0x00, 0x00, 0x05, 0x12, // confine_to
+				0x00, 0x00, 0x00, 0x00, // cursor_appearance
+				0x00, 0x00, 0x03, 0xe8, // time
+			],
+		);
+
+		assert_eq!(GrabCursor::read_from(&mut &bytes[1..]).unwrap(), request);
+
+		let request = GrabCursor {
+			cursor_appearance: Some(CursorAppearance::new(0x0040_0002)),
+			..request
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes[16..20], [0x00, 0x40, 0x00, 0x02]);
+		assert_eq!(GrabCursor::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_ungrab_cursor() {
+		let request = UngrabCursor {
+			time: CurrentableTime::CurrentTime,
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(UngrabCursor::X11_SIZE, 8);
+		assert_eq!(bytes, [27, 0, 0, 2, 0, 0, 0, 0]);
+		assert_eq!(UngrabCursor::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_grab_button_any() {
+		let request = GrabButton {
+			owner_events: false,
+			grab_window: Window::new(0x0060_0001),
+			event_mask: CursorEventMask::BUTTON_PRESS,
+			cursor_freeze: FreezeMode::Unfrozen,
+			keyboard_freeze: FreezeMode::Unfrozen,
+			confine_to: None,
+			cursor_appearance: None,
+			button: Any::Any,
+			modifiers: AnyModifierKeyMask::ANY_MODIFIER,
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(GrabButton::X11_SIZE, 24);
+		assert_eq!(
+			bytes,
+			[
+				28, 0, 0, 6, // header
+				0x00, 0x60, 0x00, 0x01, // grab_window
+				0x00, 0x04, // event_mask
+				1,    // cursor_freeze
+				1,    // keyboard_freeze
+				0x00, 0x00, 0x00, 0x00, // confine_to
+				0x00, 0x00, 0x00, 0x00, // cursor_appearance
+				0,    // button: `Any`
+				0,    // unused
+				0x80, 0x00, // modifiers: `ANY_MODIFIER`
+			],
+		);
+		assert_eq!(GrabButton::read_from(&mut &bytes[1..]).unwrap(), request);
+
+		let request = GrabButton {
+			button: Any::Other(Button::new(3)),
+			modifiers: AnyModifierKeyMask::CONTROL | AnyModifierKeyMask::MOD_1,
+			..request
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes[20..], [3, 0, 0x00, 0x0c]);
+		assert_eq!(GrabButton::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_ungrab_button_any() {
+		let request = UngrabButton {
+			button: Any::Any,
+			grab_window: Window::new(0x0060_0001),
+			modifiers: AnyModifierKeyMask::ANY_MODIFIER,
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(
+			bytes,
+			[29, 0, 0, 3, 0x00, 0x60, 0x00, 0x01, 0x80, 0x00, 0, 0],
+		);
+		assert_eq!(UngrabButton::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_grab_key_any() {
+		let request = GrabKey {
+			owner_events: true,
+			grab_window: Window::new(0x0060_0001),
+			modifiers: AnyModifierKeyMask::ANY_MODIFIER,
+			key: Any::Any,
+			cursor_freeze: FreezeMode::Unfrozen,
+			keyboard_freeze: FreezeMode::Frozen,
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(GrabKey::X11_SIZE, 16);
+		assert_eq!(
+			bytes,
+			[
+				33, 1, 0, 4, // header
+				0x00, 0x60, 0x00, 0x01, // grab_window
+				0x80, 0x00, // modifiers: `ANY_MODIFIER`
+				0,    // key: `Any`
+				1,    // cursor_freeze
+				0,    // keyboard_freeze
+				0, 0, 0, // unused
+			],
+		);
+		assert_eq!(GrabKey::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_grab_key_builder() {
+		let request = GrabKey::builder(Window::new(0x0060_0001), Any::Any)
+			.owner_events(true)
+			.modifiers(AnyModifierKeyMask::ANY_MODIFIER)
+			.keyboard_freeze(FreezeMode::Frozen)
+			.build();
+
+		assert_eq!(
+			request,
+			GrabKey {
+				owner_events: true,
+				grab_window: Window::new(0x0060_0001),
+				modifiers: AnyModifierKeyMask::ANY_MODIFIER,
+				key: Any::Any,
+				cursor_freeze: FreezeMode::Unfrozen,
+				keyboard_freeze: FreezeMode::Frozen,
+			},
+		);
+	}
+
+	#[test]
+	fn test_ungrab_key() {
+		let request = UngrabKey {
+			key: Any::Other(Keycode::new(38)),
+			grab_window: Window::new(0x0060_0001),
+			modifiers: AnyModifierKeyMask::SHIFT | AnyModifierKeyMask::MOD_4,
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(
+			bytes,
+			[34, 38, 0, 3, 0x00, 0x60, 0x00, 0x01, 0x00, 0x41, 0, 0],
+		);
+		assert_eq!(UngrabKey::read_from(&mut &bytes[1..]).unwrap(), request);
+
+		let request = UngrabKey {
+			key: Any::Any,
+			modifiers: AnyModifierKeyMask::ANY_MODIFIER,
+			..request
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes[1], 0);
+		assert_eq!(bytes[8..10], [0x80, 0x00]);
+		assert_eq!(UngrabKey::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_grab_keyboard() {
+		let request = GrabKeyboard {
+			owner_events: true,
+			grab_window: Window::new(0x0060_0001),
+			time: CurrentableTime::CurrentTime,
+			cursor_freeze: FreezeMode::Frozen,
+			keyboard_freeze: FreezeMode::Unfrozen,
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(GrabKeyboard::X11_SIZE, 16);
+		assert_eq!(
+			bytes,
+			[
+				31, 1, 0, 4, // header
+				0x00, 0x60, 0x00, 0x01, // grab_window
+				0x00, 0x00, 0x00, 0x00, // time
+				0,    // cursor_freeze
+				1,    // keyboard_freeze
+				0, 0, // unused
+			],
+		);
+		assert_eq!(GrabKeyboard::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_grab_keyboard_builder() {
+		let request = GrabKeyboard::builder(Window::new(0x0060_0001))
+			.owner_events(true)
+			.cursor_freeze(FreezeMode::Frozen)
+			.build();
+
+		assert_eq!(
+			request,
+			GrabKeyboard {
+				owner_events: true,
+				grab_window: Window::new(0x0060_0001),
+				time: CurrentableTime::CurrentTime,
+				cursor_freeze: FreezeMode::Frozen,
+				keyboard_freeze: FreezeMode::Unfrozen,
+			},
+		);
+	}
+
+	#[test]
+	fn test_ungrab_keyboard() {
+		let request = UngrabKeyboard {
+			time: CurrentableTime::Other(Timestamp::new(1_000)),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(UngrabKeyboard::X11_SIZE, 8);
+		assert_eq!(bytes, [32, 0, 0, 2, 0x00, 0x00, 0x03, 0xe8]);
+		assert_eq!(
+			UngrabKeyboard::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_allow_events_modes() {
+		for (discrim, mode) in [
+			(0, AllowEventsMode::UnfreezeCursor),
+			(1, AllowEventsMode::RefreezeCursor),
+			(2, AllowEventsMode::ReplayCursor),
+			(3, AllowEventsMode::UnfreezeKeyboard),
+			(4, AllowEventsMode::RefreezeKeyboard),
+			(5, AllowEventsMode::ReplayKeyboard),
+			(6, AllowEventsMode::UnfreezeBoth),
+			(7, AllowEventsMode::RefreezeBoth),
+		] {
+			let request = AllowEvents {
+				mode,
+				time: CurrentableTime::CurrentTime,
+			};
+
+			let mut bytes = vec![];
+			request.write_to(&mut bytes).unwrap();
+
+			assert_eq!(bytes, [35, discrim, 0, 2, 0, 0, 0, 0]);
+			assert_eq!(AllowEvents::read_from(&mut &bytes[1..]).unwrap(), request);
+		}
+
+		let bytes = [35, 8, 0, 2, 0, 0, 0, 0];
+		assert!(matches!(
+			AllowEvents::read_from(&mut &bytes[1..]),
+			Err(ReadError::UnrecognizedDiscriminant(8)),
+		));
+	}
+
+	#[test]
+	fn test_set_focus() {
+		for (revert_to, new_focus, discrim, focus) in [
+			(RevertFocus::None, FocusWindow::None, 0, [0, 0, 0, 0]),
+			(
+				RevertFocus::CursorRoot,
+				FocusWindow::CursorRoot,
+				1,
+				[0, 0, 0, 1],
+			),
+			(
+				RevertFocus::Parent,
+				FocusWindow::Other(Window::new(0x0060_0001)),
+				2,
+				[0x00, 0x60, 0x00, 0x01],
+			),
+		] {
+			let request = SetFocus {
+				revert_to,
+				new_focus,
+				time: CurrentableTime::CurrentTime,
+			};
+
+			let mut bytes = vec![];
+			request.write_to(&mut bytes).unwrap();
+
+			assert_eq!(SetFocus::X11_SIZE, 12);
+			assert_eq!(bytes[..4], [42, discrim, 0, 3]);
+			assert_eq!(bytes[4..8], focus);
+			assert_eq!(bytes[8..], [0, 0, 0, 0]);
+			assert_eq!(SetFocus::read_from(&mut &bytes[1..]).unwrap(), request);
+		}
+	}
+
+	#[test]
+	fn test_get_focus() {
+		let mut bytes = vec![];
+		GetFocus.write_to(&mut bytes).unwrap();
+
+		assert_eq!(GetFocus::X11_SIZE, 4);
+		assert_eq!(bytes, [43, 0, 0, 1]);
+	}
+
+	#[test]
+	fn test_query_cursor_location() {
+		let request = QueryCursorLocation {
+			target: Window::new(0x0060_0001),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(QueryCursorLocation::X11_SIZE, 8);
+		assert_eq!(bytes, [38, 0, 0, 2, 0x00, 0x60, 0x00, 0x01]);
+		assert_eq!(
+			QueryCursorLocation::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_convert_coordinates() {
+		let request = ConvertCoordinates {
+			original: Window::new(0x0060_0001),
+			output: Window::new(0x0000_0512),
+			original_coords: Coords::new(Px(-3), Px(40)),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(ConvertCoordinates::X11_SIZE, 16);
+		assert_eq!(
+			bytes,
+			[
+				40, 0, 0, 4, // header
+				0x00, 0x60, 0x00, 0x01, // original
+				0x00, 0x00, 0x05, 0x12, // output
+				0xff, 0xfd, 0x00, 0x28, // original_coords
+			],
+		);
+		assert_eq!(
+			ConvertCoordinates::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_warp_cursor_relative() {
+		let request = WarpCursor::relative(Px(-5), Px(10));
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(WarpCursor::X11_SIZE, 24);
+		assert_eq!(
+			bytes,
+			[
+				41, 0, 0, 6, // header
+				0, 0, 0, 0, // source
+				0, 0, 0, 0, // destination
+				0, 0, 0, 0, // source_coords
+				0, 0, 0, 0, // source_width, source_height
+				0xff, 0xfb, 0x00, 0x0a, // coords
+			],
+		);
+		assert_eq!(WarpCursor::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_warp_cursor_to() {
+		let request = WarpCursor::to(Window::new(0x0060_0001), Coords::new(Px(20), Px(30)));
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(
+			bytes,
+			[
+				41, 0, 0, 6, // header
+				0, 0, 0, 0, // source
+				0x00, 0x60, 0x00, 0x01, // destination
+				0, 0, 0, 0, // source_coords
+				0, 0, 0, 0, // source_width, source_height
+				0x00, 0x14, 0x00, 0x1e, // coords
+			],
+		);
+		assert_eq!(WarpCursor::read_from(&mut &bytes[1..]).unwrap(), request);
+
+		let request = WarpCursor {
+			source: Some(Window::new(0x0000_0512)),
+			source_coords: Coords::new(Px(1), Px(2)),
+			source_width: WarpSourceDimension::Other(100),
+			source_height: WarpSourceDimension::Other(50),
+			..request
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes[4..8], [0x00, 0x00, 0x05, 0x12]);
+		assert_eq!(bytes[12..20], [0, 1, 0, 2, 0, 100, 0, 50]);
+		assert_eq!(WarpCursor::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_change_keyboard_mapping() {
+		let request = ChangeKeyboardMapping::<4> {
+			first_keycode: Keycode::new(8),
+			mappings: (0..8)
+				.map(|keycode| [0, 1, 2, 3].map(|i| Keysym::new(keycode * 0x100 + i)))
+				.collect(),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(request.x11_size(), 8 + 8 * 4 * 4);
+		assert_eq!(bytes.len(), request.x11_size());
+		assert_eq!(
+			bytes[..8],
+			[
+				100, 8, 0, 34, // header
+				8, 4, 0, 0, // first_keycode, keysyms_per_keycode
+			],
+		);
+		// The second keysym of the last keycode.
+		assert_eq!(bytes[8 + 7 * 16 + 4..8 + 7 * 16 + 8], [0, 0, 0x07, 0x01]);
+	}
+
+	#[test]
+	fn test_get_keyboard_mapping() {
+		let request = GetKeyboardMapping {
+			range: Keycode::new(8)..=Keycode::new(15),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(GetKeyboardMapping::X11_SIZE, 8);
+		assert_eq!(bytes, [101, 0, 0, 2, 8, 8, 0, 0]);
+		assert_eq!(
+			GetKeyboardMapping::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_set_modifier_mapping() {
+		let request = SetModifierMapping {
+			shift_keycodes: vec![Keycode::new(50), Keycode::new(62)],
+			capslock_keycodes: vec![Keycode::new(66)],
+			ctrl_keycodes: vec![Keycode::new(37)],
+
+			mod1_keycodes: vec![Keycode::new(64)],
+			mod2_keycodes: vec![],
+			mod3_keycodes: vec![],
+			mod4_keycodes: vec![Keycode::new(133)],
+			mod5_keycodes: vec![],
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(request.x11_size(), 20);
+		assert_eq!(
+			bytes,
+			[
+				118, 2, 0, 5, // header
+				50, 62, 66, 0, // shift, lock
+				37, 0, 64, 0, // control, mod1
+				0, 0, 0, 0, // mod2, mod3
+				133, 0, 0, 0, // mod4, mod5
+			],
+		);
+		assert_eq!(
+			SetModifierMapping::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_change_cursor_options() {
+		let request = ChangeCursorOptions {
+			acceleration: Some(
+				Fraction::<OrDefault<Px<u8>>>::new(OrDefault::Other(Px(3)), OrDefault::Default)
+					.unwrap(),
+			),
+			threshold: Some(OrDefault::Other(Px(4))),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(ChangeCursorOptions::X11_SIZE, 12);
+		assert_eq!(
+			bytes,
+			[
+				105, 0, 0, 3, // header
+				0x00, 0x03, 0xff, 0xff, // acceleration
+				0x00, 0x04, // threshold
+				1, 1, // do_acceleration, do_threshold
+			],
+		);
+		assert_eq!(
+			ChangeCursorOptions::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_change_cursor_options_unchanged() {
+		let request = ChangeCursorOptions {
+			acceleration: None,
+			threshold: Some(OrDefault::Default),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes, [105, 0, 0, 3, 0, 0, 0, 0, 0xff, 0xff, 0, 1]);
+		assert_eq!(
+			ChangeCursorOptions::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+
+		// Options which are not changed are not read, even if they would
+		// otherwise be invalid.
+		let bytes = [105, 0, 0, 3, 0x01, 0x00, 0, 0, 0x7f, 0xff, 0, 0];
+		assert_eq!(
+			ChangeCursorOptions::read_from(&mut &bytes[1..]).unwrap(),
+			ChangeCursorOptions {
+				acceleration: None,
+				threshold: None,
+			},
+		);
+	}
+
+	#[test]
+	fn test_set_button_mapping() {
+		let request = SetButtonMapping {
+			mappings: vec![
+				Some(Button::new(3)),
+				Some(Button::new(2)),
+				Some(Button::new(1)),
+				None,
+				Some(Button::new(5)),
+			],
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(request.x11_size(), 12);
+		assert_eq!(
+			bytes,
+			[
+				116, 5, 0, 3, // header
+				3, 2, 1, 0, // mappings
+				5, 0, 0, 0, // mappings, padding
+			],
+		);
+		assert_eq!(
+			SetButtonMapping::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_get_cursor_options_and_button_mapping() {
+		let mut bytes = vec![];
+		GetCursorOptions.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes, [106, 0, 0, 1]);
+
+		let mut bytes = vec![];
+		GetButtonMapping.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes, [117, 0, 0, 1]);
+	}
+}
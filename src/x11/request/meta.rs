@@ -12,16 +12,20 @@
 
 extern crate self as xrb;
 
-use std::convert::Infallible;
+use std::{convert::Infallible, time::Duration};
+use thiserror::Error;
 use xrbk::{
 	pad,
+	read_padding,
 	Buf,
 	BufMut,
 	ConstantX11Size,
 	ReadError::FailedConversion,
+	ReadMode,
 	ReadResult,
 	Readable,
 	Writable,
+	WriteError,
 	WriteResult,
 	X11Size,
 };
@@ -29,8 +33,8 @@ use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
 
 use crate::{
 	message::Request,
-	unit::Sec,
-	x11::{error, reply},
+	unit::{Sec, ValueOutOfBounds},
+	x11::{error, event::Destroy, reply},
 	Host,
 	KillClientTarget,
 	String8,
@@ -67,6 +71,18 @@ macro_rules! request_error {
 				$Error(error::$Error)
 			),+)?
 		}
+
+		impl TryFrom<crate::x11::AnyError> for $Name {
+			type Error = crate::x11::AnyError;
+
+			fn try_from(error: crate::x11::AnyError) -> Result<Self, Self::Error> {
+				match error {
+					$($(crate::x11::AnyError::$Error(error) => Ok(Self::$Error(error)),)+)?
+
+					other => Err(other),
+				}
+			}
+		}
 	};
 }
 
@@ -80,6 +96,7 @@ request_error! {
 
 /// Whether something is added or removed.
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AddOrRemove {
 	/// The thing is added.
 	Add,
@@ -115,6 +132,7 @@ derive_xrb! {
 	/// [reparented]: super::ReparentWindow
 	#[doc(alias = "ChangeSaveSet")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ChangeSavedWindows: Request(6, ChangeSavedWindowsError) {
 		#[metabyte]
 		/// Whether the `window` is added to or removed from your saved
@@ -150,6 +168,7 @@ derive_xrb! {
 	///
 	/// [`QueryExtension` reply]: reply::QueryExtension
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct QueryExtension: Request(98) -> reply::QueryExtension {
 		// Length of `name`.
 		#[allow(clippy::cast_possible_truncation)]
@@ -174,14 +193,119 @@ derive_xrb! {
 	///
 	/// [`ListExtensions` reply]: reply::ListExtensions
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ListExtensions: Request(99) -> reply::ListExtensions;
 }
 
+/// Keeps track of the [windows] which you have added to your saved [windows].
+///
+/// A `SaveSet` only returns a [`ChangeSavedWindows` request] when it would
+/// change your saved [windows], so [windows] are never added twice or removed
+/// without having been added.
+///
+/// [windows]: Window
+/// [`ChangeSavedWindows` request]: ChangeSavedWindows
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct SaveSet {
+	/// The saved windows, in the order in which they were added.
+	windows: Vec<Window>,
+}
+
+impl SaveSet {
+	/// Creates a new, empty `SaveSet`.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			windows: Vec::new(),
+		}
+	}
+
+	/// Returns whether the given `window` is saved.
+	#[must_use]
+	pub fn contains(&self, window: Window) -> bool {
+		self.windows.contains(&window)
+	}
+
+	/// Returns whether there are no saved [windows].
+	///
+	/// [windows]: Window
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.windows.is_empty()
+	}
+
+	/// Adds the given `window` to the saved [windows].
+	///
+	/// Returns the [`ChangeSavedWindows` request] to send if the `window` was
+	/// not already saved.
+	///
+	/// [windows]: Window
+	/// [`ChangeSavedWindows` request]: ChangeSavedWindows
+	pub fn add(&mut self, window: Window) -> Option<ChangeSavedWindows> {
+		if self.contains(window) {
+			return None;
+		}
+
+		self.windows.push(window);
+
+		Some(ChangeSavedWindows {
+			change_mode: AddOrRemove::Add,
+			window,
+		})
+	}
+
+	/// Removes the given `window` from the saved [windows].
+	///
+	/// Returns the [`ChangeSavedWindows` request] to send if the `window` was
+	/// saved.
+	///
+	/// [windows]: Window
+	/// [`ChangeSavedWindows` request]: ChangeSavedWindows
+	pub fn remove(&mut self, window: Window) -> Option<ChangeSavedWindows> {
+		let index = self.windows.iter().position(|saved| *saved == window)?;
+		self.windows.remove(index);
+
+		Some(ChangeSavedWindows {
+			change_mode: AddOrRemove::Remove,
+			window,
+		})
+	}
+
+	/// Removes the `window` of the given [`Destroy` event] from the saved
+	/// [windows].
+	///
+	/// The X server removes destroyed [windows] from your saved [windows]
+	/// itself, so no [request] needs to be sent.
+	///
+	/// [windows]: Window
+	/// [request]: Request
+	/// [`Destroy` event]: Destroy
+	pub fn destroy(&mut self, event: &Destroy) {
+		self.windows.retain(|saved| *saved != event.window);
+	}
+
+	/// Removes every saved [window], returning the [`ChangeSavedWindows`
+	/// requests] to send to do so, in the order that they were added.
+	///
+	/// [window]: Window
+	/// [`ChangeSavedWindows` requests]: ChangeSavedWindows
+	pub fn drain_removals(&mut self) -> Vec<ChangeSavedWindows> {
+		self.windows
+			.drain(..)
+			.map(|window| ChangeSavedWindows {
+				change_mode: AddOrRemove::Remove,
+				window,
+			})
+			.collect()
+	}
+}
+
 /// The delay used for `timeout` and `interval` in the
 /// [`SetScreenSaver` request].
 ///
 /// [`SetScreenSaver` request]: SetScreenSaver
-#[derive(Debug, Hash, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Delay {
 	/// The default option is used.
 	Default,
@@ -189,7 +313,109 @@ pub enum Delay {
 	Disabled,
 
 	/// The option is enabled after the given delay.
-	Enabled(Sec<u8>),
+	///
+	/// The delay must be between one second and [`Delay::MAX_SECONDS`].
+	Enabled(Sec<u16>),
+}
+
+/// An error returned when a [`Duration`] cannot be converted to a [`Delay`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Error)]
+pub enum DelayFromDurationError {
+	/// The [`Duration`] is not a whole number of seconds.
+	#[error("a delay must be a whole number of seconds, found {0:?}")]
+	SubSecond(Duration),
+
+	/// The [`Duration`] is zero seconds - which would be interpreted as
+	/// [`Disabled`] - or longer than [`Delay::MAX_SECONDS`].
+	///
+	/// [`Disabled`]: Delay::Disabled
+	#[error(transparent)]
+	OutOfBounds(#[from] ValueOutOfBounds<u64>),
+}
+
+impl Delay {
+	/// The longest delay that can be [`Enabled`], in seconds.
+	///
+	/// Delays are encoded as an `i16`, so this is [`i16::MAX`].
+	///
+	/// [`Enabled`]: Delay::Enabled
+	pub const MAX_SECONDS: u16 = i16::MAX.unsigned_abs();
+
+	/// Creates an [`Enabled`] delay of the given number of `seconds`.
+	///
+	/// # Errors
+	/// Returns a [`ValueOutOfBounds`] error if `seconds` is zero - which would
+	/// be interpreted as [`Disabled`] - or greater than [`MAX_SECONDS`].
+	///
+	/// [`Enabled`]: Delay::Enabled
+	/// [`Disabled`]: Delay::Disabled
+	/// [`MAX_SECONDS`]: Delay::MAX_SECONDS
+	pub const fn enabled(seconds: u16) -> Result<Self, ValueOutOfBounds<u16>> {
+		match seconds {
+			1..=Self::MAX_SECONDS => Ok(Self::Enabled(Sec(seconds))),
+
+			_ => Err(ValueOutOfBounds {
+				min: 1,
+				max: Self::MAX_SECONDS,
+				found: seconds,
+			}),
+		}
+	}
+
+	/// Returns whether this is an [`Enabled`] delay.
+	///
+	/// [`Enabled`]: Delay::Enabled
+	#[must_use]
+	pub const fn is_enabled(&self) -> bool {
+		matches!(self, Self::Enabled(_))
+	}
+}
+
+impl TryFrom<Duration> for Delay {
+	type Error = DelayFromDurationError;
+
+	/// Converts a [`Duration`] to an [`Enabled`] delay.
+	///
+	/// # Errors
+	/// Returns [`DelayFromDurationError::SubSecond`] if `duration` is not a
+	/// whole number of seconds, and [`DelayFromDurationError::OutOfBounds`] if
+	/// it is zero or longer than [`Delay::MAX_SECONDS`].
+	///
+	/// [`Enabled`]: Delay::Enabled
+	fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+		if duration.subsec_nanos() != 0 {
+			return Err(DelayFromDurationError::SubSecond(duration));
+		}
+
+		let seconds = duration.as_secs();
+
+		match u16::try_from(seconds).map(Self::enabled) {
+			Ok(Ok(delay)) => Ok(delay),
+
+			_ => Err(DelayFromDurationError::OutOfBounds(ValueOutOfBounds {
+				min: 1,
+				max: Self::MAX_SECONDS.into(),
+				found: seconds,
+			})),
+		}
+	}
+}
+
+impl From<Delay> for Option<Duration> {
+	/// Returns the [`Duration`] of an [`Enabled`] delay.
+	///
+	/// [`Default`] and [`Disabled`] delays have no [`Duration`].
+	///
+	/// [`Enabled`]: Delay::Enabled
+	/// [`Default`]: Delay::Default
+	/// [`Disabled`]: Delay::Disabled
+	fn from(delay: Delay) -> Self {
+		match delay {
+			Delay::Enabled(Sec(seconds)) => Some(Duration::from_secs(seconds.into())),
+
+			Delay::Default | Delay::Disabled => None,
+		}
+	}
 }
 
 impl ConstantX11Size for Delay {
@@ -207,11 +433,11 @@ impl Readable for Delay {
 	where
 		Self: Sized,
 	{
-		match buf.get_i16() {
+		match i16::read_from(buf)? {
 			-1 => Ok(Self::Default),
 			0 => Ok(Self::Disabled),
 
-			other => match u8::try_from(other) {
+			other => match u16::try_from(other) {
 				Ok(sec) => Ok(Self::Enabled(Sec(sec))),
 				Err(error) => Err(FailedConversion(Box::new(error))),
 			},
@@ -225,7 +451,10 @@ impl Writable for Delay {
 			Self::Default => buf.put_i16(-1),
 			Self::Disabled => buf.put_i16(0),
 
-			Self::Enabled(Sec(sec)) => i16::from(*sec).write_to(buf)?,
+			Self::Enabled(Sec(sec)) => match i16::try_from(*sec) {
+				Ok(sec) => sec.write_to(buf)?,
+				Err(error) => return Err(WriteError::FailedConversion(Box::new(error))),
+			},
 		}
 
 		Ok(())
@@ -235,6 +464,8 @@ impl Writable for Delay {
 derive_xrb! {
 	/// A [request] that configures options for the screensaver.
 	///
+	/// A `SetScreenSaver` request is constructed with [`SetScreenSaver::builder`].
+	///
 	/// The screensaver is enabled if [`timeout`] is
 	/// [`Enabled`](Delay::Enabled). When it is enabled, after [`timeout`]
 	/// seconds without any cursor or keyboard input, the screensaver is
@@ -265,6 +496,7 @@ derive_xrb! {
 	///
 	/// [`Expose` events]: crate::x11::event::Expose
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct SetScreenSaver: Request(107, error::Value) {
 		/// Whether the screensaver is [`Enabled`] and, if so, how long without
 		/// input before it is activated.
@@ -300,9 +532,208 @@ derive_xrb! {
 	///
 	/// [`GetScreenSaver` reply]: reply::GetScreenSaver
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GetScreenSaver: Request(108) -> reply::GetScreenSaver;
 }
 
+impl SetScreenSaver {
+	/// Returns a new [`SetScreenSaverBuilder`] with which a `SetScreenSaver`
+	/// request can be created.
+	///
+	/// # Examples
+	/// ```
+	/// use xrb::{
+	///     x11::request::{Delay, SetScreenSaver},
+	///     ToggleOrDefault,
+	/// };
+	///
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// let request = SetScreenSaver::builder()
+	///     .timeout(Delay::enabled(120)?)
+	///     .interval(Delay::Disabled)
+	///     .prefer_blanking(ToggleOrDefault::Enabled)
+	///     .build()?;
+	///
+	/// assert_eq!(request.allow_expose_events, ToggleOrDefault::Default);
+	/// # Ok(())
+	/// # }
+	/// ```
+	#[must_use]
+	pub const fn builder() -> SetScreenSaverBuilder {
+		SetScreenSaverBuilder::new()
+	}
+}
+
+/// A builder used to construct a new [`SetScreenSaver` request].
+///
+/// All options start as their server-chosen defaults, and can be configured
+/// with the methods on this builder. When the builder is configured,
+/// [`build()`] can be used to construct the resulting [`SetScreenSaver`
+/// request].
+///
+/// [`build()`]: SetScreenSaverBuilder::build
+/// [`SetScreenSaver` request]: SetScreenSaver
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SetScreenSaverBuilder {
+	timeout: Delay,
+	interval: Delay,
+
+	prefer_blanking: ToggleOrDefault,
+	allow_expose_events: ToggleOrDefault,
+}
+
+/// An error returned when a [`SetScreenSaverBuilder`] is configured with
+/// invalid options.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Error)]
+pub enum InvalidScreenSaverOptions {
+	/// A `timeout` or `interval` of [`Enabled`] zero seconds was configured.
+	///
+	/// A delay of zero seconds is interpreted as [`Disabled`].
+	///
+	/// [`Enabled`]: Delay::Enabled
+	/// [`Disabled`]: Delay::Disabled
+	#[error("an enabled delay must be at least one second")]
+	ZeroDelay,
+
+	/// A `timeout` or `interval` longer than [`Delay::MAX_SECONDS`] was
+	/// configured.
+	#[error("an enabled delay must be at most {} seconds", Delay::MAX_SECONDS)]
+	DelayTooLong,
+
+	/// An `interval` was [`Enabled`] while the `timeout` is [`Disabled`].
+	///
+	/// The screensaver is never activated if the `timeout` is [`Disabled`], so
+	/// the `interval` would have no effect.
+	///
+	/// [`Enabled`]: Delay::Enabled
+	/// [`Disabled`]: Delay::Disabled
+	#[error("an interval was enabled but the timeout is disabled")]
+	IntervalWithoutTimeout,
+}
+
+impl Default for SetScreenSaverBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl SetScreenSaverBuilder {
+	/// Creates a new `SetScreenSaverBuilder`.
+	///
+	/// All options start as their server-chosen defaults ([`Delay::Default`]
+	/// and [`ToggleOrDefault::Default`]), and can be configured with the other
+	/// methods on this builder. When the builder is configured, [`build()`] can
+	/// be used to build the resulting [`SetScreenSaver` request].
+	///
+	/// [`build()`]: SetScreenSaverBuilder::build
+	/// [`SetScreenSaver` request]: SetScreenSaver
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			timeout: Delay::Default,
+			interval: Delay::Default,
+
+			prefer_blanking: ToggleOrDefault::Default,
+			allow_expose_events: ToggleOrDefault::Default,
+		}
+	}
+
+	/// Constructs the resulting [`SetScreenSaver` request] with the configured
+	/// options.
+	///
+	/// # Errors
+	/// - [`InvalidScreenSaverOptions::ZeroDelay`]: the `timeout` or `interval`
+	///   is [`Delay::Enabled`] with zero seconds.
+	/// - [`InvalidScreenSaverOptions::DelayTooLong`]: the `timeout` or
+	///   `interval` is [`Delay::Enabled`] with more than
+	///   [`Delay::MAX_SECONDS`].
+	/// - [`InvalidScreenSaverOptions::IntervalWithoutTimeout`]: the `interval`
+	///   is [`Delay::Enabled`] while the `timeout` is [`Delay::Disabled`].
+	///
+	/// # Examples
+	/// ```
+	/// use xrb::x11::request::{Delay, InvalidScreenSaverOptions, SetScreenSaver};
+	///
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// let result = SetScreenSaver::builder()
+	///     .timeout(Delay::Disabled)
+	///     .interval(Delay::enabled(5)?)
+	///     .build();
+	///
+	/// assert_eq!(result, Err(InvalidScreenSaverOptions::IntervalWithoutTimeout));
+	/// # Ok(())
+	/// # }
+	/// ```
+	///
+	/// [`SetScreenSaver` request]: SetScreenSaver
+	pub fn build(&self) -> Result<SetScreenSaver, InvalidScreenSaverOptions> {
+		if [self.timeout, self.interval].contains(&Delay::Enabled(Sec(0))) {
+			return Err(InvalidScreenSaverOptions::ZeroDelay);
+		}
+
+		if [self.timeout, self.interval].iter().any(
+			|delay| matches!(delay, Delay::Enabled(Sec(seconds)) if *seconds > Delay::MAX_SECONDS),
+		) {
+			return Err(InvalidScreenSaverOptions::DelayTooLong);
+		}
+
+		if self.timeout == Delay::Disabled && self.interval.is_enabled() {
+			return Err(InvalidScreenSaverOptions::IntervalWithoutTimeout);
+		}
+
+		Ok(SetScreenSaver {
+			timeout: self.timeout,
+			interval: self.interval,
+
+			prefer_blanking: self.prefer_blanking,
+			allow_expose_events: self.allow_expose_events,
+		})
+	}
+}
+
+impl SetScreenSaverBuilder {
+	/// Configures whether the screensaver is enabled and, if so, how long
+	/// without input before it is activated.
+	///
+	/// See [`SetScreenSaver::timeout`] for more information.
+	pub const fn timeout(&mut self, timeout: Delay) -> &mut Self {
+		self.timeout = timeout;
+
+		self
+	}
+
+	/// Configures the interval between periodic changes of the screensaver.
+	///
+	/// See [`SetScreenSaver::interval`] for more information.
+	pub const fn interval(&mut self, interval: Delay) -> &mut Self {
+		self.interval = interval;
+
+		self
+	}
+
+	/// Configures whether it is preferred that displays which support blanking
+	/// go blank when the screensaver is activated.
+	///
+	/// See [`SetScreenSaver::prefer_blanking`] for more information.
+	pub const fn prefer_blanking(&mut self, prefer_blanking: ToggleOrDefault) -> &mut Self {
+		self.prefer_blanking = prefer_blanking;
+
+		self
+	}
+
+	/// Configures whether screensavers which generate [`Expose` events] are
+	/// allowed.
+	///
+	/// See [`SetScreenSaver::allow_expose_events`] for more information.
+	///
+	/// [`Expose` events]: crate::x11::event::Expose
+	pub const fn allow_expose_events(&mut self, allow_expose_events: ToggleOrDefault) -> &mut Self {
+		self.allow_expose_events = allow_expose_events;
+
+		self
+	}
+}
+
 request_error! {
 	pub enum ChangeHostsError for ChangeHosts {
 		Access,
@@ -341,6 +772,7 @@ derive_xrb! {
 	/// [`Access` error]: error::Access
 	#[deprecated(note = "more secure forms of authentication are preferred.")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ChangeHosts: Request(109, ChangeHostsError) {
 		/// Whether the `host` is to be [added] to or [removed] from the access
 		/// control list.
@@ -378,6 +810,7 @@ derive_xrb! {
 	#[doc(alias("ListHosts"))]
 	#[deprecated(note = "more secure forms of authentication are preferred.")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct QueryAccessControl: Request(110) -> reply::QueryAccessControl;
 }
 
@@ -401,6 +834,7 @@ derive_xrb! {
 	/// [disabled]: Toggle::Disabled
 	#[deprecated(note = "more secure forms of authentication are preferred.")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct SetAccessControl: Request(111, SetAccessControlError) {
 		/// Whether access control is [enabled] or [disabled].
 		///
@@ -419,6 +853,7 @@ derive_xrb! {
 /// [`Destroy`]: RetainResourcesMode::Destroy
 #[doc(alias("CloseDownMode"))]
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RetainResourcesMode {
 	/// All of the client's resources are destroyed immediately.
 	///
@@ -464,6 +899,7 @@ derive_xrb! {
 	/// [request]: Request
 	#[doc(alias("SetCloseDownMode"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct SetRetainResourcesMode: Request(112, error::Value) {
 		/// The [`RetainResourcesMode`] set for your client.
 		///
@@ -487,6 +923,7 @@ derive_xrb! {
 	///
 	/// [request]: Request
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct KillClient: Request(113, error::Value) {
 		/// The target of this `KillClient` [request].
 		///
@@ -503,6 +940,7 @@ derive_xrb! {
 /// [resets the activation timer]: ForceScreenSaverMode::Reset
 /// [activates the screensaver]: ForceScreenSaverMode::Activate
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ForceScreenSaverMode {
 	/// If the screensaver is currently [enabled], the activation timer (i.e.
 	/// the time left before its activation) is reset and, if the screensaver is
@@ -531,7 +969,8 @@ derive_xrb! {
 	///
 	/// [reset]: ForceScreenSaverMode::Reset
 	/// [activate]: ForceScreenSaverMode::Activate
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ForceScreenSaver: Request(115, error::Value) {
 		/// Whether the screensaver's [activation timer is reset] or the
 		/// screensaver is [forcibly activated].
@@ -553,14 +992,86 @@ derive_xrb! {
 /// This can be used by X libraries which find it convenient to force
 /// [requests][request] to be aligned to 8 bytes.
 ///
+/// A `NoOp` request of a particular size can be constructed with
+/// [`NoOp::with_size`].
+///
 /// [request]: Request
+#[doc(alias("NoOperation"))]
 #[derive(Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NoOp {
 	/// The number of unused 4-byte units to add to the [request] after the
 	/// initial 4-byte header.
 	pub unused_units: u16,
 }
 
+/// An error returned when a [`NoOp` request] cannot be constructed with the
+/// given size.
+///
+/// [`NoOp` request]: NoOp
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Error)]
+pub enum InvalidNoOpSize {
+	/// The size was not a multiple of 4 bytes.
+	#[error("the size of a `NoOp` request must be a multiple of 4 bytes, found {0}")]
+	Unaligned(usize),
+
+	/// The size was smaller than the 4-byte header or larger than
+	/// [`NoOp::MAX_SIZE`].
+	#[error(transparent)]
+	OutOfBounds(#[from] ValueOutOfBounds<usize>),
+}
+
+impl NoOp {
+	/// The largest size of a `NoOp` request in bytes.
+	///
+	/// This is limited by the request's 2-byte length field, which counts
+	/// 4-byte units.
+	pub const MAX_SIZE: usize = (u16::MAX as usize) * 4;
+
+	/// Creates a new `NoOp` request which is `size` bytes long, including its
+	/// 4-byte header.
+	///
+	/// # Errors
+	/// - [`InvalidNoOpSize::Unaligned`]: `size` is not a multiple of 4.
+	/// - [`InvalidNoOpSize::OutOfBounds`]: `size` is less than 4 or greater
+	///   than [`NoOp::MAX_SIZE`].
+	///
+	/// # Examples
+	/// ```
+	/// use xrb::{message::Request, x11::request::NoOp};
+	///
+	/// let request = NoOp::with_size(32).unwrap();
+	///
+	/// assert_eq!(request.unused_units, 7);
+	/// assert_eq!(request.length(), 8);
+	///
+	/// assert!(NoOp::with_size(30).is_err());
+	/// assert!(NoOp::with_size(0).is_err());
+	/// ```
+	pub fn with_size(size: usize) -> Result<Self, InvalidNoOpSize> {
+		const HEADER: usize = 4;
+		const ALIGNMENT: usize = 4;
+
+		if !(HEADER..=Self::MAX_SIZE).contains(&size) {
+			return Err(InvalidNoOpSize::OutOfBounds(ValueOutOfBounds {
+				min: HEADER,
+				max: Self::MAX_SIZE,
+				found: size,
+			}));
+		}
+
+		if !size.is_multiple_of(ALIGNMENT) {
+			return Err(InvalidNoOpSize::Unaligned(size));
+		}
+
+		// `size` is at most `NoOp::MAX_SIZE`, so this cannot truncate.
+		#[allow(clippy::cast_possible_truncation)]
+		let unused_units = ((size - HEADER) / ALIGNMENT) as u16;
+
+		Ok(Self { unused_units })
+	}
+}
+
 impl Request for NoOp {
 	type OtherErrors = Infallible;
 	type Reply = ();
@@ -580,17 +1091,21 @@ impl X11Size for NoOp {
 
 impl Readable for NoOp {
 	fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
+		Self::read_from_with_mode(buf, ReadMode::Lenient)
+	}
+
+	fn read_from_with_mode(buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Self> {
 		const ALIGNMENT: usize = 4;
 
 		// Unused metabyte.
-		buf.advance(1);
+		read_padding(buf, 1, mode)?;
 
 		// One unit is subtracted for the header.
-		let unused_units = buf.get_u16() - 1;
+		let unused_units = u16::read_from(buf)?.saturating_sub(1);
 
 		let buf = &mut buf.take(usize::from(unused_units) * ALIGNMENT);
 		// Unused bytes.
-		buf.advance(buf.remaining());
+		read_padding(buf, buf.remaining(), mode)?;
 
 		Ok(Self { unused_units })
 	}
@@ -606,7 +1121,7 @@ impl Writable for NoOp {
 		// Unused metabyte.
 		buf.put_u8(0);
 		// Message length.
-		self.length().write_to(buf)?;
+		self.checked_length()?.write_to(buf)?;
 
 		// Unused bytes.
 		buf.put_bytes(0, usize::from(self.unused_units) * ALIGNMENT);
@@ -614,3 +1129,333 @@ impl Writable for NoOp {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{Char8, HostAddress};
+
+	#[test]
+	fn test_constant_sizes_match_protocol() {
+		assert_eq!(ChangeSavedWindows::X11_SIZE, 8);
+		assert_eq!(ListExtensions::X11_SIZE, 4);
+		assert_eq!(SetScreenSaver::X11_SIZE, 12);
+		assert_eq!(GetScreenSaver::X11_SIZE, 4);
+	}
+
+	#[test]
+	fn test_constant_sizes_match_written() {
+		let mut bytes = vec![];
+		ChangeSavedWindows {
+			change_mode: AddOrRemove::Remove,
+			window: Window::new(0x0040_0001),
+		}
+		.write_to(&mut bytes)
+		.unwrap();
+		assert_eq!(bytes.len(), ChangeSavedWindows::X11_SIZE);
+
+		let mut bytes = vec![];
+		SetScreenSaver::builder()
+			.timeout(Delay::enabled(120).unwrap())
+			.prefer_blanking(ToggleOrDefault::Enabled)
+			.build()
+			.unwrap()
+			.write_to(&mut bytes)
+			.unwrap();
+		assert_eq!(bytes.len(), SetScreenSaver::X11_SIZE);
+	}
+
+	#[test]
+	fn test_delay_from_duration() {
+		let delay = Delay::try_from(Duration::from_secs(300)).unwrap();
+		assert_eq!(delay, Delay::Enabled(Sec(300)));
+		assert_eq!(
+			Option::<Duration>::from(delay),
+			Some(Duration::from_secs(300))
+		);
+
+		let mut bytes = vec![];
+		delay.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes, 300_i16.to_be_bytes());
+
+		assert!(matches!(
+			Delay::try_from(Duration::from_millis(1500)),
+			Err(DelayFromDurationError::SubSecond(_)),
+		));
+		assert!(matches!(
+			Delay::try_from(Duration::ZERO),
+			Err(DelayFromDurationError::OutOfBounds(_)),
+		));
+		assert!(matches!(
+			Delay::try_from(Duration::from_secs(u64::from(Delay::MAX_SECONDS) + 1)),
+			Err(DelayFromDurationError::OutOfBounds(_)),
+		));
+		assert_eq!(Option::<Duration>::from(Delay::Default), None);
+	}
+
+	#[test]
+	fn test_delay_wire_encoding() {
+		for (delay, encoded) in [
+			(Delay::Default, -1),
+			(Delay::Disabled, 0),
+			(Delay::Enabled(Sec(1)), 1),
+			(Delay::Enabled(Sec(Delay::MAX_SECONDS)), i16::MAX),
+		] {
+			let mut bytes = vec![];
+			delay.write_to(&mut bytes).unwrap();
+
+			assert_eq!(bytes, encoded.to_be_bytes());
+			assert_eq!(Delay::read_from(&mut &bytes[..]).unwrap(), delay);
+		}
+
+		// Negative delays other than `-1` are not valid.
+		assert!(Delay::read_from(&mut &(-2_i16).to_be_bytes()[..]).is_err());
+		// Nor can delays longer than `i16::MAX` seconds be written.
+		assert!(Delay::Enabled(Sec(Delay::MAX_SECONDS + 1))
+			.write_to(&mut vec![])
+			.is_err());
+	}
+
+	#[test]
+	fn test_set_screen_saver_reads_long_timeout() {
+		let request = SetScreenSaver::builder()
+			.timeout(Delay::enabled(600).unwrap())
+			.interval(Delay::Disabled)
+			.build()
+			.unwrap();
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(&bytes[4..6], 600_i16.to_be_bytes());
+		assert_eq!(
+			SetScreenSaver::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_query_extension_x11_size_matches_written() {
+		for name in ["", "SHAPE", "RANDR", "XFIXES", "Composite", "MIT-SHM"] {
+			let request = QueryExtension {
+				name: String8::from(name.bytes().map(Char8::new).collect::<Vec<_>>()),
+			};
+
+			let mut bytes = vec![];
+			request.write_to(&mut bytes).unwrap();
+
+			assert_eq!(request.x11_size(), bytes.len(), "{name:?}");
+			assert_eq!(bytes.len() % 4, 0, "{name:?}");
+		}
+	}
+
+	#[test]
+	#[allow(deprecated)]
+	fn test_change_hosts_ipv6() {
+		let request = ChangeHosts {
+			mode: AddOrRemove::Remove,
+			host: Host::new(HostAddress::Ipv6([
+				0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0x02, 0x1a, 0x2b, 0xff, 0xfe, 0x3c, 0x4d, 0x5e,
+			])),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(request.x11_size(), 24);
+		assert_eq!(bytes[..4], [109, 1, 0, 6]);
+		// The family, an unused byte, and the length of the address.
+		assert_eq!(bytes[4..8], [6, 0, 0, 16]);
+		assert_eq!(bytes.len(), 24);
+
+		assert_eq!(ChangeHosts::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	#[allow(deprecated)]
+	fn test_change_hosts_pads_address() {
+		let request = ChangeHosts {
+			mode: AddOrRemove::Add,
+			host: Host::new(HostAddress::DecNet([0x12, 0x34])),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes, [109, 0, 0, 3, 1, 0, 0, 2, 0x12, 0x34, 0, 0]);
+		assert_eq!(ChangeHosts::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	#[allow(deprecated)]
+	fn test_access_control_and_screen_saver_metabytes() {
+		let mut bytes = vec![];
+		SetAccessControl {
+			mode: Toggle::Enabled,
+		}
+		.write_to(&mut bytes)
+		.unwrap();
+		assert_eq!(bytes, [111, 1, 0, 1]);
+
+		let mut bytes = vec![];
+		QueryAccessControl.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes, [110, 0, 0, 1]);
+
+		for (mode, discrim) in [
+			(ForceScreenSaverMode::Reset, 0),
+			(ForceScreenSaverMode::Activate, 1),
+		] {
+			let request = ForceScreenSaver { mode };
+
+			let mut bytes = vec![];
+			request.write_to(&mut bytes).unwrap();
+
+			assert_eq!(bytes, [115, discrim, 0, 1]);
+			assert_eq!(
+				ForceScreenSaver::read_from(&mut &bytes[1..]).unwrap(),
+				request
+			);
+		}
+	}
+
+	#[test]
+	fn test_no_op_with_size() {
+		let request = NoOp::with_size(32).unwrap();
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(request.x11_size(), 32);
+		assert_eq!(bytes.len(), 32);
+		assert_eq!(bytes[..4], [127, 0, 0, 8]);
+		assert!(bytes[4..].iter().all(|byte| *byte == 0));
+
+		assert_eq!(NoOp::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_no_op_with_size_bounds() {
+		assert_eq!(NoOp::with_size(4).unwrap().unused_units, 0);
+		assert_eq!(NoOp::with_size(NoOp::MAX_SIZE).unwrap().length(), u16::MAX);
+
+		assert_eq!(NoOp::with_size(10), Err(InvalidNoOpSize::Unaligned(10)));
+		assert!(matches!(
+			NoOp::with_size(0),
+			Err(InvalidNoOpSize::OutOfBounds(_))
+		));
+		assert!(matches!(
+			NoOp::with_size(NoOp::MAX_SIZE + 4),
+			Err(InvalidNoOpSize::OutOfBounds(_))
+		));
+	}
+
+	#[test]
+	fn test_kill_client() {
+		for (target, resource) in [
+			(KillClientTarget::DestroyTemporarilyRetainedResources, 0),
+			(
+				KillClientTarget::KillClient {
+					resource: 0x0060_0002,
+				},
+				0x0060_0002,
+			),
+		] {
+			let request = KillClient { target };
+
+			let mut bytes = vec![];
+			request.write_to(&mut bytes).unwrap();
+
+			let mut expected = vec![113, 0, 0, 2];
+			expected.extend_from_slice(&u32::to_be_bytes(resource));
+
+			assert_eq!(KillClient::X11_SIZE, 8);
+			assert_eq!(bytes, expected);
+			assert_eq!(KillClient::read_from(&mut &bytes[1..]).unwrap(), request);
+		}
+	}
+
+	#[test]
+	fn test_set_retain_resources_mode() {
+		for (mode, discrim) in [
+			(RetainResourcesMode::Destroy, 0),
+			(RetainResourcesMode::RetainPermanently, 1),
+			(RetainResourcesMode::RetainTemporarily, 2),
+		] {
+			let request = SetRetainResourcesMode { mode };
+
+			let mut bytes = vec![];
+			request.write_to(&mut bytes).unwrap();
+
+			assert_eq!(bytes, [112, discrim, 0, 1]);
+			assert_eq!(
+				SetRetainResourcesMode::read_from(&mut &bytes[1..]).unwrap(),
+				request
+			);
+		}
+	}
+
+	fn destroy(window: u32) -> Destroy {
+		Destroy {
+			sequence: 1,
+			event_window: Window::new(0x0000_0512),
+			window: Window::new(window),
+		}
+	}
+
+	fn change(change_mode: AddOrRemove, window: u32) -> ChangeSavedWindows {
+		ChangeSavedWindows {
+			change_mode,
+			window: Window::new(window),
+		}
+	}
+
+	#[test]
+	fn test_save_set_add_and_remove() {
+		let mut save_set = SaveSet::new();
+
+		assert_eq!(
+			save_set.add(Window::new(1)),
+			Some(change(AddOrRemove::Add, 1))
+		);
+		// Adding a window twice would generate a `Match` error.
+		assert_eq!(save_set.add(Window::new(1)), None);
+		assert!(save_set.contains(Window::new(1)));
+
+		// Removing an unknown window.
+		assert_eq!(save_set.remove(Window::new(2)), None);
+
+		assert_eq!(
+			save_set.remove(Window::new(1)),
+			Some(change(AddOrRemove::Remove, 1)),
+		);
+		assert_eq!(save_set.remove(Window::new(1)), None);
+		assert!(save_set.is_empty());
+	}
+
+	#[test]
+	fn test_save_set_destroy_and_drain() {
+		let mut save_set = SaveSet::new();
+
+		for window in [3, 1, 2] {
+			assert!(save_set.add(Window::new(window)).is_some());
+		}
+
+		// Destroyed windows are removed by the X server itself.
+		save_set.destroy(&destroy(1));
+		assert!(!save_set.contains(Window::new(1)));
+		assert_eq!(save_set.remove(Window::new(1)), None);
+
+		// Destroying an unknown window has no effect.
+		save_set.destroy(&destroy(4));
+
+		assert_eq!(
+			save_set.drain_removals(),
+			[
+				change(AddOrRemove::Remove, 3),
+				change(AddOrRemove::Remove, 2),
+			],
+		);
+		assert!(save_set.is_empty());
+		assert!(save_set.drain_removals().is_empty());
+	}
+}
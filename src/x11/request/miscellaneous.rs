@@ -14,6 +14,8 @@
 
 extern crate self as xrb;
 
+use std::borrow::Cow;
+
 use xrbk::{
 	pad,
 	Buf,
@@ -21,17 +23,21 @@ use xrbk::{
 	ConstantX11Size,
 	ReadError,
 	ReadError::UnrecognizedDiscriminant,
+	ReadMode,
 	ReadResult,
+	Readable,
 	ReadableWithContext,
 	Wrap,
 	Writable,
+	WritableVectored,
+	WriteError,
 	WriteResult,
 	X11Size,
 };
 use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
 
 use crate::{
-	message::{Event, Request},
+	message::{Event, Flagged, Request},
 	x11::{error, reply},
 	Any,
 	Atom,
@@ -70,6 +76,18 @@ macro_rules! request_error {
 				$Error(error::$Error)
 			),+)?
 		}
+
+		impl TryFrom<crate::x11::AnyError> for $Name {
+			type Error = crate::x11::AnyError;
+
+			fn try_from(error: crate::x11::AnyError) -> Result<Self, Self::Error> {
+				match error {
+					$($(crate::x11::AnyError::$Error(error) => Ok(Self::$Error(error)),)+)?
+
+					other => Err(other),
+				}
+			}
+		}
 	};
 }
 
@@ -90,6 +108,7 @@ derive_xrb! {
 	/// [`GetAtom` reply]: reply::GetAtom
 	#[doc(alias("InternAtom", "CreateAtom"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GetAtom: Request(16, error::Value) -> reply::GetAtom {
 		#[metabyte]
 		/// Whether the X server should avoid creating a new [atom] for an
@@ -139,6 +158,7 @@ derive_xrb! {
 	///
 	/// [`Atom` error]: error::Atom
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GetAtomName: Request(17, error::Atom) -> reply::GetAtomName {
 		/// The [atom] for which this [request] gets its name.
 		///
@@ -174,6 +194,7 @@ request_error! {
 /// [window]: Window
 #[doc(alias = "ChangePropertyMode")]
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ModifyPropertyMode {
 	/// The property replaces an existing property; the previous value is
 	/// discarded.
@@ -189,6 +210,7 @@ pub enum ModifyPropertyMode {
 /// Whether a [`DataList`] is formatted as a list of `i8` values, `i16` values,
 /// or `i32` values.
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataFormat {
 	/// The list is formatted as `i8` values.
 	I8 = 8,
@@ -234,6 +256,7 @@ impl From<DataFormat> for u8 {
 ///
 /// This represents uninterpreted 'raw' data.
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataList {
 	/// A list of `i8` values.
 	I8(Vec<i8>),
@@ -265,6 +288,16 @@ impl DataList {
 			Self::I32(list) => list.is_empty(),
 		}
 	}
+
+	/// The [`DataFormat`] of the `DataList`.
+	#[must_use]
+	pub const fn format(&self) -> DataFormat {
+		match self {
+			Self::I8(_) => DataFormat::I8,
+			Self::I16(_) => DataFormat::I16,
+			Self::I32(_) => DataFormat::I32,
+		}
+	}
 }
 
 impl X11Size for DataList {
@@ -335,6 +368,7 @@ derive_xrb! {
 	/// [`Match` error]: error::Match
 	#[doc(alias = "ChangeProperty")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ModifyProperty: Request(18, ModifyPropertyError) {
 		#[metabyte]
 		/// The way in which the property is modified.
@@ -420,6 +454,40 @@ derive_xrb! {
 	}
 }
 
+impl WritableVectored for ModifyProperty {
+	fn write_header(&self, header: &mut impl BufMut) -> Result<Cow<'_, [u8]>, WriteError> {
+		Self::MAJOR_OPCODE.write_to(header)?;
+		self.modify_mode.write_to(header)?;
+		self.checked_length()?.write_to(header)?;
+
+		self.target.write_to(header)?;
+		self.property.write_to(header)?;
+		self.r#type.write_to(header)?;
+
+		self.data.format().write_to(header)?;
+		header.put_bytes(0, 3);
+
+		#[allow(clippy::cast_possible_truncation)]
+		(self.data.len() as u32).write_to(header)?;
+
+		Ok(match &self.data {
+			// `i8` values are written as they are, so they can be borrowed.
+			DataList::I8(list) => Cow::Borrowed(i8_bytes(list)),
+
+			// Other values must be converted to big-endian bytes.
+			DataList::I16(list) => Cow::Owned(list.iter().flat_map(|n| n.to_be_bytes()).collect()),
+			DataList::I32(list) => Cow::Owned(list.iter().flat_map(|n| n.to_be_bytes()).collect()),
+		})
+	}
+}
+
+/// Reinterprets the given `i8` values as bytes.
+const fn i8_bytes(list: &[i8]) -> &[u8] {
+	// SAFETY: `i8` and `u8` have the same size and alignment, and every bit
+	//         pattern is valid for both.
+	unsafe { std::slice::from_raw_parts(list.as_ptr().cast::<u8>(), list.len()) }
+}
+
 request_error! {
 	pub enum DeletePropertyError for DeleteProperty {
 		Atom,
@@ -449,6 +517,7 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	/// [`Atom` error]: error::Atom
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct DeleteProperty: Request(19, DeletePropertyError) {
 		/// The [window] for which this [request] removes the `property`.
 		///
@@ -508,6 +577,7 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	/// [`Atom` error]: error::Atom
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GetProperty: Request(20, GetPropertyError) -> reply::GetProperty {
 		/// Whether the `property` should be deleted from the `target` [window].
 		///
@@ -586,6 +656,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ListProperties: Request(21, error::Window) -> reply::ListProperties {
 		/// The [window] for which this [request] returns its properties.
 		///
@@ -639,6 +710,7 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	/// [`Atom` error]: error::Atom
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct SetSelectionOwner: Request(22, SetSelectionOwnerError) {
 		/// Sets the new owner of the `selection`.
 		///
@@ -693,6 +765,7 @@ derive_xrb! {
 	///
 	/// [`Atom` error]: error::Atom
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GetSelectionOwner: Request(23) -> reply::GetSelectionOwner {
 		/// The selection for which this [request] returns its owner.
 		///
@@ -733,6 +806,7 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	/// [`Atom` error]: error::Atom
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ConvertSelection: Request(24, ConvertSelectionError) {
 		/// Your [window] which is requesting this conversion.
 		///
@@ -822,7 +896,8 @@ derive_xrb! {
 	//
 	// This feature would be nice for this:
 	// <https://github.com/rust-lang/rust/issues/92827>
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct SendEvent<E: Event + ConstantX11Size>: Request(25, SendEventError) {
 		/// Whether the `event` should be propagated to the closest appropriate
 		/// ancestor, if necessary.
@@ -856,6 +931,45 @@ derive_xrb! {
 	}
 }
 
+impl<E: Event + ConstantX11Size> Readable for SendEvent<E> {
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		Self::read_from_with_mode(buf, ReadMode::Lenient)
+	}
+
+	fn read_from_with_mode(buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		const HEADER: usize = 4;
+
+		// major opcode is already read
+
+		let propagate = bool::read_from(buf)?;
+
+		// The message length.
+		let length = usize::from(u16::read_from(buf)?) * 4;
+		let buf = &mut buf.take(length.saturating_sub(HEADER));
+
+		let destination = DestinationWindow::read_from_with_mode(buf, mode)?;
+		let event_mask = EventMask::read_from_with_mode(buf, mode)?;
+
+		// Unlike the `Readable` implementations of events, the event's code is
+		// read here too. It is accepted with or without the `SEND_EVENT_BIT`,
+		// as the X server sets that bit itself when it sends the event.
+		let Flagged { event, .. } = Flagged::<E>::read_from_with_mode(buf, mode)?;
+
+		Ok(Self {
+			propagate,
+			destination,
+			event_mask,
+			event,
+		})
+	}
+}
+
 request_error! {
 	pub enum RotatePropertiesError for RotateProperties {
 		Atom,
@@ -914,7 +1028,8 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	/// [`Atom` error]: error::Atom
 	/// [`Match` error]: error::Match
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct RotateProperties: Request(114, RotatePropertiesError) {
 		/// The [window] for which the given `properties` are rotated.
 		///
@@ -960,3 +1075,172 @@ derive_xrb! {
 		pub properties: Vec<Atom>,
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		x11::event::{ClientMessage, ClientMessageData},
+		Char8,
+	};
+
+	#[test]
+	fn test_modify_property_vectored() {
+		for data in [
+			DataList::I8(vec![1, -2, 3, -4, 5, -6, 7, -8]),
+			DataList::I16(vec![1, -2, 3, -4]),
+			DataList::I32(vec![0x0102_0304, -5]),
+		] {
+			let request = ModifyProperty {
+				modify_mode: ModifyPropertyMode::Append,
+				target: Window::new(0x0040_0001),
+				property: Atom::new(39),
+				r#type: Atom::new(31),
+				data,
+			};
+
+			let mut bytes = vec![];
+			request.write_to(&mut bytes).unwrap();
+
+			let vectored = request.to_vectored().unwrap();
+			let concatenated: Vec<u8> = vectored
+				.io_slices()
+				.iter()
+				.flat_map(|slice| slice.iter().copied())
+				.collect();
+
+			assert_eq!(concatenated, bytes);
+		}
+	}
+
+	#[test]
+	fn test_rotate_properties_round_trip() {
+		let request = RotateProperties {
+			target: Window::new(0x0060_0002),
+			shift: -1,
+			properties: vec![Atom::new(39), Atom::new(37), Atom::new(67)],
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		// The header, `target`, `properties_len` and `shift`, then 3 atoms.
+		assert_eq!(bytes.len(), 12 + 3 * 4);
+		assert_eq!(&bytes[..4], [114, 0, 0, 6]);
+		assert_eq!(&bytes[8..10], 3_u16.to_be_bytes());
+		assert_eq!(&bytes[10..12], (-1_i16).to_be_bytes());
+		assert_eq!(&bytes[12..16], 39_u32.to_be_bytes());
+		assert_eq!(request.x11_size(), bytes.len());
+
+		assert_eq!(
+			RotateProperties::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+
+	fn client_message() -> ClientMessage {
+		ClientMessage {
+			sequence: 0,
+			window: Window::new(0x0060_0001),
+			r#type: Atom::new(0x0000_0145),
+			data: ClientMessageData::Longs([0x0000_0146, 1_000, 0, 0, 0]),
+		}
+	}
+
+	#[test]
+	fn test_send_client_message() {
+		let request = SendEvent {
+			propagate: false,
+			destination: DestinationWindow::Other(Window::new(0x0060_0001)),
+			event_mask: EventMask::empty(),
+			event: client_message(),
+		};
+
+		let mut event = vec![];
+		client_message().write_to(&mut event).unwrap();
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 44);
+		assert_eq!(bytes[..4], [25, 0, 0, 11]);
+		assert_eq!(bytes[12..], event);
+
+		assert_eq!(SendEvent::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_send_event_code_with_send_event_bit() {
+		let request = SendEvent {
+			propagate: true,
+			destination: DestinationWindow::Focus,
+			event_mask: EventMask::STRUCTURE_NOTIFY,
+			event: client_message(),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		// The event's code is written without the `SEND_EVENT_BIT`...
+		assert_eq!(bytes[12], 33);
+
+		// ...but it is accepted with it.
+		bytes[12] |= 0x80;
+		assert_eq!(SendEvent::read_from(&mut &bytes[1..]).unwrap(), request);
+
+		// Any other code is rejected.
+		bytes[12] = 34;
+		assert!(matches!(
+			SendEvent::<ClientMessage>::read_from(&mut &bytes[1..]),
+			Err(ReadError::UnrecognizedDiscriminant(34)),
+		));
+	}
+
+	#[test]
+	fn test_get_atom() {
+		let request = GetAtom {
+			no_creation: true,
+			name: String8::from(b"WM_PROTOCOLS".map(Char8::new).to_vec()),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(
+			bytes,
+			[
+				16, 1, 0, 5, // header
+				0, 12, 0, 0, // name length
+				b'W', b'M', b'_', b'P', b'R', b'O', b'T', b'O', b'C', b'O', b'L', b'S',
+			],
+		);
+		assert_eq!(GetAtom::read_from(&mut &bytes[1..]).unwrap(), request);
+
+		// A name whose length is not a multiple of 4 is padded.
+		let request = GetAtom {
+			no_creation: false,
+			name: String8::from(b"UTF8_STRING".map(Char8::new).to_vec()),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 20);
+		assert_eq!(bytes[..8], [16, 0, 0, 5, 0, 11, 0, 0]);
+		assert_eq!(bytes[8..], *b"UTF8_STRING\0");
+		assert_eq!(GetAtom::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_get_atom_name() {
+		let request = GetAtomName {
+			target: Atom::new(0x0000_0145),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes, [17, 0, 0, 2, 0x00, 0x00, 0x01, 0x45]);
+		assert_eq!(GetAtomName::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+}
@@ -13,6 +13,8 @@
 
 extern crate self as xrb;
 
+use std::collections::HashMap;
+
 use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
 
 use crate::{
@@ -24,7 +26,9 @@ use crate::{
 	Coords,
 	CopyableFromParent,
 	Drawable,
+	EventMask,
 	Rectangle,
+	StackMode,
 	Window,
 	WindowClass,
 };
@@ -57,6 +61,18 @@ macro_rules! request_error {
 				$Error(error::$Error)
 			),+)?
 		}
+
+		impl TryFrom<crate::x11::AnyError> for $Name {
+			type Error = crate::x11::AnyError;
+
+			fn try_from(error: crate::x11::AnyError) -> Result<Self, Self::Error> {
+				match error {
+					$($(crate::x11::AnyError::$Error(error) => Ok(Self::$Error(error)),)+)?
+
+					other => Err(other),
+				}
+			}
+		}
 	};
 }
 
@@ -78,6 +94,7 @@ derive_xrb! {
 	/// [request]: Request
 	/// [window]: Window
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct CreateWindow: Request(1, CreateWindowError) {
 		#[metabyte]
 		/// The [window]'s depth.
@@ -211,6 +228,7 @@ derive_xrb! {
 	/// [`RESIZE_REDIRECT`]: crate::EventMask::RESIZE_REDIRECT
 	/// [`BUTTON_PRESS`]: crate::EventMask::BUTTON_PRESS
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ChangeWindowAttributes: Request(2, ChangeWindowAttributesError) {
 		/// The [window] which the `attributes` are changed on.
 		///
@@ -231,6 +249,7 @@ derive_xrb! {
 	/// [attributes]: Attributes
 	/// [window]: Window
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GetWindowAttributes: Request(3, error::Window) -> reply::GetWindowAttributes {
 		/// The [window] for which this [request] gets the [attributes].
 		///
@@ -264,6 +283,7 @@ derive_xrb! {
 	/// [`UnmapWindow` request]: UnmapWindow
 	/// [`Window` error]: error::Window
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct DestroyWindow: Request(4, error::Window) {
 		/// The [window] which is the target of the `DestroyWindow` [request].
 		///
@@ -296,6 +316,7 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	#[doc(alias = "DestroySubwindows")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct DestroyChildren: Request(5, error::Window) {
 		/// The [window] which will have its children [destroyed].
 		///
@@ -313,6 +334,30 @@ derive_xrb! {
 	}
 }
 
+impl ChangeWindowAttributes {
+	/// Creates a `ChangeWindowAttributes` request which selects the [events]
+	/// in the given `event_mask` on the `target` [window].
+	///
+	/// Only the [`event_mask`] attribute is changed. This replaces any events
+	/// previously selected by your client on the `target` [window].
+	///
+	/// [events]: crate::message::Event
+	/// [window]: Window
+	///
+	/// [`event_mask`]: Attributes::event_mask
+	#[doc(alias("XSelectInput"))]
+	#[must_use]
+	pub fn select_input(target: Window, event_mask: EventMask) -> Self {
+		let mut attributes = Attributes::builder();
+		attributes.event_mask(event_mask);
+
+		Self {
+			target,
+			attributes: attributes.build(),
+		}
+	}
+}
+
 request_error! {
 	pub enum ReparentWindowError for ReparentWindow {
 		Match,
@@ -361,6 +406,7 @@ derive_xrb! {
 	/// [`Match` error]: error::Match
 	/// [`Window` error]: error::Window
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ReparentWindow: Request(7, ReparentWindowError) {
 		/// The [window] which will be transferred to be a child of the
 		/// `new_parent`.
@@ -431,6 +477,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct MapWindow: Request(8, error::Window) {
 		/// The [window] which is the target of the `MapWindow` [request].
 		///
@@ -464,6 +511,7 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	#[doc(alias = "MapSubwindows")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct MapChildren: Request(9, error::Window) {
 		/// The [window] which will have its unmapped children [mapped].
 		///
@@ -498,6 +546,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct UnmapWindow: Request(10, error::Window) {
 		/// The [window] which is the target of the `UnmapWindow` [request].
 		///
@@ -530,6 +579,7 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	#[doc(alias = "UnmapSubwindows")]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct UnmapChildren: Request(11, error::Window) {
 		/// The [window] which will have its mapped children [unmapped].
 		///
@@ -592,6 +642,7 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	/// [`Match` error]: error::Match
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct ConfigureWindow: Request(12, ConfigureWindowError) {
 		/// The [window] which is the target of the `ConfigureWindow` [request].
 		///
@@ -645,6 +696,181 @@ derive_xrb! {
 	}
 }
 
+/// A single restacking of a [window], as part of a [`RestackPlan`].
+///
+/// [window]: Window
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Restack {
+	/// The [window] which is restacked.
+	///
+	/// [window]: Window
+	pub window: Window,
+	/// The sibling which the `window` is stacked relative to.
+	///
+	/// If this is [`None`], the `window` is stacked relative to all of its
+	/// siblings.
+	pub sibling: Option<Window>,
+	/// How the `window` is stacked relative to the `sibling`.
+	pub stack_mode: StackMode,
+}
+
+impl Restack {
+	/// Returns the [`ConfigureWindow` request] which performs this restack.
+	///
+	/// [`ConfigureWindow` request]: ConfigureWindow
+	#[must_use]
+	pub fn request(&self) -> ConfigureWindow {
+		let mut config = WindowConfig::builder();
+
+		if let Some(sibling) = self.sibling {
+			config.sibling(sibling);
+		}
+		config.stack_mode(self.stack_mode);
+
+		ConfigureWindow {
+			target: self.window,
+			config: config.build(),
+		}
+	}
+}
+
+/// Returns whether each of the `positions` is part of a longest strictly
+/// increasing subsequence of them.
+fn longest_increasing(positions: &[usize]) -> Vec<bool> {
+	// The index of the last position of the best subsequence of each length.
+	let mut tails: Vec<usize> = Vec::new();
+	// The index of the position before each position in its subsequence.
+	let mut previous: Vec<Option<usize>> = Vec::with_capacity(positions.len());
+
+	for (index, position) in positions.iter().enumerate() {
+		let len = tails.partition_point(|&tail| positions[tail] < *position);
+
+		previous.push(len.checked_sub(1).map(|len| tails[len]));
+
+		if len == tails.len() {
+			tails.push(index);
+		} else {
+			tails[len] = index;
+		}
+	}
+
+	let mut kept = vec![false; positions.len()];
+	let mut index = tails.last().copied();
+
+	while let Some(current) = index {
+		kept[current] = true;
+		index = previous[current];
+	}
+
+	kept
+}
+
+/// The [`ConfigureWindow` requests] needed to restack the children of a
+/// [window] from one stacking order to another.
+///
+/// A `RestackPlan` moves as few [windows] as possible: the largest set of
+/// [windows] which are already in the right order relative to each other are
+/// left in place, and every other [window] is stacked [`Above`] the [window]
+/// below it in the target order.
+///
+/// [window]: Window
+/// [windows]: Window
+/// [`ConfigureWindow` requests]: ConfigureWindow
+///
+/// [`Above`]: StackMode::Above
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct RestackPlan {
+	/// The restacks to perform, in order.
+	pub restacks: Vec<Restack>,
+}
+
+impl RestackPlan {
+	/// Plans the restacking of [windows] from the `current` stacking order to
+	/// the `target` stacking order.
+	///
+	/// Both orders are listed from bottom to top, like the `children` of a
+	/// [`QueryWindowTree` reply], and each [window] should be listed at most
+	/// once.
+	///
+	/// [Windows] in the `target` order but not the `current` order, such as
+	/// newly mapped [windows], are always restacked. [Windows] in the
+	/// `current` order but not the `target` order, such as destroyed
+	/// [windows], are ignored.
+	///
+	/// [window]: Window
+	/// [windows]: Window
+	/// [Windows]: Window
+	///
+	/// [`QueryWindowTree` reply]: reply::QueryWindowTree
+	#[must_use]
+	pub fn new(current: &[Window], target: &[Window]) -> Self {
+		let positions: HashMap<Window, usize> = current
+			.iter()
+			.enumerate()
+			.map(|(position, window)| (*window, position))
+			.collect();
+
+		// The target index and current position of the windows which are
+		// already stacked.
+		let (indices, stacked): (Vec<usize>, Vec<usize>) = target
+			.iter()
+			.enumerate()
+			.filter_map(|(index, window)| Some((index, *positions.get(window)?)))
+			.unzip();
+
+		let mut kept = vec![false; target.len()];
+		for (index, _) in indices
+			.into_iter()
+			.zip(longest_increasing(&stacked))
+			.filter(|(_, kept)| *kept)
+		{
+			kept[index] = true;
+		}
+
+		// The lowest window which is not restacked.
+		let lowest_kept = target
+			.iter()
+			.zip(&kept)
+			.find_map(|(window, kept)| kept.then_some(*window));
+
+		let restacks = target
+			.iter()
+			.enumerate()
+			.filter(|(index, _)| !kept[*index])
+			.map(|(index, &window)| match index {
+				// The lowest window has no window to be stacked above.
+				0 => Restack {
+					window,
+					sibling: lowest_kept,
+					stack_mode: StackMode::Below,
+				},
+
+				_ => Restack {
+					window,
+					sibling: Some(target[index - 1]),
+					stack_mode: StackMode::Above,
+				},
+			})
+			.collect();
+
+		Self { restacks }
+	}
+
+	/// Returns whether no restacking is needed.
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.restacks.is_empty()
+	}
+
+	/// Returns the [`ConfigureWindow` requests] which perform this plan, in
+	/// order.
+	///
+	/// [`ConfigureWindow` requests]: ConfigureWindow
+	pub fn requests(&self) -> impl Iterator<Item = ConfigureWindow> + '_ {
+		self.restacks.iter().map(Restack::request)
+	}
+}
+
 request_error! {
 	pub enum CirculateWindowError for CirculateWindow {
 		Value,
@@ -661,6 +887,7 @@ request_error! {
 ///
 /// [`CirculateWindow` request]: CirculateWindow
 #[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CirculateDirection {
 	/// Raises the lowest mapped child that is occluded by another child, if
 	/// any, to the top of the stack.
@@ -695,6 +922,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct CirculateWindow: Request(13, CirculateWindowError) {
 		#[metabyte]
 		/// Which of the [window]'s children might be circulated and in which
@@ -741,6 +969,7 @@ derive_xrb! {
 	///
 	/// [`Drawable` error]: error::Drawable
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct GetGeometry: Request(14, error::Drawable) -> reply::GetGeometry {
 		/// The [drawable] for which this [request] gets its geometry.
 		///
@@ -778,6 +1007,7 @@ derive_xrb! {
 	#[doc(alias("QueryParent", "QueryChildren", "QueryRoot"))]
 	#[doc(alias("GetParent", "GetChildren", "GetRoot"))]
 	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct QueryWindowTree: Request(15, error::Window) -> reply::QueryWindowTree {
 		/// The [window] for which this [request] gets its root [window],
 		/// parent, and children.
@@ -794,3 +1024,523 @@ derive_xrb! {
 		pub target: Window,
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{set::WindowConfigMask, x11::event::ConfigureWindowRequest};
+	use xrbk::{ConstantX11Size, Readable, Writable, X11Size};
+
+	#[test]
+	fn test_select_input() {
+		let request = ChangeWindowAttributes::select_input(
+			Window::new(0x0020_0001),
+			EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+		);
+
+		assert_eq!(
+			request.attributes.event_mask(),
+			Some(&(EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY)),
+		);
+		assert_eq!(request.attributes.do_not_propagate_mask(), None);
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(request.x11_size(), 16);
+		assert_eq!(
+			bytes,
+			[
+				2, 0, 0, 4, // header
+				0x00, 0x20, 0x00, 0x01, // target
+				0x00, 0x00, 0x08, 0x00, // value mask: `EVENT_MASK`
+				0x00, 0x18, 0x00, 0x00, // event mask
+			],
+		);
+		assert_eq!(
+			ChangeWindowAttributes::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_create_window() {
+		let mut attributes = Attributes::builder();
+		attributes
+			.override_redirect(true)
+			.event_mask(EventMask::EXPOSURE);
+
+		let request = CreateWindow {
+			depth: CopyableFromParent::CopyFromParent,
+			window_id: Window::new(0x0060_0001),
+			parent: Window::new(0x0000_0512),
+			geometry: Rectangle {
+				x: Px(-10),
+				y: Px(20),
+				width: Px(640),
+				height: Px(480),
+			},
+			border_width: Px(2),
+			class: CopyableFromParent::Other(WindowClass::InputOutput),
+			visual: CopyableFromParent::CopyFromParent,
+			attributes: attributes.build(),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(request.x11_size(), 40);
+		assert_eq!(
+			bytes,
+			[
+				1, 0, 0, 10, // header
+				0x00, 0x60, 0x00, 0x01, // window_id
+				0x00, 0x00, 0x05, 0x12, // parent
+				0xff, 0xf6, 0x00, 0x14, // x, y
+				0x02, 0x80, 0x01, 0xe0, // width, height
+				0x00, 0x02, 0x00, 0x01, // border_width, class
+				0x00, 0x00, 0x00, 0x00, // visual
+				0x00, 0x00, 0x0a, 0x00, // value mask
+				0x00, 0x00, 0x00, 0x01, // override redirect
+				0x00, 0x00, 0x80, 0x00, // event mask
+			],
+		);
+		assert_eq!(CreateWindow::read_from(&mut &bytes[1..]).unwrap(), request);
+
+		let request = CreateWindow {
+			depth: CopyableFromParent::Other(24),
+			class: CopyableFromParent::CopyFromParent,
+			..request
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 40);
+		assert_eq!(bytes[1], 24);
+		assert_eq!(bytes[22..24], [0, 0]);
+		assert_eq!(CreateWindow::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_configure_window() {
+		let mut config = WindowConfig::builder();
+		config
+			.width(Px(0xfffe))
+			.border_width(Px(1))
+			.stack_mode(StackMode::Below);
+
+		let request = ConfigureWindow {
+			target: Window::new(0x0060_0001),
+			config: config.build(),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(request.x11_size(), 24);
+		assert_eq!(
+			bytes,
+			[
+				12, 0, 0, 6, // header
+				0x00, 0x60, 0x00, 0x01, // target
+				0x00, 0x54, 0x00, 0x00, // value mask
+				0x00, 0x00, 0xff, 0xfe, // width
+				0x00, 0x00, 0x00, 0x01, // border_width
+				0x00, 0x00, 0x00, 0x01, // stack_mode
+			],
+		);
+		assert_eq!(
+			ConfigureWindow::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_configure_window_sibling_without_stack_mode() {
+		// The server generates a `Match` error for this, but it can still be
+		// encoded.
+		let mut config = WindowConfig::builder();
+		config.sibling(Window::new(0x0060_0002));
+
+		let request = ConfigureWindow {
+			target: Window::new(0x0060_0001),
+			config: config.build(),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(request.x11_size(), 16);
+		assert_eq!(
+			bytes,
+			[
+				12, 0, 0, 4, // header
+				0x00, 0x60, 0x00, 0x01, // target
+				0x00, 0x20, 0x00, 0x00, // value mask
+				0x00, 0x60, 0x00, 0x02, // sibling
+			],
+		);
+
+		let read = ConfigureWindow::read_from(&mut &bytes[1..]).unwrap();
+		assert_eq!(read.config.sibling(), Some(&Window::new(0x0060_0002)));
+		assert_eq!(read.config.stack_mode(), None);
+		assert_eq!(read, request);
+	}
+
+	#[test]
+	fn test_configure_window_from_request() {
+		let event = ConfigureWindowRequest {
+			sequence: 3,
+			stack_mode: StackMode::Above,
+			parent: Window::new(0x0000_0512),
+			window: Window::new(0x0060_0001),
+			sibling: Some(Window::new(0x0060_0002)),
+			geometry: Rectangle {
+				x: Px(-10),
+				y: Px(20),
+				width: Px(640),
+				height: Px(480),
+			},
+			border_width: Px(2),
+			mask: WindowConfigMask::Y | WindowConfigMask::HEIGHT | WindowConfigMask::STACK_MODE,
+		};
+
+		let config = WindowConfig::from_request(&event);
+
+		assert_eq!(config.x(), None);
+		assert_eq!(config.y(), Some(&Px(20)));
+		assert_eq!(config.width(), None);
+		assert_eq!(config.height(), Some(&Px(480)));
+		assert_eq!(config.border_width(), None);
+		assert_eq!(config.sibling(), None);
+		assert_eq!(config.stack_mode(), Some(&StackMode::Above));
+
+		let request = ConfigureWindow {
+			target: event.window,
+			config,
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(request.x11_size(), 24);
+		assert_eq!(
+			bytes,
+			[
+				12, 0, 0, 6, // header
+				0x00, 0x60, 0x00, 0x01, // target
+				0x00, 0x4a, 0x00, 0x00, // value mask
+				0x00, 0x00, 0x00, 0x14, // y
+				0x00, 0x00, 0x01, 0xe0, // height
+				0x00, 0x00, 0x00, 0x00, // stack_mode
+			],
+		);
+	}
+
+	/// Asserts that a `request` with a single `target` window is encoded with
+	/// the given `opcode`, a length of 2, and the `target` window.
+	fn assert_window_request<R>(request: &R, opcode: u8)
+	where
+		R: Request + ConstantX11Size + Readable + Writable + PartialEq + std::fmt::Debug,
+	{
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(R::X11_SIZE, 8);
+		assert_eq!(R::MAJOR_OPCODE, opcode);
+		assert_eq!(
+			bytes,
+			[
+				opcode, 0, 0, 2, // header
+				0x00, 0x60, 0x00, 0x01, // target
+			],
+		);
+		assert_eq!(&R::read_from(&mut &bytes[1..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_destroy_window() {
+		assert_window_request(
+			&DestroyWindow {
+				target: Window::new(0x0060_0001),
+			},
+			4,
+		);
+	}
+
+	#[test]
+	fn test_destroy_children() {
+		assert_window_request(
+			&DestroyChildren {
+				target: Window::new(0x0060_0001),
+			},
+			5,
+		);
+	}
+
+	#[test]
+	fn test_map_window() {
+		assert_window_request(
+			&MapWindow {
+				target: Window::new(0x0060_0001),
+			},
+			8,
+		);
+	}
+
+	#[test]
+	fn test_map_children() {
+		assert_window_request(
+			&MapChildren {
+				target: Window::new(0x0060_0001),
+			},
+			9,
+		);
+	}
+
+	#[test]
+	fn test_unmap_window() {
+		assert_window_request(
+			&UnmapWindow {
+				target: Window::new(0x0060_0001),
+			},
+			10,
+		);
+	}
+
+	#[test]
+	fn test_unmap_children() {
+		assert_window_request(
+			&UnmapChildren {
+				target: Window::new(0x0060_0001),
+			},
+			11,
+		);
+	}
+
+	#[test]
+	fn test_reparent_window() {
+		let request = ReparentWindow {
+			target: Window::new(0x0060_0001),
+			new_parent: Window::new(0x0080_0001),
+			coords: Coords::new(Px(-4), Px(22)),
+		};
+
+		let mut bytes = vec![];
+		request.write_to(&mut bytes).unwrap();
+
+		assert_eq!(
+			bytes,
+			[
+				7, 0, 0, 4, // header
+				0x00, 0x60, 0x00, 0x01, // target
+				0x00, 0x80, 0x00, 0x01, // new_parent
+				0xff, 0xfc, 0x00, 0x16, // x, y
+			],
+		);
+		assert_eq!(
+			ReparentWindow::read_from(&mut &bytes[1..]).unwrap(),
+			request
+		);
+	}
+
+	#[test]
+	fn test_circulate_window() {
+		for (direction, discrim) in [
+			(CirculateDirection::RaiseLowest, 0),
+			(CirculateDirection::LowerHighest, 1),
+		] {
+			let request = CirculateWindow {
+				direction,
+				target: Window::new(0x0060_0001),
+			};
+
+			let mut bytes = vec![];
+			request.write_to(&mut bytes).unwrap();
+
+			assert_eq!(
+				bytes,
+				[
+					13, discrim, 0, 2, // header
+					0x00, 0x60, 0x00, 0x01, // target
+				],
+			);
+			assert_eq!(
+				CirculateWindow::read_from(&mut &bytes[1..]).unwrap(),
+				request
+			);
+		}
+	}
+
+	#[test]
+	fn test_query_window_tree() {
+		assert_window_request(
+			&QueryWindowTree {
+				target: Window::new(0x0060_0001),
+			},
+			15,
+		);
+	}
+
+	#[test]
+	fn test_get_window_attributes() {
+		assert_window_request(
+			&GetWindowAttributes {
+				target: Window::new(0x0060_0001),
+			},
+			3,
+		);
+	}
+
+	#[test]
+	fn test_get_geometry_takes_drawable() {
+		assert_window_request(
+			&GetGeometry {
+				target: Window::new(0x0060_0001).into(),
+			},
+			14,
+		);
+	}
+
+	/// Applies the `plan` to a simulated `stack` of windows.
+	fn apply(stack: &mut Vec<Window>, plan: &RestackPlan) {
+		for restack in &plan.restacks {
+			stack.retain(|window| *window != restack.window);
+
+			let position = |sibling| stack.iter().position(|window| *window == sibling).unwrap();
+			let index = match (restack.sibling, restack.stack_mode) {
+				(Some(sibling), StackMode::Above) => position(sibling) + 1,
+				(Some(sibling), StackMode::Below) => position(sibling),
+
+				(None, StackMode::Above) => stack.len(),
+				(None, StackMode::Below) => 0,
+
+				(_, mode) => panic!("unexpected stack mode {mode:?}"),
+			};
+
+			stack.insert(index, restack.window);
+		}
+	}
+
+	/// Asserts that the `plan` restacks `current` to `target`, returning the
+	/// number of restacks.
+	fn assert_restacks(current: &[Window], target: &[Window]) -> usize {
+		let plan = RestackPlan::new(current, target);
+
+		// Newly mapped windows are stacked on top.
+		let mut stack = current.to_vec();
+		stack.extend(target.iter().filter(|window| !current.contains(window)));
+
+		apply(&mut stack, &plan);
+		stack.retain(|window| target.contains(window));
+
+		assert_eq!(stack, target, "restacking {current:?} with {plan:?}");
+
+		plan.restacks.len()
+	}
+
+	fn windows(ids: &[u32]) -> Vec<Window> {
+		ids.iter().copied().map(Window::new).collect()
+	}
+
+	#[test]
+	fn test_restack_plan() {
+		let current = windows(&[1, 2, 3, 4, 5]);
+
+		// Already in the right order.
+		let plan = RestackPlan::new(&current, &current);
+		assert!(plan.is_empty());
+		assert_eq!(plan.requests().count(), 0);
+
+		// Raising one window takes one restack.
+		let plan = RestackPlan::new(&current, &windows(&[1, 3, 4, 5, 2]));
+		assert_eq!(
+			plan.restacks,
+			[Restack {
+				window: Window::new(2),
+				sibling: Some(Window::new(5)),
+				stack_mode: StackMode::Above,
+			}],
+		);
+
+		let request = plan.requests().next().unwrap();
+		assert_eq!(request.target, Window::new(2));
+		assert_eq!(request.config.sibling(), Some(&Window::new(5)));
+		assert_eq!(request.config.stack_mode(), Some(&StackMode::Above));
+
+		// Lowering one window to the bottom.
+		let plan = RestackPlan::new(&current, &windows(&[4, 1, 2, 3, 5]));
+		assert_eq!(
+			plan.restacks,
+			[Restack {
+				window: Window::new(4),
+				sibling: Some(Window::new(1)),
+				stack_mode: StackMode::Below,
+			}],
+		);
+
+		// Fully reversed.
+		assert_eq!(assert_restacks(&current, &windows(&[5, 4, 3, 2, 1])), 4);
+
+		// Destroyed windows are ignored, and new windows are restacked.
+		assert_eq!(assert_restacks(&current, &windows(&[1, 6, 2, 4, 5])), 1);
+		assert_eq!(assert_restacks(&current, &windows(&[6, 7])), 2);
+		assert_eq!(assert_restacks(&[], &[]), 0);
+	}
+
+	#[test]
+	fn test_restack_plan_randomized() {
+		/// Returns the length of the longest increasing subsequence of
+		/// `values` by brute force.
+		fn longest_increasing(values: &[usize]) -> usize {
+			let mut lengths = vec![1; values.len()];
+
+			for i in 0..values.len() {
+				for j in 0..i {
+					if values[j] < values[i] {
+						lengths[i] = lengths[i].max(lengths[j] + 1);
+					}
+				}
+			}
+
+			lengths.into_iter().max().unwrap_or(0)
+		}
+
+		let mut state = 0x9e37_79b9_u32;
+		let mut random = |max: usize| {
+			state ^= state << 13;
+			state ^= state >> 17;
+			state ^= state << 5;
+
+			usize::try_from(state).unwrap() % max
+		};
+
+		for _ in 0..200 {
+			let len = random(10);
+			let current: Vec<Window> = (0..len).map(|id| Window::new(id as u32)).collect();
+
+			let mut target = current.clone();
+			// Shuffle the target order.
+			for i in (1..target.len()).rev() {
+				target.swap(i, random(i + 1));
+			}
+			// Destroy some windows and map some new ones.
+			target.retain(|_| random(4) != 0);
+			for id in 0..random(3) {
+				let index = random(target.len() + 1);
+				target.insert(index, Window::new(100 + id as u32));
+			}
+
+			let positions: Vec<usize> = target
+				.iter()
+				.filter_map(|window| current.iter().position(|current| current == window))
+				.collect();
+
+			assert_eq!(
+				assert_restacks(&current, &target),
+				target.len() - longest_increasing(&positions),
+			);
+		}
+	}
+}
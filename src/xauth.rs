@@ -0,0 +1,492 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Entries of an Xauthority file, such as `~/.Xauthority`.
+//!
+//! An Xauthority file contains the authorization data used to fill in the
+//! `auth_protocol_name` and `auth_protocol_data` of [`InitConnection`]. It is
+//! a list of [`AuthEntry`s](AuthEntry) with no header, each made up of a
+//! [`Family`] followed by four fields, each prefixed by its length as a
+//! big-endian `u16`.
+//!
+//! The file is usually found at the path given by the `XAUTHORITY`
+//! environment variable, or `~/.Xauthority` if that is not set.
+//!
+//! [`InitConnection`]: crate::connection::InitConnection
+
+use xrbk::{
+	ensure_remaining,
+	Buf,
+	BufMut,
+	ConstantX11Size,
+	ReadError,
+	ReadResult,
+	Readable,
+	Writable,
+	WriteError,
+	WriteResult,
+	X11Size,
+};
+
+/// The type of [`address`] an [`AuthEntry`] applies to.
+///
+/// [`address`]: AuthEntry::address
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Family {
+	/// An IPv4 address, as four bytes.
+	Internet,
+	/// A DECnet address.
+	DecNet,
+	/// A Chaosnet address.
+	Chaos,
+	/// A server-interpreted address.
+	ServerInterpreted,
+	/// An IPv6 address, as sixteen bytes.
+	InternetV6,
+
+	/// The hostname of a machine connecting locally, such as over a Unix domain
+	/// socket.
+	Local,
+	/// Matches any [`Family`] and any address.
+	Wild,
+
+	/// A family not listed here.
+	Other(u16),
+}
+
+impl From<u16> for Family {
+	fn from(family: u16) -> Self {
+		match family {
+			0 => Self::Internet,
+			1 => Self::DecNet,
+			2 => Self::Chaos,
+			5 => Self::ServerInterpreted,
+			6 => Self::InternetV6,
+
+			256 => Self::Local,
+			65535 => Self::Wild,
+
+			other => Self::Other(other),
+		}
+	}
+}
+
+impl From<Family> for u16 {
+	fn from(family: Family) -> Self {
+		match family {
+			Family::Internet => 0,
+			Family::DecNet => 1,
+			Family::Chaos => 2,
+			Family::ServerInterpreted => 5,
+			Family::InternetV6 => 6,
+
+			Family::Local => 256,
+			Family::Wild => 65535,
+
+			Family::Other(other) => other,
+		}
+	}
+}
+
+impl ConstantX11Size for Family {
+	const X11_SIZE: usize = 2;
+}
+
+impl X11Size for Family {
+	fn x11_size(&self) -> usize {
+		Self::X11_SIZE
+	}
+}
+
+impl Readable for Family {
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
+		Ok(u16::read_from(buf)?.into())
+	}
+}
+
+impl Writable for Family {
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		u16::from(*self).write_to(buf)
+	}
+}
+
+/// An entry in an Xauthority file.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuthEntry {
+	/// The type of `address` this entry applies to.
+	pub family: Family,
+	/// The address of the X server this entry applies to.
+	///
+	/// For [`Family::Local`], this is the hostname of the machine. An empty
+	/// address matches any address of the same [`Family`].
+	pub address: Vec<u8>,
+	/// The display number this entry applies to, in decimal.
+	///
+	/// An empty display number matches any display.
+	pub display_number: String,
+
+	/// The name of the authorization protocol, such as `MIT-MAGIC-COOKIE-1`.
+	///
+	/// This is sent as the `auth_protocol_name` of [`InitConnection`].
+	///
+	/// [`InitConnection`]: crate::connection::InitConnection
+	pub name: String,
+	/// The authorization data, such as the cookie for `MIT-MAGIC-COOKIE-1`.
+	///
+	/// This is sent as the `auth_protocol_data` of [`InitConnection`].
+	///
+	/// [`InitConnection`]: crate::connection::InitConnection
+	pub data: Vec<u8>,
+}
+
+/// Reads a field prefixed by its length.
+fn read_field(buf: &mut impl Buf) -> ReadResult<Vec<u8>> {
+	let length = usize::from(u16::read_from(buf)?);
+	ensure_remaining(buf, length)?;
+
+	Ok(buf.copy_to_bytes(length).to_vec())
+}
+
+/// Reads a field prefixed by its length which must be valid UTF-8.
+fn read_string_field(buf: &mut impl Buf) -> ReadResult<String> {
+	String::from_utf8(read_field(buf)?)
+		.map_err(|error| ReadError::FailedConversion(Box::new(error)))
+}
+
+/// Writes a field prefixed by its length.
+fn write_field(field: &[u8], buf: &mut impl BufMut) -> WriteResult {
+	let length = u16::try_from(field.len())
+		.map_err(|error| WriteError::FailedConversion(Box::new(error)))?;
+
+	length.write_to(buf)?;
+	buf.put_slice(field);
+
+	Ok(())
+}
+
+impl X11Size for AuthEntry {
+	fn x11_size(&self) -> usize {
+		// The family, then each field prefixed by its `u16` length.
+		Family::X11_SIZE
+			+ 2 + self.address.len()
+			+ 2 + self.display_number.len()
+			+ 2 + self.name.len()
+			+ 2 + self.data.len()
+	}
+}
+
+impl Readable for AuthEntry {
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
+		Ok(Self {
+			family: Family::read_from(buf)?,
+			address: read_field(buf)?,
+			display_number: read_string_field(buf)?,
+
+			name: read_string_field(buf)?,
+			data: read_field(buf)?,
+		})
+	}
+}
+
+impl Writable for AuthEntry {
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		self.family.write_to(buf)?;
+		write_field(&self.address, buf)?;
+		write_field(self.display_number.as_bytes(), buf)?;
+
+		write_field(self.name.as_bytes(), buf)?;
+		write_field(&self.data, buf)?;
+
+		Ok(())
+	}
+}
+
+/// Reads every [`AuthEntry`] in the contents of an Xauthority file.
+///
+/// # Errors
+/// A [`ReadError`] is returned if the file ends partway through an entry, or if
+/// an entry's display number or name is not valid UTF-8.
+pub fn read_entries(buf: &mut impl Buf) -> ReadResult<Vec<AuthEntry>> {
+	let mut entries = Vec::new();
+
+	while buf.has_remaining() {
+		entries.push(AuthEntry::read_from(buf)?);
+	}
+
+	Ok(entries)
+}
+
+/// Writes the given `entries` in the format of an Xauthority file.
+///
+/// # Errors
+/// A [`WriteError`] is returned if any field of an entry is longer than
+/// [`u16::MAX`] bytes.
+pub fn write_entries(entries: &[AuthEntry], buf: &mut impl BufMut) -> WriteResult {
+	for entry in entries {
+		entry.write_to(buf)?;
+	}
+
+	Ok(())
+}
+
+/// Finds the [`AuthEntry`] to use for connecting to the given display.
+///
+/// An entry matches if:
+/// - its [`family`] is [`Family::Wild`], or its [`family`] is `family` and its
+///   [`address`] is either empty or `address`; and
+/// - its [`display_number`] is either empty or `display`.
+///
+/// If `names` is empty, the first matching entry is returned. Otherwise, only
+/// entries with one of the given `names` are considered, and the first
+/// matching entry with the earliest name in `names` is returned.
+///
+/// [`family`]: AuthEntry::family
+/// [`address`]: AuthEntry::address
+/// [`display_number`]: AuthEntry::display_number
+#[must_use]
+pub fn find_entry<'a>(
+	entries: &'a [AuthEntry], family: Family, address: &[u8], display: u16, names: &[&str],
+) -> Option<&'a AuthEntry> {
+	let display = display.to_string();
+
+	let mut matches = entries.iter().filter(|entry| {
+		let address_matches = entry.family == Family::Wild
+			|| (entry.family == family && (entry.address.is_empty() || entry.address == address));
+		let display_matches = entry.display_number.is_empty() || entry.display_number == display;
+
+		address_matches && display_matches
+	});
+
+	if names.is_empty() {
+		return matches.next();
+	}
+
+	matches
+		.filter_map(|entry| {
+			names
+				.iter()
+				.position(|name| *name == entry.name)
+				.map(|preference| (preference, entry))
+		})
+		// `min_by_key` returns the first of equally preferred entries.
+		.min_by_key(|(preference, _)| *preference)
+		.map(|(_, entry)| entry)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	const COOKIE: [u8; 16] = [
+		0x9d, 0x27, 0x52, 0x0b, 0x61, 0xe8, 0x3c, 0x44, 0x0f, 0xa1, 0x7b, 0x36, 0xd2, 0x58, 0xc0,
+		0x1e,
+	];
+	const OTHER_COOKIE: [u8; 16] = [0x11; 16];
+
+	const LOCALHOST_V6: [u8; 16] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+
+	/// Appends a field prefixed by its big-endian length.
+	fn field(bytes: &mut Vec<u8>, field: &[u8]) {
+		bytes.extend_from_slice(&u16::try_from(field.len()).unwrap().to_be_bytes());
+		bytes.extend_from_slice(field);
+	}
+
+	/// An Xauthority file with two local entries and an IPv6 entry, as written
+	/// by `xauth`.
+	fn fixture() -> Vec<u8> {
+		let mut bytes = vec![];
+
+		// FamilyLocal, `myhost:0`
+		bytes.extend_from_slice(&[0x01, 0x00]);
+		field(&mut bytes, b"myhost");
+		field(&mut bytes, b"0");
+		field(&mut bytes, b"MIT-MAGIC-COOKIE-1");
+		field(&mut bytes, &COOKIE);
+
+		// FamilyLocal, `myhost:1`
+		bytes.extend_from_slice(&[0x01, 0x00]);
+		field(&mut bytes, b"myhost");
+		field(&mut bytes, b"1");
+		field(&mut bytes, b"MIT-MAGIC-COOKIE-1");
+		field(&mut bytes, &OTHER_COOKIE);
+
+		// FamilyInternet6, `[::1]:0`
+		bytes.extend_from_slice(&[0x00, 0x06]);
+		field(&mut bytes, &LOCALHOST_V6);
+		field(&mut bytes, b"0");
+		field(&mut bytes, b"MIT-MAGIC-COOKIE-1");
+		field(&mut bytes, &COOKIE);
+
+		bytes
+	}
+
+	fn entry(family: Family, address: &[u8], display: &str, name: &str, data: &[u8]) -> AuthEntry {
+		AuthEntry {
+			family,
+			address: address.to_vec(),
+			display_number: display.to_owned(),
+			name: name.to_owned(),
+			data: data.to_vec(),
+		}
+	}
+
+	#[test]
+	fn test_read_fixture() {
+		let bytes = fixture();
+		let entries = read_entries(&mut &bytes[..]).unwrap();
+
+		assert_eq!(
+			entries,
+			[
+				entry(Family::Local, b"myhost", "0", "MIT-MAGIC-COOKIE-1", &COOKIE),
+				entry(
+					Family::Local,
+					b"myhost",
+					"1",
+					"MIT-MAGIC-COOKIE-1",
+					&OTHER_COOKIE
+				),
+				entry(
+					Family::InternetV6,
+					&LOCALHOST_V6,
+					"0",
+					"MIT-MAGIC-COOKIE-1",
+					&COOKIE
+				),
+			]
+		);
+
+		let mut written = vec![];
+		write_entries(&entries, &mut written).unwrap();
+
+		assert_eq!(written, bytes);
+		assert_eq!(
+			entries.iter().map(X11Size::x11_size).sum::<usize>(),
+			bytes.len()
+		);
+	}
+
+	#[test]
+	fn test_read_empty() {
+		assert_eq!(read_entries(&mut &[][..]).unwrap(), []);
+	}
+
+	#[test]
+	fn test_read_truncated() {
+		let bytes = fixture();
+
+		// The offsets at which each entry ends.
+		let boundaries: Vec<_> = read_entries(&mut &bytes[..])
+			.unwrap()
+			.iter()
+			.scan(0, |end, entry| {
+				*end += entry.x11_size();
+				Some(*end)
+			})
+			.collect();
+
+		// A file cut off anywhere but between entries is incomplete.
+		for length in (1..bytes.len()).filter(|length| !boundaries.contains(length)) {
+			assert!(
+				matches!(
+					read_entries(&mut &bytes[..length]),
+					Err(ReadError::NotEnoughData { .. })
+				),
+				"{length}"
+			);
+		}
+
+		for (count, length) in boundaries.into_iter().enumerate() {
+			assert_eq!(
+				read_entries(&mut &bytes[..length]).unwrap().len(),
+				count + 1
+			);
+		}
+	}
+
+	#[test]
+	fn test_write_field_too_long() {
+		let entry = entry(
+			Family::Local,
+			b"myhost",
+			"0",
+			"MIT-MAGIC-COOKIE-1",
+			&[0; 65536],
+		);
+
+		assert!(matches!(
+			entry.write_to(&mut vec![]),
+			Err(WriteError::FailedConversion(_))
+		));
+	}
+
+	#[test]
+	fn test_find_entry() {
+		let bytes = fixture();
+		let entries = read_entries(&mut &bytes[..]).unwrap();
+		let cookie = ["MIT-MAGIC-COOKIE-1"];
+
+		let found = find_entry(&entries, Family::Local, b"myhost", 1, &cookie).unwrap();
+		assert_eq!(found.data, OTHER_COOKIE);
+
+		let found = find_entry(&entries, Family::InternetV6, &LOCALHOST_V6, 0, &[]).unwrap();
+		assert_eq!(found, &entries[2]);
+
+		assert_eq!(
+			find_entry(&entries, Family::Local, b"otherhost", 0, &cookie),
+			None
+		);
+		assert_eq!(
+			find_entry(&entries, Family::Local, b"myhost", 2, &cookie),
+			None
+		);
+		assert_eq!(
+			find_entry(
+				&entries,
+				Family::Local,
+				b"myhost",
+				0,
+				&["XDM-AUTHORIZATION-1"]
+			),
+			None
+		);
+	}
+
+	#[test]
+	fn test_find_entry_wildcards() {
+		let entries = [
+			entry(Family::Local, b"myhost", "0", "XDM-AUTHORIZATION-1", &[1]),
+			entry(Family::Local, b"", "", "MIT-MAGIC-COOKIE-1", &[2]),
+			entry(Family::Wild, b"", "", "MIT-MAGIC-COOKIE-1", &[3]),
+		];
+
+		// An empty address and display number match any of the same family.
+		let found = find_entry(&entries, Family::Local, b"otherhost", 7, &[]).unwrap();
+		assert_eq!(found.data, [2]);
+
+		// `Family::Wild` matches any family.
+		let found = find_entry(&entries, Family::Internet, &[127, 0, 0, 1], 0, &[]).unwrap();
+		assert_eq!(found.data, [3]);
+
+		// Earlier names are preferred, regardless of the order of entries.
+		let found = find_entry(
+			&entries,
+			Family::Local,
+			b"myhost",
+			0,
+			&["MIT-MAGIC-COOKIE-1", "XDM-AUTHORIZATION-1"],
+		)
+		.unwrap();
+		assert_eq!(found.data, [2]);
+
+		let found = find_entry(&entries, Family::Local, b"myhost", 0, &[]).unwrap();
+		assert_eq!(found.data, [1]);
+	}
+}
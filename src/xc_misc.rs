@@ -0,0 +1,97 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Messages defined in the [XC-MISC extension]: [requests] and [replies].
+//!
+//! The XC-MISC extension allows a client which has used up its range of
+//! resource IDs to ask the X server for resource IDs which are no longer in
+//! use.
+//!
+//! [XC-MISC extension]: https://www.x.org/releases/X11R7.7/doc/xcmiscproto/xc-misc.html
+//! [requests]: request
+//! [replies]: reply
+
+use crate::message::Extension;
+
+pub mod reply;
+pub mod request;
+
+/// The XC-MISC extension.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct XcMisc;
+
+impl Extension for XcMisc {
+	const NAME: &'static str = "XC-MISC";
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::message::ExtensionCodes;
+	use xrbk::{Readable, Writable};
+
+	#[test]
+	fn test_get_resource_id_list_layout() {
+		let codes = ExtensionCodes {
+			major_opcode: 140,
+			first_event: 0,
+			first_error: 0,
+		};
+
+		let mut bytes = vec![];
+		codes
+			.write_request(&request::GetResourceIdList { count: 3 }, &mut bytes)
+			.unwrap();
+
+		assert_eq!(bytes, [140, 2, 0, 2, 0, 0, 0, 3]);
+	}
+
+	#[test]
+	fn test_get_resource_id_list_reply_round_trip() {
+		for ids in [
+			vec![],
+			vec![0x0060_0000],
+			(0..100).map(|id| 0x0060_0000 | id).collect(),
+		] {
+			let reply = reply::GetResourceIdList {
+				sequence: 4,
+				ids: ids.clone(),
+			};
+
+			let mut bytes = vec![];
+			reply.write_to(&mut bytes).unwrap();
+
+			assert_eq!(bytes.len(), 32 + 4 * ids.len());
+
+			// The first byte, which identifies the message as a reply, is read
+			// before the reply itself.
+			let read = reply::GetResourceIdList::read_from(&mut &bytes[1..]).unwrap();
+
+			assert_eq!(read, reply);
+			assert_eq!(read.ids, ids);
+		}
+	}
+
+	#[test]
+	fn test_get_resource_id_range_reply_round_trip() {
+		let reply = reply::GetResourceIdRange {
+			sequence: 2,
+			start_id: 0x0060_0000,
+			count: 0x0010_0000,
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32);
+		assert_eq!(
+			reply::GetResourceIdRange::read_from(&mut &bytes[1..]).unwrap(),
+			reply,
+		);
+	}
+}
@@ -0,0 +1,126 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [Replies] defined in the [XC-MISC extension].
+//!
+//! [Replies] are messages sent from the X server to an X client in response to
+//! a [request].
+//!
+//! [Replies]: Reply
+//! [request]: crate::message::Request
+//! [XC-MISC extension]: super
+
+extern crate self as xrb;
+
+use derivative::Derivative;
+
+use xrbk_macro::derive_xrb;
+
+use crate::{message::Reply, xc_misc::request};
+
+derive_xrb! {
+	/// The [reply] to a [`GetVersion` request].
+	///
+	/// [reply]: Reply
+	///
+	/// [`GetVersion` request]: request::GetVersion
+	#[doc(alias("XCMiscGetVersion"))]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[derivative(Hash, PartialEq, Eq)]
+	pub struct GetVersion: Reply for request::GetVersion {
+		/// The sequence number identifying the [request] that generated this
+		/// [reply].
+		///
+		/// See [`Reply::sequence`] for more information.
+		///
+		/// [request]: crate::message::Request
+		/// [reply]: Reply
+		///
+		/// [`Reply::sequence`]: Reply::sequence
+		#[sequence]
+		#[derivative(Hash = "ignore", PartialEq = "ignore")]
+		pub sequence: u16,
+
+		/// The major version of the XC-MISC extension supported by the X
+		/// server.
+		#[doc(alias("server_major_version"))]
+		pub major_version: u16,
+		/// The minor version of the XC-MISC extension supported by the X
+		/// server.
+		#[doc(alias("server_minor_version"))]
+		pub minor_version: u16,
+		[_; ..],
+	}
+
+	/// The [reply] to a [`GetResourceIdRange` request].
+	///
+	/// [reply]: Reply
+	///
+	/// [`GetResourceIdRange` request]: request::GetResourceIdRange
+	#[doc(alias("GetXIDRange", "XCMiscGetXIDRange"))]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[derivative(Hash, PartialEq, Eq)]
+	pub struct GetResourceIdRange: Reply for request::GetResourceIdRange {
+		/// The sequence number identifying the [request] that generated this
+		/// [reply].
+		///
+		/// See [`Reply::sequence`] for more information.
+		///
+		/// [request]: crate::message::Request
+		/// [reply]: Reply
+		///
+		/// [`Reply::sequence`]: Reply::sequence
+		#[sequence]
+		#[derivative(Hash = "ignore", PartialEq = "ignore")]
+		pub sequence: u16,
+
+		/// The first resource ID in the range.
+		pub start_id: u32,
+		/// The number of resource IDs in the range.
+		///
+		/// If this is `0`, there are no unused resource IDs left.
+		pub count: u32,
+		[_; ..],
+	}
+
+	/// The [reply] to a [`GetResourceIdList` request].
+	///
+	/// [reply]: Reply
+	///
+	/// [`GetResourceIdList` request]: request::GetResourceIdList
+	#[doc(alias("GetXIDList", "XCMiscGetXIDList"))]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[derivative(Hash, PartialEq, Eq)]
+	pub struct GetResourceIdList: Reply for request::GetResourceIdList {
+		/// The sequence number identifying the [request] that generated this
+		/// [reply].
+		///
+		/// See [`Reply::sequence`] for more information.
+		///
+		/// [request]: crate::message::Request
+		/// [reply]: Reply
+		///
+		/// [`Reply::sequence`]: Reply::sequence
+		#[sequence]
+		#[derivative(Hash = "ignore", PartialEq = "ignore")]
+		pub sequence: u16,
+
+		// The length of `ids`.
+		#[allow(clippy::cast_possible_truncation)]
+		let ids_len: u32 = ids => ids.len() as u32,
+		[_; 20],
+
+		/// The unused resource IDs.
+		///
+		/// There may be fewer resource IDs than the `count` requested in the
+		/// [`GetResourceIdList` request].
+		///
+		/// [`GetResourceIdList` request]: request::GetResourceIdList
+		#[context(ids_len => *ids_len as usize)]
+		pub ids: Vec<u32>,
+	}
+}
@@ -0,0 +1,87 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [Requests] defined in the [XC-MISC extension].
+//!
+//! [Requests] are messages sent from an X client to the X server.
+//!
+//! The [major opcode] of these [requests] is assigned by the X server: they
+//! must be written with [`ExtensionCodes::write_request`].
+//!
+//! [Requests]: Request
+//! [requests]: Request
+//! [XC-MISC extension]: super
+//! [major opcode]: Request::MAJOR_OPCODE
+//! [`ExtensionCodes::write_request`]: crate::message::ExtensionCodes::write_request
+
+extern crate self as xrb;
+
+use xrbk_macro::derive_xrb;
+
+use crate::{
+	message::{Request, UNASSIGNED_MAJOR_OPCODE},
+	xc_misc::reply,
+};
+
+derive_xrb! {
+	/// A [request] that returns the version of the XC-MISC extension
+	/// supported by the X server.
+	///
+	/// # Replies
+	/// This [request] generates a [`GetVersion` reply].
+	///
+	/// [request]: Request
+	///
+	/// [`GetVersion` reply]: reply::GetVersion
+	#[doc(alias("XCMiscGetVersion"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct GetVersion: Request(UNASSIGNED_MAJOR_OPCODE, 0) -> reply::GetVersion {
+		/// The major version of the XC-MISC extension supported by this
+		/// client.
+		#[doc(alias("client_major_version"))]
+		pub major_version: u16,
+		/// The minor version of the XC-MISC extension supported by this
+		/// client.
+		#[doc(alias("client_minor_version"))]
+		pub minor_version: u16,
+	}
+
+	/// A [request] that returns a range of consecutive resource IDs which
+	/// are not in use.
+	///
+	/// # Replies
+	/// This [request] generates a [`GetResourceIdRange` reply].
+	///
+	/// [request]: Request
+	///
+	/// [`GetResourceIdRange` reply]: reply::GetResourceIdRange
+	#[doc(alias("GetXIDRange", "XCMiscGetXIDRange"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct GetResourceIdRange: Request(
+		UNASSIGNED_MAJOR_OPCODE,
+		1,
+	) -> reply::GetResourceIdRange;
+
+	/// A [request] that returns up to `count` resource IDs which are not in
+	/// use.
+	///
+	/// # Replies
+	/// This [request] generates a [`GetResourceIdList` reply].
+	///
+	/// [request]: Request
+	///
+	/// [`GetResourceIdList` reply]: reply::GetResourceIdList
+	#[doc(alias("GetXIDList", "XCMiscGetXIDList"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct GetResourceIdList: Request(
+		UNASSIGNED_MAJOR_OPCODE,
+		2,
+	) -> reply::GetResourceIdList {
+		/// The maximum number of resource IDs to return.
+		pub count: u32,
+	}
+}
@@ -0,0 +1,162 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Messages defined in the [XFixes extension]: [requests] and [replies].
+//!
+//! Only the [regions] of the XFixes extension are currently defined: they are
+//! used by the [Damage extension] to report and repair damaged areas.
+//!
+//! A [`QueryVersion` request] must be sent before any other XFixes [requests].
+//!
+//! [XFixes extension]: https://gitlab.freedesktop.org/xorg/proto/xorgproto/-/blob/master/fixesproto.txt
+//! [requests]: request
+//! [replies]: reply
+//! [regions]: Region
+//! [Damage extension]: crate::damage
+//! [`QueryVersion` request]: request::QueryVersion
+
+extern crate self as xrb;
+
+use derive_more::{From, Into};
+
+use xrbk_macro::{new, unwrap, ConstantX11Size, Readable, Wrap, Writable, X11Size};
+
+use crate::message::Extension;
+
+pub mod reply;
+pub mod request;
+
+/// The XFixes extension.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct XFixes;
+
+impl Extension for XFixes {
+	const NAME: &'static str = "XFIXES";
+}
+
+/// A resource ID referring to a region: an area made up of a list of
+/// [rectangles].
+///
+/// [rectangles]: crate::Rectangle
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	From,
+	Into,
+	// `new` and `unwrap` const fns
+	new,
+	unwrap,
+	// XRBK traits
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	Wrap,
+)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
+pub struct Region(u32);
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{message::ExtensionCodes, unit::Px, Rectangle};
+	use xrbk::{Readable, Writable};
+
+	const CODES: ExtensionCodes = ExtensionCodes {
+		major_opcode: 138,
+		first_event: 87,
+		first_error: 140,
+	};
+
+	fn round_trip<R>(request: &R) -> Vec<u8>
+	where
+		R: crate::message::Request + Readable + Writable + PartialEq + std::fmt::Debug,
+	{
+		let mut bytes = vec![];
+		CODES.write_request(request, &mut bytes).unwrap();
+		assert_eq!(request.x11_size(), bytes.len());
+
+		// The major and minor opcodes are read before the request itself.
+		assert_eq!(&R::read_from(&mut &bytes[2..]).unwrap(), request);
+
+		bytes
+	}
+
+	#[test]
+	fn test_create_region_layout() {
+		let request = request::CreateRegion {
+			region: Region::new(0x0020_0001),
+			rectangles: vec![
+				Rectangle::new(Px(1), Px(2), Px(3), Px(4)),
+				Rectangle::new(Px(-5), Px(6), Px(7), Px(8)),
+			],
+		};
+
+		#[rustfmt::skip]
+		assert_eq!(round_trip(&request), [
+			138, 5, 0, 6,
+			0x00, 0x20, 0x00, 0x01,
+			0, 1, 0, 2, 0, 3, 0, 4,
+			0xff, 0xfb, 0, 6, 0, 7, 0, 8,
+		]);
+	}
+
+	#[test]
+	fn test_requests_round_trip() {
+		assert_eq!(
+			round_trip(&request::QueryVersion {
+				major_version: 5,
+				minor_version: 0,
+			})
+			.len(),
+			12,
+		);
+		round_trip(&request::CreateRegion {
+			region: Region::new(1),
+			rectangles: vec![],
+		});
+		round_trip(&request::DestroyRegion {
+			region: Region::new(1),
+		});
+		round_trip(&request::SetRegion {
+			region: Region::new(1),
+			rectangles: vec![Rectangle::new(Px(0), Px(0), Px(640), Px(480)); 3],
+		});
+		round_trip(&request::UnionRegion {
+			first_source: Region::new(1),
+			second_source: Region::new(2),
+			destination: Region::new(3),
+		});
+	}
+
+	#[test]
+	fn test_query_version_reply_round_trip() {
+		let reply = reply::QueryVersion {
+			sequence: 1,
+			major_version: 5,
+			minor_version: 0,
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 32);
+		assert_eq!(
+			reply::QueryVersion::read_from(&mut &bytes[1..]).unwrap(),
+			reply,
+		);
+	}
+}
@@ -0,0 +1,204 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [Requests] defined in the [XFixes extension].
+//!
+//! [Requests] are messages sent from an X client to the X server.
+//!
+//! The [major opcode] of these [requests] is assigned by the X server: they
+//! must be written with [`ExtensionCodes::write_request`].
+//!
+//! [Requests]: Request
+//! [requests]: Request
+//! [XFixes extension]: super
+//! [major opcode]: Request::MAJOR_OPCODE
+//! [`ExtensionCodes::write_request`]: crate::message::ExtensionCodes::write_request
+
+extern crate self as xrb;
+
+use xrbk::ConstantX11Size;
+use xrbk_macro::derive_xrb;
+
+use crate::{
+	message::{Request, UNASSIGNED_MAJOR_OPCODE},
+	x11::error,
+	xfixes::{reply, Region},
+	Rectangle,
+};
+
+macro_rules! request_error {
+	(
+		$(#[$meta:meta])*
+		$vis:vis enum $Name:ident for $Request:ty {
+			$($($Error:ident),+$(,)?)?
+		}
+	) => {
+		#[doc = concat!(
+			"An [error](crate::message::Error) generated because of a failed [`",
+			stringify!($Request),
+			"` request](",
+			stringify!($Request),
+			")."
+		)]
+		#[doc = ""]
+		$(#[$meta])*
+		$vis enum $Name {
+			$($(
+				#[doc = concat!(
+					"A [`",
+					stringify!($Error),
+					"` error](error::",
+					stringify!($Error),
+					")."
+				)]
+				$Error(error::$Error)
+			),+)?
+		}
+
+		impl TryFrom<crate::x11::AnyError> for $Name {
+			type Error = crate::x11::AnyError;
+
+			fn try_from(error: crate::x11::AnyError) -> Result<Self, Self::Error> {
+				match error {
+					$($(crate::x11::AnyError::$Error(error) => Ok(Self::$Error(error)),)+)?
+
+					other => Err(other),
+				}
+			}
+		}
+	};
+}
+
+request_error! {
+	pub enum CreateRegionError for CreateRegion {
+		ResourceIdChoice,
+		Value,
+	}
+}
+
+// FIXME: the XFixes extension also defines a `Region` error, generated when a
+//        `Region` does not refer to a defined region, which is not yet
+//        represented in these request errors.
+
+derive_xrb! {
+	/// A [request] that returns the version of the XFixes extension supported
+	/// by the X server.
+	///
+	/// This [request] must be sent before any other XFixes [requests].
+	///
+	/// # Replies
+	/// This [request] generates a [`QueryVersion` reply].
+	///
+	/// [request]: Request
+	/// [requests]: Request
+	///
+	/// [`QueryVersion` reply]: reply::QueryVersion
+	#[doc(alias("XFixesQueryVersion"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct QueryVersion: Request(UNASSIGNED_MAJOR_OPCODE, 0) -> reply::QueryVersion {
+		/// The major version of the XFixes extension supported by this client.
+		pub major_version: u32,
+		/// The minor version of the XFixes extension supported by this client.
+		pub minor_version: u32,
+	}
+
+	/// A [request] that creates a new [region] made up of the given
+	/// `rectangles`, and assigns the provided [`Region` ID][region] to it.
+	///
+	/// # Errors
+	/// A [`ResourceIdChoice` error] is generated if `region` specifies an ID
+	/// already used for another resource, or an ID not allocated to your
+	/// client.
+	///
+	/// A [`Value` error] is generated if any of the `rectangles` are invalid.
+	///
+	/// [region]: Region
+	/// [request]: Request
+	///
+	/// [`ResourceIdChoice` error]: error::ResourceIdChoice
+	/// [`Value` error]: error::Value
+	#[doc(alias("XFixesCreateRegion"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct CreateRegion: Request(UNASSIGNED_MAJOR_OPCODE, 5, CreateRegionError) {
+		/// The [`Region` ID][region] which is to be assigned to the [region].
+		///
+		/// [region]: Region
+		pub region: Region,
+
+		/// The [rectangles] which make up the [region].
+		///
+		/// [rectangles]: Rectangle
+		/// [region]: Region
+		#[context(self::remaining => remaining / Rectangle::X11_SIZE)]
+		pub rectangles: Vec<Rectangle>,
+	}
+
+	/// A [request] that deletes the given [region].
+	///
+	/// [region]: Region
+	/// [request]: Request
+	#[doc(alias("XFixesDestroyRegion"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct DestroyRegion: Request(UNASSIGNED_MAJOR_OPCODE, 10) {
+		/// The [region] which is to be deleted.
+		///
+		/// [region]: Region
+		pub region: Region,
+	}
+
+	/// A [request] that replaces the [rectangles] of the given [region] with
+	/// the given `rectangles`.
+	///
+	/// [rectangles]: Rectangle
+	/// [region]: Region
+	/// [request]: Request
+	#[doc(alias("XFixesSetRegion"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct SetRegion: Request(UNASSIGNED_MAJOR_OPCODE, 11) {
+		/// The [region] which is to be changed.
+		///
+		/// [region]: Region
+		pub region: Region,
+
+		/// The [rectangles] which are to make up the [region].
+		///
+		/// [rectangles]: Rectangle
+		/// [region]: Region
+		#[context(self::remaining => remaining / Rectangle::X11_SIZE)]
+		pub rectangles: Vec<Rectangle>,
+	}
+
+	/// A [request] that sets the `destination` [region] to the union of the
+	/// two given source [regions].
+	///
+	/// The `destination` may be the same [region] as either source.
+	///
+	/// [region]: Region
+	/// [regions]: Region
+	/// [request]: Request
+	#[doc(alias("XFixesUnionRegion"))]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct UnionRegion: Request(UNASSIGNED_MAJOR_OPCODE, 13) {
+		/// The first [region] of the union.
+		///
+		/// [region]: Region
+		#[doc(alias("source1"))]
+		pub first_source: Region,
+		/// The second [region] of the union.
+		///
+		/// [region]: Region
+		#[doc(alias("source2"))]
+		pub second_source: Region,
+
+		/// The [region] which is set to the union of the two sources.
+		///
+		/// [region]: Region
+		pub destination: Region,
+	}
+}
@@ -34,6 +34,9 @@ use std::{
 };
 
 pub use bytes::{Buf, BufMut};
+pub use trace::{Trace, TraceField, TraceWrite};
+pub use vectored::{Vectored, WritableVectored};
+pub use verbatim::Verbatim;
 
 use thiserror::Error;
 
@@ -51,6 +54,99 @@ pub fn pad<T: X11Size>(value: &T) -> usize {
 	(ALIGNMENT - (x11_size % ALIGNMENT)) % ALIGNMENT
 }
 
+/// Returns [`ReadError::NotEnoughData`] if fewer than `expected` bytes remain
+/// in the given `buf`.
+///
+/// [`Buf`]'s `get_*` and `advance` methods panic if there are not enough
+/// bytes remaining; this should be called before using them on data which may
+/// be truncated or corrupted.
+///
+/// # Errors
+/// Returns [`ReadError::NotEnoughData`] if `buf.remaining() < expected`.
+pub fn ensure_remaining(buf: &impl Buf, expected: usize) -> ReadResult<()> {
+	let found = buf.remaining();
+
+	if found < expected {
+		Err(ReadError::NotEnoughData { expected, found })
+	} else {
+		Ok(())
+	}
+}
+
+/// Skips `count` bytes in the given `buf`.
+///
+/// This is the checked equivalent of [`Buf::advance`].
+///
+/// # Errors
+/// Returns [`ReadError::NotEnoughData`] if `buf.remaining() < count`.
+pub fn skip(buf: &mut impl Buf, count: usize) -> ReadResult<()> {
+	ensure_remaining(buf, count)?;
+	buf.advance(count);
+
+	Ok(())
+}
+
+/// Splits the first `count` bytes off of the given `buf`, returning them
+/// without copying.
+///
+/// # Errors
+/// Returns [`ReadError::NotEnoughData`] if `buf.len() < count`.
+pub fn take_bytes<'a>(buf: &mut &'a [u8], count: usize) -> ReadResult<&'a [u8]> {
+	ensure_remaining(buf, count)?;
+
+	let (bytes, rest) = buf.split_at(count);
+	*buf = rest;
+
+	Ok(bytes)
+}
+
+/// Reads `count` bytes of padding from the given `buf`.
+///
+/// In [`ReadMode::Lenient`], the padding bytes are skipped without being
+/// checked. In [`ReadMode::Strict`], each padding byte must be zero.
+///
+/// # Errors
+/// - [`ReadError::NotEnoughData`]: `buf.remaining() < count`.
+/// - [`ReadError::NonZeroPadding`]: `mode` is [`ReadMode::Strict`] and one of
+///   the padding bytes was not zero.
+pub fn read_padding(buf: &mut impl Buf, count: usize, mode: ReadMode) -> ReadResult<()> {
+	match mode {
+		ReadMode::Lenient => skip(buf, count),
+
+		ReadMode::Strict => {
+			ensure_remaining(buf, count)?;
+
+			for _ in 0..count {
+				match buf.get_u8() {
+					0 => {},
+					found => return Err(ReadError::NonZeroPadding(found)),
+				}
+			}
+
+			Ok(())
+		},
+	}
+}
+
+/// How strictly unused bytes are treated when reading.
+///
+/// The X11 protocol requires clients to write zeros in unused bytes, but
+/// servers are not required to do the same. [`ReadMode::Lenient`] accepts
+/// any value in unused bytes, while [`ReadMode::Strict`] rejects non-zero
+/// unused bytes.
+///
+/// To reproduce the exact bytes of a message when it is written again,
+/// including any non-zero unused bytes, read it as a [`Verbatim`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ReadMode {
+	/// Unused bytes are skipped without being checked.
+	#[default]
+	Lenient,
+	/// Unused bytes must be zero, else [`ReadError::NonZeroPadding`] is
+	/// returned.
+	Strict,
+}
+
 pub type ReadResult<T> = Result<T, ReadError>;
 pub type WriteResult = Result<(), WriteError>;
 
@@ -63,6 +159,19 @@ pub enum ReadError {
 	#[error("unrecognized variant discriminant: {0}")]
 	UnrecognizedDiscriminant(usize),
 
+	/// There were fewer bytes remaining than were required to read a value.
+	#[error("not enough data: expected {expected} bytes, found {found}")]
+	NotEnoughData {
+		/// The number of bytes that were required.
+		expected: usize,
+		/// The number of bytes that were remaining.
+		found: usize,
+	},
+
+	/// A padding byte was not zero while reading in [`ReadMode::Strict`].
+	#[error("non-zero padding byte: {0:#04x}")]
+	NonZeroPadding(u8),
+
 	#[error("a conversion failed")]
 	FailedConversion(Box<dyn Any>),
 	#[error("{0}")]
@@ -79,6 +188,9 @@ pub enum WriteError {
 }
 
 mod readable;
+mod trace;
+mod vectored;
+mod verbatim;
 mod wrap;
 mod writable;
 mod x11_size;
@@ -123,12 +235,38 @@ pub trait Readable: X11Size {
 	///
 	/// - [`ReadError::UnrecognizedDiscriminant`]: The value encountered is not
 	///   matching any enum's variants discriminant.
+	/// - [`ReadError::NotEnoughData`]: The `buf` ended before the value was
+	///   completely read.
 	/// - [`ReadError::Other`]: Any other error when parsing.
 	///
 	/// [`Buf`]: Buf
 	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
 	where
 		Self: Sized;
+
+	/// Reads [`Self`] from a [`Buf`] of bytes, treating unused bytes
+	/// according to the given [`ReadMode`].
+	///
+	/// By default, this ignores the `mode` and calls [`read_from`]. Types
+	/// which contain unused bytes override this to check them in
+	/// [`ReadMode::Strict`], and to pass the `mode` on to the types they
+	/// contain.
+	///
+	/// # Errors
+	///
+	/// The same errors as [`read_from`], as well as:
+	/// - [`ReadError::NonZeroPadding`]: `mode` is [`ReadMode::Strict`] and an
+	///   unused byte was not zero.
+	///
+	/// [`read_from`]: Readable::read_from
+	fn read_from_with_mode(buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		let _ = mode;
+
+		Self::read_from(buf)
+	}
 }
 
 /// Allows the reading of a type from bytes given some additional
@@ -147,12 +285,70 @@ pub trait ReadableWithContext: X11Size {
 	///
 	/// - [`ReadError::UnrecognizedDiscriminant`]: The value encountered is not
 	///   matching any enum's variants discriminant.
+	/// - [`ReadError::NotEnoughData`]: The `buf` ended before the value was
+	///   completely read.
 	/// - [`ReadError::Other`]: Any other error when parsing.
 	///
 	/// [`Buf`]: Buf
 	fn read_with(buf: &mut impl Buf, context: &Self::Context) -> ReadResult<Self>
 	where
 		Self: Sized;
+
+	/// Reads [`Self`] from a [`Buf`] of bytes, given some additional
+	/// [`Context`](Self::Context), treating unused bytes according to the
+	/// given [`ReadMode`].
+	///
+	/// By default, this ignores the `mode` and calls [`read_with`].
+	///
+	/// # Errors
+	///
+	/// The same errors as [`read_with`], as well as:
+	/// - [`ReadError::NonZeroPadding`]: `mode` is [`ReadMode::Strict`] and an
+	///   unused byte was not zero.
+	///
+	/// [`read_with`]: ReadableWithContext::read_with
+	fn read_with_mode(
+		buf: &mut impl Buf, context: &Self::Context, mode: ReadMode,
+	) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		let _ = mode;
+
+		Self::read_with(buf, context)
+	}
+}
+
+/// Allows a type to be read from a contiguous slice of bytes, borrowing from
+/// it rather than copying.
+///
+/// This is implemented for types with large payloads, like image data, which
+/// would otherwise be copied into a [`Vec`] when read with [`Readable`].
+pub trait ReadableRef<'a>: Sized {
+	/// Reads [`Self`] from a slice of bytes, advancing the slice past the bytes
+	/// that were read.
+	///
+	/// # Errors
+	///
+	/// The same errors as [`Readable::read_from`].
+	fn read_ref(buf: &mut &'a [u8]) -> ReadResult<Self>;
+
+	/// Reads [`Self`] from a slice of bytes, advancing the slice past the bytes
+	/// that were read, treating unused bytes according to the given
+	/// [`ReadMode`].
+	///
+	/// By default, this ignores the `mode` and calls [`read_ref`].
+	///
+	/// # Errors
+	///
+	/// The same errors as [`Readable::read_from_with_mode`].
+	///
+	/// [`read_ref`]: ReadableRef::read_ref
+	fn read_ref_with_mode(buf: &mut &'a [u8], mode: ReadMode) -> ReadResult<Self> {
+		let _ = mode;
+
+		Self::read_ref(buf)
+	}
 }
 
 /// Allows a type to be written as bytes.
@@ -193,7 +389,7 @@ where
 	where
 		Self: Sized,
 	{
-		Ok(match <T::Integer>::read_from(buf).unwrap() {
+		Ok(match <T::Integer>::read_from(buf)? {
 			discrim if discrim.into() == 0_u64 => None,
 			value => Some(match T::try_from(value) {
 				Ok(value) => value,
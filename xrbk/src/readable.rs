@@ -4,7 +4,7 @@
 
 //! [`Readable`] implementations for primitive types
 
-use crate::{ReadResult, Readable, ReadableWithContext, X11Size};
+use crate::{ReadMode, ReadResult, Readable, ReadableWithContext, X11Size};
 use bytes::Buf;
 use std::ops::{Range, RangeInclusive};
 
@@ -13,6 +13,8 @@ macro_rules! implement {
 		$(
 			impl $crate::Readable for $ty {
 				fn read_from($reader: &mut impl bytes::Buf) -> Result<Self, $crate::ReadError> {
+					$crate::ensure_remaining($reader, std::mem::size_of::<$ty>())?;
+
 					Ok($expr)
 				}
 			}
@@ -41,13 +43,20 @@ implement! {
 
 impl<T: Readable, const N: usize> Readable for [T; N] {
 	fn read_from(reader: &mut impl Buf) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		Self::read_from_with_mode(reader, ReadMode::Lenient)
+	}
+
+	fn read_from_with_mode(reader: &mut impl Buf, mode: ReadMode) -> ReadResult<Self>
 	where
 		Self: Sized,
 	{
 		let mut vec = Vec::new();
 
 		for _ in 0..N {
-			vec.push(T::read_from(reader)?);
+			vec.push(T::read_from_with_mode(reader, mode)?);
 		}
 
 		Ok(vec
@@ -63,19 +72,35 @@ impl<T: Readable> Readable for Box<T> {
 	{
 		Ok(Self::new(T::read_from(reader)?))
 	}
+
+	fn read_from_with_mode(reader: &mut impl Buf, mode: ReadMode) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		Ok(Self::new(T::read_from_with_mode(reader, mode)?))
+	}
 }
 
 impl<T: Readable> ReadableWithContext for Vec<T> {
 	type Context = usize;
 
 	fn read_with(reader: &mut impl Buf, context: &Self::Context) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		Self::read_with_mode(reader, context, ReadMode::Lenient)
+	}
+
+	fn read_with_mode(
+		reader: &mut impl Buf, context: &Self::Context, mode: ReadMode,
+	) -> ReadResult<Self>
 	where
 		Self: Sized,
 	{
 		let mut vec = Self::new();
 
 		for _ in 0..*context {
-			vec.push(T::read_from(reader)?);
+			vec.push(T::read_from_with_mode(reader, mode)?);
 		}
 
 		Ok(vec)
@@ -0,0 +1,309 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Annotated hex dumps of values' bytes, for debugging the protocol.
+
+use crate::{ReadError, ReadResult, Readable, Writable, WriteError, WriteResult};
+use std::{
+	fmt,
+	fmt::{Display, Formatter},
+	ops::Range,
+};
+
+/// The number of bytes shown on each line of a [`Trace`]'s hex dump.
+const BYTES_PER_LINE: usize = 8;
+
+/// A value which can be written while recording which bytes encode each of its
+/// fields.
+///
+/// This is implemented by `derive_xrb!` for definitions which derive
+/// [`Writable`], if the crate using it enables its `trace` feature.
+pub trait TraceWrite: Writable {
+	/// The number of bytes preceding the bytes read by [`Readable`].
+	///
+	/// For example, the [`Readable`] implementations of messages do not read
+	/// their first byte, as that is used to determine which message is being
+	/// read.
+	const READ_OFFSET: usize = 0;
+
+	/// Writes `self` to the given `trace`, recording each of its fields.
+	///
+	/// The bytes written are the same as those written by
+	/// [`Writable::write_to`].
+	///
+	/// # Errors
+	/// Returns a [`WriteError`] if it was not able to properly write `self`.
+	fn trace_write(&self, trace: &mut Trace) -> WriteResult;
+}
+
+/// A field recorded in a [`Trace`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TraceField {
+	name: &'static str,
+	range: Range<usize>,
+	value: Option<String>,
+}
+
+impl TraceField {
+	/// Returns the name of the field.
+	///
+	/// Unused bytes are named `unused`.
+	#[must_use]
+	pub const fn name(&self) -> &'static str {
+		self.name
+	}
+
+	/// Returns the range of bytes which encode the field.
+	#[must_use]
+	pub fn range(&self) -> Range<usize> {
+		self.range.clone()
+	}
+
+	/// Returns the rendered value of the field, or [`None`] if the bytes are
+	/// unused.
+	#[must_use]
+	pub fn value(&self) -> Option<&str> {
+		self.value.as_deref()
+	}
+
+	/// Returns whether the field's bytes are unused.
+	#[must_use]
+	pub const fn is_unused(&self) -> bool {
+		self.value.is_none()
+	}
+}
+
+/// The bytes of a value, annotated with the fields they encode.
+///
+/// A `Trace`'s [`Display`] implementation formats it as a hex dump in which
+/// each line shows the offset of the bytes, the bytes themselves, and the
+/// field they encode.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Trace {
+	bytes: Vec<u8>,
+	fields: Vec<TraceField>,
+}
+
+impl Trace {
+	/// Creates a new, empty `Trace`.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			bytes: Vec::new(),
+			fields: Vec::new(),
+		}
+	}
+
+	/// Writes the given `value` to a new `Trace`.
+	///
+	/// # Errors
+	/// Returns a [`WriteError`] if it was not able to properly write the
+	/// `value`.
+	pub fn write<T: TraceWrite>(value: &T) -> Result<Self, WriteError> {
+		let mut trace = Self::new();
+		value.trace_write(&mut trace)?;
+
+		Ok(trace)
+	}
+
+	/// Reads a `T` from the given `bytes`, then annotates those bytes with its
+	/// fields.
+	///
+	/// The `bytes` start from the beginning of the value, including any bytes
+	/// which are not read by [`Readable`] (see [`TraceWrite::READ_OFFSET`]).
+	/// The `Trace` shows the `bytes` as they were received, so unused bytes
+	/// which were not zero are shown as they are.
+	///
+	/// # Errors
+	/// Returns a [`ReadError`] if the `T` could not be read, or if it could not
+	/// be written again to find its fields.
+	pub fn read<T: Readable + TraceWrite>(bytes: &[u8]) -> ReadResult<(T, Self)> {
+		let rest = bytes
+			.get(T::READ_OFFSET..)
+			.ok_or(ReadError::NotEnoughData {
+				expected: T::READ_OFFSET,
+				found: bytes.len(),
+			})?;
+		let value = T::read_from(&mut &*rest)?;
+
+		let mut trace = Self::write(&value).map_err(|error| ReadError::Other(Box::new(error)))?;
+
+		if let Some(received) = bytes.get(..trace.bytes.len()) {
+			trace.bytes = received.to_vec();
+		}
+
+		Ok((value, trace))
+	}
+
+	/// Returns the bytes of the traced value.
+	#[must_use]
+	pub fn bytes(&self) -> &[u8] {
+		&self.bytes
+	}
+
+	/// Returns the fields recorded for the traced value, in order.
+	#[must_use]
+	pub fn fields(&self) -> &[TraceField] {
+		&self.fields
+	}
+
+	/// Returns the number of bytes written so far.
+	#[must_use]
+	pub const fn len(&self) -> usize {
+		self.bytes.len()
+	}
+
+	/// Returns whether no bytes have been written.
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.bytes.is_empty()
+	}
+
+	/// Returns the buffer to which a field's bytes are written.
+	///
+	/// Once a field has been written, it is recorded with [`record`].
+	///
+	/// [`record`]: Trace::record
+	pub const fn buf_mut(&mut self) -> &mut Vec<u8> {
+		&mut self.bytes
+	}
+
+	/// Records the bytes written since `start` as encoding the field of the
+	/// given `name`.
+	///
+	/// The `value` is [`None`] if the bytes are unused.
+	pub fn record(&mut self, name: &'static str, start: usize, value: Option<String>) {
+		self.fields.push(TraceField {
+			name,
+			range: start..self.bytes.len(),
+			value,
+		});
+	}
+
+	/// Writes a line of the hex dump for the given `range` of bytes.
+	fn line(&self, f: &mut Formatter, range: Range<usize>, label: &str) -> fmt::Result {
+		let offset = if range.len() == 1 {
+			format!("{:#06x}", range.start)
+		} else {
+			format!("{:#06x}..{:#06x}", range.start, range.end)
+		};
+
+		let hex: Vec<String> = self.bytes[range]
+			.iter()
+			.map(|byte| format!("{byte:02x}"))
+			.collect();
+
+		writeln!(
+			f,
+			"{offset:<14}  {:<width$}  {label}",
+			hex.join(" "),
+			width = BYTES_PER_LINE * 3 - 1,
+		)
+	}
+
+	/// Writes the lines of the hex dump for a labelled `range` of bytes,
+	/// splitting it across multiple lines if it is long.
+	fn lines(&self, f: &mut Formatter, range: Range<usize>, label: &str) -> fmt::Result {
+		if range.is_empty() {
+			return self.line(f, range, label);
+		}
+
+		for start in range.clone().step_by(BYTES_PER_LINE) {
+			let end = (start + BYTES_PER_LINE).min(range.end);
+			let label = if start == range.start { label } else { "" };
+
+			self.line(f, start..end, label)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Display for Trace {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let mut end = 0;
+
+		for field in &self.fields {
+			if field.range.start > end {
+				self.lines(f, end..field.range.start, "(unannotated)")?;
+			}
+
+			let label = field.value.as_ref().map_or_else(
+				|| String::from("(unused)"),
+				|value| format!("{}={value}", field.name),
+			);
+
+			self.lines(f, field.range.clone(), &label)?;
+			end = end.max(field.range.end);
+		}
+
+		if self.bytes.len() > end {
+			self.lines(f, end..self.bytes.len(), "(unannotated)")?;
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{BufMut, X11Size};
+
+	struct Example {
+		code: u8,
+		id: u32,
+	}
+
+	impl X11Size for Example {
+		fn x11_size(&self) -> usize {
+			8
+		}
+	}
+
+	impl Writable for Example {
+		fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+			buf.put_u8(self.code);
+			buf.put_bytes(0, 3);
+			buf.put_u32(self.id);
+
+			Ok(())
+		}
+	}
+
+	impl TraceWrite for Example {
+		fn trace_write(&self, trace: &mut Trace) -> WriteResult {
+			let start = trace.len();
+			trace.buf_mut().put_u8(self.code);
+			trace.record("code", start, Some(self.code.to_string()));
+
+			let start = trace.len();
+			trace.buf_mut().put_bytes(0, 3);
+			trace.record("unused", start, None);
+
+			let start = trace.len();
+			trace.buf_mut().put_u32(self.id);
+			trace.record("id", start, Some(format!("{:#x}", self.id)));
+
+			Ok(())
+		}
+	}
+
+	#[test]
+	fn test_trace_hex_dump() {
+		let trace = Trace::write(&Example {
+			code: 7,
+			id: 0x02a1,
+		})
+		.unwrap();
+
+		assert_eq!(trace.bytes(), [7, 0, 0, 0, 0, 0, 0x02, 0xa1]);
+		assert_eq!(
+			trace.to_string(),
+			"0x0000          07                       code=7\n\
+			 0x0001..0x0004  00 00 00                 (unused)\n\
+			 0x0004..0x0008  00 00 02 a1              id=0x2a1\n",
+		);
+	}
+}
@@ -0,0 +1,134 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Writing values with bulk payloads without copying those payloads.
+
+use crate::{Writable, WriteError};
+use bytes::BufMut;
+use std::{borrow::Cow, io::IoSlice};
+
+/// Zeroed bytes used for the padding following a [`Vectored`] payload.
+const PADDING: [u8; 3] = [0; 3];
+
+/// A value which can be written as a small header followed by a bulk payload,
+/// without copying that payload into the same buffer as the header.
+///
+/// This is implemented for values with a large amount of data at their end,
+/// such as images, so that they can be written with
+/// [`std::io::Write::write_vectored`]. The bytes given by [`to_vectored`] are
+/// always the same as those written by [`Writable::write_to`].
+///
+/// [`to_vectored`]: WritableVectored::to_vectored
+pub trait WritableVectored: Writable {
+	/// Writes the part of `self` which precedes its payload to the given
+	/// `header`, then returns the payload.
+	///
+	/// The payload is borrowed from `self` where its bytes are already in the
+	/// order they are written in. Otherwise, it is converted to an owned
+	/// buffer.
+	///
+	/// Only zeroed padding to a multiple of 4 bytes may follow the payload.
+	///
+	/// # Errors
+	/// Returns a [`WriteError`] if it was not able to properly write the
+	/// header.
+	fn write_header(&self, header: &mut impl BufMut) -> Result<Cow<'_, [u8]>, WriteError>;
+
+	/// Returns the bytes of `self` split into a header, its payload, and
+	/// padding.
+	///
+	/// # Errors
+	/// Returns a [`WriteError`] if it was not able to properly write the
+	/// header, or if the header, payload, and padding do not add up to the
+	/// [`x11_size`] of `self`.
+	///
+	/// # Panics
+	/// Panics if the header is longer than [`Vectored::HEADER_CAPACITY`].
+	///
+	/// [`x11_size`]: crate::X11Size::x11_size
+	fn to_vectored(&self) -> Result<Vectored<'_>, WriteError> {
+		let mut header = [0; Vectored::HEADER_CAPACITY];
+
+		let mut buf = &mut header[..];
+		let payload = self.write_header(&mut buf)?;
+		let header_len = Vectored::HEADER_CAPACITY - buf.len();
+
+		let padding = self
+			.x11_size()
+			.checked_sub(header_len + payload.len())
+			.filter(|padding| *padding < 4)
+			.ok_or_else(|| {
+				WriteError::Other(Box::new(format!(
+					"vectored header of {header_len} bytes and payload of {} bytes do not add up \
+					 to {} bytes",
+					payload.len(),
+					self.x11_size(),
+				)))
+			})?;
+
+		Ok(Vectored {
+			header,
+			header_len,
+			payload,
+			padding,
+		})
+	}
+}
+
+/// A value split into a header, a payload, and padding by
+/// [`WritableVectored::to_vectored`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Vectored<'a> {
+	header: [u8; Vectored::HEADER_CAPACITY],
+	header_len: usize,
+
+	payload: Cow<'a, [u8]>,
+	padding: usize,
+}
+
+impl Vectored<'_> {
+	/// The maximum length of a header, in bytes.
+	pub const HEADER_CAPACITY: usize = 32;
+
+	/// Returns the header preceding the payload.
+	#[must_use]
+	pub fn header(&self) -> &[u8] {
+		&self.header[..self.header_len]
+	}
+
+	/// Returns the payload.
+	#[must_use]
+	pub fn payload(&self) -> &[u8] {
+		&self.payload
+	}
+
+	/// Returns the zeroed padding following the payload.
+	#[must_use]
+	pub fn padding(&self) -> &[u8] {
+		&PADDING[..self.padding]
+	}
+
+	/// Returns the total length of the header, payload, and padding, in bytes.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.header_len + self.payload.len() + self.padding
+	}
+
+	/// Returns whether the header, payload, and padding are all empty.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Returns the header, payload, and padding as [`IoSlice`]s, for use with
+	/// [`std::io::Write::write_vectored`].
+	#[must_use]
+	pub fn io_slices(&self) -> [IoSlice<'_>; 3] {
+		[
+			IoSlice::new(self.header()),
+			IoSlice::new(self.payload()),
+			IoSlice::new(self.padding()),
+		]
+	}
+}
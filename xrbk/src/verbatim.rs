@@ -0,0 +1,148 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A wrapper which remembers the exact bytes a value was read from.
+
+use crate::{ReadMode, ReadResult, Readable, Writable, WriteResult, X11Size};
+use bytes::{Buf, BufMut};
+use std::ops::Deref;
+
+/// A value which is written as the exact bytes it was read from.
+///
+/// Unused bytes are skipped when a value is read and zeroed when it is
+/// written, so reading and then writing a value does not necessarily
+/// reproduce the original bytes. Reading a value as `Verbatim<T>` records the
+/// bytes it was read from so that writing it reproduces them exactly,
+/// including any non-zero unused bytes.
+///
+/// Messages have leading bytes which are read before the message itself: for
+/// example, the code of an event is read to find which event it is. Those
+/// bytes are given to [`read_with_prefix`] and written before the bytes the
+/// value was read from, so a `Verbatim<T>` writes the whole message, just as
+/// `T` would.
+///
+/// The wrapped value can't be modified: use [`into_inner`] to take the value
+/// and write it normally instead.
+///
+/// [`read_with_prefix`]: Verbatim::read_with_prefix
+/// [`into_inner`]: Verbatim::into_inner
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Verbatim<T> {
+	value: T,
+
+	/// The leading bytes which were read before the value.
+	prefix: Vec<u8>,
+	/// The bytes which the value was read from.
+	bytes: Vec<u8>,
+}
+
+impl<T> Verbatim<T> {
+	/// Returns a reference to the wrapped value.
+	#[must_use]
+	pub const fn value(&self) -> &T {
+		&self.value
+	}
+
+	/// Returns the leading bytes which were read before the value.
+	#[must_use]
+	pub fn prefix(&self) -> &[u8] {
+		&self.prefix
+	}
+
+	/// Returns the bytes that the value was read from.
+	#[must_use]
+	pub fn bytes(&self) -> &[u8] {
+		&self.bytes
+	}
+
+	/// Unwraps the value, discarding the bytes it was read from.
+	#[must_use]
+	pub fn into_inner(self) -> T {
+		self.value
+	}
+}
+
+impl<T> Deref for Verbatim<T> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		&self.value
+	}
+}
+
+impl<T: Readable> Verbatim<T> {
+	/// Reads a value from `buf` as a `Verbatim<T>`, recording the bytes it
+	/// is read from.
+	///
+	/// `prefix` is the leading bytes of the message which were read before
+	/// the value, such as the code of an event. It is recorded as given.
+	///
+	/// # Errors
+	/// Returns any error returned when reading the value.
+	pub fn read_with_prefix(prefix: &[u8], buf: &mut impl Buf, mode: ReadMode) -> ReadResult<Self> {
+		let mut recorder = Recorder {
+			buf,
+			bytes: Vec::new(),
+		};
+		let value = T::read_from_with_mode(&mut recorder, mode)?;
+
+		Ok(Self {
+			value,
+
+			prefix: prefix.to_vec(),
+			bytes: recorder.bytes,
+		})
+	}
+}
+
+impl<T> X11Size for Verbatim<T> {
+	fn x11_size(&self) -> usize {
+		self.prefix.len() + self.bytes.len()
+	}
+}
+
+impl<T> Writable for Verbatim<T> {
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		buf.put_slice(&self.prefix);
+		buf.put_slice(&self.bytes);
+
+		Ok(())
+	}
+}
+
+/// A [`Buf`] which records every byte that is read from the `buf` it wraps.
+struct Recorder<'a, B> {
+	buf: &'a mut B,
+	bytes: Vec<u8>,
+}
+
+impl<B: Buf> Buf for Recorder<'_, B> {
+	fn remaining(&self) -> usize {
+		self.buf.remaining()
+	}
+
+	fn chunk(&self) -> &[u8] {
+		self.buf.chunk()
+	}
+
+	fn advance(&mut self, mut count: usize) {
+		// `count` may span more than one chunk.
+		while count > 0 {
+			let chunk = self.buf.chunk();
+			let len = count.min(chunk.len());
+
+			if len == 0 {
+				break;
+			}
+
+			self.bytes.extend_from_slice(&chunk[..len]);
+			self.buf.advance(len);
+
+			count -= len;
+		}
+
+		// Let the wrapped `buf` handle advancing past its end.
+		self.buf.advance(count);
+	}
+}
@@ -61,6 +61,10 @@ impl<T: X11Size, const N: usize> X11Size for [T; N] {
 	}
 }
 
+impl<T: X11Size + ConstantX11Size, const N: usize> ConstantX11Size for [T; N] {
+	const X11_SIZE: usize = T::X11_SIZE * N;
+}
+
 impl<T: X11Size> X11Size for &[T] {
 	fn x11_size(&self) -> usize {
 		let mut x11_size: usize = 0;
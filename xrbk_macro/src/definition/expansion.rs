@@ -2,8 +2,10 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+mod constant_x11_size;
 mod message_trait;
 mod readable;
+mod trace_write;
 mod writable;
 mod x11_size;
 
@@ -34,6 +36,10 @@ impl ToTokens for Definition {
 					r#struct.impl_writable(tokens, path);
 				}
 
+				if let Some(path) = attrs.derive_writables.first() {
+					r#struct.impl_trace_write(tokens, path);
+				}
+
 				for path in &attrs.derive_readables {
 					r#struct.impl_readable(tokens, path);
 				}
@@ -41,6 +47,10 @@ impl ToTokens for Definition {
 				for path in &attrs.derive_x11_sizes {
 					r#struct.impl_x11_size(tokens, path);
 				}
+
+				for path in &attrs.derive_constant_x11_sizes {
+					r#struct.impl_constant_x11_size(tokens, path);
+				}
 			},
 
 			Self::Enum(r#enum) => {
@@ -59,18 +69,27 @@ impl ToTokens for Definition {
 				for path in &attrs.derive_x11_sizes {
 					r#enum.impl_x11_size(tokens, path);
 				}
+
+				for path in &attrs.derive_constant_x11_sizes {
+					r#enum.impl_constant_x11_size(tokens, path);
+				}
 			},
 
 			Self::Request(request) => {
 				request.to_tokens(tokens);
-				request.impl_trait(tokens);
 
 				let attrs = &request.item_attributes;
 
+				request.impl_trait(tokens, attrs.derive_writables.first());
+
 				for path in &attrs.derive_writables {
 					request.impl_writable(tokens, path);
 				}
 
+				if let Some(path) = attrs.derive_writables.first() {
+					request.impl_trace_write(tokens, path);
+				}
+
 				for path in &attrs.derive_readables {
 					request.impl_readable(tokens, path);
 				}
@@ -78,6 +97,10 @@ impl ToTokens for Definition {
 				for path in &attrs.derive_x11_sizes {
 					request.impl_x11_size(tokens, path);
 				}
+
+				for path in &attrs.derive_constant_x11_sizes {
+					request.impl_constant_x11_size(tokens, path);
+				}
 			},
 
 			Self::Reply(reply) => {
@@ -90,6 +113,10 @@ impl ToTokens for Definition {
 					reply.impl_writable(tokens, path);
 				}
 
+				if let Some(path) = attrs.derive_writables.first() {
+					reply.impl_trace_write(tokens, path);
+				}
+
 				for path in &attrs.derive_readables {
 					reply.impl_readable(tokens, path);
 				}
@@ -97,6 +124,10 @@ impl ToTokens for Definition {
 				for path in &attrs.derive_x11_sizes {
 					reply.impl_x11_size(tokens, path);
 				}
+
+				for path in &attrs.derive_constant_x11_sizes {
+					reply.impl_constant_x11_size(tokens, path);
+				}
 			},
 
 			Self::Event(event) => {
@@ -109,6 +140,10 @@ impl ToTokens for Definition {
 					event.impl_writable(tokens, path);
 				}
 
+				if let Some(path) = attrs.derive_writables.first() {
+					event.impl_trace_write(tokens, path);
+				}
+
 				for path in &attrs.derive_readables {
 					event.impl_readable(tokens, path);
 				}
@@ -116,6 +151,10 @@ impl ToTokens for Definition {
 				for path in &attrs.derive_x11_sizes {
 					event.impl_x11_size(tokens, path);
 				}
+
+				for path in &attrs.derive_constant_x11_sizes {
+					event.impl_constant_x11_size(tokens, path);
+				}
 			},
 
 			Self::Error(error) => {
@@ -128,6 +167,10 @@ impl ToTokens for Definition {
 					error.impl_writable(tokens, path);
 				}
 
+				if let Some(path) = attrs.derive_writables.first() {
+					error.impl_trace_write(tokens, path);
+				}
+
 				for path in &attrs.derive_readables {
 					error.impl_readable(tokens, path);
 				}
@@ -135,6 +178,10 @@ impl ToTokens for Definition {
 				for path in &attrs.derive_x11_sizes {
 					error.impl_x11_size(tokens, path);
 				}
+
+				for path in &attrs.derive_constant_x11_sizes {
+					error.impl_constant_x11_size(tokens, path);
+				}
 			},
 
 			Self::Other(item) => item.to_tokens(tokens),
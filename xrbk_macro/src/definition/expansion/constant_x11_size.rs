@@ -0,0 +1,227 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote_spanned;
+use syn::{Generics, Path, WhereClause};
+
+use crate::TsExt;
+
+use super::*;
+
+/// Generates a `ConstantX11Size` implementation for a structlike definition,
+/// followed by a compile-time `assertion` of its size, if any.
+///
+/// `header_size` is the size of the definition's header, which is not
+/// represented by its elements. Only normal elements are included if the
+/// definition has a header: other elements are part of that header.
+#[allow(clippy::too_many_arguments)]
+fn impl_constant_x11_size(
+	tokens: &mut TokenStream2, trait_path: &Path, ident: &Ident, generics: &Generics,
+	content: &StructlikeContent, definition_type: DefinitionType, header_size: usize,
+	assertion: Option<TokenStream2>,
+) {
+	// TODO: add generic bounds
+	let (impl_generics, type_generics, _) = generics.split_for_impl();
+	let where_clause: &Option<WhereClause> = match content {
+		StructlikeContent::Regular { where_clause, .. } => where_clause,
+		StructlikeContent::Tuple { where_clause, .. } => where_clause,
+		StructlikeContent::Unit { where_clause, .. } => where_clause,
+	};
+
+	let sizes = TokenStream2::with_tokens(|tokens| {
+		for element in content {
+			if header_size == 0 || element.is_normal() {
+				element.constant_x11_size_tokens(tokens, definition_type);
+			}
+		}
+	});
+
+	tokens.append_tokens(quote_spanned!(trait_path.span()=>
+		#[automatically_derived]
+		impl #impl_generics ::xrbk::ConstantX11Size for #ident #type_generics #where_clause {
+			#[allow(clippy::identity_op, unused_mut)]
+			const X11_SIZE: usize = {
+				let mut size: usize = #header_size;
+
+				// Add the size of each element.
+				#sizes
+
+				// Return the cumulative size.
+				size
+			};
+		}
+	));
+
+	// The size of a generic definition can't be asserted without choosing
+	// its generic parameters.
+	if let Some(assertion) = assertion
+		&& generics.params.is_empty()
+	{
+		tokens.append_tokens(quote_spanned!(trait_path.span()=>
+			const _: () = {
+				let size = <#ident as ::xrbk::ConstantX11Size>::X11_SIZE;
+
+				#assertion
+			};
+		));
+	}
+}
+
+impl Struct {
+	pub fn impl_constant_x11_size(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		impl_constant_x11_size(
+			tokens,
+			trait_path,
+			&self.ident,
+			&self.generics,
+			&self.content,
+			DefinitionType::Basic,
+			0,
+			None,
+		);
+	}
+}
+
+impl Request {
+	pub fn impl_constant_x11_size(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		let ident = &self.ident;
+
+		impl_constant_x11_size(
+			tokens,
+			trait_path,
+			ident,
+			&self.generics,
+			&self.content,
+			DefinitionType::Request,
+			// The size of a request's header.
+			4,
+			Some(quote_spanned!(trait_path.span()=>
+				::core::assert!(
+					size % 4 == 0,
+					::core::concat!(
+						"the size of `",
+						::core::stringify!(#ident),
+						"` must be a multiple of 4 bytes",
+					),
+				);
+			)),
+		);
+	}
+}
+
+impl Reply {
+	pub fn impl_constant_x11_size(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		let ident = &self.ident;
+
+		impl_constant_x11_size(
+			tokens,
+			trait_path,
+			ident,
+			&self.generics,
+			&self.content,
+			DefinitionType::Reply,
+			// The size of a reply's header.
+			8,
+			Some(quote_spanned!(trait_path.span()=>
+				::core::assert!(
+					size >= 32 && size % 4 == 0,
+					::core::concat!(
+						"the size of `",
+						::core::stringify!(#ident),
+						"` must be a multiple of 4 bytes, and at least 32 bytes",
+					),
+				);
+			)),
+		);
+	}
+}
+
+impl Event {
+	pub fn impl_constant_x11_size(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		let ident = &self.ident;
+
+		impl_constant_x11_size(
+			tokens,
+			trait_path,
+			ident,
+			&self.generics,
+			&self.content,
+			DefinitionType::Event,
+			// The size of an event's header depends on whether it has a
+			// sequence field and metabyte position.
+			if self.content.sequence_element().is_some() {
+				4
+			} else {
+				1
+			},
+			Some(quote_spanned!(trait_path.span()=>
+				::core::assert!(
+					size == 32,
+					::core::concat!("the size of `", ::core::stringify!(#ident), "` must be 32 bytes"),
+				);
+			)),
+		);
+	}
+}
+
+impl Error {
+	pub fn impl_constant_x11_size(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		let ident = &self.ident;
+
+		impl_constant_x11_size(
+			tokens,
+			trait_path,
+			ident,
+			&self.generics,
+			&self.content,
+			DefinitionType::Error,
+			// The size of the fields required in every error.
+			11,
+			Some(quote_spanned!(trait_path.span()=>
+				::core::assert!(
+					size == 32,
+					::core::concat!("the size of `", ::core::stringify!(#ident), "` must be 32 bytes"),
+				);
+			)),
+		);
+	}
+}
+
+impl Enum {
+	pub fn impl_constant_x11_size(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		let ident = &self.ident;
+		let discrim_type = self.discriminant_type.as_ref().map_or_else(
+			|| quote_spanned!(trait_path.span()=> u8),
+			|(_, r#type)| r#type.to_token_stream(),
+		);
+
+		// TODO: add generic bounds
+		let (impl_generics, type_generics, _) = self.generics.split_for_impl();
+		let where_clause = &self.where_clause;
+
+		// Only enums without any elements in their variants are a constant
+		// size: the size of their discriminant.
+		if let Some(variant) = self
+			.variants
+			.iter()
+			.find(|variant| (&variant.content).into_iter().next().is_some())
+		{
+			tokens.append_tokens(quote_spanned!(variant.ident.span()=>
+				::core::compile_error!(
+					"`ConstantX11Size` can only be derived for enums without variant elements"
+				);
+			));
+
+			return;
+		}
+
+		tokens.append_tokens(quote_spanned!(trait_path.span()=>
+			#[automatically_derived]
+			impl #impl_generics ::xrbk::ConstantX11Size for #ident #type_generics #where_clause {
+				const X11_SIZE: usize = <#discrim_type as ::xrbk::ConstantX11Size>::X11_SIZE;
+			}
+		));
+	}
+}
@@ -10,7 +10,7 @@ use super::*;
 use crate::TsExt;
 
 impl Request {
-	pub fn impl_trait(&self, tokens: &mut TokenStream2) {
+	pub fn impl_trait(&self, tokens: &mut TokenStream2, writable: Option<&Path>) {
 		let name = &self.ident;
 
 		let (impl_generics, type_generics, _) = self.generics.split_for_impl();
@@ -40,6 +40,11 @@ impl Request {
 
 		let request_token = &self.request_token;
 
+		// Requests which derive `Writable` are written with the given length
+		// encoding.
+		let write_with_encoding =
+			writable.map(|path| self.write_with_encoding_tokens(path));
+
 		tokens.append_tokens({
 			quote_spanned!(self.request_token.span()=>
 				#[automatically_derived]
@@ -59,6 +64,8 @@ impl Request {
 					fn length(&self) -> u16 {
 						(<Self as ::xrbk::X11Size>::x11_size(self) / 4) as u16
 					}
+
+					#write_with_encoding
 				}
 			)
 		});
@@ -41,15 +41,23 @@ impl Struct {
 		tokens.append_tokens(quote_spanned!(trait_path.span()=>
 			#[automatically_derived]
 			impl #impl_generics ::xrbk::Readable for #ident #type_generics #where_clause {
+				fn read_from(
+					buf: &mut impl ::xrbk::Buf,
+				) -> Result<Self, ::xrbk::ReadError> {
+					Self::read_from_with_mode(buf, ::xrbk::ReadMode::Lenient)
+				}
+
 				#[allow(
 					clippy::items_after_statements,
 					clippy::trivially_copy_pass_by_ref,
 					clippy::needless_borrow,
 					clippy::identity_op,
 					unused_mut,
+					unused_variables,
 				)]
-				fn read_from(
+				fn read_from_with_mode(
 					buf: &mut impl ::xrbk::Buf,
+					mode: ::xrbk::ReadMode,
 				) -> Result<Self, ::xrbk::ReadError> {
 					// Declare a x11_size variable if it is going to be
 					// used in an infer unused bytes element.
@@ -97,28 +105,35 @@ impl Request {
 		let metabyte = if self.minor_opcode.is_some() {
 			// If there is a minor opcode, then it has already been read in order to
 			// determine that this is the request to read.
-			// TODO: can't be in metabyte, must check this in protocol!!
 			None
 		} else if let Some(element) = self.content.metabyte_element() {
 			Some(TokenStream2::with_tokens(|tokens| {
 				element.read_tokens(tokens, DefinitionType::Request);
 			}))
 		} else {
-			Some(quote_spanned!(trait_path.span()=> <_ as ::xrbk::Buf>::advance(buf, 1);))
+			Some(quote_spanned!(trait_path.span()=> ::xrbk::read_padding(buf, 1, mode)?;))
 		};
 
 		tokens.append_tokens(quote_spanned!(trait_path.span()=>
 			#[automatically_derived]
 			impl #impl_generics ::xrbk::Readable for #ident #type_generics #where_clause {
+				fn read_from(
+					buf: &mut impl ::xrbk::Buf,
+				) -> Result<Self, ::xrbk::ReadError> {
+					Self::read_from_with_mode(buf, ::xrbk::ReadMode::Lenient)
+				}
+
 				#[allow(
 					clippy::items_after_statements,
 					clippy::trivially_copy_pass_by_ref,
 					clippy::needless_borrow,
 					clippy::identity_op,
 					unused_mut,
+					unused_variables,
 				)]
-				fn read_from(
+				fn read_from_with_mode(
 					buf: &mut impl ::xrbk::Buf,
+					mode: ::xrbk::ReadMode,
 				) -> Result<Self, ::xrbk::ReadError> {
 					let mut size: usize = 4;
 
@@ -128,10 +143,10 @@ impl Request {
 					// read.
 					#metabyte
 					// Read the request's length.
-					let length = <_ as ::xrbk::Buf>::get_u16(buf);
+					let length = <u16 as ::xrbk::Readable>::read_from(buf)?;
 					let buf = &mut <_ as ::xrbk::Buf>::take(
 						buf,
-						((length - 1) as usize) * 4,
+						(length as usize).saturating_sub(1) * 4,
 					);
 
 					// Read other elements.
@@ -178,7 +193,7 @@ impl Reply {
 				element.read_tokens(tokens, DefinitionType::Reply);
 			})
 		} else {
-			quote_spanned!(trait_path.span()=> <_ as ::xrbk::Buf>::advance(buf, 1);)
+			quote_spanned!(trait_path.span()=> ::xrbk::read_padding(buf, 1, mode)?;)
 		};
 
 		let sequence = match self.content.sequence_element() {
@@ -189,24 +204,32 @@ impl Reply {
 		tokens.append_tokens(quote_spanned!(trait_path.span()=>
 			#[automatically_derived]
 			impl #impl_generics ::xrbk::Readable for #ident #type_generics #where_clause {
+				fn read_from(
+					buf: &mut impl ::xrbk::Buf,
+				) -> Result<Self, ::xrbk::ReadError> {
+					Self::read_from_with_mode(buf, ::xrbk::ReadMode::Lenient)
+				}
+
 				#[allow(
 					clippy::items_after_statements,
 					clippy::trivially_copy_pass_by_ref,
 					clippy::needless_borrow,
 					clippy::identity_op,
 					unused_mut,
+					unused_variables,
 				)]
-				fn read_from(
+				fn read_from_with_mode(
 					buf: &mut impl ::xrbk::Buf,
+					mode: ::xrbk::ReadMode,
 				) -> Result<Self, ::xrbk::ReadError> {
 					let mut size: usize = 8;
 
 					// Metabyte position
 					#metabyte
 					// Sequence field
-					let #sequence = <_ as ::xrbk::Buf>::get_u16(buf);
+					let #sequence = <u16 as ::xrbk::Readable>::read_from(buf)?;
 					// Length
-					let length = <_ as ::xrbk::Buf>::get_u32(buf);
+					let length = <u32 as ::xrbk::Readable>::read_from(buf)?;
 					let buf = &mut <_ as ::xrbk::Buf>::take(
 						buf,
 						(((length) as usize) * 4) + (32 - 8),
@@ -215,6 +238,11 @@ impl Reply {
 					// Other elements
 					#reads
 
+					// Skip any bytes of the reply which were not read, such as
+					// those added by later versions of the protocol.
+					let remaining = <_ as ::xrbk::Buf>::remaining(buf);
+					<_ as ::xrbk::Buf>::advance(buf, remaining);
+
 					// Construct and return Self.
 					Ok(Self #cons)
 				}
@@ -265,7 +293,7 @@ impl Event {
 			}))
 		} else {
 			Some(quote_spanned!(trait_path.span()=>
-				<_ as ::xrbk::Buf>::advance(buf, 1);
+				::xrbk::read_padding(buf, 1, mode)?;
 			))
 		};
 
@@ -273,7 +301,7 @@ impl Event {
 			let formatted = &field.formatted;
 
 			Some(quote_spanned!(trait_path.span()=>
-				let #formatted = <_ as ::xrbk::Buf>::get_u16(buf);
+				let #formatted = <u16 as ::xrbk::Readable>::read_from(buf)?;
 			))
 		} else {
 			None
@@ -282,15 +310,23 @@ impl Event {
 		tokens.append_tokens(quote_spanned!(trait_path.span()=>
 			#[automatically_derived]
 			impl #impl_generics ::xrbk::Readable for #ident #type_generics #where_clause {
+				fn read_from(
+					buf: &mut impl ::xrbk::Buf,
+				) -> Result<Self, ::xrbk::ReadError> {
+					Self::read_from_with_mode(buf, ::xrbk::ReadMode::Lenient)
+				}
+
 				#[allow(
 					clippy::items_after_statements,
 					clippy::trivially_copy_pass_by_ref,
 					clippy::needless_borrow,
 					clippy::identity_op,
 					unused_mut,
+					unused_variables,
 				)]
-				fn read_from(
+				fn read_from_with_mode(
 					buf: &mut impl ::xrbk::Buf,
+					mode: ::xrbk::ReadMode,
 				) -> Result<Self, ::xrbk::ReadError> {
 					let mut size: usize = #x11_size;
 
@@ -342,7 +378,7 @@ impl Error {
 				let formatted = &field.formatted;
 
 				quote_spanned!(trait_path.span()=>
-					let #formatted = <_ as ::xrbk::Buf>::get_u16(buf);
+					let #formatted = <u16 as ::xrbk::Readable>::read_from(buf)?;
 				)
 			},
 
@@ -354,7 +390,7 @@ impl Error {
 				let formatted = &field.formatted;
 
 				quote_spanned!(trait_path.span()=>
-					let #formatted = <_ as ::xrbk::Buf>::get_u16(buf);
+					let #formatted = <u16 as ::xrbk::Readable>::read_from(buf)?;
 				)
 			},
 
@@ -366,7 +402,7 @@ impl Error {
 				let formatted = &field.formatted;
 
 				quote_spanned!(trait_path.span()=>
-					let #formatted = <_ as ::xrbk::Buf>::get_u8(buf);
+					let #formatted = <u8 as ::xrbk::Readable>::read_from(buf)?;
 				)
 			},
 
@@ -378,21 +414,29 @@ impl Error {
 				TokenStream2::with_tokens(|tokens| field.read_tokens(tokens))
 			},
 
-			_ => quote_spanned!(trait_path.span()=> <_ as ::xrbk::Buf>::advance(buf, 4);),
+			_ => quote_spanned!(trait_path.span()=> ::xrbk::read_padding(buf, 4, mode)?;),
 		};
 
 		tokens.append_tokens(quote_spanned!(trait_path.span()=>
 			#[automatically_derived]
 			impl #impl_generics ::xrbk::Readable for #ident #type_generics #where_clause {
+				fn read_from(
+					buf: &mut impl ::xrbk::Buf,
+				) -> Result<Self, ::xrbk::ReadError> {
+					Self::read_from_with_mode(buf, ::xrbk::ReadMode::Lenient)
+				}
+
 				#[allow(
 					clippy::items_after_statements,
 					clippy::trivially_copy_pass_by_ref,
 					clippy::needless_borrow,
 					clippy::identity_op,
 					unused_mut,
+					unused_variables,
 				)]
-				fn read_from(
+				fn read_from_with_mode(
 					buf: &mut impl ::xrbk::Buf,
+					mode: ::xrbk::ReadMode,
 				) -> Result<Self, ::xrbk::ReadError> {
 					// 11 bytes includes:
 					// - 1 byte to say it's an error
@@ -510,6 +554,12 @@ impl Enum {
 		tokens.append_tokens(quote_spanned!(trait_path.span()=>
 			#[automatically_derived]
 			impl #impl_generics ::xrbk::Readable for #ident #type_generics #where_clause {
+				fn read_from(
+					buf: &mut impl ::xrbk::Buf,
+				) -> Result<Self, ::xrbk::ReadError> {
+					Self::read_from_with_mode(buf, ::xrbk::ReadMode::Lenient)
+				}
+
 				#[allow(
 					clippy::items_after_statements,
 					clippy::trivially_copy_pass_by_ref,
@@ -517,9 +567,11 @@ impl Enum {
 					clippy::identity_op,
 					clippy::unnecessary_cast,
 					unused_mut,
+					unused_variables,
 				)]
-				fn read_from(
+				fn read_from_with_mode(
 					buf: &mut impl ::xrbk::Buf,
+					mode: ::xrbk::ReadMode,
 				) -> Result<Self, ::xrbk::ReadError> {
 					// Define functions and variables for variants which
 					// have custom discriminant expressions.
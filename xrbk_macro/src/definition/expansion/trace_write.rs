@@ -0,0 +1,414 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote_spanned;
+use syn::{Generics, Path, WhereClause};
+
+use super::*;
+use crate::{element::Element, TsExt};
+
+/// Generates the tokens to write a part of a message's header which is not
+/// represented by an element, recording it with the given `name`.
+fn header_tokens(
+	trait_path: &Path, name: &str, write: TokenStream2, value: Option<TokenStream2>,
+) -> TokenStream2 {
+	let value = match value {
+		Some(value) => quote_spanned!(trait_path.span()=> Some(format!("{:?}", #value))),
+		None => quote_spanned!(trait_path.span()=> None),
+	};
+
+	quote_spanned!(trait_path.span()=>
+		let trace_start = ::xrbk::Trace::len(trace);
+		let buf = ::xrbk::Trace::buf_mut(trace);
+		#write
+		::xrbk::Trace::record(trace, #name, trace_start, #value);
+	)
+}
+
+/// Generates a `TraceWrite` implementation for a structlike definition, which
+/// is only compiled with the `trace` feature.
+///
+/// `header` writes the parts of the definition's header which are not
+/// represented by its elements, and `writes` writes the rest of its elements.
+#[allow(clippy::too_many_arguments)]
+fn impl_trace_write(
+	tokens: &mut TokenStream2, trait_path: &Path, ident: &Ident, generics: &Generics,
+	content: &StructlikeContent, header_size: usize, read_offset: usize, header: TokenStream2,
+	writes: TokenStream2,
+) {
+	// The fields of generic definitions can't be rendered without requiring
+	// their generic parameters to implement `Debug`.
+	if !generics.params.is_empty() {
+		return;
+	}
+
+	let where_clause: &Option<WhereClause> = match content {
+		StructlikeContent::Regular { where_clause, .. } => where_clause,
+		StructlikeContent::Tuple { where_clause, .. } => where_clause,
+		StructlikeContent::Unit { where_clause, .. } => where_clause,
+	};
+
+	let pat = TokenStream2::with_tokens(|tokens| {
+		content.pat_cons_to_tokens(tokens);
+	});
+
+	tokens.append_tokens(quote_spanned!(trait_path.span()=>
+		#[cfg(feature = "trace")]
+		#[automatically_derived]
+		impl ::xrbk::TraceWrite for #ident #where_clause {
+			const READ_OFFSET: usize = #read_offset;
+
+			#[allow(
+				clippy::items_after_statements,
+				clippy::trivially_copy_pass_by_ref,
+				clippy::needless_borrow,
+				clippy::identity_op,
+				clippy::cast_possible_truncation,
+				unused_mut,
+			)]
+			fn trace_write(
+				&self,
+				trace: &mut ::xrbk::Trace,
+			) -> Result<(), ::xrbk::WriteError> {
+				let mut size: usize = #header_size;
+				// Destructure the definition's fields, if any.
+				let Self #pat = self;
+
+				#header
+				#writes
+
+				Ok(())
+			}
+		}
+	));
+}
+
+impl Struct {
+	pub fn impl_trace_write(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		let writes = TokenStream2::with_tokens(|tokens| {
+			for element in &self.content {
+				element.trace_write_tokens(tokens, DefinitionType::Basic);
+				element.add_x11_size_tokens(tokens);
+			}
+		});
+
+		impl_trace_write(
+			tokens,
+			trait_path,
+			&self.ident,
+			&self.generics,
+			&self.content,
+			0,
+			0,
+			TokenStream2::new(),
+			writes,
+		);
+	}
+}
+
+impl Request {
+	pub fn impl_trace_write(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		let writes = TokenStream2::with_tokens(|tokens| {
+			for element in &self.content {
+				if !element.is_metabyte() && !element.is_sequence() {
+					element.trace_write_tokens(tokens, DefinitionType::Request);
+					element.add_x11_size_tokens(tokens);
+				}
+			}
+		});
+
+		let major_opcode = header_tokens(
+			trait_path,
+			"major_opcode",
+			quote_spanned!(trait_path.span()=>
+				<_ as ::xrbk::BufMut>::put_u8(
+					buf,
+					<Self as xrb::message::Request>::MAJOR_OPCODE,
+				);
+			),
+			Some(quote_spanned!(trait_path.span()=>
+				<Self as xrb::message::Request>::MAJOR_OPCODE
+			)),
+		);
+
+		let metabyte = if self.minor_opcode.is_some() {
+			header_tokens(
+				trait_path,
+				"minor_opcode",
+				quote_spanned!(trait_path.span()=>
+					<_ as ::xrbk::BufMut>::put_u8(
+						buf,
+						<Self as xrb::message::Request>::MINOR_OPCODE.unwrap() as u8,
+					);
+				),
+				Some(quote_spanned!(trait_path.span()=>
+					<Self as xrb::message::Request>::MINOR_OPCODE.unwrap()
+				)),
+			)
+		} else if let Some(element) = self.content.metabyte_element() {
+			TokenStream2::with_tokens(|tokens| {
+				element.trace_write_tokens(tokens, DefinitionType::Request);
+			})
+		} else {
+			header_tokens(
+				trait_path,
+				"unused",
+				quote_spanned!(trait_path.span()=> <_ as ::xrbk::BufMut>::put_u8(buf, 0);),
+				None,
+			)
+		};
+
+		let length = header_tokens(
+			trait_path,
+			"length",
+			quote_spanned!(trait_path.span()=>
+				<_ as ::xrbk::BufMut>::put_u16(
+					buf,
+					<Self as xrb::message::Request>::checked_length(&self)?,
+				);
+			),
+			Some(quote_spanned!(trait_path.span()=>
+				<Self as xrb::message::Request>::length(&self)
+			)),
+		);
+
+		impl_trace_write(
+			tokens,
+			trait_path,
+			&self.ident,
+			&self.generics,
+			&self.content,
+			4,
+			// The major opcode is not read, nor is the minor opcode if there
+			// is one.
+			if self.minor_opcode.is_some() { 2 } else { 1 },
+			quote_spanned!(trait_path.span()=>
+				#major_opcode
+				#metabyte
+				#length
+			),
+			writes,
+		);
+	}
+}
+
+impl Reply {
+	pub fn impl_trace_write(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		let writes = TokenStream2::with_tokens(|tokens| {
+			for element in &self.content {
+				if !element.is_metabyte() && !element.is_sequence() {
+					element.trace_write_tokens(tokens, DefinitionType::Reply);
+					element.add_x11_size_tokens(tokens);
+				}
+			}
+		});
+
+		let reply = header_tokens(
+			trait_path,
+			"reply",
+			quote_spanned!(trait_path.span()=> <_ as ::xrbk::BufMut>::put_u8(buf, 1);),
+			Some(quote_spanned!(trait_path.span()=> 1)),
+		);
+
+		let metabyte = if let Some(element) = self.content.metabyte_element() {
+			TokenStream2::with_tokens(|tokens| {
+				element.trace_write_tokens(tokens, DefinitionType::Reply);
+			})
+		} else {
+			header_tokens(
+				trait_path,
+				"unused",
+				quote_spanned!(trait_path.span()=> <_ as ::xrbk::BufMut>::put_u8(buf, 0);),
+				None,
+			)
+		};
+
+		let sequence = match self.content.sequence_element() {
+			Some(element @ Element::Field(_)) => TokenStream2::with_tokens(|tokens| {
+				element.trace_write_tokens(tokens, DefinitionType::Reply);
+			}),
+			_ => panic!("replies must have a sequence field"),
+		};
+
+		let length = header_tokens(
+			trait_path,
+			"length",
+			quote_spanned!(trait_path.span()=>
+				<_ as ::xrbk::BufMut>::put_u32(
+					buf,
+					<Self as xrb::message::Reply>::length(&self),
+				);
+			),
+			Some(quote_spanned!(trait_path.span()=>
+				<Self as xrb::message::Reply>::length(&self)
+			)),
+		);
+
+		impl_trace_write(
+			tokens,
+			trait_path,
+			&self.ident,
+			&self.generics,
+			&self.content,
+			8,
+			// The `1` indicating that this is a reply is not read.
+			1,
+			quote_spanned!(trait_path.span()=>
+				#reply
+				#metabyte
+				#sequence
+				#length
+			),
+			writes,
+		);
+	}
+}
+
+impl Event {
+	pub fn impl_trace_write(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		let writes = TokenStream2::with_tokens(|tokens| {
+			for element in &self.content {
+				if element.is_normal() {
+					element.trace_write_tokens(tokens, DefinitionType::Event);
+					element.add_x11_size_tokens(tokens);
+				}
+			}
+		});
+
+		let code = header_tokens(
+			trait_path,
+			"code",
+			quote_spanned!(trait_path.span()=>
+				<_ as ::xrbk::BufMut>::put_u8(buf, <Self as xrb::message::Event>::CODE);
+			),
+			Some(quote_spanned!(trait_path.span()=> <Self as xrb::message::Event>::CODE)),
+		);
+
+		let (header_size, metabyte, sequence) = match self.content.sequence_element() {
+			Some(sequence) => {
+				let metabyte = if let Some(element) = self.content.metabyte_element() {
+					TokenStream2::with_tokens(|tokens| {
+						element.trace_write_tokens(tokens, DefinitionType::Event);
+					})
+				} else {
+					header_tokens(
+						trait_path,
+						"unused",
+						quote_spanned!(trait_path.span()=> <_ as ::xrbk::BufMut>::put_u8(buf, 0);),
+						None,
+					)
+				};
+
+				let sequence = TokenStream2::with_tokens(|tokens| {
+					sequence.trace_write_tokens(tokens, DefinitionType::Event);
+				});
+
+				(4, metabyte, sequence)
+			},
+
+			None => (1, TokenStream2::new(), TokenStream2::new()),
+		};
+
+		impl_trace_write(
+			tokens,
+			trait_path,
+			&self.ident,
+			&self.generics,
+			&self.content,
+			header_size,
+			// The event code is not read.
+			1,
+			quote_spanned!(trait_path.span()=>
+				#code
+				#metabyte
+				#sequence
+			),
+			writes,
+		);
+	}
+}
+
+impl Error {
+	pub fn impl_trace_write(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		let writes = TokenStream2::with_tokens(|tokens| {
+			for element in &self.content {
+				if element.is_normal() {
+					element.trace_write_tokens(tokens, DefinitionType::Error);
+					element.add_x11_size_tokens(tokens);
+				}
+			}
+		});
+
+		let error = header_tokens(
+			trait_path,
+			"error",
+			quote_spanned!(trait_path.span()=> <_ as ::xrbk::BufMut>::put_u8(buf, 0);),
+			Some(quote_spanned!(trait_path.span()=> 0)),
+		);
+
+		let code = header_tokens(
+			trait_path,
+			"code",
+			quote_spanned!(trait_path.span()=>
+				<_ as ::xrbk::BufMut>::put_u8(buf, <Self as xrb::message::Error>::CODE);
+			),
+			Some(quote_spanned!(trait_path.span()=> <Self as xrb::message::Error>::CODE)),
+		);
+
+		let element_tokens = |element: Option<&Element>, message: &str| match element {
+			Some(element @ Element::Field(_)) => TokenStream2::with_tokens(|tokens| {
+				element.trace_write_tokens(tokens, DefinitionType::Error);
+			}),
+			_ => panic!("{}", message),
+		};
+
+		let sequence = element_tokens(
+			self.content.sequence_element().as_ref(),
+			"errors must have sequence fields",
+		);
+		let minor_opcode = element_tokens(
+			self.content.minor_opcode_element().as_ref(),
+			"errors must have minor opcode fields",
+		);
+		let major_opcode = element_tokens(
+			self.content.major_opcode_element().as_ref(),
+			"errors must have major opcode fields",
+		);
+
+		let error_data = match self.content.error_data_element() {
+			Some(element @ Element::Field(_)) => TokenStream2::with_tokens(|tokens| {
+				element.trace_write_tokens(tokens, DefinitionType::Error);
+			}),
+
+			_ => header_tokens(
+				trait_path,
+				"unused",
+				quote_spanned!(trait_path.span()=> <_ as ::xrbk::BufMut>::put_bytes(buf, 0, 4);),
+				None,
+			),
+		};
+
+		impl_trace_write(
+			tokens,
+			trait_path,
+			&self.ident,
+			&self.generics,
+			&self.content,
+			11,
+			// The `0` indicating that this is an error and the error code are
+			// not read.
+			2,
+			quote_spanned!(trait_path.span()=>
+				#error
+				#code
+				#sequence
+				#error_data
+				#minor_opcode
+				#major_opcode
+			),
+			writes,
+		);
+	}
+}
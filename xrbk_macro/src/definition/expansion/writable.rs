@@ -63,17 +63,9 @@ impl Struct {
 }
 
 impl Request {
-	pub fn impl_writable(&self, tokens: &mut TokenStream2, trait_path: &Path) {
-		let ident = &self.ident;
-
-		// TODO: add generic bounds
-		let (impl_generics, type_generics, _) = self.generics.split_for_impl();
-		let where_clause = match &self.content {
-			StructlikeContent::Regular { where_clause, .. } => where_clause,
-			StructlikeContent::Tuple { where_clause, .. } => where_clause,
-			StructlikeContent::Unit { where_clause, .. } => where_clause,
-		};
-
+	/// Returns the `write_with_encoding` method of the `Request` trait, which
+	/// writes the request with the given length encoding.
+	pub fn write_with_encoding_tokens(&self, trait_path: &Path) -> TokenStream2 {
 		let pat = TokenStream2::with_tokens(|tokens| {
 			self.content.pat_cons_to_tokens(tokens);
 		});
@@ -91,11 +83,12 @@ impl Request {
 		});
 
 		let metabyte = if self.minor_opcode.is_some() {
-			// TODO: can't be in metabyte, must check this in protocol!!
+			// The minor opcode of a request is written in the metabyte
+			// position, so it is only one byte.
 			quote_spanned!(trait_path.span()=>
-				<_ as ::xrbk::BufMut>::put_u16(
+				<_ as ::xrbk::BufMut>::put_u8(
 					buf,
-					<Self as xrb::message::Request>::MINOR_OPCODE.unwrap(),
+					<Self as xrb::message::Request>::MINOR_OPCODE.unwrap() as u8,
 				);
 			)
 		} else if let Some(element) = self.content.metabyte_element() {
@@ -111,41 +104,65 @@ impl Request {
 			)
 		};
 
+		quote_spanned!(trait_path.span()=>
+			#[allow(
+				clippy::items_after_statements,
+				clippy::trivially_copy_pass_by_ref,
+				clippy::needless_borrow,
+				clippy::identity_op,
+				clippy::cast_possible_truncation,
+				unused_mut,
+			)]
+			fn write_with_encoding(
+				&self,
+				buf: &mut impl ::xrbk::BufMut,
+				encoding: xrb::message::LengthEncoding,
+			) -> Result<(), ::xrbk::WriteError> {
+				let mut size: usize = 4;
+				// Destructure the request struct's fields, if any.
+				let Self #pat = self;
+
+				// Major opcode
+				<_ as ::xrbk::BufMut>::put_u8(
+					buf,
+					<Self as xrb::message::Request>::MAJOR_OPCODE
+				);
+				// Metabyte position
+				#metabyte
+				// Length
+				<Self as xrb::message::Request>::write_length(&self, buf, encoding)?;
+
+				// Other elements
+				#writes
+
+				Ok(())
+			}
+		)
+	}
+
+	pub fn impl_writable(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		let ident = &self.ident;
+
+		// TODO: add generic bounds
+		let (impl_generics, type_generics, _) = self.generics.split_for_impl();
+		let where_clause = match &self.content {
+			StructlikeContent::Regular { where_clause, .. } => where_clause,
+			StructlikeContent::Tuple { where_clause, .. } => where_clause,
+			StructlikeContent::Unit { where_clause, .. } => where_clause,
+		};
+
 		tokens.append_tokens(quote_spanned!(trait_path.span()=>
 			#[automatically_derived]
 			impl #impl_generics ::xrbk::Writable for #ident #type_generics #where_clause {
-				#[allow(
-					clippy::items_after_statements,
-					clippy::trivially_copy_pass_by_ref,
-					clippy::needless_borrow,
-					clippy::identity_op,
-					unused_mut,
-				)]
 				fn write_to(
 					&self,
 					buf: &mut impl ::xrbk::BufMut,
 				) -> Result<(), ::xrbk::WriteError> {
-					let mut size: usize = 4;
-					// Destructure the request struct's fields, if any.
-					let Self #pat = self;
-
-					// Major opcode
-					<_ as ::xrbk::BufMut>::put_u8(
-						buf,
-						<Self as xrb::message::Request>::MAJOR_OPCODE
-					);
-					// Metabyte position
-					#metabyte
-					// Length
-					<_ as ::xrbk::BufMut>::put_u16(
+					<Self as xrb::message::Request>::write_with_encoding(
+						self,
 						buf,
-						<Self as xrb::message::Request>::length(&self),
-					);
-
-					// Other elements
-					#writes
-
-					Ok(())
+						xrb::message::LengthEncoding::Standard,
+					)
 				}
 			}
 		));
@@ -296,7 +296,7 @@ pub fn derive_reads(attributes: &[Attribute], data: &Data) -> TokenStream2 {
 					let r#type = &field.ty;
 
 					tokens.append_tokens(quote!(
-						let #ident = <#r#type as ::xrbk::Readable>::read_from(buf)?;
+						let #ident = <#r#type as ::xrbk::Readable>::read_from_with_mode(buf, mode)?;
 					));
 				}
 			},
@@ -316,7 +316,9 @@ pub fn derive_reads(attributes: &[Attribute], data: &Data) -> TokenStream2 {
 					let r#type = &field.ty;
 
 					tokens.append_tokens(quote!(
-						let #formatted = <#r#type as ::xrbk::Readable>::read_from(buf)?;
+						let #formatted = <#r#type as ::xrbk::Readable>::read_from_with_mode(
+							buf, mode,
+						)?;
 					));
 				}
 			},
@@ -364,7 +366,7 @@ pub fn derive_reads(attributes: &[Attribute], data: &Data) -> TokenStream2 {
 			});
 
 			quote!(
-				match buf.get_u8() {
+				match <u8 as ::xrbk::Readable>::read_from(buf)? {
 					#(#arms)*
 
 					other_discrim => Err(
@@ -477,7 +479,7 @@ pub fn derive_x11_sizes(attributes: &[Attribute], data: &Data) -> TokenStream2 {
 	}
 }
 
-pub fn derive_constant_x11_sizes(_attributes: &[Attribute], data: &Data) -> TokenStream2 {
+pub fn derive_constant_x11_sizes(attributes: &[Attribute], data: &Data) -> TokenStream2 {
 	fn derive_for_fields(fields: &Fields) -> TokenStream2 {
 		TokenStream2::with_tokens(|tokens| match fields {
 			Fields::Named(FieldsNamed { named: fields, .. })
@@ -512,7 +514,48 @@ pub fn derive_constant_x11_sizes(_attributes: &[Attribute], data: &Data) -> Toke
 			)
 		},
 
-		// TODO: derive for enums if all variants are the same constant size
-		Data::Enum(_) | Data::Union(_) => unimplemented!(),
+		// Enums are only a constant size if all of their variants are the same
+		// size, which is asserted at compile time.
+		Data::Enum(r#enum) => {
+			let no_discrim = attributes
+				.iter()
+				.any(|attribute| attribute.path.is_ident("no_discrim"));
+
+			let discrim_size = if no_discrim { quote!(0) } else { quote!(1) };
+
+			let mut variant_sizes = r#enum.variants.iter().map(|variant| {
+				let sizes = derive_for_fields(&variant.fields);
+
+				quote!({
+					let mut size = #discrim_size;
+
+					#sizes
+
+					size
+				})
+			});
+
+			let first = variant_sizes
+				.next()
+				.unwrap_or_else(|| quote!(#discrim_size));
+			let assertions = variant_sizes.map(|size| {
+				quote!(
+					::core::assert!(
+						#size == size,
+						"all variants must be the same size to derive `ConstantX11Size`",
+					);
+				)
+			});
+
+			quote!(
+				let size = #first;
+
+				#(#assertions)*
+
+				size
+			)
+		},
+
+		Data::Union(_) => unimplemented!(),
 	}
 }
@@ -24,6 +24,55 @@ impl Element {
 		}
 	}
 
+	/// Writes this element to a `trace` in a `TraceWrite` implementation,
+	/// recording the bytes it was written to.
+	pub fn trace_write_tokens(&self, tokens: &mut TokenStream2, definition_type: DefinitionType) {
+		let (name, value) = match self {
+			Self::Field(field) => {
+				if field.is_ignoring_trait("Writable") {
+					return;
+				}
+
+				let name = field.id.to_string();
+				let formatted = &field.formatted;
+
+				(
+					name,
+					quote_spanned!(field.span()=> Some(format!("{:?}", #formatted))),
+				)
+			},
+
+			Self::Let(r#let) => {
+				let name = r#let.ident.to_string();
+				let formatted = &r#let.formatted;
+
+				(
+					name,
+					quote_spanned!(r#let.span()=> Some(format!("{:?}", #formatted))),
+				)
+			},
+
+			Self::SingleUnused(unused) => {
+				(String::from("unused"), quote_spanned!(unused.span()=> None))
+			},
+			Self::ArrayUnused(unused) => {
+				(String::from("unused"), quote_spanned!(unused.span()=> None))
+			},
+		};
+		let name = name.trim_start_matches("r#");
+
+		tokens.append_tokens(quote_spanned!(self.span()=>
+			let trace_start = ::xrbk::Trace::len(trace);
+			let buf = ::xrbk::Trace::buf_mut(trace);
+		));
+
+		self.write_tokens(tokens, definition_type);
+
+		tokens.append_tokens(quote_spanned!(self.span()=>
+			::xrbk::Trace::record(trace, #name, trace_start, #value);
+		));
+	}
+
 	pub fn x11_size_tokens(&self, tokens: &mut TokenStream2, definition_type: DefinitionType) {
 		match self {
 			Self::Field(field) => {
@@ -38,6 +87,24 @@ impl Element {
 		}
 	}
 
+	/// Adds the constant size of this element to `size` in a
+	/// `ConstantX11Size` implementation.
+	pub fn constant_x11_size_tokens(
+		&self, tokens: &mut TokenStream2, definition_type: DefinitionType,
+	) {
+		match self {
+			Self::Field(field) => {
+				if !field.is_ignoring_trait("X11Size") {
+					field.constant_x11_size_tokens(tokens)
+				}
+			},
+			Self::Let(r#let) => r#let.constant_x11_size_tokens(tokens),
+
+			Self::SingleUnused(unused) => unused.add_x11_size_tokens(tokens),
+			Self::ArrayUnused(unused) => unused.constant_x11_size_tokens(tokens, definition_type),
+		}
+	}
+
 	pub fn read_tokens(&self, tokens: &mut TokenStream2, definition_type: DefinitionType) {
 		match self {
 			Self::Field(field) => {
@@ -114,9 +181,10 @@ impl Field {
 					);
 
 					quote_spanned!(self.span()=>
-						let #formatted = #r#type::read_with(
+						let #formatted = #r#type::read_with_mode(
 							buf,
 							&#function_call,
+							mode,
 						)?;
 					)
 				});
@@ -129,7 +197,7 @@ impl Field {
 					);
 
 					quote_spanned!(self.span()=>
-						let #formatted = #r#type::read_from(buf)?;
+						let #formatted = #r#type::read_from_with_mode(buf, mode)?;
 					)
 				});
 			},
@@ -146,6 +214,14 @@ impl Field {
 			)
 		});
 	}
+
+	pub fn constant_x11_size_tokens(&self, tokens: &mut TokenStream2) {
+		let r#type = &self.r#type;
+
+		tokens.append_tokens(quote_spanned!(self.span()=>
+			size += <#r#type as ::xrbk::ConstantX11Size>::X11_SIZE;
+		));
+	}
 }
 
 // }}} Let {{{
@@ -217,9 +293,10 @@ impl Let {
 					);
 
 					quote_spanned!(self.span()=>
-						let #formatted = #r#type::read_with(
+						let #formatted = #r#type::read_with_mode(
 							buf,
 							#function_call,
+							mode,
 						)?;
 					)
 				});
@@ -232,7 +309,7 @@ impl Let {
 					);
 
 					quote_spanned!(self.span()=>
-						let #formatted = #r#type::read_from(buf)?;
+						let #formatted = #r#type::read_from_with_mode(buf, mode)?;
 					)
 				});
 			},
@@ -249,6 +326,14 @@ impl Let {
 			)
 		});
 	}
+
+	pub fn constant_x11_size_tokens(&self, tokens: &mut TokenStream2) {
+		let r#type = &self.r#type;
+
+		tokens.append_tokens(quote_spanned!(self.span()=>
+			size += <#r#type as ::xrbk::ConstantX11Size>::X11_SIZE;
+		));
+	}
 }
 
 // }}} Single unused byte {{{
@@ -269,7 +354,7 @@ impl SingleUnused {
 	pub fn read_tokens(&self, tokens: &mut TokenStream2) {
 		tokens.append_tokens({
 			quote_spanned!(self.span()=>
-				<_ as ::xrbk::Buf>::advance(buf, 1);
+				::xrbk::read_padding(buf, 1, mode)?;
 			)
 		});
 	}
@@ -349,11 +434,56 @@ impl ArrayUnused {
 
 		tokens.append_tokens({
 			quote_spanned!(self.span()=>
-				<_ as ::xrbk::Buf>::advance(buf, #formatted);
+				::xrbk::read_padding(buf, #formatted, mode)?;
 			)
 		})
 	}
 
+	pub fn constant_x11_size_tokens(
+		&self, tokens: &mut TokenStream2, definition_type: DefinitionType,
+	) {
+		match &self.content {
+			UnusedContent::Infer { last_element, .. } => {
+				tokens.append_tokens(match definition_type.min_length() {
+					Some(min_length) if *last_element => {
+						quote_spanned!(self.span()=>
+							size += if size < #min_length {
+								#min_length - size
+							} else {
+								(4 - (size % 4)) % 4
+							};
+						)
+					},
+
+					_ => {
+						quote_spanned!(self.span()=>
+							size += (4 - (size % 4)) % 4;
+						)
+					},
+				});
+			},
+
+			// A source without arguments does not depend on the value of any
+			// other element, so its expression is constant.
+			UnusedContent::Source(source) if source.args.is_none() => {
+				let expr = &source.expr;
+
+				tokens.append_tokens(quote_spanned!(self.span()=>
+					size += #expr;
+				));
+			},
+
+			UnusedContent::Source(source) => {
+				tokens.append_tokens(quote_spanned!(source.expr.span()=>
+					::core::compile_error!(
+						"`ConstantX11Size` cannot be derived when the number of unused bytes depends \
+						 on other elements"
+					);
+				));
+			},
+		}
+	}
+
 	pub fn add_x11_size_tokens(&self, tokens: &mut TokenStream2) {
 		let formatted = &self.formatted;
 
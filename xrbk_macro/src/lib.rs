@@ -163,6 +163,14 @@ pub fn derive_readable(item: TokenStream) -> TokenStream {
 		impl #impl_generics ::xrbk::Readable for #ident #type_generics #where_clause {
 			fn read_from(
 				buf: &mut impl ::xrbk::Buf,
+			) -> Result<Self, ::xrbk::ReadError> {
+				Self::read_from_with_mode(buf, ::xrbk::ReadMode::Lenient)
+			}
+
+			#[allow(unused_variables)]
+			fn read_from_with_mode(
+				buf: &mut impl ::xrbk::Buf,
+				mode: ::xrbk::ReadMode,
 			) -> Result<Self, ::xrbk::ReadError> {
 				#reads
 			}
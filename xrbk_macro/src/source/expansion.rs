@@ -60,9 +60,15 @@ impl SourceArgs {
 	pub fn formatted_tokens(&self, tokens: &mut TokenStream2) {
 		if let Some((_, definition_type)) = &self.remaining_arg {
 			match definition_type {
-				DefinitionType::Request => quote!((((length - 1) as usize) * 4) - size,),
+				DefinitionType::Request => {
+					// `size` includes the 4 bytes of the header, so it is
+					// subtracted from the full size of the request.
+					quote!(((length as usize) * 4).saturating_sub(size),)
+				},
 				DefinitionType::Reply => {
-					quote!(((length as usize) * 4) + (32 - 8) - size,)
+					// `size` includes the 8 bytes of the header, so it is
+					// subtracted from the full size of the reply.
+					quote!((((length as usize) * 4) + 32).saturating_sub(size),)
 				},
 				_ => unreachable!(),
 			}